@@ -0,0 +1,186 @@
+#ifndef RUFFLE_H
+#define RUFFLE_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// A mouse button, for `ruffle_player_mouse_button`.
+typedef enum {
+  RUFFLE_MOUSE_BUTTON_LEFT = 0,
+  RUFFLE_MOUSE_BUTTON_RIGHT,
+  RUFFLE_MOUSE_BUTTON_MIDDLE,
+} RuffleMouseButton;
+
+// The result of a call that can fail.
+typedef enum {
+  RUFFLE_STATUS_OK = 0,
+  // A required pointer was null.
+  RUFFLE_STATUS_NULL_POINTER,
+  // An argument was out of range, or a string wasn't UTF-8.
+  RUFFLE_STATUS_INVALID_ARGUMENT,
+  // The movie couldn't be parsed.
+  RUFFLE_STATUS_INVALID_MOVIE,
+  // No movie has been loaded yet.
+  RUFFLE_STATUS_NO_MOVIE,
+  // The given buffer is too small for the result.
+  RUFFLE_STATUS_BUFFER_TOO_SMALL,
+  // The graphics device couldn't render a frame.
+  RUFFLE_STATUS_RENDER_FAILED,
+  // There is no fetch with the given ID waiting for an answer.
+  RUFFLE_STATUS_UNKNOWN_REQUEST,
+  // Ruffle panicked. The player may be left in an inconsistent state.
+  RUFFLE_STATUS_PANIC,
+} RuffleStatus;
+
+// A player together with the offscreen target it renders to and the executor running its
+// loads.
+typedef struct RufflePlayer RufflePlayer;
+
+// Asks the host to fetch `url`.
+//
+// `method` is `"GET"` or `"POST"`, and `body` is only set for requests that have one. None of
+// the pointers outlive the call. The host answers with `ruffle_player_finish_fetch` or
+// `ruffle_player_fail_fetch` and the same `request_id`, either from within the callback or
+// any time later.
+typedef void (*RuffleFetchCallback)(void *user_data,
+                                    uint64_t request_id,
+                                    const char *url,
+                                    const char *method,
+                                    const uint8_t *body,
+                                    size_t body_len);
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Creates a player whose frames are `width` by `height` pixels.
+//
+// Returns null if no graphics device could be found. The player must be freed with
+// `ruffle_player_destroy`.
+RufflePlayer *ruffle_player_new(uint32_t width, uint32_t height);
+
+// Frees a player created by `ruffle_player_new`. Passing null does nothing.
+//
+// # Safety
+//
+// `player` must not be used again afterwards, nor be freed while another call on it is running.
+void ruffle_player_destroy(RufflePlayer *player);
+
+// Starts playing the SWF in `data`, replacing any movie that was loaded before.
+//
+// `url` is where the movie is said to come from, which relative URLs are resolved against. It
+// may be null, in which case the movie has no URL.
+//
+// # Safety
+//
+// `data` must point to `len` readable bytes, and `url` must be null or a NUL-terminated string.
+// This must not be called from a fetch callback.
+RuffleStatus ruffle_player_load_movie(RufflePlayer *player,
+                                      const uint8_t *data,
+                                      size_t len,
+                                      const char *url);
+
+// Advances the player by `dt` milliseconds, running any frames and timers that became due,
+// and then any loads that can make progress.
+//
+// # Safety
+//
+// `player` must be null or a live player. This must not be called from a fetch callback.
+RuffleStatus ruffle_player_tick(const RufflePlayer *player, double dt);
+
+// Renders the current frame into `buffer`, as rows of RGBA pixels with straight alpha.
+//
+// The buffer must hold `width * height * 4` bytes, for the size the player was created with.
+//
+// # Safety
+//
+// `player` must be null or a live player, and `buffer` must point to `len` writable bytes.
+// This must not be called from a fetch callback.
+RuffleStatus ruffle_player_render(const RufflePlayer *player, uint8_t *buffer, size_t len);
+
+// Moves the mouse to (`x`, `y`), in pixels from the top left of the frame.
+//
+// # Safety
+//
+// `player` must be null or a live player. This must not be called from a fetch callback.
+RuffleStatus ruffle_player_mouse_move(const RufflePlayer *player, double x, double y);
+
+// Presses or releases a mouse button at (`x`, `y`).
+//
+// # Safety
+//
+// `player` must be null or a live player. This must not be called from a fetch callback.
+RuffleStatus ruffle_player_mouse_button(const RufflePlayer *player,
+                                        double x,
+                                        double y,
+                                        RuffleMouseButton button,
+                                        bool pressed);
+
+// Scrolls the mouse wheel by `lines`, which are positive when scrolling up.
+//
+// # Safety
+//
+// `player` must be null or a live player. This must not be called from a fetch callback.
+RuffleStatus ruffle_player_mouse_wheel(const RufflePlayer *player, double lines);
+
+// Presses or releases the key with the Flash key code `key_code`, such as 65 for A.
+//
+// Text is entered separately, with `ruffle_player_text_input`.
+//
+// # Safety
+//
+// `player` must be null or a live player. This must not be called from a fetch callback.
+RuffleStatus ruffle_player_key(const RufflePlayer *player, uint8_t key_code, bool pressed);
+
+// Types the character `codepoint` into the focused text field.
+//
+// # Safety
+//
+// `player` must be null or a live player. This must not be called from a fetch callback.
+RuffleStatus ruffle_player_text_input(const RufflePlayer *player, uint32_t codepoint);
+
+// Makes the player fetch files through `callback`, which is passed `user_data` on every call.
+//
+// Passing a null callback makes later fetches fail. Fetches already handed to a callback still
+// wait for their answer.
+//
+// # Safety
+//
+// `player` must be null or a live player, and `callback` must be safe to call with `user_data`
+// for as long as it's registered.
+RuffleStatus ruffle_player_set_fetch_callback(const RufflePlayer *player,
+                                              RuffleFetchCallback callback,
+                                              void *user_data);
+
+// Answers the fetch `request_id` with `data`, the body of a response with HTTP status `status`.
+//
+// The data is copied, and the movie sees it on a later `ruffle_player_tick`.
+//
+// # Safety
+//
+// `player` must be null or a live player, and `data` must point to `len` readable bytes (it may
+// be null if `len` is 0).
+RuffleStatus ruffle_player_finish_fetch(const RufflePlayer *player,
+                                        uint64_t request_id,
+                                        uint16_t status,
+                                        const uint8_t *data,
+                                        size_t len);
+
+// Fails the fetch `request_id`, for the reason given in `message`, which may be null.
+//
+// # Safety
+//
+// `player` must be null or a live player, and `message` must be null or a NUL-terminated
+// string.
+RuffleStatus ruffle_player_fail_fetch(const RufflePlayer *player,
+                                      uint64_t request_id,
+                                      const char *message);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
+
+#endif /* RUFFLE_H */
@@ -0,0 +1,570 @@
+//! C bindings for embedding Ruffle in applications that aren't written in Rust.
+//!
+//! A host creates a player with `ruffle_player_new`, gives it a movie with
+//! `ruffle_player_load_movie`, and then calls `ruffle_player_tick` and `ruffle_player_render`
+//! on its own schedule, passing input along as it happens. Frames are rendered offscreen and
+//! copied into a buffer of the host's, so no window is needed. Movies fetch other files
+//! through the host's `RuffleFetchCallback`, and can't fetch anything until it registers one.
+//!
+//! A player must only be used from the thread that created it. `include/ruffle.h` declares
+//! everything here for C and C++, and is regenerated with cbindgen (see `cbindgen.toml`).
+
+mod navigator;
+
+use navigator::{CallbackNavigatorBackend, Fetches};
+use ruffle_core::backend::navigator::{create_specific_fetch_error, NullExecutor, SuccessResponse};
+use ruffle_core::embed::events::{KeyCode, MouseButton, MouseWheelDelta, PlayerEvent};
+use ruffle_core::embed::{Player, PlayerBuilder, SwfMovie};
+use ruffle_render_wgpu::backend::{request_adapter_and_device, WgpuRenderBackend};
+use ruffle_render_wgpu::descriptors::Descriptors;
+use ruffle_render_wgpu::target::TextureTarget;
+use ruffle_render_wgpu::wgpu;
+use std::cell::RefCell;
+use std::ffi::{c_char, c_void, CStr};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+use std::{ptr, slice};
+
+pub use navigator::RuffleFetchCallback;
+
+/// The result of a call that can fail.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuffleStatus {
+    Ok = 0,
+
+    /// A required pointer was null.
+    NullPointer,
+
+    /// An argument was out of range, or a string wasn't UTF-8.
+    InvalidArgument,
+
+    /// The movie couldn't be parsed.
+    InvalidMovie,
+
+    /// No movie has been loaded yet.
+    NoMovie,
+
+    /// The given buffer is too small for the result.
+    BufferTooSmall,
+
+    /// The graphics device couldn't render a frame.
+    RenderFailed,
+
+    /// There is no fetch with the given ID waiting for an answer.
+    UnknownRequest,
+
+    /// Ruffle panicked. The player may be left in an inconsistent state.
+    Panic,
+}
+
+/// A mouse button, for `ruffle_player_mouse_button`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuffleMouseButton {
+    Left = 0,
+    Right,
+    Middle,
+}
+
+impl From<RuffleMouseButton> for MouseButton {
+    fn from(button: RuffleMouseButton) -> Self {
+        match button {
+            RuffleMouseButton::Left => Self::Left,
+            RuffleMouseButton::Right => Self::Right,
+            RuffleMouseButton::Middle => Self::Middle,
+        }
+    }
+}
+
+/// A player together with the offscreen target it renders to and the executor running its
+/// loads.
+pub struct RufflePlayer {
+    descriptors: Arc<Descriptors>,
+    width: u32,
+    height: u32,
+    player: Option<Arc<Mutex<Player>>>,
+    executor: RefCell<NullExecutor>,
+    fetches: Rc<RefCell<Fetches>>,
+}
+
+impl RufflePlayer {
+    fn new(width: u32, height: u32) -> Option<Self> {
+        let backends = wgpu::Backends::PRIMARY | wgpu::Backends::DX11;
+        let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
+            backends,
+            dx12_shader_compiler: wgpu::Dx12Compiler::default(),
+        });
+        let (adapter, device, queue) = futures::executor::block_on(request_adapter_and_device(
+            backends,
+            &instance,
+            None,
+            wgpu::PowerPreference::HighPerformance,
+            None,
+        ))
+        .ok()?;
+        Some(Self {
+            descriptors: Arc::new(Descriptors::new(instance, adapter, device, queue)),
+            width,
+            height,
+            player: None,
+            executor: RefCell::new(NullExecutor::new()),
+            fetches: Default::default(),
+        })
+    }
+
+    /// Replaces the player with a new one playing `movie`.
+    fn load_movie(&mut self, movie: SwfMovie) -> RuffleStatus {
+        let Ok(target) = TextureTarget::new(&self.descriptors.device, (self.width, self.height))
+        else {
+            return RuffleStatus::RenderFailed;
+        };
+        let Ok(renderer) = WgpuRenderBackend::new(self.descriptors.clone(), target) else {
+            return RuffleStatus::RenderFailed;
+        };
+
+        // Anything the old player was still loading is abandoned along with its executor.
+        let executor = NullExecutor::new();
+        let navigator = CallbackNavigatorBackend::new(&executor, self.fetches.clone());
+        self.player = Some(
+            PlayerBuilder::new()
+                .with_renderer(renderer)
+                .with_navigator(navigator)
+                .with_movie(movie)
+                .with_viewport_dimensions(self.width, self.height, 1.0)
+                .with_autoplay(true)
+                .build(),
+        );
+        *self.executor.borrow_mut() = executor;
+        RuffleStatus::Ok
+    }
+
+    fn player(&self) -> Result<&Mutex<Player>, RuffleStatus> {
+        self.player.as_deref().ok_or(RuffleStatus::NoMovie)
+    }
+
+    fn handle_event(&self, event: PlayerEvent) -> RuffleStatus {
+        match self.player() {
+            Ok(player) => {
+                player.lock().unwrap().handle_event(event);
+                RuffleStatus::Ok
+            }
+            Err(status) => status,
+        }
+    }
+}
+
+/// Runs `f` with the player behind `player`, turning panics into `RuffleStatus::Panic`.
+///
+/// Only a shared reference is handed out, as the host may call back in (to answer a fetch)
+/// while another call on the same player is still running.
+fn with_player(
+    player: *const RufflePlayer,
+    f: impl FnOnce(&RufflePlayer) -> RuffleStatus,
+) -> RuffleStatus {
+    let Some(player) = (unsafe { player.as_ref() }) else {
+        return RuffleStatus::NullPointer;
+    };
+    catch_unwind(AssertUnwindSafe(|| f(player))).unwrap_or(RuffleStatus::Panic)
+}
+
+/// Creates a player whose frames are `width` by `height` pixels.
+///
+/// Returns null if no graphics device could be found. The player must be freed with
+/// `ruffle_player_destroy`.
+#[no_mangle]
+pub extern "C" fn ruffle_player_new(width: u32, height: u32) -> *mut RufflePlayer {
+    if width == 0 || height == 0 {
+        return ptr::null_mut();
+    }
+    match catch_unwind(|| RufflePlayer::new(width, height)) {
+        Ok(Some(player)) => Box::into_raw(Box::new(player)),
+        _ => ptr::null_mut(),
+    }
+}
+
+/// Frees a player created by `ruffle_player_new`. Passing null does nothing.
+///
+/// # Safety
+///
+/// `player` must not be used again afterwards, nor be freed while another call on it is running.
+#[no_mangle]
+pub unsafe extern "C" fn ruffle_player_destroy(player: *mut RufflePlayer) {
+    if !player.is_null() {
+        // There's no status to report a panic with, but it mustn't unwind into the host.
+        let _ = catch_unwind(AssertUnwindSafe(|| drop(Box::from_raw(player))));
+    }
+}
+
+/// Starts playing the SWF in `data`, replacing any movie that was loaded before.
+///
+/// `url` is where the movie is said to come from, which relative URLs are resolved against. It
+/// may be null, in which case the movie has no URL.
+///
+/// # Safety
+///
+/// `data` must point to `len` readable bytes, and `url` must be null or a NUL-terminated string.
+/// This must not be called from a fetch callback.
+#[no_mangle]
+pub unsafe extern "C" fn ruffle_player_load_movie(
+    player: *mut RufflePlayer,
+    data: *const u8,
+    len: usize,
+    url: *const c_char,
+) -> RuffleStatus {
+    let Some(player) = player.as_mut() else {
+        return RuffleStatus::NullPointer;
+    };
+    if data.is_null() {
+        return RuffleStatus::NullPointer;
+    }
+    let url = if url.is_null() {
+        String::new()
+    } else {
+        match CStr::from_ptr(url).to_str() {
+            Ok(url) => url.to_string(),
+            Err(_) => return RuffleStatus::InvalidArgument,
+        }
+    };
+    let data = slice::from_raw_parts(data, len);
+
+    catch_unwind(AssertUnwindSafe(|| {
+        match SwfMovie::from_data(data, url, None) {
+            Ok(movie) => player.load_movie(movie),
+            Err(_) => RuffleStatus::InvalidMovie,
+        }
+    }))
+    .unwrap_or(RuffleStatus::Panic)
+}
+
+/// Advances the player by `dt` milliseconds, running any frames and timers that became due,
+/// and then any loads that can make progress.
+///
+/// # Safety
+///
+/// `player` must be null or a live player. This must not be called from a fetch callback.
+#[no_mangle]
+pub unsafe extern "C" fn ruffle_player_tick(player: *const RufflePlayer, dt: f64) -> RuffleStatus {
+    with_player(player, |player| {
+        match player.player() {
+            Ok(inner) => inner.lock().unwrap().tick(dt),
+            Err(status) => return status,
+        }
+        // Loads lock the player themselves, so they can only run once it's unlocked.
+        player.executor.borrow_mut().run();
+        RuffleStatus::Ok
+    })
+}
+
+/// Renders the current frame into `buffer`, as rows of RGBA pixels with straight alpha.
+///
+/// The buffer must hold `width * height * 4` bytes, for the size the player was created with.
+///
+/// # Safety
+///
+/// `player` must be null or a live player, and `buffer` must point to `len` writable bytes.
+/// This must not be called from a fetch callback.
+#[no_mangle]
+pub unsafe extern "C" fn ruffle_player_render(
+    player: *const RufflePlayer,
+    buffer: *mut u8,
+    len: usize,
+) -> RuffleStatus {
+    if buffer.is_null() {
+        return RuffleStatus::NullPointer;
+    }
+    with_player(player, |player| {
+        let size = player.width as usize * player.height as usize * 4;
+        if len < size {
+            return RuffleStatus::BufferTooSmall;
+        }
+        let inner = match player.player() {
+            Ok(inner) => inner,
+            Err(status) => return status,
+        };
+
+        let mut inner = inner.lock().unwrap();
+        inner.render();
+        let Some(image) = inner
+            .renderer_mut()
+            .downcast_mut::<WgpuRenderBackend<TextureTarget>>()
+            .and_then(|renderer| renderer.capture_frame())
+        else {
+            return RuffleStatus::RenderFailed;
+        };
+        let pixels = image.as_raw();
+        let buffer = slice::from_raw_parts_mut(buffer, size);
+        let copied = pixels.len().min(size);
+        buffer[..copied].copy_from_slice(&pixels[..copied]);
+        RuffleStatus::Ok
+    })
+}
+
+/// Moves the mouse to (`x`, `y`), in pixels from the top left of the frame.
+///
+/// # Safety
+///
+/// `player` must be null or a live player. This must not be called from a fetch callback.
+#[no_mangle]
+pub unsafe extern "C" fn ruffle_player_mouse_move(
+    player: *const RufflePlayer,
+    x: f64,
+    y: f64,
+) -> RuffleStatus {
+    with_player(player, |player| {
+        player.handle_event(PlayerEvent::MouseMove { x, y })
+    })
+}
+
+/// Presses or releases a mouse button at (`x`, `y`).
+///
+/// # Safety
+///
+/// `player` must be null or a live player. This must not be called from a fetch callback.
+#[no_mangle]
+pub unsafe extern "C" fn ruffle_player_mouse_button(
+    player: *const RufflePlayer,
+    x: f64,
+    y: f64,
+    button: RuffleMouseButton,
+    pressed: bool,
+) -> RuffleStatus {
+    let button = button.into();
+    with_player(player, |player| {
+        player.handle_event(if pressed {
+            PlayerEvent::MouseDown { x, y, button }
+        } else {
+            PlayerEvent::MouseUp { x, y, button }
+        })
+    })
+}
+
+/// Scrolls the mouse wheel by `lines`, which are positive when scrolling up.
+///
+/// # Safety
+///
+/// `player` must be null or a live player. This must not be called from a fetch callback.
+#[no_mangle]
+pub unsafe extern "C" fn ruffle_player_mouse_wheel(
+    player: *const RufflePlayer,
+    lines: f64,
+) -> RuffleStatus {
+    with_player(player, |player| {
+        player.handle_event(PlayerEvent::MouseWheel {
+            delta: MouseWheelDelta::Lines(lines),
+        })
+    })
+}
+
+/// Presses or releases the key with the Flash key code `key_code`, such as 65 for A.
+///
+/// Text is entered separately, with `ruffle_player_text_input`.
+///
+/// # Safety
+///
+/// `player` must be null or a live player. This must not be called from a fetch callback.
+#[no_mangle]
+pub unsafe extern "C" fn ruffle_player_key(
+    player: *const RufflePlayer,
+    key_code: u8,
+    pressed: bool,
+) -> RuffleStatus {
+    let Some(key_code) = KeyCode::from_u8(key_code) else {
+        return RuffleStatus::InvalidArgument;
+    };
+    with_player(player, |player| {
+        player.handle_event(if pressed {
+            PlayerEvent::KeyDown {
+                key_code,
+                key_char: None,
+            }
+        } else {
+            PlayerEvent::KeyUp {
+                key_code,
+                key_char: None,
+            }
+        })
+    })
+}
+
+/// Types the character `codepoint` into the focused text field.
+///
+/// # Safety
+///
+/// `player` must be null or a live player. This must not be called from a fetch callback.
+#[no_mangle]
+pub unsafe extern "C" fn ruffle_player_text_input(
+    player: *const RufflePlayer,
+    codepoint: u32,
+) -> RuffleStatus {
+    let Some(codepoint) = char::from_u32(codepoint) else {
+        return RuffleStatus::InvalidArgument;
+    };
+    with_player(player, |player| {
+        player.handle_event(PlayerEvent::TextInput { codepoint })
+    })
+}
+
+/// Makes the player fetch files through `callback`, which is passed `user_data` on every call.
+///
+/// Passing a null callback makes later fetches fail. Fetches already handed to a callback still
+/// wait for their answer.
+///
+/// # Safety
+///
+/// `player` must be null or a live player, and `callback` must be safe to call with `user_data`
+/// for as long as it's registered.
+#[no_mangle]
+pub unsafe extern "C" fn ruffle_player_set_fetch_callback(
+    player: *const RufflePlayer,
+    callback: RuffleFetchCallback,
+    user_data: *mut c_void,
+) -> RuffleStatus {
+    with_player(player, |player| {
+        player
+            .fetches
+            .borrow_mut()
+            .set_callback(callback, user_data);
+        RuffleStatus::Ok
+    })
+}
+
+/// Answers the fetch `request_id` with `data`, the body of a response with HTTP status `status`.
+///
+/// The data is copied, and the movie sees it on a later `ruffle_player_tick`.
+///
+/// # Safety
+///
+/// `player` must be null or a live player, and `data` must point to `len` readable bytes (it may
+/// be null if `len` is 0).
+#[no_mangle]
+pub unsafe extern "C" fn ruffle_player_finish_fetch(
+    player: *const RufflePlayer,
+    request_id: u64,
+    status: u16,
+    data: *const u8,
+    len: usize,
+) -> RuffleStatus {
+    if data.is_null() && len > 0 {
+        return RuffleStatus::NullPointer;
+    }
+    let body = if len > 0 {
+        slice::from_raw_parts(data, len).to_vec()
+    } else {
+        vec![]
+    };
+    with_player(player, |player| {
+        let finished = player.fetches.borrow_mut().finish(request_id, |url| {
+            Ok(SuccessResponse {
+                url,
+                body,
+                status,
+                redirected: false,
+                redirect_url: None,
+                headers: vec![],
+            })
+        });
+        if finished {
+            RuffleStatus::Ok
+        } else {
+            RuffleStatus::UnknownRequest
+        }
+    })
+}
+
+/// Fails the fetch `request_id`, for the reason given in `message`, which may be null.
+///
+/// # Safety
+///
+/// `player` must be null or a live player, and `message` must be null or a NUL-terminated
+/// string.
+#[no_mangle]
+pub unsafe extern "C" fn ruffle_player_fail_fetch(
+    player: *const RufflePlayer,
+    request_id: u64,
+    message: *const c_char,
+) -> RuffleStatus {
+    let message = if message.is_null() {
+        String::new()
+    } else {
+        CStr::from_ptr(message).to_string_lossy().into_owned()
+    };
+    with_player(player, |player| {
+        let finished = player.fetches.borrow_mut().finish(request_id, |url| {
+            create_specific_fetch_error("Host couldn't fetch", &url, message)
+        });
+        if finished {
+            RuffleStatus::Ok
+        } else {
+            RuffleStatus::UnknownRequest
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn null_players_are_refused() {
+        let mut buffer = [0; 4];
+        unsafe {
+            ruffle_player_destroy(ptr::null_mut());
+            assert_eq!(
+                ruffle_player_tick(ptr::null(), 16.0),
+                RuffleStatus::NullPointer
+            );
+            assert_eq!(
+                ruffle_player_render(ptr::null(), buffer.as_mut_ptr(), buffer.len()),
+                RuffleStatus::NullPointer
+            );
+            assert_eq!(
+                ruffle_player_load_movie(ptr::null_mut(), buffer.as_ptr(), 0, ptr::null()),
+                RuffleStatus::NullPointer
+            );
+            assert_eq!(
+                ruffle_player_finish_fetch(ptr::null(), 0, 200, ptr::null(), 0),
+                RuffleStatus::NullPointer
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_arguments_are_refused() {
+        assert!(ruffle_player_new(0, 100).is_null());
+        unsafe {
+            // Refused before the player is even looked at.
+            assert_eq!(
+                ruffle_player_key(ptr::null(), 3, true),
+                RuffleStatus::InvalidArgument
+            );
+            assert_eq!(
+                ruffle_player_text_input(ptr::null(), 0xD800),
+                RuffleStatus::InvalidArgument
+            );
+            assert_eq!(
+                ruffle_player_finish_fetch(ptr::null(), 0, 200, ptr::null(), 1),
+                RuffleStatus::NullPointer
+            );
+        }
+    }
+
+    #[test]
+    fn header_is_up_to_date() {
+        let dir = env!("CARGO_MANIFEST_DIR");
+        let config = cbindgen::Config::from_file(format!("{dir}/cbindgen.toml")).unwrap();
+        let mut generated = vec![];
+        cbindgen::Builder::new()
+            .with_config(config)
+            .with_src(format!("{dir}/src/lib.rs"))
+            .generate()
+            .unwrap()
+            .write(&mut generated);
+        let header = std::fs::read(format!("{dir}/include/ruffle.h")).unwrap();
+        assert!(
+            generated == header,
+            "include/ruffle.h is out of date, regenerate it as described in cbindgen.toml"
+        );
+    }
+}
@@ -0,0 +1,296 @@
+//! Fetching through a callback of the host.
+
+use async_channel::Receiver;
+use futures::channel::oneshot;
+use ruffle_core::backend::navigator::{
+    async_return, create_fetch_error, create_specific_fetch_error, fetch_data_url,
+    resolve_url_with_relative_base_path, ErrorResponse, NavigationMethod, NavigatorBackend,
+    NullExecutor, NullSpawner, OwnedFuture, Request, SuccessResponse,
+};
+use ruffle_core::indexmap::IndexMap;
+use ruffle_core::loader::Error;
+use ruffle_core::net_group::{NetGroupAction, NetGroupHandle};
+use ruffle_core::socket::{ConnectionState, SocketAction, SocketHandle};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::ffi::{c_char, c_void, CString};
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::ptr;
+use std::rc::Rc;
+use std::sync::mpsc::Sender;
+use std::time::Duration;
+use url::{ParseError, Url};
+
+/// Asks the host to fetch `url`.
+///
+/// `method` is `"GET"` or `"POST"`, and `body` is only set for requests that have one. None of
+/// the pointers outlive the call. The host answers with `ruffle_player_finish_fetch` or
+/// `ruffle_player_fail_fetch` and the same `request_id`, either from within the callback or
+/// any time later.
+pub type RuffleFetchCallback = Option<
+    unsafe extern "C" fn(
+        user_data: *mut c_void,
+        request_id: u64,
+        url: *const c_char,
+        method: *const c_char,
+        body: *const u8,
+        body_len: usize,
+    ),
+>;
+
+type FetchResult = Result<SuccessResponse, ErrorResponse>;
+
+/// The fetch callback of a player, and the fetches it hasn't answered yet.
+pub struct Fetches {
+    callback: RuffleFetchCallback,
+    user_data: *mut c_void,
+    next_id: u64,
+    pending: HashMap<u64, (String, oneshot::Sender<FetchResult>)>,
+}
+
+impl Default for Fetches {
+    fn default() -> Self {
+        Self {
+            callback: None,
+            user_data: ptr::null_mut(),
+            next_id: 0,
+            pending: HashMap::new(),
+        }
+    }
+}
+
+impl Fetches {
+    pub fn set_callback(&mut self, callback: RuffleFetchCallback, user_data: *mut c_void) {
+        self.callback = callback;
+        self.user_data = user_data;
+    }
+
+    /// Answers the fetch `id` with the result of `respond`, which is given its URL.
+    ///
+    /// Returns false if there is no such fetch, or it was already answered.
+    pub fn finish(&mut self, id: u64, respond: impl FnOnce(String) -> FetchResult) -> bool {
+        match self.pending.remove(&id) {
+            Some((url, sender)) => {
+                // The load may have been abandoned in the meantime, which is fine.
+                let _ = sender.send(respond(url));
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// A `NavigatorBackend` that fetches through the host's callback.
+///
+/// Fetches fail while the host hasn't registered a callback, as the movie mustn't be able to
+/// read local files behind its back. Only `data:` URLs work without one. Navigation, sockets and
+/// net groups aren't supported.
+pub struct CallbackNavigatorBackend {
+    spawner: NullSpawner,
+    fetches: Rc<RefCell<Fetches>>,
+}
+
+impl CallbackNavigatorBackend {
+    pub fn new(executor: &NullExecutor, fetches: Rc<RefCell<Fetches>>) -> Self {
+        Self {
+            spawner: executor.spawner(),
+            fetches,
+        }
+    }
+}
+
+impl NavigatorBackend for CallbackNavigatorBackend {
+    fn navigate_to_url(
+        &self,
+        _url: &str,
+        _target: &str,
+        _vars_method: Option<(NavigationMethod, IndexMap<String, String>)>,
+    ) {
+    }
+
+    fn fetch(&self, request: Request) -> OwnedFuture<SuccessResponse, ErrorResponse> {
+        let url = match self.resolve_url(request.url()) {
+            // The data is right there, so the host needn't be bothered with it.
+            Ok(url) if url.scheme() == "data" => return async_return(fetch_data_url(&url)),
+            Ok(url) => url.to_string(),
+            Err(e) => return async_return(create_fetch_error(request.url(), e)),
+        };
+        let (callback, user_data) = {
+            let fetches = self.fetches.borrow();
+            (fetches.callback, fetches.user_data)
+        };
+        let Some(callback) = callback else {
+            return async_return(create_specific_fetch_error(
+                "No fetch callback is registered to fetch",
+                &url,
+                "",
+            ));
+        };
+        let Ok(c_url) = CString::new(url.clone()) else {
+            return async_return(create_fetch_error(&url, "URL contains a NUL character"));
+        };
+        let method = CString::new(request.method().to_string()).expect("method is ASCII");
+        let (body, body_len) = match request.body() {
+            Some((body, _)) => (body.as_ptr(), body.len()),
+            None => (ptr::null(), 0),
+        };
+
+        let (sender, receiver) = oneshot::channel();
+        let id = {
+            let mut fetches = self.fetches.borrow_mut();
+            let id = fetches.next_id;
+            fetches.next_id += 1;
+            fetches.pending.insert(id, (url.clone(), sender));
+            id
+        };
+
+        // The host may answer from within the callback, so `fetches` mustn't be borrowed here.
+        unsafe {
+            callback(
+                user_data,
+                id,
+                c_url.as_ptr(),
+                method.as_ptr(),
+                body,
+                body_len,
+            )
+        };
+
+        Box::pin(async move {
+            receiver.await.unwrap_or_else(|_| {
+                create_specific_fetch_error("Fetch was never answered", &url, "")
+            })
+        })
+    }
+
+    fn resolve_url(&self, url: &str) -> Result<Url, ParseError> {
+        resolve_url_with_relative_base_path(self, PathBuf::new(), url)
+    }
+
+    fn spawn_future(&mut self, future: OwnedFuture<(), Error>) {
+        self.spawner.spawn_local(future);
+    }
+
+    fn pre_process_url(&self, url: Url) -> Url {
+        url
+    }
+
+    fn connect_socket(
+        &mut self,
+        _host: String,
+        _port: u16,
+        _require_policy_file: bool,
+        _timeout: Duration,
+        handle: SocketHandle,
+        _receiver: Receiver<Vec<u8>>,
+        sender: Sender<SocketAction>,
+    ) {
+        sender
+            .send(SocketAction::Connect(handle, ConnectionState::Failed))
+            .expect("working channel send");
+    }
+
+    fn join_net_group(
+        &mut self,
+        _address: SocketAddr,
+        handle: NetGroupHandle,
+        _receiver: Receiver<Vec<u8>>,
+        sender: Sender<NetGroupAction>,
+    ) {
+        sender
+            .send(NetGroupAction::Connect(handle, false))
+            .expect("working channel send");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::CStr;
+
+    /// Records the URL and method of each fetch in the `Vec<(u64, String, String)>` behind
+    /// `user_data`.
+    unsafe extern "C" fn record_fetch(
+        user_data: *mut c_void,
+        request_id: u64,
+        url: *const c_char,
+        method: *const c_char,
+        _body: *const u8,
+        _body_len: usize,
+    ) {
+        let fetches = &mut *(user_data as *mut Vec<(u64, String, String)>);
+        let url = CStr::from_ptr(url).to_string_lossy().into_owned();
+        let method = CStr::from_ptr(method).to_string_lossy().into_owned();
+        fetches.push((request_id, url, method));
+    }
+
+    fn navigator() -> (CallbackNavigatorBackend, Rc<RefCell<Fetches>>) {
+        let fetches = Rc::new(RefCell::new(Fetches::default()));
+        let navigator = CallbackNavigatorBackend::new(&NullExecutor::new(), fetches.clone());
+        (navigator, fetches)
+    }
+
+    #[test]
+    fn fetches_fail_without_a_callback() {
+        let (navigator, _) = navigator();
+        let result = futures::executor::block_on(
+            navigator.fetch(Request::get("file:///etc/hosts".to_string())),
+        );
+        assert!(result.is_err());
+
+        let result =
+            futures::executor::block_on(navigator.fetch(Request::get("data:,hi".to_string())));
+        assert_eq!(result.unwrap().body, b"hi");
+    }
+
+    #[test]
+    fn fetches_go_through_the_callback() {
+        let (navigator, fetches) = navigator();
+        let mut seen: Vec<(u64, String, String)> = vec![];
+        fetches
+            .borrow_mut()
+            .set_callback(Some(record_fetch), &mut seen as *mut _ as *mut c_void);
+
+        let first = navigator.fetch(Request::get("https://example.com/a.swf".to_string()));
+        let second = navigator.fetch(Request::post(
+            "https://example.com/b".to_string(),
+            Some((b"x=1".to_vec(), "text/plain".to_string())),
+        ));
+        assert_eq!(
+            seen,
+            [
+                (
+                    0,
+                    "https://example.com/a.swf".to_string(),
+                    "GET".to_string()
+                ),
+                (1, "https://example.com/b".to_string(), "POST".to_string()),
+            ]
+        );
+
+        let answered = fetches.borrow_mut().finish(0, |url| {
+            Ok(SuccessResponse {
+                url,
+                body: b"movie".to_vec(),
+                status: 200,
+                redirected: false,
+                redirect_url: None,
+                headers: vec![],
+            })
+        });
+        assert!(answered);
+        // Each fetch is only answered once.
+        assert!(!fetches
+            .borrow_mut()
+            .finish(0, |url| create_fetch_error(&url, "")));
+
+        let response = futures::executor::block_on(first).unwrap();
+        assert_eq!(response.url, "https://example.com/a.swf");
+        assert_eq!(response.body, b"movie");
+
+        // A fetch the host drops is failed rather than left hanging.
+        *fetches.borrow_mut() = Fetches::default();
+        assert!(futures::executor::block_on(second).is_err());
+    }
+}
@@ -31,6 +31,14 @@ const METADATA_NATIVE_INSTANCE_INIT: &str = "NativeInstanceInit";
 /// method (used as a metadata key with `Ruffle` metadata)
 const METADATA_CALL_HANDLER: &str = "CallHandler";
 
+/// Marks a definition as only introduced by a later Flash Player release, such as
+/// `[API("682")]`. The value is the avmplus API number of that release.
+const API_METADATA_NAME: &str = "API";
+// The API number of the first versioned release, `FP_9_0`.
+const MIN_API_NUMBER: u32 = 660;
+// The character appended to a namespace URI to mark it with `MIN_API_NUMBER`.
+const MIN_API_MARK: u32 = 0xE000;
+
 /// If successful, returns a list of paths that were used. If this is run
 /// from a build script, these paths should be printed with
 /// cargo:rerun-if-changed
@@ -230,6 +238,64 @@ fn rust_method_name_and_path(
     quote! { Some((#flash_method_path, #path_tokens)) }
 }
 
+/// Handles `[API("NNN")]` metadata on definitions in our `playerglobal`.
+///
+/// Like avmplus, we move such a definition into a namespace whose URI ends
+/// with a private use character marking the release that introduced it.
+/// When Ruffle loads `playerglobal`, it strips the marker back off and only
+/// lets movies made for that release (or later) see the definition.
+fn apply_api_versions(abc: &mut AbcFile) {
+    let mut versioned = vec![];
+    for (script_idx, script) in abc.scripts.iter().enumerate() {
+        for (trait_idx, trait_) in script.traits.iter().enumerate() {
+            for metadata_idx in &trait_.metadata {
+                let metadata = &abc.metadata[metadata_idx.0 as usize];
+                let name = &abc.constant_pool.strings[metadata.name.0 as usize - 1];
+                if name != API_METADATA_NAME {
+                    continue;
+                }
+
+                let [item] = &metadata.items[..] else {
+                    panic!("Expected a single API number in {metadata:?}");
+                };
+                let value = &abc.constant_pool.strings[item.value.0 as usize - 1];
+                let api_number = value
+                    .parse::<u32>()
+                    .ok()
+                    .filter(|number| *number >= MIN_API_NUMBER)
+                    .unwrap_or_else(|| panic!("Invalid API number {value:?}"));
+                versioned.push((script_idx, trait_idx, api_number));
+            }
+        }
+    }
+
+    for (script_idx, trait_idx, api_number) in versioned {
+        let old_name = abc.scripts[script_idx].traits[trait_idx].name;
+        let multiname = &abc.constant_pool.multinames[old_name.0 as usize - 1];
+        let Multiname::QName { name, .. } = *multiname else {
+            panic!("Unexpected Multiname {multiname:?}");
+        };
+        let marker = char::from_u32(MIN_API_MARK + (api_number - MIN_API_NUMBER)).unwrap();
+        let uri = resolve_multiname_ns(abc, multiname).to_string() + &marker.to_string();
+
+        let pool = &mut abc.constant_pool;
+        pool.strings.push(uri);
+        pool.namespaces
+            .push(Namespace::Package(Index::new(pool.strings.len() as u32)));
+        pool.multinames.push(Multiname::QName {
+            namespace: Index::new(pool.namespaces.len() as u32),
+            name,
+        });
+        let new_name = Index::new(pool.multinames.len() as u32);
+
+        let trait_ = &mut abc.scripts[script_idx].traits[trait_idx];
+        trait_.name = new_name;
+        if let TraitKind::Class { class, .. } = trait_.kind {
+            abc.instances[class.0 as usize].name = new_name;
+        }
+    }
+}
+
 fn strip_metadata(abc: &mut AbcFile) {
     abc.metadata.clear();
     for instance in &mut abc.instances {
@@ -339,6 +405,8 @@ fn write_native_table(data: &[u8], out_dir: &Path) -> Result<Vec<u8>, Box<dyn st
             let name = &abc.constant_pool.strings[metadata.name.0 as usize - 1];
             match name.as_str() {
                 RUFFLE_METADATA_NAME => {}
+                // Handled by `apply_api_versions`
+                API_METADATA_NAME => continue,
                 _ => panic!("Unexpected class metadata {name:?}"),
             }
 
@@ -464,6 +532,8 @@ fn write_native_table(data: &[u8], out_dir: &Path) -> Result<Vec<u8>, Box<dyn st
     let mut native_table_file = File::create(out_dir.join("native_table.rs"))?;
     native_table_file.write_all(make_native_table.as_bytes())?;
 
+    apply_api_versions(&mut abc);
+
     // Ruffle doesn't need metadata items at runtime, so strip
     // them out to save space
     strip_metadata(&mut abc);
@@ -17,7 +17,6 @@ use crate::vminterface::Instantiator;
 use crate::{avm_error, avm_warn};
 use gc_arena::{Gc, GcCell, Mutation};
 use indexmap::IndexMap;
-use instant::Instant;
 use rand::Rng;
 use smallvec::SmallVec;
 use std::borrow::Cow;
@@ -1176,10 +1175,11 @@ impl<'a, 'gc> Activation<'a, 'gc> {
             *self.context.time_offset += 1;
         }
 
-        let time = Instant::now()
-            .duration_since(self.context.start_time)
-            .as_millis() as u32;
-        let result = time.wrapping_add(*self.context.time_offset);
+        let result = self
+            .context
+            .clock
+            .get_timer()
+            .wrapping_add(*self.context.time_offset);
         self.context.avm1.push(result.into());
         Ok(FrameControl::Continue)
     }
@@ -1232,7 +1232,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
 
         if let Some(fscommand) = fscommand::parse(&url) {
             fscommand::handle(fscommand, &target, self)?;
-        } else {
+        } else if !self.run_javascript_url(&url) {
             self.context.navigator.navigate_to_url(
                 &url.to_utf8_lossy(),
                 &target.to_utf8_lossy(),
@@ -1243,6 +1243,21 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         Ok(FrameControl::Continue)
     }
 
+    /// Passes a `javascript:` URL to the external interface rather than navigating to it, so the
+    /// host can run it. Returns whether it was handled.
+    fn run_javascript_url(&mut self, url: &WStr) -> bool {
+        const PREFIX: &[u8] = b"javascript:";
+        if url.len() < PREFIX.len() || !url[..PREFIX.len()].eq_ignore_case(WStr::from_units(PREFIX))
+        {
+            return false;
+        }
+        // Like a browser would, decode the script before running it, as it's often escaped to
+        // be a valid URL.
+        let script = url[PREFIX.len()..].to_utf8_lossy();
+        let script = percent_encoding::percent_decode_str(&script).decode_utf8_lossy();
+        self.context.external_interface.run_javascript_url(&script)
+    }
+
     fn action_get_url_2(&mut self, action: GetUrl2) -> Result<FrameControl<'gc>, Error<'gc>> {
         // TODO: Support `LoadVariablesFlag`, `LoadTargetFlag`
         // TODO: What happens if there's only one string?
@@ -1383,6 +1398,9 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         }
 
         // `getURL` call.
+        if self.run_javascript_url(&url) {
+            return Ok(FrameControl::Continue);
+        }
         let vars = match NavigationMethod::from_send_vars_method(action.send_vars_method()) {
             Some(method) => Some((method, self.locals_into_form_values())),
             None => None,
@@ -2262,36 +2280,31 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         let mut result = self.run_actions(parent_data.to_unbounded_subslice(action.try_body));
 
         if let Some((catch_vars, actions)) = &action.catch_body {
-            if let Err(Error::ThrownValue(value)) = &result {
-                let mut activation = Activation::from_action(
-                    self.context.reborrow(),
-                    self.id.child("[Catch]"),
-                    self.swf_version,
-                    self.scope,
-                    self.constant_pool,
-                    self.base_clip,
-                    self.this,
-                    self.callee,
-                );
-
+            if let Err(Error::ThrownValue(value)) = result {
+                // The catch block runs in the same frame as the try block, so it sees the same
+                // registers, target and scope as the code around it.
                 match catch_vars {
                     CatchVar::Var(name) => {
-                        let name = AvmString::new(
-                            activation.context.gc_context,
-                            name.decode(activation.encoding()),
-                        );
-                        activation.set_variable(name, value.to_owned())?
-                    }
-                    CatchVar::Register(id) => {
-                        activation.set_current_register(*id, value.to_owned())
+                        let name =
+                            AvmString::new(self.context.gc_context, name.decode(self.encoding()));
+                        self.set_variable(name, value)?
                     }
+                    CatchVar::Register(id) => self.set_current_register(*id, value),
                 }
 
-                result = activation.run_actions(parent_data.to_unbounded_subslice(actions));
+                result = self.run_actions(parent_data.to_unbounded_subslice(actions));
             }
         }
 
+        // Errors other than thrown values abort the whole script, so no more code is run.
+        match result {
+            Ok(_) | Err(Error::ThrownValue(_)) => {}
+            Err(e) => return Err(e),
+        }
+
         if let Some(actions) = action.finally_body {
+            // Returning from the finally block overrides any value returned or thrown by the
+            // try and catch blocks.
             if let ReturnType::Explicit(value) =
                 self.run_actions(parent_data.to_unbounded_subslice(actions))?
             {
@@ -2491,10 +2504,12 @@ impl<'a, 'gc> Activation<'a, 'gc> {
                     .finish();
 
                 match method {
-                    NavigationMethod::Get if !url.contains(b'?') => {
-                        Request::get(format!("{url}?{qstring}"))
+                    NavigationMethod::Get | NavigationMethod::Head if !url.contains(b'?') => {
+                        Request::request(method, format!("{url}?{qstring}"), None)
+                    }
+                    NavigationMethod::Get | NavigationMethod::Head => {
+                        Request::request(method, format!("{url}&{qstring}"), None)
                     }
-                    NavigationMethod::Get => Request::get(format!("{url}&{qstring}")),
                     NavigationMethod::Post => Request::post(
                         url.to_utf8_lossy().into_owned(),
                         Some((
@@ -3,6 +3,7 @@
 use crate::avm1::activation::Activation;
 use crate::avm1::error::Error;
 use crate::avm1::globals::bitmap_filter;
+use crate::avm1::object::NativeObject;
 use crate::avm1::property_decl::{define_properties_on, Declaration};
 use crate::avm1::ArrayObject;
 use crate::avm1::{globals, Object, ScriptObject, TObject, Value};
@@ -44,6 +45,7 @@ const PROTO_DECLS: &[Declaration] = declare_properties! {
     "blendMode" => property(button_getter!(blend_mode), button_setter!(set_blend_mode); DONT_DELETE | VERSION_8);
     "filters" => property(button_getter!(filters), button_setter!(set_filters); DONT_DELETE | DONT_ENUM | VERSION_8);
     "cacheAsBitmap" => property(button_getter!(cache_as_bitmap), button_setter!(set_cache_as_bitmap); DONT_DELETE | DONT_ENUM | VERSION_8);
+    "transform" => property(button_getter!(transform), button_setter!(set_transform); DONT_DELETE | DONT_ENUM | VERSION_8);
 };
 
 pub fn create_proto<'gc>(
@@ -141,3 +143,39 @@ fn set_cache_as_bitmap<'gc>(
     );
     Ok(())
 }
+
+fn transform<'gc>(
+    this: Avm1Button<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let constructor = activation.context.avm1.prototypes().transform_constructor;
+    let cloned = constructor.construct(activation, &[this.object()])?;
+    Ok(cloned)
+}
+
+fn set_transform<'gc>(
+    this: Avm1Button<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+    value: Value<'gc>,
+) -> Result<(), Error<'gc>> {
+    if let Value::Object(object) = value {
+        if let NativeObject::Transform(transform) = object.native() {
+            if let Some(clip) = transform.clip(activation) {
+                let matrix = *clip.base().matrix();
+                this.set_matrix(activation.context.gc_context, matrix);
+
+                let color_transform = *clip.base().color_transform();
+                this.set_color_transform(activation.context.gc_context, color_transform);
+
+                this.invalidate_cached_bitmap(activation.context.gc_context);
+                if let Some(parent) = this.parent() {
+                    parent.invalidate_cached_bitmap(activation.context.gc_context);
+                }
+
+                this.set_transformed_by_script(activation.context.gc_context, true);
+            }
+        }
+    }
+
+    Ok(())
+}
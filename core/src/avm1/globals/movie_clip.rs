@@ -18,6 +18,7 @@ use crate::ecma_conversions::f64_to_wrapping_i32;
 use crate::prelude::*;
 use crate::string::AvmString;
 use crate::vminterface::Instantiator;
+use ruffle_render::bitmap::PixelSnapping;
 use ruffle_render::shape_utils::{DrawCommand, GradientType};
 use swf::{
     FillStyle, Fixed8, Gradient, GradientInterpolation, GradientRecord, GradientSpread,
@@ -255,11 +256,13 @@ fn attach_bitmap<'gc>(
                     .coerce_to_i32(activation)?
                     .wrapping_add(AVM_DEPTH_BIAS);
 
-                // TODO: Implement pixel snapping
-                let _pixel_snapping = args
-                    .get(2)
-                    .unwrap_or(&Value::Undefined)
-                    .as_bool(activation.swf_version());
+                let pixel_snapping = match args.get(2) {
+                    Some(pixel_snapping @ Value::String(_)) => {
+                        PixelSnapping::from_wstr(&pixel_snapping.coerce_to_string(activation)?)
+                            .unwrap_or(PixelSnapping::Auto)
+                    }
+                    _ => PixelSnapping::Auto,
+                };
 
                 let smoothing = args
                     .get(3)
@@ -273,6 +276,7 @@ fn attach_bitmap<'gc>(
                     bitmap_data,
                     smoothing,
                 );
+                display_object.set_pixel_snapping(activation.context.gc_context, pixel_snapping);
                 movie_clip.replace_at_depth(&mut activation.context, display_object.into(), depth);
                 display_object.post_instantiation(
                     &mut activation.context,
@@ -1418,6 +1422,19 @@ fn get_bounds<'gc>(
     movie_clip: MovieClip<'gc>,
     activation: &mut Activation<'_, 'gc>,
     args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    bounds_in_target_space(movie_clip, activation, args, true)
+}
+
+/// Shared implementation of `getBounds` and `getRect`.
+///
+/// `include_strokes` selects whether the width of strokes is included in the
+/// returned bounds.
+fn bounds_in_target_space<'gc>(
+    movie_clip: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+    args: &[Value<'gc>],
+    include_strokes: bool,
 ) -> Result<Value<'gc>, Error<'gc>> {
     let target = match args.get(0) {
         Some(val) => activation.resolve_target_display_object(movie_clip.into(), *val, false)?,
@@ -1439,7 +1456,11 @@ fn get_bounds<'gc>(
             }
         }
 
-        let bounds = movie_clip.bounds();
+        let bounds = if include_strokes {
+            movie_clip.bounds()
+        } else {
+            movie_clip.edge_bounds_with_transform(&Matrix::default())
+        };
         let out_bounds = if DisplayObject::ptr_eq(movie_clip.into(), target) {
             // Getting the clips bounds in its own coordinate space; no AABB transform needed.
             bounds
@@ -1488,9 +1509,7 @@ fn get_rect<'gc>(
     activation: &mut Activation<'_, 'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    // TODO: This should get the bounds ignoring strokes. Always equal to or smaller than getBounds.
-    // Just defer to getBounds for now. Will have to store edge_bounds vs. shape_bounds in Graphic.
-    get_bounds(movie_clip, activation, args)
+    bounds_in_target_space(movie_clip, activation, args, false)
 }
 
 fn get_swf_version<'gc>(
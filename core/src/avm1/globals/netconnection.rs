@@ -0,0 +1,224 @@
+use crate::avm1::function::{Executable, FunctionObject};
+use crate::avm1::object::{NativeObject, Object};
+use crate::avm1::property_decl::{define_properties_on, Declaration};
+use crate::avm1::{Activation, Error, ScriptObject, TObject, Value};
+use crate::avm1_stub;
+use crate::context::{ActionType, GcContext};
+use crate::net_status::NetStatus;
+use gc_arena::{Collect, GcCell};
+
+#[derive(Clone, Debug, Collect)]
+#[collect(no_drop)]
+struct NetConnectionData<'gc> {
+    /// Whether the connection is open.
+    is_connected: bool,
+
+    /// The URI passed to the last `connect` call, or `undefined` before the first one.
+    uri: Value<'gc>,
+}
+
+#[derive(Clone, Copy, Debug, Collect)]
+#[collect(no_drop)]
+pub struct NetConnection<'gc>(GcCell<'gc, NetConnectionData<'gc>>);
+
+impl<'gc> NetConnection<'gc> {
+    pub fn cast(value: Value<'gc>) -> Option<Self> {
+        if let Value::Object(object) = value {
+            if let NativeObject::NetConnection(net_connection) = object.native() {
+                return Some(net_connection);
+            }
+        }
+        None
+    }
+}
+
+const PROTO_DECLS: &[Declaration] = declare_properties! {
+    "isConnected" => property(is_connected; DONT_ENUM | DONT_DELETE);
+    "uri" => property(uri; DONT_ENUM | DONT_DELETE);
+    "connect" => method(connect; DONT_ENUM | DONT_DELETE);
+    "call" => method(call; DONT_ENUM | DONT_DELETE);
+    "close" => method(close; DONT_ENUM | DONT_DELETE);
+};
+
+pub fn constructor<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let net_connection = NetConnection(GcCell::new(
+        activation.context.gc_context,
+        NetConnectionData {
+            is_connected: false,
+            uri: Value::Undefined,
+        },
+    ));
+    this.set_native(
+        activation.context.gc_context,
+        NativeObject::NetConnection(net_connection),
+    );
+    Ok(this.into())
+}
+
+fn is_connected<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(net_connection) = NetConnection::cast(this.into()) {
+        Ok(net_connection.0.read().is_connected.into())
+    } else {
+        Ok(Value::Undefined)
+    }
+}
+
+fn uri<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(net_connection) = NetConnection::cast(this.into()) {
+        Ok(net_connection.0.read().uri)
+    } else {
+        Ok(Value::Undefined)
+    }
+}
+
+fn set_state<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    connected: bool,
+    uri: Value<'gc>,
+) {
+    if let Some(net_connection) = NetConnection::cast(this.into()) {
+        let mut write = net_connection.0.write(activation.context.gc_context);
+        write.is_connected = connected;
+        write.uri = uri;
+    }
+}
+
+/// Queues a call to `onStatus` on `target` with the `info` object of `status`.
+///
+/// Like a reply from a server, the status only arrives after the call that caused it returns.
+fn on_status<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    target: Object<'gc>,
+    status: NetStatus,
+) -> Result<(), Error<'gc>> {
+    let info = ScriptObject::new(
+        activation.context.gc_context,
+        Some(activation.context.avm1.prototypes().object),
+    );
+    for (key, value) in status.info(None) {
+        info.set(key, value.into(), activation)?;
+    }
+    if let Some(root) = activation.context.stage.root_clip() {
+        activation.context.action_queue.queue_action(
+            root,
+            ActionType::Method {
+                object: target,
+                name: "onStatus",
+                args: vec![info.into()],
+            },
+            false,
+        );
+    }
+    Ok(())
+}
+
+fn connect<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let command = args.get(0).copied().unwrap_or(Value::Undefined);
+    let uri = match command {
+        Value::Undefined | Value::Null => None,
+        command => Some(command.coerce_to_string(activation)?),
+    };
+
+    let uri_value = Value::from(uri.unwrap_or_else(|| "null".into()));
+    let lowercase = uri
+        .map(|uri| uri.to_utf8_lossy().to_ascii_lowercase())
+        .unwrap_or_default();
+
+    // Without a server there is nothing to fail, and NetGroups only need the local network.
+    if uri.is_none() || lowercase.starts_with("rtmfp:") {
+        set_state(activation, this, true, uri_value);
+        on_status(activation, this, NetStatus::ConnectSuccess)?;
+        return Ok(true.into());
+    }
+
+    avm1_stub!(activation, "NetConnection", "connect", "with a server");
+    set_state(activation, this, false, uri_value);
+    // Flash Remoting over HTTP doesn't connect until the first call, but anything else needs a
+    // server that we can't talk to.
+    if lowercase.starts_with("http:") || lowercase.starts_with("https:") {
+        return Ok(true.into());
+    }
+    on_status(activation, this, NetStatus::ConnectFailed)?;
+    Ok(false.into())
+}
+
+fn call<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let command = args
+        .get(0)
+        .copied()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?;
+    tracing::warn!("NetConnection.call({command}) failed: there is no server to call");
+
+    // Results and errors go to the responder when there is one, like a server's reply would.
+    let target = match args.get(1) {
+        Some(Value::Object(responder)) => *responder,
+        _ => this,
+    };
+    on_status(activation, target, NetStatus::CallFailed)?;
+    Ok(Value::Undefined)
+}
+
+fn close<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(net_connection) = NetConnection::cast(this.into()) else {
+        return Ok(Value::Undefined);
+    };
+    let (connected, uri) = {
+        let read = net_connection.0.read();
+        (read.is_connected, read.uri)
+    };
+    if connected {
+        set_state(activation, this, false, uri);
+        on_status(activation, this, NetStatus::ConnectClosed)?;
+    }
+    Ok(Value::Undefined)
+}
+
+pub fn create_proto<'gc>(
+    context: &mut GcContext<'_, 'gc>,
+    proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    let object = ScriptObject::new(context.gc_context, Some(proto));
+    define_properties_on(PROTO_DECLS, context, object, fn_proto);
+    object.into()
+}
+
+pub fn create_class<'gc>(
+    context: &mut GcContext<'_, 'gc>,
+    netconnection_proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    FunctionObject::constructor(
+        context.gc_context,
+        Executable::Native(constructor),
+        constructor_to_fn!(constructor),
+        fn_proto,
+        netconnection_proto,
+    )
+}
@@ -84,7 +84,7 @@ fn pause<'gc>(
         } else if is_pause {
             ns.pause(&mut activation.context, true);
         } else {
-            ns.resume(&mut activation.context);
+            ns.resume(&mut activation.context, true);
         }
     }
 
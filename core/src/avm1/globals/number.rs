@@ -8,6 +8,7 @@ use crate::avm1::object::value_object::ValueObject;
 use crate::avm1::property_decl::{define_properties_on, Declaration};
 use crate::avm1::{Object, TObject, Value};
 use crate::context::GcContext;
+use crate::number_format;
 use crate::string::AvmString;
 
 const PROTO_DECLS: &[Declaration] = declare_properties! {
@@ -129,36 +130,11 @@ fn to_string<'gc>(
         // TODO: Allow configuration of player version.
 
         let number = number.clamp_to_i32();
-
-        use std::cmp::Ordering;
-        let (mut number, is_negative) = match number.cmp(&0) {
-            Ordering::Less => (number.wrapping_neg(), true),
-            Ordering::Greater => (number, false),
-            Ordering::Equal => {
-                // Bail out immediately if we're 0.
-                return Ok("0".into());
-            }
-        };
-
-        // Max 32 digits in base 2 + negative sign.
-        let mut digits = [0; 33];
-        let mut i = digits.len();
-        while number != 0 {
-            let digit = number % radix;
-            number /= radix;
-
-            i -= 1;
-            digits[i] = if digit < 10 {
-                i32::from(b'0') + digit
-            } else {
-                i32::from(b'a') + digit - 10
-            } as u8;
-        }
-        if is_negative {
-            i -= 1;
-            digits[i] = b'-';
-        }
-        Ok(AvmString::new_utf8_bytes(activation.context.gc_context, &digits[i..]).into())
+        Ok(AvmString::new_utf8(
+            activation.context.gc_context,
+            number_format::to_radix(number.into(), radix as u32),
+        )
+        .into())
     }
 }
 
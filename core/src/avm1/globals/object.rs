@@ -268,18 +268,13 @@ pub fn as_set_prop_flags<'gc>(
         return Ok(Value::Undefined);
     };
 
-    let set_flags = args.get(2).unwrap_or(&0.into()).coerce_to_f64(activation)? as u16;
-    let set_attributes = Attribute::from_bits_retain(set_flags);
+    // Besides the low three bits, the flags hold the SWF versions a property is hidden from,
+    // which frameworks use to hide their internals from older content.
+    let set_flags = args.get(2).unwrap_or(&0.into()).coerce_to_i32(activation)? as u16;
+    let set_attributes = Attribute::from_bits_truncate(set_flags);
 
-    let clear_flags = args.get(3).unwrap_or(&0.into()).coerce_to_f64(activation)? as u16;
-    let clear_attributes = Attribute::from_bits_retain(clear_flags);
-
-    if set_attributes.bits() != set_flags || clear_attributes.bits() != clear_flags {
-        avm_warn!(
-            activation,
-            "ASSetPropFlags: Unimplemented support for flags > 7"
-        );
-    }
+    let clear_flags = args.get(3).unwrap_or(&0.into()).coerce_to_i32(activation)? as u16;
+    let clear_attributes = Attribute::from_bits_truncate(clear_flags);
 
     match args.get(1) {
         Some(&Value::Null) => object.set_attributes(
@@ -5,6 +5,7 @@ use crate::avm1::property_decl::{define_properties_on, Declaration};
 use crate::avm1::{Object, ScriptObject, Value};
 use crate::context::GcContext;
 use crate::display_object::{EditText, TDisplayObject, TextSelection};
+use crate::string::AvmString;
 
 const OBJECT_DECLS: &[Declaration] = declare_properties! {
     "getBeginIndex" => method(get_begin_index; DONT_ENUM | DONT_DELETE | READ_ONLY);
@@ -109,11 +110,9 @@ pub fn get_focus<'gc>(
 ) -> Result<Value<'gc>, Error<'gc>> {
     let focus = activation.context.focus_tracker.get();
     Ok(match focus {
-        Some(focus) => focus
-            .object()
-            .coerce_to_string(activation)
-            .unwrap_or_default()
-            .into(),
+        // This is always the target path, even for objects whose class overrides `toString`,
+        // since the V2 components' focus manager `eval`s it.
+        Some(focus) => AvmString::new(activation.context.gc_context, focus.path()).into(),
         None => Value::Null,
     })
 }
@@ -9,6 +9,7 @@ use crate::avm1_stub;
 use crate::context::{GcContext, UpdateContext};
 use bitflags::bitflags;
 use core::fmt;
+use serde::{Deserialize, Serialize};
 
 const OBJECT_DECLS: &[Declaration] = declare_properties! {
     "exactSettings" => property(get_exact_settings, set_exact_settings);
@@ -41,6 +42,8 @@ impl fmt::Display for CpuArchitecture {
 
 /// Available type of sandbox for a given SWF
 #[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub enum SandboxType {
     Remote,
     LocalWithFile,
@@ -317,9 +320,9 @@ impl SystemProperties {
     }
     pub fn get_version_string(&self, avm: &mut Avm1) -> String {
         format!(
-            "{} {},0,0,0",
+            "{} {}",
             self.manufacturer.get_platform_name(),
-            avm.player_version()
+            avm.player_version().capabilities_version()
         )
     }
 
@@ -390,7 +393,7 @@ impl SystemProperties {
                 "M",
                 &self.encode_string(
                     self.manufacturer
-                        .get_manufacturer_string(context.avm1.player_version())
+                        .get_manufacturer_string(context.avm1.player_version().major)
                         .as_str(),
                 ),
             )
@@ -407,7 +410,7 @@ impl SystemProperties {
             .append_pair(
                 "L",
                 self.language
-                    .get_language_code(context.avm1.player_version()),
+                    .get_language_code(context.avm1.player_version().major),
             )
             .append_pair("IME", self.encode_capability(SystemCapabilities::IME))
             .append_pair("PT", &self.player_type.to_string())
@@ -8,7 +8,7 @@ use crate::avm1::object_reference::MovieClipReference;
 use crate::avm1::property_decl::{define_properties_on, Declaration};
 use crate::avm1::{Activation, Error, Object, ScriptObject, TObject, Value};
 use crate::context::GcContext;
-use crate::display_object::{DisplayObject, TDisplayObject};
+use crate::display_object::{Avm1Button, DisplayObject, TDisplayObject};
 use gc_arena::Collect;
 use swf::{Rectangle, Twips};
 
@@ -16,23 +16,38 @@ use swf::{Rectangle, Twips};
 #[collect(no_drop)]
 pub struct TransformObject<'gc> {
     clip: Option<MovieClipReference<'gc>>,
+
+    /// The button this transform applies to.
+    /// Only movie clips can be referred to by path, so buttons are held directly.
+    button: Option<Avm1Button<'gc>>,
 }
 
 impl<'gc> TransformObject<'gc> {
     fn new(activation: &mut Activation<'_, 'gc>, args: &[Value<'gc>]) -> Option<Self> {
-        let clip = match args {
+        let (clip, button) = match args {
             // `Tranform` constructor accepts exactly 1 argument.
-            [Value::MovieClip(clip)] => Some(*clip),
+            [Value::MovieClip(clip)] => (Some(*clip), None),
             [Value::Object(clip)] => {
                 let stage_object = clip.as_stage_object()?;
-                MovieClipReference::try_from_stage_object(activation, stage_object)
+                if let Some(DisplayObject::Avm1Button(button)) = stage_object.as_display_object() {
+                    (None, Some(button))
+                } else {
+                    (
+                        MovieClipReference::try_from_stage_object(activation, stage_object),
+                        None,
+                    )
+                }
             }
             _ => return None,
         };
-        Some(Self { clip })
+        Some(Self { clip, button })
     }
 
     pub fn clip(&self, activation: &mut Activation<'_, 'gc>) -> Option<DisplayObject<'gc>> {
+        if let Some(button) = self.button {
+            return Some(button.into());
+        }
+
         let (_, _, clip) = self.clip?.resolve_reference(activation)?;
         Some(clip)
     }
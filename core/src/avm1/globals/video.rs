@@ -8,6 +8,7 @@ use crate::avm1::value::Value;
 use crate::avm1::ScriptObject;
 use crate::context::GcContext;
 use crate::display_object::{TDisplayObject, Video};
+use swf::VideoDeblocking;
 
 macro_rules! video_method {
     ( $fn: expr ) => {
@@ -33,6 +34,8 @@ pub fn constructor<'gc>(
 
 const PROTO_DECLS: &[Declaration] = declare_properties! {
     "attachVideo" => method(video_method!(attach_video); DONT_ENUM | DONT_DELETE | VERSION_6);
+    "deblocking" => property(video_method!(deblocking), video_method!(set_deblocking); VERSION_6);
+    "smoothing" => property(video_method!(smoothing), video_method!(set_smoothing); VERSION_6);
 };
 
 pub fn attach_video<'gc>(
@@ -55,6 +58,52 @@ pub fn attach_video<'gc>(
     Ok(Value::Undefined)
 }
 
+fn deblocking<'gc>(
+    video: Video<'gc>,
+    _activation: &mut Activation<'_, 'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok((video.deblocking() as i32).into())
+}
+
+fn set_deblocking<'gc>(
+    video: Video<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let value = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_i32(activation)?;
+    let deblocking = u8::try_from(value)
+        .ok()
+        .and_then(VideoDeblocking::from_u8)
+        .unwrap_or(VideoDeblocking::UseVideoPacketValue);
+    video.set_deblocking(&mut activation.context, deblocking);
+    Ok(Value::Undefined)
+}
+
+fn smoothing<'gc>(
+    video: Video<'gc>,
+    _activation: &mut Activation<'_, 'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(video.smoothing().into())
+}
+
+fn set_smoothing<'gc>(
+    video: Video<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let smoothing = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .as_bool(activation.swf_version());
+    video.set_smoothing(activation.context.gc_context, smoothing);
+    Ok(Value::Undefined)
+}
+
 pub fn create_proto<'gc>(
     context: &mut GcContext<'_, 'gc>,
     proto: Object<'gc>,
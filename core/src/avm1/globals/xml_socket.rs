@@ -5,10 +5,11 @@ use crate::avm1::{property_decl::Declaration, ScriptObject};
 use crate::avm1::{Activation, Error, Executable, ExecutionReason, TObject, Value};
 use crate::context::{GcContext, UpdateContext};
 use crate::display_object::TDisplayObject;
+use crate::policy_file::content_domain;
 use crate::socket::SocketHandle;
 use crate::string::AvmString;
 use gc_arena::{Collect, Gc};
-use std::cell::{Cell, RefCell, RefMut};
+use std::cell::Cell;
 
 #[derive(Clone, Debug, Collect)]
 #[collect(require_static)]
@@ -16,7 +17,6 @@ struct XmlSocketData {
     handle: Cell<Option<SocketHandle>>,
     /// Connection timeout in milliseconds.
     timeout: Cell<u32>,
-    read_buffer: RefCell<Vec<u8>>,
 }
 
 #[derive(Clone, Debug, Collect)]
@@ -41,10 +41,6 @@ impl<'gc> XmlSocket<'gc> {
         self.0.timeout.set(new_timeout);
     }
 
-    pub fn read_buffer(&self) -> RefMut<'_, Vec<u8>> {
-        self.0.read_buffer.borrow_mut()
-    }
-
     pub fn cast(value: Value<'gc>) -> Option<Self> {
         if let Value::Object(object) = value {
             if let NativeObject::XmlSocket(xml_socket) = object.native() {
@@ -141,10 +137,19 @@ pub fn connect<'gc>(
             .coerce_to_u16(activation)?;
 
         let UpdateContext {
-            sockets, navigator, ..
+            sockets,
+            navigator,
+            swf,
+            ..
         } = &mut activation.context;
 
-        sockets.connect_avm1(*navigator, this, host.to_utf8_lossy().into_owned(), port);
+        sockets.connect_avm1(
+            *navigator,
+            this,
+            host.to_utf8_lossy().into_owned(),
+            port,
+            content_domain(swf),
+        );
 
         // NOTE: At this point we do not know if the connection will succeed
         //       because connecting is an asynchronous process, so we just return true.
@@ -237,7 +242,6 @@ pub fn constructor<'gc>(
             handle: Cell::new(None),
             // Default timeout is 20_000 milliseconds (20 seconds)
             timeout: Cell::new(20000),
-            read_buffer: RefCell::new(Vec::new()),
         },
     ));
 
@@ -11,6 +11,7 @@ use crate::avm1::globals::displacement_map_filter::DisplacementMapFilter;
 use crate::avm1::globals::drop_shadow_filter::DropShadowFilter;
 use crate::avm1::globals::glow_filter::GlowFilter;
 use crate::avm1::globals::gradient_filter::GradientFilter;
+use crate::avm1::globals::netconnection::NetConnection;
 use crate::avm1::globals::shared_object::SharedObject;
 use crate::avm1::globals::transform::TransformObject;
 use crate::avm1::globals::xml::Xml;
@@ -56,6 +57,7 @@ pub enum NativeObject<'gc> {
     ColorTransform(GcCell<'gc, ColorTransformObject>),
     Transform(TransformObject<'gc>),
     TextFormat(Gc<'gc, RefCell<TextFormat>>),
+    NetConnection(NetConnection<'gc>),
     NetStream(NetStream<'gc>),
     BitmapData(BitmapDataWrapper<'gc>),
     Xml(Xml<'gc>),
@@ -110,9 +112,10 @@ pub trait TObject<'gc>: 'gc + Collect + Into<Object<'gc>> + Clone + Copy {
         } else {
             ((*self).into(), Value::Object((*self).into()))
         };
-        match search_prototype(proto, name.into(), activation, this, false)? {
+        let name = name.into();
+        match search_prototype(proto, name, activation, this, false)? {
             Some((value, _depth)) => Ok(value),
-            None => Ok(Value::Undefined),
+            None => call_resolve(proto, name, activation, this),
         }
     }
 
@@ -128,9 +131,10 @@ pub trait TObject<'gc>: 'gc + Collect + Into<Object<'gc>> + Clone + Copy {
         } else {
             ((*self).into(), Value::Object((*self).into()))
         };
-        match search_prototype(proto, name.into(), activation, this, true)? {
+        let name = name.into();
+        match search_prototype(proto, name, activation, this, true)? {
             Some((value, _depth)) => Ok(value),
-            None => Ok(Value::Undefined),
+            None => call_resolve(proto, name, activation, this),
         }
     }
 
@@ -680,6 +684,32 @@ impl<'gc> Object<'gc> {
     }
 }
 
+/// Handles a read of a property that doesn't exist, by calling the `__resolve` method of the
+/// object, if it has one, with the name of the property.
+///
+/// AS2 frameworks use this to make proxies and lazily-created members.
+fn call_resolve<'gc>(
+    proto: Value<'gc>,
+    name: AvmString<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    // `__resolve` doesn't resolve itself.
+    if activation.swf_version() < 6
+        || name.eq_with_case(b"__resolve", activation.is_case_sensitive())
+    {
+        return Ok(Value::Undefined);
+    }
+
+    let resolve = search_prototype(proto, "__resolve".into(), activation, this, false)?;
+    match resolve {
+        Some((Value::Object(resolve), _depth)) if resolve.as_executable().is_some() => {
+            resolve.call("__resolve".into(), activation, this.into(), &[name.into()])
+        }
+        _ => Ok(Value::Undefined),
+    }
+}
+
 /// Perform a prototype lookup of a given object.
 ///
 /// This function returns both the `Value` and the prototype depth from which
@@ -8,7 +8,7 @@ use crate::avm1::{Object, ObjectPtr, ScriptObject, TObject, Value};
 use crate::avm_warn;
 use crate::context::UpdateContext;
 use crate::display_object::{
-    DisplayObject, EditText, MovieClip, TDisplayObject, TDisplayObjectContainer,
+    DisplayObject, EditText, MovieClip, TDisplayObject, TDisplayObjectContainer, TInteractiveObject,
 };
 use crate::string::{AvmString, WStr};
 use crate::types::Percent;
@@ -740,17 +740,36 @@ fn set_high_quality<'gc>(
     Ok(())
 }
 
-fn focus_rect<'gc>(activation: &mut Activation<'_, 'gc>, _this: DisplayObject<'gc>) -> Value<'gc> {
-    avm_warn!(activation, "Unimplemented property _focusrect");
-    Value::Null
+/// `_focusrect` of a root timeline is the global setting, stored on the stage. Other objects have
+/// their own, which is `null` until set, meaning the global setting applies.
+fn focus_rect<'gc>(activation: &mut Activation<'_, 'gc>, this: DisplayObject<'gc>) -> Value<'gc> {
+    if this.is_root() || activation.swf_version() < 6 {
+        return activation.context.stage.stage_focus_rect().into();
+    }
+    match this.as_interactive().and_then(|this| this.focus_rect()) {
+        Some(focus_rect) => focus_rect.into(),
+        None => Value::Null,
+    }
 }
 
 fn set_focus_rect<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: DisplayObject<'gc>,
-    _val: Value<'gc>,
+    this: DisplayObject<'gc>,
+    val: Value<'gc>,
 ) -> Result<(), Error<'gc>> {
-    avm_warn!(activation, "Unimplemented property _focusrect");
+    if this.is_root() || activation.swf_version() < 6 {
+        let focus_rect = val.as_bool(activation.swf_version());
+        activation
+            .context
+            .stage
+            .set_stage_focus_rect(activation.context.gc_context, focus_rect);
+    } else if let Some(this) = this.as_interactive() {
+        let focus_rect = match val {
+            Value::Undefined | Value::Null => None,
+            val => Some(val.as_bool(activation.swf_version())),
+        };
+        this.set_focus_rect(activation.context.gc_context, focus_rect);
+    }
     Ok(())
 }
 
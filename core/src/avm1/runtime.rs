@@ -8,6 +8,7 @@ use crate::avm1::scope::Scope;
 use crate::avm1::{scope, Activation, ActivationIdentifier, Error, Object, Value};
 use crate::context::{GcContext, UpdateContext};
 use crate::frame_lifecycle::FramePhase;
+use crate::player_version::PlayerVersion;
 use crate::prelude::*;
 use crate::string::AvmString;
 use crate::tag_utils::SwfSlice;
@@ -21,7 +22,7 @@ use tracing::instrument;
 #[collect(no_drop)]
 pub struct Avm1<'gc> {
     /// The Flash Player version we're emulating.
-    player_version: u8,
+    player_version: PlayerVersion,
 
     /// The constant pool to use for new activations from code sources that
     /// don't close over the constant pool they were defined with.
@@ -90,7 +91,7 @@ pub struct Avm1<'gc> {
 }
 
 impl<'gc> Avm1<'gc> {
-    pub fn new(context: &mut GcContext<'_, 'gc>, player_version: u8) -> Self {
+    pub fn new(context: &mut GcContext<'_, 'gc>, player_version: PlayerVersion) -> Self {
         let gc_context = context.gc_context;
         let (prototypes, globals, broadcaster_functions) = create_globals(context);
 
@@ -399,7 +400,7 @@ impl<'gc> Avm1<'gc> {
     }
 
     /// The Flash Player version we're emulating.
-    pub fn player_version(&self) -> u8 {
+    pub fn player_version(&self) -> PlayerVersion {
         self.player_version
     }
 
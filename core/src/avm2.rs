@@ -2,6 +2,7 @@
 
 use std::rc::Rc;
 
+use crate::avm2::api_version::ApiVersion;
 use crate::avm2::class::AllocatorFn;
 use crate::avm2::function::Executable;
 use crate::avm2::globals::SystemClasses;
@@ -9,6 +10,7 @@ use crate::avm2::method::{Method, NativeMethodImpl};
 use crate::avm2::script::{Script, TranslationUnit};
 use crate::context::{GcContext, UpdateContext};
 use crate::display_object::{DisplayObject, DisplayObjectWeak, TDisplayObject};
+use crate::player_version::{EnumerationOrder, PlayerVersion};
 use crate::string::AvmString;
 use crate::tag_utils::SwfMovie;
 
@@ -29,6 +31,7 @@ macro_rules! avm_debug {
 
 pub mod activation;
 mod amf;
+mod api_version;
 mod array;
 pub mod bytearray;
 mod call_stack;
@@ -41,6 +44,8 @@ mod filters;
 mod flv;
 mod function;
 pub mod globals;
+mod hash_order;
+mod inline_cache;
 mod metadata;
 mod method;
 mod multiname;
@@ -61,11 +66,17 @@ pub mod vector;
 mod vtable;
 
 pub use crate::avm2::activation::Activation;
+pub use crate::avm2::amf::deserialize_amf3_bytes;
 pub use crate::avm2::array::ArrayStorage;
 pub use crate::avm2::call_stack::{CallNode, CallStack};
 pub use crate::avm2::domain::Domain;
 pub use crate::avm2::error::Error;
+pub use crate::avm2::events::will_trigger;
 pub use crate::avm2::flv::FlvValueAvm2Ext;
+pub use crate::avm2::globals::flash::desktop::clipboard::dispatch_paste;
+pub use crate::avm2::globals::flash::display::native_window::{
+    dispatch_native_menu_select, dispatch_window_activation, dispatch_window_bounds_change,
+};
 pub use crate::avm2::globals::flash::ui::context_menu::make_context_menu_state;
 pub use crate::avm2::multiname::Multiname;
 pub use crate::avm2::namespace::Namespace;
@@ -86,7 +97,10 @@ const BROADCAST_WHITELIST: [&str; 4] = ["enterFrame", "exitFrame", "frameConstru
 #[collect(no_drop)]
 pub struct Avm2<'gc> {
     /// The Flash Player version we're emulating.
-    player_version: u8,
+    player_version: PlayerVersion,
+
+    /// The order that dynamic properties are enumerated in.
+    enumeration_order: EnumerationOrder,
 
     /// Values currently present on the operand stack.
     stack: Vec<Value<'gc>>,
@@ -113,6 +127,8 @@ pub struct Avm2<'gc> {
     toplevel_global_object: Option<Object<'gc>>,
 
     pub public_namespace: Namespace<'gc>,
+    /// The public namespace in the API version of the root movie, made when first needed.
+    pub movie_public_namespace: Option<Namespace<'gc>>,
     pub internal_namespace: Namespace<'gc>,
     pub as3_namespace: Namespace<'gc>,
     pub vector_public_namespace: Namespace<'gc>,
@@ -154,19 +170,28 @@ pub struct Avm2<'gc> {
     /// strong references around (this matches Flash's behavior).
     orphan_objects: Rc<Vec<DisplayObjectWeak<'gc>>>,
 
+    /// Whether a `paste` event is being dispatched, which lets content read the
+    /// system clipboard.
+    pasting: bool,
+
     #[cfg(feature = "avm_debug")]
     pub debug_output: bool,
 }
 
 impl<'gc> Avm2<'gc> {
     /// Construct a new AVM interpreter.
-    pub fn new(context: &mut GcContext<'_, 'gc>, player_version: u8) -> Self {
+    pub fn new(
+        context: &mut GcContext<'_, 'gc>,
+        player_version: PlayerVersion,
+        enumeration_order: EnumerationOrder,
+    ) -> Self {
         let playerglobals_domain = Domain::uninitialized_domain(context.gc_context, None);
         let stage_domain =
             Domain::uninitialized_domain(context.gc_context, Some(playerglobals_domain));
 
         Self {
             player_version,
+            enumeration_order,
             stack: Vec::new(),
             scope_stack: Vec::new(),
             call_stack: GcCell::new(context.gc_context, CallStack::new()),
@@ -176,6 +201,7 @@ impl<'gc> Avm2<'gc> {
             toplevel_global_object: None,
 
             public_namespace: Namespace::package("", context),
+            movie_public_namespace: None,
             internal_namespace: Namespace::internal("", context),
             as3_namespace: Namespace::package("http://adobe.com/AS3/2006/builtin", context),
             vector_public_namespace: Namespace::package("__AS3__.vec", context),
@@ -198,11 +224,20 @@ impl<'gc> Avm2<'gc> {
 
             orphan_objects: Default::default(),
 
+            pasting: false,
+
             #[cfg(feature = "avm_debug")]
             debug_output: false,
         }
     }
 
+    /// The API seen by a root movie of the given SWF version, which can't be newer than the
+    /// API of the Flash Player release we're emulating.
+    pub fn movie_api_version(&self, swf_version: u8) -> ApiVersion {
+        ApiVersion::from_swf_version(swf_version)
+            .min(ApiVersion::from_player_version(self.player_version))
+    }
+
     pub fn load_player_globals(context: &mut UpdateContext<'_, 'gc>) -> Result<(), Error<'gc>> {
         let globals = context.avm2.playerglobals_domain;
         let mut activation = Activation::from_domain(context.reborrow(), globals);
@@ -501,8 +536,17 @@ impl<'gc> Avm2<'gc> {
             }
         };
 
+        // Our player globals mark the namespaces of versioned definitions, while movies see the
+        // API of the root movie's version.
+        let api_version = if Domain::ptr_eq(domain, context.avm2.playerglobals_domain) {
+            None
+        } else {
+            Some(context.avm2.movie_api_version(context.swf.version()))
+        };
+
         let num_scripts = abc.scripts.len();
-        let tunit = TranslationUnit::from_abc(abc, domain, name, movie, context.gc_context);
+        let tunit =
+            TranslationUnit::from_abc(abc, domain, name, movie, api_version, context.gc_context);
         for i in 0..num_scripts {
             tunit.load_script(i as u32, context)?;
         }
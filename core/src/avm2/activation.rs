@@ -14,6 +14,7 @@ use crate::avm2::object::{
     ScriptObject, XmlListObject,
 };
 use crate::avm2::object::{Object, TObject};
+use crate::avm2::property::Property;
 use crate::avm2::scope::{search_scope_stack, Scope, ScopeChain};
 use crate::avm2::script::Script;
 use crate::avm2::value::Value;
@@ -72,6 +73,20 @@ impl<'gc> RegisterSet<'gc> {
     }
 }
 
+/// The `arguments` object or `...rest` array of a method, which hasn't been created yet.
+///
+/// Plenty of variadic methods never actually read it, so we only allocate the
+/// array once its register is first read.
+struct PendingArguments<'gc> {
+    /// The register the array will be stored in.
+    register: u32,
+
+    storage: ArrayStorage<'gc>,
+
+    /// The method being called, if this is an `arguments` object.
+    callee: Option<Object<'gc>>,
+}
+
 #[derive(Clone)]
 enum FrameControl<'gc> {
     Continue,
@@ -143,6 +158,9 @@ pub struct Activation<'a, 'gc: 'a> {
     /// Maximum size for the scope frame.
     max_scope_size: usize,
 
+    /// The `arguments` object or `...rest` array, until it's first read.
+    pending_arguments: Option<PendingArguments<'gc>>,
+
     pub context: UpdateContext<'a, 'gc>,
 }
 
@@ -177,6 +195,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
             scope_depth: context.avm2.scope_stack.len(),
             max_stack_size: 0,
             max_scope_size: 0,
+            pending_arguments: None,
             context,
         }
     }
@@ -205,6 +224,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
             scope_depth: context.avm2.scope_stack.len(),
             max_stack_size: 0,
             max_scope_size: 0,
+            pending_arguments: None,
             context,
         }
     }
@@ -246,6 +266,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
             scope_depth: context.avm2.scope_stack.len(),
             max_stack_size: max_stack as usize,
             max_scope_size: max_scope as usize,
+            pending_arguments: None,
             context,
         })
     }
@@ -437,6 +458,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
             scope_depth: context.avm2.scope_stack.len(),
             max_stack_size: body.max_stack as usize,
             max_scope_size: (body.max_scope_depth - body.init_scope_depth) as usize,
+            pending_arguments: None,
             context,
         };
 
@@ -484,25 +506,16 @@ impl<'a, 'gc> Activation<'a, 'gc> {
                 unreachable!();
             };
 
-            let args_object = ArrayObject::from_storage(&mut activation, args_array)?;
-
-            if method
+            let callee = method
                 .method()
                 .flags
                 .contains(AbcMethodFlags::NEED_ARGUMENTS)
-            {
-                args_object.set_string_property_local("callee", callee.into(), &mut activation)?;
-                args_object.set_local_property_is_enumerable(
-                    activation.context.gc_context,
-                    "callee".into(),
-                    false,
-                );
-            }
-
-            *activation
-                .local_registers
-                .get_mut(1 + num_declared_arguments)
-                .unwrap() = args_object.into();
+                .then_some(callee);
+            activation.pending_arguments = Some(PendingArguments {
+                register: 1 + num_declared_arguments,
+                storage: args_array,
+                callee,
+            });
         }
 
         Ok(activation)
@@ -535,6 +548,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
             scope_depth: context.avm2.scope_stack.len(),
             max_stack_size: 0,
             max_scope_size: 0,
+            pending_arguments: None,
             context,
         })
     }
@@ -557,7 +571,11 @@ impl<'a, 'gc> Activation<'a, 'gc> {
     }
 
     /// Retrieve a local register.
-    pub fn local_register(&self, id: u32) -> Result<Value<'gc>, Error<'gc>> {
+    pub fn local_register(&mut self, id: u32) -> Result<Value<'gc>, Error<'gc>> {
+        if matches!(&self.pending_arguments, Some(pending) if pending.register == id) {
+            self.create_arguments()?;
+        }
+
         self.local_registers
             .get(id)
             .cloned()
@@ -572,6 +590,10 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         id: u32,
         value: impl Into<Value<'gc>>,
     ) -> Result<(), Error<'gc>> {
+        if matches!(&self.pending_arguments, Some(pending) if pending.register == id) {
+            self.pending_arguments = None;
+        }
+
         if let Some(r) = self.local_registers.get_mut(id) {
             *r = value.into();
 
@@ -581,6 +603,26 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         }
     }
 
+    /// Creates the pending `arguments` object or `...rest` array, and stores it in its register.
+    fn create_arguments(&mut self) -> Result<(), Error<'gc>> {
+        let Some(pending) = self.pending_arguments.take() else {
+            return Ok(());
+        };
+
+        let args_object = ArrayObject::from_storage(self, pending.storage)?;
+        if let Some(callee) = pending.callee {
+            args_object.set_string_property_local("callee", callee.into(), self)?;
+            args_object.set_local_property_is_enumerable(
+                self.context.gc_context,
+                "callee".into(),
+                false,
+            );
+        }
+
+        *self.local_registers.get_mut(pending.register).unwrap() = args_object.into();
+        Ok(())
+    }
+
     /// Sets the outer scope of this activation
     pub fn set_outer(&mut self, new_outer: ScopeChain<'gc>) {
         self.outer = new_outer;
@@ -1248,7 +1290,8 @@ impl<'a, 'gc> Activation<'a, 'gc> {
     }
 
     fn op_get_local(&mut self, register_index: u32) -> Result<FrameControl<'gc>, Error<'gc>> {
-        self.push_stack(self.local_register(register_index)?);
+        let value = self.local_register(register_index)?;
+        self.push_stack(value);
         Ok(FrameControl::Continue)
     }
 
@@ -1438,6 +1481,19 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         Ok(FrameControl::Return(Value::Undefined))
     }
 
+    /// Resolves the trait a static multiname refers to on `object`, through
+    /// the inline cache `method` keeps for that multiname.
+    fn lookup_trait_cached(
+        &mut self,
+        method: Gc<'gc, BytecodeMethod<'gc>>,
+        index: Index<AbcMultiname>,
+        object: Object<'gc>,
+        multiname: &Multiname<'gc>,
+    ) -> Option<Property> {
+        let vtable = object.vtable()?;
+        BytecodeMethod::lookup_trait(method, self.context.gc_context, index.0, vtable, multiname)
+    }
+
     fn op_get_property(
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
@@ -1449,7 +1505,8 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         if !multiname.has_lazy_component() {
             let object = self.pop_stack();
             let object = object.coerce_to_object_or_typeerror(self, Some(&multiname))?;
-            let value = object.get_property(&multiname, self)?;
+            let property = self.lookup_trait_cached(method, index, object, &multiname);
+            let value = object.get_property_with_trait(&multiname, property, self)?;
             self.push_stack(value);
             return Ok(FrameControl::Continue);
         }
@@ -1497,7 +1554,8 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         if !multiname.has_lazy_component() {
             let object = self.pop_stack();
             let object = object.coerce_to_object_or_typeerror(self, Some(&multiname))?;
-            object.set_property(&multiname, value, self)?;
+            let property = self.lookup_trait_cached(method, index, object, &multiname);
+            object.set_property_with_trait(&multiname, property, value, self)?;
             return Ok(FrameControl::Continue);
         }
 
@@ -2115,7 +2173,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
                 self,
                 &format!(
                     "Error #1123: Filter operator not supported on type {}.",
-                    value.instance_of_class_name(self.context.gc_context)
+                    value.instance_of_class_name()
                 ),
                 1123,
             )?));
@@ -2129,8 +2187,10 @@ impl<'a, 'gc> Activation<'a, 'gc> {
 
         // TODO: Special handling required for `Date` and ECMA-357/E4X `XML`
         let sum_value = match (value1, value2) {
-            // note: with not-yet-guaranteed assumption that Integer < 1<<28, this won't overflow.
-            (Value::Integer(n1), Value::Integer(n2)) => (n1 + n2).into(),
+            (Value::Integer(n1), Value::Integer(n2)) => match n1.checked_add(n2) {
+                Some(sum) => sum.into(),
+                None => (n1 as f64 + n2 as f64).into(),
+            },
             (Value::Number(n1), Value::Number(n2)) => (n1 + n2).into(),
             (Value::String(s), value2) => Value::String(AvmString::concat(
                 self.context.gc_context,
@@ -2200,7 +2260,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         let value2 = self.pop_stack().coerce_to_i32(self)?;
         let value1 = self.pop_stack().coerce_to_i32(self)?;
 
-        self.push_stack(value1 + value2);
+        self.push_stack(value1.wrapping_add(value2));
 
         Ok(FrameControl::Continue)
     }
@@ -2251,7 +2311,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
     fn op_declocal_i(&mut self, index: u32) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value = self.local_register(index)?.coerce_to_i32(self)?;
 
-        self.set_local_register(index, value - 1)?;
+        self.set_local_register(index, value.wrapping_sub(1))?;
 
         Ok(FrameControl::Continue)
     }
@@ -2267,7 +2327,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
     fn op_decrement_i(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value = self.pop_stack().coerce_to_i32(self)?;
 
-        self.push_stack(value - 1);
+        self.push_stack(value.wrapping_sub(1));
 
         Ok(FrameControl::Continue)
     }
@@ -2292,7 +2352,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
     fn op_inclocal_i(&mut self, index: u32) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value = self.local_register(index)?.coerce_to_i32(self)?;
 
-        self.set_local_register(index, value + 1)?;
+        self.set_local_register(index, value.wrapping_add(1))?;
 
         Ok(FrameControl::Continue)
     }
@@ -2308,7 +2368,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
     fn op_increment_i(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value = self.pop_stack().coerce_to_i32(self)?;
 
-        self.push_stack(value + 1);
+        self.push_stack(value.wrapping_add(1));
 
         Ok(FrameControl::Continue)
     }
@@ -2344,7 +2404,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         let value2 = self.pop_stack().coerce_to_i32(self)?;
         let value1 = self.pop_stack().coerce_to_i32(self)?;
 
-        self.push_stack(value1 * value2);
+        self.push_stack(value1.wrapping_mul(value2));
 
         Ok(FrameControl::Continue)
     }
@@ -2360,7 +2420,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
     fn op_negate_i(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value1 = self.pop_stack().coerce_to_i32(self)?;
 
-        self.push_stack(-value1);
+        self.push_stack(value1.wrapping_neg());
 
         Ok(FrameControl::Continue)
     }
@@ -2379,8 +2439,10 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         let value1 = self.pop_stack();
 
         let sub_value: Value<'gc> = match (value1, value2) {
-            // note: with not-yet-guaranteed assumption that Integer < 1<<28, this won't underflow.
-            (Value::Integer(n1), Value::Integer(n2)) => (n1 - n2).into(),
+            (Value::Integer(n1), Value::Integer(n2)) => match n1.checked_sub(n2) {
+                Some(difference) => difference.into(),
+                None => (n1 as f64 - n2 as f64).into(),
+            },
             (Value::Number(n1), Value::Number(n2)) => (n1 - n2).into(),
             _ => {
                 let value2 = value2.coerce_to_number(self)?;
@@ -2398,7 +2460,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         let value2 = self.pop_stack().coerce_to_i32(self)?;
         let value1 = self.pop_stack().coerce_to_i32(self)?;
 
-        self.push_stack(value1 - value2);
+        self.push_stack(value1.wrapping_sub(value2));
 
         Ok(FrameControl::Continue)
     }
@@ -1,4 +1,4 @@
-use crate::avm2::bytearray::ByteArrayStorage;
+use crate::avm2::bytearray::{ByteArrayStorage, ObjectEncoding};
 use crate::avm2::object::{ByteArrayObject, TObject, VectorObject};
 use crate::avm2::vector::VectorStorage;
 use crate::avm2::ArrayObject;
@@ -6,8 +6,11 @@ use crate::avm2::ArrayStorage;
 use crate::avm2::{Activation, Error, Object, Value};
 use crate::string::AvmString;
 use enumset::EnumSet;
+use flash_lso::amf0::read::AMF0Decoder;
+use flash_lso::amf3::read::AMF3Decoder;
 use flash_lso::types::{AMFVersion, Element, Lso};
 use flash_lso::types::{Attribute, ClassDefinition, Value as AmfValue};
+use std::rc::Rc;
 
 /// Serialize a Value to an AmfValue
 pub fn serialize_value<'gc>(
@@ -64,7 +67,13 @@ pub fn serialize_value<'gc>(
                 }
             } else if let Some(vec) = o.as_vector_storage() {
                 let val_type = vec.value_type();
-                if val_type == Some(activation.avm2().classes().int) {
+                if let Some(ints) = vec.as_ints() {
+                    Some(AmfValue::VectorInt(ints.to_vec(), vec.is_fixed()))
+                } else if let Some(uints) = vec.as_uints() {
+                    Some(AmfValue::VectorUInt(uints.to_vec(), vec.is_fixed()))
+                } else if let Some(numbers) = vec.as_numbers() {
+                    Some(AmfValue::VectorDouble(numbers.to_vec(), vec.is_fixed()))
+                } else if val_type == Some(activation.avm2().classes().int) {
                     let int_vec: Vec<_> = vec
                         .iter()
                         .map(|v| {
@@ -166,6 +175,89 @@ pub fn recursive_serialize<'gc>(
     Ok(())
 }
 
+/// Encodes a value as `writeObject` does, without any header.
+///
+/// Returns `None` if the value can't be serialized.
+pub fn write_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    value: Value<'gc>,
+    encoding: ObjectEncoding,
+) -> Result<Option<Vec<u8>>, Error<'gc>> {
+    let amf_version = match encoding {
+        ObjectEncoding::Amf0 => AMFVersion::AMF0,
+        ObjectEncoding::Amf3 => AMFVersion::AMF3,
+    };
+    let Some(amf) = serialize_value(activation, value, amf_version) else {
+        return Ok(None);
+    };
+
+    let element = Element::new("", amf);
+    let mut lso = Lso::new(vec![element], "", amf_version);
+    let bytes =
+        flash_lso::write::write_to_bytes(&mut lso).map_err(|_| "Failed to serialize object")?;
+    // This is kind of hacky: We need to strip out the header and any padding so that we only write
+    // the value. In the future, there should be a method to do this in the flash_lso crate.
+    let element_padding = match amf_version {
+        AMFVersion::AMF0 => 8,
+        AMFVersion::AMF3 => 7,
+    };
+    Ok(Some(
+        bytes[flash_lso::write::header_length(&lso.header) + element_padding..bytes.len() - 1]
+            .to_vec(),
+    ))
+}
+
+/// Decodes a value from the start of `bytes` as `readObject` does.
+///
+/// Returns the value, and how many bytes it took up.
+pub fn read_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    bytes: &[u8],
+    encoding: ObjectEncoding,
+) -> Result<(Value<'gc>, usize), Error<'gc>> {
+    let (amf, read) = parse_object(bytes, encoding)?;
+    Ok((deserialize_value(activation, &amf)?, read))
+}
+
+/// Parses a value from the start of `bytes` like [`read_object`], without creating it yet.
+///
+/// This lets callers release `bytes` before the value is created, which may run constructors.
+pub fn parse_object<'gc>(
+    bytes: &[u8],
+    encoding: ObjectEncoding,
+) -> Result<(Rc<AmfValue>, usize), Error<'gc>> {
+    let (bytes_left, amf) = match encoding {
+        ObjectEncoding::Amf0 => {
+            let mut decoder = AMF0Decoder::default();
+            let (extra, amf) = decoder
+                .parse_single_element(bytes)
+                .map_err(|_| "Error: Invalid object")?;
+            (extra.len(), amf.into())
+        }
+        ObjectEncoding::Amf3 => {
+            let mut decoder = AMF3Decoder::default();
+            let (extra, amf) = decoder
+                .parse_single_element(bytes)
+                .map_err(|_| "Error: Invalid object")?;
+            (extra.len(), amf.into())
+        }
+    };
+    Ok((amf, bytes.len() - bytes_left))
+}
+
+/// Deserialize a single AMF3-encoded value, such as the metadata of a PlaceObject4 tag.
+///
+/// Returns `undefined` if the data can't be parsed.
+pub fn deserialize_amf3_bytes<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    bytes: &[u8],
+) -> Result<Value<'gc>, Error<'gc>> {
+    match AMF3Decoder::default().parse_single_element(bytes) {
+        Ok((_, amf)) => deserialize_value(activation, &amf),
+        Err(_) => Ok(Value::Undefined),
+    }
+}
+
 /// Deserialize a AmfValue to a Value
 pub fn deserialize_value<'gc>(
     activation: &mut Activation<'_, 'gc>,
@@ -251,27 +343,15 @@ pub fn deserialize_value<'gc>(
             )?
             .into(),
         AmfValue::VectorDouble(vec, is_fixed) => {
-            let storage = VectorStorage::from_values(
-                vec.iter().map(|v| (*v).into()).collect(),
-                *is_fixed,
-                Some(activation.avm2().classes().number),
-            );
+            let storage = VectorStorage::from_numbers(vec.clone(), *is_fixed, activation);
             VectorObject::from_vector(storage, activation)?.into()
         }
         AmfValue::VectorUInt(vec, is_fixed) => {
-            let storage = VectorStorage::from_values(
-                vec.iter().map(|v| (*v).into()).collect(),
-                *is_fixed,
-                Some(activation.avm2().classes().uint),
-            );
+            let storage = VectorStorage::from_uints(vec.clone(), *is_fixed, activation);
             VectorObject::from_vector(storage, activation)?.into()
         }
         AmfValue::VectorInt(vec, is_fixed) => {
-            let storage = VectorStorage::from_values(
-                vec.iter().map(|v| (*v).into()).collect(),
-                *is_fixed,
-                Some(activation.avm2().classes().int),
-            );
+            let storage = VectorStorage::from_ints(vec.clone(), *is_fixed, activation);
             VectorObject::from_vector(storage, activation)?.into()
         }
         AmfValue::VectorObject(vec, ty_name, is_fixed) => {
@@ -285,6 +365,7 @@ pub fn deserialize_value<'gc>(
                     .collect::<Result<Vec<_>, _>>()?,
                 *is_fixed,
                 Some(activation.avm2().classes().object),
+                activation,
             );
             VectorObject::from_vector(storage, activation)?.into()
         }
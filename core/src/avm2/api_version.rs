@@ -0,0 +1,181 @@
+//! API versions of the player globals.
+//!
+//! Definitions added to the player globals by later Flash Player releases are only visible to
+//! movies made for those releases. Like avmplus, our player globals mark the namespaces of such
+//! definitions with the version that introduced them, by appending a private use character to
+//! the namespace URI. Everything else in the player sees the API of the root movie's version,
+//! unless the emulated Flash Player release is older than that.
+
+use crate::player_version::PlayerVersion;
+use gc_arena::Collect;
+use num_traits::FromPrimitive;
+
+/// The character that marks the first versioned API, `FP_9_0`.
+const MIN_API_MARK: u32 = 0xE000;
+
+#[allow(non_camel_case_types)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Collect, FromPrimitive)]
+#[collect(require_static)]
+pub enum ApiVersion {
+    /// Not tied to any version. Definitions in such namespaces are visible to every version,
+    /// and names in them see definitions of every version.
+    ///
+    /// This is used by the unmarked namespaces of our player globals, and by namespaces made
+    /// by Ruffle itself.
+    AllVersions,
+    FP_9_0,
+    AIR_1_0,
+    FP_10_0,
+    AIR_1_5,
+    AIR_1_5_1,
+    FP_10_0_32,
+    AIR_1_5_2,
+    FP_10_1,
+    AIR_2_0,
+    AIR_2_5,
+    FP_10_2,
+    AIR_2_6,
+    SWF_12,
+    AIR_2_7,
+    SWF_13,
+    AIR_3_0,
+    SWF_14,
+    AIR_3_1,
+    SWF_15,
+    AIR_3_2,
+    SWF_16,
+    AIR_3_3,
+    SWF_17,
+    AIR_3_4,
+    SWF_18,
+    AIR_3_5,
+    SWF_19,
+    AIR_3_6,
+    SWF_20,
+    AIR_3_7,
+    SWF_21,
+    AIR_3_8,
+    SWF_22,
+    AIR_3_9,
+    SWF_23,
+    AIR_4_0,
+    SWF_24,
+    AIR_13_0,
+    SWF_25,
+    AIR_14_0,
+    SWF_26,
+    AIR_15_0,
+    SWF_27,
+    AIR_16_0,
+    SWF_28,
+    AIR_17_0,
+    SWF_29,
+    AIR_18_0,
+    SWF_30,
+    AIR_19_0,
+    SWF_31,
+    AIR_20_0,
+    SWF_32,
+    AIR_21_0,
+    SWF_33,
+    AIR_22_0,
+    SWF_34,
+    AIR_23_0,
+    SWF_35,
+    AIR_24_0,
+    SWF_36,
+    AIR_25_0,
+    SWF_37,
+    AIR_26_0,
+    SWF_38,
+    AIR_27_0,
+    SWF_39,
+    AIR_28_0,
+    SWF_40,
+    AIR_29_0,
+    SWF_41,
+    AIR_30_0,
+    SWF_42,
+    AIR_31_0,
+    SWF_43,
+    AIR_32_0,
+    SWF_44,
+}
+
+impl ApiVersion {
+    /// The API that movies of the given SWF version see.
+    pub fn from_swf_version(swf_version: u8) -> Self {
+        match swf_version {
+            0..=9 => ApiVersion::FP_9_0,
+            10 => ApiVersion::FP_10_1,
+            11 => ApiVersion::FP_10_2,
+            // From here on, every SWF version comes after the AIR version before it.
+            _ => {
+                let swf_version = u32::from(swf_version.min(44));
+                Self::from_u32(ApiVersion::SWF_12 as u32 + 2 * (swf_version - 12))
+                    .unwrap_or(ApiVersion::SWF_44)
+            }
+        }
+    }
+
+    /// The API of the given Flash Player release.
+    pub fn from_player_version(player_version: PlayerVersion) -> Self {
+        match (player_version.major, player_version.minor) {
+            (0..=9, _) => ApiVersion::FP_9_0,
+            (10, 0) => ApiVersion::FP_10_0,
+            (10, 1) => ApiVersion::FP_10_1,
+            (10, 2) => ApiVersion::FP_10_2,
+            (10, _) => ApiVersion::SWF_12,
+            // Each release from 11.0 on came with a new SWF version.
+            (11, minor) => Self::from_swf_version(13 + minor.min(9)),
+            (major, _) => Self::from_swf_version(major.saturating_add(11)),
+        }
+    }
+
+    /// The version marked by a character at the end of a namespace URI, if it's a marker.
+    pub fn from_mark(mark: u16) -> Option<Self> {
+        let offset = u32::from(mark).checked_sub(MIN_API_MARK)?;
+        Self::from_u32(offset + ApiVersion::FP_9_0 as u32)
+    }
+
+    /// Whether a definition made in a namespace of version `self` can be seen by a name in a
+    /// namespace of version `lookup`.
+    pub fn is_visible_to(self, lookup: ApiVersion) -> bool {
+        self == ApiVersion::AllVersions || lookup == ApiVersion::AllVersions || self <= lookup
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ApiVersion;
+    use crate::player_version::PlayerVersion;
+
+    #[test]
+    fn api_versions() {
+        assert_eq!(ApiVersion::from_swf_version(9), ApiVersion::FP_9_0);
+        assert_eq!(ApiVersion::from_swf_version(13), ApiVersion::SWF_13);
+        assert_eq!(ApiVersion::from_swf_version(25), ApiVersion::SWF_25);
+        assert_eq!(ApiVersion::from_swf_version(44), ApiVersion::SWF_44);
+        assert_eq!(ApiVersion::from_swf_version(50), ApiVersion::SWF_44);
+        assert_eq!(
+            ApiVersion::from_player_version(PlayerVersion::new(10, 3)),
+            ApiVersion::SWF_12
+        );
+        assert_eq!(
+            ApiVersion::from_player_version(PlayerVersion::new(11, 4)),
+            ApiVersion::SWF_17
+        );
+        assert_eq!(
+            ApiVersion::from_player_version(PlayerVersion::new(32, 0)),
+            ApiVersion::SWF_43
+        );
+        assert_eq!(ApiVersion::from_mark(0xE00E), Some(ApiVersion::SWF_13));
+        assert_eq!(ApiVersion::from_mark(0xE000), Some(ApiVersion::FP_9_0));
+        assert_eq!(ApiVersion::from_mark(0x41), None);
+
+        assert!(ApiVersion::SWF_13.is_visible_to(ApiVersion::SWF_17));
+        assert!(!ApiVersion::SWF_17.is_visible_to(ApiVersion::SWF_13));
+        assert!(ApiVersion::SWF_17.is_visible_to(ApiVersion::AllVersions));
+        assert!(ApiVersion::AllVersions.is_visible_to(ApiVersion::FP_9_0));
+    }
+}
@@ -2,9 +2,11 @@ use crate::avm2::error::{eof_error, range_error};
 use crate::avm2::Activation;
 use crate::avm2::Error;
 use crate::string::{FromWStr, WStr};
+use encoding_rs::{Encoding, UTF_8};
 use flate2::read::*;
 use flate2::Compression;
 use gc_arena::Collect;
+use std::borrow::Cow;
 use std::cell::Cell;
 use std::cmp;
 use std::fmt::{self, Display, Formatter};
@@ -55,6 +57,53 @@ impl ByteArrayError {
     }
 }
 
+/// Strips what `readUTFBytes` and `readUTF` leave out of the bytes they read:
+/// - an optional UTF8 BOM at the beginning
+/// - everything from the first null byte on, to recreate a bug in FP
+pub fn trim_utf_bytes(mut bytes: &[u8]) -> &[u8] {
+    if let Some(without_bom) = bytes.strip_prefix(&[0xEF, 0xBB, 0xBF]) {
+        bytes = without_bom;
+    }
+    if let Some(null) = bytes.iter().position(|b| *b == b'\0') {
+        bytes = &bytes[..null];
+    }
+    bytes
+}
+
+/// Encodes a string as `writeUTF` does, prefixed with its length as an unsigned short.
+pub fn encode_utf(utf_string: &str, endian: Endian) -> Result<Vec<u8>, ByteArrayError> {
+    let Ok(str_size) = u16::try_from(utf_string.len()) else {
+        return Err(ByteArrayError::IndexOutOfBounds);
+    };
+    let mut bytes = match endian {
+        Endian::Big => str_size.to_be_bytes(),
+        Endian::Little => str_size.to_le_bytes(),
+    }
+    .to_vec();
+    bytes.extend_from_slice(utf_string.as_bytes());
+    Ok(bytes)
+}
+
+/// Decodes the bytes read by `readMultiByte`, from the character set with the given label,
+/// or UTF-8 if it's unknown.
+pub fn decode_multi_byte<'a>(mut bytes: &'a [u8], charset_label: &str) -> Cow<'a, str> {
+    // Flash cuts off the string at the first null byte (after checking that
+    // the original length was available)
+    if let Some(null) = bytes.iter().position(|b| *b == b'\0') {
+        bytes = &bytes[..null];
+    }
+
+    let encoder = Encoding::for_label(charset_label.as_bytes()).unwrap_or(UTF_8);
+    encoder.decode(bytes).0
+}
+
+/// Encodes a string as `writeMultiByte` does, in the character set with the given label,
+/// or UTF-8 if it's unknown.
+pub fn encode_multi_byte<'a>(string: &'a str, charset_label: &str) -> Cow<'a, [u8]> {
+    let encoder = Encoding::for_label(charset_label.as_bytes()).unwrap_or(UTF_8);
+    encoder.encode(string).0
+}
+
 impl Display for CompressionAlgorithm {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         let s = match *self {
@@ -154,14 +203,7 @@ impl ByteArrayStorage {
     /// - cuts the result at the first null byte to recreate a bug in FP
     /// - strips off an optional UTF8 BOM at the beginning
     pub fn read_utf_bytes(&self, amnt: usize) -> Result<&[u8], ByteArrayError> {
-        let mut bytes = self.read_bytes(amnt)?;
-        if let Some(without_bom) = bytes.strip_prefix(&[0xEF, 0xBB, 0xBF]) {
-            bytes = without_bom;
-        }
-        if let Some(null) = bytes.iter().position(|b| *b == b'\0') {
-            bytes = &bytes[..null];
-        }
-        Ok(bytes)
+        Ok(trim_utf_bytes(self.read_bytes(amnt)?))
     }
 
     /// Reads any amount of bytes at any offset in the ByteArray
@@ -302,12 +344,8 @@ impl ByteArrayStorage {
 
     // Writes a UTF String into the buffer, with its length as a prefix
     pub fn write_utf(&mut self, utf_string: &str) -> Result<(), ByteArrayError> {
-        if let Ok(str_size) = u16::try_from(utf_string.len()) {
-            self.write_unsigned_short(str_size)?;
-            self.write_bytes(utf_string.as_bytes())
-        } else {
-            Err(ByteArrayError::IndexOutOfBounds)
-        }
+        let bytes = encode_utf(utf_string, self.endian)?;
+        self.write_bytes(&bytes)
     }
 
     #[inline]
@@ -77,6 +77,12 @@ pub struct Class<'gc> {
     /// The name of the class.
     name: QName<'gc>,
 
+    /// The name of the class, formatted by `QName::to_qualified_name`.
+    ///
+    /// This is asked for by every `getQualifiedClassName`, `describeType` and
+    /// error message naming the class, so we only format it once.
+    qualified_name: AvmString<'gc>,
+
     /// The type parameter for this class (only supported for Vector)
     param: Option<Option<GcCell<'gc, Class<'gc>>>>,
 
@@ -202,6 +208,7 @@ impl<'gc> Class<'gc> {
             mc,
             Self {
                 name,
+                qualified_name: name.to_qualified_name(mc),
                 param: None,
                 super_class,
                 attributes: ClassAttributes::empty(),
@@ -256,7 +263,7 @@ impl<'gc> Class<'gc> {
             .expect("Vector.<*> not initialized?");
 
         let param = param.expect("Trying to create Vector<*>, which shouldn't happen here");
-        let name = format!("Vector.<{}>", param.read().name().to_qualified_name(mc));
+        let name = format!("Vector.<{}>", param.read().qualified_name());
 
         let new_class = Self::new(
             // FIXME - we should store a `Multiname` instead of a `QName`, and use the
@@ -384,6 +391,7 @@ impl<'gc> Class<'gc> {
             activation.context.gc_context,
             Self {
                 name,
+                qualified_name: name.to_qualified_name(activation.context.gc_context),
                 param: None,
                 super_class,
                 attributes,
@@ -476,7 +484,7 @@ impl<'gc> Class<'gc> {
                         let my_name = instance_trait.name();
 
                         let names_match = super_name.local_name() == my_name.local_name()
-                            && (super_name.namespace() == my_name.namespace()
+                            && (my_name.namespace().matches_ns(super_name.namespace())
                                 || (is_protected
                                     && read.protected_namespace() == Some(super_name.namespace())));
                         if names_match {
@@ -535,10 +543,13 @@ impl<'gc> Class<'gc> {
             )?);
         }
 
+        let name = QName::new(activation.avm2().public_namespace, name);
+
         Ok(GcCell::new(
             activation.context.gc_context,
             Self {
-                name: QName::new(activation.avm2().public_namespace, name),
+                name,
+                qualified_name: name.to_qualified_name(activation.context.gc_context),
                 param: None,
                 super_class: None,
                 attributes: ClassAttributes::empty(),
@@ -575,8 +586,14 @@ impl<'gc> Class<'gc> {
         self.name
     }
 
-    pub fn set_name(&mut self, name: QName<'gc>) {
+    /// The name of the class, including its namespace URI, as `to_qualified_name` formats it.
+    pub fn qualified_name(&self) -> AvmString<'gc> {
+        self.qualified_name
+    }
+
+    pub fn set_name(&mut self, name: QName<'gc>, mc: &Mutation<'gc>) {
         self.name = name;
+        self.qualified_name = name.to_qualified_name(mc);
     }
 
     pub fn set_param(&mut self, param: Option<Option<GcCell<'gc, Class<'gc>>>>) {
@@ -70,7 +70,11 @@ impl<'gc> Domain<'gc> {
     }
 
     pub fn is_playerglobals_domain(&self, activation: &mut Activation<'_, 'gc>) -> bool {
-        activation.avm2().playerglobals_domain.0.as_ptr() == self.0.as_ptr()
+        Self::ptr_eq(activation.avm2().playerglobals_domain, *self)
+    }
+
+    pub fn ptr_eq(a: Domain<'gc>, b: Domain<'gc>) -> bool {
+        a.0.as_ptr() == b.0.as_ptr()
     }
 
     /// Create a new domain with a given parent.
@@ -232,6 +232,12 @@ impl<'gc> E4XNode<'gc> {
     }
 
     pub fn deep_copy(&self, mc: &Mutation<'gc>) -> Self {
+        self.deep_copy_with_parent(None, mc)
+    }
+
+    /// Copies this node and its descendants, giving the copy `parent` right away
+    /// rather than setting it on every copied node afterwards.
+    fn deep_copy_with_parent(&self, parent: Option<Self>, mc: &Mutation<'gc>) -> Self {
         let this = self.0.read();
 
         let kind = match &this.kind {
@@ -242,19 +248,16 @@ impl<'gc> E4XNode<'gc> {
                 E4XNodeKind::ProcessingInstruction(*string)
             }
             E4XNodeKind::Attribute(string) => E4XNodeKind::Attribute(*string),
-            E4XNodeKind::Element {
-                attributes,
-                children,
-            } => E4XNodeKind::Element {
-                attributes: attributes.iter().map(|attr| attr.deep_copy(mc)).collect(),
-                children: children.iter().map(|child| child.deep_copy(mc)).collect(),
+            E4XNodeKind::Element { .. } => E4XNodeKind::Element {
+                attributes: vec![],
+                children: vec![],
             },
         };
 
         let node = E4XNode(GcCell::new(
             mc,
             E4XNodeData {
-                parent: None,
+                parent,
                 namespace: this.namespace,
                 local_name: this.local_name,
                 kind,
@@ -264,17 +267,20 @@ impl<'gc> E4XNode<'gc> {
         if let E4XNodeKind::Element {
             attributes,
             children,
-        } = &mut node.0.write(mc).kind
+        } = &this.kind
         {
-            for attr in attributes.iter_mut() {
-                let mut data = attr.0.write(mc);
-                data.parent = Some(node);
-            }
-
-            for child in children.iter_mut() {
-                let mut data = child.0.write(mc);
-                data.parent = Some(node);
-            }
+            let attributes = attributes
+                .iter()
+                .map(|attr| attr.deep_copy_with_parent(Some(node), mc))
+                .collect();
+            let children = children
+                .iter()
+                .map(|child| child.deep_copy_with_parent(Some(node), mc))
+                .collect();
+            node.0.write(mc).kind = E4XNodeKind::Element {
+                attributes,
+                children,
+            };
         }
 
         node
@@ -662,6 +668,16 @@ impl<'gc> E4XNode<'gc> {
                 }
                 Event::End(_) => {
                     let node = open_tags.pop().unwrap();
+                    // Large documents have many elements, so don't keep the spare
+                    // capacity their lists grew while being parsed.
+                    if let E4XNodeKind::Element {
+                        attributes,
+                        children,
+                    } = &mut *node.kind_mut(activation.context.gc_context)
+                    {
+                        attributes.shrink_to_fit();
+                        children.shrink_to_fit();
+                    }
                     if open_tags.is_empty() {
                         top_level.push(node);
                     }
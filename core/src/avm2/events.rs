@@ -2,14 +2,14 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::error::type_error;
-use crate::avm2::object::{Object, TObject};
+use crate::avm2::object::{FunctionObject, FunctionObjectWeak, Object, ObjectPtr, TObject};
 use crate::avm2::value::Value;
 use crate::avm2::Error;
 use crate::avm2::Multiname;
 use crate::display_object::TDisplayObject;
 use crate::string::AvmString;
 use fnv::FnvHashMap;
-use gc_arena::Collect;
+use gc_arena::{Collect, GcCell, Mutation};
 use std::collections::BTreeMap;
 use std::hash::{Hash, Hasher};
 
@@ -228,14 +228,19 @@ impl<'gc> DispatchList<'gc> {
     /// more than one priority (since we can't enforce that with clever-er data
     /// structure selection). If an event handler already exists, it will not
     /// be added again, and this function will silently fail.
+    ///
+    /// If `use_weak_reference` is set, the dispatch list will not keep the
+    /// handler alive. Handlers that have been collected are silently dropped
+    /// from the list the next time it is iterated.
     pub fn add_event_listener(
         &mut self,
         event: impl Into<AvmString<'gc>> + Clone,
         priority: i32,
         handler: Object<'gc>,
         use_capture: bool,
+        use_weak_reference: bool,
     ) {
-        let new_handler = EventHandler::new(handler, use_capture);
+        let new_handler = EventHandler::new(handler, use_capture, use_weak_reference);
 
         if let Some(event_sheaf) = self.get_event(event.clone()) {
             for (_other_prio, other_set) in event_sheaf.iter() {
@@ -259,7 +264,7 @@ impl<'gc> DispatchList<'gc> {
         handler: Object<'gc>,
         use_capture: bool,
     ) {
-        let old_handler = EventHandler::new(handler, use_capture);
+        let old_handler = EventHandler::new(handler, use_capture, false);
 
         for (_prio, set) in self.get_event_mut(event).iter_mut() {
             if let Some(pos) = set.iter().position(|h| *h == old_handler) {
@@ -269,10 +274,13 @@ impl<'gc> DispatchList<'gc> {
     }
 
     /// Determine if there are any event listeners in this dispatch list.
+    ///
+    /// Weakly-held listeners that have already been collected are not
+    /// counted.
     pub fn has_event_listener(&self, event: impl Into<AvmString<'gc>>) -> bool {
         if let Some(event_sheaf) = self.get_event(event) {
             for (_prio, set) in event_sheaf.iter() {
-                if !set.is_empty() {
+                if set.iter().any(|h| !h.handler.is_dropped()) {
                     return true;
                 }
             }
@@ -286,20 +294,33 @@ impl<'gc> DispatchList<'gc> {
     /// Event handlers will be yielded in the order they are intended to be
     /// executed.
     ///
+    /// Higher priorities run first; handlers of equal priority run in the
+    /// order they were added.
+    ///
     /// `use_capture` indicates if you want handlers that execute during the
     /// capture phase, or handlers that execute during the bubble and target
     /// phases.
+    ///
+    /// Any weakly-held handlers that have been collected since the last
+    /// dispatch are removed from the list.
     pub fn iter_event_handlers<'a>(
         &'a mut self,
+        mc: &'a Mutation<'gc>,
         event: impl Into<AvmString<'gc>>,
         use_capture: bool,
     ) -> impl 'a + Iterator<Item = Object<'gc>> {
-        self.get_event_mut(event)
+        let event_sheaf = self.get_event_mut(event);
+
+        for set in event_sheaf.values_mut() {
+            set.retain(|eh| !eh.handler.is_dropped());
+        }
+
+        event_sheaf
             .iter()
             .rev()
             .flat_map(|(_p, v)| v.iter())
             .filter(move |eh| eh.use_capture == use_capture)
-            .map(|eh| eh.handler)
+            .filter_map(move |eh| eh.handler.upgrade(mc))
     }
 }
 
@@ -309,12 +330,59 @@ impl<'gc> Default for DispatchList<'gc> {
     }
 }
 
+/// A reference to an event handler function, which may or may not keep the
+/// handler alive.
+#[derive(Clone, Copy, Collect)]
+#[collect(no_drop)]
+enum HandlerRef<'gc> {
+    /// A handler that is kept alive for as long as it is registered.
+    Strong(Object<'gc>),
+
+    /// A handler registered with `useWeakReference`.
+    ///
+    /// Only function objects can be held weakly; any other callable is held
+    /// strongly instead.
+    Weak(FunctionObjectWeak<'gc>),
+}
+
+impl<'gc> HandlerRef<'gc> {
+    fn new(handler: Object<'gc>, use_weak_reference: bool) -> Self {
+        match handler.as_function_object() {
+            Some(function) if use_weak_reference => {
+                Self::Weak(FunctionObjectWeak(GcCell::downgrade(function.0)))
+            }
+            _ => Self::Strong(handler),
+        }
+    }
+
+    fn as_ptr(&self) -> *const ObjectPtr {
+        match self {
+            Self::Strong(handler) => handler.as_ptr(),
+            Self::Weak(handler) => handler.0.as_ptr() as *const ObjectPtr,
+        }
+    }
+
+    fn is_dropped(&self) -> bool {
+        match self {
+            Self::Strong(_) => false,
+            Self::Weak(handler) => handler.0.is_dropped(),
+        }
+    }
+
+    fn upgrade(&self, mc: &Mutation<'gc>) -> Option<Object<'gc>> {
+        match self {
+            Self::Strong(handler) => Some(*handler),
+            Self::Weak(handler) => handler.0.upgrade(mc).map(|f| FunctionObject(f).into()),
+        }
+    }
+}
+
 /// A single instance of an event handler.
 #[derive(Clone, Collect)]
 #[collect(no_drop)]
 struct EventHandler<'gc> {
     /// The event handler to call.
-    handler: Object<'gc>,
+    handler: HandlerRef<'gc>,
 
     /// Indicates if this handler should only be called for capturing events
     /// (when `true`), or if it should only be called for bubbling and
@@ -323,9 +391,9 @@ struct EventHandler<'gc> {
 }
 
 impl<'gc> EventHandler<'gc> {
-    fn new(handler: Object<'gc>, use_capture: bool) -> Self {
+    fn new(handler: Object<'gc>, use_capture: bool, use_weak_reference: bool) -> Self {
         Self {
-            handler,
+            handler: HandlerRef::new(handler, use_weak_reference),
             use_capture,
         }
     }
@@ -333,7 +401,7 @@ impl<'gc> EventHandler<'gc> {
 
 impl<'gc> PartialEq for EventHandler<'gc> {
     fn eq(&self, rhs: &Self) -> bool {
-        self.use_capture == rhs.use_capture && Object::ptr_eq(self.handler, rhs.handler)
+        self.use_capture == rhs.use_capture && self.handler.as_ptr() == rhs.handler.as_ptr()
     }
 }
 
@@ -364,6 +432,46 @@ pub fn parent_of(target: Object<'_>) -> Option<Object<'_>> {
     None
 }
 
+/// Determine if dispatching an event of the given type to `target` would call
+/// any handlers, either on the target itself or on one of its ancestors.
+pub fn will_trigger<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    target: Object<'gc>,
+    event_type: AvmString<'gc>,
+) -> Result<bool, Error<'gc>> {
+    let mut dispatcher = Some(target);
+    while let Some(this) = dispatcher {
+        let dispatch_list = this
+            .get_property(
+                &Multiname::new(activation.avm2().flash_events_internal, "_dispatchList"),
+                activation,
+            )?
+            .as_object();
+
+        // Objects with no dispatch list act as if they had an empty one
+        if let Some(dispatch_list) = dispatch_list {
+            if dispatch_list
+                .as_dispatch_mut(activation.context.gc_context)
+                .ok_or_else(|| Error::from("Internal dispatch list is missing during dispatch!"))?
+                .has_event_listener(event_type)
+            {
+                return Ok(true);
+            }
+        }
+
+        let target = this
+            .get_property(
+                &Multiname::new(activation.avm2().flash_events_internal, "_target"),
+                activation,
+            )?
+            .as_object()
+            .unwrap_or(this);
+        dispatcher = parent_of(target);
+    }
+
+    Ok(false)
+}
+
 /// Call all of the event handlers on a given target.
 ///
 /// The `target` is the current target of the `event`. `event` must be a valid
@@ -406,7 +514,7 @@ pub fn dispatch_event_to_target<'gc>(
     let handlers: Vec<Object<'gc>> = dispatch_list
         .as_dispatch_mut(activation.context.gc_context)
         .ok_or_else(|| Error::from("Internal dispatch list is missing during dispatch!"))?
-        .iter_event_handlers(name, use_capture)
+        .iter_event_handlers(activation.context.gc_context, name, use_capture)
         .collect();
 
     for handler in handlers.iter() {
@@ -256,7 +256,7 @@ pub fn display_function<'gc>(
 ) {
     let class_def = superclass.map(|superclass| {
         let class_def = superclass.inner_class_definition();
-        let name = class_def.read().name().to_qualified_name_no_mc();
+        let name = class_def.read().qualified_name();
         output.push_str(&name);
         class_def
     });
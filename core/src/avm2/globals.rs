@@ -94,6 +94,7 @@ pub struct SystemClasses<'gc> {
     pub graphicsstroke: ClassObject<'gc>,
     pub loader: ClassObject<'gc>,
     pub loaderinfo: ClassObject<'gc>,
+    pub nativewindow: ClassObject<'gc>,
     pub bytearray: ClassObject<'gc>,
     pub stage: ClassObject<'gc>,
     pub sprite: ClassObject<'gc>,
@@ -114,6 +115,7 @@ pub struct SystemClasses<'gc> {
     pub httpstatusevent: ClassObject<'gc>,
     pub textevent: ClassObject<'gc>,
     pub errorevent: ClassObject<'gc>,
+    pub asyncerrorevent: ClassObject<'gc>,
     pub ioerrorevent: ClassObject<'gc>,
     pub securityerrorevent: ClassObject<'gc>,
     pub transform: ClassObject<'gc>,
@@ -145,6 +147,7 @@ pub struct SystemClasses<'gc> {
     pub vertexbuffer3d: ClassObject<'gc>,
     pub program3d: ClassObject<'gc>,
     pub urlvariables: ClassObject<'gc>,
+    pub urlrequestheader: ClassObject<'gc>,
     pub bevelfilter: ClassObject<'gc>,
     pub bitmapfilter: ClassObject<'gc>,
     pub blurfilter: ClassObject<'gc>,
@@ -221,6 +224,7 @@ impl<'gc> SystemClasses<'gc> {
             graphicsstroke: object,
             loader: object,
             loaderinfo: object,
+            nativewindow: object,
             bytearray: object,
             stage: object,
             sprite: object,
@@ -239,6 +243,7 @@ impl<'gc> SystemClasses<'gc> {
             httpstatusevent: object,
             textevent: object,
             errorevent: object,
+            asyncerrorevent: object,
             ioerrorevent: object,
             securityerrorevent: object,
             transform: object,
@@ -270,6 +275,7 @@ impl<'gc> SystemClasses<'gc> {
             vertexbuffer3d: object,
             program3d: object,
             urlvariables: object,
+            urlrequestheader: object,
             bevelfilter: object,
             bitmapfilter: object,
             blurfilter: object,
@@ -363,7 +369,7 @@ fn class<'gc>(
                 format!(
                     "Could not resolve superclass {} when defining global class {}",
                     sc_name.to_qualified_name(mc),
-                    class_read.name().to_qualified_name(mc)
+                    class_read.qualified_name()
                 )
                 .into()
             });
@@ -743,6 +749,7 @@ fn load_playerglobal<'gc>(
             ("flash.display", "LoaderInfo", loaderinfo),
             ("flash.display", "MorphShape", morphshape),
             ("flash.display", "MovieClip", movieclip),
+            ("flash.display", "NativeWindow", nativewindow),
             ("flash.display", "ShaderInput", shaderinput),
             ("flash.display", "ShaderParameter", shaderparameter),
             ("flash.display", "Shape", shape),
@@ -772,6 +779,7 @@ fn load_playerglobal<'gc>(
             ("flash.events", "EventDispatcher", eventdispatcher),
             ("flash.events", "TextEvent", textevent),
             ("flash.events", "ErrorEvent", errorevent),
+            ("flash.events", "AsyncErrorEvent", asyncerrorevent),
             ("flash.events", "KeyboardEvent", keyboardevent),
             ("flash.events", "ProgressEvent", progressevent),
             ("flash.events", "HTTPStatusEvent", httpstatusevent),
@@ -792,6 +800,7 @@ fn load_playerglobal<'gc>(
             ("flash.media", "SoundTransform", soundtransform),
             ("flash.media", "Video", video),
             ("flash.net", "URLVariables", urlvariables),
+            ("flash.net", "URLRequestHeader", urlrequestheader),
             ("flash.utils", "ByteArray", bytearray),
             ("flash.system", "ApplicationDomain", application_domain),
             ("flash.text", "StaticText", statictext),
@@ -1,4 +1,5 @@
 package {
+    [API("674")]
     public final class JSON {
         public static native function parse(text:String, reviver:Function = null): Object;
         public static native function stringify(value:Object, replacer:* = null, space:* = null): String;
@@ -6,7 +6,7 @@ use crate::avm2::parameters::ParametersExt;
 use crate::avm2::property::Property;
 use crate::avm2::ClassObject;
 
-use crate::avm2::{Activation, Error, Object, Value};
+use crate::avm2::{Activation, Error, Namespace, Object, Value};
 use crate::avm2_stub_method;
 
 // Implements `avmplus.describeTypeJSON`
@@ -36,9 +36,7 @@ pub fn describe_type_json<'gc>(
     let class = class_obj.inner_class_definition();
     let class = class.read();
 
-    let qualified_name = class
-        .name()
-        .to_qualified_name(activation.context.gc_context);
+    let qualified_name = class.qualified_name();
 
     object.set_public_property("name", qualified_name.into(), activation)?;
 
@@ -158,11 +156,7 @@ fn describe_internal_body<'gc>(
     if flags.contains(DescribeTypeFlags::INCLUDE_BASES) {
         let mut current_super_obj = superclass;
         while let Some(super_obj) = current_super_obj {
-            let super_name = super_obj
-                .inner_class_definition()
-                .read()
-                .name()
-                .to_qualified_name(activation.context.gc_context);
+            let super_name = super_obj.inner_class_definition().read().qualified_name();
             bases_array.push(super_name.into());
             current_super_obj = super_obj.superclass_object();
         }
@@ -183,43 +177,26 @@ fn describe_internal_body<'gc>(
 
     if flags.contains(DescribeTypeFlags::INCLUDE_INTERFACES) && use_instance_traits {
         for interface in class_obj.interfaces() {
-            let interface_name = interface
-                .read()
-                .name()
-                .to_qualified_name(activation.context.gc_context);
+            let interface_name = interface.read().qualified_name();
             interfaces_array.push(interface_name.into());
         }
     }
 
     // Implement the weird 'HIDE_NSURI_METHODS' behavior from avmplus:
     // https://github.com/adobe/avmplus/blob/858d034a3bd3a54d9b70909386435cf4aec81d21/core/TypeDescriber.cpp#L237
-    let mut skip_ns = Vec::new();
+    let mut skip_ns: Vec<Namespace<'gc>> = Vec::new();
     if let Some(super_vtable) = super_vtable {
         for (_, ns, prop) in super_vtable.resolved_traits().iter() {
             if !ns.as_uri().is_empty() {
-                if let Property::Method { disp_id } = prop {
-                    let method = super_vtable
-                        .get_full_method(*disp_id)
-                        .unwrap_or_else(|| panic!("Missing method for id {disp_id:?}"));
-                    let is_playerglobals = method
-                        .class
-                        .class_scope()
-                        .domain()
-                        .is_playerglobals_domain(activation);
-
-                    if !skip_ns.contains(&(ns, is_playerglobals)) {
-                        skip_ns.push((ns, is_playerglobals));
+                if let Property::Method { .. } = prop {
+                    if !skip_ns.contains(&ns) {
+                        skip_ns.push(ns);
                     }
                 }
             }
         }
     }
 
-    let class_is_playerglobals = class_obj
-        .class_scope()
-        .domain()
-        .is_playerglobals_domain(activation);
-
     // FIXME - avmplus iterates over their own hashtable, so the order in the final XML
     // is different
     for (prop_name, ns, prop) in vtable.resolved_traits().iter() {
@@ -227,16 +204,14 @@ fn describe_internal_body<'gc>(
             continue;
         }
 
-        // Hack around our lack of namespace versioning.
-        // This is hack to work around the fact that we don't have namespace versioning
-        // Once we do, methods from playerglobals should end up distinct public and AS3
-        // namespaces, due to the special `kApiVersion_VM_ALLVERSIONS` used:
+        // Namespaces only hide methods in the same API version. Methods from playerglobals
+        // are in namespaces of `kApiVersion_VM_ALLVERSIONS`, which are distinct from the ones
+        // used by the movie:
         // https://github.com/adobe/avmplus/blob/858d034a3bd3a54d9b70909386435cf4aec81d21/core/AbcParser.cpp#L1497
         //
-        // The main way this is
-        // observable is by having a class like this:
+        // The main way this is observable is by having a class like this:
         //
-        // ``
+        // ```
         // class SubClass extends SuperClass {
         //   AS3 function subclassMethod {}
         // }
@@ -244,19 +219,9 @@ fn describe_internal_body<'gc>(
         // ```
         //
         // Here, `subclassMethod` will not get hidden - even though `Object`
-        // has AS3 methods, they are in the playerglobal AS3 namespace
-        // (with version kApiVersion_VM_ALLVERSIONS), which is distinct
-        // from the AS3 namespace used by SubClass. However, if we have any
-        // user-defined classes in the inheritance chain, then the namespace
-        // *should* match (if the swf version numbers match).
-        //
-        // For now, we approximate this by checking if the declaring class
-        // and our starting class are both in the playerglobals domain
-        // or both not in the playerglobals domain. If not, then we ignore
-        // `skip_ns`, since we should really have two different namespaces here.
-        if flags.contains(DescribeTypeFlags::HIDE_NSURI_METHODS)
-            && skip_ns.contains(&(ns, class_is_playerglobals))
-        {
+        // has AS3 methods, they are in the playerglobal AS3 namespace,
+        // which is distinct from the AS3 namespace used by SubClass.
+        if flags.contains(DescribeTypeFlags::HIDE_NSURI_METHODS) && skip_ns.contains(&ns) {
             continue;
         }
 
@@ -328,11 +293,7 @@ fn describe_internal_body<'gc>(
                     continue;
                 }
 
-                let declared_by_name = declared_by
-                    .inner_class_definition()
-                    .read()
-                    .name()
-                    .to_qualified_name(activation.context.gc_context);
+                let declared_by_name = declared_by.inner_class_definition().read().qualified_name();
 
                 let trait_metadata = vtable.get_metadata_for_disp(disp_id);
 
@@ -419,8 +380,7 @@ fn describe_internal_body<'gc>(
                 let declared_by = defining_class
                     .inner_class_definition()
                     .read()
-                    .name()
-                    .to_qualified_name(activation.context.gc_context);
+                    .qualified_name();
 
                 let accessor_obj = activation
                     .avm2()
@@ -0,0 +1,15 @@
+package flash.data {
+    import flash.utils.ByteArray;
+
+    public class EncryptedLocalStore {
+        public static function get isSupported(): Boolean {
+            return true;
+        }
+
+        // Items aren't tied to the publisher's signature, so `stronglyBound` has no effect.
+        public static native function setItem(name:String, data:ByteArray, stronglyBound:Boolean = false): void;
+        public static native function getItem(name:String): ByteArray;
+        public static native function removeItem(name:String): void;
+        public static native function reset(): void;
+    }
+}
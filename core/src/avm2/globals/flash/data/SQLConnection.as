@@ -0,0 +1,228 @@
+package flash.data {
+    import flash.errors.IllegalOperationError;
+    import flash.errors.SQLError;
+    import flash.errors.SQLErrorOperation;
+    import flash.events.Event;
+    import flash.events.EventDispatcher;
+    import flash.events.SQLErrorEvent;
+    import flash.events.SQLEvent;
+    import flash.filesystem.File;
+    import flash.net.Responder;
+    import flash.utils.ByteArray;
+    import flash.utils.setTimeout;
+
+    namespace ruffle = "__ruffle__";
+
+    public class SQLConnection extends EventDispatcher {
+        // 0 while the connection isn't open.
+        internal var _handle: uint = 0;
+
+        private var _async: Boolean = false;
+        private var _totalChanges: Number = 0;
+        private var _lastInsertRowID: Number = 0;
+
+        // Callbacks of asynchronous statements still running, by request.
+        private var _pending: Object = {};
+        private var _pendingCount: uint = 0;
+
+        public function SQLConnection() {
+        }
+
+        public static function get isSupported(): Boolean {
+            return SQLConnection.supported();
+        }
+
+        public function get connected(): Boolean {
+            return this._handle != 0;
+        }
+
+        public function get inTransaction(): Boolean {
+            return this._handle != 0 && SQLConnection.isInTransaction(this._handle);
+        }
+
+        public function get lastInsertRowID(): Number {
+            return this._lastInsertRowID;
+        }
+
+        public function get totalChanges(): Number {
+            return this._totalChanges;
+        }
+
+        public function open(reference: Object = null, openMode: String = "create", autoCompact: Boolean = false,
+                             pageSize: int = 1024, encryptionKey: ByteArray = null): void {
+            this._async = false;
+            this.openDatabase(reference, openMode, null);
+        }
+
+        public function openAsync(reference: Object = null, openMode: String = "create", responder: Responder = null,
+                                  autoCompact: Boolean = false, pageSize: int = 1024, encryptionKey: ByteArray = null): void {
+            this._async = true;
+            this.openDatabase(reference, openMode, responder);
+        }
+
+        public function close(responder: Responder = null): void {
+            if (this._handle != 0) {
+                SQLConnection.disconnect(this._handle);
+                this._handle = 0;
+            }
+            this.report(this, responder, new SQLEvent(SQLEvent.CLOSE), null);
+        }
+
+        public function begin(option: String = null, responder: Responder = null): void {
+            this.runTransactionStatement("BEGIN " + (option != null ? option : "DEFERRED"), SQLErrorOperation.BEGIN, SQLEvent.BEGIN, responder);
+        }
+
+        public function commit(responder: Responder = null): void {
+            this.runTransactionStatement("COMMIT", SQLErrorOperation.COMMIT, SQLEvent.COMMIT, responder);
+        }
+
+        public function rollback(responder: Responder = null): void {
+            this.runTransactionStatement("ROLLBACK", SQLErrorOperation.ROLLBACK, SQLEvent.ROLLBACK, responder);
+        }
+
+        private function openDatabase(reference: Object, openMode: String, responder: Responder): void {
+            if (this._handle != 0) {
+                throw new IllegalOperationError("The connection is already open.");
+            }
+            if (openMode != SQLMode.CREATE && openMode != SQLMode.READ && openMode != SQLMode.UPDATE) {
+                throw new ArgumentError("Error #2008: Parameter openMode must be one of the accepted values.", 2008);
+            }
+
+            var url: String = null;
+            if (reference is File) {
+                url = File(reference).url;
+            } else if (reference != null) {
+                url = String(reference);
+            }
+
+            try {
+                this._handle = SQLConnection.connect(url, openMode);
+            } catch (e: Error) {
+                this.fail(this, SQLErrorOperation.OPEN, e.message, responder);
+                return;
+            }
+            this.report(this, responder, new SQLEvent(SQLEvent.OPEN), this);
+        }
+
+        private function runTransactionStatement(text: String, operation: String, eventType: String, responder: Responder): void {
+            this.checkConnected();
+            var self: SQLConnection = this;
+            this.run(text, [], [], function(raw: Object, error: String): void {
+                if (error != null) {
+                    self.fail(self, operation, error, responder);
+                    return;
+                }
+                self.report(self, responder, new SQLEvent(eventType), null);
+            });
+        }
+
+        // Runs `text`, then calls `done` with its raw result or an error message.
+        // Asynchronous connections run it in the background and call `done` once it's finished.
+        internal function run(text: String, keys: Array, values: Array, done: Function): void {
+            if (!this._async) {
+                var raw: Object;
+                try {
+                    raw = SQLConnection.executeSql(this._handle, text, keys, values);
+                } catch (e: Error) {
+                    done(null, e.message);
+                    return;
+                }
+                done(raw, null);
+                return;
+            }
+
+            var request: uint;
+            try {
+                request = SQLConnection.executeSqlAsync(this._handle, text, keys, values);
+            } catch (e: Error) {
+                done(null, e.message);
+                return;
+            }
+            this._pending[request] = done;
+            this._pendingCount++;
+            if (this._pendingCount == 1) {
+                setTimeout(this.poll, 1);
+            }
+        }
+
+        private function poll(): void {
+            if (this._handle == 0) {
+                this._pending = {};
+                this._pendingCount = 0;
+                return;
+            }
+            var responses: Array = SQLConnection.takeResponses(this._handle);
+            for each (var response: Object in responses) {
+                var done: Function = this._pending[response.request];
+                delete this._pending[response.request];
+                this._pendingCount--;
+                done(response.result, response.error);
+            }
+            if (this._pendingCount > 0) {
+                setTimeout(this.poll, 1);
+            }
+        }
+
+        internal function checkConnected(): void {
+            if (this._handle == 0) {
+                throw new IllegalOperationError("Error #3104: A SQLConnection must be open to perform this operation.", 3104);
+            }
+        }
+
+        internal function recordResult(result: SQLResult): void {
+            if (result.rowsAffected > 0) {
+                this._totalChanges += result.rowsAffected;
+                this._lastInsertRowID = result.lastInsertRowID;
+            }
+        }
+
+        // Delivers the outcome of an operation: to the responder if there is one, and as an event
+        // on `target` otherwise. Asynchronous connections deliver it once the current code is done.
+        internal function report(target: EventDispatcher, responder: Responder, event: Event, value: Object): void {
+            var deliver: Function = function(): void {
+                if (responder == null) {
+                    target.dispatchEvent(event);
+                    return;
+                }
+                var handler: Function = event is SQLErrorEvent ? responder.ruffle::status : responder.ruffle::result;
+                if (handler != null) {
+                    handler(value);
+                }
+            };
+
+            if (this._async) {
+                setTimeout(deliver, 0);
+            } else {
+                deliver();
+            }
+        }
+
+        // Synchronous connections throw errors, asynchronous ones report them.
+        internal function fail(target: EventDispatcher, operation: String, details: String, responder: Responder): void {
+            var error: SQLError = new SQLError(operation, details, "Error #3115: SQL Error.", 3115);
+            if (!this._async) {
+                throw error;
+            }
+            this.report(target, responder, new SQLErrorEvent(SQLErrorEvent.ERROR, false, false, error), error);
+        }
+
+        private static native function supported(): Boolean;
+
+        // Opens the database at `url`, or an in-memory one if it's null, returning its handle.
+        internal static native function connect(url: String, openMode: String): uint;
+
+        internal static native function disconnect(handle: uint): void;
+
+        // Runs `text` with the given parameters, returning an object with `data`,
+        // `rowsAffected` and `lastInsertRowID`.
+        internal static native function executeSql(handle: uint, text: String, keys: Array, values: Array): Object;
+
+        // Starts running `text` in the background, returning the request it'll be reported as.
+        internal static native function executeSqlAsync(handle: uint, text: String, keys: Array, values: Array): uint;
+
+        // Takes the finished requests, as objects with the `request` and either its `result` or `error`.
+        internal static native function takeResponses(handle: uint): Array;
+
+        internal static native function isInTransaction(handle: uint): Boolean;
+    }
+}
@@ -0,0 +1,7 @@
+package flash.data {
+    public class SQLMode {
+        public static const CREATE: String = "create";
+        public static const READ: String = "read";
+        public static const UPDATE: String = "update";
+    }
+}
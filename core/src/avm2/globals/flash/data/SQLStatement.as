@@ -0,0 +1,117 @@
+package flash.data {
+    import flash.errors.IllegalOperationError;
+    import flash.errors.SQLErrorOperation;
+    import flash.events.EventDispatcher;
+    import flash.events.SQLEvent;
+    import flash.net.Responder;
+    import __ruffle__.stub_method;
+
+    public class SQLStatement extends EventDispatcher {
+        private var _sqlConnection: SQLConnection;
+        private var _text: String;
+        private var _parameters: Object = {};
+        private var _itemClass: Class;
+
+        // Results not yet taken with `getResult`, oldest first.
+        private var _results: Array = [];
+
+        private var _executing: Boolean = false;
+
+        public function SQLStatement() {
+        }
+
+        public function get sqlConnection(): SQLConnection {
+            return this._sqlConnection;
+        }
+
+        public function set sqlConnection(value: SQLConnection): void {
+            this._sqlConnection = value;
+        }
+
+        public function get text(): String {
+            return this._text;
+        }
+
+        public function set text(value: String): void {
+            this._text = value;
+        }
+
+        public function get itemClass(): Class {
+            return this._itemClass;
+        }
+
+        public function set itemClass(value: Class): void {
+            this._itemClass = value;
+        }
+
+        public function get parameters(): Object {
+            return this._parameters;
+        }
+
+        public function get executing(): Boolean {
+            return this._executing;
+        }
+
+        public function clearParameters(): void {
+            this._parameters = {};
+        }
+
+        public function cancel(): void {
+            stub_method("flash.data.SQLStatement", "cancel");
+        }
+
+        public function next(prefetch: int = -1, responder: Responder = null): void {
+            // Every result is complete, so there is never anything more to fetch.
+            stub_method("flash.data.SQLStatement", "next");
+        }
+
+        public function getResult(): SQLResult {
+            return this._results.length > 0 ? this._results.shift() : null;
+        }
+
+        public function execute(prefetch: int = -1, responder: Responder = null): void {
+            if (this._text == null || this._text == "" || this._sqlConnection == null) {
+                throw new IllegalOperationError("Error #3105: Operation is only allowed if a connection has been opened.", 3105);
+            }
+            this._sqlConnection.checkConnected();
+
+            var keys: Array = [];
+            var values: Array = [];
+            for (var key: String in this._parameters) {
+                keys.push(key);
+                values.push(this._parameters[key]);
+            }
+
+            this._executing = true;
+            var self: SQLStatement = this;
+            this._sqlConnection.run(this._text, keys, values, function(raw: Object, error: String): void {
+                self._executing = false;
+                if (error != null) {
+                    self._sqlConnection.fail(self, SQLErrorOperation.EXECUTE, error, responder);
+                    return;
+                }
+                self.complete(raw, responder);
+            });
+        }
+
+        private function complete(raw: Object, responder: Responder): void {
+            var data: Array = raw.data;
+            if (data != null && this._itemClass != null) {
+                for (var i: int = 0; i < data.length; i++) {
+                    var item: Object = new this._itemClass();
+                    for (var column: String in data[i]) {
+                        item[column] = data[i][column];
+                    }
+                    data[i] = item;
+                }
+            }
+
+            var result: SQLResult = new SQLResult(data, raw.rowsAffected, true, raw.lastInsertRowID);
+            this._sqlConnection.recordResult(result);
+            if (responder == null) {
+                this._results.push(result);
+            }
+            this._sqlConnection.report(this, responder, new SQLEvent(SQLEvent.RESULT), result);
+        }
+    }
+}
@@ -0,0 +1,85 @@
+//! `flash.data.EncryptedLocalStore` native methods
+
+use crate::avm2::bytearray::ByteArrayStorage;
+use crate::avm2::error::argument_error;
+use crate::avm2::object::ByteArrayObject;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, Error, Object, Value};
+use crate::string::AvmString;
+
+/// Reads the item name, which must not be empty.
+fn item_name<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    args: &[Value<'gc>],
+) -> Result<AvmString<'gc>, Error<'gc>> {
+    let name = args.get_string_non_null(activation, 0, "name")?;
+    if name.is_empty() {
+        return Err(Error::AvmError(argument_error(
+            activation,
+            "Error #2004: One of the parameters is invalid.",
+            2004,
+        )?));
+    }
+    Ok(name)
+}
+
+/// Implements `EncryptedLocalStore.setItem`
+pub fn set_item<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = item_name(activation, args)?.to_string();
+    let data = args.get_object(activation, 1, "data")?;
+    let bytes = data
+        .as_bytearray()
+        .map(|bytes| bytes.bytes().to_vec())
+        .unwrap_or_default();
+
+    if !activation.context.secure_storage.set_item(&name, &bytes) {
+        tracing::warn!("Couldn't store EncryptedLocalStore item {name}");
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `EncryptedLocalStore.getItem`
+pub fn get_item<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = item_name(activation, args)?.to_string();
+
+    match activation.context.secure_storage.get_item(&name) {
+        Some(bytes) => Ok(ByteArrayObject::from_storage(
+            activation,
+            ByteArrayStorage::from_vec(bytes),
+        )?
+        .into()),
+        None => Ok(Value::Null),
+    }
+}
+
+/// Implements `EncryptedLocalStore.removeItem`
+pub fn remove_item<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = item_name(activation, args)?.to_string();
+    activation.context.secure_storage.remove_item(&name);
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `EncryptedLocalStore.reset`
+pub fn reset<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    activation.context.secure_storage.reset();
+
+    Ok(Value::Undefined)
+}
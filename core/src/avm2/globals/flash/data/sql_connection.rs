@@ -0,0 +1,302 @@
+//! `flash.data.SQLConnection` native methods
+
+use crate::avm2::bytearray::ByteArrayStorage;
+use crate::avm2::error::error;
+use crate::avm2::globals::flash::filesystem::file::air_file_path;
+use crate::avm2::object::{ByteArrayObject, TObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, ArrayObject, ArrayStorage, Error, Object, Value};
+use crate::sql::{SqlConnections, SqlOpenMode, SqlParameter, SqlResult, SqlValue};
+use crate::string::AvmString;
+
+fn sql_error<'gc>(activation: &mut Activation<'_, 'gc>, message: &str) -> Error<'gc> {
+    match error(activation, message, 0) {
+        Ok(err) => Error::AvmError(err),
+        Err(err) => err,
+    }
+}
+
+fn to_sql_value<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    value: Value<'gc>,
+) -> Result<SqlValue, Error<'gc>> {
+    Ok(match value {
+        Value::Undefined | Value::Null => SqlValue::Null,
+        Value::Bool(value) => SqlValue::Integer(value.into()),
+        Value::Integer(value) => SqlValue::Integer(value.into()),
+        Value::Number(value) if value.fract() == 0.0 && value.abs() < 9007199254740992.0 => {
+            SqlValue::Integer(value as i64)
+        }
+        Value::Number(value) => SqlValue::Real(value),
+        Value::Object(object) => {
+            if let Some(bytes) = object.as_bytearray() {
+                SqlValue::Blob(bytes.bytes().to_vec())
+            } else {
+                SqlValue::Text(value.coerce_to_string(activation)?.to_string())
+            }
+        }
+        Value::String(value) => SqlValue::Text(value.to_string()),
+    })
+}
+
+fn from_sql_value<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    value: SqlValue,
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(match value {
+        SqlValue::Null => Value::Null,
+        SqlValue::Integer(value) => match i32::try_from(value) {
+            Ok(value) => value.into(),
+            Err(_) => (value as f64).into(),
+        },
+        SqlValue::Real(value) => value.into(),
+        SqlValue::Text(value) => AvmString::new_utf8(activation.context.gc_context, value).into(),
+        SqlValue::Blob(value) => {
+            ByteArrayObject::from_storage(activation, ByteArrayStorage::from_vec(value))?.into()
+        }
+    })
+}
+
+/// Reads the elements of an array argument.
+fn array_values<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    args: &[Value<'gc>],
+    index: usize,
+    name: &'static str,
+) -> Result<Vec<Value<'gc>>, Error<'gc>> {
+    let array = args.get_object(activation, index, name)?;
+    let values = array
+        .as_array_storage()
+        .map(|storage| {
+            storage
+                .iter()
+                .map(|v| v.unwrap_or(Value::Undefined))
+                .collect()
+        })
+        .unwrap_or_default();
+    Ok(values)
+}
+
+/// Implements `SQLConnection.supported`
+pub fn supported<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    // Only AIR content has databases.
+    let is_air = activation.context.navigator.air_directories().is_some();
+
+    Ok((SqlConnections::is_supported() && is_air).into())
+}
+
+/// Implements `SQLConnection.connect`
+pub fn connect<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let url = match args.get_value(0) {
+        Value::Null | Value::Undefined => None,
+        url => Some(url.coerce_to_string(activation)?.to_string()),
+    };
+    let mode = args.get_string(activation, 1)?.to_string();
+    let mode = SqlOpenMode::parse(&mode).unwrap_or(SqlOpenMode::Create);
+
+    if activation.context.navigator.air_directories().is_none() {
+        return Err(sql_error(
+            activation,
+            "Databases are only available to AIR content",
+        ));
+    }
+    // AIR content can only keep databases in its own directories.
+    let path = match url {
+        Some(url) => match air_file_path(activation, &url, mode != SqlOpenMode::Read) {
+            Some(path) => Some(path),
+            None => {
+                return Err(sql_error(
+                    activation,
+                    "Error #3125: Unable to open the database file.",
+                ))
+            }
+        },
+        None => None,
+    };
+
+    match activation
+        .context
+        .sql_connections
+        .open(path.as_deref(), mode)
+    {
+        Ok(handle) => Ok(handle.into()),
+        Err(message) => Err(sql_error(activation, &message)),
+    }
+}
+
+/// Implements `SQLConnection.disconnect`
+pub fn disconnect<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let handle = args.get_u32(activation, 0)?;
+    activation.context.sql_connections.close(handle);
+
+    Ok(Value::Undefined)
+}
+
+/// Reads the parameters of a statement, from arrays of their keys and values.
+fn statement_parameters<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    args: &[Value<'gc>],
+) -> Result<Vec<(SqlParameter, SqlValue)>, Error<'gc>> {
+    let keys = array_values(activation, args, 2, "keys")?;
+    let values = array_values(activation, args, 3, "values")?;
+
+    let mut parameters = Vec::with_capacity(keys.len());
+    for (key, value) in keys.into_iter().zip(values) {
+        let key = key.coerce_to_string(activation)?.to_string();
+        let parameter = match key.parse() {
+            Ok(index) => SqlParameter::Index(index),
+            Err(_) => SqlParameter::Named(key),
+        };
+        parameters.push((parameter, to_sql_value(activation, value)?));
+    }
+    Ok(parameters)
+}
+
+/// Makes an object with the `data`, `rowsAffected` and `lastInsertRowID` of a result.
+fn result_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    result: SqlResult,
+) -> Result<Object<'gc>, Error<'gc>> {
+    let data = if result.rows.is_empty() {
+        Value::Null
+    } else {
+        let columns: Vec<AvmString<'gc>> = result
+            .columns
+            .iter()
+            .map(|column| AvmString::new_utf8(activation.context.gc_context, column))
+            .collect();
+        let mut rows = Vec::with_capacity(result.rows.len());
+        for row in result.rows {
+            let object = activation
+                .avm2()
+                .classes()
+                .object
+                .construct(activation, &[])?;
+            for (column, value) in columns.iter().zip(row) {
+                let value = from_sql_value(activation, value)?;
+                object.set_public_property(*column, value, activation)?;
+            }
+            rows.push(Some(object.into()));
+        }
+        ArrayObject::from_storage(activation, ArrayStorage::from_storage(rows))?.into()
+    };
+
+    let object = activation
+        .avm2()
+        .classes()
+        .object
+        .construct(activation, &[])?;
+    object.set_public_property("data", data, activation)?;
+    object.set_public_property(
+        "rowsAffected",
+        (result.rows_affected as f64).into(),
+        activation,
+    )?;
+    object.set_public_property(
+        "lastInsertRowID",
+        (result.last_insert_row_id as f64).into(),
+        activation,
+    )?;
+    Ok(object)
+}
+
+/// Implements `SQLConnection.executeSql`
+pub fn execute_sql<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let handle = args.get_u32(activation, 0)?;
+    let text = args.get_string(activation, 1)?.to_string();
+    let parameters = statement_parameters(activation, args)?;
+
+    let result = match activation
+        .context
+        .sql_connections
+        .execute(handle, &text, &parameters)
+    {
+        Ok(result) => result,
+        Err(message) => return Err(sql_error(activation, &message)),
+    };
+
+    Ok(result_object(activation, result)?.into())
+}
+
+/// Implements `SQLConnection.executeSqlAsync`
+pub fn execute_sql_async<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let handle = args.get_u32(activation, 0)?;
+    let text = args.get_string(activation, 1)?.to_string();
+    let parameters = statement_parameters(activation, args)?;
+
+    match activation
+        .context
+        .sql_connections
+        .execute_async(handle, &text, &parameters)
+    {
+        Ok(request) => Ok(request.into()),
+        Err(message) => Err(sql_error(activation, &message)),
+    }
+}
+
+/// Implements `SQLConnection.takeResponses`
+pub fn take_responses<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let handle = args.get_u32(activation, 0)?;
+
+    let mut responses = vec![];
+    for (request, result) in activation.context.sql_connections.take_responses(handle) {
+        let response = activation
+            .avm2()
+            .classes()
+            .object
+            .construct(activation, &[])?;
+        response.set_public_property("request", request.into(), activation)?;
+        match result {
+            Ok(result) => {
+                let result = result_object(activation, result)?;
+                response.set_public_property("result", result.into(), activation)?;
+            }
+            Err(message) => {
+                let message = AvmString::new_utf8(activation.context.gc_context, message);
+                response.set_public_property("error", message.into(), activation)?;
+            }
+        }
+        responses.push(Some(response.into()));
+    }
+
+    Ok(ArrayObject::from_storage(activation, ArrayStorage::from_storage(responses))?.into())
+}
+
+/// Implements `SQLConnection.isInTransaction`
+pub fn is_in_transaction<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let handle = args.get_u32(activation, 0)?;
+
+    Ok(activation
+        .context
+        .sql_connections
+        .in_transaction(handle)
+        .into())
+}
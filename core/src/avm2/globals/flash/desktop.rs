@@ -0,0 +1,4 @@
+//! `flash.desktop` namespace
+
+pub mod clipboard;
+pub mod native_drag_manager;
@@ -0,0 +1,94 @@
+package flash.desktop {
+    public class Clipboard {
+        private static var _generalClipboard: Clipboard;
+
+        // Formats and their data, or the handlers that produce it.
+        private var _data: Object = {};
+        private var _handlers: Object = {};
+        private var _isGeneral: Boolean = false;
+
+        public function Clipboard() {
+        }
+
+        // The system clipboard. Only text goes through to the host; other
+        // formats stay within the player. Outside of AIR, the host's text can
+        // only be read while handling a `paste` event.
+        public static function get generalClipboard(): Clipboard {
+            if (_generalClipboard == null) {
+                _generalClipboard = new Clipboard();
+                _generalClipboard._isGeneral = true;
+            }
+            return _generalClipboard;
+        }
+
+        public function get formats(): Array {
+            var formats: Array = [];
+            for (var format: String in this._data) {
+                formats.push(format);
+            }
+            for (format in this._handlers) {
+                formats.push(format);
+            }
+            if (this._isGeneral && formats.indexOf(ClipboardFormats.TEXT_FORMAT) < 0 &&
+                    Clipboard.canReadSystemText() && Clipboard.getSystemText() != "") {
+                formats.push(ClipboardFormats.TEXT_FORMAT);
+            }
+            return formats;
+        }
+
+        public function clear(): void {
+            this._data = {};
+            this._handlers = {};
+            if (this._isGeneral) {
+                Clipboard.setSystemText("");
+            }
+        }
+
+        public function clearData(format: String): void {
+            delete this._data[format];
+            delete this._handlers[format];
+            if (this._isGeneral && format == ClipboardFormats.TEXT_FORMAT) {
+                Clipboard.setSystemText("");
+            }
+        }
+
+        public function setData(format: String, data: Object, serializable: Boolean = true): Boolean {
+            delete this._handlers[format];
+            if (this._isGeneral && format == ClipboardFormats.TEXT_FORMAT) {
+                Clipboard.setSystemText(String(data));
+                return true;
+            }
+            this._data[format] = data;
+            return true;
+        }
+
+        public function setDataHandler(format: String, handler: Function, serializable: Boolean = true): Boolean {
+            delete this._data[format];
+            this._handlers[format] = handler;
+            return true;
+        }
+
+        // Data never leaves the player, so the original object is always
+        // returned regardless of `transferMode`.
+        public function getData(format: String, transferMode: String = "originalPreferred"): Object {
+            if (this._handlers.hasOwnProperty(format)) {
+                this._data[format] = this._handlers[format]();
+                delete this._handlers[format];
+            }
+            if (this._isGeneral && format == ClipboardFormats.TEXT_FORMAT) {
+                return Clipboard.getSystemText();
+            }
+            return this._data.hasOwnProperty(format) ? this._data[format] : null;
+        }
+
+        public function hasFormat(format: String): Boolean {
+            return this.formats.indexOf(format) >= 0;
+        }
+
+        private static native function canReadSystemText(): Boolean;
+
+        private static native function getSystemText(): String;
+
+        private static native function setSystemText(text: String): void;
+    }
+}
@@ -0,0 +1,8 @@
+package flash.desktop {
+    public final class NativeDragActions {
+        public static const COPY: String = "copy";
+        public static const LINK: String = "link";
+        public static const MOVE: String = "move";
+        public static const NONE: String = "none";
+    }
+}
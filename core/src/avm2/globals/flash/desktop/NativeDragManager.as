@@ -0,0 +1,196 @@
+package flash.desktop {
+    import flash.display.Bitmap;
+    import flash.display.BitmapData;
+    import flash.display.InteractiveObject;
+    import flash.display.Sprite;
+    import flash.display.Stage;
+    import flash.events.Event;
+    import flash.events.MouseEvent;
+    import flash.events.NativeDragEvent;
+    import flash.filesystem.File;
+    import flash.geom.Point;
+
+    // Drags within the player are tracked here, from the mouse events of the
+    // initiator's stage. Only file lists can be dragged out to the host.
+    public class NativeDragManager {
+        private static var _dragInitiator: InteractiveObject;
+        private static var _clipboard: Clipboard;
+        private static var _allowedActions: NativeDragOptions;
+        private static var _dropAction: String = NativeDragActions.NONE;
+        private static var _stage: Stage;
+        private static var _dragImage: Sprite;
+
+        // The object under the mouse, and the one that accepted the drop.
+        private static var _target: InteractiveObject;
+        private static var _acceptingTarget: InteractiveObject;
+
+        public static function get isSupported(): Boolean {
+            return true;
+        }
+
+        public static function get isDragging(): Boolean {
+            return _dragInitiator != null;
+        }
+
+        public static function get dragInitiator(): InteractiveObject {
+            return _dragInitiator;
+        }
+
+        public static function get dropAction(): String {
+            return _dropAction;
+        }
+
+        public static function set dropAction(value: String): void {
+            _dropAction = value;
+        }
+
+        public static function acceptDragDrop(target: InteractiveObject): void {
+            if (isDragging) {
+                _acceptingTarget = target;
+            }
+        }
+
+        public static function doDrag(dragInitiator: InteractiveObject, clipboard: Clipboard, dragImage: BitmapData = null,
+                                      offset: Point = null, allowedActions: NativeDragOptions = null): void {
+            if (isDragging || dragInitiator == null || dragInitiator.stage == null) {
+                return;
+            }
+
+            _dragInitiator = dragInitiator;
+            _clipboard = clipboard;
+            _allowedActions = allowedActions != null ? allowedActions : new NativeDragOptions();
+            _dropAction = defaultAction(_allowedActions);
+            _stage = dragInitiator.stage;
+
+            if (!dispatchTo(dragInitiator, NativeDragEvent.NATIVE_DRAG_START, true)) {
+                reset();
+                return;
+            }
+
+            if (dragImage != null) {
+                // The image must not hide the drop targets beneath it from the mouse.
+                _dragImage = new Sprite();
+                _dragImage.mouseEnabled = false;
+                _dragImage.mouseChildren = false;
+                var bitmap: Bitmap = new Bitmap(dragImage);
+                if (offset != null) {
+                    bitmap.x = offset.x;
+                    bitmap.y = offset.y;
+                }
+                _dragImage.addChild(bitmap);
+                _stage.addChild(_dragImage);
+                moveDragImage();
+            }
+
+            _stage.addEventListener(MouseEvent.MOUSE_MOVE, onMouseMove, true);
+            _stage.addEventListener(MouseEvent.MOUSE_UP, onMouseUp, true);
+            _stage.addEventListener(Event.MOUSE_LEAVE, onMouseLeave);
+        }
+
+        private static function defaultAction(options: NativeDragOptions): String {
+            if (options.allowCopy) {
+                return NativeDragActions.COPY;
+            }
+            if (options.allowMove) {
+                return NativeDragActions.MOVE;
+            }
+            if (options.allowLink) {
+                return NativeDragActions.LINK;
+            }
+            return NativeDragActions.NONE;
+        }
+
+        private static function moveDragImage(): void {
+            if (_dragImage != null) {
+                _dragImage.x = _stage.mouseX;
+                _dragImage.y = _stage.mouseY;
+                // Keep the image above anything added during the drag.
+                _stage.setChildIndex(_dragImage, _stage.numChildren - 1);
+            }
+        }
+
+        private static function dispatchTo(target: InteractiveObject, type: String, cancelable: Boolean = false): Boolean {
+            var local: Point = target.globalToLocal(new Point(_stage.mouseX, _stage.mouseY));
+            return target.dispatchEvent(new NativeDragEvent(type, true, cancelable, local.x, local.y, null,
+                                                            _clipboard, _allowedActions, _dropAction));
+        }
+
+        private static function onMouseMove(event: MouseEvent): void {
+            moveDragImage();
+
+            var target: InteractiveObject = event.target as InteractiveObject;
+            if (target != _target) {
+                if (_target != null) {
+                    dispatchTo(_target, NativeDragEvent.NATIVE_DRAG_EXIT);
+                }
+                // Every new target has to accept the drop for itself.
+                _acceptingTarget = null;
+                _target = target;
+                if (_target != null) {
+                    dispatchTo(_target, NativeDragEvent.NATIVE_DRAG_ENTER);
+                }
+            }
+            if (_target != null) {
+                dispatchTo(_target, NativeDragEvent.NATIVE_DRAG_OVER, true);
+            }
+            dispatchTo(_dragInitiator, NativeDragEvent.NATIVE_DRAG_UPDATE);
+        }
+
+        private static function onMouseUp(event: MouseEvent): void {
+            if (_acceptingTarget != null && _dropAction != NativeDragActions.NONE) {
+                dispatchTo(_acceptingTarget, NativeDragEvent.NATIVE_DRAG_DROP);
+            } else {
+                _dropAction = NativeDragActions.NONE;
+            }
+            finish();
+        }
+
+        private static function onMouseLeave(event: Event): void {
+            if (_clipboard == null || !_clipboard.hasFormat(ClipboardFormats.FILE_LIST_FORMAT)) {
+                return;
+            }
+
+            var files: Array = _clipboard.getData(ClipboardFormats.FILE_LIST_FORMAT) as Array;
+            var paths: Array = [];
+            for each (var file: Object in files) {
+                if (file is File && File(file).nativePath != null) {
+                    paths.push(File(file).nativePath);
+                }
+            }
+            if (paths.length > 0 && NativeDragManager.startHostDrag(paths)) {
+                if (_target != null) {
+                    dispatchTo(_target, NativeDragEvent.NATIVE_DRAG_EXIT);
+                }
+                finish();
+            }
+        }
+
+        private static function finish(): void {
+            var initiator: InteractiveObject = _dragInitiator;
+
+            _stage.removeEventListener(MouseEvent.MOUSE_MOVE, onMouseMove, true);
+            _stage.removeEventListener(MouseEvent.MOUSE_UP, onMouseUp, true);
+            _stage.removeEventListener(Event.MOUSE_LEAVE, onMouseLeave);
+            if (_dragImage != null) {
+                _stage.removeChild(_dragImage);
+            }
+
+            dispatchTo(initiator, NativeDragEvent.NATIVE_DRAG_COMPLETE);
+            reset();
+        }
+
+        private static function reset(): void {
+            _dragInitiator = null;
+            _clipboard = null;
+            _allowedActions = null;
+            _stage = null;
+            _dragImage = null;
+            _target = null;
+            _acceptingTarget = null;
+        }
+
+        // Hands the drag of the given files over to the host, once the mouse
+        // leaves the player.
+        private static native function startHostDrag(paths: Array): Boolean;
+    }
+}
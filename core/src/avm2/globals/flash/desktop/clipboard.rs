@@ -0,0 +1,61 @@
+//! `flash.desktop.Clipboard` native methods
+
+use crate::avm2::error::security_error;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, Avm2, Error, EventObject, Object, Value};
+use crate::string::AvmString;
+
+/// Dispatches `paste` on `target`, which may read the system clipboard while handling it.
+pub fn dispatch_paste<'gc>(activation: &mut Activation<'_, 'gc>, target: Object<'gc>) {
+    let paste_event = EventObject::bare_default_event(&mut activation.context, "paste");
+
+    let was_pasting = std::mem::replace(&mut activation.context.avm2.pasting, true);
+    Avm2::dispatch_event(&mut activation.context, paste_event, target);
+    activation.context.avm2.pasting = was_pasting;
+}
+
+/// Whether the system clipboard may be read, which Flash only allows AIR content or
+/// content handling a `paste` event to do.
+fn can_read_system<'gc>(activation: &mut Activation<'_, 'gc>) -> bool {
+    activation.context.avm2.pasting || activation.context.navigator.air_directories().is_some()
+}
+
+/// Implements `Clipboard.canReadSystemText`
+pub fn can_read_system_text<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(can_read_system(activation).into())
+}
+
+/// Implements `Clipboard.getSystemText`
+pub fn get_system_text<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if !can_read_system(activation) {
+        return Err(Error::AvmError(security_error(
+            activation,
+            "Error #2179: The Clipboard.generalClipboard object may only be read while processing a flash.events.Event.PASTE event.",
+            2179,
+        )?));
+    }
+
+    let content = activation.context.ui.clipboard_content();
+
+    Ok(AvmString::new_utf8(activation.context.gc_context, content).into())
+}
+
+/// Implements `Clipboard.setSystemText`
+pub fn set_system_text<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let content = args.get_string(activation, 0)?.to_string();
+    activation.context.ui.set_clipboard_content(content);
+
+    Ok(Value::Undefined)
+}
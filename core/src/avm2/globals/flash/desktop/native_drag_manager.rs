@@ -0,0 +1,26 @@
+//! `flash.desktop.NativeDragManager` native methods
+
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, Error, Object, Value};
+use std::path::PathBuf;
+
+/// Implements `NativeDragManager.startHostDrag`
+pub fn start_host_drag<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let paths = args.get_object(activation, 0, "paths")?;
+    let paths: Vec<Value<'gc>> = paths
+        .as_array_storage()
+        .map(|storage| storage.iter().flatten().collect())
+        .unwrap_or_default();
+
+    let mut files = Vec::with_capacity(paths.len());
+    for path in paths {
+        let path = path.coerce_to_string(activation)?;
+        files.push(PathBuf::from(path.to_string()));
+    }
+
+    Ok(activation.context.ui.start_file_drag(&files).into())
+}
@@ -11,6 +11,8 @@ pub mod loader;
 pub mod loader_info;
 pub mod morph_shape;
 pub mod movie_clip;
+pub mod native_window;
+pub mod screen;
 pub mod shader_data;
 pub mod shader_job;
 pub mod shader_parameter;
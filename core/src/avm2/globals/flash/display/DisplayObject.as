@@ -115,6 +115,9 @@ package flash.display {
         public native function get cacheAsBitmap():Boolean;
         public native function set cacheAsBitmap(value:Boolean):void;
 
+        public native function get metaData():Object;
+        public native function set metaData(value:Object):void;
+
         public native function hitTestPoint(x:Number, y:Number, shapeFlag:Boolean = false):Boolean;
 
         public native function hitTestObject(obj:DisplayObject):Boolean;
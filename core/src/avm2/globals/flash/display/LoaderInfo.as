@@ -4,6 +4,8 @@ package flash.display {
     import flash.utils.ByteArray;
     import flash.events.UncaughtErrorEvents;
 
+    namespace ruffle = "__ruffle__";
+
     [Ruffle(InstanceAllocator)]
     [Ruffle(NativeInstanceInit)]
     public class LoaderInfo extends EventDispatcher {
@@ -32,5 +34,8 @@ package flash.display {
         public native function get uncaughtErrorEvents():UncaughtErrorEvents;
         public native function get sameDomain():Boolean;
         public native function get childAllowsParent():Boolean;
+
+        // Not part of Flash's API; the XMP metadata from the movie's Metadata tag, or null.
+        ruffle native function get xmpMetadata():String;
     }
 }
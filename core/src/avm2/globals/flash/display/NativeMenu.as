@@ -1,6 +1,140 @@
 package flash.display {
     import flash.events.EventDispatcher;
+
     public class NativeMenu extends EventDispatcher {
+        private var _items: Array = [];
+
+        // The item this menu is the submenu of, if any.
+        internal var parentItem: NativeMenuItem;
+
+        public function NativeMenu() {
+        }
+
+        public static function get isSupported(): Boolean {
+            return true;
+        }
+
+        public function get items(): Array {
+            return this._items.concat();
+        }
+
+        public function set items(value: Array): void {
+            this.removeAllItems();
+            for each (var item: NativeMenuItem in value) {
+                this.addItem(item);
+            }
+        }
+
+        public function get numItems(): int {
+            return this._items.length;
+        }
+
+        public function get parent(): NativeMenu {
+            return this.parentItem != null ? this.parentItem.menu : null;
+        }
+
+        public function addItem(item: NativeMenuItem): NativeMenuItem {
+            return this.addItemAt(item, this._items.length);
+        }
+
+        public function addItemAt(item: NativeMenuItem, index: int): NativeMenuItem {
+            if (item == null) {
+                throw new TypeError("Error #2007: Parameter item must be non-null.", 2007);
+            }
+            if (item.ownerMenu != null || (item.submenu != null && this.isWithin(item.submenu))) {
+                throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+            if (index < 0 || index > this._items.length) {
+                throw new RangeError("Error #2006: The supplied index is out of bounds.", 2006);
+            }
+
+            this._items.splice(index, 0, item);
+            item.ownerMenu = this;
+            this.changed();
+            return item;
+        }
+
+        public function addSubmenu(submenu: NativeMenu, label: String): NativeMenuItem {
+            return this.addSubmenuAt(submenu, this._items.length, label);
+        }
+
+        public function addSubmenuAt(submenu: NativeMenu, index: int, label: String): NativeMenuItem {
+            var item: NativeMenuItem = new NativeMenuItem(label);
+            item.submenu = submenu;
+            return this.addItemAt(item, index);
+        }
+
+        public function containsItem(item: NativeMenuItem): Boolean {
+            return this._items.indexOf(item) != -1;
+        }
+
+        public function getItemAt(index: int): NativeMenuItem {
+            if (index < 0 || index >= this._items.length) {
+                throw new RangeError("Error #2006: The supplied index is out of bounds.", 2006);
+            }
+            return this._items[index];
+        }
+
+        public function getItemByName(name: String): NativeMenuItem {
+            for each (var item: NativeMenuItem in this._items) {
+                if (item.name == name) {
+                    return item;
+                }
+            }
+            return null;
+        }
+
+        public function getItemIndex(item: NativeMenuItem): int {
+            return this._items.indexOf(item);
+        }
+
+        public function setItemIndex(item: NativeMenuItem, index: int): void {
+            this.removeItem(item);
+            this.addItemAt(item, index);
+        }
+
+        public function removeItem(item: NativeMenuItem): NativeMenuItem {
+            var index: int = this._items.indexOf(item);
+            if (index == -1) {
+                throw new RangeError("Error #2025: The supplied NativeMenuItem must be a child of the caller.", 2025);
+            }
+            return this.removeItemAt(index);
+        }
+
+        public function removeItemAt(index: int): NativeMenuItem {
+            var item: NativeMenuItem = this.getItemAt(index);
+            this._items.splice(index, 1);
+            item.ownerMenu = null;
+            this.changed();
+            return item;
+        }
+
+        public function removeAllItems(): void {
+            for each (var item: NativeMenuItem in this._items) {
+                item.ownerMenu = null;
+            }
+            this._items = [];
+            this.changed();
+        }
+
+        // Whether this menu is `menu` or one of its submenus, at any depth.
+        // Menus can't contain themselves, so this is checked before adding a submenu.
+        internal function isWithin(menu: NativeMenu): Boolean {
+            for (var current: NativeMenu = this; current != null; current = current.parent) {
+                if (current === menu) {
+                    return true;
+                }
+            }
+            return false;
+        }
 
+        // Called whenever this menu or anything in it changes, so a shown menu can be updated.
+        internal function changed(): void {
+            if (this.parentItem != null) {
+                this.parentItem.changed();
+            } else {
+                NativeWindow.mainWindow.menuChanged(this);
+            }
+        }
     }
 }
@@ -1,6 +1,84 @@
 package flash.display {
     import flash.events.EventDispatcher;
+
     public class NativeMenuItem extends EventDispatcher {
-        public var enabled: Boolean;
+        private var _label: String;
+        private var _isSeparator: Boolean;
+        private var _enabled: Boolean = true;
+        private var _checked: Boolean = false;
+        private var _submenu: NativeMenu;
+
+        // The menu this item is in, if any.
+        internal var ownerMenu: NativeMenu;
+
+        public var name: String = "";
+        public var data: Object;
+        public var keyEquivalent: String = "";
+        public var keyEquivalentModifiers: Array = [];
+        public var mnemonicIndex: int = -1;
+
+        public function NativeMenuItem(label: String = "", isSeparator: Boolean = false) {
+            this._label = label;
+            this._isSeparator = isSeparator;
+        }
+
+        public function get label(): String {
+            return this._label;
+        }
+
+        public function set label(value: String): void {
+            this._label = value;
+            this.changed();
+        }
+
+        public function get isSeparator(): Boolean {
+            return this._isSeparator;
+        }
+
+        public function get enabled(): Boolean {
+            return this._enabled;
+        }
+
+        public function set enabled(value: Boolean): void {
+            this._enabled = value;
+            this.changed();
+        }
+
+        public function get checked(): Boolean {
+            return this._checked;
+        }
+
+        public function set checked(value: Boolean): void {
+            this._checked = value;
+            this.changed();
+        }
+
+        public function get submenu(): NativeMenu {
+            return this._submenu;
+        }
+
+        public function set submenu(value: NativeMenu): void {
+            if (value != null && this.ownerMenu != null && this.ownerMenu.isWithin(value)) {
+                throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+            if (this._submenu != null) {
+                this._submenu.parentItem = null;
+            }
+            this._submenu = value;
+            if (value != null) {
+                value.parentItem = this;
+            }
+            this.changed();
+        }
+
+        public function get menu(): NativeMenu {
+            return this.ownerMenu;
+        }
+
+        internal function changed(): void {
+            if (this.ownerMenu != null) {
+                this.ownerMenu.changed();
+            }
+        }
     }
 }
@@ -0,0 +1,128 @@
+package flash.display {
+    import __ruffle__.stub_method;
+    import flash.events.Event;
+    import flash.events.EventDispatcher;
+    import flash.events.NativeWindowBoundsEvent;
+    import flash.geom.Rectangle;
+
+    // The window the player is shown in. Ruffle only ever has one.
+    public class NativeWindow extends EventDispatcher {
+        private static var _mainWindow: NativeWindow;
+
+        private var _menu: NativeMenu;
+
+        private var _active: Boolean = true;
+
+        public function NativeWindow() {
+        }
+
+        internal static function get mainWindow(): NativeWindow {
+            if (_mainWindow == null) {
+                _mainWindow = new NativeWindow();
+            }
+            return _mainWindow;
+        }
+
+        public static function get isSupported(): Boolean {
+            return NativeWindow.hasNativeWindow();
+        }
+
+        public function get bounds(): Rectangle {
+            return NativeWindow.getBounds();
+        }
+
+        public function set bounds(value: Rectangle): void {
+            NativeWindow.setBounds(value.x, value.y, value.width, value.height);
+        }
+
+        public function get x(): Number {
+            return this.bounds.x;
+        }
+
+        public function set x(value: Number): void {
+            var bounds: Rectangle = this.bounds;
+            bounds.x = value;
+            this.bounds = bounds;
+        }
+
+        public function get y(): Number {
+            return this.bounds.y;
+        }
+
+        public function set y(value: Number): void {
+            var bounds: Rectangle = this.bounds;
+            bounds.y = value;
+            this.bounds = bounds;
+        }
+
+        public function get width(): Number {
+            return this.bounds.width;
+        }
+
+        public function set width(value: Number): void {
+            var bounds: Rectangle = this.bounds;
+            bounds.width = value;
+            this.bounds = bounds;
+        }
+
+        public function get height(): Number {
+            return this.bounds.height;
+        }
+
+        public function set height(value: Number): void {
+            var bounds: Rectangle = this.bounds;
+            bounds.height = value;
+            this.bounds = bounds;
+        }
+
+        public function get active(): Boolean {
+            return this._active;
+        }
+
+        public function activate(): void {
+            stub_method("flash.display.NativeWindow", "activate");
+        }
+
+        // Called by the player when the host reports that the window gained or lost focus.
+        internal function activeChanged(active: Boolean): void {
+            if (active != this._active) {
+                this._active = active;
+                this.dispatchEvent(new Event(active ? Event.ACTIVATE : Event.DEACTIVATE));
+            }
+        }
+
+        // Called by the player when the host reports that the window was moved or resized.
+        internal function boundsChanged(before: Rectangle, after: Rectangle): void {
+            if (before.x != after.x || before.y != after.y) {
+                this.dispatchEvent(new NativeWindowBoundsEvent(NativeWindowBoundsEvent.MOVE, false, false, before, after));
+            }
+            if (before.width != after.width || before.height != after.height) {
+                this.dispatchEvent(new NativeWindowBoundsEvent(NativeWindowBoundsEvent.RESIZE, false, false, before, after));
+            }
+        }
+
+        public function get menu(): NativeMenu {
+            return this._menu;
+        }
+
+        public function set menu(value: NativeMenu): void {
+            this._menu = value;
+            NativeWindow.installMenu(value);
+        }
+
+        // Shows the changes to a menu, if it's the one in this window.
+        internal function menuChanged(menu: NativeMenu): void {
+            if (menu === this._menu) {
+                NativeWindow.installMenu(menu);
+            }
+        }
+
+        private static native function hasNativeWindow(): Boolean;
+
+        private static native function getBounds(): Rectangle;
+
+        private static native function setBounds(x: Number, y: Number, width: Number, height: Number): void;
+
+        private static native function installMenu(menu: NativeMenu): void;
+    }
+}
@@ -0,0 +1,54 @@
+package flash.display {
+    import flash.events.EventDispatcher;
+    import flash.geom.Rectangle;
+
+    public final class Screen extends EventDispatcher {
+        private var _bounds: Rectangle;
+        private var _visibleBounds: Rectangle;
+        private var _colorDepth: int;
+
+        // Screens are only created by the player.
+        public function Screen() {
+        }
+
+        // The screens are queried afresh every time, as displays may have
+        // been attached or rearranged since.
+        public static function get screens(): Array {
+            var screens: Array = [];
+            for each (var info: Array in Screen.queryScreens()) {
+                var screen: Screen = new Screen();
+                screen._bounds = info[0];
+                screen._visibleBounds = info[1];
+                screen._colorDepth = info[2];
+                screens.push(screen);
+            }
+            return screens;
+        }
+
+        public static function get mainScreen(): Screen {
+            return Screen.screens[0];
+        }
+
+        public static function getScreensForRectangle(rect: Rectangle): Array {
+            return Screen.screens.filter(function(screen: Screen, index: int, array: Array): Boolean {
+                return screen._bounds.intersects(rect);
+            });
+        }
+
+        public function get bounds(): Rectangle {
+            return this._bounds.clone();
+        }
+
+        public function get visibleBounds(): Rectangle {
+            return this._visibleBounds.clone();
+        }
+
+        public function get colorDepth(): int {
+            return this._colorDepth;
+        }
+
+        // Returns `[bounds, visibleBounds, colorDepth]` for every screen,
+        // main screen first.
+        private static native function queryScreens(): Array;
+    }
+}
@@ -161,6 +161,10 @@ package flash.display {
 
         public native function get stage3Ds():Vector.<Stage3D>;
 
+        public function get nativeWindow():NativeWindow {
+            return NativeWindow.mainWindow;
+        }
+
         public native function invalidate():void;
     }
 }
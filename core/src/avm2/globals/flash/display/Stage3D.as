@@ -5,6 +5,7 @@ package flash.display {
     import flash.utils.setTimeout;
     import __ruffle__.stub_method;
 
+    [API("674")]
     [Ruffle(InstanceAllocator)]
     public class Stage3D extends EventDispatcher {
 
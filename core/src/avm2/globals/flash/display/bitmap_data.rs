@@ -356,8 +356,7 @@ pub fn get_vector<'gc>(
 
         let pixels = operations::get_vector(bitmap_data, x, y, width, height);
 
-        let value_type = activation.avm2().classes().uint;
-        let new_storage = VectorStorage::from_values(pixels, false, Some(value_type));
+        let new_storage = VectorStorage::from_uints(pixels, false, activation);
 
         return Ok(VectorObject::from_vector(new_storage, activation)?.into());
     }
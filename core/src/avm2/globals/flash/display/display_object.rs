@@ -15,7 +15,6 @@ use crate::prelude::*;
 use crate::string::AvmString;
 use crate::types::{Degrees, Percent};
 use crate::vminterface::Instantiator;
-use crate::{avm2_stub_getter, avm2_stub_setter};
 use ruffle_render::blend::ExtendedBlendMode;
 use ruffle_render::filters::Filter;
 use std::str::FromStr;
@@ -343,93 +342,142 @@ pub fn set_y<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Normalizes a rotation in degrees to the range Flash reports (-180, 180].
+fn normalize_rotation(degrees: Degrees) -> f64 {
+    let rem = f64::from(degrees) % 360.0;
+
+    if rem <= 180.0 {
+        rem
+    } else {
+        rem - 360.0
+    }
+}
+
+/// Implements `z`'s getter.
 pub fn get_z<'gc>(
-    activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(activation, "flash.display.DisplayObject", "z");
-    Ok(0.into())
+    if let Some(dobj) = this.as_display_object() {
+        return Ok(dobj.z().into());
+    }
+
+    Ok(Value::Undefined)
 }
 
+/// Implements `z`'s setter.
 pub fn set_z<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_setter!(activation, "flash.display.DisplayObject", "z");
+    if let Some(dobj) = this.as_display_object() {
+        let z = args.get_f64(activation, 0)?;
+        dobj.set_z(&mut activation.context, z);
+    }
+
     Ok(Value::Undefined)
 }
 
+/// Implements `rotationX`'s getter.
 pub fn get_rotation_x<'gc>(
-    activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(activation, "flash.display.DisplayObject", "rotationX");
-    Ok(0.into())
+    if let Some(dobj) = this.as_display_object() {
+        return Ok(normalize_rotation(dobj.rotation_x()).into());
+    }
+
+    Ok(Value::Undefined)
 }
 
+/// Implements `rotationX`'s setter.
 pub fn set_rotation_x<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_setter!(activation, "flash.display.DisplayObject", "rotationX");
+    if let Some(dobj) = this.as_display_object() {
+        let new_rotation = args.get_f64(activation, 0)?;
+        dobj.set_rotation_x(&mut activation.context, Degrees::from(new_rotation));
+    }
+
     Ok(Value::Undefined)
 }
 
+/// Implements `rotationY`'s getter.
 pub fn get_rotation_y<'gc>(
-    activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(activation, "flash.display.DisplayObject", "rotationY");
-    Ok(0.into())
+    if let Some(dobj) = this.as_display_object() {
+        return Ok(normalize_rotation(dobj.rotation_y()).into());
+    }
+
+    Ok(Value::Undefined)
 }
 
+/// Implements `rotationY`'s setter.
 pub fn set_rotation_y<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_setter!(activation, "flash.display.DisplayObject", "rotationY");
+    if let Some(dobj) = this.as_display_object() {
+        let new_rotation = args.get_f64(activation, 0)?;
+        dobj.set_rotation_y(&mut activation.context, Degrees::from(new_rotation));
+    }
+
     Ok(Value::Undefined)
 }
 
+/// Implements `rotationZ`'s getter.
+///
+/// This is the same rotation as `rotation`.
 pub fn get_rotation_z<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(activation, "flash.display.DisplayObject", "rotationZ");
-    Ok(0.into())
+    get_rotation(activation, this, args)
 }
 
+/// Implements `rotationZ`'s setter.
 pub fn set_rotation_z<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_setter!(activation, "flash.display.DisplayObject", "rotationZ");
-    Ok(Value::Undefined)
+    set_rotation(activation, this, args)
 }
 
+/// Implements `scaleZ`'s getter.
 pub fn get_scale_z<'gc>(
-    activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(activation, "flash.display.DisplayObject", "scaleZ");
-    Ok(1.into())
+    if let Some(dobj) = this.as_display_object() {
+        return Ok(dobj.scale_z().into());
+    }
+
+    Ok(Value::Undefined)
 }
 
+/// Implements `scaleZ`'s setter.
 pub fn set_scale_z<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_setter!(activation, "flash.display.DisplayObject", "scaleZ");
+    if let Some(dobj) = this.as_display_object() {
+        let new_scale = args.get_f64(activation, 0)?;
+        dobj.set_scale_z(&mut activation.context, new_scale);
+    }
+
     Ok(Value::Undefined)
 }
 
@@ -440,14 +488,8 @@ pub fn get_rotation<'gc>(
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(dobj) = this.as_display_object() {
-        let rot: f64 = dobj.rotation(activation.context.gc_context).into();
-        let rem = rot % 360.0;
-
-        if rem <= 180.0 {
-            return Ok(rem.into());
-        } else {
-            return Ok((rem - 360.0).into());
-        }
+        let rot = dobj.rotation(activation.context.gc_context);
+        return Ok(normalize_rotation(rot).into());
     }
 
     Ok(Value::Undefined)
@@ -630,6 +672,15 @@ pub fn hit_test_point<'gc>(
                 return Ok(false.into());
             }
 
+            // Content scrolled out of an ancestor's `scrollRect` can't be hit.
+            let mut ancestor = dobj.parent();
+            while let Some(parent) = ancestor {
+                if !parent.hit_test_scroll_rect(global) {
+                    return Ok(false.into());
+                }
+                ancestor = parent.parent();
+            }
+
             return Ok(dobj
                 .hit_test_shape(
                     &mut activation.context,
@@ -905,43 +956,63 @@ pub fn global_to_local<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Computes the bounds of `dobj` in the coordinate space of the display
+/// object passed as the first argument, defaulting to `dobj` itself.
+///
+/// `include_strokes` selects between the `getBounds` and `getRect` behavior.
+fn bounds_in_target_space<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    dobj: DisplayObject<'gc>,
+    args: &[Value<'gc>],
+    include_strokes: bool,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let target = args
+        .try_get_object(activation, 0)
+        .and_then(|o| o.as_display_object())
+        .unwrap_or(dobj);
+    let bounds = if include_strokes {
+        dobj.bounds()
+    } else {
+        dobj.edge_bounds_with_transform(&Matrix::default())
+    };
+    let out_bounds = if DisplayObject::ptr_eq(dobj, target) {
+        // Getting the clips bounds in its own coordinate space; no AABB transform needed.
+        bounds
+    } else {
+        // Transform AABB to target space.
+        // Calculate the matrix to transform into the target coordinate space, and transform the above AABB.
+        // Note that this doesn't produce as tight of an AABB as if we had used `bounds_with_transform` with
+        // the final matrix, but this matches Flash's behavior.
+        let to_global_matrix = dobj.local_to_global_matrix();
+        let to_target_matrix = target.global_to_local_matrix().unwrap_or_default();
+        to_target_matrix * to_global_matrix * bounds
+    };
+
+    Ok(new_rectangle(activation, out_bounds)?.into())
+}
+
 pub fn get_bounds<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(dobj) = this.as_display_object() {
-        let target = args
-            .try_get_object(activation, 0)
-            .and_then(|o| o.as_display_object())
-            .unwrap_or(dobj);
-        let bounds = dobj.bounds();
-        let out_bounds = if DisplayObject::ptr_eq(dobj, target) {
-            // Getting the clips bounds in its own coordinate space; no AABB transform needed.
-            bounds
-        } else {
-            // Transform AABB to target space.
-            // Calculate the matrix to transform into the target coordinate space, and transform the above AABB.
-            // Note that this doesn't produce as tight of an AABB as if we had used `bounds_with_transform` with
-            // the final matrix, but this matches Flash's behavior.
-            let to_global_matrix = dobj.local_to_global_matrix();
-            let to_target_matrix = target.global_to_local_matrix().unwrap_or_default();
-            to_target_matrix * to_global_matrix * bounds
-        };
-
-        return Ok(new_rectangle(activation, out_bounds)?.into());
+        return bounds_in_target_space(activation, dobj, args, true);
     }
     Ok(Value::Undefined)
 }
 
+/// Implements `getRect`, which is the same as `getBounds` except that it
+/// ignores the width of strokes.
 pub fn get_rect<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    // TODO: This should get the bounds ignoring strokes. Always equal to or smaller than getBounds.
-    // Just defer to getBounds for now. Will have to store edge_bounds vs. shape_bounds in Graphic.
-    get_bounds(activation, this, args)
+    if let Some(dobj) = this.as_display_object() {
+        return bounds_in_target_space(activation, dobj, args, false);
+    }
+    Ok(Value::Undefined)
 }
 
 pub fn get_mask<'gc>(
@@ -1000,6 +1071,32 @@ pub fn set_cache_as_bitmap<'gc>(
     Ok(Value::Undefined)
 }
 
+/// `metaData`'s getter.
+pub fn get_meta_data<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this
+        .as_display_object()
+        .and_then(|this| this.meta_data())
+        .map_or(Value::Null, Value::Object))
+}
+
+/// `metaData`'s setter.
+pub fn set_meta_data<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(dobj) = this.as_display_object() {
+        let value = args.try_get_object(activation, 0);
+        dobj.set_meta_data(activation.context.gc_context, value);
+    }
+
+    Ok(Value::Undefined)
+}
+
 /// `opaqueBackground`'s getter.
 pub fn get_opaque_background<'gc>(
     _activation: &mut Activation<'_, 'gc>,
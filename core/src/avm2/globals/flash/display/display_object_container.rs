@@ -540,18 +540,73 @@ pub fn stop_all_movie_clips<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Collects the descendants of `container` under the given stage point into `under_point`,
+/// in render order (bottom-most first).
+fn objects_under_point<'gc>(
+    context: &mut UpdateContext<'_, 'gc>,
+    container: DisplayObject<'gc>,
+    point: Point<Twips>,
+    under_point: &mut Vec<DisplayObject<'gc>>,
+) {
+    let Some(ctr) = container.as_container() else {
+        return;
+    };
+
+    // Children up to this depth are hidden by a timeline clip layer that misses the point.
+    let mut clipped_depth = 0;
+
+    for child in ctr.iter_render_list() {
+        // Masks (either via `mask` or timeline clip layers) are never reported.
+        if child.clip_depth() > 0 {
+            if !child.hit_test_shape(context, point, HitTestOptions::SKIP_INVISIBLE) {
+                clipped_depth = child.clip_depth();
+            }
+            continue;
+        }
+
+        if child.maskee().is_some() || child.depth() <= clipped_depth {
+            continue;
+        }
+
+        if !child.hit_test_scroll_rect(point) {
+            continue;
+        }
+
+        if let Some(masker) = child.masker() {
+            if !masker.hit_test_shape(context, point, HitTestOptions::empty()) {
+                continue;
+            }
+        }
+
+        if child.as_container().is_some() {
+            // A container is only under the point because of its own vector content;
+            // its children report themselves.
+            if let Some(mc) = child.as_movie_clip() {
+                if let Some(local_matrix) = mc.global_to_local_matrix() {
+                    let local = local_matrix * point;
+                    if mc
+                        .drawing(context.gc_context)
+                        .hit_test(local, &local_matrix)
+                    {
+                        under_point.push(child);
+                    }
+                }
+            }
+            objects_under_point(context, child, point, under_point);
+        } else if child.hit_test_shape(context, point, HitTestOptions::SKIP_MASK) {
+            under_point.push(child);
+        }
+    }
+}
+
+/// Implements `DisplayObjectContainer.getObjectsUnderPoint`.
+///
+/// The returned objects are ordered from bottom to top, so the topmost object is last.
 pub fn get_objects_under_point<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(
-        activation,
-        "flash.display.DisplayObjectContainer",
-        "getObjectsUnderPoint",
-        "proper hit-test behavior"
-    );
-
     let point = args.get_object(activation, 0, "point")?;
     let x = point
         .get_public_property("x", activation)?
@@ -566,20 +621,16 @@ pub fn get_objects_under_point<'gc>(
     };
 
     let mut under_point = Vec::new();
-    let mut children = vec![this.as_display_object().unwrap()];
-    // FIXME - what are the actual options?
-    let options = HitTestOptions::SKIP_MASK;
-    while let Some(child) = children.pop() {
-        if child.hit_test_shape(&mut activation.context, point, options) {
-            under_point.push(Some(child.object2()));
-        }
-        if let Some(container) = child.as_container() {
-            for child in container.iter_render_list() {
-                children.push(child);
-            }
-        }
+    if let Some(dobj) = this.as_display_object() {
+        objects_under_point(&mut activation.context, dobj, point, &mut under_point);
     }
 
+    let under_point = under_point
+        .into_iter()
+        .filter_map(|dobj| dobj.object2().as_object())
+        .map(|obj| Some(obj.into()))
+        .collect();
+
     Ok(ArrayObject::from_storage(activation, ArrayStorage::from_storage(under_point))?.into())
 }
 
@@ -591,8 +642,11 @@ pub fn are_inaccessible_objects_under_point<'gc>(
     avm2_stub_method!(
         activation,
         "flash.display.DisplayObjectContainer",
-        "areInaccessibleObjectsUnderPoint"
+        "areInaccessibleObjectsUnderPoint",
+        "security sandboxes"
     );
+    // We don't implement security sandboxes, so every object under the point is
+    // accessible and `getObjectsUnderPoint` never omits anything.
     Ok(false.into())
 }
 
@@ -3,6 +3,7 @@
 use indexmap::IndexMap;
 
 use crate::avm2::activation::Activation;
+use crate::avm2::error::security_error;
 use crate::avm2::globals::flash::display::display_object::initialize_for_allocator;
 use crate::avm2::object::LoaderInfoObject;
 use crate::avm2::object::LoaderStream;
@@ -13,12 +14,13 @@ use crate::avm2::ClassObject;
 use crate::avm2::Multiname;
 use crate::avm2::{Error, Object};
 use crate::avm2_stub_method;
-use crate::backend::navigator::{NavigationMethod, Request};
+use crate::backend::navigator::{NavigationMethod, RedirectPolicy, Request};
 use crate::display_object::LoaderDisplay;
 use crate::display_object::MovieClip;
-use crate::loader::MovieLoaderVMData;
+use crate::loader::{ContentType, MovieLoaderVMData};
 use crate::tag_utils::SwfMovie;
 use std::sync::Arc;
+use std::time::Duration;
 
 pub fn loader_allocator<'gc>(
     class: ClassObject<'gc>,
@@ -117,8 +119,7 @@ pub fn request_from_url_request<'gc>(
     activation: &mut Activation<'_, 'gc>,
     url_request: Object<'gc>,
 ) -> Result<Request, Error<'gc>> {
-    // FIXME: set `followRedirects`  and `userAgent`
-    // from the `URLRequest`
+    // FIXME: set `userAgent` from the `URLRequest`
 
     let mut url = url_request
         .get_public_property("url", activation)?
@@ -159,11 +160,12 @@ pub fn request_from_url_request<'gc>(
 
     // TODO: URLRequest.method should not be able to have invalid types.
     // We should throw an error there on set.
-    let method = NavigationMethod::from_method_str(&method).unwrap_or(NavigationMethod::Get);
+    let method =
+        NavigationMethod::from_url_request_method(&method).unwrap_or(NavigationMethod::Get);
     let data = url_request.get_public_property("data", activation)?;
     let body = match (method, data) {
         (_, Value::Null | Value::Undefined) => None,
-        (NavigationMethod::Get, data) => {
+        (NavigationMethod::Get | NavigationMethod::Head, data) => {
             // This looks "wrong" but it's Flash-correct.
             // It simply appends the data to the URL if there's already a query,
             // otherwise it adds ?data.
@@ -195,8 +197,26 @@ pub fn request_from_url_request<'gc>(
         }
     };
 
+    let redirect_policy = if url_request
+        .get_public_property("followRedirects", activation)?
+        .coerce_to_boolean()
+    {
+        RedirectPolicy::Follow
+    } else {
+        RedirectPolicy::Manual
+    };
+
+    // A timeout of zero means the system default should be used.
+    let idle_timeout = url_request
+        .get_public_property("idleTimeout", activation)?
+        .coerce_to_number(activation)?;
+    let idle_timeout =
+        (idle_timeout > 0.0).then(|| Duration::from_secs_f64(idle_timeout.min(1e12) / 1000.0));
+
     let mut request = Request::request(method, url.to_string(), body);
     request.set_headers(string_headers);
+    request.set_redirect_policy(redirect_policy);
+    request.set_idle_timeout(idle_timeout);
 
     Ok(request)
 }
@@ -207,9 +227,23 @@ pub fn load_bytes<'gc>(
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     let arg0 = args.get_object(activation, 0, "data")?;
-    let bytearray = arg0.as_bytearray().unwrap();
     let context = args.try_get_object(activation, 1);
 
+    let allow_code_import = match context {
+        Some(context) => context
+            .get_public_property("allowCodeImport", activation)?
+            .coerce_to_boolean(),
+        None => true,
+    };
+    let bytearray = arg0.as_bytearray().unwrap();
+    if !allow_code_import && ContentType::sniff(bytearray.bytes()) == ContentType::Swf {
+        return Err(Error::AvmError(security_error(
+            activation,
+            "Error #3226: Cannot import a SWF file when LoaderContext.allowCodeImport is false.",
+            3226,
+        )?));
+    }
+
     // This is a dummy MovieClip, which will get overwritten in `Loader`
     let content = MovieClip::new(
         Arc::new(SwfMovie::empty(activation.context.swf.version())),
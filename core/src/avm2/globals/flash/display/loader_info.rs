@@ -117,20 +117,18 @@ pub fn get_bytes_loaded<'gc>(
     this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    if let Some(loader_stream) = this
-        .as_loader_info_object()
-        .and_then(|o| o.as_loader_stream())
-    {
-        match &*loader_stream {
-            LoaderStream::NotYetLoaded(_, None, _) => return Ok(0.into()),
-            LoaderStream::Swf(_, root) | LoaderStream::NotYetLoaded(_, Some(root), _) => {
-                return Ok(root
+    if let Some(loader_info) = this.as_loader_info_object() {
+        let streamed_bytes = loader_info.streamed_bytes() as u32;
+        if let Some(loader_stream) = loader_info.as_loader_stream() {
+            let preloaded_bytes = match &*loader_stream {
+                LoaderStream::NotYetLoaded(_, None, _) => 0,
+                LoaderStream::Swf(_, root) | LoaderStream::NotYetLoaded(_, Some(root), _) => root
                     .as_movie_clip()
                     .map(|mc| mc.compressed_loaded_bytes())
-                    .unwrap_or_default()
-                    .into())
-            }
-        };
+                    .unwrap_or_default(),
+            };
+            return Ok(preloaded_bytes.max(streamed_bytes).into());
+        }
     }
 
     Ok(Value::Undefined)
@@ -331,6 +329,31 @@ pub fn get_swf_version<'gc>(
     Ok(Value::Undefined)
 }
 
+/// `xmpMetadata` getter, in the `__ruffle__` namespace
+pub fn get_xmp_metadata<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(loader_stream) = this
+        .as_loader_info_object()
+        .and_then(|o| o.as_loader_stream())
+    {
+        match &*loader_stream {
+            LoaderStream::NotYetLoaded(_, _, _) => {
+                return Err(Error::AvmError(error(activation, INSUFFICIENT, 2099)?));
+            }
+            LoaderStream::Swf(root, _) => {
+                return Ok(root.xmp_metadata().map_or(Value::Null, |xmp| {
+                    AvmString::new_utf8(activation.context.gc_context, xmp).into()
+                }));
+            }
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
 /// `url` getter
 pub fn get_url<'gc>(
     activation: &mut Activation<'_, 'gc>,
@@ -0,0 +1,221 @@
+//! `flash.display.NativeWindow` native methods
+
+use crate::avm2::events::{dispatch_event_to_target, EventPhase};
+use crate::avm2::globals::flash::display::screen::{rect_to_object, viewport_rect};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, Error, EventObject, Multiname, Object, TObject, Value};
+use crate::backend::ui::{DesktopRect, NativeMenuItem};
+
+/// Implements `NativeWindow.hasNativeWindow`
+pub fn has_native_window<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(activation
+        .context
+        .navigator
+        .air_directories()
+        .is_some()
+        .into())
+}
+
+/// Implements `NativeWindow.getBounds`
+pub fn get_bounds<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let bounds = match activation.context.ui.window_bounds() {
+        Some(bounds) => bounds,
+        None => viewport_rect(activation),
+    };
+
+    rect_to_object(bounds, activation)
+}
+
+/// Implements `NativeWindow.setBounds`
+pub fn set_bounds<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    // Only AIR content owns the window it's shown in.
+    if activation.context.navigator.air_directories().is_none() {
+        tracing::warn!("Only AIR content can move or resize the window");
+        return Ok(Value::Undefined);
+    }
+
+    let bounds = DesktopRect {
+        x: args.get_f64(activation, 0)?.round() as i32,
+        y: args.get_f64(activation, 1)?.round() as i32,
+        width: args.get_f64(activation, 2)?.round().max(1.0) as u32,
+        height: args.get_f64(activation, 3)?.round().max(1.0) as u32,
+    };
+    activation.context.ui.set_window_bounds(bounds);
+
+    Ok(Value::Undefined)
+}
+
+/// The window the player is shown in, as `Stage.nativeWindow` returns it.
+fn main_window<'gc>(activation: &mut Activation<'_, 'gc>) -> Result<Object<'gc>, Error<'gc>> {
+    let class = activation.avm2().classes().nativewindow;
+    class
+        .get_property(
+            &Multiname::new(activation.avm2().flash_display_internal, "mainWindow"),
+            activation,
+        )?
+        .coerce_to_object(activation)
+}
+
+/// Dispatches `move` and `resize` on the `NativeWindow`, as appropriate for the host's window
+/// changing from `before` to `after`.
+pub fn dispatch_window_bounds_change<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    before: DesktopRect,
+    after: DesktopRect,
+) -> Result<(), Error<'gc>> {
+    let window = main_window(activation)?;
+    let before = rect_to_object(before, activation)?;
+    let after = rect_to_object(after, activation)?;
+    window.call_property(
+        &Multiname::new(activation.avm2().flash_display_internal, "boundsChanged"),
+        &[before, after],
+        activation,
+    )?;
+
+    Ok(())
+}
+
+/// Dispatches `activate` or `deactivate` on the `NativeWindow`, if the host's window gained or
+/// lost focus.
+pub fn dispatch_window_activation<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    active: bool,
+) -> Result<(), Error<'gc>> {
+    let window = main_window(activation)?;
+    window.call_property(
+        &Multiname::new(activation.avm2().flash_display_internal, "activeChanged"),
+        &[active.into()],
+        activation,
+    )?;
+
+    Ok(())
+}
+
+/// Implements `NativeWindow.installMenu`
+pub fn install_menu<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let mut objects = vec![];
+    let menu = match args.try_get_object(activation, 0) {
+        Some(menu) => Some(describe_menu(activation, menu, &mut objects, &mut vec![])?),
+        None => None,
+    };
+
+    activation.context.native_menu.set_items(objects);
+    activation.context.ui.set_native_menu(menu);
+
+    Ok(Value::Undefined)
+}
+
+/// Describes the items of a `NativeMenu` for the UI backend, numbering them by their position
+/// in `objects`.
+///
+/// `open_menus` are the menus this one is a submenu of. A submenu that's one of them again is
+/// left out, rather than described forever.
+fn describe_menu<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    menu: Object<'gc>,
+    objects: &mut Vec<Object<'gc>>,
+    open_menus: &mut Vec<Object<'gc>>,
+) -> Result<Vec<NativeMenuItem>, Error<'gc>> {
+    let items: Vec<Object<'gc>> = match menu.get_public_property("items", activation)? {
+        Value::Object(items) => items
+            .as_array_storage()
+            .map(|storage| {
+                storage
+                    .iter()
+                    .flatten()
+                    .filter_map(|v| v.as_object())
+                    .collect()
+            })
+            .unwrap_or_default(),
+        _ => vec![],
+    };
+
+    open_menus.push(menu);
+    let mut description = Vec::with_capacity(items.len());
+    for item in items {
+        let id = objects.len();
+        objects.push(item);
+
+        let submenu = match item.get_public_property("submenu", activation)? {
+            Value::Object(submenu) if !open_menus.iter().any(|m| Object::ptr_eq(*m, submenu)) => {
+                Some(describe_menu(activation, submenu, objects, open_menus)?)
+            }
+            _ => None,
+        };
+        description.push(NativeMenuItem {
+            id,
+            label: item
+                .get_public_property("label", activation)?
+                .coerce_to_string(activation)?
+                .to_string(),
+            enabled: item
+                .get_public_property("enabled", activation)?
+                .coerce_to_boolean(),
+            checked: item
+                .get_public_property("checked", activation)?
+                .coerce_to_boolean(),
+            is_separator: item
+                .get_public_property("isSeparator", activation)?
+                .coerce_to_boolean(),
+            submenu,
+        });
+    }
+    open_menus.pop();
+
+    Ok(description)
+}
+
+/// Dispatches `select` for a `NativeMenuItem` the user picked.
+///
+/// The event reaches the item first and then bubbles through every menu above it, with the item
+/// staying its target. Menus aren't display objects, so the usual bubbling doesn't apply.
+pub fn dispatch_native_menu_select<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    item: Object<'gc>,
+) -> Result<(), Error<'gc>> {
+    let event = EventObject::bare_event(&mut activation.context, "select", true, false);
+    let Some(mut event_mut) = event.as_event_mut(activation.context.gc_context) else {
+        return Err("NativeMenuItem select event is not an Event".into());
+    };
+    event_mut.set_target(item);
+    event_mut.set_phase(EventPhase::AtTarget);
+    drop(event_mut);
+    dispatch_event_to_target(activation, item, item, event)?;
+
+    if let Some(mut event_mut) = event.as_event_mut(activation.context.gc_context) {
+        event_mut.set_phase(EventPhase::Bubbling);
+    }
+    let mut visited = vec![];
+    let mut menu = item.get_public_property("menu", activation)?.as_object();
+    while let Some(current) = menu {
+        let stopped = event
+            .as_event()
+            .map_or(true, |event| event.is_propagation_stopped());
+        if stopped || visited.iter().any(|m| Object::ptr_eq(*m, current)) {
+            break;
+        }
+        visited.push(current);
+        dispatch_event_to_target(activation, current, current, event)?;
+        menu = current
+            .get_public_property("parent", activation)?
+            .as_object();
+    }
+
+    Ok(())
+}
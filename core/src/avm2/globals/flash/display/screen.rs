@@ -0,0 +1,63 @@
+//! `flash.display.Screen` native methods
+
+use crate::avm2::{Activation, ArrayObject, ArrayStorage, Error, Object, Value};
+use crate::backend::ui::{DesktopRect, ScreenInfo};
+
+/// The area of the player, for hosts that can't tell where it is.
+pub fn viewport_rect(activation: &mut Activation<'_, '_>) -> DesktopRect {
+    let viewport_dimensions = activation.context.renderer.viewport_dimensions();
+    // Viewport size is adjusted for HiDPI.
+    let scale_factor = viewport_dimensions.scale_factor;
+    DesktopRect {
+        x: 0,
+        y: 0,
+        width: (f64::from(viewport_dimensions.width) / scale_factor).round() as u32,
+        height: (f64::from(viewport_dimensions.height) / scale_factor).round() as u32,
+    }
+}
+
+pub fn rect_to_object<'gc>(
+    rect: DesktopRect,
+    activation: &mut Activation<'_, 'gc>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let object = activation.avm2().classes().rectangle.construct(
+        activation,
+        &[
+            rect.x.into(),
+            rect.y.into(),
+            rect.width.into(),
+            rect.height.into(),
+        ],
+    )?;
+    Ok(object.into())
+}
+
+/// Implements `Screen.queryScreens`
+pub fn query_screens<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let mut screens = activation.context.ui.screens();
+    if screens.is_empty() {
+        let bounds = viewport_rect(activation);
+        screens.push(ScreenInfo {
+            bounds,
+            visible_bounds: bounds,
+            color_depth: 24,
+        });
+    }
+
+    let mut values = Vec::with_capacity(screens.len());
+    for screen in screens {
+        let info = [
+            rect_to_object(screen.bounds, activation)?,
+            rect_to_object(screen.visible_bounds, activation)?,
+            screen.color_depth.into(),
+        ];
+        let info = ArrayObject::from_storage(activation, ArrayStorage::from_args(&info))?;
+        values.push(Some(info.into()));
+    }
+
+    Ok(ArrayObject::from_storage(activation, ArrayStorage::from_storage(values))?.into())
+}
@@ -226,7 +226,10 @@ pub fn set_frame_rate<'gc>(
 ) -> Result<Value<'gc>, Error<'gc>> {
     if !activation.context.forced_frame_rate {
         let new_frame_rate = args.get_f64(activation, 0)?;
-        *activation.context.frame_rate = new_frame_rate;
+        // Flash clamps the frame rate and ignores NaN.
+        if !new_frame_rate.is_nan() {
+            *activation.context.frame_rate = new_frame_rate.clamp(0.01, 1000.0);
+        }
     }
 
     Ok(Value::Undefined)
@@ -329,11 +332,17 @@ pub fn get_stage_width<'gc>(
 
 /// Implement `stageWidth`'s setter
 pub fn set_stage_width<'gc>(
-    _activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    // For some reason this value is settable but it does nothing.
+    // This doesn't resize anything, but with `noScale` the new value is reported until the
+    // stage is next resized.
+    if let Some(dobj) = this.as_display_object().and_then(|this| this.as_stage()) {
+        let width = args.get_i32(activation, 0)?.max(0) as u32;
+        dobj.set_stage_width(activation.context.gc_context, width);
+    }
+
     Ok(Value::Undefined)
 }
 
@@ -352,11 +361,17 @@ pub fn get_stage_height<'gc>(
 
 /// Implement `stageHeight`'s setter
 pub fn set_stage_height<'gc>(
-    _activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    // For some reason this value is settable but it does nothing.
+    // This doesn't resize anything, but with `noScale` the new value is reported until the
+    // stage is next resized.
+    if let Some(dobj) = this.as_display_object().and_then(|this| this.as_stage()) {
+        let height = args.get_i32(activation, 0)?.max(0) as u32;
+        dobj.set_stage_height(activation.context.gc_context, height);
+    }
+
     Ok(Value::Undefined)
 }
 
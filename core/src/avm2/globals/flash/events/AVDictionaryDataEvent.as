@@ -21,6 +21,10 @@ package flash.events
             this._time = init_dataTime;
         }
         
+        override public function clone():Event
+        {
+            return new AVDictionaryDataEvent(this.type, this.bubbles, this.cancelable, this._dictionary, this._time);
+        }
 
         public function get dictionary() : Dictionary
         {
@@ -17,6 +17,10 @@ package flash.events
             this._userData = userData;
         }
         
+        override public function clone():Event
+        {
+            return new AVPauseAtPeriodEndEvent(this.type, this.bubbles, this.cancelable, this._userData);
+        }
 
         public function get userData() : int
         {
@@ -16,6 +16,10 @@ package flash.events
             this._reason = reason;
         }
         
+        override public function clone():Event
+        {
+            return new AudioOutputChangeEvent(this.type, this.bubbles, this.cancelable, this._reason);
+        }
 
         public function get reason() : String
         {
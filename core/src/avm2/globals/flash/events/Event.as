@@ -39,6 +39,8 @@ package flash.events {
 
 		public static const INIT:String = "init";
 
+		public static const LOCATION_CHANGE:String = "locationChange";
+
 		public static const MOUSE_LEAVE:String = "mouseLeave";
 
 		public static const OPEN:String = "open";
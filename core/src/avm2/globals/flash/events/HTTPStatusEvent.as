@@ -26,7 +26,10 @@ package flash.events
         //  Creates a copy of the HTTPStatusEvent object and sets the value of each property to match that of the original.
         override public function clone():Event
         {
-            return new HTTPStatusEvent(this.type, this.bubbles, this.cancelable, this.status, this.redirected);
+            var event:HTTPStatusEvent = new HTTPStatusEvent(this.type, this.bubbles, this.cancelable, this.status, this.redirected);
+            event.responseURL = this.responseURL;
+            event.responseHeaders = this.responseHeaders;
+            return event;
         }
 
         //  Returns a string that contains all the properties of the HTTPStatusEvent object.
@@ -0,0 +1,36 @@
+package flash.events {
+    import flash.geom.Rectangle;
+
+    public class NativeWindowBoundsEvent extends Event {
+        public static const MOVE: String = "move";
+        public static const MOVING: String = "moving";
+        public static const RESIZE: String = "resize";
+        public static const RESIZING: String = "resizing";
+
+        private var _beforeBounds: Rectangle;
+        private var _afterBounds: Rectangle;
+
+        public function NativeWindowBoundsEvent(type: String, bubbles: Boolean = false, cancelable: Boolean = false,
+                                                beforeBounds: Rectangle = null, afterBounds: Rectangle = null) {
+            super(type, bubbles, cancelable);
+            this._beforeBounds = beforeBounds;
+            this._afterBounds = afterBounds;
+        }
+
+        public function get beforeBounds(): Rectangle {
+            return this._beforeBounds;
+        }
+
+        public function get afterBounds(): Rectangle {
+            return this._afterBounds;
+        }
+
+        override public function clone(): Event {
+            return new NativeWindowBoundsEvent(this.type, this.bubbles, this.cancelable, this.beforeBounds, this.afterBounds);
+        }
+
+        override public function toString(): String {
+            return this.formatToString("NativeWindowBoundsEvent", "type", "bubbles", "cancelable", "eventPhase", "beforeBounds", "afterBounds");
+        }
+    }
+}
@@ -0,0 +1,26 @@
+package flash.events {
+    import flash.errors.SQLError;
+
+    public class SQLErrorEvent extends ErrorEvent {
+        public static const ERROR: String = "error";
+
+        private var _error: SQLError;
+
+        public function SQLErrorEvent(type: String, bubbles: Boolean = false, cancelable: Boolean = false, error: SQLError = null) {
+            super(type, bubbles, cancelable, error != null ? error.message : "", error != null ? error.errorID : 0);
+            this._error = error;
+        }
+
+        public function get error(): SQLError {
+            return this._error;
+        }
+
+        override public function clone(): Event {
+            return new SQLErrorEvent(this.type, this.bubbles, this.cancelable, this._error);
+        }
+
+        override public function toString(): String {
+            return this.formatToString("SQLErrorEvent", "type", "bubbles", "cancelable", "error");
+        }
+    }
+}
@@ -19,6 +19,10 @@ package flash.events
             this._availability = availability;
         }
         
+        override public function clone():Event
+        {
+            return new StageVideoAvailabilityEvent(this.type, this.bubbles, this.cancelable, this._availability);
+        }
 
         public function get availability() : String
         {
@@ -20,6 +20,10 @@ package flash.events
             this._colorSpace = colorSpace;
         }
         
+        override public function clone():Event
+        {
+            return new StageVideoEvent(this.type, this.bubbles, this.cancelable, this._status, this._colorSpace);
+        }
 
         public function get status() : String
         {
@@ -8,6 +8,7 @@ import flash.utils.ByteArray;
 import flash.display.InteractiveObject;
 import __ruffle__.stub_method;
 
+[API("667")]
 public class TouchEvent extends Event {
     public static const PROXIMITY_BEGIN: String = "proximityBegin"; // [static] Defines the value of the type property of a PROXIMITY_BEGIN touch event object.
     public static const PROXIMITY_END: String = "proximityEnd"; // [static] Defines the value of the type property of a PROXIMITY_END touch event object.
@@ -21,6 +21,10 @@ package flash.events
             this._status = status;
         }
         
+        override public function clone():Event
+        {
+            return new VideoEvent(this.type, this.bubbles, this.cancelable, this._status);
+        }
 
         public function get status() : String
         {
@@ -19,6 +19,10 @@ package flash.events
             this._colorSpace = colorSpace;
         }
         
+        override public function clone():Event
+        {
+            return new VideoTextureEvent(this.type, this.bubbles, this.cancelable, this._status, this._colorSpace);
+        }
 
         public function get status() : String
         {
@@ -1,7 +1,9 @@
 //! `flash.events.EventDispatcher` builtin/prototype
 
 use crate::avm2::activation::Activation;
-use crate::avm2::events::{dispatch_event as dispatch_event_internal, parent_of};
+use crate::avm2::events::{
+    dispatch_event as dispatch_event_internal, will_trigger as will_trigger_internal,
+};
 use crate::avm2::object::{DispatchObject, Object, TObject};
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::value::Value;
@@ -44,12 +46,18 @@ pub fn add_event_listener<'gc>(
         .as_callable(activation, None, None, false)?;
     let use_capture = args.get_bool(2);
     let priority = args.get_i32(activation, 3)?;
+    let use_weak_reference = args.get_bool(4);
 
-    //TODO: If we ever get weak GC references, we should respect `useWeakReference`.
     dispatch_list
         .as_dispatch_mut(activation.context.gc_context)
         .ok_or_else(|| Error::from("Internal properties should have what I put in them"))?
-        .add_event_listener(event_type, priority, listener, use_capture);
+        .add_event_listener(
+            event_type,
+            priority,
+            listener,
+            use_capture,
+            use_weak_reference,
+        );
 
     Avm2::register_broadcast_listener(&mut activation.context, this, event_type);
 
@@ -101,30 +109,9 @@ pub fn will_trigger<'gc>(
     this: Object<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    let dispatch_list = dispatch_list(activation, this)?;
     let event_type = args.get_string(activation, 0)?;
 
-    if dispatch_list
-        .as_dispatch_mut(activation.context.gc_context)
-        .ok_or_else(|| Error::from("Internal properties should have what I put in them"))?
-        .has_event_listener(event_type)
-    {
-        return Ok(true.into());
-    }
-
-    let target = this
-        .get_property(
-            &Multiname::new(activation.avm2().flash_events_internal, "_target"),
-            activation,
-        )?
-        .as_object()
-        .unwrap_or(this);
-
-    if let Some(parent) = parent_of(target) {
-        return will_trigger(activation, parent, args);
-    }
-
-    Ok(false.into())
+    Ok(will_trigger_internal(activation, this, event_type)?.into())
 }
 
 /// Implements `EventDispatcher.dispatchEvent`.
@@ -1,3 +1,4 @@
 //! `flash.external` namespace
 
+pub mod extension_context;
 pub mod external_interface;
@@ -0,0 +1,44 @@
+package flash.external
+{
+    import flash.events.EventDispatcher;
+    import flash.filesystem.File;
+
+    public final class ExtensionContext extends EventDispatcher
+    {
+        private var _actionScriptData: Object;
+
+        public function ExtensionContext() {
+        }
+
+        public static function createExtensionContext(extensionID: String, contextType: String): ExtensionContext {
+            if (extensionID == null) {
+                throw new ArgumentError("Error #2007: Parameter extensionID must be non-null.", 2007);
+            }
+
+            var context: ExtensionContext = new ExtensionContext();
+            if (!context.init(extensionID, contextType)) {
+                return null;
+            }
+            return context;
+        }
+
+        public static function getExtensionDirectory(extensionID: String): File {
+            return File.applicationDirectory.resolvePath("META-INF/AIR/extensions/" + extensionID);
+        }
+
+        public function get actionScriptData(): Object {
+            return this._actionScriptData;
+        }
+
+        public function set actionScriptData(value: Object): void {
+            this._actionScriptData = value;
+        }
+
+        // Returns false if no host implementation of the extension accepted the context type.
+        private native function init(extensionID: String, contextType: String): Boolean;
+
+        public native function call(functionName: String, ... args): Object;
+
+        public native function dispose(): void;
+    }
+}
@@ -0,0 +1,73 @@
+//! `flash.external.ExtensionContext` native methods
+
+use crate::avm2::error::{argument_error, illegal_operation_error};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, Error, Object, Value};
+use crate::external::Value as ExternalValue;
+use crate::native_extension::CallError;
+
+/// Implements `ExtensionContext.init`
+pub fn init<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let id = args.get_string(activation, 0)?.to_utf8_lossy().into_owned();
+    let context_type = match args.get_value(1) {
+        Value::Null | Value::Undefined => String::new(),
+        value => value
+            .coerce_to_string(activation)?
+            .to_utf8_lossy()
+            .into_owned(),
+    };
+
+    Ok(activation
+        .context
+        .native_extensions
+        .create_context(this, &id, &context_type)
+        .into())
+}
+
+/// Implements `ExtensionContext.call`
+pub fn call<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = args.get_string(activation, 0)?.to_utf8_lossy().into_owned();
+    let external_args: Vec<_> = args[1..]
+        .iter()
+        .map(|arg| ExternalValue::from_avm2(*arg))
+        .collect();
+
+    match activation
+        .context
+        .native_extensions
+        .call(this, &name, &external_args)
+    {
+        Ok(result) => Ok(result.into_avm2(activation)),
+        Err(CallError::Disposed) => Err(Error::AvmError(illegal_operation_error(
+            activation,
+            "Error #3501: The extension context has already been disposed.",
+            3501,
+        )?)),
+        Err(CallError::NoSuchFunction) => Err(Error::AvmError(argument_error(
+            activation,
+            &format!(
+                "Error #3500: The extension context does not have a method with the name {name}."
+            ),
+            3500,
+        )?)),
+    }
+}
+
+/// Implements `ExtensionContext.dispose`
+pub fn dispose<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    activation.context.native_extensions.dispose(this);
+
+    Ok(Value::Undefined)
+}
@@ -0,0 +1,85 @@
+package flash.filesystem
+{
+    import flash.net.FileReference;
+
+    public class File extends FileReference
+    {
+        public static const lineEnding: String = "\n";
+        public static const separator: String = "/";
+
+        private var _url: String;
+
+        public function File(path: String = null) {
+            if (path != null) {
+                this.nativePath = path;
+            }
+        }
+
+        // The directory the application was installed to, as `app:/`.
+        public static function get applicationDirectory(): File {
+            var file: File = new File();
+            file._url = "app:/";
+            return file;
+        }
+
+        // The application's private storage directory, as `app-storage:/`.
+        public static function get applicationStorageDirectory(): File {
+            var file: File = new File();
+            file._url = "app-storage:/";
+            return file;
+        }
+
+        public function get url(): String {
+            return this._url;
+        }
+
+        public function set url(value: String): void {
+            this._url = value;
+        }
+
+        public function get nativePath(): String {
+            if (this._url == null) {
+                return null;
+            }
+            return File.toNativePath(this._url);
+        }
+
+        public function set nativePath(value: String): void {
+            this._url = File.fromNativePath(value);
+        }
+
+        override public function get name(): String {
+            if (this._url == null) {
+                return null;
+            }
+            var path: String = this._url.replace(/[?#].*$/, "").replace(/\/+$/, "");
+            return decodeURIComponent(path.substring(path.lastIndexOf("/") + 1));
+        }
+
+        public function get exists(): Boolean {
+            return this._url != null && File.pathExists(this._url);
+        }
+
+        public function resolvePath(path: String): File {
+            var file: File = new File();
+            file._url = File.joinUrl(this._url, path);
+            return file;
+        }
+
+        public function clone(): File {
+            var file: File = new File();
+            file._url = this._url;
+            return file;
+        }
+
+        // Maps a URL (including `app:/` and `app-storage:/`) to a path on disk,
+        // or returns it unchanged if it doesn't refer to one.
+        private static native function toNativePath(url: String): String;
+
+        private static native function fromNativePath(path: String): String;
+
+        private static native function pathExists(url: String): Boolean;
+
+        private static native function joinUrl(base: String, path: String): String;
+    }
+}
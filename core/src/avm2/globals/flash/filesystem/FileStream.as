@@ -0,0 +1,255 @@
+package flash.filesystem {
+    import flash.errors.IOError;
+    import flash.events.Event;
+    import flash.events.EventDispatcher;
+    import flash.utils.ByteArray;
+    import flash.utils.IDataInput;
+    import flash.utils.IDataOutput;
+
+    // The whole file is read when it's opened and written back when it's closed, so that all
+    // reading and writing goes through the same code as `ByteArray`.
+    public class FileStream extends EventDispatcher implements IDataInput, IDataOutput {
+        // Null while the stream isn't open.
+        private var _url: String = null;
+        private var _mode: String = null;
+        private var _buffer: ByteArray = new ByteArray();
+        private var _modified: Boolean = false;
+
+        public function FileStream() {
+        }
+
+        public function open(file: File, fileMode: String): void {
+            if (this._url != null) {
+                this.close();
+            }
+
+            var bytes: ByteArray = null;
+            if (fileMode != FileMode.WRITE) {
+                bytes = FileStream.readFile(file.url);
+            }
+            if (bytes == null) {
+                if (fileMode == FileMode.READ) {
+                    throw new IOError("Error #3003: File or directory does not exist.", 3003);
+                }
+                bytes = new ByteArray();
+            }
+
+            bytes.endian = this._buffer.endian;
+            bytes.objectEncoding = this._buffer.objectEncoding;
+            bytes.position = fileMode == FileMode.APPEND ? bytes.length : 0;
+            this._buffer = bytes;
+            this._url = file.url;
+            this._mode = fileMode;
+            // Opening a file to write truncates it, even if nothing gets written.
+            this._modified = fileMode == FileMode.WRITE;
+        }
+
+        public function close(): void {
+            if (this._url == null) {
+                return;
+            }
+
+            var url: String = this._url;
+            this._url = null;
+            this._mode = null;
+            if (this._modified) {
+                this._modified = false;
+                if (!FileStream.writeFile(url, this._buffer)) {
+                    throw new IOError("Error #2038: File I/O Error.", 2038);
+                }
+            }
+            this.dispatchEvent(new Event(Event.CLOSE));
+        }
+
+        public function get bytesAvailable(): uint {
+            return this._url == null ? 0 : this._buffer.bytesAvailable;
+        }
+
+        public function get position(): Number {
+            return this._buffer.position;
+        }
+
+        public function set position(value: Number): void {
+            this._buffer.position = value;
+        }
+
+        public function get endian(): String {
+            return this._buffer.endian;
+        }
+
+        public function set endian(value: String): void {
+            this._buffer.endian = value;
+        }
+
+        public function get objectEncoding(): uint {
+            return this._buffer.objectEncoding;
+        }
+
+        public function set objectEncoding(value: uint): void {
+            this._buffer.objectEncoding = value;
+        }
+
+        public function truncate(): void {
+            this.checkWritable();
+            this._buffer.length = this._buffer.position;
+        }
+
+        private function checkReadable(): void {
+            if (this._url == null) {
+                throw new IOError("Error #2029: This FileStream object does not have a stream opened.", 2029);
+            }
+            if (this._mode == FileMode.WRITE || this._mode == FileMode.APPEND) {
+                throw new IOError("Error #3001: File or directory access denied.", 3001);
+            }
+        }
+
+        private function checkWritable(): void {
+            if (this._url == null) {
+                throw new IOError("Error #2029: This FileStream object does not have a stream opened.", 2029);
+            }
+            if (this._mode == FileMode.READ) {
+                throw new IOError("Error #3001: File or directory access denied.", 3001);
+            }
+            if (this._mode == FileMode.APPEND) {
+                this._buffer.position = this._buffer.length;
+            }
+            this._modified = true;
+        }
+
+        public function readBoolean(): Boolean {
+            this.checkReadable();
+            return this._buffer.readBoolean();
+        }
+
+        public function readByte(): int {
+            this.checkReadable();
+            return this._buffer.readByte();
+        }
+
+        public function readBytes(bytes: ByteArray, offset: uint = 0, length: uint = 0): void {
+            this.checkReadable();
+            this._buffer.readBytes(bytes, offset, length);
+        }
+
+        public function readDouble(): Number {
+            this.checkReadable();
+            return this._buffer.readDouble();
+        }
+
+        public function readFloat(): Number {
+            this.checkReadable();
+            return this._buffer.readFloat();
+        }
+
+        public function readInt(): int {
+            this.checkReadable();
+            return this._buffer.readInt();
+        }
+
+        public function readMultiByte(length: uint, charSet: String): String {
+            this.checkReadable();
+            return this._buffer.readMultiByte(length, charSet);
+        }
+
+        public function readObject(): * {
+            this.checkReadable();
+            return this._buffer.readObject();
+        }
+
+        public function readShort(): int {
+            this.checkReadable();
+            return this._buffer.readShort();
+        }
+
+        public function readUnsignedByte(): uint {
+            this.checkReadable();
+            return this._buffer.readUnsignedByte();
+        }
+
+        public function readUnsignedInt(): uint {
+            this.checkReadable();
+            return this._buffer.readUnsignedInt();
+        }
+
+        public function readUnsignedShort(): uint {
+            this.checkReadable();
+            return this._buffer.readUnsignedShort();
+        }
+
+        public function readUTF(): String {
+            this.checkReadable();
+            return this._buffer.readUTF();
+        }
+
+        public function readUTFBytes(length: uint): String {
+            this.checkReadable();
+            return this._buffer.readUTFBytes(length);
+        }
+
+        public function writeBoolean(value: Boolean): void {
+            this.checkWritable();
+            this._buffer.writeBoolean(value);
+        }
+
+        public function writeByte(value: int): void {
+            this.checkWritable();
+            this._buffer.writeByte(value);
+        }
+
+        public function writeBytes(bytes: ByteArray, offset: uint = 0, length: uint = 0): void {
+            this.checkWritable();
+            this._buffer.writeBytes(bytes, offset, length);
+        }
+
+        public function writeDouble(value: Number): void {
+            this.checkWritable();
+            this._buffer.writeDouble(value);
+        }
+
+        public function writeFloat(value: Number): void {
+            this.checkWritable();
+            this._buffer.writeFloat(value);
+        }
+
+        public function writeInt(value: int): void {
+            this.checkWritable();
+            this._buffer.writeInt(value);
+        }
+
+        public function writeMultiByte(value: String, charSet: String): void {
+            this.checkWritable();
+            this._buffer.writeMultiByte(value, charSet);
+        }
+
+        public function writeObject(object: *): void {
+            this.checkWritable();
+            this._buffer.writeObject(object);
+        }
+
+        public function writeShort(value: int): void {
+            this.checkWritable();
+            this._buffer.writeShort(value);
+        }
+
+        public function writeUnsignedInt(value: uint): void {
+            this.checkWritable();
+            this._buffer.writeUnsignedInt(value);
+        }
+
+        public function writeUTF(value: String): void {
+            this.checkWritable();
+            this._buffer.writeUTF(value);
+        }
+
+        public function writeUTFBytes(value: String): void {
+            this.checkWritable();
+            this._buffer.writeUTFBytes(value);
+        }
+
+        // Returns null if the file can't be read.
+        private static native function readFile(url: String): ByteArray;
+
+        // Returns false if the file can't be written.
+        private static native function writeFile(url: String, bytes: ByteArray): Boolean;
+    }
+}
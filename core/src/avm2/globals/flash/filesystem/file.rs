@@ -0,0 +1,125 @@
+//! `flash.filesystem.File` native methods
+
+use crate::avm2::error::argument_error;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, Error, Object, Value};
+use crate::backend::navigator::{url_from_file_path, url_to_file_path};
+use crate::string::AvmString;
+use std::path::{Path, PathBuf};
+use url::Url;
+
+fn invalid_path<'gc>(activation: &mut Activation<'_, 'gc>) -> Error<'gc> {
+    match argument_error(
+        activation,
+        "Error #2004: One of the parameters is invalid.",
+        2004,
+    ) {
+        Ok(err) => Error::AvmError(err),
+        Err(err) => err,
+    }
+}
+
+/// Resolves a URL, including `app:/` and `app-storage:/` ones, to a local path.
+///
+/// Only AIR content gets to see local paths.
+fn local_path(activation: &mut Activation<'_, '_>, url: &str) -> Option<PathBuf> {
+    let navigator = &activation.context.navigator;
+    navigator.air_directories()?;
+    navigator
+        .resolve_url(url)
+        .ok()
+        .filter(|url| url.scheme() == "file")
+        .and_then(|url| url_to_file_path(&url).ok())
+}
+
+/// Resolves a URL to a local path that AIR content may read, or if `write` is set, write.
+///
+/// Content can only reach files in its application directory, which is read-only, and
+/// in its storage directory. Content running outside of AIR can't reach any files.
+pub(crate) fn air_file_path(
+    activation: &mut Activation<'_, '_>,
+    url: &str,
+    write: bool,
+) -> Option<PathBuf> {
+    let navigator = &activation.context.navigator;
+    let directories = navigator.air_directories()?;
+    let url = navigator
+        .resolve_url(url)
+        .ok()
+        .filter(|url| url.scheme() == "file")?;
+
+    let in_directory = |directory: &Url| url.as_str().starts_with(directory.as_str());
+    if !in_directory(directories.storage()) && (write || !in_directory(directories.application())) {
+        tracing::warn!("AIR content tried to access {url} outside of its directories");
+        return None;
+    }
+    url_to_file_path(&url).ok()
+}
+
+pub fn to_native_path<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let url = args.get_string(activation, 0)?.to_string();
+
+    let native_path = match local_path(activation, &url) {
+        Some(path) => path.to_string_lossy().into_owned(),
+        None => url,
+    };
+
+    Ok(AvmString::new_utf8(activation.context.gc_context, native_path).into())
+}
+
+pub fn from_native_path<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let path = args.get_string(activation, 0)?.to_string();
+
+    let Ok(url) = url_from_file_path(Path::new(&path)) else {
+        return Err(invalid_path(activation));
+    };
+
+    Ok(AvmString::new_utf8(activation.context.gc_context, url.as_str()).into())
+}
+
+pub fn path_exists<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let url = args.get_string(activation, 0)?.to_string();
+
+    Ok(air_file_path(activation, &url, false)
+        .map_or(false, |path| path.exists())
+        .into())
+}
+
+pub fn join_url<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let base = args.get_string(activation, 0)?.to_string();
+    let path = args.get_string(activation, 1)?.to_string();
+
+    let joined = if Path::new(&path).is_absolute() {
+        url_from_file_path(Path::new(&path)).ok()
+    } else {
+        // Paths are resolved as if every file were a directory.
+        let mut base = base;
+        if !base.ends_with('/') {
+            base.push('/');
+        }
+        Url::parse(&base)
+            .and_then(|base| base.join(&path.replace('\\', "/")))
+            .ok()
+    };
+    let Some(joined) = joined else {
+        return Err(invalid_path(activation));
+    };
+
+    Ok(AvmString::new_utf8(activation.context.gc_context, joined.as_str()).into())
+}
@@ -0,0 +1,43 @@
+//! `flash.filesystem.FileStream` native methods
+
+use super::file::air_file_path;
+use crate::avm2::bytearray::ByteArrayStorage;
+use crate::avm2::object::ByteArrayObject;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, Error, Object, TObject, Value};
+
+pub fn read_file<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let url = args.get_string(activation, 0)?.to_string();
+
+    let Some(data) =
+        air_file_path(activation, &url, false).and_then(|path| std::fs::read(path).ok())
+    else {
+        return Ok(Value::Null);
+    };
+
+    let storage = ByteArrayStorage::from_vec(data);
+    Ok(ByteArrayObject::from_storage(activation, storage)?.into())
+}
+
+pub fn write_file<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let url = args.get_string(activation, 0)?.to_string();
+    let bytes = args.get_object(activation, 1, "bytes")?;
+
+    let Some(path) = air_file_path(activation, &url, true) else {
+        return Ok(false.into());
+    };
+    let data = bytes
+        .as_bytearray()
+        .map(|bytearray| bytearray.bytes().to_vec())
+        .unwrap_or_default();
+
+    Ok(std::fs::write(path, data).is_ok().into())
+}
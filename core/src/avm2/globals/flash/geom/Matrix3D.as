@@ -404,8 +404,108 @@ package flash.geom {
 			other.rawData = rawData;
 		}
 
+		// Rotates the matrix around its own position, so that the object-relative direction `at`
+		// faces `pos` and the object-relative direction `up` is as close to world "up" as possible.
+		// Translation and scale are kept.
 		public function pointAt(pos:Vector3D, at:Vector3D = null, up:Vector3D = null):void {
-			stub_method("flash.geom.Matrix3D", "pointAt");
+			if (at == null) {
+				at = new Vector3D(0, 0, -1);
+			}
+			if (up == null) {
+				up = new Vector3D(0, -1, 0);
+			}
+
+			var position = this.position;
+			var forward = pos.subtract(position);
+			if (forward.normalize() == 0) {
+				return;
+			}
+
+			// The world axes the object should end up with.
+			var worldUp = orthogonalTo(new Vector3D(0, -1, 0), forward);
+			var worldSide = worldUp.crossProduct(forward);
+
+			// The object's own axes.
+			var localAt = at.clone();
+			if (localAt.normalize() == 0) {
+				return;
+			}
+			var localUp = orthogonalTo(up, localAt);
+			var localSide = localUp.crossProduct(localAt);
+
+			var scale = new Vector3D(
+				Math.sqrt(_rawData[0] * _rawData[0] + _rawData[1] * _rawData[1] + _rawData[2] * _rawData[2]),
+				Math.sqrt(_rawData[4] * _rawData[4] + _rawData[5] * _rawData[5] + _rawData[6] * _rawData[6]),
+				Math.sqrt(_rawData[8] * _rawData[8] + _rawData[9] * _rawData[9] + _rawData[10] * _rawData[10])
+			);
+
+			// The rotation maps each local axis onto its world axis: `world * transpose(local)`.
+			var world = new <Vector3D>[forward, worldUp, worldSide];
+			var local = new <Vector3D>[localAt, localUp, localSide];
+			var components = new <String>["x", "y", "z"];
+			for (var column = 0; column < 3; column++) {
+				for (var row = 0; row < 3; row++) {
+					var value:Number = 0;
+					for (var axis = 0; axis < 3; axis++) {
+						value += world[axis][components[row]] * local[axis][components[column]];
+					}
+					_rawData[column * 4 + row] = value * scale[components[column]];
+				}
+				_rawData[column * 4 + 3] = 0;
+			}
+			_rawData[12] = position.x;
+			_rawData[13] = position.y;
+			_rawData[14] = position.z;
+			_rawData[15] = 1;
+		}
+
+		// Interpolates translation and scale linearly, and rotation spherically.
+		public static function interpolate(thisMat:Matrix3D, toMat:Matrix3D, percent:Number):Matrix3D {
+			var from = thisMat.decompose(Orientation3D.QUATERNION);
+			var to = toMat.decompose(Orientation3D.QUATERNION);
+
+			var position = lerp(from[0], to[0], percent);
+			var scale = lerp(from[2], to[2], percent);
+
+			var a = from[1];
+			var b = to[1];
+			var cos = a.x * b.x + a.y * b.y + a.z * b.z + a.w * b.w;
+			// Take the shorter way around.
+			var sign = 1;
+			if (cos < 0) {
+				cos = -cos;
+				sign = -1;
+			}
+
+			var fromWeight = 1 - percent;
+			var toWeight = percent;
+			if (cos < 0.999999) {
+				var angle = Math.acos(cos);
+				var sin = Math.sin(angle);
+				fromWeight = Math.sin((1 - percent) * angle) / sin;
+				toWeight = Math.sin(percent * angle) / sin;
+			}
+			toWeight *= sign;
+
+			var rotation = new Vector3D(
+				a.x * fromWeight + b.x * toWeight,
+				a.y * fromWeight + b.y * toWeight,
+				a.z * fromWeight + b.z * toWeight,
+				a.w * fromWeight + b.w * toWeight
+			);
+			var length = Math.sqrt(rotation.lengthSquared + rotation.w * rotation.w);
+			if (length != 0) {
+				rotation.scaleBy(1 / length);
+				rotation.w /= length;
+			}
+
+			var result = new Matrix3D();
+			result.compose(new <Vector3D>[position, rotation, scale], Orientation3D.QUATERNION);
+			return result;
+		}
+
+		public function interpolateTo(toMat:Matrix3D, percent:Number):void {
+			copyFrom(interpolate(this, toMat, percent));
 		}
 
 		// Based on OpenFL: https://github.com/openfl/openfl/blob/971a4c9e43b5472fd84d73920a2b7c1b3d8d9257/src/openfl/geom/Matrix3D.hx#L1437
@@ -422,6 +522,10 @@ package flash.geom {
 				return false;
 			}
 
+			return compose(components, orientationStyle);
+		}
+
+		private function compose(components:Vector.<Vector3D>, orientationStyle:String):Boolean {
 			identity();
 
 			var scale = [];
@@ -499,7 +603,7 @@ package flash.geom {
 				_rawData[10] = 1e-15;
 			}
 
-			return !(components[2].x == 0 || components[2].y == 0 || components[2].y == 0);
+			return !(components[2].x == 0 || components[2].y == 0 || components[2].z == 0);
 		}
 
 		public function copyColumnTo(column:uint, vector3D:Vector3D):void {
@@ -734,6 +838,32 @@ package flash.geom {
 }
 
 import flash.geom.Orientation3D;
+import flash.geom.Vector3D;
+
+// Returns `v` with its component along the unit vector `axis` removed, normalized.
+// Falls back to any unit vector perpendicular to `axis` if `v` is parallel to it.
+function orthogonalTo(v:Vector3D, axis:Vector3D):Vector3D {
+	var along = axis.clone();
+	along.scaleBy(v.dotProduct(axis));
+	var result = v.subtract(along);
+	if (result.normalize() == 0) {
+		if (axis.x != 0 || axis.y != 0) {
+			result = new Vector3D(-axis.y, axis.x, 0);
+		} else {
+			result = new Vector3D(1, 0, 0);
+		}
+		result.normalize();
+	}
+	return result;
+}
+
+function lerp(a:Vector3D, b:Vector3D, percent:Number):Vector3D {
+	return new Vector3D(
+		a.x + (b.x - a.x) * percent,
+		a.y + (b.y - a.y) * percent,
+		a.z + (b.z - a.z) * percent
+	);
+}
 
 function checkOrientation(orientationStyle:String) {
 	if (!(orientationStyle == Orientation3D.AXIS_ANGLE || orientationStyle == Orientation3D.EULER_ANGLES || orientationStyle == Orientation3D.QUATERNION)) {
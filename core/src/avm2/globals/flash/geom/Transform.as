@@ -2,9 +2,8 @@ package flash.geom {
 	import flash.display.DisplayObject;
 	import flash.geom.Matrix3D;
 	import flash.geom.PerspectiveProjection;
-	import __ruffle__.stub_getter;
+	import flash.geom.Point;
 	import __ruffle__.stub_method;
-	import __ruffle__.stub_setter;
 
 	public class Transform {
 		internal var _displayObject:DisplayObject;
@@ -24,23 +23,44 @@ package flash.geom {
 		public native function get pixelBounds():Rectangle;
 
 		public function get matrix3D():Matrix3D {
-			stub_getter("flash.geom.Transform", "matrix3D");
-			return new Matrix3D();
+			var rawData:Vector.<Number> = this.getRawMatrix3D();
+			return rawData == null ? null : new Matrix3D(rawData);
 		}
 
 		public function set matrix3D(m:Matrix3D):void {
-			stub_setter("flash.geom.Transform", "matrix3D");
+			this.setRawMatrix3D(m == null ? null : m.rawData);
 		}
 
+		// Returns null if the display object has no 3D transform.
+		private native function getRawMatrix3D():Vector.<Number>;
+		private native function setRawMatrix3D(rawData:Vector.<Number>):void;
+
 		public function get perspectiveProjection():PerspectiveProjection {
-			stub_getter("flash.geom.Transform", "perspectiveProjection");
-			return new PerspectiveProjection();
+			var focalLength:Number = this.getPerspectiveFocalLength();
+			if (isNaN(focalLength)) {
+				return null;
+			}
+
+			var projection:PerspectiveProjection = new PerspectiveProjection();
+			projection.focalLength = focalLength;
+			projection.projectionCenter = this.getPerspectiveProjectionCenter();
+			return projection;
 		}
 
 		public function set perspectiveProjection(val: PerspectiveProjection):void {
-			stub_setter("flash.geom.Transform", "perspectiveProjection");
+			if (val == null) {
+				this.setPerspectiveProjection(NaN, 0, 0);
+			} else {
+				var center:Point = val.projectionCenter;
+				this.setPerspectiveProjection(val.focalLength, center.x, center.y);
+			}
 		}
 
+		// Return NaN and null if the display object has no perspective projection of its own.
+		private native function getPerspectiveFocalLength():Number;
+		private native function getPerspectiveProjectionCenter():Point;
+		private native function setPerspectiveProjection(focalLength:Number, centerX:Number, centerY:Number):void;
+
 		public function getRelativeMatrix3D(relativeTo:DisplayObject):Matrix3D {
 			stub_method("flash.geom.Transform", "getRelativeMatrix3D");
 			return new Matrix3D();
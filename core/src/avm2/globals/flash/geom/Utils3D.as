@@ -9,6 +9,12 @@ package flash.geom {
             return projected;
         }
 
+        public static function pointTowards(percent:Number, mat:Matrix3D, pos:Vector3D, at:Vector3D = null, up:Vector3D = null):Matrix3D {
+            var target = mat.clone();
+            target.pointAt(pos, at, up);
+            return Matrix3D.interpolate(mat, target, percent);
+        }
+
         // Based on https://github.com/openfl/openfl/blob/971a4c9e43b5472fd84d73920a2b7c1b3d8d9257/src/openfl/geom/Utils3D.hx
         public static function projectVectors(m:Matrix3D, verts:Vector.<Number>, projectedVerts:Vector.<Number>, uvts:Vector.<Number>):void {
             var n = m.rawData;
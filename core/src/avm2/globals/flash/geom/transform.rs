@@ -1,9 +1,12 @@
+use crate::avm2::object::VectorObject;
 use crate::avm2::parameters::ParametersExt;
+use crate::avm2::vector::VectorStorage;
 use crate::avm2::Multiname;
 use crate::avm2::{Activation, Error, Object, TObject, Value};
 use crate::avm2_stub_getter;
 use crate::display_object::TDisplayObject;
 use crate::prelude::{DisplayObject, Matrix, Twips};
+use ruffle_render::matrix3d::{Matrix3D, Projection};
 use ruffle_render::quality::StageQuality;
 use swf::{ColorTransform, Fixed8, Rectangle};
 
@@ -84,6 +87,95 @@ pub fn set_matrix<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements `Transform.getRawMatrix3D`.
+pub fn get_raw_matrix_3d<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let dobj = get_display_object(this, activation)?;
+    let Some(matrix) = dobj.matrix_3d(activation.context.gc_context) else {
+        return Ok(Value::Null);
+    };
+
+    let storage = VectorStorage::from_numbers(matrix.raw_data.to_vec(), false, activation);
+    Ok(VectorObject::from_vector(storage, activation)?.into())
+}
+
+/// Implements `Transform.setRawMatrix3D`.
+pub fn set_raw_matrix_3d<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let dobj = get_display_object(this, activation)?;
+
+    let matrix = if let Some(raw_data) = args.try_get_object(activation, 0) {
+        let values: Vec<Value<'gc>> = raw_data
+            .as_vector_storage()
+            .map(|storage| storage.iter().collect())
+            .unwrap_or_default();
+
+        let mut matrix = Matrix3D::IDENTITY;
+        for (element, value) in matrix.raw_data.iter_mut().zip(values) {
+            *element = value.coerce_to_number(activation)?;
+        }
+        Some(matrix)
+    } else {
+        None
+    };
+
+    dobj.set_matrix_3d(&mut activation.context, matrix);
+    Ok(Value::Undefined)
+}
+
+/// Implements `Transform.getPerspectiveFocalLength`.
+pub fn get_perspective_focal_length<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let dobj = get_display_object(this, activation)?;
+    let projection = dobj.base().perspective_projection();
+    Ok(projection.map_or(f64::NAN, |p| p.focal_length).into())
+}
+
+/// Implements `Transform.getPerspectiveProjectionCenter`.
+pub fn get_perspective_projection_center<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let dobj = get_display_object(this, activation)?;
+    let Some(projection) = dobj.base().perspective_projection() else {
+        return Ok(Value::Null);
+    };
+
+    let (x, y) = projection.center;
+    let point = activation.avm2().classes().point;
+    Ok(point.construct(activation, &[x.into(), y.into()])?.into())
+}
+
+/// Implements `Transform.setPerspectiveProjection`.
+pub fn set_perspective_projection<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let dobj = get_display_object(this, activation)?;
+    let focal_length = args.get_f64(activation, 0)?;
+    let projection = if focal_length.is_nan() {
+        None
+    } else {
+        Some(Projection {
+            focal_length,
+            center: (args.get_f64(activation, 1)?, args.get_f64(activation, 2)?),
+        })
+    };
+    dobj.set_perspective_projection(activation.context.gc_context, projection);
+    Ok(Value::Undefined)
+}
+
 pub fn get_concatenated_matrix<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
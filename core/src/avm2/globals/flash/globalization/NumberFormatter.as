@@ -6,6 +6,7 @@ package flash.globalization {
     import flash.globalization.NationalDigitsType;
     import flash.globalization.NumberParseResult;
 
+    [API("667")]
     public final class NumberFormatter {
         private var _decimalSeparator = ".";
         private var _digitsType = NationalDigitsType.EUROPEAN;
@@ -0,0 +1,3 @@
+//! `flash.html` namespace
+
+pub mod html_loader;
@@ -0,0 +1,85 @@
+package flash.html {
+    import flash.display.Sprite;
+    import flash.events.Event;
+    import flash.net.URLRequest;
+
+    // Pages are shown by a web view the embedder provides. Without one,
+    // `isSupported` is false and loads do nothing.
+    public class HTMLLoader extends Sprite {
+        private var _loaded: Boolean = false;
+
+        public function HTMLLoader() {
+            this.addEventListener(Event.COMPLETE, this.onComplete);
+        }
+
+        public static function get isSupported(): Boolean {
+            return HTMLLoader.hasWebView();
+        }
+
+        public static function createRootWindow(visible: Boolean = true, windowInitOptions: Object = null,
+                                                scrollBarsVisible: Boolean = true, bounds: Object = null): HTMLLoader {
+            return new HTMLLoader();
+        }
+
+        public function get loaded(): Boolean {
+            return this._loaded;
+        }
+
+        public function get location(): String {
+            return this.getLocation();
+        }
+
+        // A snapshot of the page's `window`, exposing what login pages are
+        // usually scraped for.
+        public function get window(): Object {
+            var loader: HTMLLoader = this;
+            return {
+                eval: function(script: String): * {
+                    return loader.executeJavaScript(script);
+                },
+                location: {
+                    href: this.getLocation()
+                },
+                document: {
+                    body: {
+                        innerText: this.getBodyText()
+                    }
+                }
+            };
+        }
+
+        public function load(urlRequestToLoad: URLRequest): void {
+            this._loaded = false;
+            this.loadUrl(urlRequestToLoad.url);
+        }
+
+        public function loadString(htmlContent: String): void {
+            this._loaded = false;
+            this.loadHtml(htmlContent);
+        }
+
+        public function reload(): void {
+            var location: String = this.location;
+            if (location != null) {
+                this._loaded = false;
+                this.loadUrl(location);
+            }
+        }
+
+        private function onComplete(event: Event): void {
+            this._loaded = true;
+        }
+
+        private static native function hasWebView(): Boolean;
+
+        private native function loadUrl(url: String): void;
+
+        private native function loadHtml(html: String): void;
+
+        private native function executeJavaScript(script: String): *;
+
+        private native function getBodyText(): String;
+
+        private native function getLocation(): String;
+    }
+}
@@ -0,0 +1,99 @@
+//! `flash.html.HTMLLoader` native methods
+
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, Error, Object, Value};
+use crate::string::AvmString;
+
+/// Implements `HTMLLoader.hasWebView`
+pub fn has_web_view<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(activation.context.web_views.is_supported().into())
+}
+
+/// Implements `HTMLLoader.loadUrl`
+pub fn load_url<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let url = args.get_string(activation, 0)?.to_string();
+    // Relative URLs are resolved as the navigator would, including `app:/` ones.
+    let url = match activation.context.navigator.resolve_url(&url) {
+        Ok(url) => url.to_string(),
+        Err(_) => url,
+    };
+    activation
+        .context
+        .web_views
+        .load_url(activation.context.gc_context, this, &url);
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `HTMLLoader.loadHtml`
+pub fn load_html<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let html = args.get_string(activation, 0)?.to_string();
+    activation
+        .context
+        .web_views
+        .load_string(activation.context.gc_context, this, &html);
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `HTMLLoader.executeJavaScript`
+pub fn execute_java_script<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let script = args.get_string(activation, 0)?.to_string();
+
+    match activation.context.web_views.execute_javascript(
+        activation.context.gc_context,
+        this,
+        &script,
+    ) {
+        Some(result) => Ok(result.into_avm2(activation)),
+        None => Ok(Value::Undefined),
+    }
+}
+
+/// Implements `HTMLLoader.getBodyText`
+pub fn get_body_text<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    match activation
+        .context
+        .web_views
+        .body_text(activation.context.gc_context, this)
+    {
+        Some(text) => Ok(AvmString::new_utf8(activation.context.gc_context, text).into()),
+        None => Ok(Value::Null),
+    }
+}
+
+/// Implements `HTMLLoader.getLocation`
+pub fn get_location<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    match activation
+        .context
+        .web_views
+        .location(activation.context.gc_context, this)
+    {
+        Some(location) => Ok(AvmString::new_utf8(activation.context.gc_context, location).into()),
+        None => Ok(Value::Null),
+    }
+}
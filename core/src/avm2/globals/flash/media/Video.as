@@ -8,8 +8,6 @@ package flash.media
     [Ruffle(InstanceAllocator)]
     public class Video extends DisplayObject
     {
-        private var _deblocking: int;
-        private var _smoothing: Boolean;
         private var _videoWidth: int;
         private var _videoHeight: int;
 
@@ -24,21 +22,11 @@ package flash.media
 
         private native function init(width: int, height: int);
 
-        public function get deblocking():int {
-            return this._deblocking;
-        }
-
-        public function set deblocking(value:int):void {
-            this._deblocking = value;
-        }
+        public native function get deblocking():int;
+        public native function set deblocking(value:int):void;
 
-        public function get smoothing():Boolean {
-            return this._smoothing;
-        }
-
-        public function set smoothing(value:Boolean):void {
-            this._smoothing = value;
-        }
+        public native function get smoothing():Boolean;
+        public native function set smoothing(value:Boolean):void;
 
         public function get videoWidth():int {
             return this._videoWidth;
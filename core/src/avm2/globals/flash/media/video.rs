@@ -2,6 +2,7 @@ use crate::avm2::globals::flash::display::display_object::initialize_for_allocat
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::{Activation, ClassObject, Error, Object, TObject, Value};
 use crate::display_object::{TDisplayObject, Video};
+use swf::VideoDeblocking;
 
 pub fn video_allocator<'gc>(
     class: ClassObject<'gc>,
@@ -54,6 +55,62 @@ pub fn init<'gc>(
     Ok(Value::Undefined)
 }
 
+pub fn get_deblocking<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(video) = this.as_display_object().and_then(|dobj| dobj.as_video()) {
+        return Ok((video.deblocking() as i32).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn set_deblocking<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(video) = this.as_display_object().and_then(|dobj| dobj.as_video()) {
+        let value = args.get_i32(activation, 0)?;
+        // Values Flash doesn't know of behave like the default.
+        let deblocking = u8::try_from(value)
+            .ok()
+            .and_then(VideoDeblocking::from_u8)
+            .unwrap_or(VideoDeblocking::UseVideoPacketValue);
+
+        video.set_deblocking(&mut activation.context, deblocking);
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_smoothing<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(video) = this.as_display_object().and_then(|dobj| dobj.as_video()) {
+        return Ok(video.smoothing().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn set_smoothing<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(video) = this.as_display_object().and_then(|dobj| dobj.as_video()) {
+        let smoothing = args.get_bool(0);
+        video.set_smoothing(activation.context.gc_context, smoothing);
+    }
+
+    Ok(Value::Undefined)
+}
+
 pub fn attach_net_stream<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
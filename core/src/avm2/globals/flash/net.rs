@@ -5,11 +5,13 @@ use crate::avm2::{Activation, Error, Object, Value};
 
 pub mod local_connection;
 pub mod net_connection;
+pub mod net_group;
 pub mod net_stream;
 pub mod object_encoding;
 pub mod shared_object;
 pub mod socket;
 pub mod url_loader;
+pub mod url_stream;
 pub mod xml_socket;
 
 /// Implements `flash.net.navigateToURL`
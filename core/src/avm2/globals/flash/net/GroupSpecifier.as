@@ -0,0 +1,112 @@
+package flash.net {
+    // NOTE: Ruffle only emulates NetGroups on the local network, so groupspecs are
+    // a readable list of URL-encoded options rather than Flash's binary format.
+    // Passwords are recorded in the authorizations, but never checked.
+    [API("667")]
+    public class GroupSpecifier {
+        private var _name:String;
+        private var _addresses:String = "";
+        private var _postingAuthorization:String = "";
+        private var _publishAuthorization:String = "";
+
+        public var ipMulticastMemberUpdatesEnabled:Boolean = true;
+        public var minGroupspecVersion:int = 2;
+        public var multicastEnabled:Boolean = false;
+        public var objectReplicationEnabled:Boolean = false;
+        public var peerToPeerDisabled:Boolean = false;
+        public var postingEnabled:Boolean = false;
+        public var routingEnabled:Boolean = false;
+        public var serverChannelEnabled:Boolean = false;
+
+        public function GroupSpecifier(name:String) {
+            if (name == null || name.length == 0) {
+                throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+            this._name = name;
+        }
+
+        public static function encodeBootstrapPeerIDSpec(peerID:String):String {
+            return "&bootstrap=" + encodeURIComponent(peerID);
+        }
+
+        public static function encodeIPMulticastAddressSpec(address:String, port:* = null, source:String = null):String {
+            if (port != null) {
+                if (address.indexOf(":") >= 0) {
+                    address = "[" + address + "]";
+                }
+                address += ":" + port;
+            }
+            var spec:String = "&ipmulticast=" + encodeURIComponent(address);
+            if (source != null) {
+                spec += "&source=" + encodeURIComponent(source);
+            }
+            return spec;
+        }
+
+        public static function encodePostingAuthorization(password:String):String {
+            return "&auth.posting=" + encodeURIComponent(password);
+        }
+
+        public static function encodePublishAuthorization(password:String):String {
+            return "&auth.publish=" + encodeURIComponent(password);
+        }
+
+        public function addBootstrapPeer(peerID:String):void {
+            this._addresses += encodeBootstrapPeerIDSpec(peerID);
+        }
+
+        public function addIPMulticastAddress(address:String, port:* = null, source:String = null):void {
+            this._addresses += encodeIPMulticastAddressSpec(address, port, source);
+        }
+
+        public function authorizations():String {
+            return this._postingAuthorization + this._publishAuthorization;
+        }
+
+        public function groupspecWithAuthorizations():String {
+            return this.groupspecWithoutAuthorizations() + this.authorizations();
+        }
+
+        public function groupspecWithoutAuthorizations():String {
+            var spec:String = "G:name=" + encodeURIComponent(this._name) + "&version=" + this.minGroupspecVersion;
+            if (this.postingEnabled) {
+                spec += "&posting";
+            }
+            if (this.routingEnabled) {
+                spec += "&routing";
+            }
+            if (this.multicastEnabled) {
+                spec += "&multicast";
+            }
+            if (this.objectReplicationEnabled) {
+                spec += "&replication";
+            }
+            if (this.peerToPeerDisabled) {
+                spec += "&nopeers";
+            }
+            if (this.serverChannelEnabled) {
+                spec += "&server";
+            }
+            if (this.ipMulticastMemberUpdatesEnabled) {
+                spec += "&memberupdates";
+            }
+            return spec + this._addresses;
+        }
+
+        public function makeUnique():void {
+            this._name += ":" + Math.floor(Math.random() * 0x7FFFFFFF).toString(16) + new Date().time.toString(16);
+        }
+
+        public function setPostingPassword(password:String = null, salt:String = null):void {
+            this._postingAuthorization = password == null ? "" : encodePostingAuthorization(password + (salt == null ? "" : salt));
+        }
+
+        public function setPublishPassword(password:String = null, salt:String = null):void {
+            this._publishAuthorization = password == null ? "" : encodePublishAuthorization(password + (salt == null ? "" : salt));
+        }
+
+        public function toString():String {
+            return this.groupspecWithAuthorizations();
+        }
+    }
+}
@@ -4,24 +4,34 @@ package flash.net {
     import __ruffle__.stub_method;
 
     public class NetConnection extends EventDispatcher {
+        namespace ruffle = "__ruffle__";
 
         public static var defaultObjectEncoding:uint = 3;
 
         public var objectEncoding:uint = NetConnection.defaultObjectEncoding;
 
+        ruffle var _connected:Boolean = false;
+
+        ruffle var _uri:String = null;
 
         public native function connect(command:String, ... arguments):void;
 
-        public function addHeader(operation:String, mustUnderstand:Boolean = false, param:Object = null):void {
-            stub_method("flash.net.NetConnection", "addHeader");
+        public native function get nearID():String;
+
+        public function get connected():Boolean {
+            return this.ruffle::_connected;
         }
 
-        public function call(command:String, responder:Responder, ... arguments):void {
-            stub_method("flash.net.NetConnection", "call");
+        public function get uri():String {
+            return this.ruffle::_uri;
         }
-        
-        public function close():void {
-            stub_method("flash.net.NetConnection", "close");
+
+        public function addHeader(operation:String, mustUnderstand:Boolean = false, param:Object = null):void {
+            stub_method("flash.net.NetConnection", "addHeader");
         }
+
+        public native function call(command:String, responder:Responder, ... arguments):void;
+
+        public native function close():void;
     }
 }
@@ -0,0 +1,82 @@
+package flash.net {
+    import flash.events.EventDispatcher;
+    import __ruffle__.stub_method;
+    import __ruffle__.stub_getter;
+
+    // NOTE: Members find each other on the local network, so only other Ruffle
+    // instances nearby can join the group. Object replication is not supported.
+    [API("667")]
+    public class NetGroup extends EventDispatcher {
+        public var receiveMode:String = NetGroupReceiveMode.EXACT;
+
+        public var replicationStrategy:String = NetGroupReplicationStrategy.LOWEST_FIRST;
+
+        public function NetGroup(connection:NetConnection, groupspec:String) {
+            this.connect(connection, groupspec);
+        }
+
+        private native function connect(connection:NetConnection, groupspec:String):void;
+
+        public native function get neighborCount():Number;
+
+        public function get estimatedMemberCount():Number {
+            return this.neighborCount + 1;
+        }
+
+        public function get localCoverageFrom():String {
+            stub_getter("flash.net.NetGroup", "localCoverageFrom");
+            return "0000000000000000000000000000000000000000000000000000000000000000";
+        }
+
+        public function get localCoverageTo():String {
+            stub_getter("flash.net.NetGroup", "localCoverageTo");
+            return "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff";
+        }
+
+        public native function close():void;
+
+        public native function post(message:Object):String;
+
+        public native function sendToAllNeighbors(message:Object):String;
+
+        public native function sendToNeighbor(message:Object, sendMode:String):String;
+
+        public native function sendToNearest(message:Object, groupAddress:String):String;
+
+        public native function convertPeerIDToGroupAddress(peerID:String):String;
+
+        public function addHaveObjects(startIndex:Number, endIndex:Number):void {
+            stub_method("flash.net.NetGroup", "addHaveObjects");
+        }
+
+        public function addMemberHint(peerID:String):Boolean {
+            stub_method("flash.net.NetGroup", "addMemberHint");
+            return false;
+        }
+
+        public function addNeighbor(peerID:String):Boolean {
+            stub_method("flash.net.NetGroup", "addNeighbor");
+            return false;
+        }
+
+        public function addWantObjects(startIndex:Number, endIndex:Number):void {
+            stub_method("flash.net.NetGroup", "addWantObjects");
+        }
+
+        public function denyRequestedObject(requestID:int):void {
+            stub_method("flash.net.NetGroup", "denyRequestedObject");
+        }
+
+        public function removeHaveObjects(startIndex:Number, endIndex:Number):void {
+            stub_method("flash.net.NetGroup", "removeHaveObjects");
+        }
+
+        public function removeWantObjects(startIndex:Number, endIndex:Number):void {
+            stub_method("flash.net.NetGroup", "removeWantObjects");
+        }
+
+        public function writeRequestedObject(requestID:int, object:Object):void {
+            stub_method("flash.net.NetGroup", "writeRequestedObject");
+        }
+    }
+}
@@ -21,13 +21,9 @@ package flash.net {
 
         }
 
-        public function appendBytes(bytes:ByteArray) {
-            stub_method("flash.net.NetStream", "appendBytes");
-        }
+        public native function appendBytes(bytes:ByteArray):void;
 
-        public function appendBytesAction(action:String) {
-            stub_method("flash.net.NetStream", "appendBytesAction");
-        }
+        public native function appendBytesAction(action:String):void;
 
         public function attach(connection:NetConnection) {
             stub_method("flash.net.NetStream", "attach");
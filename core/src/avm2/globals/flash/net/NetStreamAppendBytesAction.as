@@ -0,0 +1,7 @@
+package flash.net {
+    public final class NetStreamAppendBytesAction {
+        public static const END_SEQUENCE:String = "endSequence";
+        public static const RESET_BEGIN:String = "resetBegin";
+        public static const RESET_SEEK:String = "resetSeek";
+    }
+}
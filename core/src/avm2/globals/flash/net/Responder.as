@@ -1,9 +1,13 @@
 package flash.net {
-    import __ruffle__.stub_constructor;
-    
     public class Responder {
+        namespace ruffle = "__ruffle__";
+
+        ruffle var result: Function;
+        ruffle var status: Function;
+
         public function Responder(result:Function, status:Function = null) {
-            stub_constructor("flash.net.Responder");
+            this.ruffle::result = result;
+            this.ruffle::status = status;
         }
     }
 }
@@ -16,6 +16,8 @@ package flash.net {
 		public var digest:String;
 		private var _method:String = URLRequestMethod.GET;
 		private var _data:Object;
+		private var _followRedirects:Boolean = URLRequestDefaults.followRedirects;
+		private var _idleTimeout:Number = URLRequestDefaults.idleTimeout;
 
 		public function URLRequest(url:String = null) {
 			this.url = url;
@@ -46,6 +48,25 @@ package flash.net {
 			return this._contentType;
 		}
 
+		public function get followRedirects():Boolean {
+			return this._followRedirects;
+		}
+
+		public function set followRedirects(value:Boolean):void {
+			this._followRedirects = value;
+		}
+
+		public function get idleTimeout():Number {
+			return this._idleTimeout;
+		}
+
+		public function set idleTimeout(value:Number):void {
+			if (value < 0) {
+				throw new RangeError("Error #2027: Parameter idleTimeout must be a non-negative number; got " + value + ".", 2027);
+			}
+			this._idleTimeout = value;
+		}
+
 		public function get requestHeaders():Array {
 			return _requestHeaders;
 		}
@@ -35,7 +35,7 @@ package flash.net
         // Sets default user and password credentials for a selected host.
         public static function setLoginCredentialsForHost(hostname:String, user:String, password:String):*
         {
-            stub_method("flash.media.URLRequestDefaults", "setLoginCredentialsForHost");
+            stub_method("flash.net.URLRequestDefaults", "setLoginCredentialsForHost");
         }
 
         public static function get authenticate():Boolean
@@ -75,6 +75,9 @@ package flash.net
 
         public static function set idleTimeout(value:Number):void
         {
+            if (value < 0) {
+                throw new RangeError("Error #2027: Parameter idleTimeout must be a non-negative number; got " + value + ".", 2027);
+            }
             _idleTimeout = value;
         }
 
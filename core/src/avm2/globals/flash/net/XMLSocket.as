@@ -7,16 +7,15 @@ package flash.net
     import flash.events.SecurityErrorEvent;
     import flash.events.Event;
     import flash.events.DataEvent;
-    import flash.utils.ByteArray;
 
     public class XMLSocket extends EventDispatcher
     {
-        private var tempBuf:ByteArray = new ByteArray();
         private var socket:Socket;
 
         public function XMLSocket(host:String = null, port:int = 0)
         {
             this.socket = new Socket();
+            this.useNullFraming(this.socket);
 
             this.socket.addEventListener(Event.CLOSE, this.socketCloseListener);
             this.socket.addEventListener(Event.CONNECT, this.socketConnectEvent);
@@ -32,9 +31,11 @@ package flash.net
 
         private native function get domain():String;
 
+        // Makes the socket deliver each null-terminated message in a single `socketData` event.
+        private native function useNullFraming(socket:Socket):void;
+
         private function socketCloseListener(evt:Event):void
         {
-            this.tempBuf.clear();
             this.dispatchEvent(evt);
         }
 
@@ -45,26 +46,11 @@ package flash.net
 
         private function socketDataListener(evt:ProgressEvent):void
         {
-            // FIXME: There is probably a better way to do this.
-            for (var i:uint = 0; i < evt.bytesLoaded; i++)
-            {
-                var byte:int = this.socket.readByte();
-
-                if (byte == 0)
-                {
-                    var length:uint = this.tempBuf.position;
-                    this.tempBuf.position = 0;
-
-                    var data:String = this.tempBuf.readUTFBytes(length);
-                    this.tempBuf.clear();
-
-                    this.dispatchEvent(new DataEvent(DataEvent.DATA, false, false, data));
-                }
-                else
-                {
-                    this.tempBuf.writeByte(byte);
-                }
-            }
+            // Each event holds exactly one message, including its null terminator.
+            var data:String = this.socket.readUTFBytes(evt.bytesLoaded - 1);
+            this.socket.readByte();
+
+            this.dispatchEvent(new DataEvent(DataEvent.DATA, false, false, data));
         }
 
         private function socketIoErrorListener(evt:IOErrorEvent):void
@@ -94,7 +80,6 @@ package flash.net
 
         public function close():void
         {
-            this.tempBuf.clear();
             this.socket.close();
         }
 
@@ -1,30 +1,144 @@
 use crate::{
-    avm2::{Activation, Avm2, Error, EventObject, Object, Value},
+    avm2::{
+        parameters::ParametersExt, Activation, Avm2, Error, EventObject, Multiname, Namespace,
+        Object, TObject, Value,
+    },
     avm2_stub_method,
+    net_status::NetStatus,
+    string::AvmString,
 };
 
+fn ruffle_name<'gc>(activation: &mut Activation<'_, 'gc>, name: &'static str) -> Multiname<'gc> {
+    Multiname::new(
+        Namespace::package("__ruffle__", &mut activation.borrow_gc()),
+        name,
+    )
+}
+
+fn dispatch_status<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    status: NetStatus,
+) {
+    let event = EventObject::net_status_event(activation, "netStatus", status.info(None));
+    Avm2::dispatch_event(&mut activation.context, event, this);
+}
+
+fn set_connected<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    connected: bool,
+) -> Result<(), Error<'gc>> {
+    let name = ruffle_name(activation, "_connected");
+    this.set_property(&name, connected.into(), activation)
+}
+
 pub fn connect<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    if let Value::Null = args[0] {
-        let event = EventObject::net_status_event(
-            activation,
-            "netStatus",
-            vec![
-                ("code", "NetConnection.Connect.Success"),
-                ("level", "status"),
-            ],
-        );
-        Avm2::dispatch_event(&mut activation.context, event, this);
+    let command = match args[0] {
+        Value::String(command) => Some(command),
+        _ => None,
+    };
+
+    let uri = ruffle_name(activation, "_uri");
+    this.set_property(
+        &uri,
+        command.unwrap_or_else(|| "null".into()).into(),
+        activation,
+    )?;
+
+    let lowercase = command
+        .map(|command| command.to_utf8_lossy().to_ascii_lowercase())
+        .unwrap_or_default();
+
+    // There are no RTMFP servers to talk to, but NetGroups can still find peers on the local
+    // network, so pretend that we're connected.
+    if command.is_none() || lowercase.starts_with("rtmfp:") {
+        set_connected(activation, this, true)?;
+        dispatch_status(activation, this, NetStatus::ConnectSuccess);
         return Ok(Value::Undefined);
     }
+
     avm2_stub_method!(
         activation,
         "flash.net.NetConnection",
         "connect",
         "with non-null command"
     );
+    set_connected(activation, this, false)?;
+    // Flash Remoting over HTTP doesn't connect until the first call, but anything else needs a
+    // server that we can't talk to.
+    if !lowercase.starts_with("http:") && !lowercase.starts_with("https:") {
+        dispatch_status(activation, this, NetStatus::ConnectFailed);
+    }
+    Ok(Value::Undefined)
+}
+
+/// Implements `NetConnection.call`
+///
+/// There is never a server to answer, so every call fails. Like a server's error, the failure
+/// goes to the responder's `status` function if it has one, and to `netStatus` listeners
+/// otherwise.
+pub fn call<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let command = args.get_string(activation, 0)?;
+    tracing::warn!("NetConnection.call({command}) failed: there is no server to call");
+
+    let status = match args.try_get_object(activation, 1) {
+        Some(responder) => {
+            let name = ruffle_name(activation, "status");
+            responder.get_property(&name, activation)?
+        }
+        None => Value::Null,
+    };
+
+    if let Value::Object(status) = status {
+        let info = activation
+            .avm2()
+            .classes()
+            .object
+            .construct(activation, &[])?;
+        for (key, value) in NetStatus::CallFailed.info(None) {
+            info.set_public_property(key, value.into(), activation)?;
+        }
+        status.call(Value::Null, &[info.into()], activation)?;
+    } else {
+        dispatch_status(activation, this, NetStatus::CallFailed);
+    }
+
     Ok(Value::Undefined)
 }
+
+/// Implements `NetConnection.close`
+pub fn close<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let connected = ruffle_name(activation, "_connected");
+    if this
+        .get_property(&connected, activation)?
+        .coerce_to_boolean()
+    {
+        set_connected(activation, this, false)?;
+        dispatch_status(activation, this, NetStatus::ConnectClosed);
+    }
+    Ok(Value::Undefined)
+}
+
+/// Implements `NetConnection.nearID`
+pub fn get_near_id<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let context = &mut activation.context;
+    let peer_id = context.net_groups.peer_id(context.rng).to_string();
+    Ok(AvmString::new_utf8(activation.gc(), peer_id).into())
+}
@@ -0,0 +1,184 @@
+//! `flash.net.NetGroup` native methods
+
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, Error, Object, Value};
+use crate::net_group::{dispatch_net_status, NeighborDirection, SendResult};
+use crate::net_status::NetStatus;
+use crate::string::AvmString;
+use flash_lso::amf3::read::AMF3Decoder;
+use flash_lso::types::{AMFVersion, Element, Lso};
+
+/// Serializes a message as AMF3, or returns `None` if it can't be sent.
+fn encode_message<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    message: Value<'gc>,
+) -> Option<Vec<u8>> {
+    let amf = crate::avm2::amf::serialize_value(activation, message, AMFVersion::AMF3)?;
+    let mut lso = Lso::new(vec![Element::new("", amf)], "", AMFVersion::AMF3);
+    let bytes = flash_lso::write::write_to_bytes(&mut lso).ok()?;
+    // Strip the LSO header and padding, as `Socket.writeObject` does.
+    Some(bytes[flash_lso::write::header_length(&lso.header) + 7..bytes.len() - 1].to_vec())
+}
+
+/// Deserializes a message sent by another group member.
+pub fn decode_message<'gc>(activation: &mut Activation<'_, 'gc>, bytes: &[u8]) -> Value<'gc> {
+    match AMF3Decoder::default().parse_single_element(bytes) {
+        Ok((_, amf)) => {
+            crate::avm2::amf::deserialize_value(activation, &amf).unwrap_or(Value::Undefined)
+        }
+        Err(_) => Value::Undefined,
+    }
+}
+
+fn send_result<'gc>(result: SendResult) -> Value<'gc> {
+    match result {
+        SendResult::Sent | SendResult::Local => "sent".into(),
+        SendResult::NoRoute => "no route".into(),
+    }
+}
+
+/// Implements `NetGroup.connect`
+pub fn connect<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let connection = args.get_object(activation, 0, "connection")?;
+    let groupspec = args.get_string_non_null(activation, 1, "groupspec")?;
+
+    let context = &mut activation.context;
+    context.net_groups.join(
+        context.navigator,
+        context.rng,
+        this,
+        connection,
+        &groupspec.to_utf8_lossy(),
+    );
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `NetGroup.close`
+pub fn close<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    activation.context.net_groups.close(this);
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `NetGroup.neighborCount`
+pub fn get_neighbor_count<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(activation.context.net_groups.neighbor_count(this).into())
+}
+
+/// Implements `NetGroup.post`
+pub fn post<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(payload) = encode_message(activation, args.get_value(0)) else {
+        return Ok(Value::Null);
+    };
+
+    Ok(match activation.context.net_groups.post(this, &payload) {
+        Some(message_id) => AvmString::new_utf8(activation.gc(), message_id).into(),
+        None => Value::Null,
+    })
+}
+
+/// Implements `NetGroup.sendToAllNeighbors`
+pub fn send_to_all_neighbors<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(payload) = encode_message(activation, args.get_value(0)) else {
+        return Ok("error".into());
+    };
+
+    let result = activation
+        .context
+        .net_groups
+        .send_to_all_neighbors(this, &payload);
+    Ok(send_result(result))
+}
+
+/// Implements `NetGroup.sendToNeighbor`
+pub fn send_to_neighbor<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let direction = match args.get_string(activation, 1)?.to_utf8_lossy().as_ref() {
+        "nextIncreasing" => NeighborDirection::Increasing,
+        "nextDecreasing" => NeighborDirection::Decreasing,
+        _ => return Ok("error".into()),
+    };
+    let Some(payload) = encode_message(activation, args.get_value(0)) else {
+        return Ok("error".into());
+    };
+
+    let result = activation
+        .context
+        .net_groups
+        .send_to_neighbor(this, direction, &payload);
+    Ok(send_result(result))
+}
+
+/// Implements `NetGroup.sendToNearest`
+pub fn send_to_nearest<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let message = args.get_value(0);
+    let address = args.get_string_non_null(activation, 1, "groupAddress")?;
+    let Some(payload) = encode_message(activation, message) else {
+        return Ok("error".into());
+    };
+
+    let result =
+        activation
+            .context
+            .net_groups
+            .send_to_nearest(this, &address.to_utf8_lossy(), &payload);
+    if result == SendResult::Local {
+        // Like Flash, deliver a copy rather than the original object.
+        let message = decode_message(activation, &payload);
+        let from = activation
+            .context
+            .net_groups
+            .peer_id(activation.context.rng)
+            .to_string();
+        let from = AvmString::new_utf8(activation.gc(), from);
+        dispatch_net_status(
+            activation,
+            this,
+            NetStatus::GroupSendToNotify,
+            &[
+                ("message", message),
+                ("from", from.into()),
+                ("fromLocal", true.into()),
+            ],
+        );
+    }
+    Ok(send_result(result))
+}
+
+/// Implements `NetGroup.convertPeerIDToGroupAddress`
+pub fn convert_peer_id_to_group_address<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    // Our group addresses are simply peer IDs, rather than a hash of them.
+    Ok(args.get_string(activation, 0)?.into())
+}
@@ -1,6 +1,7 @@
-use crate::avm2::error::type_error;
+use crate::avm2::error::{make_error_2008, type_error};
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::{Activation, Error, Object, TObject, Value};
+use crate::streams::AppendBytesAction;
 
 pub use crate::avm2::object::netstream_allocator as net_stream_allocator;
 
@@ -28,6 +29,41 @@ pub fn get_bytes_total<'gc>(
     Ok(Value::Undefined)
 }
 
+pub fn append_bytes<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(ns) = this.as_netstream() {
+        let bytearray = args.get_object(activation, 0, "bytes")?;
+        let bytes = bytearray
+            .as_bytearray()
+            .expect("Parameter must be a bytearray!")
+            .bytes()
+            .to_vec();
+
+        ns.append_bytes(&mut activation.context, &bytes);
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn append_bytes_action<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(ns) = this.as_netstream() {
+        let action = args.get_string_non_null(activation, 0, "action")?;
+        let action = AppendBytesAction::from_name(&action.to_utf8_lossy())
+            .ok_or_else(|| make_error_2008(activation, "action"))?;
+
+        ns.append_bytes_action(&mut activation.context, action);
+    }
+
+    Ok(Value::Undefined)
+}
+
 pub fn play<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
@@ -65,7 +101,7 @@ pub fn resume<'gc>(
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(ns) = this.as_netstream() {
-        ns.resume(&mut activation.context);
+        ns.resume(&mut activation.context, true);
     }
 
     Ok(Value::Undefined)
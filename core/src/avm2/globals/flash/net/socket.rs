@@ -1,15 +1,12 @@
-use crate::avm2::bytearray::{Endian, ObjectEncoding};
+use crate::avm2::amf;
+use crate::avm2::bytearray::{decode_multi_byte, encode_multi_byte, Endian, ObjectEncoding};
 use crate::avm2::error::{io_error, make_error_2008, security_error};
 pub use crate::avm2::object::socket_allocator;
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::string::AvmString;
 use crate::avm2::{Activation, Error, Object, TObject, Value};
 use crate::context::UpdateContext;
-use encoding_rs::Encoding;
-use encoding_rs::UTF_8;
-use flash_lso::amf0::read::AMF0Decoder;
-use flash_lso::amf3::read::AMF3Decoder;
-use flash_lso::types::{AMFVersion, Element};
+use crate::policy_file::content_domain;
 
 macro_rules! assert_socket_open {
     ($activation:expr, $socket:expr) => {
@@ -40,10 +37,19 @@ pub fn connect<'gc>(
         .map_err(|_| invalid_port_number(activation))?;
 
     let UpdateContext {
-        sockets, navigator, ..
+        sockets,
+        navigator,
+        swf,
+        ..
     } = &mut activation.context;
 
-    sockets.connect_avm2(*navigator, socket, host.to_utf8_lossy().into_owned(), port);
+    sockets.connect_avm2(
+        *navigator,
+        socket,
+        host.to_utf8_lossy().into_owned(),
+        port,
+        content_domain(swf),
+    );
 
     Ok(Value::Undefined)
 }
@@ -333,19 +339,11 @@ pub fn read_multi_byte<'gc>(
 
         let len = args.get_u32(activation, 0)?;
         let charset_label = args.get_string(activation, 1)?;
-        let mut bytes = &*socket
+        let bytes = socket
             .read_bytes(len as usize)
             .map_err(|e| e.to_avm(activation))?;
 
-        // Flash cuts off the string at the first null byte (after checking that
-        // the original length fits in the ByteArray)
-        if let Some(null) = bytes.iter().position(|b| *b == b'\0') {
-            bytes = &bytes[..null];
-        }
-
-        let encoder =
-            Encoding::for_label(charset_label.to_utf8_lossy().as_bytes()).unwrap_or(UTF_8);
-        let (decoded_str, _, _) = encoder.decode(bytes);
+        let decoded_str = decode_multi_byte(&bytes, &charset_label.to_utf8_lossy());
         return Ok(AvmString::new_utf8(activation.gc(), decoded_str).into());
     }
 
@@ -360,34 +358,14 @@ pub fn read_object<'gc>(
     if let Some(socket) = this.as_socket() {
         assert_socket_open!(activation, socket);
 
-        let mut bytes = socket.read_buffer();
-
-        let (bytes_left, value) = match socket.object_encoding() {
-            ObjectEncoding::Amf0 => {
-                let mut decoder = AMF0Decoder::default();
-                let (extra, amf) = decoder
-                    .parse_single_element(&bytes)
-                    .map_err(|_| "Error: Invalid object")?;
-                (
-                    extra.len(),
-                    crate::avm2::amf::deserialize_value(activation, &amf)?,
-                )
-            }
-            ObjectEncoding::Amf3 => {
-                let mut decoder = AMF3Decoder::default();
-                let (extra, amf) = decoder
-                    .parse_single_element(&bytes)
-                    .map_err(|_| "Error: Invalid object")?;
-                (
-                    extra.len(),
-                    crate::avm2::amf::deserialize_value(activation, &amf)?,
-                )
-            }
+        // The buffer can't stay borrowed while the value is created, as that may run constructors.
+        let amf = {
+            let mut buffer = socket.read_buffer();
+            let (amf, read) = amf::parse_object(&buffer, socket.object_encoding())?;
+            buffer.drain(..read);
+            amf
         };
-
-        let len = bytes.len();
-        let _ = bytes.drain(..(len - bytes_left));
-        return Ok(value);
+        return amf::deserialize_value(activation, &amf);
     }
 
     Ok(Value::Undefined)
@@ -489,11 +467,13 @@ pub fn read_utf_bytes<'gc>(
 
         let length = args.get_u32(activation, 0)?;
 
-        return Ok(AvmString::new_utf8_bytes(
+        return Ok(AvmString::new_utf8(
             activation.gc(),
-            &socket
-                .read_utf_bytes(length as usize)
-                .map_err(|e| e.to_avm(activation))?,
+            String::from_utf8_lossy(
+                &socket
+                    .read_utf_bytes(length as usize)
+                    .map_err(|e| e.to_avm(activation))?,
+            ),
         )
         .into());
     }
@@ -621,11 +601,10 @@ pub fn write_multi_byte<'gc>(
         let string = args.get_string(activation, 0)?;
         let charset_label = args.get_string(activation, 1)?;
 
-        let encoder =
-            Encoding::for_label(charset_label.to_utf8_lossy().as_bytes()).unwrap_or(UTF_8);
-        let utf8 = string.to_utf8_lossy();
-        let (encoded_bytes, _, _) = encoder.encode(&utf8);
-        socket.write_bytes(&encoded_bytes);
+        socket.write_bytes(&encode_multi_byte(
+            &string.to_utf8_lossy(),
+            &charset_label.to_utf8_lossy(),
+        ));
     }
 
     Ok(Value::Undefined)
@@ -640,25 +619,8 @@ pub fn write_object<'gc>(
         assert_socket_open!(activation, socket);
 
         let obj = args.get_value(0);
-        let amf_version = match socket.object_encoding() {
-            ObjectEncoding::Amf0 => AMFVersion::AMF0,
-            ObjectEncoding::Amf3 => AMFVersion::AMF3,
-        };
-        if let Some(amf) = crate::avm2::amf::serialize_value(activation, obj, amf_version) {
-            let element = Element::new("", amf);
-            let mut lso = flash_lso::types::Lso::new(vec![element], "", amf_version);
-            let bytes = flash_lso::write::write_to_bytes(&mut lso)
-                .map_err(|_| "Failed to serialize object")?;
-            // This is kind of hacky: We need to strip out the header and any padding so that we only write
-            // the value. In the future, there should be a method to do this in the flash_lso crate.
-            let element_padding = match amf_version {
-                AMFVersion::AMF0 => 8,
-                AMFVersion::AMF3 => 7,
-            };
-            socket.write_bytes(
-                &bytes[flash_lso::write::header_length(&lso.header) + element_padding
-                    ..bytes.len() - 1],
-            );
+        if let Some(bytes) = amf::write_object(activation, obj, socket.object_encoding())? {
+            socket.write_bytes(&bytes);
         }
     }
 
@@ -705,7 +667,9 @@ pub fn write_utf<'gc>(
 
         let string = args.get_string(activation, 0)?;
 
-        socket.write_utf(&string.to_utf8_lossy())?;
+        socket
+            .write_utf(&string.to_utf8_lossy())
+            .map_err(|e| e.to_avm(activation))?;
     }
 
     Ok(Value::Undefined)
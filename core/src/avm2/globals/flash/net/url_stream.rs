@@ -0,0 +1,58 @@
+//! `flash.net.URLStream` native function definitions
+
+use crate::avm2::activation::Activation;
+use crate::avm2::globals::flash::display::loader::request_from_url_request;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::{Error, Object};
+
+/// Native function definition for `URLStream.startLoad`
+pub fn start_load<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let url_request = args.get_object(activation, 0, "request")?;
+    let buffer = args.get_object(activation, 1, "buffer")?;
+    let request = request_from_url_request(activation, url_request)?;
+
+    let future = activation.context.load_manager.load_data_into_url_stream(
+        activation.context.player.clone(),
+        this,
+        buffer,
+        request,
+    );
+    activation.context.navigator.spawn_future(future);
+    Ok(Value::Undefined)
+}
+
+/// Native function definition for `URLStream.connected`
+pub fn get_connected<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let connected = activation
+        .context
+        .load_manager
+        .url_stream_loader(this)
+        .is_some();
+    Ok(connected.into())
+}
+
+/// Native function definition for `URLStream.cancelLoad`
+///
+/// Returns whether there was a load to cancel.
+pub fn cancel_load<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let load_manager = &mut activation.context.load_manager;
+    let Some(handle) = load_manager.url_stream_loader(this) else {
+        return Ok(false.into());
+    };
+    // The load notices that its loader is gone, and stops fetching.
+    load_manager.remove_loader(handle);
+    Ok(true.into())
+}
@@ -1,6 +1,8 @@
 use crate::avm2::error::Error;
+use crate::avm2::parameters::ParametersExt;
 use crate::avm2::value::Value;
 use crate::avm2::{Activation, Object};
+use crate::socket::SocketFraming;
 use crate::string::AvmString;
 
 // NOTE: This is used to get the movie domain when null is passed to connect function.
@@ -27,3 +29,15 @@ pub fn get_domain<'gc>(
 
     Ok(Value::String(domain))
 }
+
+pub fn use_null_framing<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(socket) = args.get_object(activation, 0, "socket")?.as_socket() {
+        socket.set_framing(SocketFraming::NullTerminated);
+    }
+
+    Ok(Value::Undefined)
+}
@@ -0,0 +1,3 @@
+//! `flash.profiler` namespace
+
+pub mod telemetry;
@@ -1,10 +1,10 @@
 package flash.profiler {
     public final class Telemetry {
-        public static const connected: Boolean = false;
-        public static const spanMarker: Number = 0;
+        public static native function get connected():Boolean;
+        public static native function get spanMarker():Number;
 
-        public static function sendMetric(metric:String, value:*):void {}
-        public static function sendSpanMetric(metric:String, startSpanMarker:Number, value:* = null):void {}
+        public static native function sendMetric(metric:String, value:*):void;
+        public static native function sendSpanMetric(metric:String, startSpanMarker:Number, value:* = null):void;
         	
         public static function registerCommandHandler(commandName:String, handler:Function):Boolean {
             return false;
@@ -14,4 +14,4 @@ package flash.profiler {
             return false;
         }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,60 @@
+//! `flash.profiler.Telemetry` native methods
+
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, Error, Object, Value};
+use crate::telemetry;
+
+/// Implements `flash.profiler.Telemetry.connected`
+pub fn get_connected<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(activation.context.telemetry.into())
+}
+
+/// Implements `flash.profiler.Telemetry.spanMarker`
+pub fn get_span_marker<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(activation.context.clock.now().into())
+}
+
+/// Implements `flash.profiler.Telemetry.sendMetric`
+pub fn send_metric<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if activation.context.telemetry {
+        let name = args.get_string_non_null(activation, 0, "metric")?;
+        let value = args.get_string(activation, 1)?;
+        telemetry::metric(&name.to_utf8_lossy(), &value.to_utf8_lossy());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.profiler.Telemetry.sendSpanMetric`
+pub fn send_span_metric<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if activation.context.telemetry {
+        let name = args.get_string_non_null(activation, 0, "metric")?;
+        let start = args.get_f64(activation, 1)?;
+        let value = args.try_get_string(activation, 2)?;
+        let duration = activation.context.clock.now() - start;
+        telemetry::span_metric(
+            &name.to_utf8_lossy(),
+            start,
+            duration,
+            value.map(|value| value.to_utf8_lossy()).as_deref(),
+        );
+    }
+
+    Ok(Value::Undefined)
+}
@@ -1,4 +1,5 @@
 package flash.system {
+    [API("682")]
     public final class Worker {
         public function Worker() {
             throw new ArgumentError("Error #2012: Worker$ class cannot be instantiated.", 2012);
@@ -1,4 +1,5 @@
 package flash.system {
+    [API("682")]
     public final class WorkerDomain {
         public static const isSupported: Boolean = false;
 
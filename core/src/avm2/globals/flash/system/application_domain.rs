@@ -124,6 +124,7 @@ pub fn get_qualified_definition_names<'gc>(
                 .collect(),
             false,
             Some(activation.avm2().classes().string),
+            activation,
         );
 
         let name_vector = VectorObject::from_vector(storage, activation)?;
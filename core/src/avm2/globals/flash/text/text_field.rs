@@ -1255,14 +1255,9 @@ pub fn set_scroll_h<'gc>(
         .as_display_object()
         .and_then(|this| this.as_edit_text())
     {
-        // NOTE: The clamping behavior here is identical to AVM1.
-        // This is incorrect, SWFv9 uses more complex behavior and AS3 can only
-        // be present in v9 SWFs.
-        let input = args
-            .get(0)
-            .cloned()
-            .unwrap_or(Value::Undefined)
-            .coerce_to_i32(activation)?;
+        // `scrollH` is always a whole number of pixels within `0..=maxScrollH`,
+        // so that it round-trips through the `int` getter.
+        let input = args.get_i32(activation, 0)?;
         let clamped = input.clamp(0, this.maxhscroll() as i32);
         this.set_hscroll(clamped as f64, &mut activation.context);
     }
@@ -1306,20 +1301,34 @@ pub fn set_max_chars<'gc>(
 }
 
 pub fn get_mouse_wheel_enabled<'gc>(
-    activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(activation, "flash.text.TextField", "mouseWheelEnabled");
-    Ok(true.into())
+    if let Some(this) = this
+        .as_display_object()
+        .and_then(|this| this.as_edit_text())
+    {
+        return Ok(this.is_mouse_wheel_enabled().into());
+    }
+
+    Ok(Value::Undefined)
 }
 
 pub fn set_mouse_wheel_enabled<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_setter!(activation, "flash.text.TextField", "mouseWheelEnabled");
+    if let Some(this) = this
+        .as_display_object()
+        .and_then(|this| this.as_edit_text())
+    {
+        let is_enabled = args.get_bool(0);
+
+        this.set_mouse_wheel_enabled(is_enabled, activation.context.gc_context);
+    }
+
     Ok(Value::Undefined)
 }
 
@@ -1,4 +1,5 @@
 package flash.ui {
+    [API("667")]
     public final class Multitouch {
         // FIXME - implement a setter and gett
         public static var inputMode:String = MultitouchInputMode.GESTURE;
@@ -5,7 +5,6 @@ use crate::avm2::object::TObject;
 use crate::avm2::{Activation, Error, Object, Value};
 use crate::string::AvmString;
 use crate::string::WString;
-use instant::Instant;
 use std::fmt::Write;
 
 pub mod byte_array;
@@ -19,10 +18,7 @@ pub fn get_timer<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    Ok((Instant::now()
-        .duration_since(activation.context.start_time)
-        .as_millis() as u32)
-        .into())
+    Ok(activation.context.clock.get_timer().into())
 }
 
 /// Implements `flash.utils.setInterval`
@@ -210,8 +206,7 @@ pub fn get_qualified_class_name<'gc>(
     Ok(class
         .inner_class_definition()
         .read()
-        .name()
-        .to_qualified_name(activation.context.gc_context)
+        .qualified_name()
         .into())
 }
 
@@ -238,8 +233,7 @@ pub fn get_qualified_superclass_name<'gc>(
         Ok(super_class
             .inner_class_definition()
             .read()
-            .name()
-            .to_qualified_name(activation.context.gc_context)
+            .qualified_name()
             .into())
     } else {
         Ok(Value::Null)
@@ -1,7 +1,9 @@
 package flash.utils {
 	[Ruffle(InstanceAllocator)]
 	public class ByteArray implements IDataInput, IDataOutput {
-		private static var _defaultObjectEncoding:uint = 3;
+		private static native function playerObjectEncoding():uint;
+
+		private static var _defaultObjectEncoding:uint = playerObjectEncoding();
 		public static function get defaultObjectEncoding():uint {
 			return _defaultObjectEncoding;
 		}
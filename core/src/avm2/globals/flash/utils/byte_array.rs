@@ -1,16 +1,12 @@
 use crate::avm2::activation::Activation;
-use crate::avm2::bytearray::{Endian, ObjectEncoding};
+use crate::avm2::amf;
+use crate::avm2::bytearray::{decode_multi_byte, encode_multi_byte, Endian, ObjectEncoding};
 use crate::avm2::error::make_error_2008;
 pub use crate::avm2::object::byte_array_allocator;
 use crate::avm2::object::{Object, TObject};
 use crate::avm2::value::Value;
 use crate::avm2::Error;
 use crate::string::AvmString;
-use encoding_rs::Encoding;
-use encoding_rs::UTF_8;
-use flash_lso::amf0::read::AMF0Decoder;
-use flash_lso::amf3::read::AMF3Decoder;
-use flash_lso::types::{AMFVersion, Element};
 
 /// Writes a single byte to the bytearray
 pub fn write_byte<'gc>(
@@ -590,10 +586,8 @@ pub fn write_multi_byte<'gc>(
             .get(1)
             .unwrap_or(&"UTF-8".into())
             .coerce_to_string(activation)?;
-        let encoder =
-            Encoding::for_label(charset_label.to_utf8_lossy().as_bytes()).unwrap_or(UTF_8);
-        let utf8 = string.to_utf8_lossy();
-        let (encoded_bytes, _, _) = encoder.encode(&utf8);
+        let encoded_bytes =
+            encode_multi_byte(&string.to_utf8_lossy(), &charset_label.to_utf8_lossy()).into_owned();
         bytearray
             .write_bytes(&encoded_bytes)
             .map_err(|e| e.to_avm(activation))?;
@@ -616,19 +610,11 @@ pub fn read_multi_byte<'gc>(
             .get(1)
             .unwrap_or(&"UTF-8".into())
             .coerce_to_string(activation)?;
-        let mut bytes = bytearray
+        let bytes = bytearray
             .read_bytes(len as usize)
             .map_err(|e| e.to_avm(activation))?;
 
-        // Flash cuts off the string at the first null byte (after checking that
-        // the original length fits in the ByteArray)
-        if let Some(null) = bytes.iter().position(|b| *b == b'\0') {
-            bytes = &bytes[..null];
-        }
-
-        let encoder =
-            Encoding::for_label(charset_label.to_utf8_lossy().as_bytes()).unwrap_or(UTF_8);
-        let (decoded_str, _, _) = encoder.decode(bytes);
+        let decoded_str = decode_multi_byte(bytes, &charset_label.to_utf8_lossy());
         return Ok(AvmString::new_utf8(activation.context.gc_context, decoded_str).into());
     }
 
@@ -733,30 +719,9 @@ pub fn read_object<'gc>(
         let bytes = bytearray
             .read_at(bytearray.bytes_available(), bytearray.position())
             .map_err(|e| e.to_avm(activation))?;
-        let (bytes_left, value) = match bytearray.object_encoding() {
-            ObjectEncoding::Amf0 => {
-                let mut decoder = AMF0Decoder::default();
-                let (extra, amf) = decoder
-                    .parse_single_element(bytes)
-                    .map_err(|_| "Error: Invalid object")?;
-                (
-                    extra.len(),
-                    crate::avm2::amf::deserialize_value(activation, &amf)?,
-                )
-            }
-            ObjectEncoding::Amf3 => {
-                let mut decoder = AMF3Decoder::default();
-                let (extra, amf) = decoder
-                    .parse_single_element(bytes)
-                    .map_err(|_| "Error: Invalid object")?;
-                (
-                    extra.len(),
-                    crate::avm2::amf::deserialize_value(activation, &amf)?,
-                )
-            }
-        };
+        let (value, read) = amf::read_object(activation, bytes, bytearray.object_encoding())?;
 
-        bytearray.set_position(bytearray.len() - bytes_left);
+        bytearray.set_position(bytearray.position() + read);
         return Ok(value);
     }
 
@@ -770,26 +735,9 @@ pub fn write_object<'gc>(
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
         let obj = args.get(0).cloned().unwrap_or(Value::Undefined);
-        let amf_version = match bytearray.object_encoding() {
-            ObjectEncoding::Amf0 => AMFVersion::AMF0,
-            ObjectEncoding::Amf3 => AMFVersion::AMF3,
-        };
-        if let Some(amf) = crate::avm2::amf::serialize_value(activation, obj, amf_version) {
-            let element = Element::new("", amf);
-            let mut lso = flash_lso::types::Lso::new(vec![element], "", amf_version);
-            let bytes = flash_lso::write::write_to_bytes(&mut lso)
-                .map_err(|_| "Failed to serialize object")?;
-            // This is kind of hacky: We need to strip out the header and any padding so that we only write
-            // the value. In the future, there should be a method to do this in the flash_lso crate.
-            let element_padding = match amf_version {
-                AMFVersion::AMF0 => 8,
-                AMFVersion::AMF3 => 7,
-            };
+        if let Some(bytes) = amf::write_object(activation, obj, bytearray.object_encoding())? {
             bytearray
-                .write_bytes(
-                    &bytes[flash_lso::write::header_length(&lso.header) + element_padding
-                        ..bytes.len() - 1],
-                )
+                .write_bytes(&bytes)
                 .map_err(|e| e.to_avm(activation))?;
         }
     }
@@ -797,6 +745,16 @@ pub fn write_object<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements `ByteArray.playerObjectEncoding`
+pub fn player_object_encoding<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let encoding = activation.avm2().player_version.default_object_encoding();
+    Ok((encoding as u8).into())
+}
+
 pub fn get_object_encoding<'gc>(
     _activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
@@ -117,19 +117,25 @@ fn apply<'gc>(
 
     let arg_array = args.get(1).cloned().unwrap_or(Value::Undefined).as_object();
     let resolved_args = if let Some(arg_array) = arg_array {
-        let arg_storage: Vec<Option<Value<'gc>>> = arg_array
-            .as_array_storage()
-            .map(|a| a.iter().collect())
-            .ok_or_else(|| {
-                Error::from("Second parameter of apply must be an array or undefined")
-            })?;
-
-        let mut resolved_args = Vec::with_capacity(arg_storage.len());
-        for (i, v) in arg_storage.iter().enumerate() {
-            resolved_args.push(resolve_array_hole(activation, arg_array, i, *v)?);
-        }
+        let storage = arg_array.as_array_storage().ok_or_else(|| {
+            Error::from("Second parameter of apply must be an array or undefined")
+        })?;
+
+        // Arrays without holes, which is nearly all of them, can be passed along as they are.
+        let dense_args: Option<Vec<Value<'gc>>> = storage.iter().collect();
+        if let Some(dense_args) = dense_args {
+            dense_args
+        } else {
+            let arg_storage: Vec<Option<Value<'gc>>> = storage.iter().collect();
+            drop(storage);
 
-        resolved_args
+            let mut resolved_args = Vec::with_capacity(arg_storage.len());
+            for (i, v) in arg_storage.iter().enumerate() {
+                resolved_args.push(resolve_array_hole(activation, arg_array, i, *v)?);
+            }
+
+            resolved_args
+        }
     } else {
         Vec::new()
     };
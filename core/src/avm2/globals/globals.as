@@ -34,8 +34,12 @@ include "flash/utils/IDataOutput.as"
 include "flash/utils/IExternalizable.as"
 include "flash/utils/ByteArray.as"
 include "flash/utils/Dictionary.as"
+include "flash/desktop/Clipboard.as"
 include "flash/desktop/ClipboardFormats.as"
 include "flash/desktop/ClipboardTransferMode.as"
+include "flash/desktop/NativeDragActions.as"
+include "flash/desktop/NativeDragManager.as"
+include "flash/desktop/NativeDragOptions.as"
 
 include "flash/events/IEventDispatcher.as"
 include "flash/events/EventDispatcher.as"
@@ -86,9 +90,11 @@ include "flash/display/LineScaleMode.as"
 include "flash/display/MorphShape.as"
 include "flash/display/NativeMenu.as"
 include "flash/display/NativeMenuItem.as"
+include "flash/display/NativeWindow.as"
 include "flash/display/PixelSnapping.as"
 include "flash/display/PNGEncoderOptions.as"
 include "flash/display/Scene.as"
+include "flash/display/Screen.as"
 include "flash/display/Shader.as"
 include "flash/display/ShaderData.as"
 include "flash/display/ShaderInput.as"
@@ -160,6 +166,8 @@ include "flash/events/GesturePhase.as"
 include "flash/events/HTTPStatusEvent.as"
 include "flash/events/IOErrorEvent.as"
 include "flash/events/KeyboardEvent.as"
+include "flash/events/NativeDragEvent.as"
+include "flash/events/NativeWindowBoundsEvent.as"
 include "flash/events/NetDataEvent.as"
 include "flash/events/NetStatusEvent.as"
 include "flash/events/PressAndTapGestureEvent.as"
@@ -169,6 +177,8 @@ include "flash/events/SecurityErrorEvent.as"
 include "flash/events/ShaderEvent.as"
 include "flash/events/SoftKeyboardEvent.as"
 include "flash/events/SoftKeyboardTrigger.as"
+include "flash/events/SQLErrorEvent.as"
+include "flash/events/SQLEvent.as"
 include "flash/events/StageVideoAvailabilityEvent.as"
 include "flash/events/StageVideoEvent.as"
 include "flash/events/StatusEvent.as"
@@ -189,8 +199,16 @@ include "flash/errors/IllegalOperationError.as"
 include "flash/errors/InvalidSWFError.as"
 include "flash/errors/MemoryError.as"
 include "flash/errors/ScriptTimeoutError.as"
+include "flash/errors/SQLError.as"
+include "flash/errors/SQLErrorOperation.as"
 include "flash/errors/StackOverflowError.as"
 
+include "flash/data/EncryptedLocalStore.as"
+include "flash/data/SQLConnection.as"
+include "flash/data/SQLMode.as"
+include "flash/data/SQLResult.as"
+include "flash/data/SQLStatement.as"
+
 include "flash/filters/BitmapFilter.as"
 include "flash/filters/BitmapFilterQuality.as"
 include "flash/filters/BitmapFilterType.as"
@@ -228,6 +246,8 @@ include "flash/globalization/NationalDigitsType.as"
 include "flash/globalization/NumberFormatter.as"
 include "flash/globalization/NumberParseResult.as"
 
+include "flash/html/HTMLLoader.as"
+
 include "flash/media/AudioDecoder.as"
 include "flash/media/AudioOutputChangeReason.as"
 include "flash/media/AVNetworkingParams.as"
@@ -257,15 +277,24 @@ include "flash/net.as"
 include "flash/net/FileFilter.as"
 include "flash/net/FileReference.as"
 include "flash/net/FileReferenceList.as"
+// File is a subclass of FileReference
+include "flash/filesystem/File.as"
+include "flash/filesystem/FileMode.as"
+include "flash/filesystem/FileStream.as"
+// ExtensionContext refers to File
+include "flash/external/ExtensionContext.as"
+include "flash/net/GroupSpecifier.as"
 include "flash/net/IDynamicPropertyOutput.as"
 include "flash/net/IDynamicPropertyWriter.as"
 include "flash/net/LocalConnection.as"
 include "flash/net/NetConnection.as"
+include "flash/net/NetGroup.as"
 include "flash/net/NetGroupReceiveMode.as"
 include "flash/net/NetGroupReplicationStrategy.as"
 include "flash/net/NetGroupSendMode.as"
 include "flash/net/NetGroupSendResult.as"
 include "flash/net/NetStream.as"
+include "flash/net/NetStreamAppendBytesAction.as"
 include "flash/net/NetStreamInfo.as"
 include "flash/net/NetStreamMulticastInfo.as"
 include "flash/net/NetStreamPlayOptions.as"
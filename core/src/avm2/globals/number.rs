@@ -9,6 +9,7 @@ use crate::avm2::value::Value;
 use crate::avm2::Multiname;
 use crate::avm2::QName;
 use crate::avm2::{AvmString, Error};
+use crate::number_format;
 use gc_arena::GcCell;
 
 /// Implements `Number`'s instance initializer.
@@ -166,14 +167,9 @@ pub fn to_exponential<'gc>(
         return Err(make_error_1002(activation));
     }
 
-    let digits = digits as usize;
-
     Ok(AvmString::new_utf8(
         activation.context.gc_context,
-        format!("{number:.digits$e}")
-            .replace('e', "e+")
-            .replace("e+-", "e-")
-            .replace("e+0", ""),
+        number_format::to_exponential(number, digits as usize),
     )
     .into())
 }
@@ -198,42 +194,11 @@ pub fn to_fixed<'gc>(
 
     Ok(AvmString::new_utf8(
         activation.context.gc_context,
-        format!("{0:.1$}", number, digits as usize),
+        number_format::to_fixed(number, digits as usize),
     )
     .into())
 }
 
-pub fn print_with_precision<'gc>(
-    activation: &mut Activation<'_, 'gc>,
-    number: f64,
-    wanted_digits: u32,
-) -> Result<AvmString<'gc>, Error<'gc>> {
-    let mut available_digits = number.abs().log10().floor();
-    if available_digits.is_nan() || available_digits.is_infinite() {
-        available_digits = 1.0;
-    }
-
-    let precision = (number * 10.0_f64.powf(wanted_digits as f64 - available_digits - 1.0)).floor()
-        / 10.0_f64.powf(wanted_digits as f64 - available_digits - 1.0);
-
-    if (wanted_digits as f64) <= available_digits {
-        Ok(AvmString::new_utf8(
-            activation.context.gc_context,
-            format!(
-                "{}e{}{}",
-                precision / 10.0_f64.powf(available_digits),
-                if available_digits < 0.0 { "-" } else { "+" },
-                available_digits.abs()
-            ),
-        ))
-    } else {
-        Ok(AvmString::new_utf8(
-            activation.context.gc_context,
-            format!("{precision}"),
-        ))
-    }
-}
-
 /// Implements `Number.toPrecision`
 pub fn to_precision<'gc>(
     activation: &mut Activation<'_, 'gc>,
@@ -254,60 +219,25 @@ pub fn to_precision<'gc>(
         return Err(make_error_1002(activation));
     }
 
-    Ok(print_with_precision(activation, number, wanted_digits as u32)?.into())
+    Ok(AvmString::new_utf8(
+        activation.context.gc_context,
+        number_format::to_precision(number, wanted_digits as usize),
+    )
+    .into())
 }
 
 pub fn print_with_radix<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    mut number: f64,
+    number: f64,
     radix: usize,
 ) -> Result<AvmString<'gc>, Error<'gc>> {
     if radix == 10 {
         return Value::from(number).coerce_to_string(activation);
     }
 
-    if number.is_nan() {
-        return Ok("NaN".into());
-    }
-
-    if number.is_infinite() {
-        if number < 0.0 {
-            return Ok("-Infinity".into());
-        } else if number > 0.0 {
-            return Ok("Infinity".into());
-        }
-    }
-
-    let mut digits = vec![];
-    let sign = number.signum();
-    number = number.abs();
-
-    loop {
-        let digit = number % radix as f64;
-        number /= radix as f64;
-
-        const DIGIT_CHARS: [char; 36] = [
-            '0', '1', '2', '3', '4', '5', '6', '7', '8', '9', 'a', 'b', 'c', 'd', 'e', 'f', 'g',
-            'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o', 'p', 'q', 'r', 's', 't', 'u', 'v', 'w', 'x',
-            'y', 'z',
-        ];
-
-        digits.push(*DIGIT_CHARS.get(digit as usize).unwrap());
-
-        if number < 1.0 {
-            break;
-        }
-    }
-
-    if sign < 0.0 {
-        digits.push('-');
-    }
-
-    let formatted: String = digits.into_iter().rev().collect();
-
     Ok(AvmString::new_utf8(
         activation.context.gc_context,
-        formatted,
+        number_format::to_radix(number, radix as u32),
     ))
 }
 
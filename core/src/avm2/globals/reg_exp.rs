@@ -200,7 +200,7 @@ pub fn exec<'gc>(
             .unwrap_or(&Value::Undefined)
             .coerce_to_string(activation)?;
 
-        let (storage, index) = match re.exec(text) {
+        let (storage, index, named_groups) = match re.exec(text) {
             Some(matched) => {
                 let substrings = matched
                     .groups()
@@ -211,13 +211,34 @@ pub fn exec<'gc>(
                     Some(s) => AvmString::new(activation.context.gc_context, s).into(),
                 }));
 
-                (storage, matched.start())
+                // Named groups are also available as properties of the result.
+                let named_groups = matched
+                    .named_groups()
+                    .map(|(name, range)| {
+                        let value = match range {
+                            Some(r) => {
+                                AvmString::new(activation.context.gc_context, &text[r]).into()
+                            }
+                            None => Value::Undefined,
+                        };
+                        (
+                            AvmString::new_utf8(activation.context.gc_context, name),
+                            value,
+                        )
+                    })
+                    .collect::<Vec<_>>();
+
+                (storage, matched.start(), named_groups)
             }
             None => return Ok(Value::Null),
         };
 
         let object = ArrayObject::from_storage(activation, storage)?;
 
+        for (name, value) in named_groups {
+            object.set_string_property_local(name, value, activation)?;
+        }
+
         object.set_string_property_local("index", Value::Number(index as f64), activation)?;
 
         object.set_string_property_local("input", text.into(), activation)?;
@@ -127,7 +127,7 @@ fn class_init<'gc>(
         .ok_or_else(|| {
             format!(
                 "Specialization {} has a prototype of null or undefined",
-                this.instance_of_class_name(activation.context.gc_context)
+                this.instance_of_class_name()
             )
         })?;
     let scope = activation.create_scopechain();
@@ -274,7 +274,7 @@ pub fn concat<'gc>(
                 activation,
                 &format!(
                     "Error #1034: Type Coercion failed: cannot convert {}@00000000000 to {}.",
-                    arg_obj.instance_of_class_name(activation.context.gc_context),
+                    arg_obj.instance_of_class_name(),
                     base_vector_name,
                 ),
                 1034,
@@ -631,7 +631,11 @@ pub fn push<'gc>(
         vs.check_fixed(activation)?;
 
         for arg in args {
-            let coerced_arg = arg.coerce_to_type(activation, value_type)?;
+            let coerced_arg = if vs.is_coerced(*arg) {
+                *arg
+            } else {
+                arg.coerce_to_type(activation, value_type)?
+            };
 
             vs.push(coerced_arg, activation)?;
         }
@@ -902,8 +906,12 @@ pub fn splice<'gc>(
             to_coerce.push(value.coerce_to_type(activation, value_type_for_coercion)?);
         }
 
-        let new_vs =
-            VectorStorage::from_values(vs.splice(start..end, to_coerce)?, false, value_type);
+        let new_vs = VectorStorage::from_values(
+            vs.splice(start..end, to_coerce)?,
+            false,
+            value_type,
+            activation,
+        );
         let new_vector = VectorObject::from_vector(new_vs, activation)?;
 
         return Ok(new_vector.into());
@@ -939,7 +947,7 @@ pub fn create_builtin_class<'gc>(
     // FIXME - we should store a `Multiname` instead of a `QName`, and use the
     // `params` field. For now, this is good enough to get tests passing
     let name = if let Some(param) = param {
-        let name = format!("Vector.<{}>", param.read().name().to_qualified_name(mc));
+        let name = format!("Vector.<{}>", param.read().qualified_name());
         QName::new(
             activation.avm2().vector_public_namespace,
             AvmString::new_utf8(mc, name),
@@ -397,9 +397,7 @@ pub fn call_handler<'gc>(
             // This re-uses the XML object stored in the list
             if let Some(xml_list) = obj.as_xml_list_object() {
                 if xml_list.length() == 1 {
-                    return Ok(xml_list.children_mut(activation.context.gc_context)[0]
-                        .get_or_create_xml(activation)
-                        .into());
+                    return Ok(xml_list.xml_object_child(0, activation).unwrap().into());
                 }
                 return Err(Error::AvmError(ill_formed_markup_err(activation)?));
             }
@@ -274,13 +274,11 @@ pub fn name<'gc>(
 ) -> Result<Value<'gc>, Error<'gc>> {
     let list = this.as_xml_list_object().unwrap();
 
-    let mut children = list.children_mut(activation.context.gc_context);
-    match &mut children[..] {
-        [child] => {
-            child
-                .get_or_create_xml(activation)
-                .call_public_property("name", &[], activation)
-        }
+    match list.length() {
+        1 => list
+            .xml_object_child(0, activation)
+            .unwrap()
+            .call_public_property("name", &[], activation),
         _ => Err(Error::AvmError(type_error(
             activation,
             "Error #1086: The name method only works on lists containing one item.",
@@ -0,0 +1,214 @@
+//! Enumerating dynamic properties in the order of avmplus's hashtables.
+//!
+//! avmplus keeps the dynamic properties of an object in an open-addressed hashtable, and
+//! `for..in` walks that table slot by slot. Some content relies on the order that gives,
+//! most often on properties named by small integers coming out in numeric order.
+
+use crate::string::{AvmString, WStr};
+use gc_arena::Collect;
+
+/// The number of slots in a new table.
+const INITIAL_CAPACITY: usize = 4;
+
+/// The largest integer name avmplus stores as an integer atom rather than a string.
+const MAX_INTEGER_ATOM: u32 = 0x0FFF_FFFF;
+
+/// The names of an object's dynamic properties, laid out the way avmplus would lay them out.
+#[derive(Clone, Collect, Debug)]
+#[collect(no_drop)]
+pub struct HashOrder<'gc> {
+    /// The table, whose size is always a power of two.
+    slots: Vec<Slot<'gc>>,
+
+    /// The number of names in the table, including non-enumerable ones.
+    len: usize,
+
+    /// The number of slots left behind by removed names, which aren't reused until a rehash.
+    deleted: usize,
+}
+
+#[derive(Clone, Copy, Collect, Debug)]
+#[collect(no_drop)]
+enum Slot<'gc> {
+    Empty,
+    Deleted,
+    Name {
+        name: AvmString<'gc>,
+        enumerable: bool,
+    },
+}
+
+impl<'gc> Slot<'gc> {
+    fn is_enumerable(&self) -> bool {
+        matches!(
+            self,
+            Slot::Name {
+                enumerable: true,
+                ..
+            }
+        )
+    }
+}
+
+impl<'gc> HashOrder<'gc> {
+    /// A table holding `names`, added in that order.
+    pub fn new(names: impl IntoIterator<Item = AvmString<'gc>>) -> Self {
+        let mut table = Self {
+            slots: vec![Slot::Empty; INITIAL_CAPACITY],
+            len: 0,
+            deleted: 0,
+        };
+        for name in names {
+            table.insert(name);
+        }
+        table
+    }
+
+    /// Adds an enumerable name to the table, growing it first if it's getting full.
+    pub fn insert(&mut self, name: AvmString<'gc>) {
+        // avmplus keeps its tables at most 80% full, counting the slots of removed names.
+        if 5 * (self.len + self.deleted + 1) >= 4 * self.slots.len() {
+            let capacity = if 5 * (self.len + 1) >= 4 * self.slots.len() {
+                self.slots.len() * 2
+            } else {
+                self.slots.len()
+            };
+            self.rehash(capacity);
+        }
+
+        let index = self.find(&name);
+        if let Slot::Empty = self.slots[index] {
+            self.len += 1;
+            self.slots[index] = Slot::Name {
+                name,
+                enumerable: true,
+            };
+        }
+    }
+
+    pub fn remove(&mut self, name: AvmString<'gc>) {
+        let index = self.find(&name);
+        if let Slot::Name { .. } = self.slots[index] {
+            self.slots[index] = Slot::Deleted;
+            self.len -= 1;
+            self.deleted += 1;
+        }
+    }
+
+    /// Changes whether a name in the table is enumerated. It keeps its slot either way.
+    pub fn set_enumerable(&mut self, name: AvmString<'gc>, is_enumerable: bool) {
+        let index = self.find(&name);
+        if let Slot::Name { enumerable, .. } = &mut self.slots[index] {
+            *enumerable = is_enumerable;
+        }
+    }
+
+    /// The index of the first slot after `last_index` holding an enumerable name, plus one.
+    ///
+    /// Like avmplus, enumeration goes by slot, so that `for..in` only walks the table once.
+    /// Enumerants are numbered from one, as zero ends the enumeration.
+    pub fn next_enumerant(&self, last_index: usize) -> Option<usize> {
+        let offset = self
+            .slots
+            .get(last_index..)?
+            .iter()
+            .position(Slot::is_enumerable)?;
+        Some(last_index + offset + 1)
+    }
+
+    /// The enumerable name returned by `next_enumerant` as `index`.
+    pub fn enumerant(&self, index: usize) -> Option<AvmString<'gc>> {
+        match self.slots.get(index.checked_sub(1)?)? {
+            Slot::Name {
+                name,
+                enumerable: true,
+            } => Some(*name),
+            _ => None,
+        }
+    }
+
+    /// The slot holding `name`, or the empty slot it would go in.
+    fn find(&self, name: &WStr) -> usize {
+        let mask = self.slots.len() - 1;
+        let mut index = hash(name) & mask;
+        let mut step = 7;
+        loop {
+            match self.slots[index] {
+                Slot::Empty => return index,
+                Slot::Name { name: other, .. } if &*other == name => return index,
+                _ => {}
+            }
+            index = (index + step) & mask;
+            step += 1;
+        }
+    }
+
+    /// Moves every name into a new table of `capacity` slots, dropping those of removed names.
+    fn rehash(&mut self, capacity: usize) {
+        let old_slots = std::mem::replace(&mut self.slots, vec![Slot::Empty; capacity]);
+        self.deleted = 0;
+        for slot in old_slots {
+            if let Slot::Name { name, enumerable } = slot {
+                let index = self.find(&name);
+                self.slots[index] = Slot::Name { name, enumerable };
+            }
+        }
+    }
+}
+
+/// Where avmplus would start looking for `name` in a table.
+fn hash(name: &WStr) -> usize {
+    // Integer names are stored as integer atoms, which hash to their own value.
+    if let Some(integer) = integer_atom(name) {
+        return integer as usize;
+    }
+
+    // Other names are interned strings, which avmplus hashes by their address. That isn't
+    // reproducible, so they're hashed by their contents instead.
+    name.iter().fold(0x811c_9dc5u32, |hash, unit| {
+        (hash ^ u32::from(unit)).wrapping_mul(0x0100_0193)
+    }) as usize
+}
+
+/// The value of `name` if avmplus would store it as an integer atom.
+fn integer_atom(name: &WStr) -> Option<u32> {
+    if name.is_empty() || (name.len() > 1 && name.at(0) == u16::from(b'0')) {
+        return None;
+    }
+
+    name.iter()
+        .try_fold(0u32, |value, unit| {
+            let digit = char::from_u32(unit.into())?.to_digit(10)?;
+            value.checked_mul(10)?.checked_add(digit)
+        })
+        .filter(|value| *value <= MAX_INTEGER_ATOM)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::HashOrder;
+    use crate::string::AvmString;
+
+    fn enumerants(table: &HashOrder<'static>) -> Vec<String> {
+        let mut names = vec![];
+        let mut index = 0;
+        while let Some(next) = table.next_enumerant(index) {
+            names.push(table.enumerant(next).unwrap().to_string());
+            index = next;
+        }
+        names
+    }
+
+    #[test]
+    fn integer_names_in_numeric_order() {
+        let mut table = HashOrder::new(["3", "0", "2", "1"].map(AvmString::from));
+        assert_eq!(enumerants(&table), ["0", "1", "2", "3"]);
+
+        table.remove("1".into());
+        table.set_enumerable("2".into(), false);
+        assert_eq!(enumerants(&table), ["0", "3"]);
+
+        table.insert("5".into());
+        assert_eq!(enumerants(&table), ["0", "3", "5"]);
+    }
+}
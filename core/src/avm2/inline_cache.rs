@@ -0,0 +1,80 @@
+//! Inline caches for property lookups
+
+use crate::avm2::property::Property;
+use crate::avm2::vtable::VTable;
+use crate::avm2::Multiname;
+use gc_arena::Collect;
+use std::collections::HashMap;
+
+/// How many vtables a single cache remembers.
+///
+/// Sites that see more shapes than this are rare, and are left to evict
+/// their oldest entries.
+const MAX_ENTRIES: usize = 4;
+
+#[derive(Collect, Clone, Copy)]
+#[collect(no_drop)]
+struct CacheEntry<'gc> {
+    vtable: VTable<'gc>,
+
+    /// The generation of `vtable` this lookup was made against.
+    generation: u32,
+
+    property: Option<Property>,
+}
+
+/// A polymorphic inline cache, remembering which trait a static multiname
+/// resolved to on each of the last few vtables it was looked up on.
+#[derive(Collect, Clone, Default)]
+#[collect(no_drop)]
+pub struct PropertyCache<'gc> {
+    entries: Vec<CacheEntry<'gc>>,
+}
+
+impl<'gc> PropertyCache<'gc> {
+    /// Looks up `multiname` on `vtable`, going through the cache.
+    pub fn get_trait(
+        &mut self,
+        vtable: VTable<'gc>,
+        multiname: &Multiname<'gc>,
+    ) -> Option<Property> {
+        let generation = vtable.generation();
+        let position = self
+            .entries
+            .iter()
+            .position(|entry| VTable::ptr_eq(entry.vtable, vtable));
+
+        if let Some(position) = position {
+            let entry = &mut self.entries[position];
+            if entry.generation != generation {
+                entry.generation = generation;
+                entry.property = vtable.get_trait(multiname);
+            }
+            return entry.property;
+        }
+
+        let property = vtable.get_trait(multiname);
+        if self.entries.len() >= MAX_ENTRIES {
+            self.entries.remove(0);
+        }
+        self.entries.push(CacheEntry {
+            vtable,
+            generation,
+            property,
+        });
+        property
+    }
+
+    /// Looks up `multiname` on `vtable` if the cache already knows the answer.
+    pub fn peek_trait(&self, vtable: VTable<'gc>) -> Option<Option<Property>> {
+        let generation = vtable.generation();
+        self.entries
+            .iter()
+            .find(|entry| VTable::ptr_eq(entry.vtable, vtable) && entry.generation == generation)
+            .map(|entry| entry.property)
+    }
+}
+
+/// The inline caches of a single method, keyed by the ABC index of the
+/// multiname each `getproperty`/`setproperty` site uses.
+pub type PropertyCaches<'gc> = HashMap<u32, PropertyCache<'gc>>;
@@ -1,15 +1,18 @@
 //! AVM2 methods
 
 use crate::avm2::activation::Activation;
+use crate::avm2::inline_cache::PropertyCaches;
 use crate::avm2::object::{ClassObject, Object};
+use crate::avm2::property::Property;
 use crate::avm2::script::TranslationUnit;
 use crate::avm2::value::{abc_default_value, Value};
+use crate::avm2::vtable::VTable;
 use crate::avm2::Error;
 use crate::avm2::Multiname;
 use crate::string::AvmString;
 use crate::tag_utils::SwfMovie;
 use gc_arena::barrier::unlock;
-use gc_arena::lock::Lock;
+use gc_arena::lock::{Lock, RefLock};
 use gc_arena::{Collect, Gc, Mutation};
 use std::fmt;
 use std::ops::Deref;
@@ -128,6 +131,9 @@ pub struct BytecodeMethod<'gc> {
     /// if the method requires it.
     activation_class: Lock<Option<ClassObject<'gc>>>,
 
+    /// Inline caches for the property lookups this method makes.
+    property_caches: RefLock<PropertyCaches<'gc>>,
+
     /// Whether or not this method was declared as a free-standing function.
     ///
     /// A free-standing function corresponds to the `Function` trait type, and
@@ -168,6 +174,7 @@ impl<'gc> BytecodeMethod<'gc> {
                         return_type,
                         is_function,
                         activation_class: Lock::new(None),
+                        property_caches: RefLock::new(PropertyCaches::new()),
                     });
                 }
             }
@@ -182,6 +189,7 @@ impl<'gc> BytecodeMethod<'gc> {
             return_type: Multiname::any(activation.gc()),
             is_function,
             activation_class: Lock::new(None),
+            property_caches: RefLock::new(PropertyCaches::new()),
         })
     }
 
@@ -286,6 +294,31 @@ impl<'gc> BytecodeMethod<'gc> {
             None
         })
     }
+
+    /// Resolves the trait that the static multiname at `index` refers to on
+    /// `vtable`, going through this method's inline cache for that multiname.
+    pub fn lookup_trait(
+        this: Gc<'gc, Self>,
+        mc: &Mutation<'gc>,
+        index: u32,
+        vtable: VTable<'gc>,
+        multiname: &Multiname<'gc>,
+    ) -> Option<Property> {
+        let cached = this
+            .property_caches
+            .borrow()
+            .get(&index)
+            .and_then(|cache| cache.peek_trait(vtable));
+        if let Some(property) = cached {
+            return property;
+        }
+
+        unlock!(Gc::write(mc, this), Self, property_caches)
+            .borrow_mut()
+            .entry(index)
+            .or_default()
+            .get_trait(vtable, multiname)
+    }
 }
 
 /// An uninstantiated method
@@ -1,4 +1,5 @@
 use crate::avm2::activation::Activation;
+use crate::avm2::qname::join_qualified_name;
 use crate::avm2::script::TranslationUnit;
 use crate::avm2::Error;
 use crate::avm2::Namespace;
@@ -389,7 +390,7 @@ impl<'gc> Multiname<'gc> {
         let ns_match = self
             .namespace_set()
             .iter()
-            .any(|ns| ns.is_any() || *ns == name.namespace());
+            .any(|ns| ns.is_any() || ns.matches_ns(name.namespace()));
         let name_match = self.name.map(|n| n == name.local_name()).unwrap_or(true);
 
         ns_match && name_match
@@ -401,14 +402,17 @@ impl<'gc> Multiname<'gc> {
     }
 
     pub fn to_qualified_name(&self, mc: &Mutation<'gc>) -> AvmString<'gc> {
-        let mut uri = WString::new();
-        let ns = match self.ns.get(0).filter(|_| self.ns.len() == 1) {
-            Some(ns) if ns.is_any() => "*".into(),
-            Some(ns) => ns.as_uri(),
-            None => "".into(),
-        };
+        let ns = self.single_namespace_uri();
+
+        if let (Some(name), None) = (self.name, self.param) {
+            // Public names are the most common, and don't need a new string at all.
+            if ns.is_empty() {
+                return name;
+            }
+            return AvmString::new(mc, join_qualified_name(&ns, WStr::from_units(b"::"), &name));
+        }
 
-        uri.push_str(&ns);
+        let mut uri = WString::from(ns.as_wstr());
 
         if let Some(name) = self.name {
             if !uri.is_empty() {
@@ -441,25 +445,23 @@ impl<'gc> Multiname<'gc> {
     // note: I didn't look very deeply into how different exactly this should be
     // this is currently generally based on to_qualified_name, without params and leading ::
     pub fn as_uri(&self, mc: &Mutation<'gc>) -> AvmString<'gc> {
-        let mut uri = WString::new();
-        let ns = match self.ns.get(0).filter(|_| self.ns.len() == 1) {
+        let ns = self.single_namespace_uri();
+        let name = self.name.unwrap_or_else(|| "*".into());
+        if ns.is_empty() {
+            return name;
+        }
+
+        AvmString::new(mc, join_qualified_name(&ns, WStr::from_units(b"::"), &name))
+    }
+
+    /// The URI of this multiname's namespace, `*` for the any namespace, or the empty
+    /// string if it has several.
+    fn single_namespace_uri(&self) -> AvmString<'gc> {
+        match self.ns.get(0).filter(|_| self.ns.len() == 1) {
             Some(ns) if ns.is_any() => "*".into(),
             Some(ns) => ns.as_uri(),
             None => "".into(),
-        };
-
-        if !ns.is_empty() {
-            uri.push_str(&ns);
-            uri.push_str(WStr::from_units(b"::"));
-        }
-
-        if let Some(name) = self.name {
-            uri.push_str(&name);
-        } else {
-            uri.push_str(WStr::from_units(b"*"));
         }
-
-        AvmString::new(mc, uri)
     }
 
     pub fn set_single_namespace(&mut self, namespace: Namespace<'gc>) {
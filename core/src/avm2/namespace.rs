@@ -1,3 +1,4 @@
+use crate::avm2::api_version::ApiVersion;
 use crate::avm2::Error;
 use crate::string::{AvmAtom, AvmString};
 use crate::{avm2::script::TranslationUnit, context::GcContext};
@@ -16,6 +17,8 @@ impl<'gc> PartialEq for Namespace<'gc> {
         } else if self.is_private() || other.is_private() {
             false
         } else {
+            // Like in avmplus, public namespaces of different API versions are distinct.
+            // Use `matches_ns` to find the definitions a name refers to.
             *self.0 == *other.0
         }
     }
@@ -29,7 +32,7 @@ impl<'gc> Eq for Namespace<'gc> {}
 enum NamespaceData<'gc> {
     // note: this is the default "public namespace", corresponding to both
     // ABC Namespace and PackageNamespace
-    Namespace(AvmAtom<'gc>),
+    Namespace(AvmAtom<'gc>, ApiVersion),
     PackageInternal(AvmAtom<'gc>),
     Protected(AvmAtom<'gc>),
     Explicit(AvmAtom<'gc>),
@@ -63,11 +66,24 @@ impl<'gc> Namespace<'gc> {
             .ok_or_else(|| format!("Unknown namespace constant {}", namespace_index.0).into());
 
         let ns = match abc_namespace? {
-            AbcNamespace::Namespace(idx) => {
-                NamespaceData::Namespace(translation_unit.pool_string(idx.0, context)?)
-            }
-            AbcNamespace::Package(idx) => {
-                NamespaceData::Namespace(translation_unit.pool_string(idx.0, context)?)
+            AbcNamespace::Namespace(idx) | AbcNamespace::Package(idx) => {
+                let uri = translation_unit.pool_string(idx.0, context)?;
+                match translation_unit.api_version() {
+                    Some(api_version) => NamespaceData::Namespace(uri, api_version),
+                    // The player globals mark the namespaces of versioned definitions.
+                    None => {
+                        let uri_str = uri.as_wstr();
+                        match uri_str.iter().last().and_then(ApiVersion::from_mark) {
+                            Some(api_version) => {
+                                let unmarked = context
+                                    .interner
+                                    .intern_wstr(context.gc_context, &uri_str[..uri_str.len() - 1]);
+                                NamespaceData::Namespace(unmarked, api_version)
+                            }
+                            None => NamespaceData::Namespace(uri, ApiVersion::AllVersions),
+                        }
+                    }
+                }
             }
             AbcNamespace::PackageInternal(idx) => {
                 NamespaceData::PackageInternal(translation_unit.pool_string(idx.0, context)?)
@@ -92,15 +108,28 @@ impl<'gc> Namespace<'gc> {
         Self(Gc::new(mc, NamespaceData::Any))
     }
 
+    /// A public namespace that isn't tied to any API version.
     // TODO(moulins): allow passing an AvmAtom or a non-static `&WStr` directly
     pub fn package(
         package_name: impl Into<AvmString<'gc>>,
         context: &mut GcContext<'_, 'gc>,
+    ) -> Self {
+        Self::package_with_api_version(package_name, ApiVersion::AllVersions, context)
+    }
+
+    /// A public namespace whose names only see definitions of `api_version` and earlier.
+    pub fn package_with_api_version(
+        package_name: impl Into<AvmString<'gc>>,
+        api_version: ApiVersion,
+        context: &mut GcContext<'_, 'gc>,
     ) -> Self {
         let atom = context
             .interner
             .intern(context.gc_context, package_name.into());
-        Self(Gc::new(context.gc_context, NamespaceData::Namespace(atom)))
+        Self(Gc::new(
+            context.gc_context,
+            NamespaceData::Namespace(atom, api_version),
+        ))
     }
 
     // TODO(moulins): allow passing an AvmAtom or a non-static `&WStr` directly
@@ -118,11 +147,11 @@ impl<'gc> Namespace<'gc> {
     }
 
     pub fn is_public(&self) -> bool {
-        matches!(*self.0, NamespaceData::Namespace(name) if name.as_wstr().is_empty())
+        matches!(*self.0, NamespaceData::Namespace(name, _) if name.as_wstr().is_empty())
     }
 
     pub fn is_public_ignoring_ns(&self) -> bool {
-        matches!(*self.0, NamespaceData::Namespace(_))
+        matches!(*self.0, NamespaceData::Namespace(..))
     }
 
     pub fn is_any(&self) -> bool {
@@ -134,12 +163,12 @@ impl<'gc> Namespace<'gc> {
     }
 
     pub fn is_namespace(&self) -> bool {
-        matches!(*self.0, NamespaceData::Namespace(_))
+        matches!(*self.0, NamespaceData::Namespace(..))
     }
 
     pub fn as_uri_opt(&self) -> Option<AvmString<'gc>> {
         match *self.0 {
-            NamespaceData::Namespace(a) => Some(a.into()),
+            NamespaceData::Namespace(a, _) => Some(a.into()),
             NamespaceData::PackageInternal(a) => Some(a.into()),
             NamespaceData::Protected(a) => Some(a.into()),
             NamespaceData::Explicit(a) => Some(a.into()),
@@ -149,6 +178,27 @@ impl<'gc> Namespace<'gc> {
         }
     }
 
+    /// The API version of this namespace, if it's a public one.
+    pub fn api_version(&self) -> Option<ApiVersion> {
+        match *self.0 {
+            NamespaceData::Namespace(_, api_version) => Some(api_version),
+            _ => None,
+        }
+    }
+
+    /// Whether a name in this namespace refers to definitions made in `declared`.
+    ///
+    /// This is the case when they're the same namespace, except that a public namespace also
+    /// refers to definitions made in the public namespaces of the API versions it can see.
+    pub fn matches_ns(&self, declared: Self) -> bool {
+        match (*self.0, *declared.0) {
+            (NamespaceData::Namespace(a, lookup), NamespaceData::Namespace(b, declared)) => {
+                a == b && declared.is_visible_to(lookup)
+            }
+            _ => *self == declared,
+        }
+    }
+
     /// Get the string value of this namespace, ignoring its type.
     ///
     /// TODO: Is this *actually* the namespace URI?
@@ -205,11 +205,23 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
     /// structured objects.
     #[allow(unused_mut)] //Not unused.
     fn get_property(
+        self,
+        multiname: &Multiname<'gc>,
+        activation: &mut Activation<'_, 'gc>,
+    ) -> Result<Value<'gc>, Error<'gc>> {
+        let property = self.vtable().and_then(|vtable| vtable.get_trait(multiname));
+        self.get_property_with_trait(multiname, property, activation)
+    }
+
+    /// Same as get_property, but takes the trait `multiname` resolves to on
+    /// this object's vtable, for callers that already looked it up.
+    fn get_property_with_trait(
         mut self,
         multiname: &Multiname<'gc>,
+        property: Option<Property>,
         activation: &mut Activation<'_, 'gc>,
     ) -> Result<Value<'gc>, Error<'gc>> {
-        match self.vtable().and_then(|vtable| vtable.get_trait(multiname)) {
+        match property {
             Some(Property::Slot { slot_id }) | Some(Property::ConstSlot { slot_id }) => {
                 self.base().get_slot(slot_id)
             }
@@ -304,7 +316,20 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         value: Value<'gc>,
         activation: &mut Activation<'_, 'gc>,
     ) -> Result<(), Error<'gc>> {
-        match self.vtable().and_then(|vtable| vtable.get_trait(multiname)) {
+        let property = self.vtable().and_then(|vtable| vtable.get_trait(multiname));
+        self.set_property_with_trait(multiname, property, value, activation)
+    }
+
+    /// Same as set_property, but takes the trait `multiname` resolves to on
+    /// this object's vtable, for callers that already looked it up.
+    fn set_property_with_trait(
+        &self,
+        multiname: &Multiname<'gc>,
+        property: Option<Property>,
+        value: Value<'gc>,
+        activation: &mut Activation<'_, 'gc>,
+    ) -> Result<(), Error<'gc>> {
+        match property {
             Some(Property::Slot { slot_id }) => {
                 let value = self
                     .vtable()
@@ -1115,9 +1140,9 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
     }
 
     /// Get this object's class's name, formatted for debug output.
-    fn instance_of_class_name(&self, mc: &Mutation<'gc>) -> AvmString<'gc> {
+    fn instance_of_class_name(&self) -> AvmString<'gc> {
         self.instance_of_class_definition()
-            .map(|r| r.read().name().to_qualified_name(mc))
+            .map(|r| r.read().qualified_name())
             .unwrap_or_else(|| "<Unknown type>".into())
     }
 
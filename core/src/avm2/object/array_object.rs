@@ -210,7 +210,6 @@ impl<'gc> TObject<'gc> for ArrayObject<'gc> {
         _activation: &mut Activation<'_, 'gc>,
     ) -> Result<Option<u32>, Error<'gc>> {
         let read = self.0.read();
-        let num_enumerants = read.base.num_enumerants();
         let array_length = read.array.length() as u32;
 
         // Array enumeration skips over holes.
@@ -224,10 +223,10 @@ impl<'gc> TObject<'gc> for ArrayObject<'gc> {
         // After enumerating all of the 'normal' array entries,
         // we enumerate all of the local properties stored on the
         // ScriptObject.
-        if last_index < num_enumerants + array_length {
-            return Ok(Some(last_index + 1));
-        }
-        Ok(None)
+        Ok(read
+            .base
+            .get_next_enumerant(last_index - array_length)
+            .map(|index| index + array_length))
     }
 
     fn get_enumerant_name(
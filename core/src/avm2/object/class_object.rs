@@ -931,11 +931,7 @@ impl<'gc> TObject<'gc> for ClassObject<'gc> {
         }
 
         if nullable_params.len() != 1 {
-            let class_name = self
-                .inner_class_definition()
-                .read()
-                .name()
-                .to_qualified_name(activation.context.gc_context);
+            let class_name = self.inner_class_definition().read().qualified_name();
 
             return Err(Error::AvmError(type_error(
                 activation,
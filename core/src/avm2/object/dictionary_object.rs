@@ -112,14 +112,16 @@ impl<'gc> TObject<'gc> for DictionaryObject<'gc> {
         _activation: &mut Activation<'_, 'gc>,
     ) -> Result<Option<u32>, Error<'gc>> {
         let read = self.0.read();
-        let num_enumerants = read.base.num_enumerants();
         let object_space_length = read.object_space.keys().len() as u32;
 
-        if last_index < num_enumerants + object_space_length {
-            Ok(Some(last_index.saturating_add(1)))
-        } else {
-            Ok(None)
+        // Object keys come first, then the names stored on the ScriptObject.
+        if last_index < object_space_length {
+            return Ok(Some(last_index + 1));
         }
+        Ok(read
+            .base
+            .get_next_enumerant(last_index - object_space_length)
+            .map(|index| index + object_space_length))
     }
 
     fn get_enumerant_name(
@@ -91,6 +91,12 @@ pub struct LoaderInfoObjectData<'gc> {
     /// Whether or not we've fired our 'complete' event
     complete_event_fired: bool,
 
+    /// How many bytes of the movie have arrived while it's still downloading.
+    ///
+    /// `bytesLoaded` never reports less than this, even though it otherwise
+    /// follows how much of the movie has been preloaded.
+    streamed_bytes: usize,
+
     /// The `EventDispatcher` used for `LoaderInfo.sharedEvents`.
     // FIXME: If we ever implement sandboxing, then ensure that we allow
     // events to be fired across security boundaries using this object.
@@ -119,6 +125,7 @@ impl<'gc> LoaderInfoObject<'gc> {
                 loader,
                 init_event_fired: false,
                 complete_event_fired: false,
+                streamed_bytes: 0,
                 shared_events: activation
                     .context
                     .avm2
@@ -163,6 +170,7 @@ impl<'gc> LoaderInfoObject<'gc> {
                 loader,
                 init_event_fired: false,
                 complete_event_fired: false,
+                streamed_bytes: 0,
                 shared_events: activation
                     .context
                     .avm2
@@ -250,6 +258,14 @@ impl<'gc> LoaderInfoObject<'gc> {
         }
     }
 
+    pub fn streamed_bytes(&self) -> usize {
+        self.0.read().streamed_bytes
+    }
+
+    pub fn set_streamed_bytes(&self, mc: &Mutation<'gc>, streamed_bytes: usize) {
+        self.0.write(mc).streamed_bytes = streamed_bytes;
+    }
+
     /// Unwrap this object's loader stream
     pub fn as_loader_stream(&self) -> Option<Ref<LoaderStream<'gc>>> {
         if self.0.read().loaded_stream.is_some() {
@@ -2,11 +2,13 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::error;
+use crate::avm2::hash_order::HashOrder;
 use crate::avm2::object::{ClassObject, FunctionObject, Object, ObjectPtr, TObject};
 use crate::avm2::value::Value;
 use crate::avm2::vtable::VTable;
 use crate::avm2::Multiname;
 use crate::avm2::{Error, QName};
+use crate::player_version::EnumerationOrder;
 use crate::string::AvmString;
 use fnv::FnvHashMap;
 use gc_arena::{Collect, GcCell, GcWeakCell, Mutation};
@@ -62,6 +64,10 @@ pub struct ScriptObjectData<'gc> {
 
     /// Enumeratable property names.
     enumerants: Vec<AvmString<'gc>>,
+
+    /// Where the dynamic properties would be in an avmplus hashtable, when they're enumerated
+    /// in that order.
+    hash_order: Option<HashOrder<'gc>>,
 }
 
 impl<'gc> TObject<'gc> for ScriptObject<'gc> {
@@ -139,6 +145,7 @@ impl<'gc> ScriptObjectData<'gc> {
             instance_of,
             vtable: instance_of.map(|cls| cls.instance_vtable()),
             enumerants: Vec::new(),
+            hash_order: None,
         }
     }
 
@@ -229,6 +236,12 @@ impl<'gc> ScriptObjectData<'gc> {
                 //TODO: Not all classes are dynamic like this
                 self.enumerants.push(local_name);
                 v.insert(value);
+
+                if let Some(hash_order) = &mut self.hash_order {
+                    hash_order.insert(local_name);
+                } else if activation.avm2().enumeration_order == EnumerationOrder::Hash {
+                    self.hash_order = Some(HashOrder::new(self.enumerants.iter().copied()));
+                }
             }
         };
         Ok(())
@@ -249,7 +262,11 @@ impl<'gc> ScriptObjectData<'gc> {
         }
         if let Some(name) = multiname.local_name() {
             self.set_local_property_is_enumerable(name, false);
-            self.values.remove(&name);
+            if self.values.remove(&name).is_some() {
+                if let Some(hash_order) = &mut self.hash_order {
+                    hash_order.remove(name);
+                }
+            }
             true
         } else {
             false
@@ -357,6 +374,12 @@ impl<'gc> ScriptObjectData<'gc> {
     }
 
     pub fn get_next_enumerant(&self, last_index: u32) -> Option<u32> {
+        if let Some(hash_order) = &self.hash_order {
+            return hash_order
+                .next_enumerant(last_index as usize)
+                .map(|index| index as u32);
+        }
+
         if last_index < self.enumerants.len() as u32 {
             Some(last_index.saturating_add(1))
         } else {
@@ -371,6 +394,10 @@ impl<'gc> ScriptObjectData<'gc> {
         // Hence why we have to `checked_sub` here in case some miscompiled
         // code doesn't check for the zero index, which is actually a failure
         // sentinel.
+        if let Some(hash_order) = &self.hash_order {
+            return hash_order.enumerant(index as usize).map(|q| q.into());
+        }
+
         let true_index = (index as usize).checked_sub(1)?;
 
         self.enumerants.get(true_index).cloned().map(|q| q.into())
@@ -383,7 +410,14 @@ impl<'gc> ScriptObjectData<'gc> {
     pub fn set_local_property_is_enumerable(&mut self, name: AvmString<'gc>, is_enumerable: bool) {
         if is_enumerable && self.values.contains_key(&name) && !self.enumerants.contains(&name) {
             self.enumerants.push(name);
+            if let Some(hash_order) = &mut self.hash_order {
+                hash_order.set_enumerable(name, true);
+            }
         } else if !is_enumerable && self.enumerants.contains(&name) {
+            if let Some(hash_order) = &mut self.hash_order {
+                hash_order.set_enumerable(name, false);
+            }
+
             let mut index = None;
             for (i, other_name) in self.enumerants.iter().enumerate() {
                 if *other_name == name {
@@ -397,11 +431,6 @@ impl<'gc> ScriptObjectData<'gc> {
         }
     }
 
-    /// Gets the number of (standard) enumerants.
-    pub fn num_enumerants(&self) -> u32 {
-        self.enumerants.len() as u32
-    }
-
     /// Install a method into the object.
     pub fn install_bound_method(&mut self, disp_id: u32, function: FunctionObject<'gc>) {
         if self.bound_methods.len() <= disp_id as usize {
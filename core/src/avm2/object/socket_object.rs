@@ -1,9 +1,9 @@
-use crate::avm2::bytearray::{ByteArrayError, Endian, ObjectEncoding};
+use crate::avm2::bytearray::{encode_utf, trim_utf_bytes, ByteArrayError, Endian, ObjectEncoding};
 use crate::avm2::object::script_object::ScriptObjectData;
 use crate::avm2::object::{ClassObject, Object, ObjectPtr, TObject};
 use crate::avm2::value::Value;
 use crate::avm2::{Activation, Error};
-use crate::socket::SocketHandle;
+use crate::socket::{SocketFraming, SocketHandle};
 use gc_arena::barrier::unlock;
 use gc_arena::{lock::RefLock, Collect, Gc};
 use gc_arena::{GcWeak, Mutation};
@@ -23,9 +23,10 @@ pub fn socket_allocator<'gc>(
             base,
             // Default endianness is Big.
             endian: Cell::new(Endian::Big),
-            object_encoding: Cell::new(ObjectEncoding::Amf3),
+            object_encoding: Cell::new(activation.avm2().player_version.default_object_encoding()),
             timeout: Cell::new(0),
             handle: Cell::new(None),
+            framing: Cell::new(None),
             read_buffer: RefCell::new(vec![]),
             write_buffer: RefCell::new(vec![]),
         },
@@ -90,6 +91,14 @@ impl<'gc> SocketObject<'gc> {
         self.0.timeout.set(std::cmp::max(250, timeout));
     }
 
+    pub fn framing(&self) -> Option<SocketFraming> {
+        self.0.framing.get()
+    }
+
+    pub fn set_framing(&self, framing: SocketFraming) {
+        self.0.framing.set(Some(framing));
+    }
+
     pub fn handle(&self) -> Option<SocketHandle> {
         self.0.handle.get()
     }
@@ -131,18 +140,9 @@ impl<'gc> SocketObject<'gc> {
         self.write_bytes(&[val as u8; 1])
     }
 
-    /// Same as `read_bytes`, but:
-    /// - cuts the result at the first null byte to recreate a bug in FP
-    /// - strips off an optional UTF8 BOM at the beginning
+    /// Same as `read_bytes`, but trimmed like `ByteArray.readUTFBytes`.
     pub fn read_utf_bytes(&self, amnt: usize) -> Result<Vec<u8>, ByteArrayError> {
-        let mut bytes = &*self.read_bytes(amnt)?;
-        if let Some(without_bom) = bytes.strip_prefix(&[0xEF, 0xBB, 0xBF]) {
-            bytes = without_bom;
-        }
-        if let Some(null) = bytes.iter().position(|b| *b == b'\0') {
-            bytes = &bytes[..null];
-        }
-        Ok(bytes.to_vec())
+        Ok(trim_utf_bytes(&self.read_bytes(amnt)?).to_vec())
     }
 
     pub fn read_utf(&self) -> Result<Vec<u8>, ByteArrayError> {
@@ -152,14 +152,9 @@ impl<'gc> SocketObject<'gc> {
     }
 
     // Writes a UTF String into the buffer, with its length as a prefix
-    pub fn write_utf(&self, utf_string: &str) -> Result<(), Error<'gc>> {
-        if let Ok(str_size) = u16::try_from(utf_string.len()) {
-            self.write_unsigned_short(str_size);
-            self.write_bytes(utf_string.as_bytes());
-            Ok(())
-        } else {
-            Err("RangeError: UTF String length must fit into a short".into())
-        }
+    pub fn write_utf(&self, utf_string: &str) -> Result<(), ByteArrayError> {
+        self.write_bytes(&encode_utf(utf_string, self.endian())?);
+        Ok(())
     }
 }
 
@@ -209,6 +204,9 @@ pub struct SocketObjectData<'gc> {
     object_encoding: Cell<ObjectEncoding>,
     /// Socket connection timeout in milliseconds.
     timeout: Cell<u32>,
+    /// How received data is split into `socketData` events, if not the player's default.
+    #[collect(require_static)]
+    framing: Cell<Option<SocketFraming>>,
 
     read_buffer: RefCell<Vec<u8>>,
     write_buffer: RefCell<Vec<u8>>,
@@ -125,6 +125,12 @@ impl<'gc> TObject<'gc> for VectorObject<'gc> {
         if name.contains_public_namespace() {
             if let Some(name) = name.local_name() {
                 if let Ok(index) = name.parse::<usize>() {
+                    let mut write = self.0.write(activation.context.gc_context);
+                    if write.vector.is_coerced(value) {
+                        return write.vector.set(index, value, activation);
+                    }
+                    drop(write);
+
                     let type_of = self
                         .0
                         .read()
@@ -161,6 +167,12 @@ impl<'gc> TObject<'gc> for VectorObject<'gc> {
         if name.contains_public_namespace() {
             if let Some(name) = name.local_name() {
                 if let Ok(index) = name.parse::<usize>() {
+                    let mut write = self.0.write(activation.context.gc_context);
+                    if write.vector.is_coerced(value) {
+                        return write.vector.set(index, value, activation);
+                    }
+                    drop(write);
+
                     let type_of = self
                         .0
                         .read()
@@ -5,7 +5,8 @@ use crate::avm2::object::script_object::ScriptObjectData;
 use crate::avm2::object::{Object, ObjectPtr, TObject};
 use crate::avm2::value::Value;
 use crate::avm2::{Error, Multiname};
-use gc_arena::{Collect, GcCell, GcWeakCell, Mutation};
+use gc_arena::lock::{GcRefLock, RefLock};
+use gc_arena::{Collect, Gc, GcCell, GcWeakCell, Mutation};
 use std::cell::{Ref, RefMut};
 use std::fmt::{self, Debug};
 use std::ops::Deref;
@@ -23,7 +24,7 @@ pub fn xml_list_allocator<'gc>(
         activation.context.gc_context,
         XmlListObjectData {
             base,
-            children: Vec::new(),
+            children: XmlListChildren::new(activation.context.gc_context, Vec::new()),
             // An XMLList created by 'new XMLList()' is not linked
             // to any object
             target_object: None,
@@ -61,7 +62,7 @@ impl<'gc> XmlListObject<'gc> {
             activation.context.gc_context,
             XmlListObjectData {
                 base,
-                children,
+                children: XmlListChildren::new(activation.context.gc_context, children),
                 target_object,
                 target_property,
             },
@@ -69,7 +70,7 @@ impl<'gc> XmlListObject<'gc> {
     }
 
     pub fn length(&self) -> usize {
-        self.0.read().children.len()
+        self.0.read().children.read().len()
     }
 
     pub fn xml_object_child(
@@ -77,8 +78,8 @@ impl<'gc> XmlListObject<'gc> {
         index: usize,
         activation: &mut Activation<'_, 'gc>,
     ) -> Option<XmlObject<'gc>> {
-        let mut write = self.0.write(activation.context.gc_context);
-        if let Some(child) = write.children.get_mut(index) {
+        let mut children = self.0.read().children.write_shared(activation.gc());
+        if let Some(child) = children.get_mut(index) {
             Some(child.get_or_create_xml(activation))
         } else {
             None
@@ -86,15 +87,37 @@ impl<'gc> XmlListObject<'gc> {
     }
 
     pub fn children(&self) -> Ref<'_, Vec<E4XOrXml<'gc>>> {
-        Ref::map(self.0.read(), |d| &d.children)
+        self.0.read().children.read()
     }
 
+    /// Returns the children for modifying this list.
+    ///
+    /// Children shared with other lists are copied first. To only wrap a child
+    /// in an `XmlObject`, use `xml_object_child`, which doesn't need a copy.
     pub fn children_mut(&self, mc: &Mutation<'gc>) -> RefMut<'_, Vec<E4XOrXml<'gc>>> {
-        RefMut::map(self.0.write(mc), |d| &mut d.children)
+        self.0.write(mc).children.write(mc)
     }
 
     pub fn set_children(&self, mc: &Mutation<'gc>, children: Vec<E4XOrXml<'gc>>) {
-        self.0.write(mc).children = children;
+        self.0.write(mc).children = XmlListChildren::new(mc, children);
+    }
+
+    /// Creates a list of the same children, with the same target.
+    ///
+    /// The children are shared with this list until either list is modified.
+    pub fn shallow_copy(&self, activation: &mut Activation<'_, 'gc>) -> XmlListObject<'gc> {
+        let mut write = self.0.write(activation.context.gc_context);
+        let children = write.children.share();
+        let base = ScriptObjectData::new(activation.context.avm2.classes().xml_list);
+        XmlListObject(GcCell::new(
+            activation.context.gc_context,
+            XmlListObjectData {
+                base,
+                children,
+                target_object: write.target_object,
+                target_property: write.target_property.clone(),
+            },
+        ))
     }
 
     pub fn target_object(&self) -> Option<XmlOrXmlListObject<'gc>> {
@@ -130,13 +153,23 @@ impl<'gc> XmlListObject<'gc> {
             // 3.b. Let x.[[TargetProperty]] = V.[[TargetProperty]]
             write.target_property = list.target_property();
 
-            for el in &*list.children() {
-                write.children.push(el.clone());
+            if write.children.read().is_empty() && !GcCell::ptr_eq(self.0, list.0) {
+                // Appending to an empty list is how most lists get built up, so
+                // share the children rather than copying them.
+                write.children = list.0.write(activation.gc()).children.share();
+            } else {
+                let mut children = write.children.write(activation.gc());
+                for el in &*list.children() {
+                    children.push(el.clone());
+                }
             }
         }
 
         if let Some(xml) = value.as_object().and_then(|x| x.as_xml_object()) {
-            write.children.push(E4XOrXml::Xml(xml));
+            write
+                .children
+                .write(activation.gc())
+                .push(E4XOrXml::Xml(xml));
         }
     }
 
@@ -261,14 +294,14 @@ impl<'gc> XmlListObject<'gc> {
     }
 }
 
-#[derive(Clone, Collect)]
+#[derive(Collect)]
 #[collect(no_drop)]
 pub struct XmlListObjectData<'gc> {
     /// Base script object
     base: ScriptObjectData<'gc>,
 
     /// The children stored by this list.
-    children: Vec<E4XOrXml<'gc>>,
+    children: XmlListChildren<'gc>,
 
     /// The XML or XMLList object that this list was created from.
     /// If `Some`, then modifications to this list are reflected
@@ -278,6 +311,66 @@ pub struct XmlListObjectData<'gc> {
     target_property: Option<Multiname<'gc>>,
 }
 
+/// The children of an XMLList, which may be shared with other lists.
+///
+/// Copying large lists is common in E4X code, so copies share the same children
+/// until one of them is modified. Modifying a list through `write` gives it its
+/// own copy of the children first, so the other lists never see the change.
+///
+/// Wrapping a child node in an `XmlObject` is done in place through
+/// `write_shared` instead. Every list sharing the children holds the same nodes,
+/// so they should all hand out the same `XmlObject` for them.
+#[derive(Collect, Debug)]
+#[collect(no_drop)]
+struct XmlListChildren<'gc> {
+    children: GcRefLock<'gc, Vec<E4XOrXml<'gc>>>,
+
+    /// Whether `children` may also be held by another list.
+    ///
+    /// This is never cleared on the other lists once one of them makes its own
+    /// copy, so the last list holding the children may make a needless copy.
+    shared: bool,
+}
+
+impl<'gc> XmlListChildren<'gc> {
+    fn new(mc: &Mutation<'gc>, children: Vec<E4XOrXml<'gc>>) -> Self {
+        Self {
+            children: Gc::new(mc, RefLock::new(children)),
+            shared: false,
+        }
+    }
+
+    /// Returns children sharing the same backing storage as these ones.
+    fn share(&mut self) -> Self {
+        self.shared = true;
+        Self {
+            children: self.children,
+            shared: true,
+        }
+    }
+
+    fn read(&self) -> Ref<'gc, Vec<E4XOrXml<'gc>>> {
+        self.children.borrow()
+    }
+
+    /// Returns the children for modifying them, copying them first if they are
+    /// shared with another list.
+    fn write(&mut self, mc: &Mutation<'gc>) -> RefMut<'gc, Vec<E4XOrXml<'gc>>> {
+        if self.shared {
+            let children = self.children.borrow().clone();
+            self.children = Gc::new(mc, RefLock::new(children));
+            self.shared = false;
+        }
+        self.children.borrow_mut(mc)
+    }
+
+    /// Returns the children for changes that every list sharing them should see,
+    /// which is only the case for wrapping a child node in an `XmlObject`.
+    fn write_shared(&self, mc: &Mutation<'gc>) -> RefMut<'gc, Vec<E4XOrXml<'gc>>> {
+        self.children.borrow_mut(mc)
+    }
+}
+
 /// Holds either an `E4XNode` or an `XmlObject`. This can be converted
 /// in-palce to an `XmlObject` via `get_or_create_xml`.
 /// This deliberately does not implement `Copy`, since `get_or_create_xml`
@@ -428,7 +521,7 @@ impl<'gc> TObject<'gc> for XmlListObject<'gc> {
         multiname: &Multiname<'gc>,
     ) -> Option<XmlListObject<'gc>> {
         let mut descendants = Vec::new();
-        for child in self.0.read().children.iter() {
+        for child in self.children().iter() {
             child.node().descendants(multiname, &mut descendants);
         }
         Some(XmlListObject::new(activation, descendants, None, None))
@@ -440,12 +533,12 @@ impl<'gc> TObject<'gc> for XmlListObject<'gc> {
         activation: &mut Activation<'_, 'gc>,
     ) -> Result<Value<'gc>, Error<'gc>> {
         // FIXME - implement everything from E4X spec (XMLListObject::getMultinameProperty in avmplus)
-        let mut write = self.0.write(activation.context.gc_context);
+        let mut children = self.0.read().children.write_shared(activation.gc());
 
         if !name.has_explicit_namespace() {
             if let Some(local_name) = name.local_name() {
                 if let Ok(index) = local_name.parse::<usize>() {
-                    if let Some(child) = write.children.get_mut(index) {
+                    if let Some(child) = children.get_mut(index) {
                         return Ok(Value::Object(child.get_or_create_xml(activation).into()));
                     } else {
                         return Ok(Value::Undefined);
@@ -454,8 +547,7 @@ impl<'gc> TObject<'gc> for XmlListObject<'gc> {
             }
         }
 
-        let matched_children = write
-            .children
+        let matched_children = children
             .iter_mut()
             .flat_map(|child| {
                 let child_prop = child
@@ -522,9 +614,9 @@ impl<'gc> TObject<'gc> for XmlListObject<'gc> {
             let prop = self.get_property_local(multiname, activation)?;
             if let Some(list) = prop.as_object().and_then(|obj| obj.as_xml_list_object()) {
                 if list.length() == 0 && self.length() == 1 {
-                    let mut this = self.0.write(activation.context.gc_context);
-                    return this.children[0]
-                        .get_or_create_xml(activation)
+                    return self
+                        .xml_object_child(0, activation)
+                        .unwrap()
                         .call_property(multiname, arguments, activation);
                 }
             }
@@ -764,12 +856,7 @@ impl<'gc> TObject<'gc> for XmlListObject<'gc> {
                         value.as_object().and_then(|x| x.as_xml_list_object())
                     {
                         // 2.f.i. Create a shallow copy c of V
-                        let c = XmlListObject::new(
-                            activation,
-                            list.children().clone(),
-                            list.target_object(),
-                            list.target_property(),
-                        );
+                        let c = list.shallow_copy(activation);
                         // 2.f.ii. Let parent = x[i].[[Parent]]
                         let parent = child.parent();
 
@@ -896,10 +983,8 @@ impl<'gc> TObject<'gc> for XmlListObject<'gc> {
                 self.append(r.as_object().into(), activation);
             }
 
-            let mut write = self.0.write(activation.gc());
-
             // 3.b. Call the [[Put]] method of x[0] with arguments P and V
-            let xml = write.children[0].get_or_create_xml(activation);
+            let xml = self.xml_object_child(0, activation).unwrap();
             return xml.set_property_local(name, value, activation);
         }
 
@@ -912,8 +997,7 @@ impl<'gc> TObject<'gc> for XmlListObject<'gc> {
         last_index: u32,
         _activation: &mut Activation<'_, 'gc>,
     ) -> Result<Option<u32>, Error<'gc>> {
-        let read = self.0.read();
-        if (last_index as usize) < read.children.len() {
+        if (last_index as usize) < self.length() {
             return Ok(Some(last_index + 1));
         }
         // Return `Some(0)` instead of `None`, as we do *not* want to
@@ -927,17 +1011,13 @@ impl<'gc> TObject<'gc> for XmlListObject<'gc> {
         index: u32,
         activation: &mut Activation<'_, 'gc>,
     ) -> Result<Value<'gc>, Error<'gc>> {
-        let mut write = self.0.write(activation.context.gc_context);
-        let children_len = write.children.len() as u32;
+        let children_len = self.length() as u32;
 
         if children_len >= index {
             Ok(index
                 .checked_sub(1)
-                .map(|index| {
-                    write.children[index as usize]
-                        .get_or_create_xml(activation)
-                        .into()
-                })
+                .and_then(|index| self.xml_object_child(index as usize, activation))
+                .map(Value::from)
                 .unwrap_or(Value::Undefined))
         } else {
             Ok(Value::Undefined)
@@ -949,7 +1029,7 @@ impl<'gc> TObject<'gc> for XmlListObject<'gc> {
         index: u32,
         _activation: &mut Activation<'_, 'gc>,
     ) -> Result<Value<'gc>, Error<'gc>> {
-        let children_len = self.0.read().children.len() as u32;
+        let children_len = self.length() as u32;
         if children_len >= index {
             Ok(index
                 .checked_sub(1)
@@ -968,13 +1048,11 @@ impl<'gc> TObject<'gc> for XmlListObject<'gc> {
         activation: &mut Activation<'_, 'gc>,
         name: &Multiname<'gc>,
     ) -> Result<bool, Error<'gc>> {
-        let mut write = self.0.write(activation.context.gc_context);
-
         if !name.is_any_name() && !name.is_attribute() {
             if let Some(local_name) = name.local_name() {
                 if let Ok(index) = local_name.parse::<usize>() {
-                    if index < write.children.len() {
-                        let removed = write.children.remove(index);
+                    if index < self.length() {
+                        let removed = self.children_mut(activation.gc()).remove(index);
                         let removed_node = removed.node();
                         if let Some(parent) = removed_node.parent() {
                             if let E4XNodeKind::Attribute(_) = &*removed_node.kind() {
@@ -990,7 +1068,8 @@ impl<'gc> TObject<'gc> for XmlListObject<'gc> {
             }
         }
 
-        for child in write.children.iter_mut() {
+        let mut children = self.0.read().children.write_shared(activation.gc());
+        for child in children.iter_mut() {
             if matches!(&*child.node().kind(), E4XNodeKind::Element { .. }) {
                 child
                     .get_or_create_xml(activation)
@@ -1001,3 +1080,68 @@ impl<'gc> TObject<'gc> for XmlListObject<'gc> {
         Ok(true)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{E4XOrXml, XmlListChildren};
+    use crate::avm2::e4x::E4XNode;
+    use crate::string::AvmString;
+    use gc_arena::{rootless_arena, Gc, Mutation};
+
+    fn text_nodes<'gc>(mc: &Mutation<'gc>, texts: &[&str]) -> Vec<E4XOrXml<'gc>> {
+        texts
+            .iter()
+            .map(|text| E4XOrXml::E4X(E4XNode::text(mc, AvmString::new_utf8(mc, *text), None)))
+            .collect()
+    }
+
+    #[test]
+    fn shared_children_are_not_copied_until_written() {
+        rootless_arena(|mc| {
+            let mut original = XmlListChildren::new(mc, text_nodes(mc, &["a", "b", "c"]));
+            let copy = original.share();
+            assert!(Gc::ptr_eq(original.children, copy.children));
+            assert_eq!(copy.read().len(), 3);
+
+            // Writing in place, as when wrapping a child in an `XmlObject`, must
+            // keep the children shared.
+            let mut children = original.write_shared(mc);
+            children[0] = children[0].clone();
+            drop(children);
+            assert!(Gc::ptr_eq(original.children, copy.children));
+            assert_eq!(copy.read().len(), 3);
+        });
+    }
+
+    #[test]
+    fn writing_shared_children_copies_them() {
+        rootless_arena(|mc| {
+            let mut original = XmlListChildren::new(mc, text_nodes(mc, &["a", "b"]));
+            let mut copy = original.share();
+            let extra = text_nodes(mc, &["c"]).remove(0);
+
+            copy.write(mc).push(extra);
+            assert!(!Gc::ptr_eq(original.children, copy.children));
+            assert_eq!(original.read().len(), 2);
+            assert_eq!(copy.read().len(), 3);
+            for (a, b) in original.read().iter().zip(copy.read().iter()) {
+                assert!(E4XNode::ptr_eq(*a.node(), *b.node()));
+            }
+
+            original.write(mc).clear();
+            assert!(original.read().is_empty());
+            assert_eq!(copy.read().len(), 3);
+        });
+    }
+
+    #[test]
+    fn unshared_children_are_written_in_place() {
+        rootless_arena(|mc| {
+            let mut children = XmlListChildren::new(mc, text_nodes(mc, &["a"]));
+            let storage = children.children;
+
+            children.write(mc).clear();
+            assert!(Gc::ptr_eq(storage, children.children));
+        });
+    }
+}
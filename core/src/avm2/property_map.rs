@@ -23,7 +23,9 @@ use std::mem::swap;
 ///
 /// The internal structure of the `PropertyMap` technically allows storage of
 /// multiple values per `QName`. It's implementation enforces the invariant
-/// that each `QName` only have one associated `V`.
+/// that each `QName` only have one associated `V`. A `QName` refers to the
+/// entry whose namespace it matches, see `Namespace::matches_ns`, so a name
+/// declared in a later API version than an existing entry gets its own.
 #[derive(Clone, Debug)]
 pub struct PropertyMap<'gc, V>(
     HashMap<AvmString<'gc>, SmallVec<[(Namespace<'gc>, V); 2]>, FnvBuildHasher>,
@@ -59,7 +61,7 @@ impl<'gc, V> PropertyMap<'gc, V> {
     pub fn get(&self, name: QName<'gc>) -> Option<&V> {
         self.0.get(&name.local_name()).iter().find_map(|v| {
             v.iter()
-                .filter(|(n, _)| *n == name.namespace())
+                .filter(|(n, _)| name.namespace().matches_ns(*n))
                 .map(|(_, v)| v)
                 .next()
         })
@@ -72,7 +74,7 @@ impl<'gc, V> PropertyMap<'gc, V> {
         if let Some(local_name) = name.local_name() {
             self.0.get(&local_name).iter().find_map(|v| {
                 v.iter()
-                    .filter(|(n, _)| name.namespace_set().iter().any(|ns| *ns == *n))
+                    .filter(|(n, _)| name.namespace_set().iter().any(|ns| ns.matches_ns(*n)))
                     .map(|(_, v)| v)
                     .next()
             })
@@ -88,7 +90,7 @@ impl<'gc, V> PropertyMap<'gc, V> {
         if let Some(local_name) = name.local_name() {
             self.0.get(&local_name).iter().find_map(|v| {
                 v.iter()
-                    .filter(|(n, _)| name.namespace_set().iter().any(|ns| *ns == *n))
+                    .filter(|(n, _)| name.namespace_set().iter().any(|ns| ns.matches_ns(*n)))
                     .map(|(ns, v)| (*ns, v))
                     .next()
             })
@@ -99,7 +101,10 @@ impl<'gc, V> PropertyMap<'gc, V> {
 
     pub fn get_mut(&mut self, name: QName<'gc>) -> Option<&mut V> {
         if let Some(bucket) = self.0.get_mut(&name.local_name()) {
-            if let Some((_, old_value)) = bucket.iter_mut().find(|(n, _)| *n == name.namespace()) {
+            if let Some((_, old_value)) = bucket
+                .iter_mut()
+                .find(|(n, _)| name.namespace().matches_ns(*n))
+            {
                 return Some(old_value);
             }
         }
@@ -111,7 +116,7 @@ impl<'gc, V> PropertyMap<'gc, V> {
         self.0
             .get(&name.local_name())
             .iter()
-            .any(|v| v.iter().any(|(n, _)| *n == name.namespace()))
+            .any(|v| v.iter().any(|(n, _)| name.namespace().matches_ns(*n)))
     }
 
     pub fn iter(&self) -> impl Iterator<Item = (AvmString<'gc>, Namespace<'gc>, &V)> {
@@ -123,7 +128,10 @@ impl<'gc, V> PropertyMap<'gc, V> {
     pub fn insert(&mut self, name: QName<'gc>, mut value: V) -> Option<V> {
         let bucket = self.0.entry(name.local_name()).or_default();
 
-        if let Some((_, old_value)) = bucket.iter_mut().find(|(n, _)| *n == name.namespace()) {
+        if let Some((_, old_value)) = bucket
+            .iter_mut()
+            .find(|(n, _)| name.namespace().matches_ns(*n))
+        {
             swap(old_value, &mut value);
 
             Some(value)
@@ -142,7 +150,7 @@ impl<'gc, V> PropertyMap<'gc, V> {
     ) -> Option<V> {
         let bucket = self.0.entry(name).or_default();
 
-        if let Some((_, old_value)) = bucket.iter_mut().find(|(n, _)| *n == ns) {
+        if let Some((_, old_value)) = bucket.iter_mut().find(|(n, _)| ns.matches_ns(*n)) {
             swap(old_value, &mut value);
 
             Some(value)
@@ -161,7 +169,7 @@ impl<'gc, V> PropertyMap<'gc, V> {
             let position = bucket
                 .iter_mut()
                 .enumerate()
-                .find(|(_, (n, _))| *n == name.namespace());
+                .find(|(_, (n, _))| name.namespace().matches_ns(*n));
             if let Some((position, _)) = position {
                 return Some(bucket.remove(position).1);
             }
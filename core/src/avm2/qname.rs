@@ -83,6 +83,9 @@ impl<'gc> QName<'gc> {
             .rsplit_once(WStr::from_units(b"::"))
             .or_else(|| name.rsplit_once(WStr::from_units(b".")));
 
+        // Like names in the movie's code, these only see the API of the root movie's version.
+        let swf_version = activation.context.swf.version();
+        let api_version = activation.avm2().movie_api_version(swf_version);
         if let Some((package_name, local_name)) = parts {
             let mut context = activation.borrow_gc();
             let package_name = context
@@ -90,14 +93,24 @@ impl<'gc> QName<'gc> {
                 .intern_wstr(context.gc_context, package_name);
 
             Self {
-                ns: Namespace::package(package_name, &mut context),
+                ns: Namespace::package_with_api_version(package_name, api_version, &mut context),
                 name: AvmString::new(context.gc_context, local_name),
             }
         } else {
-            Self {
-                ns: activation.avm2().public_namespace,
-                name,
-            }
+            let ns = match activation.avm2().movie_public_namespace {
+                Some(ns) if ns.api_version() == Some(api_version) => ns,
+                _ => {
+                    let ns = Namespace::package_with_api_version(
+                        "",
+                        api_version,
+                        &mut activation.borrow_gc(),
+                    );
+                    activation.avm2().movie_public_namespace = Some(ns);
+                    ns
+                }
+            };
+
+            Self { ns, name }
         }
     }
 
@@ -122,12 +135,7 @@ impl<'gc> QName<'gc> {
         if uri.is_empty() {
             Either::Left(name)
         } else {
-            Either::Right({
-                let mut buf = WString::from(uri.as_wstr());
-                buf.push_str(WStr::from_units(b"::"));
-                buf.push_str(&name);
-                buf
-            })
+            Either::Right(join_qualified_name(&uri, WStr::from_units(b"::"), &name))
         }
     }
 
@@ -135,14 +143,14 @@ impl<'gc> QName<'gc> {
     // the namespace and local name. This matches the output produced by
     // Flash Player in error messages
     pub fn to_qualified_name_err_message(self, mc: &Mutation<'gc>) -> AvmString<'gc> {
-        let mut buf = WString::new();
         let uri = self.namespace().as_uri();
-        if !uri.is_empty() {
-            buf.push_str(&uri);
-            buf.push_char('.');
+        if uri.is_empty() {
+            return self.local_name();
         }
-        buf.push_str(&self.local_name());
-        AvmString::new(mc, buf)
+        AvmString::new(
+            mc,
+            join_qualified_name(&uri, WStr::from_units(b"."), &self.local_name()),
+        )
     }
 
     pub fn local_name(&self) -> AvmString<'gc> {
@@ -162,13 +170,26 @@ impl<'gc> QName<'gc> {
             None => WStr::from_units(b"*"),
         };
 
-        let mut uri = WString::from(ns);
-        uri.push_str(WStr::from_units(b"::"));
-        uri.push_str(&self.name);
-        AvmString::new(mc, uri)
+        AvmString::new(
+            mc,
+            join_qualified_name(ns, WStr::from_units(b"::"), &self.name),
+        )
     }
 }
 
+/// Joins a namespace URI and a local name with `separator`.
+///
+/// Qualified names get formatted for every error message and `describeType`
+/// call, so this sizes the string up front rather than growing it piece by piece.
+pub fn join_qualified_name(ns: &WStr, separator: &WStr, name: &WStr) -> WString {
+    let wide = ns.is_wide() || separator.is_wide() || name.is_wide();
+    let mut buf = WString::with_capacity(ns.len() + separator.len() + name.len(), wide);
+    buf.push_str(ns);
+    buf.push_str(separator);
+    buf.push_str(name);
+    buf
+}
+
 impl<'gc> Debug for QName<'gc> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
         match self.to_qualified_name_no_mc() {
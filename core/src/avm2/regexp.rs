@@ -96,16 +96,30 @@ impl<'gc> RegExp<'gc> {
         F: FnOnce(&mut CachedText<'gc>, regress::Match) -> T,
     {
         if self.cached_regex.is_none() {
-            let re = regress::Regex::with_flags(
-                &self.source.to_utf8_lossy(),
-                regress::Flags {
-                    icase: self.flags.contains(RegExpFlags::IGNORE_CASE),
-                    multiline: self.flags.contains(RegExpFlags::MULTILINE),
-                    dot_all: self.flags.contains(RegExpFlags::DOTALL),
-                    no_opt: false,
-                    unicode: false,
-                },
-            );
+            let source = self.source.to_utf8_lossy();
+            let compile = |pattern: &str, unicode| {
+                regress::Regex::with_flags(
+                    pattern,
+                    regress::Flags {
+                        icase: self.flags.contains(RegExpFlags::IGNORE_CASE),
+                        multiline: self.flags.contains(RegExpFlags::MULTILINE),
+                        // `.` is translated by hand, see `translate_pattern`.
+                        dot_all: false,
+                        no_opt: false,
+                        unicode,
+                    },
+                )
+            };
+            // Property classes need Unicode mode, which is stricter about escapes.
+            // If the pattern doesn't compile that way, `\p` is just a `p`, as in Flash.
+            let (pattern, uses_properties) = translate_pattern(&source, self.flags, true);
+            let re = match uses_properties {
+                true => compile(&pattern, true).or_else(|_| {
+                    let (pattern, _) = translate_pattern(&source, self.flags, false);
+                    compile(&pattern, false)
+                }),
+                false => compile(&pattern, false),
+            };
             self.cached_regex = Some(re.map_err(drop));
         }
 
@@ -362,6 +376,149 @@ impl<'gc> RegExp<'gc> {
     }
 }
 
+/// The characters PCRE's `\s` matches. Unlike ECMAScript, this doesn't
+/// include vertical tab or any non-ASCII spaces.
+const PCRE_SPACES: &str = "\\t\\n\\x0c\\r ";
+
+/// The ranges of every character outside of [`PCRE_SPACES`], for a `\S` in a
+/// class, where it can't be written as a negated class.
+const PCRE_NON_SPACES: &str = "\\x00-\\x08\\x0b\\x0e-\\x1f\\x21-\u{10FFFF}";
+
+/// Translates a Flash (PCRE) pattern into one `regress` understands.
+///
+/// This covers the syntax the two disagree on: named groups (`(?P<name>...)`
+/// and `(?P=name)`), `(?#...)` comments, a leading `]` in a class, the
+/// extended (`x`) flag, `.` (which only excludes `\n` in Flash), ASCII-only
+/// `\s`, and PCRE's spelling of Unicode property classes.
+///
+/// Property classes are only translated for `unicode` mode. Otherwise, `\p`
+/// and `\P` are plain letters, and what follows them is left as it is.
+///
+/// Also returns whether the pattern uses Unicode property classes.
+fn translate_pattern(source: &str, flags: RegExpFlags, unicode: bool) -> (String, bool) {
+    let extended = flags.contains(RegExpFlags::EXTENDED);
+    let dot_all = flags.contains(RegExpFlags::DOTALL);
+
+    let mut out = String::with_capacity(source.len());
+    let mut uses_properties = false;
+    let mut in_class = false;
+    let mut chars = source.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some('s') if in_class => out.push_str(PCRE_SPACES),
+                Some('s') => {
+                    out.push('[');
+                    out.push_str(PCRE_SPACES);
+                    out.push(']');
+                }
+                Some('S') if in_class => out.push_str(PCRE_NON_SPACES),
+                Some('S') => {
+                    out.push_str("[^");
+                    out.push_str(PCRE_SPACES);
+                    out.push(']');
+                }
+                Some(p @ ('p' | 'P')) if !unicode => {
+                    uses_properties = true;
+                    out.push(p);
+                }
+                Some(p @ ('p' | 'P')) => {
+                    let name = if chars.next_if_eq(&'{').is_some() {
+                        let mut name = String::new();
+                        for c in chars.by_ref() {
+                            if c == '}' {
+                                break;
+                            }
+                            name.push(c);
+                        }
+                        name
+                    } else {
+                        chars.next().map(String::from).unwrap_or_default()
+                    };
+                    uses_properties = true;
+                    out.push_str(&unicode_property(p, &name));
+                }
+                Some(c) => {
+                    out.push('\\');
+                    out.push(c);
+                }
+                None => out.push('\\'),
+            },
+            '[' if !in_class => {
+                in_class = true;
+                out.push('[');
+                if chars.next_if_eq(&'^').is_some() {
+                    out.push('^');
+                }
+                // A `]` straight after the opening bracket is a literal in PCRE.
+                if chars.next_if_eq(&']').is_some() {
+                    out.push_str("\\]");
+                }
+            }
+            ']' if in_class => {
+                in_class = false;
+                out.push(']');
+            }
+            '.' if !in_class && !dot_all => out.push_str("[^\\n]"),
+            '(' if !in_class && chars.next_if_eq(&'?').is_some() => {
+                if chars.next_if_eq(&'#').is_some() {
+                    // Comment group.
+                    for c in chars.by_ref() {
+                        if c == ')' {
+                            break;
+                        }
+                    }
+                } else if chars.next_if_eq(&'P').is_some() {
+                    if chars.next_if_eq(&'=').is_some() {
+                        // Named backreference.
+                        out.push_str("\\k<");
+                        for c in chars.by_ref() {
+                            if c == ')' {
+                                break;
+                            }
+                            out.push(c);
+                        }
+                        out.push('>');
+                    } else {
+                        out.push_str("(?");
+                    }
+                } else {
+                    out.push_str("(?");
+                }
+            }
+            c if extended && !in_class && c.is_ascii_whitespace() => {}
+            '#' if extended && !in_class => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            }
+            c => out.push(c),
+        }
+    }
+
+    (out, uses_properties)
+}
+
+/// Spells a PCRE property class (`\pL`, `\p{Lu}`, `\p{^Greek}`, ...) the
+/// ECMAScript way.
+fn unicode_property(p: char, name: &str) -> String {
+    let (p, name) = match name.strip_prefix('^') {
+        Some(name) => (if p == 'p' { 'P' } else { 'p' }, name),
+        None => (p, name),
+    };
+    match name {
+        "L&" => format!("\\{p}{{LC}}"),
+        "Any" => format!("\\{p}{{Any}}"),
+        // PCRE only knows general categories by their one or two letter names,
+        // and anything longer is a script.
+        _ if name.len() <= 2 => format!("\\{p}{{{name}}}"),
+        _ => format!("\\{p}{{Script={name}}}"),
+    }
+}
+
 #[derive(Collect, Debug)]
 #[collect(no_drop)]
 struct CachedText<'gc> {
@@ -468,3 +625,64 @@ impl<'gc> CachedText<'gc> {
         Some(self.cur_utf16_index)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{translate_pattern, RegExpFlags};
+
+    fn translate(source: &str, flags: RegExpFlags) -> String {
+        translate_pattern(source, flags, true).0
+    }
+
+    #[test]
+    fn named_groups() {
+        assert_eq!(
+            translate("(?P<id>\\d+)-(?P=id)", RegExpFlags::DOTALL),
+            "(?<id>\\d+)-\\k<id>"
+        );
+        assert_eq!(
+            translate("(?<=a)b(?<!c)", RegExpFlags::DOTALL),
+            "(?<=a)b(?<!c)"
+        );
+    }
+
+    #[test]
+    fn extended() {
+        assert_eq!(
+            translate(
+                "^ [a-z ]+ # name\n \\# \\d{2} (?# id)$",
+                RegExpFlags::EXTENDED | RegExpFlags::DOTALL
+            ),
+            "^[a-z ]+\\#\\d{2}$"
+        );
+        assert_eq!(translate("a b#c", RegExpFlags::DOTALL), "a b#c");
+    }
+
+    #[test]
+    fn classes() {
+        assert_eq!(translate("a.b", RegExpFlags::empty()), "a[^\\n]b");
+        assert_eq!(translate("a.b[.]", RegExpFlags::DOTALL), "a.b[.]");
+        assert_eq!(translate("[]a]", RegExpFlags::DOTALL), "[\\]a]");
+        assert_eq!(translate("[^]]", RegExpFlags::DOTALL), "[^\\]]");
+        assert_eq!(
+            translate("\\s[\\s]", RegExpFlags::DOTALL),
+            "[\\t\\n\\x0c\\r ][\\t\\n\\x0c\\r ]"
+        );
+        assert_eq!(
+            translate("\\S[\\S][^\\S]", RegExpFlags::DOTALL),
+            "[^\\t\\n\\x0c\\r ][\\x00-\\x08\\x0b\\x0e-\\x1f\\x21-\u{10FFFF}][^\\x00-\\x08\\x0b\\x0e-\\x1f\\x21-\u{10FFFF}]"
+        );
+    }
+
+    #[test]
+    fn unicode_properties() {
+        assert_eq!(
+            translate_pattern("\\pL\\p{^Lu}\\P{Greek}", RegExpFlags::DOTALL, true),
+            ("\\p{L}\\P{Lu}\\P{Script=Greek}".to_string(), true)
+        );
+        assert_eq!(
+            translate_pattern("\\pL\\p{^Lu}", RegExpFlags::DOTALL, false),
+            ("pLp{^Lu}".to_string(), true)
+        );
+    }
+}
@@ -2,6 +2,7 @@
 
 use super::traits::TraitKind;
 use crate::avm2::activation::Activation;
+use crate::avm2::api_version::ApiVersion;
 use crate::avm2::class::Class;
 use crate::avm2::domain::Domain;
 use crate::avm2::method::{BytecodeMethod, Method};
@@ -77,6 +78,10 @@ struct TranslationUnitData<'gc> {
 
     /// The movie that this TranslationUnit was loaded from.
     movie: Arc<SwfMovie>,
+
+    /// The API version of the public namespaces in this translation unit, or `None` if they're
+    /// marked with their own, as in our player globals.
+    api_version: Option<ApiVersion>,
 }
 
 impl<'gc> TranslationUnit<'gc> {
@@ -87,6 +92,7 @@ impl<'gc> TranslationUnit<'gc> {
         domain: Domain<'gc>,
         name: Option<AvmString<'gc>>,
         movie: Arc<SwfMovie>,
+        api_version: Option<ApiVersion>,
         mc: &Mutation<'gc>,
     ) -> Self {
         let classes = vec![None; abc.classes.len()];
@@ -109,6 +115,7 @@ impl<'gc> TranslationUnit<'gc> {
                 namespaces,
                 multinames,
                 movie,
+                api_version,
             },
         ))
     }
@@ -117,6 +124,12 @@ impl<'gc> TranslationUnit<'gc> {
         self.0.read().domain
     }
 
+    /// The API version of the public namespaces in this translation unit, or `None` if they're
+    /// marked with their own.
+    pub fn api_version(self) -> Option<ApiVersion> {
+        self.0.read().api_version
+    }
+
     // Retrieve the name associated with the original `DoAbc2` tag
     pub fn name(self) -> Option<AvmString<'gc>> {
         self.0.read().name
@@ -8,7 +8,8 @@ use crate::avm2::script::TranslationUnit;
 use crate::avm2::Error;
 use crate::avm2::Multiname;
 use crate::avm2::Namespace;
-use crate::ecma_conversions::{f64_to_wrapping_i32, f64_to_wrapping_u32};
+use crate::ecma_conversions::{f64_to_wrapping_i32, f64_to_wrapping_u32, is_negative_zero};
+use crate::number_format;
 use crate::string::{AvmAtom, AvmString, WStr};
 use gc_arena::{Collect, GcCell, Mutation};
 use std::cell::Ref;
@@ -578,11 +579,11 @@ impl<'gc> Value<'gc> {
     pub fn as_integer(&self, mc: &Mutation<'gc>) -> Result<i32, Error<'gc>> {
         match self {
             Value::Object(num) => match num.value_of(mc)? {
-                Value::Number(num) => Ok(num as i32),
+                Value::Number(num) => Ok(f64_to_wrapping_i32(num)),
                 Value::Integer(num) => Ok(num),
                 _ => Err(format!("Expected Number, int, or uint, found {self:?}").into()),
             },
-            Value::Number(num) => Ok(*num as i32),
+            Value::Number(num) => Ok(f64_to_wrapping_i32(*num)),
             Value::Integer(num) => Ok(*num),
             _ => Err(format!("Expected Number, int, or uint, found {self:?}").into()),
         }
@@ -592,11 +593,11 @@ impl<'gc> Value<'gc> {
     pub fn as_u32(&self, mc: &Mutation<'gc>) -> Result<u32, Error<'gc>> {
         match self {
             Value::Object(num) => match num.value_of(mc)? {
-                Value::Number(num) => Ok(num as u32),
+                Value::Number(num) => Ok(f64_to_wrapping_u32(num)),
                 Value::Integer(num) => Ok(num as u32),
                 _ => Err(format!("Expected Number, int, or uint, found {self:?}").into()),
             },
-            Value::Number(num) => Ok(*num as u32),
+            Value::Number(num) => Ok(f64_to_wrapping_u32(*num)),
             Value::Integer(num) => Ok(*num as u32),
             _ => Err(format!("Expected Number, int, or uint, found {self:?}").into()),
         }
@@ -680,7 +681,7 @@ impl<'gc> Value<'gc> {
                     activation,
                     &format!(
                         "Error #1050: Cannot convert {} to primitive.",
-                        o.instance_of_class_name(activation.context.gc_context)
+                        o.instance_of_class_name()
                     ),
                     1050,
                 )?))
@@ -709,7 +710,7 @@ impl<'gc> Value<'gc> {
                     activation,
                     &format!(
                         "Error #1050: Cannot convert {} to primitive.",
-                        o.instance_of_class_name(activation.context.gc_context)
+                        o.instance_of_class_name()
                     ),
                     1050,
                 )?))
@@ -769,32 +770,15 @@ impl<'gc> Value<'gc> {
         Ok(f64_to_wrapping_i32(self.coerce_to_number(activation)?))
     }
 
-    /// Minimum number of digits after which numbers are formatted as
-    /// exponential strings.
-    const MIN_DIGITS: f64 = -6.0;
-
-    /// Maximum number of digits before numbers are formatted as exponential
-    /// strings.
-    const MAX_DIGITS: f64 = 21.0;
-
-    /// Maximum number of significant digits renderable within coerced numbers.
-    ///
-    /// Any precision beyond this point will be discarded and replaced with
-    /// zeroes (for whole parts) or not rendered (for decimal parts).
-    const MAX_PRECISION: f64 = 15.0;
-
     /// Coerce the value to a String.
     ///
     /// This function returns the resulting String directly; or a TypeError if
     /// the value is an `Object` that cannot be converted to a primitive value.
     ///
     /// String conversions generally occur according to ECMA-262 3rd Edition's
-    /// ToString algorithm. The conversion of numbers to strings appears to be
-    /// somewhat underspecified; there are several formatting modes which
-    /// change at specific digit count cutoffs, but the spec allows
-    /// implementations to limit how much precision is displayed on coerced
-    /// numbers, even if that precision would result in rounding the whole part
-    /// of the number. (This is confusingly expressed in ECMA-262.)
+    /// ToString algorithm. Like avmplus, numbers are printed with the shortest
+    /// digits that convert back to the same number; see
+    /// `number_format::to_string`.
     ///
     /// TODO: The cutoffs change based on SWF/ABC version. Targeting FP10.3 in
     /// Animate CC 2020 significantly reduces them (towards zero).
@@ -809,32 +793,8 @@ impl<'gc> Value<'gc> {
             Value::Bool(false) => "false".into(),
             Value::Number(n) if n.is_nan() => "NaN".into(),
             Value::Number(n) if *n == 0.0 => "0".into(),
-            Value::Number(n) if *n < 0.0 => AvmString::new_utf8(
-                activation.context.gc_context,
-                format!("-{}", Value::Number(-n).coerce_to_string(activation)?),
-            ),
-            Value::Number(n) if n.is_infinite() => "Infinity".into(),
             Value::Number(n) => {
-                let digits = n.log10().floor();
-
-                // TODO: This needs to limit precision in the resulting decimal
-                // output, not in binary.
-                let precision = (n * 10.0_f64.powf(Self::MAX_PRECISION - digits)).floor()
-                    / 10.0_f64.powf(Self::MAX_PRECISION - digits);
-
-                if digits < Self::MIN_DIGITS || digits >= Self::MAX_DIGITS {
-                    AvmString::new_utf8(
-                        activation.context.gc_context,
-                        format!(
-                            "{}e{}{}",
-                            precision / 10.0_f64.powf(digits),
-                            if digits < 0.0 { "-" } else { "+" },
-                            digits.abs()
-                        ),
-                    )
-                } else {
-                    AvmString::new_utf8(activation.context.gc_context, n.to_string())
-                }
+                AvmString::new_utf8(activation.context.gc_context, number_format::to_string(*n))
             }
             Value::Integer(i) => AvmString::new_utf8(activation.context.gc_context, i.to_string()),
             Value::String(s) => *s,
@@ -955,7 +915,7 @@ impl<'gc> Value<'gc> {
                         &format!(
                             "Error #1006: {} is not a function of class {}.",
                             name,
-                            receiver.instance_of_class_name(activation.context.gc_context)
+                            receiver.instance_of_class_name()
                         ),
                         1006,
                     )
@@ -1070,10 +1030,7 @@ impl<'gc> Value<'gc> {
                 // followed by something that looks like an address (it varies between executions).
                 // For now, we just set the "address" to all zeroes, on the off chance that some
                 // application is trying to parse the error message.
-                format!(
-                    "{}@00000000000",
-                    obj.instance_of_class_name(activation.context.gc_context)
-                )
+                format!("{}@00000000000", obj.instance_of_class_name())
             }
             _ => self.coerce_to_debug_string(activation)?.to_string(),
         };
@@ -1097,10 +1054,12 @@ impl<'gc> Value<'gc> {
 
     /// Determine if this value is a number representable as a u32 without loss
     /// of precision.
+    ///
+    /// Like avmplus, negative zero is only ever a `Number`.
     #[allow(clippy::float_cmp)]
     pub fn is_u32(&self) -> bool {
         match self {
-            Value::Number(n) => *n == (*n as u32 as f64),
+            Value::Number(n) => *n == (*n as u32 as f64) && !is_negative_zero(*n),
             Value::Integer(i) => *i >= 0,
             Value::Object(o) => o.as_primitive().map(|p| p.is_u32()).unwrap_or(false),
             _ => false,
@@ -1109,10 +1068,12 @@ impl<'gc> Value<'gc> {
 
     /// Determine if this value is a number representable as an i32 without
     /// loss of precision.
+    ///
+    /// Like avmplus, negative zero is only ever a `Number`.
     #[allow(clippy::float_cmp)]
     pub fn is_i32(&self) -> bool {
         match self {
-            Value::Number(n) => *n == (*n as i32 as f64),
+            Value::Number(n) => *n == (*n as i32 as f64) && !is_negative_zero(*n),
             Value::Integer(_) => true,
             Value::Object(o) => o.as_primitive().map(|p| p.is_i32()).unwrap_or(false),
             _ => false,
@@ -7,8 +7,214 @@ use crate::avm2::value::Value;
 use crate::avm2::Error;
 use gc_arena::Collect;
 use std::cmp::{max, min};
-use std::ops::{Index, RangeBounds};
-use std::slice::SliceIndex;
+use std::ops::{Bound, RangeBounds};
+
+/// The elements of a vector.
+///
+/// Vectors of `int`, `uint` and `Number` store their elements unboxed, which
+/// makes them much cheaper to create, read and write than a `Vec<Value>`.
+///
+/// Values stored into a specialized vector are assumed to already have been
+/// coerced to its type; anything else is converted like `Value::as_number`
+/// would, falling back to zero (or NaN).
+#[derive(Collect, Clone, Debug)]
+#[collect(no_drop)]
+enum VectorData<'gc> {
+    Int(Vec<i32>),
+    Uint(Vec<u32>),
+    Number(Vec<f64>),
+    Value(Vec<Value<'gc>>),
+}
+
+fn to_int(value: Value<'_>) -> i32 {
+    match value {
+        Value::Integer(i) => i,
+        Value::Number(n) => n as i32,
+        _ => 0,
+    }
+}
+
+fn to_uint(value: Value<'_>) -> u32 {
+    match value {
+        Value::Integer(i) => i as u32,
+        Value::Number(n) => n as u32,
+        _ => 0,
+    }
+}
+
+fn to_number(value: Value<'_>) -> f64 {
+    match value {
+        Value::Integer(i) => i as f64,
+        Value::Number(n) => n,
+        _ => f64::NAN,
+    }
+}
+
+impl<'gc> VectorData<'gc> {
+    /// Creates empty storage suited to the given vector type.
+    fn for_type(
+        value_type: Option<ClassObject<'gc>>,
+        activation: &mut Activation<'_, 'gc>,
+    ) -> Self {
+        let classes = activation.avm2().classes();
+        match value_type {
+            Some(class) if Object::ptr_eq(class, classes.int) => Self::Int(vec![]),
+            Some(class) if Object::ptr_eq(class, classes.uint) => Self::Uint(vec![]),
+            Some(class) if Object::ptr_eq(class, classes.number) => Self::Number(vec![]),
+            _ => Self::Value(vec![]),
+        }
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            Self::Int(v) => v.len(),
+            Self::Uint(v) => v.len(),
+            Self::Number(v) => v.len(),
+            Self::Value(v) => v.len(),
+        }
+    }
+
+    /// Whether `value` can be stored as-is, without being coerced to the vector type first.
+    fn is_coerced(&self, value: Value<'gc>) -> bool {
+        matches!(
+            (self, value),
+            (Self::Int(_), Value::Integer(_))
+                | (Self::Number(_), Value::Integer(_) | Value::Number(_))
+        ) || matches!((self, value), (Self::Uint(_), Value::Integer(i)) if i >= 0)
+    }
+
+    fn default_value(&self) -> Value<'gc> {
+        match self {
+            Self::Int(_) | Self::Uint(_) => Value::Integer(0),
+            Self::Number(_) => Value::Number(0.0),
+            Self::Value(_) => Value::Null,
+        }
+    }
+
+    /// The value `pop` and `shift` return when the vector is empty.
+    fn empty_value(&self) -> Value<'gc> {
+        match self {
+            Self::Value(_) => Value::Undefined,
+            _ => self.default_value(),
+        }
+    }
+
+    fn get(&self, pos: usize) -> Option<Value<'gc>> {
+        match self {
+            Self::Int(v) => v.get(pos).map(|&i| i.into()),
+            Self::Uint(v) => v.get(pos).map(|&u| u.into()),
+            Self::Number(v) => v.get(pos).map(|&n| n.into()),
+            Self::Value(v) => v.get(pos).cloned(),
+        }
+    }
+
+    /// Stores a value, returning `false` if `pos` is out of bounds.
+    fn set(&mut self, pos: usize, value: Value<'gc>) -> bool {
+        match self {
+            Self::Int(v) => v.get_mut(pos).map(|slot| *slot = to_int(value)),
+            Self::Uint(v) => v.get_mut(pos).map(|slot| *slot = to_uint(value)),
+            Self::Number(v) => v.get_mut(pos).map(|slot| *slot = to_number(value)),
+            Self::Value(v) => v.get_mut(pos).map(|slot| *slot = value),
+        }
+        .is_some()
+    }
+
+    fn insert(&mut self, pos: usize, value: Value<'gc>) {
+        match self {
+            Self::Int(v) => v.insert(pos, to_int(value)),
+            Self::Uint(v) => v.insert(pos, to_uint(value)),
+            Self::Number(v) => v.insert(pos, to_number(value)),
+            Self::Value(v) => v.insert(pos, value),
+        }
+    }
+
+    fn push(&mut self, value: Value<'gc>) {
+        match self {
+            Self::Int(v) => v.push(to_int(value)),
+            Self::Uint(v) => v.push(to_uint(value)),
+            Self::Number(v) => v.push(to_number(value)),
+            Self::Value(v) => v.push(value),
+        }
+    }
+
+    fn pop(&mut self) -> Option<Value<'gc>> {
+        match self {
+            Self::Int(v) => v.pop().map(Into::into),
+            Self::Uint(v) => v.pop().map(Into::into),
+            Self::Number(v) => v.pop().map(Into::into),
+            Self::Value(v) => v.pop(),
+        }
+    }
+
+    fn remove(&mut self, pos: usize) -> Value<'gc> {
+        match self {
+            Self::Int(v) => v.remove(pos).into(),
+            Self::Uint(v) => v.remove(pos).into(),
+            Self::Number(v) => v.remove(pos).into(),
+            Self::Value(v) => v.remove(pos),
+        }
+    }
+
+    fn resize(&mut self, length: usize) {
+        match self {
+            Self::Int(v) => v.resize(length, 0),
+            Self::Uint(v) => v.resize(length, 0),
+            Self::Number(v) => v.resize(length, 0.0),
+            Self::Value(v) => v.resize(length, Value::Null),
+        }
+    }
+
+    fn reserve_exact(&mut self, additional: usize) {
+        match self {
+            Self::Int(v) => v.reserve_exact(additional),
+            Self::Uint(v) => v.reserve_exact(additional),
+            Self::Number(v) => v.reserve_exact(additional),
+            Self::Value(v) => v.reserve_exact(additional),
+        }
+    }
+
+    fn reverse(&mut self) {
+        match self {
+            Self::Int(v) => v.reverse(),
+            Self::Uint(v) => v.reverse(),
+            Self::Number(v) => v.reverse(),
+            Self::Value(v) => v.reverse(),
+        }
+    }
+
+    /// Replaces the contents with `values`, keeping the same specialization.
+    fn replace(&mut self, values: Vec<Value<'gc>>) {
+        *self = match self {
+            Self::Int(_) => Self::Int(values.into_iter().map(to_int).collect()),
+            Self::Uint(_) => Self::Uint(values.into_iter().map(to_uint).collect()),
+            Self::Number(_) => Self::Number(values.into_iter().map(to_number).collect()),
+            Self::Value(_) => Self::Value(values),
+        };
+    }
+
+    fn splice(
+        &mut self,
+        range: (Bound<usize>, Bound<usize>),
+        replace_with: Vec<Value<'gc>>,
+    ) -> Vec<Value<'gc>> {
+        let replace_with = replace_with.into_iter();
+        match self {
+            Self::Int(v) => v
+                .splice(range, replace_with.map(to_int))
+                .map(Into::into)
+                .collect(),
+            Self::Uint(v) => v
+                .splice(range, replace_with.map(to_uint))
+                .map(Into::into)
+                .collect(),
+            Self::Number(v) => v
+                .splice(range, replace_with.map(to_number))
+                .map(Into::into)
+                .collect(),
+            Self::Value(v) => v.splice(range, replace_with).collect(),
+        }
+    }
+}
 
 /// The vector storage portion of a vector object.
 ///
@@ -23,7 +229,7 @@ use std::slice::SliceIndex;
 #[collect(no_drop)]
 pub struct VectorStorage<'gc> {
     /// The storage for vector values.
-    storage: Vec<Value<'gc>>,
+    storage: VectorData<'gc>,
 
     /// Whether or not the array length is fixed.
     is_fixed: bool,
@@ -46,19 +252,14 @@ impl<'gc> VectorStorage<'gc> {
         value_type: Option<ClassObject<'gc>>,
         activation: &mut Activation<'_, 'gc>,
     ) -> Self {
-        let storage = Vec::new();
+        let mut storage = VectorData::for_type(value_type, activation);
+        storage.resize(length);
 
-        let mut self_vec = VectorStorage {
+        VectorStorage {
             storage,
             is_fixed,
             value_type,
-        };
-
-        self_vec
-            .storage
-            .resize(length, self_vec.default(activation));
-
-        self_vec
+        }
     }
 
     pub fn check_fixed(&self, activation: &mut Activation<'_, 'gc>) -> Result<(), Error<'gc>> {
@@ -77,10 +278,14 @@ impl<'gc> VectorStorage<'gc> {
     /// The values are assumed to already have been coerced to the value type
     /// given.
     pub fn from_values(
-        storage: Vec<Value<'gc>>,
+        values: Vec<Value<'gc>>,
         is_fixed: bool,
         value_type: Option<ClassObject<'gc>>,
+        activation: &mut Activation<'_, 'gc>,
     ) -> Self {
+        let mut storage = VectorData::for_type(value_type, activation);
+        storage.replace(values);
+
         VectorStorage {
             storage,
             is_fixed,
@@ -88,6 +293,69 @@ impl<'gc> VectorStorage<'gc> {
         }
     }
 
+    /// Create a new `Vector.<int>` storage.
+    pub fn from_ints(
+        values: Vec<i32>,
+        is_fixed: bool,
+        activation: &mut Activation<'_, 'gc>,
+    ) -> Self {
+        VectorStorage {
+            storage: VectorData::Int(values),
+            is_fixed,
+            value_type: Some(activation.avm2().classes().int),
+        }
+    }
+
+    /// Create a new `Vector.<uint>` storage.
+    pub fn from_uints(
+        values: Vec<u32>,
+        is_fixed: bool,
+        activation: &mut Activation<'_, 'gc>,
+    ) -> Self {
+        VectorStorage {
+            storage: VectorData::Uint(values),
+            is_fixed,
+            value_type: Some(activation.avm2().classes().uint),
+        }
+    }
+
+    /// Create a new `Vector.<Number>` storage.
+    pub fn from_numbers(
+        values: Vec<f64>,
+        is_fixed: bool,
+        activation: &mut Activation<'_, 'gc>,
+    ) -> Self {
+        VectorStorage {
+            storage: VectorData::Number(values),
+            is_fixed,
+            value_type: Some(activation.avm2().classes().number),
+        }
+    }
+
+    /// The elements of a `Vector.<int>`.
+    pub fn as_ints(&self) -> Option<&[i32]> {
+        match &self.storage {
+            VectorData::Int(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    /// The elements of a `Vector.<uint>`.
+    pub fn as_uints(&self) -> Option<&[u32]> {
+        match &self.storage {
+            VectorData::Uint(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    /// The elements of a `Vector.<Number>`.
+    pub fn as_numbers(&self) -> Option<&[f64]> {
+        match &self.storage {
+            VectorData::Number(values) => Some(values),
+            _ => None,
+        }
+    }
+
     pub fn is_fixed(&self) -> bool {
         self.is_fixed
     }
@@ -110,26 +378,14 @@ impl<'gc> VectorStorage<'gc> {
         activation: &mut Activation<'_, 'gc>,
     ) -> Result<(), Error<'gc>> {
         self.check_fixed(activation)?;
-        self.storage.resize(new_length, self.default(activation));
+        self.storage.resize(new_length);
 
         Ok(())
     }
 
     /// Get the default value for this vector.
-    pub fn default(&self, activation: &mut Activation<'_, 'gc>) -> Value<'gc> {
-        if let Some(value_type) = self.value_type {
-            if Object::ptr_eq(value_type, activation.avm2().classes().int)
-                || Object::ptr_eq(value_type, activation.avm2().classes().uint)
-            {
-                Value::Integer(0)
-            } else if Object::ptr_eq(value_type, activation.avm2().classes().number) {
-                Value::Number(0.0)
-            } else {
-                Value::Null
-            }
-        } else {
-            Value::Null
-        }
+    pub fn default(&self, _activation: &mut Activation<'_, 'gc>) -> Value<'gc> {
+        self.storage.default_value()
     }
 
     /// Get the value type stored in this vector (same as the class <T> type).
@@ -146,6 +402,14 @@ impl<'gc> VectorStorage<'gc> {
             .unwrap_or_else(|| activation.avm2().classes().object)
     }
 
+    /// Check if a value can be stored into this vector without coercing it first.
+    ///
+    /// This is a fast path for specialized vectors; a `false` result only
+    /// means that the value has to go through `coerce_to_type`.
+    pub fn is_coerced(&self, value: Value<'gc>) -> bool {
+        self.storage.is_coerced(value)
+    }
+
     /// Check if a vector index is in bounds.
     pub fn is_in_range(&self, pos: usize) -> bool {
         pos < self.storage.len()
@@ -170,7 +434,7 @@ impl<'gc> VectorStorage<'gc> {
         pos: usize,
         activation: &mut Activation<'_, 'gc>,
     ) -> Result<Value<'gc>, Error<'gc>> {
-        if let Some(val) = self.storage.get(pos).cloned() {
+        if let Some(val) = self.storage.get(pos) {
             Ok(val)
         } else {
             Err(Error::AvmError(range_error(
@@ -200,11 +464,9 @@ impl<'gc> VectorStorage<'gc> {
         activation: &mut Activation<'_, 'gc>,
     ) -> Result<(), Error<'gc>> {
         if !self.is_fixed && pos == self.length() {
-            self.storage.resize(pos + 1, self.default(activation));
-        }
-
-        if let Some(v) = self.storage.get_mut(pos) {
-            *v = value;
+            self.storage.push(value);
+            Ok(())
+        } else if self.storage.set(pos, value) {
             Ok(())
         } else {
             Err(Error::AvmError(range_error(
@@ -245,18 +507,8 @@ impl<'gc> VectorStorage<'gc> {
 
         if let Some(v) = self.storage.pop() {
             Ok(v)
-        } else if let Some(value_type) = self.value_type() {
-            if Object::ptr_eq(value_type, activation.avm2().classes().uint)
-                || Object::ptr_eq(value_type, activation.avm2().classes().int)
-            {
-                Ok(Value::Integer(0))
-            } else if Object::ptr_eq(value_type, activation.avm2().classes().number) {
-                Ok(Value::Number(0.0))
-            } else {
-                Ok(Value::Undefined)
-            }
         } else {
-            Ok(Value::Undefined)
+            Ok(self.storage.empty_value())
         }
     }
 
@@ -289,20 +541,10 @@ impl<'gc> VectorStorage<'gc> {
     ) -> Result<Value<'gc>, Error<'gc>> {
         self.check_fixed(activation)?;
 
-        if !self.storage.is_empty() {
+        if self.storage.len() > 0 {
             Ok(self.storage.remove(0))
-        } else if let Some(value_type) = self.value_type() {
-            if Object::ptr_eq(value_type, activation.avm2().classes().uint)
-                || Object::ptr_eq(value_type, activation.avm2().classes().int)
-            {
-                Ok(Value::Integer(0))
-            } else if Object::ptr_eq(value_type, activation.avm2().classes().number) {
-                Ok(Value::Number(0.0))
-            } else {
-                Ok(Value::Undefined)
-            }
         } else {
-            Ok(Value::Undefined)
+            Ok(self.storage.empty_value())
         }
     }
 
@@ -326,11 +568,7 @@ impl<'gc> VectorStorage<'gc> {
         self.check_fixed(activation)?;
 
         let position = self.clamp_parameter_index(position);
-        if position >= self.storage.len() {
-            self.storage.push(value);
-        } else {
-            self.storage.insert(position, value);
-        }
+        self.storage.insert(position, value);
 
         Ok(())
     }
@@ -380,12 +618,16 @@ impl<'gc> VectorStorage<'gc> {
         &'a self,
     ) -> impl DoubleEndedIterator<Item = Value<'gc>> + ExactSizeIterator<Item = Value<'gc>> + 'a
     {
-        self.storage.iter().cloned()
+        (0..self.storage.len()).map(|pos| {
+            self.storage
+                .get(pos)
+                .expect("Vector index should be in range")
+        })
     }
 
     /// Replace this vector's storage with new values.
     pub fn replace_storage(&mut self, new_storage: Vec<Value<'gc>>) {
-        self.storage = new_storage;
+        self.storage.replace(new_storage);
     }
 
     pub fn splice<R>(
@@ -394,12 +636,24 @@ impl<'gc> VectorStorage<'gc> {
         replace_with: Vec<Value<'gc>>,
     ) -> Result<Vec<Value<'gc>>, Error<'gc>>
     where
-        R: Clone + SliceIndex<[Value<'gc>], Output = [Value<'gc>]> + RangeBounds<usize>,
+        R: RangeBounds<usize>,
     {
-        if self.is_fixed && self.storage.index(range.clone()).len() != replace_with.len() {
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start + 1,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end + 1,
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => self.length(),
+        };
+        if self.is_fixed && end.saturating_sub(start) != replace_with.len() {
             return Err("RangeError: Vector is fixed".into());
         }
 
-        Ok(self.storage.splice(range, replace_with).collect())
+        Ok(self
+            .storage
+            .splice((Bound::Included(start), Bound::Excluded(end)), replace_with))
     }
 }
@@ -34,6 +34,10 @@ pub struct VTableData<'gc> {
 
     resolved_traits: PropertyMap<'gc, Property>,
 
+    /// Incremented whenever `resolved_traits` changes, so that inline caches
+    /// holding lookups made against older traits know to redo them.
+    generation: u32,
+
     /// Use hashmaps for the metadata tables because metadata will rarely be present on traits
     slot_metadata_table: HashMap<u32, Box<[Metadata<'gc>]>>,
 
@@ -69,6 +73,7 @@ impl<'gc> VTable<'gc> {
                 scope: None,
                 protected_namespace: None,
                 resolved_traits: PropertyMap::new(),
+                generation: 0,
                 slot_metadata_table: HashMap::new(),
                 disp_metadata_table: HashMap::new(),
                 slot_classes: vec![],
@@ -91,6 +96,7 @@ impl<'gc> VTable<'gc> {
                 scope: None,
                 protected_namespace: None,
                 resolved_traits: rt,
+                generation: 0,
                 slot_metadata_table: HashMap::new(),
                 disp_metadata_table: HashMap::new(),
                 method_table: vec![],
@@ -114,6 +120,15 @@ impl<'gc> VTable<'gc> {
         Ref::map(self.0.read(), |v| &v.resolved_traits)
     }
 
+    pub fn ptr_eq(a: Self, b: Self) -> bool {
+        GcCell::ptr_eq(a.0, b.0)
+    }
+
+    /// Changes whenever the traits of this vtable do.
+    pub fn generation(self) -> u32 {
+        self.0.read().generation
+    }
+
     pub fn get_metadata_for_slot(&self, slot_id: &u32) -> Option<Box<[Metadata<'gc>]>> {
         self.0.read().slot_metadata_table.get(slot_id).cloned()
     }
@@ -274,6 +289,7 @@ impl<'gc> VTable<'gc> {
 
         write.defining_class = Some(defining_class);
         write.scope = Some(scope);
+        write.generation = write.generation.wrapping_add(1);
 
         write.protected_namespace = defining_class
             .inner_class_definition()
@@ -556,6 +572,7 @@ impl<'gc> VTable<'gc> {
         let mut write = self.0.write(mc);
 
         write.default_slots.push(Some(value));
+        write.generation = write.generation.wrapping_add(1);
         let new_slot_id = write.default_slots.len() as u32 - 1;
         write
             .resolved_traits
@@ -581,6 +598,7 @@ impl<'gc> VTable<'gc> {
 
         if let Some(prop) = prop {
             write.resolved_traits.insert(interface_name, prop);
+            write.generation = write.generation.wrapping_add(1);
         }
     }
 
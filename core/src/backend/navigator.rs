@@ -1,20 +1,29 @@
 //! Browser-related platform functions
 
+mod archive;
+
+pub use archive::{ArchiveNavigatorBackend, ArchiveStore, ARCHIVE_SCHEME};
+
 use crate::loader::Error;
+use crate::net_group::{NetGroupAction, NetGroupHandle};
 use crate::socket::{ConnectionState, SocketAction, SocketHandle};
 use crate::string::WStr;
 use async_channel::Receiver;
+use base64::alphabet;
+use base64::engine::{DecodePaddingMode, Engine, GeneralPurpose, GeneralPurposeConfig};
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::fmt::Display;
 use std::future::Future;
+use std::net::SocketAddr;
 use std::path::{Path, PathBuf};
 use std::pin::Pin;
+use std::str::FromStr;
 use std::sync::mpsc::Sender;
 use std::time::Duration;
 use swf::avm1::types::SendVarsMethod;
-use url::{ParseError, Url};
+use url::{ParseError, Position, Url};
 
 /// Enumerates all possible navigation methods.
 #[derive(Copy, Clone)]
@@ -24,6 +33,9 @@ pub enum NavigationMethod {
 
     /// Indicates that navigation should generate a POST request.
     Post,
+
+    /// Indicates that a fetch should generate a HEAD request, whose response has no body.
+    Head,
 }
 
 #[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
@@ -56,6 +68,174 @@ pub enum OpenURLMode {
     Deny,
 }
 
+/// Points connections to one host (and optionally only one of its ports) at another address,
+/// like an entry in a hosts file.
+///
+/// Written as `from=to`, where both sides are a host with an optional `:port`, e.g.
+/// `game.example.com=127.0.0.1:8080`. Without a port on the right, the original port is kept.
+/// IPv6 addresses need brackets to be given a port, as in URLs: `[::1]:8080`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HostOverride {
+    from_host: String,
+    from_port: Option<u16>,
+    to_host: String,
+    to_port: Option<u16>,
+}
+
+impl HostOverride {
+    fn split_host_port(address: &str) -> Result<(String, Option<u16>), String> {
+        let parse_port = |port: &str| {
+            port.parse()
+                .map_err(|_| format!("Invalid port in {address:?}"))
+        };
+        let (host, port) = if let Some(bracketed) = address.strip_prefix('[') {
+            let (host, rest) = bracketed
+                .split_once(']')
+                .ok_or_else(|| format!("Unclosed bracket in {address:?}"))?;
+            let port = match rest {
+                "" => None,
+                _ => match rest.strip_prefix(':') {
+                    Some(port) => Some(parse_port(port)?),
+                    None => return Err(format!("Unexpected {rest:?} in {address:?}")),
+                },
+            };
+            (host, port)
+        } else {
+            match address.rsplit_once(':') {
+                // More than one colon is an IPv6 address without a port.
+                Some((host, _)) if host.contains(':') => (address, None),
+                Some((host, port)) => (host, Some(parse_port(port)?)),
+                None => (address, None),
+            }
+        };
+        if host.is_empty() {
+            return Err(format!("Missing host in {address:?}"));
+        }
+        Ok((host.to_ascii_lowercase(), port))
+    }
+}
+
+impl FromStr for HostOverride {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (from, to) = s
+            .split_once('=')
+            .ok_or_else(|| format!("Expected `from=to`, got {s:?}"))?;
+        let (from_host, from_port) = Self::split_host_port(from.trim())?;
+        let (to_host, to_port) = Self::split_host_port(to.trim())?;
+        Ok(Self {
+            from_host,
+            from_port,
+            to_host,
+            to_port,
+        })
+    }
+}
+
+/// A table of `HostOverride`s, consulted before connecting sockets or fetching URLs.
+#[derive(Clone, Debug, Default)]
+pub struct HostOverrides(Vec<HostOverride>);
+
+impl HostOverrides {
+    pub fn new(overrides: Vec<HostOverride>) -> Self {
+        Self(overrides)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns where a connection to `host:port` should actually go, if it's overridden.
+    ///
+    /// Overrides for the exact port take precedence over those for the whole host.
+    pub fn get(&self, host: &str, port: u16) -> Option<(&str, u16)> {
+        // URLs keep the brackets around IPv6 addresses, but overrides don't.
+        let host = host
+            .strip_prefix('[')
+            .and_then(|host| host.strip_suffix(']'))
+            .unwrap_or(host)
+            .to_ascii_lowercase();
+        let matching = |with_port: bool| {
+            self.0.iter().find(|o| {
+                o.from_host == host
+                    && match o.from_port {
+                        Some(from_port) => with_port && from_port == port,
+                        None => !with_port,
+                    }
+            })
+        };
+        matching(true)
+            .or_else(|| matching(false))
+            .map(|o| (o.to_host.as_str(), o.to_port.unwrap_or(port)))
+    }
+
+    /// Resolves `host:port` through this table, leaving it alone if it isn't overridden.
+    pub fn resolve(&self, host: &str, port: u16) -> (String, u16) {
+        match self.get(host, port) {
+            Some((host, port)) => (host.to_string(), port),
+            None => (host.to_string(), port),
+        }
+    }
+}
+
+/// The directories that the `app:/` and `app-storage:/` URLs of an AIR application refer to.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AirDirectories {
+    /// The directory the application is installed in, seen as `app:/`.
+    application: Url,
+
+    /// The application's private storage directory, seen as `app-storage:/`.
+    storage: Url,
+}
+
+impl AirDirectories {
+    /// Both URLs have to point at directories, and end in a slash.
+    pub fn new(application: Url, storage: Url) -> Self {
+        Self {
+            application,
+            storage,
+        }
+    }
+
+    #[cfg(any(unix, windows, target_os = "redox"))]
+    pub fn from_paths(application: &Path, storage: &Path) -> Option<Self> {
+        Some(Self::new(
+            Url::from_directory_path(application).ok()?,
+            Url::from_directory_path(storage).ok()?,
+        ))
+    }
+
+    pub fn application(&self) -> &Url {
+        &self.application
+    }
+
+    pub fn storage(&self) -> &Url {
+        &self.storage
+    }
+
+    /// Maps an `app:/` or `app-storage:/` URL to the file it refers to.
+    ///
+    /// Returns `None` for URLs with any other scheme, and for those that would
+    /// escape their directory.
+    pub fn resolve(&self, url: &str) -> Option<Url> {
+        let (scheme, path) = url.split_once(':')?;
+        let base = if scheme.eq_ignore_ascii_case("app") {
+            &self.application
+        } else if scheme.eq_ignore_ascii_case("app-storage") {
+            &self.storage
+        } else {
+            return None;
+        };
+
+        let resolved = base.join(path.trim_start_matches('/')).ok()?;
+        resolved
+            .as_str()
+            .starts_with(base.as_str())
+            .then_some(resolved)
+    }
+}
+
 impl NavigationMethod {
     /// Convert an SWF method enum into a NavigationMethod.
     pub fn from_send_vars_method(s: SendVarsMethod) -> Option<Self> {
@@ -77,6 +257,17 @@ impl NavigationMethod {
             None
         }
     }
+
+    /// Convert a `URLRequest.method` into a NavigationMethod.
+    ///
+    /// Unlike AVM1, `URLRequest` can also make HEAD requests.
+    pub fn from_url_request_method(method: &WStr) -> Option<Self> {
+        if method.eq_ignore_case(WStr::from_units(b"head")) {
+            Some(Self::Head)
+        } else {
+            Self::from_method_str(method)
+        }
+    }
 }
 
 impl fmt::Display for NavigationMethod {
@@ -84,11 +275,26 @@ impl fmt::Display for NavigationMethod {
         let method = match self {
             Self::Get => "GET",
             Self::Post => "POST",
+            Self::Head => "HEAD",
         };
         f.write_str(method)
     }
 }
 
+/// What a fetch should do when the server responds with a redirect.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum RedirectPolicy {
+    /// Follow redirects until a non-redirect response is received.
+    #[default]
+    Follow,
+
+    /// Return the redirect response itself, along with where it points to.
+    Manual,
+
+    /// Treat a redirect as a failed request.
+    Error,
+}
+
 /// A fetch request.
 pub struct Request {
     /// The URL of the request.
@@ -109,6 +315,15 @@ pub struct Request {
     /// to give us a consistent order - hopefully, no servers depend on
     /// the order of headers.
     headers: IndexMap<String, String>,
+
+    /// How redirects in response to this request are handled.
+    redirect_policy: RedirectPolicy,
+
+    /// How long to wait for a connection to the server before giving up.
+    connection_timeout: Option<Duration>,
+
+    /// How long to wait for more data before giving up, once connected.
+    idle_timeout: Option<Duration>,
 }
 
 impl Request {
@@ -119,6 +334,9 @@ impl Request {
             method: NavigationMethod::Get,
             body: None,
             headers: Default::default(),
+            redirect_policy: Default::default(),
+            connection_timeout: None,
+            idle_timeout: None,
         }
     }
 
@@ -129,6 +347,9 @@ impl Request {
             method: NavigationMethod::Post,
             body,
             headers: Default::default(),
+            redirect_policy: Default::default(),
+            connection_timeout: None,
+            idle_timeout: None,
         }
     }
 
@@ -140,6 +361,9 @@ impl Request {
             method,
             body,
             headers: Default::default(),
+            redirect_policy: Default::default(),
+            connection_timeout: None,
+            idle_timeout: None,
         }
     }
 
@@ -169,6 +393,33 @@ impl Request {
     pub fn set_headers(&mut self, headers: IndexMap<String, String>) {
         self.headers = headers;
     }
+
+    /// Retrieve how redirects are handled for this request.
+    pub fn redirect_policy(&self) -> RedirectPolicy {
+        self.redirect_policy
+    }
+
+    pub fn set_redirect_policy(&mut self, redirect_policy: RedirectPolicy) {
+        self.redirect_policy = redirect_policy;
+    }
+
+    /// Retrieve how long this request may take to connect, if limited.
+    pub fn connection_timeout(&self) -> Option<Duration> {
+        self.connection_timeout
+    }
+
+    pub fn set_connection_timeout(&mut self, connection_timeout: Option<Duration>) {
+        self.connection_timeout = connection_timeout;
+    }
+
+    /// Retrieve how long this request may go without receiving data, if limited.
+    pub fn idle_timeout(&self) -> Option<Duration> {
+        self.idle_timeout
+    }
+
+    pub fn set_idle_timeout(&mut self, idle_timeout: Option<Duration>) {
+        self.idle_timeout = idle_timeout;
+    }
 }
 
 /// A response to a successful fetch request.
@@ -184,6 +435,27 @@ pub struct SuccessResponse {
 
     /// The field to indicate if the request has been redirected.
     pub redirected: bool,
+
+    /// Where a redirect response points to, if it wasn't followed because of
+    /// `RedirectPolicy::Manual`.
+    pub redirect_url: Option<String>,
+
+    /// The headers of the response, in the order they were received.
+    ///
+    /// Left empty when there are none, or the backend can't see them.
+    pub headers: Vec<(String, String)>,
+}
+
+/// A part of a response body delivered by [`NavigatorBackend::fetch_stream`].
+#[derive(Debug)]
+pub enum BodyChunk {
+    /// The length of the whole body, when the server says what it is.
+    ///
+    /// This is sent before any data.
+    Length(usize),
+
+    /// The next bytes of the body.
+    Data(Vec<u8>),
 }
 
 /// A response to a non-successful fetch request.
@@ -233,6 +505,29 @@ pub trait NavigatorBackend {
     /// Fetch data and return it some time in the future.
     fn fetch(&self, request: Request) -> OwnedFuture<SuccessResponse, ErrorResponse>;
 
+    /// Fetch data, delivering the response body over `body_sender` as it arrives.
+    ///
+    /// The returned future resolves once the whole body has been delivered,
+    /// and the `body` of its response is left empty.
+    ///
+    /// Backends which can't stream may rely on the default implementation,
+    /// which delivers the entire body as a single chunk.
+    fn fetch_stream(
+        &self,
+        request: Request,
+        body_sender: async_channel::Sender<BodyChunk>,
+    ) -> OwnedFuture<SuccessResponse, ErrorResponse> {
+        let fetch = self.fetch(request);
+        Box::pin(async move {
+            let mut response = fetch.await?;
+            let body = std::mem::take(&mut response.body);
+            // The receiver going away just means nobody wants the data anymore.
+            let _ = body_sender.send(BodyChunk::Length(body.len())).await;
+            let _ = body_sender.send(BodyChunk::Data(body)).await;
+            Ok(response)
+        })
+    }
+
     /// Take a URL string and resolve it to the actual URL from which a file
     /// can be fetched. This includes handling of relative links and pre-processing.
     ///
@@ -240,6 +535,15 @@ pub trait NavigatorBackend {
     /// fetch may return a different URL, e.g. considering redirections.
     fn resolve_url(&self, url: &str) -> Result<Url, ParseError>;
 
+    /// The directories `app:/` and `app-storage:/` URLs refer to, if this
+    /// backend is running an AIR application.
+    ///
+    /// Implementations of `resolve_url` are expected to map those schemes through
+    /// [`AirDirectories::resolve`] before anything else.
+    fn air_directories(&self) -> Option<&AirDirectories> {
+        None
+    }
+
     /// Arrange for a future to be run at some point in the... well, future.
     ///
     /// This function must be called to ensure a future is actually computed.
@@ -265,15 +569,53 @@ pub trait NavigatorBackend {
     /// Use [SocketAction::Data] to send data to AVM side.
     ///
     /// When the Sender of the Receiver is dropped then this task should end.
+    ///
+    /// If `require_policy_file` is set, the host must serve a socket policy file allowing
+    /// the connection, as in Flash; otherwise the connection fails.
+    #[allow(clippy::too_many_arguments)]
     fn connect_socket(
         &mut self,
         host: String,
         port: u16,
+        require_policy_file: bool,
         timeout: Duration,
         handle: SocketHandle,
         receiver: Receiver<Vec<u8>>,
         sender: Sender<SocketAction>,
     );
+
+    /// Fetch the socket policy file served at `host:port`, for `Security.loadPolicyFile`.
+    ///
+    /// The policy file is requested by sending `<policy-file-request/>` followed by a null
+    /// byte, and is everything received up to the next null byte.
+    fn fetch_socket_policy(
+        &self,
+        host: String,
+        port: u16,
+        _timeout: Duration,
+    ) -> OwnedFuture<String, Error> {
+        async_return(Err(Error::FetchError(format!(
+            "Socket policy files can't be fetched from {host}:{port}"
+        ))))
+    }
+
+    /// Handle a request to join a local NetGroup
+    ///
+    /// Join `address` as a multicast group, then use [NetGroupAction::Connect] to notify AVM
+    /// whether that worked.
+    ///
+    /// Every datagram received from `address` should be sent to AVM as [NetGroupAction::Packet],
+    /// and everything received from `receiver` should be sent to `address`.
+    ///
+    /// The group has been closed once the sender of `receiver` is dropped. At that point, leave
+    /// the multicast group and stop.
+    fn join_net_group(
+        &mut self,
+        address: SocketAddr,
+        handle: NetGroupHandle,
+        receiver: Receiver<Vec<u8>>,
+        sender: Sender<NetGroupAction>,
+    );
 }
 
 #[cfg(not(target_family = "wasm"))]
@@ -354,6 +696,9 @@ pub struct NullNavigatorBackend {
 
     /// The base path for all relative fetches.
     relative_base_path: PathBuf,
+
+    /// Where `app:/` and `app-storage:/` URLs point, if anywhere.
+    air_directories: Option<AirDirectories>,
 }
 
 impl NullNavigatorBackend {
@@ -362,6 +707,7 @@ impl NullNavigatorBackend {
         Self {
             spawner: executor.spawner(),
             relative_base_path: PathBuf::new(),
+            air_directories: None,
         }
     }
 
@@ -369,8 +715,14 @@ impl NullNavigatorBackend {
         Ok(Self {
             spawner: executor.spawner(),
             relative_base_path: path.canonicalize()?,
+            air_directories: None,
         })
     }
+
+    pub fn with_air_directories(mut self, air_directories: AirDirectories) -> Self {
+        self.air_directories = Some(air_directories);
+        self
+    }
 }
 
 impl Default for NullNavigatorBackend {
@@ -396,6 +748,10 @@ impl NavigatorBackend for NullNavigatorBackend {
         resolve_url_with_relative_base_path(self, self.relative_base_path.clone(), url)
     }
 
+    fn air_directories(&self) -> Option<&AirDirectories> {
+        self.air_directories.as_ref()
+    }
+
     fn spawn_future(&mut self, future: OwnedFuture<(), Error>) {
         self.spawner.spawn_local(future);
     }
@@ -408,6 +764,7 @@ impl NavigatorBackend for NullNavigatorBackend {
         &mut self,
         _host: String,
         _port: u16,
+        _require_policy_file: bool,
         _timeout: Duration,
         handle: SocketHandle,
         _receiver: Receiver<Vec<u8>>,
@@ -417,6 +774,18 @@ impl NavigatorBackend for NullNavigatorBackend {
             .send(SocketAction::Connect(handle, ConnectionState::Failed))
             .expect("working channel send");
     }
+
+    fn join_net_group(
+        &mut self,
+        _address: SocketAddr,
+        handle: NetGroupHandle,
+        _receiver: Receiver<Vec<u8>>,
+        sender: Sender<NetGroupAction>,
+    ) {
+        sender
+            .send(NetGroupAction::Connect(handle, false))
+            .expect("working channel send");
+    }
 }
 
 // The following functions are helper functions used in different
@@ -462,22 +831,22 @@ pub fn create_specific_fetch_error<ErrorType: Display>(
 // Therefore, we need to use cfg to make Ruffle compile for all targets.
 
 #[cfg(any(unix, windows, target_os = "redox"))]
-fn url_from_file_path(path: &Path) -> Result<Url, ()> {
+pub(crate) fn url_from_file_path(path: &Path) -> Result<Url, ()> {
     Url::from_file_path(path)
 }
 
 #[cfg(not(any(unix, windows, target_os = "redox")))]
-fn url_from_file_path(_path: &Path) -> Result<Url, ()> {
+pub(crate) fn url_from_file_path(_path: &Path) -> Result<Url, ()> {
     Err(())
 }
 
 #[cfg(any(unix, windows, target_os = "redox"))]
-fn url_to_file_path(url: &Url) -> Result<PathBuf, ()> {
+pub(crate) fn url_to_file_path(url: &Url) -> Result<PathBuf, ()> {
     Url::to_file_path(url)
 }
 
 #[cfg(not(any(unix, windows, target_os = "redox")))]
-fn url_to_file_path(_path: &Url) -> Result<PathBuf, ()> {
+pub(crate) fn url_to_file_path(_path: &Url) -> Result<PathBuf, ()> {
     Err(())
 }
 
@@ -487,7 +856,8 @@ fn url_to_file_path(_path: &Url) -> Result<PathBuf, ()> {
 /// This is the resolve implementation for the TestNavigatorBackend and the
 /// NullNavigatorBackend.
 ///
-/// It resolves the given URL with the given relative base path.
+/// It resolves the given URL with the given relative base path, after mapping
+/// any AIR application URL to its directory.
 pub fn resolve_url_with_relative_base_path<NavigatorType: NavigatorBackend>(
     navigator: &NavigatorType,
     base_path: PathBuf,
@@ -505,6 +875,10 @@ pub fn resolve_url_with_relative_base_path<NavigatorType: NavigatorBackend>(
         }
     }
 
+    if let Some(url) = navigator.air_directories().and_then(|d| d.resolve(url)) {
+        return Ok(navigator.pre_process_url(url));
+    }
+
     if let Ok(mut base_url) = url_from_file_path(base_path.as_path()) {
         // Make sure we have a trailing slash, so that joining a request url like 'data.txt'
         // gets appended, rather than replacing the last component.
@@ -534,6 +908,9 @@ pub fn fetch_path<NavigatorType: NavigatorBackend>(
         Ok(url) => url,
         Err(e) => return async_return(create_fetch_error(url, e)),
     };
+    if url.scheme() == "data" {
+        return async_return(fetch_data_url(&url));
+    }
     let path = if url.scheme() == "file" {
         // Flash supports query parameters with local urls.
         // SwfMovie takes care of exposing those to ActionScript -
@@ -569,6 +946,167 @@ pub fn fetch_path<NavigatorType: NavigatorBackend>(
             body,
             status: 0,
             redirected: false,
+            redirect_url: None,
+            headers: vec![],
         })
     })
 }
+
+/// Base64 as it appears in `data:` URLs, where padding is optional.
+const DATA_URL_BASE64: GeneralPurpose = GeneralPurpose::new(
+    &alphabet::STANDARD,
+    GeneralPurposeConfig::new().with_decode_padding_mode(DecodePaddingMode::Indifferent),
+);
+
+/// This is the fetch implementation for `data:` URLs, which every
+/// NavigatorBackend that doesn't get them for free should use.
+///
+/// The data is percent-encoded, and also base64-encoded if the media type is
+/// followed by `;base64`. The fragment isn't part of the data.
+pub fn fetch_data_url(url: &Url) -> Result<SuccessResponse, ErrorResponse> {
+    let contents = &url[Position::BeforePath..Position::AfterQuery];
+    let Some((media_type, data)) = contents.split_once(',') else {
+        return create_specific_fetch_error("Invalid data URL", url.as_str(), "missing comma");
+    };
+
+    let data: Vec<u8> = percent_encoding::percent_decode_str(data).collect();
+    let is_base64 = media_type.rsplit_once(';').map_or(false, |(_, parameter)| {
+        parameter.trim().eq_ignore_ascii_case("base64")
+    });
+    let body = if is_base64 {
+        let data: Vec<u8> = data
+            .into_iter()
+            .filter(|byte| !byte.is_ascii_whitespace())
+            .collect();
+        match DATA_URL_BASE64.decode(data) {
+            Ok(body) => body,
+            Err(e) => return create_specific_fetch_error("Invalid data URL", url.as_str(), e),
+        }
+    } else {
+        data
+    };
+
+    Ok(SuccessResponse {
+        url: url.to_string(),
+        body,
+        status: 200,
+        redirected: false,
+        redirect_url: None,
+        headers: vec![],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn host_overrides() {
+        let overrides = HostOverrides::new(vec![
+            "game.example.com=127.0.0.1:8080".parse().unwrap(),
+            "Chat.example.com:9339=localhost".parse().unwrap(),
+        ]);
+        assert_eq!(
+            overrides.get("game.example.com", 80),
+            Some(("127.0.0.1", 8080))
+        );
+        assert_eq!(
+            overrides.get("chat.example.com", 9339),
+            Some(("localhost", 9339))
+        );
+        assert_eq!(overrides.get("chat.example.com", 80), None);
+        assert_eq!(
+            overrides.resolve("other.example.com", 443),
+            ("other.example.com".to_string(), 443)
+        );
+        assert!("game.example.com".parse::<HostOverride>().is_err());
+        assert!("game.example.com=:80".parse::<HostOverride>().is_err());
+    }
+
+    #[test]
+    fn host_overrides_ipv6() {
+        let overrides = HostOverrides::new(vec![
+            "game.example.com=[::1]:8080".parse().unwrap(),
+            "[2001:DB8::1]:843=::1".parse().unwrap(),
+            "fe80::2=127.0.0.1".parse().unwrap(),
+        ]);
+        assert_eq!(overrides.get("game.example.com", 80), Some(("::1", 8080)));
+        assert_eq!(overrides.get("2001:db8::1", 843), Some(("::1", 843)));
+        assert_eq!(overrides.get("[2001:db8::1]", 843), Some(("::1", 843)));
+        assert_eq!(overrides.get("2001:db8::1", 80), None);
+        assert_eq!(overrides.get("[fe80::2]", 80), Some(("127.0.0.1", 80)));
+        assert!("game.example.com=[::1".parse::<HostOverride>().is_err());
+        assert!("game.example.com=[::1]8080"
+            .parse::<HostOverride>()
+            .is_err());
+        assert!("game.example.com=[]:8080".parse::<HostOverride>().is_err());
+    }
+
+    #[test]
+    fn air_directories() {
+        let directories = AirDirectories::new(
+            Url::parse("file:///apps/tool/").unwrap(),
+            Url::parse("file:///data/tool/Local%20Store/").unwrap(),
+        );
+        let resolve = |url| directories.resolve(url).map(String::from);
+        assert_eq!(
+            resolve("app:/assets/items.swf").as_deref(),
+            Some("file:///apps/tool/assets/items.swf")
+        );
+        assert_eq!(
+            resolve("APP:///config.xml?v=2").as_deref(),
+            Some("file:///apps/tool/config.xml?v=2")
+        );
+        assert_eq!(
+            resolve("app-storage:/prefs.json").as_deref(),
+            Some("file:///data/tool/Local%20Store/prefs.json")
+        );
+        assert_eq!(resolve("app:/../../etc/passwd"), None);
+        assert_eq!(resolve("http://example.com/app:/x"), None);
+        assert_eq!(resolve("assets/items.swf"), None);
+
+        let navigator = NullNavigatorBackend::new().with_air_directories(directories);
+        assert_eq!(
+            navigator
+                .resolve_url("app:/assets/items.swf")
+                .map(String::from),
+            Ok("file:///apps/tool/assets/items.swf".to_string())
+        );
+    }
+
+    #[test]
+    fn data_urls() {
+        let fetch = |url| {
+            fetch_data_url(&Url::parse(url).unwrap())
+                .map(|response| response.body)
+                .map_err(|response| response.error.to_string())
+        };
+        assert_eq!(
+            fetch("data:,Hello%2C%20World%21"),
+            Ok(b"Hello, World!".to_vec())
+        );
+        assert_eq!(
+            fetch("data:text/plain;charset=utf-8,a?b=c#fragment"),
+            Ok(b"a?b=c".to_vec())
+        );
+        assert_eq!(
+            fetch("data:text/plain;base64,SGVsbG8sIFdvcmxkIQ=="),
+            Ok(b"Hello, World!".to_vec())
+        );
+        assert_eq!(
+            fetch("data:application/octet-stream;BASE64,AAEC%20/w"),
+            Ok(vec![0, 1, 2, 255])
+        );
+        assert_eq!(fetch("data:text/base64,aGk="), Ok(b"aGk=".to_vec()));
+        assert!(fetch("data:text/plain;base64,!!!").is_err());
+        assert!(fetch("data:text/plain").is_err());
+
+        let navigator = NullNavigatorBackend::new();
+        let response =
+            futures::executor::block_on(navigator.fetch(Request::get("data:,hi".to_string())));
+        assert_eq!(
+            response.ok().map(|response| response.body),
+            Some(b"hi".to_vec())
+        );
+    }
+}
@@ -0,0 +1,323 @@
+//! Archives named after the hash of their contents, referenced through `archive://<hash>/path` URLs.
+
+use super::{
+    async_return, create_specific_fetch_error, AirDirectories, BodyChunk, ErrorResponse,
+    NavigationMethod, NavigatorBackend, OwnedFuture, Request, SuccessResponse,
+};
+use crate::loader::Error;
+use crate::net_group::{NetGroupAction, NetGroupHandle};
+use crate::socket::{SocketAction, SocketHandle};
+use async_channel::Receiver;
+use indexmap::IndexMap;
+use std::net::SocketAddr;
+use std::path::{Component, Path, PathBuf};
+use std::str::FromStr;
+use std::sync::mpsc::Sender;
+use std::time::Duration;
+use url::{ParseError, Url};
+
+/// The scheme of URLs that point into an archive.
+pub const ARCHIVE_SCHEME: &str = "archive";
+
+/// Where the contents of archives are looked up.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ArchiveStore {
+    /// A local directory holding one sub-directory per archive, named after its hash.
+    Directory(PathBuf),
+
+    /// An IPFS HTTP gateway, from which archives are fetched as `<gateway>/ipfs/<hash>/path`.
+    ///
+    /// What the gateway sends isn't checked against the hash, so it has to be trusted
+    /// to serve the archive that was asked for.
+    Gateway(Url),
+}
+
+impl FromStr for ArchiveStore {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err("Expected a directory or a gateway URL".to_string());
+        }
+        match Url::parse(s) {
+            Ok(url) if matches!(url.scheme(), "http" | "https") => Ok(Self::Gateway(url)),
+            _ => Ok(Self::Directory(PathBuf::from(s))),
+        }
+    }
+}
+
+/// Where the file an archive URL refers to actually lives.
+enum ArchiveLocation {
+    File(PathBuf),
+    Remote(Url),
+}
+
+impl ArchiveStore {
+    fn locate(&self, url: &Url) -> Result<ArchiveLocation, &'static str> {
+        let hash = url.host_str().unwrap_or_default();
+        if hash.is_empty() || !hash.bytes().all(|b| b.is_ascii_alphanumeric()) {
+            return Err("invalid archive hash");
+        }
+
+        let mut segments = vec![];
+        for segment in url.path_segments().into_iter().flatten() {
+            let segment = percent_encoding::percent_decode_str(segment)
+                .decode_utf8()
+                .map_err(|_| "invalid path")?;
+            match &*segment {
+                "" => {}
+                "." | ".." => return Err("path leaves the archive"),
+                segment if !is_plain_name(segment) => return Err("path leaves the archive"),
+                segment => segments.push(segment.to_string()),
+            }
+        }
+
+        match self {
+            Self::Directory(root) => {
+                let mut path = root.join(hash);
+                path.extend(segments);
+                Ok(ArchiveLocation::File(path))
+            }
+            Self::Gateway(gateway) => {
+                let mut location = gateway.clone();
+                location.set_query(None);
+                location
+                    .path_segments_mut()
+                    .map_err(|_| "invalid gateway URL")?
+                    .pop_if_empty()
+                    .push("ipfs")
+                    .push(hash)
+                    .extend(&segments);
+                Ok(ArchiveLocation::Remote(location))
+            }
+        }
+    }
+}
+
+/// Whether `segment` names a single file or directory, which can't replace or climb
+/// out of the path it's joined to on any platform.
+fn is_plain_name(segment: &str) -> bool {
+    let mut components = Path::new(segment).components();
+    !segment.contains(['/', '\\', ':'])
+        && matches!(components.next(), Some(Component::Normal(_)))
+        && components.next().is_none()
+}
+
+/// A navigator that serves `archive://` URLs from an [`ArchiveStore`],
+/// and leaves everything else to another navigator.
+///
+/// Movies keep seeing the archive URL they asked for, so references into an
+/// archive stay the same no matter where the store lives.
+pub struct ArchiveNavigatorBackend<N: NavigatorBackend> {
+    inner: N,
+    store: ArchiveStore,
+}
+
+impl<N: NavigatorBackend> ArchiveNavigatorBackend<N> {
+    pub fn new(inner: N, store: ArchiveStore) -> Self {
+        Self { inner, store }
+    }
+
+    pub fn inner(&self) -> &N {
+        &self.inner
+    }
+
+    /// The archive URL a request is for, if it's for one at all.
+    fn archive_url(&self, request: &Request) -> Option<Url> {
+        self.inner
+            .resolve_url(request.url())
+            .ok()
+            .filter(|url| url.scheme() == ARCHIVE_SCHEME)
+    }
+
+    /// Fetch a file from the store, streaming it to `body_sender` if given.
+    fn fetch_archive(
+        &self,
+        request: &Request,
+        url: Url,
+        body_sender: Option<async_channel::Sender<BodyChunk>>,
+    ) -> OwnedFuture<SuccessResponse, ErrorResponse> {
+        if !matches!(request.method(), NavigationMethod::Get) {
+            return async_return(create_specific_fetch_error(
+                "Archives can only be read from",
+                url.as_str(),
+                "",
+            ));
+        }
+
+        let location = match self.store.locate(&url) {
+            Ok(location) => location,
+            Err(e) => {
+                return async_return(create_specific_fetch_error("Invalid URL", url.as_str(), e))
+            }
+        };
+
+        match location {
+            ArchiveLocation::File(path) => Box::pin(async move {
+                let body = match std::fs::read(path) {
+                    Ok(body) => body,
+                    Err(e) => {
+                        return create_specific_fetch_error("Can't open file", url.as_str(), e)
+                    }
+                };
+                let body = match body_sender {
+                    Some(body_sender) => {
+                        let _ = body_sender.send(BodyChunk::Length(body.len())).await;
+                        let _ = body_sender.send(BodyChunk::Data(body)).await;
+                        vec![]
+                    }
+                    None => body,
+                };
+                Ok(SuccessResponse {
+                    url: url.to_string(),
+                    body,
+                    status: 0,
+                    redirected: false,
+                    redirect_url: None,
+                    headers: vec![],
+                })
+            }),
+            ArchiveLocation::Remote(location) => {
+                let mut remote = Request::get(location.to_string());
+                remote.set_connection_timeout(request.connection_timeout());
+                remote.set_idle_timeout(request.idle_timeout());
+                let fetch = match body_sender {
+                    Some(body_sender) => self.inner.fetch_stream(remote, body_sender),
+                    None => self.inner.fetch(remote),
+                };
+                Box::pin(async move {
+                    match fetch.await {
+                        Ok(response) => Ok(SuccessResponse {
+                            url: url.to_string(),
+                            redirected: false,
+                            redirect_url: None,
+                            ..response
+                        }),
+                        Err(response) => Err(ErrorResponse {
+                            url: url.to_string(),
+                            ..response
+                        }),
+                    }
+                })
+            }
+        }
+    }
+}
+
+impl<N: NavigatorBackend> NavigatorBackend for ArchiveNavigatorBackend<N> {
+    fn navigate_to_url(
+        &self,
+        url: &str,
+        target: &str,
+        vars_method: Option<(NavigationMethod, IndexMap<String, String>)>,
+    ) {
+        self.inner.navigate_to_url(url, target, vars_method)
+    }
+
+    fn fetch(&self, request: Request) -> OwnedFuture<SuccessResponse, ErrorResponse> {
+        match self.archive_url(&request) {
+            Some(url) => self.fetch_archive(&request, url, None),
+            None => self.inner.fetch(request),
+        }
+    }
+
+    fn fetch_stream(
+        &self,
+        request: Request,
+        body_sender: async_channel::Sender<BodyChunk>,
+    ) -> OwnedFuture<SuccessResponse, ErrorResponse> {
+        match self.archive_url(&request) {
+            Some(url) => self.fetch_archive(&request, url, Some(body_sender)),
+            None => self.inner.fetch_stream(request, body_sender),
+        }
+    }
+
+    fn resolve_url(&self, url: &str) -> Result<Url, ParseError> {
+        self.inner.resolve_url(url)
+    }
+
+    fn air_directories(&self) -> Option<&AirDirectories> {
+        self.inner.air_directories()
+    }
+
+    fn spawn_future(&mut self, future: OwnedFuture<(), Error>) {
+        self.inner.spawn_future(future)
+    }
+
+    fn pre_process_url(&self, url: Url) -> Url {
+        self.inner.pre_process_url(url)
+    }
+
+    fn connect_socket(
+        &mut self,
+        host: String,
+        port: u16,
+        require_policy_file: bool,
+        timeout: Duration,
+        handle: SocketHandle,
+        receiver: Receiver<Vec<u8>>,
+        sender: Sender<SocketAction>,
+    ) {
+        self.inner.connect_socket(
+            host,
+            port,
+            require_policy_file,
+            timeout,
+            handle,
+            receiver,
+            sender,
+        )
+    }
+
+    fn fetch_socket_policy(
+        &self,
+        host: String,
+        port: u16,
+        timeout: Duration,
+    ) -> OwnedFuture<String, Error> {
+        self.inner.fetch_socket_policy(host, port, timeout)
+    }
+
+    fn join_net_group(
+        &mut self,
+        address: SocketAddr,
+        handle: NetGroupHandle,
+        receiver: Receiver<Vec<u8>>,
+        sender: Sender<NetGroupAction>,
+    ) {
+        self.inner.join_net_group(address, handle, receiver, sender)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn locate(store: &ArchiveStore, url: &str) -> Result<String, &'static str> {
+        match store.locate(&Url::parse(url).unwrap())? {
+            ArchiveLocation::File(path) => Ok(path.to_string_lossy().replace('\\', "/")),
+            ArchiveLocation::Remote(url) => Ok(url.to_string()),
+        }
+    }
+
+    #[test]
+    fn archive_urls() {
+        let directory: ArchiveStore = "/srv/archives".parse().unwrap();
+        assert_eq!(
+            locate(&directory, "archive://QmHash/items/hat%20one.swf?v=1"),
+            Ok("/srv/archives/QmHash/items/hat one.swf".to_string())
+        );
+        assert!(locate(&directory, "archive://Qm.Hash/item.swf").is_err());
+        assert!(locate(&directory, "archive://QmHash/items%2F..%2F..%2Fsecret").is_err());
+        assert!(locate(&directory, "archive://QmHash/items%5C..%5Csecret").is_err());
+        assert!(locate(&directory, "archive://QmHash/C:secret").is_err());
+        assert!(locate(&directory, "archive://QmHash/C%3A%5Csecret").is_err());
+        assert!(locate(&directory, "archive://QmHash/%5C%5Cserver%5Cshare").is_err());
+
+        let gateway: ArchiveStore = "https://ipfs.example.com/".parse().unwrap();
+        assert_eq!(
+            locate(&gateway, "archive://QmHash/items/hat.swf"),
+            Ok("https://ipfs.example.com/ipfs/QmHash/items/hat.swf".to_string())
+        );
+    }
+}
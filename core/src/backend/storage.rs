@@ -37,3 +37,49 @@ impl StorageBackend for MemoryStorageBackend {
         self.map.remove(name);
     }
 }
+
+/// Storage for AIR's `EncryptedLocalStore`.
+///
+/// Unlike shared objects, items are expected to be kept secret from other users and
+/// applications, so implementations should use the platform's secure storage where they can.
+pub trait SecureStorageBackend {
+    fn get_item(&self, name: &str) -> Option<Vec<u8>>;
+
+    fn set_item(&mut self, name: &str, value: &[u8]) -> bool;
+
+    fn remove_item(&mut self, name: &str);
+
+    /// Removes every item.
+    fn reset(&mut self);
+}
+
+/// Keeps items for the lifetime of the player only.
+#[derive(Default)]
+pub struct MemorySecureStorageBackend {
+    map: HashMap<String, Vec<u8>>,
+}
+
+impl MemorySecureStorageBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl SecureStorageBackend for MemorySecureStorageBackend {
+    fn get_item(&self, name: &str) -> Option<Vec<u8>> {
+        self.map.get(name).cloned()
+    }
+
+    fn set_item(&mut self, name: &str, value: &[u8]) -> bool {
+        self.map.insert(name.into(), value.to_vec());
+        true
+    }
+
+    fn remove_item(&mut self, name: &str) {
+        self.map.remove(name);
+    }
+
+    fn reset(&mut self) {
+        self.map.clear();
+    }
+}
@@ -4,6 +4,7 @@ use fluent_templates::loader::langid;
 pub use fluent_templates::LanguageIdentifier;
 use std::borrow::Cow;
 use std::collections::HashSet;
+use std::path::PathBuf;
 use url::Url;
 
 pub type FullscreenError = Cow<'static, str>;
@@ -28,6 +29,32 @@ pub trait UiBackend: Downcast {
     /// Sets the clipboard to the given content.
     fn set_clipboard_content(&mut self, content: String);
 
+    /// Lists the displays attached to the host, with the main display first.
+    ///
+    /// An empty list means the host can't tell, in which case content sees a single screen the
+    /// size of the player.
+    fn screens(&self) -> Vec<ScreenInfo>;
+
+    /// The outer bounds of the window the player is shown in, if it has a window of its own.
+    fn window_bounds(&self) -> Option<DesktopRect>;
+
+    /// Moves and resizes the player's window, as `NativeWindow.bounds` does.
+    fn set_window_bounds(&mut self, bounds: DesktopRect);
+
+    /// Hands a drag of the given files over to the host, when content drags them out of the
+    /// player with `NativeDragManager`.
+    ///
+    /// Returns `false` if the host can't take over the drag, in which case it continues inside
+    /// the player.
+    fn start_file_drag(&mut self, files: &[PathBuf]) -> bool;
+
+    /// Shows the given menu in the player's window, as `NativeWindow.menu` does, replacing any
+    /// earlier one. `None` removes the menu.
+    ///
+    /// When the user picks an item, the host should call `Player::run_native_menu_callback` with
+    /// its id.
+    fn set_native_menu(&mut self, menu: Option<Vec<NativeMenuItem>>);
+
     fn set_fullscreen(&mut self, is_full: bool) -> Result<(), FullscreenError>;
 
     /// Displays a message about an error during root movie download.
@@ -76,6 +103,44 @@ pub enum MouseCursor {
     Grab,
 }
 
+/// A rectangle on the host's desktop, in pixels.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DesktopRect {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// A display attached to the host.
+/// Communicated from the UI backend to `flash.display.Screen` via `UiBackend::screens`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ScreenInfo {
+    /// The whole area of the screen.
+    pub bounds: DesktopRect,
+
+    /// The part of the screen not covered by taskbars, docks and menu bars.
+    pub visible_bounds: DesktopRect,
+
+    /// Bits per pixel.
+    pub color_depth: u32,
+}
+
+/// An item of a menu set with `NativeWindow.menu`.
+/// Communicated from the core to the UI backend via `UiBackend::set_native_menu`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NativeMenuItem {
+    /// Identifies the item to `Player::run_native_menu_callback`.
+    pub id: usize,
+    pub label: String,
+    pub enabled: bool,
+    pub checked: bool,
+    pub is_separator: bool,
+
+    /// The items of the menu this item opens, if any.
+    pub submenu: Option<Vec<NativeMenuItem>>,
+}
+
 pub struct InputManager {
     keys_down: HashSet<KeyCode>,
     last_key: KeyCode,
@@ -178,6 +243,22 @@ impl UiBackend for NullUiBackend {
 
     fn set_clipboard_content(&mut self, _content: String) {}
 
+    fn screens(&self) -> Vec<ScreenInfo> {
+        vec![]
+    }
+
+    fn window_bounds(&self) -> Option<DesktopRect> {
+        None
+    }
+
+    fn set_window_bounds(&mut self, _bounds: DesktopRect) {}
+
+    fn start_file_drag(&mut self, _files: &[PathBuf]) -> bool {
+        false
+    }
+
+    fn set_native_menu(&mut self, _menu: Option<Vec<NativeMenuItem>>) {}
+
     fn set_fullscreen(&mut self, _is_full: bool) -> Result<(), FullscreenError> {
         Ok(())
     }
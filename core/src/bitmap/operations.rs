@@ -1,7 +1,7 @@
 use crate::avm2::bytearray::{ByteArrayError, ByteArrayStorage};
 use crate::avm2::error::range_error;
 use crate::avm2::vector::VectorStorage;
-use crate::avm2::{Activation, Error, Value as Avm2Value};
+use crate::avm2::{Activation, Error};
 use crate::bitmap::bitmap_data::{
     BitmapData, BitmapDataDrawError, BitmapDataWrapper, ChannelOptions, Color, IBitmapDrawable,
     LehmerRng, ThresholdOperation,
@@ -1546,13 +1546,7 @@ pub fn draw<'gc>(
     }
 }
 
-pub fn get_vector(
-    target: BitmapDataWrapper,
-    x: i32,
-    y: i32,
-    width: i32,
-    height: i32,
-) -> Vec<Avm2Value> {
+pub fn get_vector(target: BitmapDataWrapper, x: i32, y: i32, width: i32, height: i32) -> Vec<u32> {
     let mut region = PixelRegion::for_region_i32(x, y, width, height);
     region.clamp(target.width(), target.height());
 
@@ -1563,8 +1557,7 @@ pub fn get_vector(
     for y in region.y_min..region.y_max {
         for x in region.x_min..region.x_max {
             let color = read.get_pixel32_raw(x, y);
-            let color = u32::from(color.to_un_multiplied_alpha());
-            result.push(color.into());
+            result.push(u32::from(color.to_un_multiplied_alpha()));
         }
     }
 
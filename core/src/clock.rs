@@ -0,0 +1,211 @@
+//! The time that movies see.
+//!
+//! `getTimer`, timers and the frame scheduler all run on the same clock, which the player moves
+//! to the time each frame was due to run at. In between, it follows the host's clock, but never
+//! past the time the next frame is due. Movies that compare `getTimer` against the number of
+//! frames that ran (often to detect speed hacks) then see the two agree, even when frames had
+//! to catch up or the host skipped ahead.
+//!
+//! Movies that wait for time to pass in a busy loop still see it creep past the next frame's
+//! due time, as they would otherwise wait forever. The frames after them then see that later
+//! time, since the clock never goes back.
+
+use instant::Instant;
+
+/// How far each reading past the time the next frame is due creeps forward, in milliseconds.
+const CREEP: f64 = 0.01;
+
+/// A monotonic clock counting milliseconds since the movie started, with sub-millisecond precision.
+pub struct PlayerClock {
+    /// The time that the player has been ticked to.
+    tick_time: f64,
+
+    /// The time when `base_instant` was taken.
+    base_time: f64,
+
+    /// Time passes at the rate of the host's clock from here until the clock is next set.
+    base_instant: Instant,
+
+    /// The time that readings stop at until the clock is next set, if any.
+    limit: Option<f64>,
+
+    /// The latest time that was read, which later readings never go below.
+    last_reading: f64,
+}
+
+impl PlayerClock {
+    pub fn new() -> Self {
+        Self {
+            tick_time: 0.0,
+            base_time: 0.0,
+            base_instant: Instant::now(),
+            limit: None,
+            last_reading: 0.0,
+        }
+    }
+
+    /// Moves the clock forward by the `dt` milliseconds that a tick covers.
+    pub fn tick(&mut self, dt: f64) {
+        self.tick_time += dt.max(0.0);
+        self.set(self.tick_time, None);
+    }
+
+    /// The time that the player has been ticked to.
+    pub fn tick_time(&self) -> f64 {
+        self.tick_time
+    }
+
+    /// Sets the clock to `due`, when a frame was due to run, which is at most the current tick.
+    ///
+    /// Until the clock is set again, it follows the host's clock up to `next_due`, when the next
+    /// frame is due. That frame then sees its own due time, even when it runs late.
+    pub fn set_due(&mut self, due: f64, next_due: f64) {
+        let due = due.min(self.tick_time);
+        self.set(due, Some(next_due.max(due)));
+    }
+
+    fn set(&mut self, time: f64, limit: Option<f64>) {
+        self.base_time = time;
+        self.base_instant = Instant::now();
+        self.limit = limit;
+    }
+
+    /// The current time in milliseconds.
+    pub fn now(&mut self) -> f64 {
+        let elapsed = self.base_instant.elapsed().as_secs_f64() * 1000.0;
+        let mut time = self.base_time + elapsed;
+        if let Some(limit) = self.limit.filter(|limit| time > *limit) {
+            time = (self.last_reading + CREEP).max(limit).min(time);
+        }
+        self.last_reading = self.last_reading.max(time);
+        self.last_reading
+    }
+
+    /// The current time in whole milliseconds, as returned by `getTimer`.
+    pub fn get_timer(&mut self) -> u32 {
+        self.now() as u64 as u32
+    }
+}
+
+impl Default for PlayerClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PlayerClock;
+    use crate::avm1::{Activation, ActivationIdentifier, TObject};
+    use crate::limits::ExecutionLimit;
+    use crate::player::PlayerBuilder;
+    use crate::tag_utils::SwfMovie;
+    use swf::avm1::types::{Action, Push, Value};
+    use swf::avm1::write::Writer;
+    use swf::{Fixed8, Header, SwfStr, Tag};
+
+    #[test]
+    fn readings_stop_at_the_next_due_time() {
+        let mut clock = PlayerClock::new();
+        clock.tick(250.0);
+        clock.set_due(100.0, 100.0);
+        assert_eq!(clock.now(), 100.0);
+
+        // Busy loops see the time creep forward.
+        std::thread::sleep(std::time::Duration::from_millis(1));
+        assert!(clock.now() > 100.0);
+
+        clock.set_due(200.0, 200.0);
+        assert_eq!(clock.now(), 200.0);
+
+        // The clock never goes back.
+        clock.set_due(50.0, 50.0);
+        assert_eq!(clock.now(), 200.0);
+        assert_eq!(clock.get_timer(), 200);
+    }
+
+    /// Appends `getTimer()` and a comma to the variable `t`.
+    fn record_time(first_frame: bool) -> Vec<u8> {
+        let name = Value::Str(SwfStr::from_utf8_str("t"));
+        let mut actions = vec![];
+        if first_frame {
+            actions.push(Action::Push(Push {
+                values: vec![name.clone(), Value::Str(SwfStr::from_utf8_str(""))],
+            }));
+            actions.push(Action::SetVariable);
+        }
+        actions.extend([
+            Action::Push(Push {
+                values: vec![name.clone(), name],
+            }),
+            Action::GetVariable,
+            Action::GetTime,
+            Action::Add2,
+            Action::Push(Push {
+                values: vec![Value::Str(SwfStr::from_utf8_str(","))],
+            }),
+            Action::Add2,
+            Action::SetVariable,
+        ]);
+
+        let mut data = vec![];
+        let mut writer = Writer::new(&mut data, 8);
+        for action in &actions {
+            writer.write_action(action).unwrap();
+        }
+        data.push(0);
+        data
+    }
+
+    #[test]
+    fn catch_up_frames_see_their_due_time() {
+        let scripts = [record_time(true), record_time(false), record_time(false)];
+        let tags: Vec<_> = scripts
+            .iter()
+            .flat_map(|script| [Tag::DoAction(script), Tag::ShowFrame])
+            .collect();
+        let header = Header {
+            num_frames: 3,
+            frame_rate: Fixed8::from_f64(10.0),
+            ..Header::default_with_swf_version(8)
+        };
+        let mut data = vec![];
+        swf::write_swf(&header, &tags, &mut data).unwrap();
+        let movie = SwfMovie::from_data(&data, "file:///test.swf".into(), None).unwrap();
+
+        let player = PlayerBuilder::new()
+            .with_movie(movie)
+            .with_autoplay(true)
+            .build();
+        let mut player = player.lock().unwrap();
+        player.preload(&mut ExecutionLimit::none());
+
+        // Two frames are due in the first 250ms, the next one 50ms after the second tick.
+        player.tick(250.0);
+        player.tick(100.0);
+
+        let times = player.mutate_with_update_context(|context| {
+            let root = context.stage.root_clip().unwrap();
+            let mut activation = Activation::from_nothing(
+                context.reborrow(),
+                ActivationIdentifier::root("[Test]"),
+                root,
+            );
+            let root = root.object().coerce_to_object(&mut activation);
+            let times = root.get("t", &mut activation).unwrap();
+            times.coerce_to_string(&mut activation).unwrap().to_string()
+        });
+        let times: Vec<u32> = times
+            .split_terminator(',')
+            .map(|time| time.parse().unwrap())
+            .collect();
+
+        assert_eq!(times.len(), 3);
+        for (time, due) in times.into_iter().zip([100, 200, 300]) {
+            assert!(
+                (due..due + 100).contains(&time),
+                "{time} isn't due at {due}"
+            );
+        }
+    }
+}
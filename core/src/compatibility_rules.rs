@@ -1,6 +1,7 @@
+use serde::{Deserialize, Serialize};
 use url::Url;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct UrlRewriteRule {
     pub host: String,
     pub replacement: String,
@@ -62,6 +63,23 @@ impl CompatibilityRules {
         }
     }
 
+    /// Rules that only rewrite the hosts of SWF URLs, applying `rules` in order.
+    pub fn from_swf_domain_rewrite_rules(name: impl ToString, rules: Vec<UrlRewriteRule>) -> Self {
+        Self {
+            rule_sets: vec![RuleSet {
+                name: name.to_string(),
+                swf_domain_rewrite_rules: rules,
+            }],
+        }
+    }
+
+    /// Every rule rewriting the hosts of SWF URLs, in the order they're applied.
+    pub fn swf_domain_rewrite_rules(&self) -> impl Iterator<Item = &UrlRewriteRule> {
+        self.rule_sets
+            .iter()
+            .flat_map(|rule_set| &rule_set.swf_domain_rewrite_rules)
+    }
+
     pub fn rewrite_swf_url(&self, original_url: String) -> String {
         let mut url = match Url::parse(&original_url) {
             Ok(url) => url,
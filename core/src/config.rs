@@ -1,3 +1,9 @@
+use crate::compatibility_rules::{CompatibilityRules, UrlRewriteRule};
+use crate::player_version::PlayerVersion;
+use crate::socket::SocketPolicy;
+use crate::tag_utils::TagStrictness;
+use crate::{LoadBehavior, SandboxType};
+use ruffle_render::quality::StageQuality;
 use serde::{Deserialize, Serialize};
 
 /// Controls whether the content is letterboxed or pillarboxed when the
@@ -51,3 +57,148 @@ pub enum NetworkingAccessMode {
     #[serde(rename = "none")]
     None,
 }
+
+/// The settings that decide how a player runs its movies, saved to and loaded from TOML.
+///
+/// Together with the movie, this is enough to reproduce a run of it, such as an export.
+/// Backends and providers can't be written down, so they aren't part of it.
+/// Any setting missing from a file keeps the value `PlayerBuilder` defaults to.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PlayerConfig {
+    /// How the root movie and the movies it loads are loaded.
+    pub load_behavior: LoadBehavior,
+
+    /// The security sandbox the root movie runs in.
+    pub sandbox_type: SandboxType,
+
+    /// The root SWF URL provided to ActionScript, instead of the real one.
+    pub spoofed_url: Option<String>,
+
+    /// Hosts to replace in the URLs of loaded SWFs, applied in order.
+    pub url_rewrite_rules: Vec<UrlRewriteRule>,
+
+    /// The initial quality of the stage.
+    pub quality: StageQuality,
+
+    /// A frame rate to lock the player to, instead of the movie's own.
+    pub frame_rate: Option<f64>,
+
+    /// The Flash Player release to emulate, or the newest one supported if not set.
+    ///
+    /// Setting it also makes `for..in` visit the dynamic properties of AVM2 objects in the
+    /// order of Flash Player's hashtables, which some content depends on.
+    ///
+    /// Either a major version such as `32`, or a release such as `"11.2"`.
+    pub player_version: Option<PlayerVersion>,
+
+    /// Whether frame and render timings are logged as telemetry events.
+    pub telemetry: bool,
+
+    /// How malformed tags in loaded movies are dealt with.
+    pub tag_strictness: TagStrictness,
+
+    /// Which hosts must serve a socket policy file before sockets may connect to them.
+    pub socket_policy: SocketPolicy,
+}
+
+impl PlayerConfig {
+    pub fn from_toml(toml: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(toml)
+    }
+
+    pub fn to_toml(&self) -> Result<String, toml::ser::Error> {
+        toml::to_string(self)
+    }
+
+    pub(crate) fn compatibility_rules(&self) -> CompatibilityRules {
+        CompatibilityRules::from_swf_domain_rewrite_rules(
+            "player_config",
+            self.url_rewrite_rules.clone(),
+        )
+    }
+}
+
+impl Default for PlayerConfig {
+    fn default() -> Self {
+        Self {
+            load_behavior: LoadBehavior::Streaming,
+            sandbox_type: SandboxType::LocalTrusted,
+            spoofed_url: None,
+            url_rewrite_rules: CompatibilityRules::default()
+                .swf_domain_rewrite_rules()
+                .cloned()
+                .collect(),
+            quality: StageQuality::High,
+            frame_rate: None,
+            player_version: None,
+            telemetry: false,
+            tag_strictness: TagStrictness::default(),
+            socket_policy: SocketPolicy::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PlayerConfig;
+    use crate::compatibility_rules::UrlRewriteRule;
+    use crate::player_version::PlayerVersion;
+    use crate::socket::SocketPolicyOverride;
+    use crate::LoadBehavior;
+    use ruffle_render::quality::StageQuality;
+
+    #[test]
+    fn player_config_toml() {
+        let config = PlayerConfig::from_toml(
+            r#"
+            load_behavior = "blocking"
+            quality = "8x8linear"
+            spoofed_url = "https://example.com/game.swf"
+            player_version = "10.3"
+            telemetry = true
+            url_rewrite_rules = [{ host = "*.example.org", replacement = "example.com" }]
+
+            [socket_policy]
+            require_policy_files = true
+            overrides = [{ host = "*.example.net", port = 9339, require_policy_file = false }]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.load_behavior, LoadBehavior::Blocking);
+        assert_eq!(config.quality, StageQuality::High8x8Linear);
+        assert_eq!(config.player_version, Some(PlayerVersion::new(10, 3)));
+        assert!(config.telemetry);
+        assert_eq!(
+            PlayerConfig::from_toml("player_version = 9")
+                .unwrap()
+                .player_version,
+            Some(PlayerVersion::from(9))
+        );
+        assert_eq!(
+            config.url_rewrite_rules,
+            vec![UrlRewriteRule::new("*.example.org", "example.com")]
+        );
+        assert_eq!(
+            config.socket_policy.overrides,
+            vec![SocketPolicyOverride {
+                host: "*.example.net".to_string(),
+                port: Some(9339),
+                require_policy_file: false,
+            }]
+        );
+        assert!(!config
+            .socket_policy
+            .requires_policy_file("game.example.net", 9339));
+        assert!(config
+            .socket_policy
+            .requires_policy_file("game.example.net", 9340));
+        // Settings that aren't given keep their defaults.
+        assert_eq!(config.frame_rate, PlayerConfig::default().frame_rate);
+
+        let saved = config.to_toml().unwrap();
+        assert_eq!(PlayerConfig::from_toml(&saved).unwrap(), config);
+
+        assert!(PlayerConfig::from_toml("unknown_setting = true").is_err());
+    }
+}
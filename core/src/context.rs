@@ -8,9 +8,10 @@ use crate::backend::{
     audio::{AudioBackend, AudioManager, SoundHandle, SoundInstanceHandle},
     log::LogBackend,
     navigator::NavigatorBackend,
-    storage::StorageBackend,
+    storage::{SecureStorageBackend, StorageBackend},
     ui::{InputManager, UiBackend},
 };
+use crate::clock::PlayerClock;
 use crate::context_menu::ContextMenuState;
 use crate::display_object::{EditText, InteractiveObject, MovieClip, SoundTransform, Stage};
 use crate::external::ExternalInterface;
@@ -18,14 +19,20 @@ use crate::focus_tracker::FocusTracker;
 use crate::frame_lifecycle::FramePhase;
 use crate::library::Library;
 use crate::loader::LoadManager;
+use crate::native_extension::NativeExtensions;
+use crate::native_menu::NativeMenuState;
+use crate::net_group::NetGroups;
 use crate::player::Player;
+use crate::player_version::PlayerVersion;
 use crate::prelude::*;
 use crate::socket::Sockets;
+use crate::sql::SqlConnections;
 use crate::streams::StreamManager;
 use crate::string::AvmStringInterner;
 use crate::stub::StubCollection;
 use crate::tag_utils::{SwfMovie, SwfSlice};
 use crate::timer::Timers;
+use crate::web_view::WebViews;
 use core::fmt;
 use gc_arena::{Collect, Mutation};
 use instant::Instant;
@@ -34,6 +41,7 @@ use ruffle_render::backend::{BitmapCacheEntry, RenderBackend};
 use ruffle_render::commands::CommandList;
 use ruffle_render::transform::TransformStack;
 use ruffle_video::backend::VideoBackend;
+use ruffle_video::media::MediaDecoderBackend;
 use std::collections::{HashMap, VecDeque};
 use std::sync::{Arc, Mutex, Weak};
 use std::time::Duration;
@@ -84,16 +92,18 @@ pub struct UpdateContext<'a, 'gc> {
     /// A collection of stubs encountered during this movie.
     pub stub_tracker: &'a mut StubCollection,
 
+    /// SQLite databases opened by AIR content.
+    pub sql_connections: &'a mut SqlConnections,
+
     /// The library containing character definitions for this SWF.
     /// Used to instantiate a `DisplayObject` of a given ID.
     pub library: &'a mut Library<'gc>,
 
     /// The version of the Flash Player we are emulating.
-    /// TODO: This is a little confusing because this represents the player's max SWF version,
-    /// which is an integer (e.g. 13), but "Flash Player version" is a triplet (11.6.0), and these
-    /// aren't in sync. It may be better to have separate `player_swf_version` and `player_version`
-    /// variables.
-    pub player_version: u8,
+    /// TODO: Its major version also serves as the player's max SWF version, which is an integer
+    /// (e.g. 13) that isn't in sync with the Flash Player release (11.6). It may be better to have
+    /// separate `player_swf_version` and `player_version` variables.
+    pub player_version: PlayerVersion,
 
     /// Requests that the player re-renders after this execution (e.g. due to `updateAfterEvent`).
     pub needs_render: &'a mut bool,
@@ -119,6 +129,9 @@ pub struct UpdateContext<'a, 'gc> {
     /// The storage backend, used for storing persistent state
     pub storage: &'a mut dyn StorageBackend,
 
+    /// The secure storage backend, used for AIR's `EncryptedLocalStore`
+    pub secure_storage: &'a mut dyn SecureStorageBackend,
+
     /// The logging backend, used for trace output capturing.
     ///
     /// **DO NOT** use this field directly, use the `avm_trace` method instead.
@@ -127,6 +140,9 @@ pub struct UpdateContext<'a, 'gc> {
     /// The video backend, used for video decoding
     pub video: &'a mut dyn VideoBackend,
 
+    /// The media decoder backend, used for the codecs only `NetStream` plays
+    pub media: &'a mut dyn MediaDecoderBackend,
+
     /// The RNG, used by the AVM `RandomNumber` opcode,  `Math.random(),` and `random()`.
     pub rng: &'a mut SmallRng,
 
@@ -189,8 +205,11 @@ pub struct UpdateContext<'a, 'gc> {
     /// External interface for (for example) JavaScript <-> ActionScript interaction
     pub external_interface: &'a mut ExternalInterface<'gc>,
 
-    /// The instant at which the SWF was launched.
-    pub start_time: Instant,
+    /// The time that movies see, which only advances as the player is ticked.
+    pub clock: &'a mut PlayerClock,
+
+    /// Whether frame and render timings, and metrics sent by movies, are logged as telemetry.
+    pub telemetry: bool,
 
     /// The instant at which the current update started.
     pub update_start: Instant,
@@ -227,6 +246,14 @@ pub struct UpdateContext<'a, 'gc> {
 
     pub sockets: &'a mut Sockets<'gc>,
 
+    pub net_groups: &'a mut NetGroups<'gc>,
+
+    pub native_extensions: &'a mut NativeExtensions<'gc>,
+
+    pub web_views: &'a mut WebViews<'gc>,
+
+    pub native_menu: &'a mut NativeMenuState<'gc>,
+
     /// Dynamic root for allowing handles to GC objects to exist outside of the GC.
     pub dynamic_root: gc_arena::DynamicRootSet<'gc>,
 }
@@ -351,6 +378,7 @@ impl<'a, 'gc> UpdateContext<'a, 'gc> {
             gc_context: self.gc_context,
             interner: self.interner,
             stub_tracker: self.stub_tracker,
+            sql_connections: self.sql_connections,
             library: self.library,
             player_version: self.player_version,
             needs_render: self.needs_render,
@@ -362,7 +390,9 @@ impl<'a, 'gc> UpdateContext<'a, 'gc> {
             log: self.log,
             ui: self.ui,
             video: self.video,
+            media: self.media,
             storage: self.storage,
+            secure_storage: self.secure_storage,
             rng: self.rng,
             stage: self.stage,
             mouse_over_object: self.mouse_over_object,
@@ -382,7 +412,8 @@ impl<'a, 'gc> UpdateContext<'a, 'gc> {
             avm1: self.avm1,
             avm2: self.avm2,
             external_interface: self.external_interface,
-            start_time: self.start_time,
+            clock: self.clock,
+            telemetry: self.telemetry,
             update_start: self.update_start,
             max_execution_duration: self.max_execution_duration,
             focus_tracker: self.focus_tracker,
@@ -394,6 +425,10 @@ impl<'a, 'gc> UpdateContext<'a, 'gc> {
             frame_phase: self.frame_phase,
             stream_manager: self.stream_manager,
             sockets: self.sockets,
+            net_groups: self.net_groups,
+            native_extensions: self.native_extensions,
+            web_views: self.web_views,
+            native_menu: self.native_menu,
             dynamic_root: self.dynamic_root,
         }
     }
@@ -13,13 +13,14 @@ use crate::vminterface::Instantiator;
 use bitflags::bitflags;
 use gc_arena::{Collect, Mutation};
 use ruffle_macros::enum_trait_object;
+use ruffle_render::matrix3d::{Components3D, Matrix3D, Projection};
 use ruffle_render::pixel_bender::PixelBenderShaderHandle;
 use ruffle_render::transform::{Transform, TransformStack};
 use std::cell::{Ref, RefMut};
 use std::fmt::Debug;
 use std::hash::Hash;
 use std::sync::Arc;
-use swf::{ColorTransform, Fixed8};
+use swf::{BlendMode, ColorTransform, Fixed8};
 
 mod avm1_button;
 mod avm2_button;
@@ -182,6 +183,47 @@ impl BitmapCache {
     }
 }
 
+/// The parts of a display object's transform that only exist in 3D.
+///
+/// `rotationZ` is the same as the 2D rotation, so it is not stored here.
+#[derive(Clone, Debug)]
+pub struct Transform3D {
+    /// The position of this object before it's projected.
+    ///
+    /// The 2D matrix holds where the projection puts it instead.
+    pub x: Twips,
+
+    pub y: Twips,
+
+    /// The position of this object along the Z axis, in pixels.
+    pub z: f64,
+
+    pub rotation_x: Degrees,
+
+    pub rotation_y: Degrees,
+
+    pub scale_z: f64,
+
+    /// The perspective projection this object is seen through, with its centre
+    /// in the coordinate space of the parent.
+    pub projection: Projection,
+}
+
+impl Transform3D {
+    /// A 3D transform that doesn't move an object at the given position out of 2D yet.
+    pub fn new(x: Twips, y: Twips, projection: Projection) -> Self {
+        Self {
+            x,
+            y,
+            z: 0.0,
+            rotation_x: 0.0.into(),
+            rotation_y: 0.0.into(),
+            scale_z: 1.0,
+            projection,
+        }
+    }
+}
+
 #[derive(Clone, Collect)]
 #[collect(no_drop)]
 pub struct DisplayObjectBase<'gc> {
@@ -207,6 +249,18 @@ pub struct DisplayObjectBase<'gc> {
 
     skew: f64,
 
+    /// The 3D transform of this object, if it has ever been given one.
+    ///
+    /// Ruffle can only render 2D affine transforms, so while this is set the 2D
+    /// `transform.matrix` holds a flattened projection of the 3D transform.
+    #[collect(require_static)]
+    transform_3d: Option<Box<Transform3D>>,
+
+    /// The perspective projection set on this object via `transform.perspectiveProjection`,
+    /// which applies to its descendants. Its centre is in this object's coordinate space.
+    #[collect(require_static)]
+    perspective_projection: Option<Projection>,
+
     /// The next display object in order of execution.
     ///
     /// `None` in an AVM2 movie.
@@ -256,6 +310,9 @@ pub struct DisplayObjectBase<'gc> {
     /// None means not cached, Some means cached.
     #[collect(require_static)]
     cache: Option<BitmapCache>,
+
+    /// The AVM2 `metaData` object, set by content or from the AMF data of a PlaceObject4 tag.
+    meta_data: Option<Avm2Object<'gc>>,
 }
 
 impl<'gc> Default for DisplayObjectBase<'gc> {
@@ -272,6 +329,8 @@ impl<'gc> Default for DisplayObjectBase<'gc> {
             scale_x: Percent::from_unit(1.0),
             scale_y: Percent::from_unit(1.0),
             skew: 0.0,
+            transform_3d: None,
+            perspective_projection: None,
             next_avm1_clip: None,
             masker: None,
             maskee: None,
@@ -283,6 +342,7 @@ impl<'gc> Default for DisplayObjectBase<'gc> {
             scroll_rect: None,
             next_scroll_rect: Default::default(),
             cache: None,
+            meta_data: None,
         }
     }
 }
@@ -324,9 +384,114 @@ impl<'gc> DisplayObjectBase<'gc> {
 
     pub fn set_matrix(&mut self, matrix: Matrix) {
         self.transform.matrix = matrix;
+        self.transform_3d = None;
         self.set_scale_rotation_cached(false);
     }
 
+    pub fn transform_3d(&self) -> Option<&Transform3D> {
+        self.transform_3d.as_deref()
+    }
+
+    /// Returns this object's transform as a 3D matrix, regardless of whether
+    /// it has been transformed in 3D.
+    fn matrix_3d(&mut self) -> Matrix3D {
+        self.cache_scale_rotation();
+        let transform_3d = self.transform_3d.as_deref();
+        Matrix3D::recompose(&Components3D {
+            translation: [
+                self.x().to_pixels(),
+                self.y().to_pixels(),
+                transform_3d.map_or(0.0, |t| t.z),
+            ],
+            rotation: [
+                transform_3d.map_or(0.0, |t| t.rotation_x.into_radians()),
+                transform_3d.map_or(0.0, |t| t.rotation_y.into_radians()),
+                self.rotation.into_radians(),
+            ],
+            scale: [
+                self.scale_x.unit(),
+                self.scale_y.unit(),
+                transform_3d.map_or(1.0, |t| t.scale_z),
+            ],
+        })
+    }
+
+    /// Replaces the 3D transform of this object.
+    ///
+    /// Passing `None` returns the object to 2D, keeping its position, scale
+    /// and rotation around Z.
+    fn set_matrix_3d(&mut self, matrix: Option<Matrix3D>, projection: Projection) -> bool {
+        self.set_transformed_by_script(true);
+        self.cache_scale_rotation();
+        self.set_scale_rotation_cached(true);
+
+        match matrix {
+            Some(matrix) => {
+                let components = matrix.decompose();
+                self.rotation = Degrees::from_radians(components.rotation[2]);
+                self.scale_x = Percent::from_unit(components.scale[0]);
+                self.scale_y = Percent::from_unit(components.scale[1]);
+                self.skew = 0.0;
+                self.transform_3d = Some(Box::new(Transform3D {
+                    x: Twips::from_pixels(components.translation[0]),
+                    y: Twips::from_pixels(components.translation[1]),
+                    z: components.translation[2],
+                    rotation_x: Degrees::from_radians(components.rotation[0]),
+                    rotation_y: Degrees::from_radians(components.rotation[1]),
+                    scale_z: components.scale[2],
+                    projection,
+                }));
+            }
+            None => {
+                if let Some(transform_3d) = self.transform_3d.take() {
+                    self.transform.matrix.tx = transform_3d.x;
+                    self.transform.matrix.ty = transform_3d.y;
+                }
+                self.skew = 0.0;
+            }
+        }
+
+        // Without any depth, the projection leaves the matrix as it is.
+        self.transform.matrix = self.matrix_3d().to_projected_matrix(&projection);
+        true
+    }
+
+    /// Modifies the 3D transform of this object, turning it into a 3D object if
+    /// it wasn't one already.
+    fn modify_transform_3d(
+        &mut self,
+        projection: Projection,
+        f: impl FnOnce(&mut Transform3D) -> bool,
+    ) -> bool {
+        self.set_transformed_by_script(true);
+        self.cache_scale_rotation();
+        self.set_scale_rotation_cached(true);
+
+        let (x, y) = (self.transform.matrix.tx, self.transform.matrix.ty);
+        let transform_3d = self
+            .transform_3d
+            .get_or_insert_with(|| Box::new(Transform3D::new(x, y, projection)));
+        transform_3d.projection = projection;
+        let changed = f(transform_3d);
+        self.update_projected_matrix();
+        changed
+    }
+
+    /// Re-flattens the 3D transform into the 2D matrix, if this is a 3D object.
+    fn update_projected_matrix(&mut self) {
+        if let Some(projection) = self.transform_3d.as_ref().map(|t| t.projection) {
+            self.transform.matrix = self.matrix_3d().to_projected_matrix(&projection);
+        }
+    }
+
+    pub fn perspective_projection(&self) -> Option<Projection> {
+        self.perspective_projection
+    }
+
+    fn set_perspective_projection(&mut self, projection: Option<Projection>) {
+        self.perspective_projection = projection;
+    }
+
     pub fn color_transform(&self) -> &ColorTransform {
         &self.transform.color_transform
     }
@@ -340,24 +505,42 @@ impl<'gc> DisplayObjectBase<'gc> {
     }
 
     fn x(&self) -> Twips {
-        self.transform.matrix.tx
+        match &self.transform_3d {
+            Some(transform_3d) => transform_3d.x,
+            None => self.transform.matrix.tx,
+        }
     }
 
     fn set_x(&mut self, x: Twips) -> bool {
-        let changed = self.transform.matrix.tx != x;
+        let changed = self.x() != x;
         self.set_transformed_by_script(true);
-        self.transform.matrix.tx = x;
+        match &mut self.transform_3d {
+            Some(transform_3d) => {
+                transform_3d.x = x;
+                self.update_projected_matrix();
+            }
+            None => self.transform.matrix.tx = x,
+        }
         changed
     }
 
     fn y(&self) -> Twips {
-        self.transform.matrix.ty
+        match &self.transform_3d {
+            Some(transform_3d) => transform_3d.y,
+            None => self.transform.matrix.ty,
+        }
     }
 
     fn set_y(&mut self, y: Twips) -> bool {
-        let changed = self.transform.matrix.ty != y;
+        let changed = self.y() != y;
         self.set_transformed_by_script(true);
-        self.transform.matrix.ty = y;
+        match &mut self.transform_3d {
+            Some(transform_3d) => {
+                transform_3d.y = y;
+                self.update_projected_matrix();
+            }
+            None => self.transform.matrix.ty = y,
+        }
         changed
     }
 
@@ -430,6 +613,7 @@ impl<'gc> DisplayObjectBase<'gc> {
         matrix.b = (self.scale_x.unit() * sin_x) as f32;
         matrix.c = (self.scale_y.unit() * -sin_y) as f32;
         matrix.d = (self.scale_y.unit() * cos_y) as f32;
+        self.update_projected_matrix();
 
         changed
     }
@@ -457,6 +641,7 @@ impl<'gc> DisplayObjectBase<'gc> {
         let matrix = &mut self.transform.matrix;
         matrix.a = (cos * value.unit()) as f32;
         matrix.b = (sin * value.unit()) as f32;
+        self.update_projected_matrix();
 
         changed
     }
@@ -484,6 +669,7 @@ impl<'gc> DisplayObjectBase<'gc> {
         let matrix = &mut self.transform.matrix;
         matrix.c = (-sin * value.unit()) as f32;
         matrix.d = (cos * value.unit()) as f32;
+        self.update_projected_matrix();
 
         changed
     }
@@ -952,7 +1138,9 @@ pub fn render_base<'gc>(this: DisplayObject<'gc>, context: &mut RenderContext<'_
 /// This applies the **standard** method of `mask` and `scrollRect`.
 ///
 /// It uses the stencil buffer so that any pixel drawn in the mask will allow the inner contents to show.
-/// This is what is used for most cases, except for cacheAsBitmap-on-cacheAsBitmap.
+/// This is what is used for most cases, except for cacheAsBitmap-on-cacheAsBitmap: when both the
+/// object and its mask are bitmap cached, the mask is a "soft" mask, and the alpha of each of its
+/// pixels is applied to the contents instead.
 pub fn apply_standard_mask_and_scroll<'gc, F>(
     this: DisplayObject<'gc>,
     context: &mut RenderContext<'_, 'gc>,
@@ -985,14 +1173,24 @@ pub fn apply_standard_mask_and_scroll<'gc, F>(
 
     let mask = this.masker();
     let mut mask_transform = ruffle_render::transform::Transform::default();
+    let is_soft_mask = mask.is_some_and(|m| {
+        context.use_bitmap_cache
+            && context.renderer.is_offscreen_supported()
+            && this.is_bitmap_cached()
+            && m.is_bitmap_cached()
+    });
+    // A soft mask is drawn over the contents afterwards, so draw the contents into their own layer.
+    let original_commands = is_soft_mask.then(|| std::mem::take(&mut context.commands));
     if let Some(m) = mask {
         mask_transform.matrix = this.global_to_local_matrix().unwrap_or_default();
         mask_transform.matrix *= m.local_to_global_matrix();
-        context.commands.push_mask();
-        context.transform_stack.push(&mask_transform);
-        m.render_self(context);
-        context.transform_stack.pop();
-        context.commands.activate_mask();
+        if !is_soft_mask {
+            context.commands.push_mask();
+            context.transform_stack.push(&mask_transform);
+            m.render_self(context);
+            context.transform_stack.pop();
+            context.commands.activate_mask();
+        }
     }
 
     // There are two parts to 'DisplayObject.scrollRect':
@@ -1024,11 +1222,26 @@ pub fn apply_standard_mask_and_scroll<'gc, F>(
     }
 
     if let Some(m) = mask {
-        context.commands.deactivate_mask();
-        context.transform_stack.push(&mask_transform);
-        m.render_self(context);
-        context.transform_stack.pop();
-        context.commands.pop_mask();
+        if let Some(original_commands) = original_commands {
+            let contents = std::mem::replace(&mut context.commands, CommandList::new());
+            context.transform_stack.push(&mask_transform);
+            m.render_self(context);
+            context.transform_stack.pop();
+            let mask_commands = std::mem::replace(&mut context.commands, contents);
+            context
+                .commands
+                .blend(mask_commands, RenderBlendMode::Builtin(BlendMode::Alpha));
+            let layer = std::mem::replace(&mut context.commands, original_commands);
+            context
+                .commands
+                .blend(layer, RenderBlendMode::Builtin(BlendMode::Layer));
+        } else {
+            context.commands.deactivate_mask();
+            context.transform_stack.push(&mask_transform);
+            m.render_self(context);
+            context.transform_stack.pop();
+            context.commands.pop_mask();
+        }
     }
 
     if scroll_rect_matrix.is_some() {
@@ -1130,6 +1343,39 @@ pub trait TDisplayObject<'gc>:
         bounds
     }
 
+    /// The untransformed inherent bounding box of this object, excluding the
+    /// width of any strokes.
+    /// These are the bounds returned by the AS3 `getRect` method.
+    fn self_edge_bounds(&self) -> Rectangle<Twips> {
+        self.self_bounds()
+    }
+
+    /// Gets the bounds of this object and all children, excluding strokes,
+    /// transformed by a given matrix.
+    /// This is the same as `bounds_with_transform`, but uses `self_edge_bounds`.
+    fn edge_bounds_with_transform(&self, matrix: &Matrix) -> Rectangle<Twips> {
+        if let Some(scroll_rect) = self.scroll_rect() {
+            return *matrix
+                * Rectangle {
+                    x_min: Twips::ZERO,
+                    y_min: Twips::ZERO,
+                    x_max: scroll_rect.width(),
+                    y_max: scroll_rect.height(),
+                };
+        }
+
+        let mut bounds = *matrix * self.self_edge_bounds();
+
+        if let Some(ctr) = self.as_container() {
+            for child in ctr.iter_render_list() {
+                let matrix = *matrix * *child.base().matrix();
+                bounds = bounds.union(&child.edge_bounds_with_transform(&matrix));
+            }
+        }
+
+        bounds
+    }
+
     /// Gets the **render bounds** of this object and all its children.
     /// This differs from the bounds that are exposed to Flash, in two main ways:
     /// - It may be larger if filters are applied which will increase the size of what's shown
@@ -1343,6 +1589,148 @@ pub trait TDisplayObject<'gc>:
         }
     }
 
+    /// The perspective projection this object is seen through, with its centre in
+    /// the coordinate space of the parent.
+    ///
+    /// This comes from the nearest ancestor with a `perspectiveProjection`, or else
+    /// is the stage's default projection.
+    fn inherited_projection(&self, context: &UpdateContext<'_, 'gc>) -> Projection {
+        let to_parent = self
+            .parent()
+            .and_then(|parent| parent.global_to_local_matrix())
+            .unwrap_or_default();
+
+        let mut node = self.parent();
+        while let Some(obj) = node {
+            if let Some(projection) = obj.base().perspective_projection() {
+                return projection.transformed(&(to_parent * obj.local_to_global_matrix()));
+            }
+            node = obj.parent();
+        }
+
+        let (width, height) = context.stage.stage_size();
+        Projection::for_stage(width.into(), height.into()).transformed(&to_parent)
+    }
+
+    /// Modifies the 3D transform of this object, converting it into a 3D object
+    /// if necessary.
+    /// This invalidates any ancestors cacheAsBitmap automatically.
+    fn modify_transform_3d(
+        &self,
+        context: &mut UpdateContext<'_, 'gc>,
+        f: impl FnOnce(&mut Transform3D) -> bool,
+    ) {
+        let projection = self.inherited_projection(context);
+        if self
+            .base_mut(context.gc_context)
+            .modify_transform_3d(projection, f)
+        {
+            if let Some(parent) = self.parent() {
+                parent.invalidate_cached_bitmap(context.gc_context);
+            }
+        }
+    }
+
+    /// The Z position of this display object in local space.
+    /// Returned by the AS3 `z` property.
+    fn z(&self) -> f64 {
+        self.base().transform_3d().map_or(0.0, |t| t.z)
+    }
+
+    /// Sets the Z position of this display object in local space.
+    /// Set by the AS3 `z` property.
+    fn set_z(&self, context: &mut UpdateContext<'_, 'gc>, value: f64) {
+        self.modify_transform_3d(context, |t| {
+            let changed = t.z != value;
+            t.z = value;
+            changed
+        });
+    }
+
+    /// Returned by the AS3 `rotationX` property.
+    fn rotation_x(&self) -> Degrees {
+        self.base()
+            .transform_3d()
+            .map_or(0.0.into(), |t| t.rotation_x)
+    }
+
+    /// Set by the AS3 `rotationX` property.
+    fn set_rotation_x(&self, context: &mut UpdateContext<'_, 'gc>, degrees: Degrees) {
+        self.modify_transform_3d(context, |t| {
+            let changed = t.rotation_x != degrees;
+            t.rotation_x = degrees;
+            changed
+        });
+    }
+
+    /// Returned by the AS3 `rotationY` property.
+    fn rotation_y(&self) -> Degrees {
+        self.base()
+            .transform_3d()
+            .map_or(0.0.into(), |t| t.rotation_y)
+    }
+
+    /// Set by the AS3 `rotationY` property.
+    fn set_rotation_y(&self, context: &mut UpdateContext<'_, 'gc>, degrees: Degrees) {
+        self.modify_transform_3d(context, |t| {
+            let changed = t.rotation_y != degrees;
+            t.rotation_y = degrees;
+            changed
+        });
+    }
+
+    /// Returned by the AS3 `scaleZ` property.
+    fn scale_z(&self) -> f64 {
+        self.base().transform_3d().map_or(1.0, |t| t.scale_z)
+    }
+
+    /// Set by the AS3 `scaleZ` property.
+    fn set_scale_z(&self, context: &mut UpdateContext<'_, 'gc>, value: f64) {
+        self.modify_transform_3d(context, |t| {
+            let changed = t.scale_z != value;
+            t.scale_z = value;
+            changed
+        });
+    }
+
+    /// The 3D transform of this object, or `None` if it is a 2D object.
+    /// Returned by the AS3 `transform.matrix3D` property.
+    fn matrix_3d(&self, gc_context: &Mutation<'gc>) -> Option<Matrix3D> {
+        let mut base = self.base_mut(gc_context);
+        base.transform_3d()?;
+        Some(base.matrix_3d())
+    }
+
+    /// Replaces the 3D transform of this object, or returns it to 2D if `None`.
+    /// Set by the AS3 `transform.matrix3D` property.
+    /// This invalidates any ancestors cacheAsBitmap automatically.
+    fn set_matrix_3d(&self, context: &mut UpdateContext<'_, 'gc>, matrix: Option<Matrix3D>) {
+        let projection = self.inherited_projection(context);
+        if self
+            .base_mut(context.gc_context)
+            .set_matrix_3d(matrix, projection)
+        {
+            if let Some(parent) = self.parent() {
+                parent.invalidate_cached_bitmap(context.gc_context);
+            }
+        }
+    }
+
+    /// Sets the perspective projection applied to this object's descendants, or
+    /// removes it if `None`. The projection centre is in this object's coordinate space.
+    /// Set by the AS3 `transform.perspectiveProjection` property.
+    ///
+    /// Descendants only pick up the new projection the next time their 3D
+    /// transform changes.
+    fn set_perspective_projection(
+        &self,
+        gc_context: &Mutation<'gc>,
+        projection: Option<Projection>,
+    ) {
+        self.base_mut(gc_context)
+            .set_perspective_projection(projection);
+    }
+
     /// Gets the pixel width of the AABB containing this display object in local space.
     /// Returned by the ActionScript `_width`/`width` properties.
     fn width(&self) -> f64 {
@@ -1692,6 +2080,15 @@ pub trait TDisplayObject<'gc>:
         }
     }
 
+    /// The AVM2 `metaData` object of this display object.
+    fn meta_data(&self) -> Option<Avm2Object<'gc>> {
+        self.base().meta_data
+    }
+
+    fn set_meta_data(&self, gc_context: &Mutation<'gc>, value: Option<Avm2Object<'gc>>) {
+        self.base_mut(gc_context).meta_data = value;
+    }
+
     /// Whether this display object represents the root of loaded content.
     fn is_root(&self) -> bool {
         self.base().is_root()
@@ -2098,20 +2495,21 @@ pub trait TDisplayObject<'gc>:
             if let Some(blend_mode) = place_object.blend_mode {
                 self.set_blend_mode(context.gc_context, blend_mode.into());
             }
-            if self.swf_version() >= 11 {
-                if let Some(visible) = place_object.is_visible {
-                    self.set_visible(context.gc_context, visible);
-                }
-                if let Some(mut color) = place_object.background_color {
-                    let color = if color.a > 0 {
-                        // Force opaque background to have no transpranecy.
-                        color.a = 255;
-                        Some(color)
-                    } else {
-                        None
-                    };
-                    self.set_opaque_background(context.gc_context, color);
-                }
+            // These flags were only introduced in SWF 11, but they're read whenever they're set,
+            // so they apply whenever they're set as well. Some generated SWFs of earlier versions
+            // use them.
+            if let Some(visible) = place_object.is_visible {
+                self.set_visible(context.gc_context, visible);
+            }
+            if let Some(mut color) = place_object.background_color {
+                let color = if color.a > 0 {
+                    // Force opaque background to have no transpranecy.
+                    color.a = 255;
+                    Some(color)
+                } else {
+                    None
+                };
+                self.set_opaque_background(context.gc_context, color);
             }
             if let Some(filters) = &place_object.filters {
                 self.set_filters(
@@ -2146,6 +2544,16 @@ pub trait TDisplayObject<'gc>:
         self.world_bounds().contains(point)
     }
 
+    /// Tests if a given stage position point lies within this object's `scrollRect`.
+    /// Objects without a `scrollRect` are not clipped, so this is always `true` for them.
+    fn hit_test_scroll_rect(&self, point: Point<Twips>) -> bool {
+        let Some(scroll_rect) = self.scroll_rect() else {
+            return true;
+        };
+        self.global_to_local(point)
+            .is_some_and(|local| scroll_rect.contains(local))
+    }
+
     /// Tests if a given object's world bounds intersects with the world bounds
     /// of this object.
     fn hit_test_object(&self, other: DisplayObject<'gc>) -> bool {
@@ -360,6 +360,23 @@ impl<'gc> TDisplayObject<'gc> for Avm1Button<'gc> {
         point: Point<Twips>,
         options: HitTestOptions,
     ) -> bool {
+        if options.contains(HitTestOptions::SKIP_INVISIBLE)
+            && !self.visible()
+            && self.maskee().is_none()
+        {
+            return false;
+        }
+
+        if options.contains(HitTestOptions::SKIP_MASK) && self.maskee().is_some() {
+            return false;
+        }
+
+        if let Some(masker) = self.masker() {
+            if !masker.hit_test_shape(context, point, HitTestOptions::SKIP_INVISIBLE) {
+                return false;
+            }
+        }
+
         for child in self.iter_render_list() {
             if child.hit_test_shape(context, point, options) {
                 return true;
@@ -574,6 +591,17 @@ impl<'gc> TInteractiveObject<'gc> for Avm1Button<'gc> {
     ) -> Option<InteractiveObject<'gc>> {
         // The button is hovered if the mouse is over any child nodes.
         if self.visible() && self.mouse_enabled() {
+            // A button used as a mask can't be hovered.
+            if self.maskee().is_some() || !self.hit_test_scroll_rect(point) {
+                return None;
+            }
+
+            if let Some(masker) = self.masker() {
+                if !masker.hit_test_shape(context, point, HitTestOptions::SKIP_INVISIBLE) {
+                    return None;
+                }
+            }
+
             for child in self.iter_render_list().rev() {
                 let result = child
                     .as_interactive()
@@ -642,6 +642,29 @@ impl<'gc> TDisplayObject<'gc> for Avm2Button<'gc> {
         bounds
     }
 
+    fn edge_bounds_with_transform(&self, matrix: &Matrix) -> Rectangle<Twips> {
+        if let Some(scroll_rect) = self.scroll_rect() {
+            return *matrix
+                * Rectangle {
+                    x_min: Twips::ZERO,
+                    y_min: Twips::ZERO,
+                    x_max: scroll_rect.width(),
+                    y_max: scroll_rect.height(),
+                };
+        }
+
+        let mut bounds = *matrix * self.self_edge_bounds();
+
+        let state = self.0.read().state;
+        if let Some(child) = self.get_state_child(state.into()) {
+            let matrix = *matrix * *child.base().matrix();
+            let child_bounds = child.edge_bounds_with_transform(&matrix);
+            bounds = bounds.union(&child_bounds);
+        }
+
+        bounds
+    }
+
     fn render_bounds_with_transform(
         &self,
         matrix: &Matrix,
@@ -17,6 +17,7 @@ use crate::vminterface::Instantiator;
 use core::fmt;
 use gc_arena::{Collect, GcCell, GcWeakCell, Mutation};
 use ruffle_render::bitmap::{BitmapFormat, PixelSnapping};
+use ruffle_render::quality::StageQuality;
 use std::cell::{Ref, RefMut};
 use std::sync::Arc;
 
@@ -380,9 +381,11 @@ impl<'gc> TDisplayObject<'gc> for Bitmap<'gc> {
         }
 
         let bitmap_data = self.0.read();
+        // Bitmaps are never smoothed at low quality.
+        let smoothing = bitmap_data.smoothing && context.stage.quality().smooths_bitmaps();
         bitmap_data
             .bitmap_data
-            .render(bitmap_data.smoothing, context, bitmap_data.pixel_snapping);
+            .render(smoothing, context, bitmap_data.pixel_snapping);
     }
 
     fn object2(&self) -> Avm2Value<'gc> {
@@ -19,7 +19,7 @@ use crate::display_object::interactive::{
 };
 use crate::display_object::{DisplayObjectBase, DisplayObjectPtr, TDisplayObject};
 use crate::drawing::Drawing;
-use crate::events::{ClipEvent, ClipEventResult, TextControlCode};
+use crate::events::{ClipEvent, ClipEventResult, MouseWheelDelta, TextControlCode};
 use crate::font::{round_down_to_pixel, Glyph, TextRenderSettings};
 use crate::html::{
     BoxBounds, FormatSpans, LayoutBox, LayoutContent, LayoutMetrics, Position, TextFormat,
@@ -155,6 +155,10 @@ pub struct EditTextData<'gc> {
     /// Doesn't affect script-triggered modifications.
     max_chars: i32,
 
+    /// The span of the link that the mouse was pressed on, if any.
+    /// The link is followed if the mouse is released over the same span.
+    pressed_link_span: Option<usize>,
+
     /// Flags indicating the text field's settings.
     #[collect(require_static)]
     flags: EditTextFlag,
@@ -322,6 +326,7 @@ impl<'gc> EditText<'gc> {
                 line_data,
                 scroll: 1,
                 max_chars: swf_tag.max_length().unwrap_or_default() as i32,
+                pressed_link_span: None,
             },
         ));
 
@@ -492,6 +497,33 @@ impl<'gc> EditText<'gc> {
             .set(EditTextFlag::NO_SELECT, !is_selectable);
     }
 
+    pub fn is_mouse_wheel_enabled(self) -> bool {
+        !self
+            .0
+            .read()
+            .flags
+            .contains(EditTextFlag::MOUSE_WHEEL_DISABLED)
+    }
+
+    pub fn set_mouse_wheel_enabled(self, is_enabled: bool, gc_context: &Mutation<'gc>) {
+        self.0
+            .write(gc_context)
+            .flags
+            .set(EditTextFlag::MOUSE_WHEEL_DISABLED, !is_enabled);
+    }
+
+    /// Scrolls this text field by the given mouse wheel movement, as Flash does for the
+    /// text field under the mouse.
+    pub fn scroll_by_mouse_wheel(
+        self,
+        delta: MouseWheelDelta,
+        context: &mut UpdateContext<'_, 'gc>,
+    ) {
+        if self.is_mouse_wheel_enabled() {
+            self.set_scroll(self.scroll() as f64 - delta.lines(), context);
+        }
+    }
+
     pub fn is_word_wrap(self) -> bool {
         self.0.read().flags.contains(EditTextFlag::WORD_WRAP)
     }
@@ -724,6 +756,9 @@ impl<'gc> EditText<'gc> {
     /// have already been calculated and applied to HTML trees lowered into the
     /// text-span representation.
     fn relayout(self, context: &mut UpdateContext<'_, 'gc>) {
+        let old_max_scroll = self.maxscroll();
+        let old_max_hscroll = self.maxhscroll();
+
         let mut edit_text = self.0.write(context.gc_context);
         let autosize = edit_text.autosize;
         let is_word_wrap = edit_text.flags.contains(EditTextFlag::WORD_WRAP);
@@ -750,9 +785,6 @@ impl<'gc> EditText<'gc> {
         edit_text.line_data = get_line_data(&new_layout);
         edit_text.layout = new_layout;
         edit_text.intrinsic_bounds = intrinsic_bounds;
-        // reset scroll
-        edit_text.hscroll = 0.0;
-        edit_text.scroll = 1;
 
         if autosize != AutoSizeMode::None {
             if !is_word_wrap {
@@ -780,6 +812,27 @@ impl<'gc> EditText<'gc> {
             drop(edit_text);
             self.invalidate_cached_bitmap(context.gc_context);
         }
+
+        // Like Flash, keep the scroll position across relayouts (e.g. appending to a chat log),
+        // snapped back into the new scrollable range.
+        let max_scroll = self.maxscroll();
+        let max_hscroll = self.maxhscroll();
+        let mut edit_text = self.0.write(context.gc_context);
+        let old_scroll = edit_text.scroll;
+        let old_hscroll = edit_text.hscroll;
+        edit_text.scroll = old_scroll.clamp(1, max_scroll);
+        edit_text.hscroll = old_hscroll.clamp(0.0, max_hscroll);
+        // Listeners are also told when the scrollable range changes, so that scroll bars can follow.
+        #[allow(clippy::float_cmp)]
+        let changed = edit_text.scroll != old_scroll
+            || edit_text.hscroll != old_hscroll
+            || max_scroll != old_max_scroll
+            || max_hscroll != old_max_hscroll;
+        drop(edit_text);
+
+        if changed {
+            self.on_scroller(context);
+        }
     }
 
     /// Measure the width and height of the `EditText`'s current text load.
@@ -1083,6 +1136,10 @@ impl<'gc> EditText<'gc> {
             .flags
             .contains(EditTextFlag::FIRING_VARIABLE_BINDING)
         {
+            let Some(parent) = self.avm1_parent() else {
+                return;
+            };
+
             self.0.write(activation.context.gc_context).flags |=
                 EditTextFlag::FIRING_VARIABLE_BINDING;
             if let Some(variable) = self.variable() {
@@ -1092,13 +1149,13 @@ impl<'gc> EditText<'gc> {
                 drop(variable);
 
                 if let Ok(Some((object, property))) =
-                    activation.resolve_variable_path(self.avm1_parent().unwrap(), &variable_path)
+                    activation.resolve_variable_path(parent, &variable_path)
                 {
                     // Note that this can call virtual setters, even though the opposite direction won't work
                     // (virtual property changes do not affect the text field)
                     activation.run_with_child_frame_for_display_object(
                         "[Propagate Text Binding]",
-                        self.avm1_parent().unwrap(),
+                        parent,
                         activation.context.swf.version(),
                         |activation| {
                             let property = AvmString::new(activation.context.gc_context, property);
@@ -1148,7 +1205,11 @@ impl<'gc> EditText<'gc> {
     }
 
     pub fn set_hscroll(self, hscroll: f64, context: &mut UpdateContext<'_, 'gc>) {
-        self.0.write(context.gc_context).hscroll = hscroll;
+        let old_hscroll = std::mem::replace(&mut self.0.write(context.gc_context).hscroll, hscroll);
+        #[allow(clippy::float_cmp)]
+        if old_hscroll != hscroll {
+            self.on_scroller(context);
+        }
     }
 
     pub fn scroll(self) -> usize {
@@ -1165,7 +1226,10 @@ impl<'gc> EditText<'gc> {
             scroll as usize
         };
         let clamped = scroll_lines.clamp(1, self.maxscroll());
-        self.0.write(context.gc_context).scroll = clamped;
+        let old_scroll = std::mem::replace(&mut self.0.write(context.gc_context).scroll, clamped);
+        if old_scroll != clamped {
+            self.on_scroller(context);
+        }
     }
 
     pub fn max_chars(self) -> i32 {
@@ -1319,16 +1383,18 @@ impl<'gc> EditText<'gc> {
                     }
                 }
                 TextControlCode::Paste => {
-                    let text = &context.ui.clipboard_content();
+                    let text = WString::from_utf8(&context.ui.clipboard_content());
                     // TODO: To match Flash Player, we should truncate pasted text that is longer than max_chars
                     // instead of canceling the paste action entirely
-                    if text.len() <= self.available_chars() {
-                        self.replace_text(
-                            selection.start(),
-                            selection.end(),
-                            &WString::from_utf8(text),
+                    if text.len() <= self.available_chars()
+                        && self.dispatch_text_input(
+                            AvmString::new(context.gc_context, text.clone()),
                             context,
-                        );
+                        )
+                    {
+                        // A `textInput` listener may have changed the selection or the text.
+                        let selection = self.selection().unwrap_or(selection);
+                        self.replace_text(selection.start(), selection.end(), &text, context);
                         let new_pos = selection.start() + text.len();
                         if is_selectable {
                             self.set_selection(
@@ -1417,62 +1483,67 @@ impl<'gc> EditText<'gc> {
         }
     }
 
+    /// Fire an AVM2 `textInput` event for text about to be inserted by the
+    /// user, before it is inserted.
+    ///
+    /// Returns `false` if a listener cancelled the event, in which case the
+    /// text must not be inserted. AVM1 has no equivalent event, so this always
+    /// returns `true` for AVM1 text fields.
+    fn dispatch_text_input(
+        self,
+        text: AvmString<'gc>,
+        context: &mut UpdateContext<'_, 'gc>,
+    ) -> bool {
+        if let Avm2Value::Object(target) = self.object2() {
+            let mut activation = Avm2Activation::from_nothing(context.reborrow());
+            let text_evt = Avm2EventObject::text_event(
+                &mut activation,
+                "textInput",
+                text,
+                true, /* bubbles */
+                true, /* cancelable */
+            );
+            Avm2::dispatch_event(&mut activation.context, text_evt, target);
+
+            return !text_evt.as_event().unwrap().is_cancelled();
+        }
+
+        true
+    }
+
     pub fn text_input(self, character: char, context: &mut UpdateContext<'_, 'gc>) {
         if self.0.read().flags.contains(EditTextFlag::READ_ONLY) {
             return;
         }
 
-        if let Some(selection) = self.selection() {
-            let mut changed = false;
-            let mut cancelled = false;
-            match character as u8 {
-                code if !(code as char).is_control() => {
-                    if self.available_chars() > 0 {
-                        if let Avm2Value::Object(target) = self.object2() {
-                            let character_string =
-                                AvmString::new_utf8(context.gc_context, character.to_string());
-
-                            let mut activation = Avm2Activation::from_nothing(context.reborrow());
-                            let text_evt = Avm2EventObject::text_event(
-                                &mut activation,
-                                "textInput",
-                                character_string,
-                                true,
-                                true,
-                            );
-                            Avm2::dispatch_event(&mut activation.context, text_evt, target);
+        // Characters composed by an IME may lie outside of the BMP, in which case
+        // they take up two UTF-16 code units in the text.
+        let text = WString::from_char(character);
+        if character.is_control() || self.available_chars() < text.len() {
+            return;
+        }
 
-                            cancelled = text_evt.as_event().unwrap().is_cancelled();
-                        }
+        if !self.dispatch_text_input(AvmString::new(context.gc_context, text.clone()), context) {
+            return;
+        }
 
-                        if !cancelled {
-                            self.replace_text(
-                                selection.start(),
-                                selection.end(),
-                                &WString::from_char(character),
-                                context,
-                            );
-                            let new_pos = selection.start() + character.len_utf8();
-                            self.set_selection(
-                                Some(TextSelection::for_position(new_pos)),
-                                context.gc_context,
-                            );
-                            changed = true;
-                        }
-                    }
-                }
-                _ => {}
-            }
+        // A `textInput` listener may have changed the selection or the text,
+        // so only read it back after the event has been dispatched.
+        if let Some(selection) = self.selection() {
+            self.replace_text(selection.start(), selection.end(), &text, context);
+            let new_pos = selection.start() + text.len();
+            self.set_selection(
+                Some(TextSelection::for_position(new_pos)),
+                context.gc_context,
+            );
 
-            if changed {
-                let mut activation = Avm1Activation::from_nothing(
-                    context.reborrow(),
-                    ActivationIdentifier::root("[Propagate Text Binding]"),
-                    self.into(),
-                );
-                self.propagate_text_binding(&mut activation);
-                self.on_changed(&mut activation);
-            }
+            let mut activation = Avm1Activation::from_nothing(
+                context.reborrow(),
+                ActivationIdentifier::root("[Propagate Text Binding]"),
+                self.into(),
+            );
+            self.propagate_text_binding(&mut activation);
+            self.on_changed(&mut activation);
         }
     }
 
@@ -1516,6 +1587,28 @@ impl<'gc> EditText<'gc> {
         }
     }
 
+    /// Notifies listeners that the scroll position of this text field changed.
+    ///
+    /// In AVM1 this broadcasts `onScroller`, in AVM2 it dispatches a `scroll` event.
+    fn on_scroller(&self, context: &mut UpdateContext<'_, 'gc>) {
+        if let Avm1Value::Object(object) = self.object() {
+            let mut activation = Avm1Activation::from_nothing(
+                context.reborrow(),
+                ActivationIdentifier::root("[OnScroller]"),
+                (*self).into(),
+            );
+            let _ = object.call_method(
+                "broadcastMessage".into(),
+                &["onScroller".into(), object.into()],
+                &mut activation,
+                ExecutionReason::Special,
+            );
+        } else if let Avm2Value::Object(object) = self.object2() {
+            let scroll_evt = Avm2EventObject::bare_default_event(context, "scroll");
+            Avm2::dispatch_event(context, scroll_evt, object);
+        }
+    }
+
     /// Construct the text field's AVM1 representation.
     fn construct_as_avm1_object(&self, context: &mut UpdateContext<'_, 'gc>, run_frame: bool) {
         let mut text = self.0.write(context.gc_context);
@@ -1695,6 +1788,37 @@ impl<'gc> EditText<'gc> {
         }
     }
 
+    /// Follows the link the mouse was pressed on, if it is released over that same link.
+    fn release_link(self, context: &mut UpdateContext<'_, 'gc>) -> ClipEventResult {
+        let Some(pressed_span) = self.0.write(context.gc_context).pressed_link_span.take() else {
+            return ClipEventResult::NotHandled;
+        };
+
+        let released_span = self
+            .screen_position_to_index(*context.mouse_position)
+            .and_then(|position| self.0.read().text_spans.resolve_position_as_span(position))
+            .map(|(span_index, _)| span_index);
+        if released_span != Some(pressed_span) {
+            return ClipEventResult::NotHandled;
+        }
+
+        // We can't hold self as any link may end up modifying this object, so pull the info out
+        let link_to_open = self
+            .0
+            .read()
+            .text_spans
+            .span(pressed_span)
+            .map(|s| (s.url.clone(), s.target.clone()));
+
+        if let Some((url, target)) = link_to_open {
+            if !url.is_empty() {
+                self.open_url(context, &url, &target);
+            }
+        }
+
+        ClipEventResult::Handled
+    }
+
     fn is_link_at(self, point: Point<Twips>) -> bool {
         let text = self.0.read();
         let Some(mut position) = self.global_to_local(point) else {
@@ -1900,7 +2024,9 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
 
         edit_text.drawing.render(context);
 
-        context.commands.push_mask();
+        // Masks can't be nested while drawing a mask, so a text field used as a mask
+        // isn't clipped to its bounds.
+        let clip_to_bounds = !context.commands.drawing_mask();
         let mask = Matrix::create_box(
             edit_text.bounds.width().to_pixels() as f32,
             edit_text.bounds.height().to_pixels() as f32,
@@ -1908,11 +2034,14 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
             Twips::ZERO,
             Twips::ZERO,
         );
-        context.commands.draw_rect(
-            Color::WHITE,
-            context.transform_stack.transform().matrix * mask,
-        );
-        context.commands.activate_mask();
+        if clip_to_bounds {
+            context.commands.push_mask();
+            context.commands.draw_rect(
+                Color::WHITE,
+                context.transform_stack.transform().matrix * mask,
+            );
+            context.commands.activate_mask();
+        }
 
         let scroll_offset = edit_text.vertical_scroll_offset();
         // TODO: Where does this come from? How is this different than INTERNAL_PADDING? Does this apply to y as well?
@@ -1959,20 +2088,18 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
 
         context.transform_stack.pop();
 
-        context.commands.deactivate_mask();
-        context.commands.draw_rect(
-            Color::WHITE,
-            context.transform_stack.transform().matrix * mask,
-        );
-        context.commands.pop_mask();
+        if clip_to_bounds {
+            context.commands.deactivate_mask();
+            context.commands.draw_rect(
+                Color::WHITE,
+                context.transform_stack.transform().matrix * mask,
+            );
+            context.commands.pop_mask();
+        }
 
         context.transform_stack.pop();
     }
 
-    fn allow_as_mask(&self) -> bool {
-        false
-    }
-
     fn avm1_unload(&self, context: &mut UpdateContext<'_, 'gc>) {
         let had_focus = self.0.read().flags.contains(EditTextFlag::HAS_FOCUS);
         if had_focus {
@@ -2039,7 +2166,7 @@ impl<'gc> TInteractiveObject<'gc> for EditText<'gc> {
         _context: &mut UpdateContext<'_, 'gc>,
         event: ClipEvent,
     ) -> ClipEventResult {
-        if event != ClipEvent::Press {
+        if event != ClipEvent::Press && event != ClipEvent::Release {
             return ClipEventResult::NotHandled;
         }
 
@@ -2049,15 +2176,18 @@ impl<'gc> TInteractiveObject<'gc> for EditText<'gc> {
     fn event_dispatch(
         self,
         context: &mut UpdateContext<'_, 'gc>,
-        _event: ClipEvent<'gc>,
+        event: ClipEvent<'gc>,
     ) -> ClipEventResult {
+        if event == ClipEvent::Release {
+            return self.release_link(context);
+        }
+
         if self.is_editable() || self.is_selectable() {
             let tracker = context.focus_tracker;
             tracker.set(Some(self.into()), context);
         }
 
-        // We can't hold self as any link may end up modifying this object, so pull the info out
-        let mut link_to_open = None;
+        let mut pressed_link_span = None;
 
         if let Some(position) = self.screen_position_to_index(*context.mouse_position) {
             self.0.write(context.gc_context).selection =
@@ -2066,26 +2196,22 @@ impl<'gc> TInteractiveObject<'gc> for EditText<'gc> {
             if let Some((span_index, _)) =
                 self.0.read().text_spans.resolve_position_as_span(position)
             {
-                link_to_open = self
+                let is_link = self
                     .0
                     .read()
                     .text_spans
                     .span(span_index)
-                    .map(|s| (s.url.clone(), s.target.clone()));
+                    .is_some_and(|s| !s.url.is_empty());
+                if is_link {
+                    pressed_link_span = Some(span_index);
+                }
             }
         } else {
             self.0.write(context.gc_context).selection =
                 Some(TextSelection::for_position(self.text_length()));
         }
 
-        if let Some((url, target)) = link_to_open {
-            if !url.is_empty() {
-                // TODO: This fires on mouse DOWN but it should be mouse UP...
-                // but only if it went down in the same span.
-                // Needs more advanced focus handling than we have at time of writing this comment.
-                self.open_url(context, &url, &target);
-            }
-        }
+        self.0.write(context.gc_context).pressed_link_span = pressed_link_span;
 
         ClipEventResult::Handled
     }
@@ -2149,6 +2275,7 @@ bitflags::bitflags! {
         const FIRING_VARIABLE_BINDING = 1 << 0;
         const HAS_BACKGROUND = 1 << 1;
         const HAS_FOCUS = 1 << 2;
+        const MOUSE_WHEEL_DISABLED = 1 << 7;
 
         // The following bits need to match `swf::EditTextFlag`.
         const READ_ONLY = 1 << 3;
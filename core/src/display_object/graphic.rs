@@ -13,6 +13,7 @@ use core::fmt;
 use gc_arena::{Collect, GcCell, Mutation};
 use ruffle_render::backend::ShapeHandle;
 use ruffle_render::commands::CommandHandler;
+use ruffle_render::shape_utils::DistilledShape;
 use std::cell::{Ref, RefMut};
 use std::sync::Arc;
 
@@ -36,6 +37,9 @@ pub struct GraphicData<'gc> {
     avm2_object: Option<Avm2Object<'gc>>,
     #[collect(require_static)]
     drawing: Option<Drawing>,
+    /// The shape registered again for the matrix its non-scaling strokes were sized for.
+    #[collect(require_static)]
+    scaled_render_handle: Option<(Matrix, ShapeHandle)>,
 }
 
 impl<'gc> Graphic<'gc> {
@@ -46,16 +50,20 @@ impl<'gc> Graphic<'gc> {
         movie: Arc<SwfMovie>,
     ) -> Self {
         let library = context.library.library_for_movie(movie.clone()).unwrap();
+        let shape: DistilledShape = (&swf_shape).into();
+        let has_non_scaling_strokes = shape.has_non_scaling_strokes();
+        let render_handle = context.renderer.register_shape(
+            shape,
+            &MovieLibrarySource {
+                library,
+                gc_context: context.gc_context,
+            },
+        );
         let static_data = GraphicStatic {
             id: swf_shape.id,
             bounds: swf_shape.shape_bounds.clone(),
-            render_handle: Some(context.renderer.register_shape(
-                (&swf_shape).into(),
-                &MovieLibrarySource {
-                    library,
-                    gc_context: context.gc_context,
-                },
-            )),
+            render_handle: Some(render_handle),
+            has_non_scaling_strokes,
             shape: swf_shape,
             movie,
         };
@@ -67,6 +75,7 @@ impl<'gc> Graphic<'gc> {
                 static_data: gc_arena::Gc::new(context.gc_context, static_data),
                 avm2_object: None,
                 drawing: None,
+                scaled_render_handle: None,
             },
         ))
     }
@@ -77,6 +86,7 @@ impl<'gc> Graphic<'gc> {
             id: 0,
             bounds: Default::default(),
             render_handle: None,
+            has_non_scaling_strokes: false,
             shape: swf::Shape {
                 version: 32,
                 id: 0,
@@ -100,10 +110,55 @@ impl<'gc> Graphic<'gc> {
                 static_data: gc_arena::Gc::new(context.gc_context, static_data),
                 avm2_object: None,
                 drawing: Some(drawing),
+                scaled_render_handle: None,
             },
         ))
     }
 
+    /// Returns the render handle to draw this graphic's shape with the given matrix.
+    ///
+    /// Non-scaling strokes are sized for the matrix they're drawn with, so shapes that have any
+    /// are registered again whenever their scale or rotation changes.
+    fn render_handle(
+        self,
+        context: &mut RenderContext<'_, 'gc>,
+        matrix: &Matrix,
+    ) -> Option<ShapeHandle> {
+        let static_data = self.0.read().static_data;
+        if !static_data.has_non_scaling_strokes {
+            return static_data.render_handle.clone();
+        }
+
+        let line_scale_matrix = Matrix {
+            tx: Twips::ZERO,
+            ty: Twips::ZERO,
+            ..*matrix
+        };
+        if let Some((scaled_matrix, handle)) = &self.0.read().scaled_render_handle {
+            if *scaled_matrix == line_scale_matrix {
+                return Some(handle.clone());
+            }
+        }
+
+        let library = context
+            .library
+            .library_for_movie(static_data.movie.clone())?;
+        let shape = DistilledShape {
+            line_scale_matrix: Some(line_scale_matrix),
+            ..(&static_data.shape).into()
+        };
+        let handle = context.renderer.register_shape(
+            shape,
+            &MovieLibrarySource {
+                library,
+                gc_context: context.gc_context,
+            },
+        );
+        self.0.write(context.gc_context).scaled_render_handle =
+            Some((line_scale_matrix, handle.clone()));
+        Some(handle)
+    }
+
     pub fn drawing(&self, gc_context: &Mutation<'gc>) -> RefMut<'_, Drawing> {
         RefMut::map(self.0.write(gc_context), |w| {
             w.drawing.get_or_insert_with(Drawing::new)
@@ -140,6 +195,14 @@ impl<'gc> TDisplayObject<'gc> for Graphic<'gc> {
         }
     }
 
+    fn self_edge_bounds(&self) -> Rectangle<Twips> {
+        if let Some(drawing) = &self.0.read().drawing {
+            drawing.edge_bounds().clone()
+        } else {
+            self.0.read().static_data.shape.edge_bounds.clone()
+        }
+    }
+
     fn construct_frame(&self, context: &mut UpdateContext<'_, 'gc>) {
         if context.is_action_script_3() && matches!(self.object2(), Avm2Value::Null) {
             let shape_constr = context.avm2.classes().shape;
@@ -170,7 +233,9 @@ impl<'gc> TDisplayObject<'gc> for Graphic<'gc> {
             .library_for_movie_mut(self.movie())
             .get_graphic(id)
         {
-            self.0.write(context.gc_context).static_data = new_graphic.0.read().static_data;
+            let mut write = self.0.write(context.gc_context);
+            write.static_data = new_graphic.0.read().static_data;
+            write.scaled_render_handle = None;
         } else {
             tracing::warn!("PlaceObject: expected Graphic at character ID {}", id);
         }
@@ -189,10 +254,12 @@ impl<'gc> TDisplayObject<'gc> for Graphic<'gc> {
 
         if let Some(drawing) = &self.0.read().drawing {
             drawing.render(context);
-        } else if let Some(render_handle) = self.0.read().static_data.render_handle.clone() {
-            context
-                .commands
-                .render_shape(render_handle, context.transform_stack.transform())
+            return;
+        }
+
+        let transform = context.transform_stack.transform();
+        if let Some(render_handle) = self.render_handle(context, &transform.matrix) {
+            context.commands.render_shape(render_handle, transform)
         }
     }
 
@@ -205,6 +272,7 @@ impl<'gc> TDisplayObject<'gc> for Graphic<'gc> {
         // Transform point to local coordinates and test.
         if (!options.contains(HitTestOptions::SKIP_INVISIBLE) || self.visible())
             && self.world_bounds().contains(point)
+            && self.hit_test_scroll_rect(point)
         {
             let Some(local_matrix) = self.global_to_local_matrix() else {
                 return false;
@@ -272,6 +340,7 @@ struct GraphicStatic {
     id: CharacterId,
     shape: swf::Shape,
     render_handle: Option<ShapeHandle>,
+    has_non_scaling_strokes: bool,
     bounds: Rectangle<Twips>,
     movie: Arc<SwfMovie>,
 }
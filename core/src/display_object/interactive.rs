@@ -14,7 +14,7 @@ use crate::display_object::stage::Stage;
 use crate::display_object::{
     DisplayObject, DisplayObjectBase, TDisplayObject, TDisplayObjectContainer,
 };
-use crate::events::{ClipEvent, ClipEventResult};
+use crate::events::{ClipEvent, ClipEventResult, MouseButton};
 use bitflags::bitflags;
 use gc_arena::{Collect, Mutation};
 use instant::Instant;
@@ -89,6 +89,11 @@ pub struct InteractiveObjectBase<'gc> {
     /// display object.
     #[collect(require_static)]
     last_click: Option<Instant>,
+
+    /// Whether a yellow rectangle is drawn around this object when it has focus, as set by the
+    /// AVM1 `_focusrect` property. `None` means the stage's setting applies.
+    #[collect(require_static)]
+    focus_rect: Option<bool>,
 }
 
 impl<'gc> Default for InteractiveObjectBase<'gc> {
@@ -98,6 +103,7 @@ impl<'gc> Default for InteractiveObjectBase<'gc> {
             flags: InteractiveObjectFlags::MOUSE_ENABLED,
             context_menu: Avm2Value::Null,
             last_click: None,
+            focus_rect: None,
         }
     }
 }
@@ -159,6 +165,14 @@ pub trait TInteractiveObject<'gc>:
         self.raw_interactive_mut(mc).context_menu = value;
     }
 
+    fn focus_rect(self) -> Option<bool> {
+        self.raw_interactive().focus_rect
+    }
+
+    fn set_focus_rect(self, mc: &Mutation<'gc>, value: Option<bool>) {
+        self.raw_interactive_mut(mc).focus_rect = value;
+    }
+
     /// Filter the incoming clip event.
     ///
     /// If this returns `Handled`, then the rest of the event handling
@@ -406,6 +420,57 @@ pub trait TInteractiveObject<'gc>:
 
                 ClipEventResult::Handled
             }
+            ClipEvent::SecondaryPress { button } => {
+                let event_type = match button {
+                    MouseButton::Right => "rightMouseDown",
+                    MouseButton::Middle => "middleMouseDown",
+                    _ => return ClipEventResult::NotHandled,
+                };
+                let avm2_event = Avm2EventObject::mouse_event(
+                    &mut activation,
+                    event_type,
+                    self.as_displayobject(),
+                    None,
+                    0,
+                    true,
+                );
+
+                Avm2::dispatch_event(&mut activation.context, avm2_event, target);
+
+                ClipEventResult::Handled
+            }
+            ClipEvent::SecondaryRelease { button, click } => {
+                let (up_type, click_type) = match button {
+                    MouseButton::Right => ("rightMouseUp", "rightClick"),
+                    MouseButton::Middle => ("middleMouseUp", "middleClick"),
+                    _ => return ClipEventResult::NotHandled,
+                };
+                let avm2_event = Avm2EventObject::mouse_event(
+                    &mut activation,
+                    up_type,
+                    self.as_displayobject(),
+                    None,
+                    0,
+                    true,
+                );
+
+                Avm2::dispatch_event(&mut activation.context, avm2_event, target);
+
+                if click {
+                    let avm2_event = Avm2EventObject::mouse_event(
+                        &mut activation,
+                        click_type,
+                        self.as_displayobject(),
+                        None,
+                        0,
+                        true,
+                    );
+
+                    Avm2::dispatch_event(&mut activation.context, avm2_event, target);
+                }
+
+                ClipEventResult::Handled
+            }
             ClipEvent::MouseWheel { delta } => {
                 let avm2_event = Avm2EventObject::mouse_event(
                     &mut activation,
@@ -153,6 +153,12 @@ impl<'gc> TDisplayObject<'gc> for MorphShape<'gc> {
         frame.bounds.clone()
     }
 
+    fn self_edge_bounds(&self) -> Rectangle<Twips> {
+        let this = self.0.read();
+        let frame = this.static_data.get_frame(this.ratio);
+        frame.edge_bounds.clone()
+    }
+
     fn hit_test_shape(
         &self,
         _context: &mut UpdateContext<'_, 'gc>,
@@ -161,6 +167,7 @@ impl<'gc> TDisplayObject<'gc> for MorphShape<'gc> {
     ) -> bool {
         if (!options.contains(HitTestOptions::SKIP_INVISIBLE) || self.visible())
             && self.world_bounds().contains(point)
+            && self.hit_test_scroll_rect(point)
         {
             if let Some(frame) = self.0.read().static_data.frames.borrow().get(&self.ratio()) {
                 let Some(local_matrix) = self.global_to_local_matrix() else {
@@ -189,6 +196,7 @@ struct Frame {
     shape_handle: Option<ShapeHandle>,
     shape: swf::Shape,
     bounds: Rectangle<Twips>,
+    edge_bounds: Rectangle<Twips>,
 }
 
 /// Static data shared between all instances of a morph shape.
@@ -357,12 +365,15 @@ impl MorphShapeStatic {
             line_styles,
         };
 
-        let bounds = ruffle_render::shape_utils::calculate_shape_bounds(&shape);
+        // The bounds including strokes are interpolated like everything else, but the edges
+        // alone are measured exactly, as `DefineMorphShape` doesn't store their bounds.
+        let bounds = lerp_bounds(&self.start.shape_bounds, &self.end.shape_bounds, a, b);
+        let edge_bounds = ruffle_render::shape_utils::calculate_shape_bounds(&shape);
         let shape = swf::Shape {
             version: 4,
             id: 0,
             shape_bounds: bounds.clone(),
-            edge_bounds: bounds.clone(),
+            edge_bounds: edge_bounds.clone(),
             flags: swf::ShapeFlag::HAS_SCALING_STROKES,
             styles,
             shape,
@@ -372,6 +383,7 @@ impl MorphShapeStatic {
             shape_handle: None,
             shape,
             bounds,
+            edge_bounds,
         }
     }
 
@@ -418,6 +430,20 @@ fn lerp_twips(start: Twips, end: Twips, a: f32, b: f32) -> Twips {
     Twips::new((start.get() as f32 * a + end.get() as f32 * b) as i32)
 }
 
+fn lerp_bounds(
+    start: &Rectangle<Twips>,
+    end: &Rectangle<Twips>,
+    a: f32,
+    b: f32,
+) -> Rectangle<Twips> {
+    Rectangle {
+        x_min: lerp_twips(start.x_min, end.x_min, a, b),
+        y_min: lerp_twips(start.y_min, end.y_min, a, b),
+        x_max: lerp_twips(start.x_max, end.x_max, a, b),
+        y_max: lerp_twips(start.y_max, end.y_max, a, b),
+    }
+}
+
 fn lerp_fill(start: &swf::FillStyle, end: &swf::FillStyle, a: f32, b: f32) -> swf::FillStyle {
     use swf::FillStyle;
     match (start, end) {
@@ -170,6 +170,12 @@ pub struct MovieClipData<'gc> {
     /// A DisplayObject (doesn't need to be visible) to use for hit tests instead of this clip.
     hit_area: Option<DisplayObject<'gc>>,
 
+    /// How many Sprites use this clip as their `hitArea`.
+    ///
+    /// Hit areas only define where their owners can be clicked, and never
+    /// receive mouse events themselves.
+    hit_area_owners: u32,
+
     /// Force enable button mode, which causes all mouse-related events to
     /// trigger on this clip rather than any input-eligible children.
     button_mode: bool,
@@ -217,6 +223,7 @@ impl<'gc> MovieClip<'gc> {
                 queued_goto_frame: None,
                 drop_target: None,
                 hit_area: None,
+                hit_area_owners: 0,
 
                 #[cfg(feature = "timeline_debug")]
                 tag_frame_boundaries: Default::default(),
@@ -257,6 +264,7 @@ impl<'gc> MovieClip<'gc> {
                 queued_goto_frame: None,
                 drop_target: None,
                 hit_area: None,
+                hit_area_owners: 0,
 
                 #[cfg(feature = "timeline_debug")]
                 tag_frame_boundaries: Default::default(),
@@ -301,6 +309,7 @@ impl<'gc> MovieClip<'gc> {
                 queued_goto_frame: None,
                 drop_target: None,
                 hit_area: None,
+                hit_area_owners: 0,
 
                 #[cfg(feature = "timeline_debug")]
                 tag_frame_boundaries: Default::default(),
@@ -367,6 +376,7 @@ impl<'gc> MovieClip<'gc> {
                 queued_goto_frame: None,
                 drop_target: None,
                 hit_area: None,
+                hit_area_owners: 0,
 
                 #[cfg(feature = "timeline_debug")]
                 tag_frame_boundaries: Default::default(),
@@ -700,7 +710,11 @@ impl<'gc> MovieClip<'gc> {
         };
 
         let result = if sub_preload_done {
-            tag_utils::decode_tags(&mut reader, tag_callback)
+            tag_utils::decode_tags_with_strictness(
+                &mut reader,
+                data.movie.strictness(),
+                tag_callback,
+            )
         } else {
             Ok(true)
         };
@@ -1598,6 +1612,63 @@ impl<'gc> MovieClip<'gc> {
         }
     }
 
+    /// Resolves the class named by a PlaceObject tag, in this clip's domain.
+    fn place_object_class(
+        self,
+        context: &mut UpdateContext<'_, 'gc>,
+        place_object: &swf::PlaceObject,
+    ) -> Option<Avm2ClassObject<'gc>> {
+        let class_name = place_object.class_name?;
+        let movie = self.movie();
+        let mut activation = Avm2Activation::from_nothing(context.reborrow());
+        let class_name = AvmString::new(
+            activation.context.gc_context,
+            class_name.decode(SwfStr::encoding_for_version(movie.version())),
+        );
+        let name = Avm2QName::from_qualified_name(class_name, &mut activation);
+        let domain = activation
+            .context
+            .library
+            .library_for_movie_mut(movie)
+            .avm2_domain();
+        let class = domain
+            .get_defined_value(&mut activation, name)
+            .ok()
+            .and_then(|v| v.as_object())
+            .and_then(|o| o.as_class_object());
+        if class.is_none() {
+            tracing::warn!("PlaceObject refers to unknown class {}", class_name);
+        }
+        class
+    }
+
+    /// Instantiate a child of the class named by a PlaceObject tag, at a given depth.
+    ///
+    /// Only classes linked to a symbol of this movie can be placed this way.
+    fn instantiate_child_of_class(
+        self,
+        context: &mut UpdateContext<'_, 'gc>,
+        depth: Depth,
+        place_object: &swf::PlaceObject,
+    ) -> Option<DisplayObject<'gc>> {
+        if !self.movie().is_action_script_3() {
+            return None;
+        }
+        let class = self.place_object_class(context, place_object)?;
+        match context.library.avm2_class_registry().class_symbol(class) {
+            Some((movie, id)) if Arc::ptr_eq(&movie, &self.movie()) => {
+                self.instantiate_child(context, id, depth, place_object)
+            }
+            _ => {
+                tracing::warn!(
+                    "PlaceObject refers to class {:?}, which isn't linked to a symbol",
+                    class.inner_class_definition().read().name()
+                );
+                None
+            }
+        }
+    }
+
     /// Instantiate a given child object on the timeline at a given depth.
     fn instantiate_child(
         self,
@@ -1645,7 +1716,29 @@ impl<'gc> MovieClip<'gc> {
                                 .collect(),
                         );
                     }
-                    // TODO: Missing PlaceObject property: amf_data
+                    if movie.is_action_script_3() {
+                        if let Some(class) = self.place_object_class(context, place_object) {
+                            if let Some(clip) = child.as_movie_clip() {
+                                clip.set_avm2_class(context.gc_context, Some(class));
+                            } else if let Some(button) = child.as_avm2_button() {
+                                button.set_avm2_class(context.gc_context, class);
+                            } else if let Some(bitmap) = child.as_bitmap() {
+                                bitmap.set_avm2_bitmapdata_class(context, class);
+                            }
+                        }
+                        if let Some(amf_data) = place_object.amf_data {
+                            let mut activation = Avm2Activation::from_nothing(context.reborrow());
+                            match crate::avm2::deserialize_amf3_bytes(&mut activation, amf_data) {
+                                Ok(Avm2Value::Object(meta_data)) => {
+                                    child.set_meta_data(context.gc_context, Some(meta_data));
+                                }
+                                Ok(_) => {}
+                                Err(e) => {
+                                    tracing::warn!("Unable to read PlaceObject metadata: {e:?}")
+                                }
+                            }
+                        }
+                    }
 
                     // Run first frame.
                     child.post_instantiation(context, None, Instantiator::Movie, false);
@@ -1949,6 +2042,15 @@ impl<'gc> MovieClip<'gc> {
                         child.set_place_frame(context.gc_context, params.frame);
                     }
                 }
+                (PlaceObjectAction::PlaceClass, _, _) => {
+                    if let Some(child) = clip.instantiate_child_of_class(
+                        context,
+                        params.depth(),
+                        &params.place_object,
+                    ) {
+                        child.set_place_frame(context.gc_context, params.frame);
+                    }
+                }
                 _ => {
                     tracing::error!(
                         "Unexpected PlaceObject during goto: {:?}",
@@ -2018,6 +2120,19 @@ impl<'gc> MovieClip<'gc> {
         if self.0.read().object.is_none() {
             let avm1_constructor = self.0.read().get_registered_avm1_constructor(context);
 
+            // `onClipEvent(initialize)` handlers run before anything else, and
+            // `onClipEvent(construct)` handlers run just before the class constructor.
+            let mut initialize_events = Vec::new();
+            let mut construct_events = Vec::new();
+            for event_handler in self.0.read().clip_event_handlers().iter() {
+                if event_handler.events.contains(ClipEventFlag::INITIALIZE) {
+                    initialize_events.push(event_handler.action_data.clone());
+                }
+                if event_handler.events.contains(ClipEventFlag::CONSTRUCT) {
+                    construct_events.push(event_handler.action_data.clone());
+                }
+            }
+
             // If we are running within the AVM, this must be an immediate action.
             // If we are not, then this must be queued to be ran first-thing
             if let Some(constructor) = avm1_constructor.filter(|_| instantiated_by.is_avm()) {
@@ -2056,6 +2171,10 @@ impl<'gc> MovieClip<'gc> {
                             }
                         }
                     }
+                    for event in initialize_events.into_iter().chain(construct_events) {
+                        let _ =
+                            activation.run_child_frame_for_action("[Actions]", self.into(), event);
+                    }
                     let _ = constructor.construct_on_existing(&mut activation, object, &[]);
                 }
 
@@ -2088,33 +2207,22 @@ impl<'gc> MovieClip<'gc> {
                 }
             }
 
-            let mut events = Vec::new();
-
-            for event_handler in self
-                .0
-                .write(context.gc_context)
-                .clip_event_handlers()
-                .iter()
-            {
-                if event_handler.events.contains(ClipEventFlag::INITIALIZE) {
-                    context.action_queue.queue_action(
-                        self.into(),
-                        ActionType::Initialize {
-                            bytecode: event_handler.action_data.clone(),
-                        },
-                        false,
-                    );
-                }
-                if event_handler.events.contains(ClipEventFlag::CONSTRUCT) {
-                    events.push(event_handler.action_data.clone());
-                }
+            for bytecode in initialize_events {
+                context.action_queue.queue_action(
+                    self.into(),
+                    ActionType::Initialize { bytecode },
+                    false,
+                );
             }
 
+            // The class constructor, if any, is looked up now, when the clip is placed. Classes
+            // registered later only apply to clips placed after that. `onLoad` is queued with a
+            // lower priority, so it runs once the constructor is done.
             context.action_queue.queue_action(
                 self.into(),
                 ActionType::Construct {
                     constructor: avm1_constructor,
-                    events,
+                    events: construct_events,
                 },
                 false,
             );
@@ -2190,7 +2298,7 @@ impl<'gc> MovieClip<'gc> {
                     e,
                     class_object
                         .try_inner_class_definition()
-                        .map(|c| c.read().name().to_qualified_name(context.gc_context))
+                        .map(|c| c.read().qualified_name())
                         .unwrap_or_else(|_| "[BorrowError!]".into())
                 );
             }
@@ -2337,7 +2445,19 @@ impl<'gc> MovieClip<'gc> {
         context: &mut UpdateContext<'_, 'gc>,
         hit_area: Option<DisplayObject<'gc>>,
     ) {
-        self.0.write(context.gc_context).hit_area = hit_area;
+        let old_hit_area =
+            std::mem::replace(&mut self.0.write(context.gc_context).hit_area, hit_area);
+        if let Some(old_hit_area) = old_hit_area.and_then(|o| o.as_movie_clip()) {
+            let mut write = old_hit_area.0.write(context.gc_context);
+            write.hit_area_owners = write.hit_area_owners.saturating_sub(1);
+        }
+        if let Some(hit_area) = hit_area.and_then(|o| o.as_movie_clip()) {
+            hit_area.0.write(context.gc_context).hit_area_owners += 1;
+        }
+    }
+
+    fn is_hit_area(self) -> bool {
+        self.0.read().hit_area_owners > 0
     }
 
     pub fn tag_stream_len(&self) -> usize {
@@ -2769,6 +2889,10 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
         self.0.read().drawing.self_bounds().clone()
     }
 
+    fn self_edge_bounds(&self) -> Rectangle<Twips> {
+        self.0.read().drawing.edge_bounds().clone()
+    }
+
     fn hit_test_shape(
         &self,
         context: &mut UpdateContext<'_, 'gc>,
@@ -2786,7 +2910,7 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
             return false;
         }
 
-        if self.world_bounds().contains(point) {
+        if self.world_bounds().contains(point) && self.hit_test_scroll_rect(point) {
             let Some(local_matrix) = self.global_to_local_matrix() else {
                 return false;
             };
@@ -3087,6 +3211,10 @@ impl<'gc> TInteractiveObject<'gc> for MovieClip<'gc> {
                 return None;
             };
 
+            if !self.hit_test_scroll_rect(point) {
+                return None;
+            }
+
             if let Some(masker) = self.masker() {
                 // FIXME - should this really use `SKIP_INVISIBLE`? Avm2 doesn't.
                 if !masker.hit_test_shape(context, point, HitTestOptions::SKIP_INVISIBLE) {
@@ -3175,6 +3303,10 @@ impl<'gc> TInteractiveObject<'gc> for MovieClip<'gc> {
                 return Avm2MousePick::Miss;
             };
 
+            if self.is_hit_area() || !self.hit_test_scroll_rect(point) {
+                return Avm2MousePick::Miss;
+            }
+
             if let Some(masker) = self.masker() {
                 if !masker.hit_test_shape(context, point, HitTestOptions::empty()) {
                     return Avm2MousePick::Miss;
@@ -3208,6 +3340,10 @@ impl<'gc> TInteractiveObject<'gc> for MovieClip<'gc> {
                 .rev()
                 .peekable();
 
+            // A hit area replaces this clip's own content (its graphics and non-interactive
+            // children) when deciding whether the clip itself is under the mouse.
+            let hit_area = self.hit_area();
+
             // Interactive children run first, followed by non-interactive children.
             // Depth is considered within each group.
 
@@ -3228,9 +3364,7 @@ impl<'gc> TInteractiveObject<'gc> for MovieClip<'gc> {
 
                 let mut res = if let Some(child) = child.as_interactive() {
                     child.mouse_pick_avm2(context, point, require_button_mode)
-                } else if child.as_interactive().is_none()
-                    && child.hit_test_shape(context, point, options)
-                {
+                } else if hit_area.is_none() && child.hit_test_shape(context, point, options) {
                     if self.mouse_enabled() {
                         Avm2MousePick::Hit(this)
                     } else {
@@ -3276,6 +3410,19 @@ impl<'gc> TInteractiveObject<'gc> for MovieClip<'gc> {
                 return propagate.combine_with_parent((*self).into());
             }
 
+            if let Some(hit_area) = hit_area {
+                // Hit areas are tested even while invisible, as they're usually hidden.
+                return if hit_area.hit_test_shape(context, point, HitTestOptions::empty()) {
+                    if self.mouse_enabled() {
+                        Avm2MousePick::Hit((*self).into())
+                    } else {
+                        Avm2MousePick::PropagateToParent
+                    }
+                } else {
+                    Avm2MousePick::Miss
+                };
+            }
+
             // Check drawing, because this selects the current clip, it must have mouse enabled
             if self.world_bounds().contains(point) {
                 let point = local_matrix * point;
@@ -3577,7 +3724,7 @@ impl<'gc, 'a> MovieClipData<'gc> {
             .library_for_movie_mut(self.movie())
             .jpeg_tables();
         let jpeg_data = ruffle_render::utils::glue_tables_to_jpeg(jpeg_data, jpeg_tables);
-        let bitmap = ruffle_render::utils::decode_define_bits_jpeg(&jpeg_data, None)?;
+        let bitmap = ruffle_render::utils::decode_define_bits_jpeg(&jpeg_data, None, 0.0)?;
         let bitmap = Bitmap::new(context, id, bitmap)?;
         context
             .library
@@ -3594,7 +3741,7 @@ impl<'gc, 'a> MovieClipData<'gc> {
     ) -> Result<(), Error> {
         let id = reader.read_u16()?;
         let jpeg_data = reader.read_slice_to_end();
-        let bitmap = ruffle_render::utils::decode_define_bits_jpeg(jpeg_data, None)?;
+        let bitmap = ruffle_render::utils::decode_define_bits_jpeg(jpeg_data, None, 0.0)?;
         let bitmap = Bitmap::new(context, id, bitmap)?;
         context
             .library
@@ -3612,12 +3759,16 @@ impl<'gc, 'a> MovieClipData<'gc> {
     ) -> Result<(), Error> {
         let id = reader.read_u16()?;
         let jpeg_len = reader.read_u32()? as usize;
-        if version == 4 {
-            let _deblocking = reader.read_u16()?;
-        }
+        // An 8.8 fixed point fraction.
+        let deblocking = if version == 4 {
+            f32::from(reader.read_u16()?) / 256.0
+        } else {
+            0.0
+        };
         let jpeg_data = reader.read_slice(jpeg_len)?;
         let alpha_data = reader.read_slice_to_end();
-        let bitmap = ruffle_render::utils::decode_define_bits_jpeg(jpeg_data, Some(alpha_data))?;
+        let bitmap =
+            ruffle_render::utils::decode_define_bits_jpeg(jpeg_data, Some(alpha_data), deblocking)?;
         let bitmap = Bitmap::new(context, id, bitmap)?;
         context
             .library
@@ -4221,6 +4372,9 @@ impl<'gc, 'a> MovieClip<'gc> {
             PlaceObjectAction::Place(id) => {
                 self.instantiate_child(context, id, place_object.depth.into(), &place_object);
             }
+            PlaceObjectAction::PlaceClass => {
+                self.instantiate_child_of_class(context, place_object.depth.into(), &place_object);
+            }
             PlaceObjectAction::Replace(id) => {
                 if let Some(child) = self.child_by_depth(place_object.depth.into()) {
                     child.replace_with(context, id);
@@ -4537,7 +4691,9 @@ impl<'a> GotoPlaceObject<'a> {
         version: u8,
     ) -> Self {
         if is_rewind {
-            if let swf::PlaceObjectAction::Place(_) = place_object.action {
+            if let swf::PlaceObjectAction::Place(_) | swf::PlaceObjectAction::PlaceClass =
+                place_object.action
+            {
                 if place_object.matrix.is_none() {
                     place_object.matrix = Some(Default::default());
                 }
@@ -4560,7 +4716,7 @@ impl<'a> GotoPlaceObject<'a> {
                     place_object.filters = Some(Default::default());
                 }
                 // Purposely omitted properties:
-                // name, clip_depth, clip_actions, amf_data
+                // name, clip_depth, clip_actions, class_name, amf_data
                 // These properties are only set on initial placement in `MovieClip::instantiate_child`
                 // and can not be modified by subsequent PlaceObject tags.
                 // Also, is_visible flag persists during rewind unlike all other properties.
@@ -4591,6 +4747,9 @@ impl<'a> GotoPlaceObject<'a> {
             }
             (_, new) => {
                 cur_place.action = new;
+                // The class to instantiate and its metadata belong to the placement.
+                cur_place.class_name = next_place.class_name.take();
+                cur_place.amf_data = next_place.amf_data.take();
                 self.frame = next.frame;
             }
         };
@@ -4619,7 +4778,7 @@ impl<'a> GotoPlaceObject<'a> {
             cur_place.filters = next_place.filters.take();
         }
         // Purposely omitted properties:
-        // name, clip_depth, clip_actions, amf_data
+        // name, clip_depth, clip_actions
         // These properties are only set on initial placement in `MovieClip::instantiate_child`
         // and can not be modified by subsequent PlaceObject tags.
     }
@@ -4778,3 +4937,117 @@ impl ClipEventHandler {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::GotoPlaceObject;
+    use crate::display_object::{TDisplayObject, TDisplayObjectContainer};
+    use crate::limits::ExecutionLimit;
+    use crate::player::PlayerBuilder;
+    use crate::tag_utils::SwfMovie;
+    use swf::{Header, PlaceObject, PlaceObjectAction, Sprite, Tag};
+
+    fn place_object(action: PlaceObjectAction) -> PlaceObject<'static> {
+        PlaceObject {
+            version: 3,
+            action,
+            depth: 1,
+            matrix: None,
+            color_transform: None,
+            ratio: None,
+            name: None,
+            clip_depth: None,
+            class_name: None,
+            filters: None,
+            background_color: None,
+            blend_mode: None,
+            clip_actions: None,
+            has_image: false,
+            is_bitmap_cached: None,
+            is_visible: None,
+            amf_data: None,
+        }
+    }
+
+    /// Whether a clip is visible after being placed by a PlaceObject3 tag that hides it.
+    fn hidden_placement_is_visible(swf_version: u8) -> bool {
+        let mut place = place_object(PlaceObjectAction::Place(1));
+        place.is_visible = Some(false);
+        let tags = [
+            Tag::DefineSprite(Sprite {
+                id: 1,
+                num_frames: 1,
+                tags: vec![Tag::ShowFrame],
+            }),
+            Tag::PlaceObject(Box::new(place)),
+            Tag::ShowFrame,
+        ];
+        let header = Header {
+            num_frames: 1,
+            ..Header::default_with_swf_version(swf_version)
+        };
+        let mut data = vec![];
+        swf::write_swf(&header, &tags, &mut data).unwrap();
+        let movie = SwfMovie::from_data(&data, "file:///test.swf".into(), None).unwrap();
+
+        let player = PlayerBuilder::new().with_movie(movie).build();
+        let mut player = player.lock().unwrap();
+        player.preload(&mut ExecutionLimit::none());
+        player.run_frame();
+        player.mutate_with_update_context(|context| {
+            let root = context.stage.root_clip().unwrap();
+            let child = root.as_container().unwrap().child_by_depth(1).unwrap();
+            child.visible()
+        })
+    }
+
+    #[test]
+    fn visible_flag_applies_before_swf_11() {
+        assert!(!hidden_placement_is_visible(10));
+        assert!(!hidden_placement_is_visible(11));
+    }
+
+    #[test]
+    fn goto_placements_keep_their_class() {
+        let mut goto = GotoPlaceObject::new(
+            1,
+            place_object(PlaceObjectAction::Place(1)),
+            false,
+            0,
+            0,
+            10,
+        );
+
+        let mut class_place = place_object(PlaceObjectAction::PlaceClass);
+        class_place.class_name = Some("Hat".into());
+        class_place.amf_data = Some(&[1]);
+        goto.merge(&mut GotoPlaceObject::new(2, class_place, false, 1, 0, 10));
+        assert_eq!(goto.place_object.action, PlaceObjectAction::PlaceClass);
+        assert_eq!(goto.place_object.class_name, Some("Hat".into()));
+        assert_eq!(goto.place_object.amf_data, Some(&[1][..]));
+
+        // Modifying the placed object doesn't change its class.
+        goto.merge(&mut GotoPlaceObject::new(
+            3,
+            place_object(PlaceObjectAction::Modify),
+            false,
+            2,
+            0,
+            10,
+        ));
+        assert_eq!(goto.place_object.action, PlaceObjectAction::PlaceClass);
+        assert_eq!(goto.place_object.class_name, Some("Hat".into()));
+
+        // Placing a character replaces the class.
+        goto.merge(&mut GotoPlaceObject::new(
+            4,
+            place_object(PlaceObjectAction::Place(2)),
+            false,
+            3,
+            0,
+            10,
+        ));
+        assert_eq!(goto.place_object.action, PlaceObjectAction::Place(2));
+        assert_eq!(goto.place_object.class_name, None);
+    }
+}
@@ -19,13 +19,14 @@ use crate::display_object::{
     render_base, DisplayObject, DisplayObjectBase, DisplayObjectPtr, TDisplayObject,
 };
 use crate::events::{ClipEvent, ClipEventResult};
+use crate::player::StageArea;
 use crate::prelude::*;
 use crate::string::{FromWStr, WStr};
 use crate::tag_utils::SwfMovie;
 use crate::vminterface::Instantiator;
 use bitflags::bitflags;
 use gc_arena::{Collect, GcCell, Mutation};
-use ruffle_render::backend::ViewportDimensions;
+use ruffle_render::backend::{RenderBackend, ViewportDimensions};
 use ruffle_render::commands::CommandHandler;
 use ruffle_render::quality::StageQuality;
 use ruffle_render::transform::Transform;
@@ -85,6 +86,13 @@ pub struct StageData<'gc> {
     #[collect(require_static)]
     stage_size: (u32, u32),
 
+    /// The width and height assigned to `Stage.stageWidth` and `stageHeight`.
+    ///
+    /// Flash doesn't resize anything when these are set, but reports the new values until the
+    /// stage is next resized.
+    #[collect(require_static)]
+    stage_size_override: (Option<u32>, Option<u32>),
+
     /// The scale mode of the stage.
     #[collect(require_static)]
     scale_mode: StageScaleMode,
@@ -148,6 +156,28 @@ pub struct StageData<'gc> {
     /// identity matrix unless explicitly set from ActionScript)
     #[collect(require_static)]
     viewport_matrix: Matrix,
+
+    /// The part of the viewport this stage is drawn into.
+    ///
+    /// This is only set for additional stages hosted by the same player;
+    /// the main stage always covers the whole viewport.
+    #[collect(require_static)]
+    viewport_area: Option<StageArea>,
+}
+
+impl StageData<'_> {
+    /// The size of the area of the viewport this stage is drawn into.
+    fn viewport_size(&self, renderer: &dyn RenderBackend) -> ViewportDimensions {
+        let dimensions = renderer.viewport_dimensions();
+        match self.viewport_area {
+            Some(area) => ViewportDimensions {
+                width: area.width,
+                height: area.height,
+                scale_factor: dimensions.scale_factor,
+            },
+            None => dimensions,
+        }
+    }
 }
 
 impl<'gc> Stage<'gc> {
@@ -164,6 +194,7 @@ impl<'gc> Stage<'gc> {
                 quality: Default::default(),
                 // This is updated in `build_matrices`
                 stage_size: (0, 0),
+                stage_size_override: (None, None),
                 scale_mode: Default::default(),
                 forced_scale_mode: false,
                 display_state: if fullscreen {
@@ -184,6 +215,7 @@ impl<'gc> Stage<'gc> {
                 stage3ds: vec![],
                 movie,
                 viewport_matrix: Matrix::IDENTITY,
+                viewport_area: None,
             },
         ));
         stage.set_is_root(gc_context, true);
@@ -219,6 +251,18 @@ impl<'gc> Stage<'gc> {
         self.0.write(gc_context).letterbox = letterbox
     }
 
+    /// The part of the viewport this stage is drawn into, if it doesn't cover all of it.
+    pub fn viewport_area(self) -> Option<StageArea> {
+        self.0.read().viewport_area
+    }
+
+    /// Restrict this stage to a part of the viewport.
+    ///
+    /// `build_matrices` must be called afterwards for this to take effect.
+    pub fn set_viewport_area(self, gc_context: &Mutation<'gc>, area: Option<StageArea>) {
+        self.0.write(gc_context).viewport_area = area;
+    }
+
     /// Get the size of the SWF file.
     pub fn movie_size(self) -> (u32, u32) {
         self.0.read().movie_size
@@ -298,10 +342,36 @@ impl<'gc> Stage<'gc> {
 
     /// Get the size of the stage.
     /// Used by AVM1 `stage.width`/`height` and AVM2 `Stage.stageWidth`/`stageHeight` properties.
-    /// If `scale_mode` is `StageScaleMode::NO_SCALE`, this returns the size of the viewport.
+    /// If `scale_mode` is `StageScaleMode::NO_SCALE`, this returns the size of the viewport,
+    /// or the size assigned by ActionScript since the viewport last changed.
     /// Otherwise, this returns the size of the SWF file.
     pub fn stage_size(self) -> (u32, u32) {
-        self.0.read().stage_size
+        let stage = self.0.read();
+        let (width, height) = stage.stage_size;
+        let (width_override, height_override) = stage.stage_size_override;
+        (
+            width_override.unwrap_or(width),
+            height_override.unwrap_or(height),
+        )
+    }
+
+    /// Assign to `Stage.stageWidth`.
+    ///
+    /// This only changes the reported width, and only when `scale_mode` is `NoScale`; in any
+    /// other scale mode the stage size is fixed to the size of the SWF file.
+    pub fn set_stage_width(self, gc_context: &Mutation<'gc>, width: u32) {
+        let mut stage = self.0.write(gc_context);
+        if stage.scale_mode == StageScaleMode::NoScale {
+            stage.stage_size_override.0 = Some(width);
+        }
+    }
+
+    /// Assign to `Stage.stageHeight`. See `set_stage_width`.
+    pub fn set_stage_height(self, gc_context: &Mutation<'gc>, height: u32) {
+        let mut stage = self.0.write(gc_context);
+        if stage.scale_mode == StageScaleMode::NoScale {
+            stage.stage_size_override.1 = Some(height);
+        }
     }
 
     /// Get the stage mode.
@@ -313,8 +383,19 @@ impl<'gc> Stage<'gc> {
     /// Set the stage scale mode.
     pub fn set_scale_mode(self, context: &mut UpdateContext<'_, 'gc>, scale_mode: StageScaleMode) {
         if !self.forced_scale_mode() {
+            let previous_mode = self.scale_mode();
+            let previous_size = self.stage_size();
             self.0.write(context.gc_context).scale_mode = scale_mode;
             self.build_matrices(context);
+
+            // `build_matrices` only notifies `NoScale` movies, but leaving `NoScale` also snaps
+            // the stage back to the size of the SWF file, which layout code needs to hear about.
+            if previous_mode == StageScaleMode::NoScale
+                && scale_mode != StageScaleMode::NoScale
+                && previous_size != self.stage_size()
+            {
+                self.fire_resize_event(context);
+            }
         }
     }
 
@@ -460,7 +541,10 @@ impl<'gc> Stage<'gc> {
         let scale_mode = stage.scale_mode;
         let align = stage.align;
         let prev_stage_size = stage.stage_size;
-        let viewport_size = context.renderer.viewport_dimensions();
+        let viewport_size = stage.viewport_size(context.renderer);
+        let (offset_x, offset_y) = stage
+            .viewport_area
+            .map_or((0.0, 0.0), |area| (area.x as f64, area.y as f64));
 
         // Update stage size based on scale mode and DPI.
         stage.stage_size = if stage.scale_mode == StageScaleMode::NoScale {
@@ -472,6 +556,9 @@ impl<'gc> Stage<'gc> {
             stage.movie_size
         };
         let stage_size_changed = prev_stage_size != stage.stage_size;
+        if stage_size_changed || scale_mode != StageScaleMode::NoScale {
+            stage.stage_size_override = (None, None);
+        }
 
         // Create view matrix to scale stage into viewport area.
         let (movie_width, movie_height) = stage.movie_size;
@@ -537,8 +624,8 @@ impl<'gc> Stage<'gc> {
             b: 0.0,
             c: 0.0,
             d: scale_y as f32,
-            tx: Twips::from_pixels(tx),
-            ty: Twips::from_pixels(ty),
+            tx: Twips::from_pixels(tx + offset_x),
+            ty: Twips::from_pixels(ty + offset_y),
         };
 
         drop(stage);
@@ -577,19 +664,22 @@ impl<'gc> Stage<'gc> {
             width: viewport_width,
             height: viewport_height,
             scale_factor: _,
-        } = context.renderer.viewport_dimensions();
+        } = self.0.read().viewport_size(context.renderer);
         let viewport_width = viewport_width as f32;
         let viewport_height = viewport_height as f32;
 
         let view_matrix = self.0.read().viewport_matrix;
+        let (offset_x, offset_y) = self
+            .viewport_area()
+            .map_or((0.0, 0.0), |area| (area.x as f64, area.y as f64));
 
         let (movie_width, movie_height) = self.0.read().movie_size;
         let movie_width = movie_width as f32 * view_matrix.a;
         let movie_height = movie_height as f32 * view_matrix.d;
 
-        let margin_left = view_matrix.tx.to_pixels() as f32;
+        let margin_left = (view_matrix.tx.to_pixels() - offset_x) as f32;
         let margin_right = viewport_width - movie_width - margin_left;
-        let margin_top = view_matrix.ty.to_pixels() as f32;
+        let margin_top = (view_matrix.ty.to_pixels() - offset_y) as f32;
         let margin_bottom = viewport_height - movie_height - margin_top;
 
         // Letterboxing only occurs in `StageScaleMode::ShowAll`, and they would only appear on the top+bottom or left+right.
@@ -602,8 +692,8 @@ impl<'gc> Stage<'gc> {
                         viewport_width,
                         margin_top,
                         0.0,
-                        Twips::default(),
-                        Twips::default(),
+                        Twips::from_pixels(offset_x),
+                        Twips::from_pixels(offset_y),
                     ),
                 );
             }
@@ -614,8 +704,8 @@ impl<'gc> Stage<'gc> {
                         viewport_width,
                         margin_bottom,
                         0.0,
-                        Twips::default(),
-                        Twips::from_pixels((viewport_height - margin_bottom) as f64),
+                        Twips::from_pixels(offset_x),
+                        Twips::from_pixels(offset_y + (viewport_height - margin_bottom) as f64),
                     ),
                 );
             }
@@ -628,8 +718,8 @@ impl<'gc> Stage<'gc> {
                         margin_left,
                         viewport_height,
                         0.0,
-                        Twips::default(),
-                        Twips::default(),
+                        Twips::from_pixels(offset_x),
+                        Twips::from_pixels(offset_y),
                     ),
                 );
             }
@@ -640,8 +730,8 @@ impl<'gc> Stage<'gc> {
                         margin_right,
                         viewport_height,
                         0.0,
-                        Twips::from_pixels((viewport_width - margin_right) as f64),
-                        Twips::default(),
+                        Twips::from_pixels(offset_x + (viewport_width - margin_right) as f64),
+                        Twips::from_pixels(offset_y),
                     ),
                 );
             }
@@ -805,6 +895,22 @@ impl<'gc> TDisplayObject<'gc> for Stage<'gc> {
     }
 
     fn render(&self, context: &mut RenderContext<'_, 'gc>) {
+        // A stage sharing the viewport with others must not draw outside of its own area.
+        let area_matrix = self.viewport_area().map(|area| {
+            Matrix::create_box(
+                area.width as f32,
+                area.height as f32,
+                0.0,
+                Twips::from_pixels(area.x as f64),
+                Twips::from_pixels(area.y as f64),
+            )
+        });
+        if let Some(area_matrix) = area_matrix {
+            context.commands.push_mask();
+            context.commands.draw_rect(Color::WHITE, area_matrix);
+            context.commands.activate_mask();
+        }
+
         context.transform_stack.push(&Transform {
             matrix: self.0.read().viewport_matrix,
             color_transform: Default::default(),
@@ -829,6 +935,12 @@ impl<'gc> TDisplayObject<'gc> for Stage<'gc> {
         }
 
         context.transform_stack.pop();
+
+        if let Some(area_matrix) = area_matrix {
+            context.commands.deactivate_mask();
+            context.commands.draw_rect(Color::WHITE, area_matrix);
+            context.commands.pop_mask();
+        }
     }
 
     fn enter_frame(&self, context: &mut UpdateContext<'_, 'gc>) {
@@ -23,7 +23,7 @@ use std::borrow::BorrowMut;
 use std::cell::{Ref, RefMut};
 use std::collections::{BTreeMap, BTreeSet};
 use std::sync::Arc;
-use swf::{CharacterId, DefineVideoStream, VideoCodec, VideoFrame};
+use swf::{CharacterId, DefineVideoStream, VideoCodec, VideoDeblocking, VideoFrame};
 
 /// A Video display object is a high-level interface to a video player.
 ///
@@ -66,6 +66,13 @@ pub struct VideoData<'gc> {
     /// treated as a keyframe regardless of it being flagged as one.
     keyframes: BTreeSet<u32>,
 
+    /// The frames that were handed to the video backend to preload.
+    ///
+    /// A SWF that is still streaming in can instantiate a video before all of
+    /// its frames are loaded, so frames that arrive later are preloaded once
+    /// they're needed for seeking. They don't have to arrive in order.
+    preloaded: BTreeSet<u32>,
+
     /// The movie whose tagstream or code created the Video object.
     movie: Arc<SwfMovie>,
 
@@ -74,9 +81,19 @@ pub struct VideoData<'gc> {
 
     /// The last decoded frame in the video stream.
     ///
+    /// The frame number is the last frame the decoder actually decoded, which
+    /// is what the next frame decodes on top of.
+    ///
     /// NOTE: This is only used for SWF-source video streams.
     #[collect(require_static)]
     decoded_frame: Option<(u32, BitmapInfo)>,
+
+    /// Whether the video is smoothed when scaled, if the stage quality allows.
+    smoothing: bool,
+
+    /// The deblocking filter the video is decoded with.
+    #[collect(require_static)]
+    deblocking: VideoDeblocking,
 }
 
 /// An optionally-instantiated video stream.
@@ -130,6 +147,8 @@ impl<'gc> Video<'gc> {
         mc: &Mutation<'gc>,
     ) -> Self {
         let size = (streamdef.width.into(), streamdef.height.into());
+        let smoothing = streamdef.is_smoothed;
+        let deblocking = streamdef.deblocking;
         let source = GcCell::new(
             mc,
             VideoSource::Swf {
@@ -146,9 +165,12 @@ impl<'gc> Video<'gc> {
                 stream: VideoStream::Uninstantiated(0),
                 object: None,
                 keyframes: BTreeSet::new(),
+                preloaded: BTreeSet::new(),
                 movie,
                 size,
                 decoded_frame: None,
+                smoothing,
+                deblocking,
             },
         ))
     }
@@ -170,9 +192,12 @@ impl<'gc> Video<'gc> {
                 stream: VideoStream::Uninstantiated(0),
                 object,
                 keyframes: BTreeSet::new(),
+                preloaded: BTreeSet::new(),
                 movie,
                 size: (width, height),
                 decoded_frame: None,
+                smoothing: false,
+                deblocking: VideoDeblocking::UseVideoPacketValue,
             },
         ))
     }
@@ -190,6 +215,48 @@ impl<'gc> Video<'gc> {
         video.source = GcCell::new(context.gc_context, VideoSource::NetStream { stream });
         video.stream = VideoStream::Uninstantiated(0);
         video.keyframes = BTreeSet::new();
+        video.preloaded = BTreeSet::new();
+        let deblocking = video.deblocking;
+        drop(video);
+
+        stream.set_deblocking(context, deblocking);
+    }
+
+    pub fn smoothing(self) -> bool {
+        self.0.read().smoothing
+    }
+
+    pub fn set_smoothing(self, mc: &Mutation<'gc>, smoothing: bool) {
+        self.0.write(mc).smoothing = smoothing;
+        self.invalidate_cached_bitmap(mc);
+    }
+
+    pub fn deblocking(self) -> VideoDeblocking {
+        self.0.read().deblocking
+    }
+
+    /// Change the deblocking filter the video is decoded with.
+    ///
+    /// Frames that were already decoded are left as they are.
+    pub fn set_deblocking(self, context: &mut UpdateContext<'_, 'gc>, deblocking: VideoDeblocking) {
+        let mut write = self.0.write(context.gc_context);
+        write.deblocking = deblocking;
+        let source = write.source;
+        let video_stream = write.stream.clone();
+        drop(write);
+
+        match (&*source.read(), video_stream) {
+            (VideoSource::Swf { .. }, VideoStream::Instantiated(video_stream)) => {
+                if let Err(e) = context
+                    .video
+                    .set_video_stream_deblocking(video_stream, deblocking)
+                {
+                    tracing::error!("Got error when changing video deblocking: {}", e);
+                }
+            }
+            (VideoSource::NetStream { stream }, _) => stream.set_deblocking(context, deblocking),
+            _ => {}
+        }
     }
 
     /// Preload frame data from an SWF.
@@ -250,6 +317,11 @@ impl<'gc> Video<'gc> {
             VideoSource::NetStream { .. } => return,
             VideoSource::Unconnected { .. } => return,
         };
+        drop(read);
+
+        // The keyframe to decode from may have streamed in since we last looked.
+        self.preload_new_frames(context);
+        let read = self.0.read();
 
         frame_id = if num_frames > 0 {
             frame_id % num_frames as u32
@@ -300,6 +372,50 @@ impl<'gc> Video<'gc> {
         }
     }
 
+    /// Preload the frames that were loaded since we last did so, noting which
+    /// of them are keyframes.
+    fn preload_new_frames(self, context: &mut UpdateContext<'_, 'gc>) {
+        let mut write = self.0.write(context.gc_context);
+        let VideoStream::Instantiated(stream) = write.stream else {
+            return;
+        };
+        let movie = write.movie.clone();
+        let source = write.source;
+        let source = source.read();
+        let VideoSource::Swf { streamdef, frames } = &*source else {
+            return;
+        };
+
+        if write.preloaded.len() == frames.len() {
+            return;
+        }
+
+        for (frame_id, (frame_start, frame_end)) in frames {
+            if !write.preloaded.insert(*frame_id) {
+                continue;
+            }
+
+            let dep = context.video.preload_video_stream_frame(
+                stream,
+                EncodedFrame {
+                    codec: streamdef.codec,
+                    data: &movie.data()[*frame_start..*frame_end],
+                    frame_id: *frame_id,
+                },
+            );
+
+            match dep {
+                Ok(d) if d.is_keyframe() => {
+                    write.keyframes.insert(*frame_id);
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    tracing::error!("Got error when pre-loading video frame: {}", e);
+                }
+            }
+        }
+    }
+
     /// Decode a single frame of video.
     ///
     /// This function makes no attempt to ensure that the proposed seek is
@@ -327,13 +443,11 @@ impl<'gc> Video<'gc> {
                         .video
                         .decode_video_stream_frame(*stream, encframe, context.renderer)
                 }
-                None => {
-                    if let Some((_old_id, old_frame)) = read.decoded_frame.clone() {
-                        Ok(old_frame)
-                    } else {
-                        Err(Error::SeekingBeforeDecoding(frame_id))
-                    }
-                }
+                // Frames missing from the stream leave the last decoded one
+                // on screen. It must stay the decoded frame, so that the
+                // missing one is decoded once it has loaded in.
+                None if read.decoded_frame.is_some() => return,
+                None => Err(Error::SeekingBeforeDecoding(frame_id)),
             },
             VideoSource::NetStream { .. } => return,
             VideoSource::Unconnected { .. } => return,
@@ -384,20 +498,19 @@ impl<'gc> TDisplayObject<'gc> for Video<'gc> {
 
         let mut write = self.0.write(context.gc_context);
         let movie = write.movie.clone();
+        let deblocking = write.deblocking;
 
-        let (stream, keyframes) = match &*write.source.read() {
-            VideoSource::Swf {
-                streamdef, frames, ..
-            } => {
+        let stream = match &*write.source.read() {
+            VideoSource::Swf { streamdef, .. } => {
                 if streamdef.codec == VideoCodec::None {
                     // No codec means no frames.
-                    (None, BTreeSet::new())
+                    None
                 } else {
                     let stream = context.video.register_video_stream(
                         streamdef.num_frames.into(),
                         (streamdef.width, streamdef.height),
                         streamdef.codec,
-                        streamdef.deblocking,
+                        deblocking,
                     );
                     if stream.is_err() {
                         tracing::error!(
@@ -407,31 +520,7 @@ impl<'gc> TDisplayObject<'gc> for Video<'gc> {
                         return;
                     }
 
-                    let stream = stream.unwrap();
-                    let mut keyframes = BTreeSet::new();
-
-                    for (frame_id, (frame_start, frame_end)) in frames {
-                        let dep = context.video.preload_video_stream_frame(
-                            stream,
-                            EncodedFrame {
-                                codec: streamdef.codec,
-                                data: &movie.data()[*frame_start..*frame_end],
-                                frame_id: *frame_id,
-                            },
-                        );
-
-                        match dep {
-                            Ok(d) if d.is_keyframe() => {
-                                keyframes.insert(*frame_id);
-                            }
-                            Ok(_) => {}
-                            Err(e) => {
-                                tracing::error!("Got error when pre-loading video frame: {}", e);
-                            }
-                        }
-                    }
-
-                    (Some(stream), keyframes)
+                    Some(stream.unwrap())
                 }
             }
             VideoSource::NetStream { .. } => return,
@@ -449,7 +538,8 @@ impl<'gc> TDisplayObject<'gc> for Video<'gc> {
         if let Some(stream) = stream {
             write.stream = VideoStream::Instantiated(stream);
         }
-        write.keyframes = keyframes;
+        write.keyframes = BTreeSet::new();
+        write.preloaded = BTreeSet::new();
 
         if write.object.is_none() && !movie.is_action_script_3() {
             let object: Avm1Object<'_> = Avm1StageObject::for_display_object(
@@ -463,6 +553,7 @@ impl<'gc> TDisplayObject<'gc> for Video<'gc> {
 
         drop(write);
 
+        self.preload_new_frames(context);
         self.seek(context, starting_seek);
 
         if !context.is_action_script_3() && run_frame {
@@ -524,18 +615,14 @@ impl<'gc> TDisplayObject<'gc> for Video<'gc> {
         let mut transform = context.transform_stack.transform();
         let bounds = self.self_bounds();
 
-        // TODO: smoothing flag should be a video property
-        let (smoothed_flag, num_frames, version, decoded_frame, codec) = match &*read.source.read()
-        {
+        let (num_frames, version, decoded_frame, codec) = match &*read.source.read() {
             VideoSource::Swf { streamdef, frames } => (
-                streamdef.is_smoothed,
                 Some(frames.len()),
                 read.movie.version(),
                 read.decoded_frame.clone().map(|df| df.1),
                 Some(streamdef.codec),
             ),
             VideoSource::NetStream { stream, .. } => (
-                false,
                 None,
                 read.movie.version(),
                 stream.last_decoded_bitmap(),
@@ -546,7 +633,7 @@ impl<'gc> TDisplayObject<'gc> for Video<'gc> {
 
         let smoothing = match (context.stage.quality(), version) {
             (StageQuality::Low, _) => false,
-            (_, 8..) => smoothed_flag,
+            (_, 8..) => read.smoothing,
             (StageQuality::Medium, _) => false,
             (StageQuality::High, _) => num_frames == Some(1),
             (_, _) => true,
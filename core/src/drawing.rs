@@ -2,6 +2,7 @@ use crate::context::RenderContext;
 use ruffle_render::backend::{RenderBackend, ShapeHandle};
 use ruffle_render::bitmap::{BitmapHandle, BitmapInfo, BitmapSize, BitmapSource};
 use ruffle_render::commands::CommandHandler;
+use ruffle_render::matrix::Matrix;
 use ruffle_render::shape_utils::{
     cubic_curve_bounds, quadratic_curve_bounds, DistilledShape, DrawCommand, DrawPath, FillRule,
 };
@@ -11,6 +12,8 @@ use swf::{FillStyle, LineStyle, Point, Rectangle, Twips};
 #[derive(Clone, Debug)]
 pub struct Drawing {
     render_handle: RefCell<Option<ShapeHandle>>,
+    /// The matrix that non-scaling strokes in `render_handle` were sized for, if it has any.
+    line_scale_matrix: Cell<Option<Matrix>>,
     shape_bounds: Rectangle<Twips>,
     edge_bounds: Rectangle<Twips>,
     dirty: Cell<bool>,
@@ -34,6 +37,7 @@ impl Drawing {
     pub fn new() -> Self {
         Self {
             render_handle: RefCell::new(None),
+            line_scale_matrix: Cell::new(None),
             shape_bounds: Default::default(),
             edge_bounds: Default::default(),
             dirty: Cell::new(false),
@@ -51,6 +55,7 @@ impl Drawing {
     pub fn from_swf_shape(shape: &swf::Shape) -> Self {
         let mut this = Self {
             render_handle: RefCell::new(None),
+            line_scale_matrix: Cell::new(None),
             shape_bounds: shape.shape_bounds.clone(),
             edge_bounds: shape.edge_bounds.clone(),
             dirty: Cell::new(true),
@@ -107,6 +112,7 @@ impl Drawing {
     pub fn copy_from(&mut self, other: &Drawing) {
         *self = Drawing {
             render_handle: RefCell::new(None),
+            line_scale_matrix: Cell::new(None),
             dirty: Cell::new(true),
             shape_bounds: other.shape_bounds.clone(),
             edge_bounds: other.edge_bounds.clone(),
@@ -239,7 +245,28 @@ impl Drawing {
         id
     }
 
-    pub fn register_or_replace(&self, renderer: &mut dyn RenderBackend) -> ShapeHandle {
+    /// Returns the render handle of this drawing, registering it again if it has changed.
+    ///
+    /// Non-scaling strokes are sized for the given matrix, so the drawing is also registered
+    /// again if it has any and the matrix's scale or rotation changes.
+    pub fn register_or_replace(
+        &self,
+        renderer: &mut dyn RenderBackend,
+        matrix: &Matrix,
+    ) -> ShapeHandle {
+        let line_scale_matrix = Matrix {
+            tx: Twips::ZERO,
+            ty: Twips::ZERO,
+            ..*matrix
+        };
+        if self
+            .line_scale_matrix
+            .get()
+            .is_some_and(|old| old != line_scale_matrix)
+        {
+            self.dirty.set(true);
+        }
+
         if self.dirty.get() || self.render_handle.borrow().is_none() {
             self.dirty.set(false);
             let mut paths = Vec::with_capacity(self.paths.len());
@@ -301,12 +328,17 @@ impl Drawing {
                 })
             }
 
-            let shape = DistilledShape {
+            let mut shape = DistilledShape {
                 paths,
                 shape_bounds: self.shape_bounds.clone(),
                 edge_bounds: self.edge_bounds.clone(),
                 id: 0,
+                line_scale_matrix: None,
             };
+            if shape.has_non_scaling_strokes() {
+                shape.line_scale_matrix = Some(line_scale_matrix);
+            }
+            self.line_scale_matrix.set(shape.line_scale_matrix);
             let handle = renderer.register_shape(shape, self);
             self.render_handle.replace(Some(handle.clone()));
             handle
@@ -319,16 +351,20 @@ impl Drawing {
     }
 
     pub fn render(&self, context: &mut RenderContext) {
-        let handle = self.register_or_replace(context.renderer);
-        context
-            .commands
-            .render_shape(handle, context.transform_stack.transform());
+        let transform = context.transform_stack.transform();
+        let handle = self.register_or_replace(context.renderer, &transform.matrix);
+        context.commands.render_shape(handle, transform);
     }
 
     pub fn self_bounds(&self) -> &Rectangle<Twips> {
         &self.shape_bounds
     }
 
+    /// The bounds of this drawing, excluding the width of any strokes.
+    pub fn edge_bounds(&self) -> &Rectangle<Twips> {
+        &self.edge_bounds
+    }
+
     pub fn hit_test(
         &self,
         point: Point<Twips>,
@@ -43,6 +43,11 @@ pub fn f64_to_wrapping_i32(n: f64) -> i32 {
     f64_to_wrapping_u32(n) as i32
 }
 
+/// Whether `n` is negative zero, which avmplus never stores as an `int` or `uint`.
+pub fn is_negative_zero(n: f64) -> bool {
+    n == 0.0 && n.is_sign_negative()
+}
+
 /// Implements the IEEE-754 "Round to nearest, ties to even" rounding rule.
 /// (e.g., both 1.5 and 2.5 will round to 2).
 /// Although this is easy to do on most architectures, Rust provides no standard
@@ -61,3 +66,64 @@ pub fn round_to_even(n: f64) -> i32 {
         i32::MIN
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{f64_to_wrapping_i32, f64_to_wrapping_u32, is_negative_zero};
+
+    /// Interesting inputs around the edges of the integer types, along with their
+    /// neighbours and fractional offsets.
+    fn boundary_values() -> Vec<f64> {
+        let edges = [
+            0.0,
+            1.0,
+            (1u64 << 28) as f64,
+            i32::MAX as f64,
+            u32::MAX as f64,
+            (1u64 << 32) as f64,
+            (1u64 << 52) as f64,
+            (1u64 << 53) as f64,
+            1e20,
+        ];
+        let mut values = vec![];
+        for edge in edges {
+            for offset in [-1.5, -1.0, -0.5, 0.0, 0.5, 1.0, 1.5] {
+                values.push(edge + offset);
+                values.push(-(edge + offset));
+            }
+        }
+        values.extend([-0.0, f64::MAX, f64::MIN, f64::MIN_POSITIVE]);
+        values
+    }
+
+    /// ECMA-262's ToUint32, done with exact integer arithmetic.
+    fn reference_to_uint32(n: f64) -> u32 {
+        if !n.is_finite() || n.abs() >= 1e30 {
+            // Every double this large is a multiple of 2^32.
+            return 0;
+        }
+        (n.trunc() as i128).rem_euclid(1 << 32) as u32
+    }
+
+    #[test]
+    fn conversions_match_reference() {
+        for n in boundary_values() {
+            let expected = reference_to_uint32(n);
+            assert_eq!(f64_to_wrapping_u32(n), expected, "ToUint32({n})");
+            assert_eq!(f64_to_wrapping_i32(n), expected as i32, "ToInt32({n})");
+        }
+        for n in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            assert_eq!(f64_to_wrapping_u32(n), 0);
+            assert_eq!(f64_to_wrapping_i32(n), 0);
+        }
+    }
+
+    #[test]
+    fn negative_zero() {
+        assert!(is_negative_zero(-0.0));
+        assert!(!is_negative_zero(0.0));
+        assert!(!is_negative_zero(-1.0));
+        assert_eq!(f64_to_wrapping_i32(-0.0), 0);
+        assert_eq!(f64_to_wrapping_u32(-0.0), 0);
+    }
+}
@@ -0,0 +1,90 @@
+//! Everything needed to embed Ruffle in another application, in one place.
+//!
+//! A host builds a `Player` with `PlayerBuilder`, giving it backends for whatever it wants to
+//! handle itself (rendering, audio, networking, storage, the UI around the movie) and leaving
+//! the rest to the null backends. It then drives the player: `tick` and `render` on its own
+//! schedule, `handle_event` for input, and the `export_item` family to pull items out of the
+//! movie's library. Hosts that need more than this can reach into the other modules of the
+//! crate, but those change far more often than what's collected here.
+//!
+//! ```no_run
+//! use ruffle_core::embed::backend::NullRenderer;
+//! use ruffle_core::embed::*;
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let config = std::fs::read_to_string("player.toml")?;
+//! let renderer = NullRenderer::new(ViewportDimensions {
+//!     width: 550,
+//!     height: 400,
+//!     scale_factor: 1.0,
+//! });
+//!
+//! let player = PlayerBuilder::new()
+//!     .with_config(PlayerConfig::from_toml(&config)?)
+//!     .with_renderer(renderer)
+//!     .with_movie(SwfMovie::from_path("movie.swf", None)?)
+//!     .build();
+//!
+//! let mut player = player.lock().unwrap();
+//! player.tick(1000.0 / 24.0);
+//! player.handle_event(events::PlayerEvent::MouseMove { x: 10.0, y: 20.0 });
+//! player.render();
+//!
+//! let movie = player.root_movie().clone();
+//! for file in player.export_item(&movie, 1, "spritesheet")? {
+//!     std::fs::write(&file.name, &file.data)?;
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+pub use crate::config::{Letterbox, NetworkingAccessMode, PlayerConfig};
+pub use crate::limits::ExecutionLimit;
+pub use crate::loader::{LoadBehavior, LoadMetrics};
+pub use crate::log_filter::{LogFilter, LogSubsystem};
+pub use crate::player::{Player, PlayerBuilder};
+pub use crate::player_version::PlayerVersion;
+pub use crate::tag_utils::{SwfMovie, TagStrictness};
+pub use crate::SandboxType;
+pub use crate::{StageAlign, StageDisplayState, StageScaleMode};
+pub use ruffle_render::backend::ViewportDimensions;
+pub use ruffle_render::quality::StageQuality;
+
+/// The backends and providers a host can plug into a `PlayerBuilder`.
+pub mod backend {
+    pub use crate::backend::audio::{AudioBackend, NullAudioBackend};
+    pub use crate::backend::log::{LogBackend, NullLogBackend};
+    pub use crate::backend::navigator::{NavigatorBackend, NullNavigatorBackend};
+    pub use crate::backend::storage::{
+        MemorySecureStorageBackend, MemoryStorageBackend, SecureStorageBackend, StorageBackend,
+    };
+    pub use crate::backend::ui::{NullUiBackend, UiBackend};
+    pub use crate::external::{ExternalInterfaceProvider, FsCommandProvider};
+    pub use crate::native_extension::NativeExtension;
+    pub use crate::web_view::WebViewProvider;
+    pub use ruffle_render::backend::null::NullRenderer;
+    pub use ruffle_render::backend::RenderBackend;
+    pub use ruffle_video::backend::VideoBackend;
+    pub use ruffle_video::media::MediaDecoderBackend;
+    pub use ruffle_video::null::{NullMediaDecoderBackend, NullVideoBackend};
+}
+
+/// The input a host passes to `Player::handle_event`.
+pub mod events {
+    pub use crate::events::{KeyCode, MouseButton, MouseWheelDelta, PlayerEvent, TextControlCode};
+}
+
+/// Exporting library items with `Player::export_item` and its variants.
+pub mod export {
+    pub use crate::export::{
+        library_items, ColorVariant, ExportContent, ExportError, ExportImage, ExportItem,
+        ExportServices, ExportedFile, ItemExporterPlugin, ItemKind, LibraryItem, PaletteMap,
+        Recolor, RenderedFrame, SoundExporter, ThumbnailService, ThumbnailSubject,
+    };
+
+    #[cfg(feature = "png")]
+    pub use crate::export::{PngExporter, SpritesheetExporter};
+
+    #[cfg(any(unix, windows, target_os = "redox"))]
+    pub use crate::export::{BatchEvent, BatchExporter, BatchProgress, ExportJob};
+}
@@ -221,6 +221,26 @@ pub enum ClipEvent<'gc> {
     MouseWheel {
         delta: MouseWheelDelta,
     },
+
+    /// The right or middle mouse button was pressed over a display object.
+    ///
+    /// This is a targeted event with no anycast equivalent. It is targeted to
+    /// any interactive object under the mouse cursor, including the stage
+    /// itself. Only AVM2 can receive these events.
+    SecondaryPress {
+        button: MouseButton,
+    },
+
+    /// The right or middle mouse button was released over a display object.
+    ///
+    /// This is a targeted event with no anycast equivalent. It is targeted to
+    /// any interactive object under the mouse cursor, including the stage
+    /// itself. `click` is set if the button was also pressed over the same
+    /// object. Only AVM2 can receive these events.
+    SecondaryRelease {
+        button: MouseButton,
+        click: bool,
+    },
 }
 
 impl<'gc> ClipEvent<'gc> {
@@ -269,6 +289,8 @@ impl<'gc> ClipEvent<'gc> {
             ClipEvent::ReleaseOutside => Some(ClipEventFlag::RELEASE_OUTSIDE),
             ClipEvent::Unload => Some(ClipEventFlag::UNLOAD),
             ClipEvent::MouseWheel { .. }
+            | ClipEvent::SecondaryPress { .. }
+            | ClipEvent::SecondaryRelease { .. }
             | ClipEvent::MouseMoveInside
             | ClipEvent::MouseUpInside => None,
         }
@@ -328,6 +350,8 @@ impl<'gc> ClipEvent<'gc> {
             ClipEvent::ReleaseOutside => Some("onReleaseOutside"),
             ClipEvent::Unload => Some("onUnload"),
             ClipEvent::MouseWheel { .. }
+            | ClipEvent::SecondaryPress { .. }
+            | ClipEvent::SecondaryRelease { .. }
             | ClipEvent::MouseMoveInside
             | ClipEvent::MouseUpInside => None,
         }
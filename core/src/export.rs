@@ -0,0 +1,858 @@
+//! Exporting the items of a movie's library to files, through embedder-provided plugins.
+//!
+//! Ruffle itself provides sounds, as well as spritesheets and PNG frames with the `png` feature.
+//! Other formats, such as game engine resources, are added by registering `ItemExporterPlugin`s
+//! with `PlayerBuilder::with_exporter_plugin` or `Player::register_exporter_plugin`, and are run
+//! with `Player::export_item`. `library_items` lists what there is to export.
+//!
+//! Items can be recolored as they're exported, and several `ColorVariant`s of an item can be
+//! exported in one go with `Player::export_item_variants`. `BatchExporter` runs many exports at
+//! once, on a pool of players.
+//!
+//! Every export also writes a manifest recording where the item came from and how it was
+//! exported; see `manifest`.
+//!
+//! For browsing a library without exporting it, `ThumbnailService` renders and caches small
+//! previews of movies and their items.
+
+#[cfg(any(unix, windows, target_os = "redox"))]
+mod batch;
+#[cfg(feature = "png")]
+mod frames;
+mod manifest;
+mod recolor;
+mod sound;
+#[cfg(feature = "png")]
+mod spritesheet;
+mod thumbnail;
+
+#[cfg(any(unix, windows, target_os = "redox"))]
+pub use batch::{BatchEvent, BatchExporter, BatchProgress, ExportJob};
+#[cfg(feature = "png")]
+pub use frames::PngExporter;
+pub use recolor::{ColorVariant, PaletteMap, Recolor};
+pub use sound::SoundExporter;
+#[cfg(feature = "png")]
+pub use spritesheet::SpritesheetExporter;
+pub use thumbnail::{ThumbnailService, ThumbnailSubject};
+
+use crate::bitmap::bitmap_data::{BitmapData, BitmapDataWrapper, IBitmapDrawable};
+use crate::bitmap::operations;
+use crate::character::Character;
+use crate::context::UpdateContext;
+use crate::display_object::{DisplayObject, TDisplayObject};
+use crate::tag_utils::{self, SwfMovie};
+use crate::vminterface::Instantiator;
+use gc_arena::GcCell;
+use ruffle_render::bitmap::PixelRegion;
+use ruffle_render::matrix::Matrix;
+use ruffle_render::transform::Transform;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use swf::{BlendMode, CharacterId, ColorTransform, Rectangle, Tag, Twips};
+use thiserror::Error;
+
+/// An output format for library items.
+pub trait ItemExporterPlugin {
+    /// A short name identifying the format, such as `"spritesheet"`.
+    ///
+    /// Registering a plugin replaces any earlier one with the same name.
+    fn format_name(&self) -> &str;
+
+    /// Whether this plugin can export the given item.
+    fn supports(&self, _item: &ExportItem<'_>) -> bool {
+        true
+    }
+
+    /// The settings that affect this plugin's output, recorded in export manifests.
+    fn settings(&self) -> serde_json::Value {
+        serde_json::Value::Null
+    }
+
+    /// Converts an item into one or more files.
+    fn export(
+        &self,
+        item: &ExportItem<'_>,
+        services: &mut dyn ExportServices,
+    ) -> Result<Vec<ExportedFile>, Box<dyn std::error::Error + Send + Sync>>;
+}
+
+/// What the player can do for a plugin while it exports an item.
+pub trait ExportServices {
+    /// The number of frames of the item, which is 1 for anything but sprites.
+    fn num_frames(&self) -> u16;
+
+    /// Renders a frame of the item, counting from 1, scaled by `scale` and cropped to its
+    /// bounds.
+    ///
+    /// Returns `None` if the item isn't displayable, such as a sound, or if it can't be drawn.
+    fn render_frame(&mut self, frame: u16, scale: f64) -> Option<RenderedFrame>;
+}
+
+/// A library item, resolved for exporting.
+pub struct ExportItem<'a> {
+    pub id: CharacterId,
+
+    /// The name the item is exported or linked with, if any.
+    pub name: Option<String>,
+
+    pub content: ExportContent<'a>,
+}
+
+impl ExportItem<'_> {
+    /// A name to base file names on: the item's own name, or `symbolN` if it has none.
+    pub fn file_stem(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("symbol{}", self.id),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub enum ExportContent<'a> {
+    /// A bitmap's pixels.
+    Bitmap(ExportImage),
+
+    /// A sound, as stored in the movie.
+    Sound(swf::Sound<'a>),
+
+    /// Any other symbol, extracted along with everything it uses into a movie of its own.
+    Symbol(SwfMovie),
+}
+
+/// An image, in RGBA with straight (not premultiplied) alpha.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ExportImage {
+    pub width: u32,
+    pub height: u32,
+    pub rgba: Vec<u8>,
+}
+
+/// A frame rendered by `ExportServices::render_frame`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RenderedFrame {
+    pub image: ExportImage,
+
+    /// Where the item's registration point lies in the image, in pixels.
+    pub origin_x: f64,
+    pub origin_y: f64,
+}
+
+/// A file written by a plugin.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExportedFile {
+    /// A suggested file name, which may include directories.
+    pub name: String,
+    pub data: Vec<u8>,
+}
+
+/// What kind of character a library item is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ItemKind {
+    Bitmap,
+    Sound,
+    Sprite,
+    Shape,
+    MorphShape,
+    Button,
+    Text,
+    EditText,
+    Font,
+    BinaryData,
+    Video,
+}
+
+/// A character defined by a movie, as listed by `library_items`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LibraryItem {
+    pub id: CharacterId,
+    pub kind: ItemKind,
+
+    /// The name the item is exported or linked with, if any.
+    pub name: Option<String>,
+}
+
+#[derive(Error, Debug)]
+pub enum ExportError {
+    #[error("No exporter is registered for the format {0:?}")]
+    UnknownFormat(String),
+
+    #[error("Character {0} is not defined in this movie")]
+    CharacterNotFound(CharacterId),
+
+    #[error("The {format} exporter doesn't support character {id}")]
+    Unsupported { format: String, id: CharacterId },
+
+    #[error("Character {0} can't be recolored by this exporter")]
+    NotRecolorable(CharacterId),
+
+    #[error("No variants of character {0} were asked for")]
+    NoVariants(CharacterId),
+
+    #[error("Couldn't extract the symbol: {0}")]
+    Extraction(#[from] tag_utils::Error),
+
+    #[error("Export failed: {0}")]
+    Failed(Box<dyn std::error::Error + Send + Sync>),
+}
+
+/// The registered exporter plugins, by format name.
+///
+/// `ExporterPlugins::default()` is empty, while `ExporterPlugins::new()` starts with the formats
+/// Ruffle provides itself.
+#[derive(Default)]
+pub struct ExporterPlugins {
+    plugins: Vec<Box<dyn ItemExporterPlugin>>,
+}
+
+impl ExporterPlugins {
+    pub fn new() -> Self {
+        let mut plugins = Self::default();
+        #[cfg(feature = "png")]
+        {
+            plugins.register(Box::new(SpritesheetExporter::default()));
+            plugins.register(Box::new(PngExporter::default()));
+        }
+        plugins.register(Box::new(SoundExporter));
+        plugins
+    }
+
+    /// Adds a plugin, replacing any earlier one for the same format.
+    pub fn register(&mut self, plugin: Box<dyn ItemExporterPlugin>) {
+        self.plugins
+            .retain(|existing| existing.format_name() != plugin.format_name());
+        self.plugins.push(plugin);
+    }
+
+    pub fn formats(&self) -> impl Iterator<Item = &str> {
+        self.plugins.iter().map(|plugin| plugin.format_name())
+    }
+
+    pub fn get(&self, format: &str) -> Option<&dyn ItemExporterPlugin> {
+        self.plugins
+            .iter()
+            .find(|plugin| plugin.format_name() == format)
+            .map(|plugin| plugin.as_ref())
+    }
+}
+
+/// Resolves a character of `movie` and runs `plugin` on each variant of it.
+pub(crate) fn export_item(
+    context: &mut UpdateContext<'_, '_>,
+    plugin: &dyn ItemExporterPlugin,
+    movie: &Arc<SwfMovie>,
+    id: CharacterId,
+    variants: &[ColorVariant],
+) -> Result<Vec<ExportedFile>, ExportError> {
+    if variants.is_empty() {
+        return Err(ExportError::NoVariants(id));
+    }
+    let character = context
+        .library
+        .library_for_movie(movie.clone())
+        .and_then(|library| library.character_by_id(id))
+        .cloned()
+        .ok_or(ExportError::CharacterNotFound(id))?;
+
+    let (names, sound) = find_names_and_sound(movie, id);
+    let name = names.last().cloned();
+    let (content, num_frames) = match character {
+        Character::Bitmap(bitmap) => {
+            let bitmap_data = bitmap.bitmap_data_wrapper();
+            (ExportContent::Bitmap(read_image(bitmap_data)), 1)
+        }
+        Character::Sound(_) => (
+            ExportContent::Sound(sound.ok_or(ExportError::CharacterNotFound(id))?),
+            1,
+        ),
+        Character::MovieClip(clip) => (
+            ExportContent::Symbol(movie.export_symbol_as_swf(id)?),
+            clip.total_frames(),
+        ),
+        _ => (ExportContent::Symbol(movie.export_symbol_as_swf(id)?), 1),
+    };
+
+    let original = ExportItem { id, name, content };
+    if !plugin.supports(&original) {
+        return Err(ExportError::Unsupported {
+            format: plugin.format_name().to_string(),
+            id,
+        });
+    }
+    let dependencies = movie.symbol_dependencies(id)?;
+
+    let mut files = vec![];
+    for variant in variants {
+        let item = recolor_item(&original, variant)?;
+        let mut services = PlayerExportServices {
+            context: &mut *context,
+            movie: movie.clone(),
+            id,
+            num_frames,
+            recolor: &variant.recolor,
+            rendered: false,
+            appearance: Default::default(),
+        };
+        let mut variant_files = plugin
+            .export(&item, &mut services)
+            .map_err(ExportError::Failed)?;
+        // Symbols are only recolored as they're rendered, so a plugin that exports the movie
+        // itself would name files after a variant they don't look like.
+        if let ExportContent::Symbol(_) = item.content {
+            if !services.rendered && !variant.recolor.is_identity() {
+                return Err(ExportError::NotRecolorable(id));
+            }
+        }
+
+        let provenance = manifest::Provenance {
+            movie,
+            names: &names,
+            dependencies: &dependencies,
+            variant,
+        };
+        let appearance = std::mem::take(&mut services.appearance);
+        let manifest = manifest::write_manifest(
+            &item,
+            &provenance,
+            plugin,
+            &mut services,
+            appearance,
+            &variant_files,
+        )
+        .map_err(|error| ExportError::Failed(Box::new(error)))?;
+        variant_files.push(manifest);
+        files.extend(variant_files);
+    }
+    Ok(files)
+}
+
+/// Applies a variant to an item, renaming it after the variant and recoloring bitmaps.
+///
+/// Symbols are left for `ExportServices::render_frame` to recolor, and sounds can't be
+/// recolored at all.
+fn recolor_item<'a>(
+    item: &ExportItem<'a>,
+    variant: &ColorVariant,
+) -> Result<ExportItem<'a>, ExportError> {
+    let name = if variant.name.is_empty() {
+        item.name.clone()
+    } else {
+        Some(format!("{}_{}", item.file_stem(), variant.name))
+    };
+    let content = match &item.content {
+        ExportContent::Bitmap(image) => {
+            let mut image = image.clone();
+            variant.recolor.apply(&mut image);
+            ExportContent::Bitmap(image)
+        }
+        ExportContent::Sound(_) if !variant.recolor.is_identity() => {
+            return Err(ExportError::NotRecolorable(item.id));
+        }
+        content => content.clone(),
+    };
+    Ok(ExportItem {
+        id: item.id,
+        name,
+        content,
+    })
+}
+
+/// Finds the names a character is exported or linked with, and its definition if it's a sound.
+///
+/// Names are in the order they're given, so the last one is the one that applies.
+fn find_names_and_sound(
+    movie: &SwfMovie,
+    id: CharacterId,
+) -> (Vec<String>, Option<swf::Sound<'_>>) {
+    let mut names = vec![];
+    let mut add_name = |name: String| {
+        names.retain(|existing| *existing != name);
+        names.push(name);
+    };
+    let mut sound = None;
+
+    let mut reader = swf::read::Reader::new(movie.data(), movie.version());
+    loop {
+        let remaining = reader.get_ref().len();
+        match reader.read_tag() {
+            Ok(Tag::End) => break,
+            Ok(Tag::DefineSound(definition)) if definition.id == id => sound = Some(*definition),
+            Ok(Tag::ExportAssets(assets)) => {
+                if let Some(asset) = assets.iter().find(|asset| asset.id == id) {
+                    add_name(asset.name.to_string_lossy(movie.encoding()));
+                }
+            }
+            Ok(Tag::SymbolClass(links)) => {
+                if let Some(link) = links.iter().find(|link| link.id == id) {
+                    add_name(link.class_name.to_string_lossy(movie.encoding()));
+                }
+            }
+            Ok(_) => {}
+            Err(_) if reader.get_ref().len() < remaining => {}
+            Err(_) => break,
+        }
+    }
+
+    (names, sound)
+}
+
+/// Encodes RGBA pixels with straight alpha as a PNG.
+#[cfg(feature = "png")]
+fn encode_png(width: u32, height: u32, rgba: &[u8]) -> Result<Vec<u8>, png::EncodingError> {
+    let mut data = Vec::new();
+    {
+        let mut encoder = png::Encoder::new(&mut data, width, height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.write_header()?.write_image_data(rgba)?;
+    }
+    Ok(data)
+}
+
+/// Lists the characters `movie` defines, in the order it defines them.
+pub fn library_items(movie: &SwfMovie) -> Vec<LibraryItem> {
+    let mut items = vec![];
+    let mut names = HashMap::new();
+    let mut add = |id, kind| {
+        items.push(LibraryItem {
+            id,
+            kind,
+            name: None,
+        })
+    };
+
+    let mut reader = swf::read::Reader::new(movie.data(), movie.version());
+    loop {
+        let remaining = reader.get_ref().len();
+        match reader.read_tag() {
+            Ok(Tag::End) => break,
+            Ok(Tag::DefineBits { id, .. } | Tag::DefineBitsJpeg2 { id, .. }) => {
+                add(id, ItemKind::Bitmap)
+            }
+            Ok(Tag::DefineBitsJpeg3(bits)) => add(bits.id, ItemKind::Bitmap),
+            Ok(Tag::DefineBitsLossless(bits)) => add(bits.id, ItemKind::Bitmap),
+            Ok(Tag::DefineSound(sound)) => add(sound.id, ItemKind::Sound),
+            Ok(Tag::DefineSprite(sprite)) => add(sprite.id, ItemKind::Sprite),
+            Ok(Tag::DefineShape(shape)) => add(shape.id, ItemKind::Shape),
+            Ok(Tag::DefineMorphShape(shape)) => add(shape.id, ItemKind::MorphShape),
+            Ok(Tag::DefineButton(button) | Tag::DefineButton2(button)) => {
+                add(button.id, ItemKind::Button)
+            }
+            Ok(Tag::DefineText(text)) => add(text.id, ItemKind::Text),
+            Ok(Tag::DefineEditText(text)) => add(text.id(), ItemKind::EditText),
+            Ok(Tag::DefineFont(font)) => add(font.id, ItemKind::Font),
+            Ok(Tag::DefineFont2(font)) => add(font.id, ItemKind::Font),
+            Ok(Tag::DefineFont4(font)) => add(font.id, ItemKind::Font),
+            Ok(Tag::DefineBinaryData(data)) => add(data.id, ItemKind::BinaryData),
+            Ok(Tag::DefineVideoStream(video)) => add(video.id, ItemKind::Video),
+            Ok(Tag::ExportAssets(assets)) => {
+                for asset in assets {
+                    names.insert(asset.id, asset.name.to_string_lossy(movie.encoding()));
+                }
+            }
+            Ok(Tag::SymbolClass(links)) => {
+                for link in links {
+                    names.insert(link.id, link.class_name.to_string_lossy(movie.encoding()));
+                }
+            }
+            Ok(_) => {}
+            Err(_) if reader.get_ref().len() < remaining => {}
+            Err(_) => break,
+        }
+    }
+
+    for item in &mut items {
+        item.name = names.remove(&item.id);
+    }
+    items
+}
+
+fn read_image(bitmap_data: BitmapDataWrapper<'_>) -> ExportImage {
+    let (width, height) = (bitmap_data.width(), bitmap_data.height());
+    let read = bitmap_data.read_area(PixelRegion::for_whole_size(width, height));
+    let rgba = read
+        .pixels()
+        .iter()
+        .flat_map(|pixel| {
+            let pixel = pixel.to_un_multiplied_alpha();
+            [pixel.red(), pixel.green(), pixel.blue(), pixel.alpha()]
+        })
+        .collect();
+
+    ExportImage {
+        width,
+        height,
+        rgba,
+    }
+}
+
+struct PlayerExportServices<'a, 'b, 'gc> {
+    context: &'a mut UpdateContext<'b, 'gc>,
+    movie: Arc<SwfMovie>,
+    id: CharacterId,
+    num_frames: u16,
+    recolor: &'a Recolor,
+
+    /// Whether the plugin rendered anything, and so saw the item recolored.
+    rendered: bool,
+
+    /// The frames the plugin rendered at full size, for the manifest to reuse.
+    appearance: manifest::Appearance,
+}
+
+impl ExportServices for PlayerExportServices<'_, '_, '_> {
+    fn num_frames(&self) -> u16 {
+        self.num_frames
+    }
+
+    fn render_frame(&mut self, frame: u16, scale: f64) -> Option<RenderedFrame> {
+        self.rendered = true;
+        // A fresh instance each time, so frames don't depend on what was rendered before.
+        let object = self
+            .context
+            .library
+            .library_for_movie_mut(self.movie.clone())
+            .instantiate_by_id(self.id, self.context.gc_context)
+            .ok()?;
+        object.post_instantiation(self.context, None, Instantiator::Movie, false);
+        let frame = frame.clamp(1, self.num_frames);
+        if let Some(clip) = object.as_movie_clip() {
+            clip.goto_frame(self.context, frame, true);
+        }
+
+        let color_transform = match self.recolor {
+            Recolor::Transform(color_transform) => *color_transform,
+            Recolor::Palette(_) => ColorTransform::IDENTITY,
+        };
+        let mut rendered = render_object(
+            self.context,
+            object,
+            object.bounds(),
+            scale,
+            color_transform,
+        )?;
+        if let Recolor::Palette(_) = self.recolor {
+            self.recolor.apply(&mut rendered.image);
+        }
+        if scale == 1.0 {
+            self.appearance.add_frame(frame, &rendered);
+        }
+        Some(rendered)
+    }
+}
+
+/// The longest side of an image drawn by `render_object`, as for a `BitmapData`.
+const MAX_RENDER_SIDE: f64 = 8191.0;
+
+/// The most pixels in an image drawn by `render_object`, as for a `BitmapData`.
+const MAX_RENDER_PIXELS: f64 = 16_777_215.0;
+
+/// Lowers `scale` as needed for `bounds` to fit in the largest image `render_object` draws.
+///
+/// Returns `None` for scales that can't draw anything.
+fn clamp_scale(bounds: &Rectangle<Twips>, scale: f64) -> Option<f64> {
+    if !scale.is_finite() || scale <= 0.0 {
+        return None;
+    }
+    let width = bounds.width().to_pixels() * scale;
+    let height = bounds.height().to_pixels() * scale;
+
+    // Rounding up partial pixels may add a pixel to each side.
+    let mut fit = ((MAX_RENDER_SIDE - 1.0) / width).min((MAX_RENDER_SIDE - 1.0) / height);
+    let area = width * height;
+    if area > 0.0 {
+        // Solves `(width * fit + 1) * (height * fit + 1) = MAX_RENDER_PIXELS`.
+        let sum = width + height;
+        let discriminant = sum * sum + 4.0 * area * (MAX_RENDER_PIXELS - 1.0);
+        fit = fit.min((discriminant.sqrt() - sum) / (2.0 * area));
+    }
+    Some(scale * fit.min(1.0))
+}
+
+/// Draws the part of `object` within `bounds`, in its own coordinates, scaled by `scale`.
+///
+/// Objects too large to draw at `scale` are drawn smaller instead.
+fn render_object<'gc>(
+    context: &mut UpdateContext<'_, 'gc>,
+    object: DisplayObject<'gc>,
+    bounds: Rectangle<Twips>,
+    scale: f64,
+    color_transform: ColorTransform,
+) -> Option<RenderedFrame> {
+    let scale = clamp_scale(&bounds, scale)?;
+    let scale_matrix = Matrix::scale(scale as f32, scale as f32);
+    let bounds = scale_matrix * bounds;
+    if !bounds.is_valid() {
+        return None;
+    }
+    let width = (bounds.x_max - bounds.x_min).to_pixels().ceil() as u32;
+    let height = (bounds.y_max - bounds.y_min).to_pixels().ceil() as u32;
+    if width == 0 || height == 0 {
+        return None;
+    }
+
+    let target = BitmapDataWrapper::new(GcCell::new(
+        context.gc_context,
+        BitmapData::new(width, height, true, 0),
+    ));
+    let transform = Transform {
+        matrix: Matrix::translate(-bounds.x_min, -bounds.y_min) * scale_matrix,
+        color_transform,
+    };
+    let quality = context.stage.quality();
+    operations::draw(
+        context,
+        target,
+        IBitmapDrawable::DisplayObject(object),
+        transform,
+        true,
+        BlendMode::Normal,
+        None,
+        quality,
+    )
+    .ok()?;
+    Some(RenderedFrame {
+        image: read_image(target),
+        origin_x: -bounds.x_min.to_pixels(),
+        origin_y: -bounds.y_min.to_pixels(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        clamp_scale, library_items, recolor_item, ColorVariant, ExportContent, ExportError,
+        ExportImage, ExportItem, ExportServices, ExportedFile, ItemExporterPlugin, ItemKind,
+        LibraryItem, Recolor, SoundExporter, MAX_RENDER_PIXELS, MAX_RENDER_SIDE,
+    };
+    use crate::player::PlayerBuilder;
+    use crate::tag_utils::SwfMovie;
+    use swf::{
+        AudioCompression, CharacterId, ColorTransform, DefineBinaryData, ExportedAsset, Fixed8,
+        Header, Rectangle, Sound, SoundFormat, Sprite, SwfStr, Tag, Twips,
+    };
+
+    const SAMPLES: [u8; 4] = [0x80, 0x90, 0xa0, 0xb0];
+
+    /// A movie defining an 8-bit mono sound named "beep", a sprite and some binary data.
+    fn library_movie() -> SwfMovie {
+        let tags = [
+            Tag::DefineSound(Box::new(Sound {
+                id: 1,
+                format: SoundFormat {
+                    compression: AudioCompression::Uncompressed,
+                    sample_rate: 11025,
+                    is_stereo: false,
+                    is_16_bit: false,
+                },
+                num_samples: SAMPLES.len() as u32,
+                data: &SAMPLES,
+            })),
+            Tag::DefineSprite(Sprite {
+                id: 2,
+                num_frames: 1,
+                tags: vec![Tag::ShowFrame],
+            }),
+            Tag::DefineBinaryData(DefineBinaryData {
+                id: 3,
+                data: b"data",
+            }),
+            Tag::ExportAssets(vec![ExportedAsset {
+                id: 1,
+                name: SwfStr::from_utf8_str("beep"),
+            }]),
+            Tag::ShowFrame,
+        ];
+        let mut data = vec![];
+        swf::write_swf(&Header::default_with_swf_version(10), &tags, &mut data).unwrap();
+        SwfMovie::from_data(&data, "file:///test.swf".into(), None).unwrap()
+    }
+
+    fn bounds(width: f64, height: f64) -> Rectangle<Twips> {
+        Rectangle {
+            x_min: Twips::ZERO,
+            y_min: Twips::ZERO,
+            x_max: Twips::from_pixels(width),
+            y_max: Twips::from_pixels(height),
+        }
+    }
+
+    fn rendered_size(width: f64, height: f64, scale: f64) -> Option<(f64, f64)> {
+        let scale = clamp_scale(&bounds(width, height), scale)?;
+        Some(((width * scale).ceil(), (height * scale).ceil()))
+    }
+
+    #[test]
+    fn library_items_are_listed_with_their_names() {
+        let item = |id, kind, name: Option<&str>| LibraryItem {
+            id,
+            kind,
+            name: name.map(String::from),
+        };
+        assert_eq!(
+            library_items(&library_movie()),
+            [
+                item(1, ItemKind::Sound, Some("beep")),
+                item(2, ItemKind::Sprite, None),
+                item(3, ItemKind::BinaryData, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn uncompressed_sounds_export_to_wav() {
+        let player = PlayerBuilder::new().with_movie(library_movie()).build();
+        let mut player = player.lock().unwrap();
+        let movie = player.root_movie().clone();
+
+        let files = player.export_item(&movie, 1, "sound").unwrap();
+        let names: Vec<_> = files.iter().map(|file| file.name.as_str()).collect();
+        assert_eq!(names, ["beep.wav", "beep.manifest.json"]);
+        assert_eq!(&files[0].data[..4], b"RIFF");
+        assert_eq!(&files[0].data[44..], SAMPLES);
+
+        // Only sounds can be exported as sounds.
+        assert!(player.export_item(&movie, 2, "sound").is_err());
+    }
+
+    /// Exports any item to a single empty file named after the plugin.
+    struct Label(&'static str);
+
+    impl ItemExporterPlugin for Label {
+        fn format_name(&self) -> &str {
+            "label"
+        }
+
+        fn export(
+            &self,
+            _item: &ExportItem<'_>,
+            _services: &mut dyn ExportServices,
+        ) -> Result<Vec<ExportedFile>, Box<dyn std::error::Error + Send + Sync>> {
+            Ok(vec![ExportedFile {
+                name: self.0.to_string(),
+                data: vec![],
+            }])
+        }
+    }
+
+    #[test]
+    fn one_off_plugins_are_not_registered() {
+        let player = PlayerBuilder::new()
+            .with_movie(library_movie())
+            .with_exporter_plugin(Box::new(Label("registered")))
+            .build();
+        let mut player = player.lock().unwrap();
+        let movie = player.root_movie().clone();
+
+        let files = player
+            .export_item_with(&movie, 2, &Label("one-off"), &[ColorVariant::default()])
+            .unwrap();
+        assert_eq!(files[0].name, "one-off");
+        let files = player.export_item(&movie, 2, "label").unwrap();
+        assert_eq!(files[0].name, "registered");
+    }
+
+    fn player_variants(
+        variants: &[ColorVariant],
+        id: CharacterId,
+        plugin: &dyn ItemExporterPlugin,
+    ) -> Result<Vec<String>, ExportError> {
+        let player = PlayerBuilder::new().with_movie(library_movie()).build();
+        let mut player = player.lock().unwrap();
+        let movie = player.root_movie().clone();
+        let files = player.export_item_with(&movie, id, plugin, variants)?;
+        Ok(files.into_iter().map(|file| file.name).collect())
+    }
+
+    fn tinted() -> Recolor {
+        Recolor::Transform(ColorTransform {
+            r_multiply: Fixed8::ZERO,
+            ..ColorTransform::IDENTITY
+        })
+    }
+
+    #[test]
+    fn variants_must_be_given() {
+        assert!(matches!(
+            player_variants(&[], 1, &SoundExporter),
+            Err(ExportError::NoVariants(1))
+        ));
+    }
+
+    #[test]
+    fn sounds_are_renamed_but_not_recolored() {
+        let names = player_variants(
+            &[ColorVariant::new("quiet", Recolor::default())],
+            1,
+            &SoundExporter,
+        )
+        .unwrap();
+        assert_eq!(names, ["beep_quiet.wav", "beep_quiet.manifest.json"]);
+
+        assert!(matches!(
+            player_variants(&[ColorVariant::new("red", tinted())], 1, &SoundExporter),
+            Err(ExportError::NotRecolorable(1))
+        ));
+    }
+
+    #[test]
+    fn symbols_must_be_rendered_to_be_recolored() {
+        // `Label` never renders the sprite, so it can't see it recolored.
+        assert!(player_variants(&[ColorVariant::default()], 2, &Label("plain")).is_ok());
+        assert!(matches!(
+            player_variants(&[ColorVariant::new("red", tinted())], 2, &Label("red")),
+            Err(ExportError::NotRecolorable(2))
+        ));
+    }
+
+    #[test]
+    fn bitmaps_are_recolored() {
+        let item = ExportItem {
+            id: 4,
+            name: None,
+            content: ExportContent::Bitmap(ExportImage {
+                width: 1,
+                height: 1,
+                rgba: vec![255, 255, 255, 255],
+            }),
+        };
+        let recolored = recolor_item(&item, &ColorVariant::new("cyan", tinted())).unwrap();
+        assert_eq!(recolored.file_stem(), "symbol4_cyan");
+        let ExportContent::Bitmap(image) = recolored.content else {
+            panic!("Bitmaps stay bitmaps");
+        };
+        assert_eq!(image.rgba, [0, 255, 255, 255]);
+    }
+
+    #[test]
+    fn small_objects_keep_their_scale() {
+        assert_eq!(clamp_scale(&bounds(100.0, 50.0), 2.0), Some(2.0));
+        assert_eq!(rendered_size(100.0, 50.0, 2.0), Some((200.0, 100.0)));
+    }
+
+    #[test]
+    fn large_objects_are_drawn_smaller() {
+        let (width, height) = rendered_size(8000.0, 10.0, 4.0).unwrap();
+        assert!(width <= MAX_RENDER_SIDE, "{width}");
+        assert!(height >= 9.0, "{height}");
+
+        let (width, height) = rendered_size(6000.0, 6000.0, 1.0).unwrap();
+        assert!(width * height <= MAX_RENDER_PIXELS, "{width}x{height}");
+        assert!(width >= 4000.0, "{width}");
+
+        let (width, height) = rendered_size(8191.0, 8191.0, 1000.0).unwrap();
+        assert!(width <= MAX_RENDER_SIDE && height <= MAX_RENDER_SIDE);
+        assert!(width * height <= MAX_RENDER_PIXELS);
+    }
+
+    #[test]
+    fn invalid_scales_draw_nothing() {
+        assert_eq!(clamp_scale(&bounds(100.0, 100.0), 0.0), None);
+        assert_eq!(clamp_scale(&bounds(100.0, 100.0), -1.0), None);
+        assert_eq!(clamp_scale(&bounds(100.0, 100.0), f64::NAN), None);
+        assert_eq!(clamp_scale(&bounds(100.0, 100.0), f64::INFINITY), None);
+    }
+}
@@ -0,0 +1,424 @@
+//! Exporting many items at once, spread over several threads.
+
+use super::{ColorVariant, ExportedFile};
+use crate::limits::ExecutionLimit;
+use crate::player::{Player, PlayerBuilder};
+use crate::tag_utils::SwfMovie;
+use std::any::Any;
+use std::collections::{HashMap, VecDeque};
+use std::num::NonZeroUsize;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use swf::CharacterId;
+
+/// An item to export.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExportJob {
+    /// The movie the item is in.
+    pub swf: PathBuf,
+
+    pub character_id: CharacterId,
+
+    /// The format to export to, as passed to `Player::export_item`.
+    pub format: String,
+
+    /// The variants to export, as passed to `Player::export_item_variants`. If empty, the item
+    /// is exported as it is.
+    pub variants: Vec<ColorVariant>,
+}
+
+/// Something that happened to a job.
+#[derive(Debug)]
+pub enum BatchEvent {
+    Exported {
+        job: ExportJob,
+        files: Vec<ExportedFile>,
+    },
+
+    /// The job failed, and is being tried again.
+    Retrying {
+        job: ExportJob,
+        attempt: u32,
+        error: String,
+    },
+
+    /// The job failed for the last time.
+    Failed { job: ExportJob, error: String },
+}
+
+/// How far a batch has got.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BatchProgress {
+    pub total: usize,
+    pub exported: usize,
+    pub failed: usize,
+}
+
+impl BatchProgress {
+    /// How many jobs are done, successfully or not.
+    pub fn finished(&self) -> usize {
+        self.exported + self.failed
+    }
+}
+
+type PlayerFactory = dyn Fn() -> PlayerBuilder + Send + Sync;
+
+/// Runs export jobs on a pool of threads, each with a player of its own.
+///
+/// Jobs are grouped by movie, so each movie is only loaded once. A job that fails, or panics, is
+/// tried again with a fresh player, up to `with_max_attempts` times in all.
+pub struct BatchExporter {
+    make_player: Arc<PlayerFactory>,
+    threads: usize,
+    max_attempts: u32,
+}
+
+impl BatchExporter {
+    /// Creates a batch exporter whose players are built from `make_player`.
+    ///
+    /// The builder is made on the thread that uses the player, so its backends don't need to
+    /// be `Send`. It should have a renderer able to draw offscreen, such as wgpu's, and any
+    /// exporter plugins the jobs need; the movie is set by the batch exporter.
+    pub fn new(make_player: impl Fn() -> PlayerBuilder + Send + Sync + 'static) -> Self {
+        Self {
+            make_player: Arc::new(make_player),
+            threads: thread::available_parallelism().map_or(1, NonZeroUsize::get),
+            max_attempts: 3,
+        }
+    }
+
+    /// Sets how many threads to export on, which defaults to the available parallelism.
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = threads.max(1);
+        self
+    }
+
+    /// Sets how many times a job is attempted before giving up on it, which defaults to 3.
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Runs every job, calling `on_event` on this thread as they progress.
+    ///
+    /// Returns once all jobs are done.
+    pub fn run(
+        &self,
+        jobs: impl IntoIterator<Item = ExportJob>,
+        mut on_event: impl FnMut(&BatchEvent, &BatchProgress),
+    ) -> BatchProgress {
+        let mut groups: VecDeque<(PathBuf, Vec<ExportJob>)> = VecDeque::new();
+        let mut group_indices = HashMap::new();
+        let mut progress = BatchProgress::default();
+        for job in jobs {
+            progress.total += 1;
+            let index = *group_indices.entry(job.swf.clone()).or_insert_with(|| {
+                groups.push_back((job.swf.clone(), vec![]));
+                groups.len() - 1
+            });
+            groups[index].1.push(job);
+        }
+
+        let threads = self.threads.min(groups.len());
+        let queue = Mutex::new(groups);
+        let (sender, receiver) = mpsc::channel();
+        thread::scope(|scope| {
+            for _ in 0..threads {
+                let sender = sender.clone();
+                let queue = &queue;
+                let make_player = self.make_player.clone();
+                let max_attempts = self.max_attempts;
+                scope.spawn(move || {
+                    // Players aren't `Send`, so the worker is made on its own thread.
+                    let mut worker = Worker {
+                        make_player,
+                        max_attempts,
+                        loaded: None,
+                    };
+                    loop {
+                        let next = queue.lock().expect("Queue lock").pop_front();
+                        let Some((swf, jobs)) = next else {
+                            break;
+                        };
+                        for job in jobs {
+                            worker.run(&swf, job, &sender);
+                        }
+                    }
+                });
+            }
+            drop(sender);
+
+            for event in receiver {
+                match &event {
+                    BatchEvent::Exported { .. } => progress.exported += 1,
+                    BatchEvent::Failed { .. } => progress.failed += 1,
+                    BatchEvent::Retrying { .. } => {}
+                }
+                on_event(&event, &progress);
+            }
+        });
+
+        progress
+    }
+}
+
+/// The state of one thread of a `BatchExporter`.
+struct Worker {
+    make_player: Arc<PlayerFactory>,
+    max_attempts: u32,
+
+    /// The player for the movie the last job was in.
+    loaded: Option<(PathBuf, Arc<Mutex<Player>>)>,
+}
+
+impl Worker {
+    fn run(&mut self, swf: &Path, job: ExportJob, events: &mpsc::Sender<BatchEvent>) {
+        // Jobs run side by side, so tag everything they log with the job it came from.
+        let _span = tracing::info_span!(
+            "export_job",
+            swf = %swf.display(),
+            character_id = job.character_id,
+            format = %job.format,
+        )
+        .entered();
+
+        let mut attempt = 1;
+        loop {
+            let result = catch_unwind(AssertUnwindSafe(|| self.export(swf, &job)))
+                .unwrap_or_else(|panic| Err(panic_message(panic)));
+            let event = match result {
+                Ok(files) => BatchEvent::Exported { job, files },
+                Err(error) => {
+                    // The player may be left in a bad state, so start over with a new one.
+                    self.loaded = None;
+                    if attempt < self.max_attempts {
+                        let _ = events.send(BatchEvent::Retrying {
+                            job: job.clone(),
+                            attempt,
+                            error,
+                        });
+                        attempt += 1;
+                        continue;
+                    }
+                    BatchEvent::Failed { job, error }
+                }
+            };
+            let _ = events.send(event);
+            return;
+        }
+    }
+
+    fn export(&mut self, swf: &Path, job: &ExportJob) -> Result<Vec<ExportedFile>, String> {
+        let player = match &self.loaded {
+            Some((path, player)) if path == swf => player.clone(),
+            _ => {
+                self.loaded = None;
+                let movie = SwfMovie::from_path(swf, None).map_err(|e| e.to_string())?;
+                let player = (self.make_player)().with_movie(movie).build();
+                player
+                    .lock()
+                    .expect("Player lock")
+                    .preload(&mut ExecutionLimit::none());
+                self.loaded = Some((swf.to_path_buf(), player.clone()));
+                player
+            }
+        };
+
+        let mut player = player.lock().map_err(|e| e.to_string())?;
+        let movie = player.root_movie().clone();
+        let result = if job.variants.is_empty() {
+            player.export_item(&movie, job.character_id, &job.format)
+        } else {
+            player.export_item_variants(&movie, job.character_id, &job.format, &job.variants)
+        };
+        result.map_err(|e| e.to_string())
+    }
+}
+
+fn panic_message(panic: Box<dyn Any + Send>) -> String {
+    match panic.downcast::<String>() {
+        Ok(message) => format!("Panicked: {message}"),
+        Err(panic) => match panic.downcast::<&str>() {
+            Ok(message) => format!("Panicked: {message}"),
+            Err(_) => "Panicked".to_string(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BatchEvent, BatchExporter, BatchProgress, ExportJob};
+    use crate::export::{ExportItem, ExportServices, ExportedFile, ItemExporterPlugin};
+    use crate::player::PlayerBuilder;
+    use std::path::PathBuf;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use swf::{AudioCompression, Header, Sound, SoundFormat, Tag};
+
+    /// Writes a movie defining an 8-bit mono sound to a file of its own, named `name`.
+    fn sound_movie(name: &str) -> PathBuf {
+        let tags = [
+            Tag::DefineSound(Box::new(Sound {
+                id: 1,
+                format: SoundFormat {
+                    compression: AudioCompression::Uncompressed,
+                    sample_rate: 11025,
+                    is_stereo: false,
+                    is_16_bit: false,
+                },
+                num_samples: 4,
+                data: &[0x80, 0x90, 0xa0, 0xb0],
+            })),
+            Tag::ShowFrame,
+        ];
+        let mut data = vec![];
+        swf::write_swf(&Header::default_with_swf_version(10), &tags, &mut data).unwrap();
+
+        let dir = std::env::temp_dir().join(format!("ruffle_batch_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(name);
+        std::fs::write(&path, data).unwrap();
+        path
+    }
+
+    fn job(swf: &PathBuf, format: &str) -> ExportJob {
+        ExportJob {
+            swf: swf.clone(),
+            character_id: 1,
+            format: format.to_string(),
+            variants: vec![],
+        }
+    }
+
+    /// Panics on every export.
+    struct Panics;
+
+    impl ItemExporterPlugin for Panics {
+        fn format_name(&self) -> &str {
+            "panics"
+        }
+
+        fn export(
+            &self,
+            _item: &ExportItem<'_>,
+            _services: &mut dyn ExportServices,
+        ) -> Result<Vec<ExportedFile>, Box<dyn std::error::Error + Send + Sync>> {
+            panic!("Can't export this");
+        }
+    }
+
+    /// A batch exporter that counts how many players it has made.
+    fn counting_exporter() -> (BatchExporter, Arc<AtomicUsize>) {
+        let players = Arc::new(AtomicUsize::new(0));
+        let count = players.clone();
+        let exporter = BatchExporter::new(move || {
+            count.fetch_add(1, Ordering::SeqCst);
+            PlayerBuilder::new().with_exporter_plugin(Box::new(Panics))
+        });
+        (exporter, players)
+    }
+
+    #[test]
+    fn each_movie_is_loaded_once() {
+        let first = sound_movie("first.swf");
+        let second = sound_movie("second.swf");
+        let (exporter, players) = counting_exporter();
+        let jobs = [
+            job(&first, "sound"),
+            job(&second, "sound"),
+            job(&first, "sound"),
+        ];
+
+        let mut seen = vec![];
+        let progress = exporter
+            .with_threads(1)
+            .run(jobs.clone(), |event, progress| {
+                let BatchEvent::Exported { job, files } = event else {
+                    panic!("Unexpected {event:?}");
+                };
+                assert_eq!(files[0].name, "symbol1.wav");
+                seen.push((job.clone(), *progress));
+            });
+
+        assert_eq!(
+            progress,
+            BatchProgress {
+                total: 3,
+                exported: 3,
+                failed: 0,
+            }
+        );
+        assert_eq!(players.load(Ordering::SeqCst), 2);
+        // Jobs in the same movie are run together, in the order they were given.
+        let order: Vec<_> = seen.iter().map(|(job, _)| job.clone()).collect();
+        assert_eq!(order, [jobs[0].clone(), jobs[2].clone(), jobs[1].clone()]);
+        let finished: Vec<_> = seen
+            .iter()
+            .map(|(_, progress)| progress.finished())
+            .collect();
+        assert_eq!(finished, [1, 2, 3]);
+    }
+
+    #[test]
+    fn failed_jobs_are_retried_then_given_up_on() {
+        let swf = sound_movie("failing.swf");
+        let (exporter, _) = counting_exporter();
+        let mut retries = vec![];
+        let mut failures = 0;
+        let progress = exporter.with_max_attempts(3).run(
+            [job(&swf, "unknown format"), job(&swf, "sound")],
+            |event, _| match event {
+                BatchEvent::Retrying { job, attempt, .. } => {
+                    assert_eq!(job.format, "unknown format");
+                    retries.push(*attempt);
+                }
+                BatchEvent::Failed { job, .. } => {
+                    assert_eq!(job.format, "unknown format");
+                    failures += 1;
+                }
+                BatchEvent::Exported { job, .. } => assert_eq!(job.format, "sound"),
+            },
+        );
+
+        assert_eq!(retries, [1, 2]);
+        assert_eq!(failures, 1);
+        assert_eq!(progress.exported, 1);
+        assert_eq!(progress.failed, 1);
+    }
+
+    #[test]
+    fn panics_are_caught_and_retried_with_a_new_player() {
+        let swf = sound_movie("panicking.swf");
+        let (exporter, players) = counting_exporter();
+        let mut errors = vec![];
+        let progress =
+            exporter
+                .with_threads(1)
+                .with_max_attempts(2)
+                .run([job(&swf, "panics")], |event, _| match event {
+                    BatchEvent::Retrying { error, .. } | BatchEvent::Failed { error, .. } => {
+                        errors.push(error.clone())
+                    }
+                    BatchEvent::Exported { .. } => panic!("Unexpected {event:?}"),
+                });
+
+        assert_eq!(progress.failed, 1);
+        assert_eq!(errors, ["Panicked: Can't export this"; 2]);
+        assert_eq!(players.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn missing_movies_fail() {
+        let (exporter, players) = counting_exporter();
+        let missing = std::env::temp_dir().join("ruffle_batch_test_missing.swf");
+        let progress = exporter
+            .with_max_attempts(1)
+            .run([job(&missing, "sound")], |event, _| {
+                assert!(matches!(event, BatchEvent::Failed { .. }), "{event:?}");
+            });
+        assert_eq!(progress.failed, 1);
+        assert_eq!(players.load(Ordering::SeqCst), 0);
+    }
+}
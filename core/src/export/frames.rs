@@ -0,0 +1,156 @@
+//! Frames of a symbol as separate PNG images.
+
+use super::{
+    encode_png, ExportContent, ExportItem, ExportServices, ExportedFile, ItemExporterPlugin,
+};
+use serde::Serialize;
+
+/// Renders frames of a symbol, each to a PNG image of its own.
+///
+/// Each image is cropped to what's drawn in that frame, so frames of an animation may differ
+/// in size. The spritesheet format keeps them aligned instead.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PngExporter {
+    /// How much to scale the symbol by when rendering it.
+    pub scale: f64,
+
+    /// The only frame to render, counting from 1. Every frame is rendered if this is `None`.
+    pub frame: Option<u16>,
+}
+
+impl Default for PngExporter {
+    fn default() -> Self {
+        Self {
+            scale: 1.0,
+            frame: None,
+        }
+    }
+}
+
+impl ItemExporterPlugin for PngExporter {
+    fn format_name(&self) -> &str {
+        "png"
+    }
+
+    fn settings(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap_or_default()
+    }
+
+    fn supports(&self, item: &ExportItem<'_>) -> bool {
+        !matches!(item.content, ExportContent::Sound(_))
+    }
+
+    fn export(
+        &self,
+        item: &ExportItem<'_>,
+        services: &mut dyn ExportServices,
+    ) -> Result<Vec<ExportedFile>, Box<dyn std::error::Error + Send + Sync>> {
+        let num_frames = services.num_frames();
+        let frames = match self.frame {
+            Some(frame) => frame..=frame,
+            None => 1..=num_frames,
+        };
+
+        let mut files = vec![];
+        for frame in frames {
+            // Frames with nothing to draw are left out.
+            let Some(rendered) = services.render_frame(frame, self.scale) else {
+                continue;
+            };
+            let name = if num_frames == 1 || self.frame.is_some() {
+                format!("{}.png", item.file_stem())
+            } else {
+                format!("{}_{frame}.png", item.file_stem())
+            };
+            let image = &rendered.image;
+            files.push(ExportedFile {
+                name,
+                data: encode_png(image.width, image.height, &image.rgba)?,
+            });
+        }
+
+        if files.is_empty() {
+            return Err("Nothing was drawn".into());
+        }
+        Ok(files)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::{ExportImage, RenderedFrame};
+    use crate::tag_utils::SwfMovie;
+
+    /// Draws a 1x1 pixel in every frame but the second, scaled by `scale`.
+    struct Frames {
+        num_frames: u16,
+        drawn: Vec<(u16, f64)>,
+    }
+
+    impl ExportServices for Frames {
+        fn num_frames(&self) -> u16 {
+            self.num_frames
+        }
+
+        fn render_frame(&mut self, frame: u16, scale: f64) -> Option<RenderedFrame> {
+            self.drawn.push((frame, scale));
+            let size = scale as u32;
+            (frame != 2).then(|| RenderedFrame {
+                image: ExportImage {
+                    width: size,
+                    height: size,
+                    rgba: vec![255; (size * size * 4) as usize],
+                },
+                origin_x: 0.0,
+                origin_y: 0.0,
+            })
+        }
+    }
+
+    fn export(exporter: PngExporter, num_frames: u16) -> (Vec<String>, Vec<(u16, f64)>) {
+        let item = ExportItem {
+            id: 5,
+            name: Some("walk".into()),
+            content: ExportContent::Symbol(SwfMovie::empty(10)),
+        };
+        let mut services = Frames {
+            num_frames,
+            drawn: vec![],
+        };
+        let names = match exporter.export(&item, &mut services) {
+            Ok(files) => files.into_iter().map(|file| file.name).collect(),
+            Err(_) => vec![],
+        };
+        (names, services.drawn)
+    }
+
+    #[test]
+    fn every_drawn_frame_is_exported() {
+        let (names, drawn) = export(PngExporter::default(), 3);
+        assert_eq!(names, ["walk_1.png", "walk_3.png"]);
+        assert_eq!(drawn, [(1, 1.0), (2, 1.0), (3, 1.0)]);
+
+        let (names, _) = export(PngExporter::default(), 1);
+        assert_eq!(names, ["walk.png"]);
+    }
+
+    #[test]
+    fn a_single_frame_is_exported_scaled() {
+        let exporter = PngExporter {
+            scale: 2.0,
+            frame: Some(3),
+        };
+        let (names, drawn) = export(exporter, 3);
+        assert_eq!(names, ["walk.png"]);
+        assert_eq!(drawn, [(3, 2.0)]);
+
+        // An empty frame has nothing to export.
+        let exporter = PngExporter {
+            scale: 1.0,
+            frame: Some(2),
+        };
+        assert_eq!(export(exporter, 3).0, Vec::<String>::new());
+    }
+}
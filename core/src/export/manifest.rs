@@ -0,0 +1,381 @@
+//! Manifests recording where exported files came from and how they were made.
+
+use super::{
+    ColorVariant, ExportContent, ExportImage, ExportItem, ExportServices, ExportedFile,
+    ItemExporterPlugin, RenderedFrame,
+};
+use crate::tag_utils::SwfMovie;
+use serde_json::{json, Value};
+#[cfg(feature = "sha2")]
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use swf::CharacterId;
+
+/// How many of an item's most common colors its manifest lists.
+const DOMINANT_COLORS: usize = 8;
+
+/// Where an item came from, beyond what `ExportItem` holds.
+pub(super) struct Provenance<'a> {
+    pub movie: &'a SwfMovie,
+
+    /// Every name the item is exported or linked with.
+    pub names: &'a [String],
+
+    /// The characters the item uses.
+    pub dependencies: &'a [CharacterId],
+
+    /// How the item was recolored.
+    pub variant: &'a ColorVariant,
+}
+
+/// Describes an item and the files exported from it, as `<stem>.manifest.json`.
+///
+/// A manifest only depends on the movie, the item and the exporter's settings, so exporting an
+/// item again the same way reproduces it, and the hashes in it can be used to deduplicate
+/// archives. Hashes are only included with the `sha2` feature.
+///
+/// `appearance` already holds the frames the plugin rendered at full size, so only the others
+/// are rendered here.
+pub(super) fn write_manifest(
+    item: &ExportItem<'_>,
+    provenance: &Provenance<'_>,
+    plugin: &dyn ItemExporterPlugin,
+    services: &mut dyn ExportServices,
+    mut appearance: Appearance,
+    files: &[ExportedFile],
+) -> Result<ExportedFile, serde_json::Error> {
+    let movie = provenance.movie;
+    let build_info = movie.build_info();
+
+    let (kind, frame_count) = match &item.content {
+        ExportContent::Bitmap(image) => {
+            appearance.add(image, 0.0, 0.0);
+            ("bitmap", Some(1))
+        }
+        ExportContent::Sound(_) => ("sound", None),
+        ExportContent::Symbol(_) => {
+            let num_frames = services.num_frames();
+            for frame in 1..=num_frames {
+                if appearance.has_frame(frame) {
+                    continue;
+                }
+                if let Some(rendered) = services.render_frame(frame, 1.0) {
+                    appearance.add_frame(frame, &rendered);
+                }
+            }
+            ("symbol", Some(num_frames))
+        }
+    };
+
+    let manifest = json!({
+        "source": {
+            "url": movie.url(),
+            // Of the uncompressed movie, so it doesn't depend on how the file was compressed.
+            "sha256": sha256(movie.data()),
+            "swfVersion": movie.version(),
+            "actionScript3": movie.is_action_script_3(),
+            "createDate": movie.xmp_create_date(),
+            "compiler": build_info.compiler(),
+            "debugId": build_info.debug_id_string(),
+        },
+        "item": {
+            "characterId": item.id,
+            "kind": kind,
+            "exportNames": provenance.names,
+            "dependencies": provenance.dependencies,
+            "frameCount": frame_count,
+            "bounds": appearance.bounds_json(),
+            "palette": appearance.palette_json(),
+            "variant": provenance.variant.name,
+            "recolor": provenance.variant.recolor.to_json(),
+        },
+        "exporter": {
+            "format": plugin.format_name(),
+            "settings": plugin.settings(),
+            "ruffleVersion": env!("CARGO_PKG_VERSION"),
+        },
+        "files": files
+            .iter()
+            .map(|file| {
+                json!({
+                    "name": file.name,
+                    "size": file.data.len(),
+                    "sha256": sha256(&file.data),
+                })
+            })
+            .collect::<Vec<_>>(),
+    });
+
+    Ok(ExportedFile {
+        name: format!("{}.manifest.json", item.file_stem()),
+        data: serde_json::to_vec_pretty(&manifest)?,
+    })
+}
+
+#[cfg(feature = "sha2")]
+fn sha256(data: &[u8]) -> Option<String> {
+    Some(format!("{:x}", Sha256::digest(data)))
+}
+
+#[cfg(not(feature = "sha2"))]
+fn sha256(_data: &[u8]) -> Option<String> {
+    None
+}
+
+/// The area an item covers and the colors it uses, over all of its frames.
+#[derive(Default)]
+pub(super) struct Appearance {
+    /// The frames of a symbol added so far.
+    frames: HashSet<u16>,
+
+    /// `[x_min, y_min, x_max, y_max]` in pixels, relative to the registration point.
+    bounds: Option<[f64; 4]>,
+
+    /// How many visible pixels have each color.
+    colors: HashMap<[u8; 3], u64>,
+
+    /// Whether any pixel is partially transparent.
+    translucent: bool,
+}
+
+impl Appearance {
+    pub(super) fn has_frame(&self, frame: u16) -> bool {
+        self.frames.contains(&frame)
+    }
+
+    /// Adds a frame of a symbol rendered at full size, unless it was already added.
+    pub(super) fn add_frame(&mut self, frame: u16, rendered: &RenderedFrame) {
+        if self.frames.insert(frame) {
+            self.add(&rendered.image, rendered.origin_x, rendered.origin_y);
+        }
+    }
+
+    fn add(&mut self, image: &ExportImage, origin_x: f64, origin_y: f64) {
+        let frame_bounds = [
+            -origin_x,
+            -origin_y,
+            image.width as f64 - origin_x,
+            image.height as f64 - origin_y,
+        ];
+        self.bounds = Some(match self.bounds {
+            Some([x_min, y_min, x_max, y_max]) => [
+                x_min.min(frame_bounds[0]),
+                y_min.min(frame_bounds[1]),
+                x_max.max(frame_bounds[2]),
+                y_max.max(frame_bounds[3]),
+            ],
+            None => frame_bounds,
+        });
+
+        for pixel in image.rgba.chunks_exact(4) {
+            match pixel[3] {
+                0 => continue,
+                255 => {}
+                _ => self.translucent = true,
+            }
+            *self
+                .colors
+                .entry([pixel[0], pixel[1], pixel[2]])
+                .or_default() += 1;
+        }
+    }
+
+    fn bounds_json(&self) -> Value {
+        match self.bounds {
+            Some([x_min, y_min, x_max, y_max]) => json!({
+                "xMin": x_min,
+                "yMin": y_min,
+                "xMax": x_max,
+                "yMax": y_max,
+            }),
+            None => Value::Null,
+        }
+    }
+
+    fn palette_json(&self) -> Value {
+        if self.bounds.is_none() {
+            return Value::Null;
+        }
+
+        let total: u64 = self.colors.values().sum();
+        let mut colors: Vec<_> = self.colors.iter().collect();
+        // Ties are broken by color, so the order doesn't depend on the hash map's.
+        colors.sort_by(|(a, a_count), (b, b_count)| b_count.cmp(a_count).then(a.cmp(b)));
+        let dominant: Vec<_> = colors
+            .into_iter()
+            .take(DOMINANT_COLORS)
+            .map(|([r, g, b], &count)| {
+                json!({
+                    "color": format!("#{r:02x}{g:02x}{b:02x}"),
+                    "share": count as f64 / total as f64,
+                })
+            })
+            .collect();
+
+        json!({
+            "uniqueColors": self.colors.len(),
+            "translucent": self.translucent,
+            "dominant": dominant,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Draws a single red pixel in every frame.
+    struct RedPixels {
+        num_frames: u16,
+        drawn: Vec<u16>,
+    }
+
+    impl ExportServices for RedPixels {
+        fn num_frames(&self) -> u16 {
+            self.num_frames
+        }
+
+        fn render_frame(&mut self, frame: u16, _scale: f64) -> Option<RenderedFrame> {
+            self.drawn.push(frame);
+            Some(red_pixel())
+        }
+    }
+
+    fn red_pixel() -> RenderedFrame {
+        RenderedFrame {
+            image: ExportImage {
+                width: 1,
+                height: 1,
+                rgba: vec![255, 0, 0, 255],
+            },
+            origin_x: 0.0,
+            origin_y: 0.0,
+        }
+    }
+
+    struct Plugin;
+
+    impl ItemExporterPlugin for Plugin {
+        fn format_name(&self) -> &str {
+            "test"
+        }
+
+        fn settings(&self) -> Value {
+            json!({ "scale": 1 })
+        }
+
+        fn export(
+            &self,
+            _item: &ExportItem<'_>,
+            _services: &mut dyn ExportServices,
+        ) -> Result<Vec<ExportedFile>, Box<dyn std::error::Error + Send + Sync>> {
+            Ok(vec![])
+        }
+    }
+
+    fn manifest(
+        content: ExportContent<'_>,
+        services: &mut RedPixels,
+        appearance: Appearance,
+        files: &[ExportedFile],
+    ) -> Value {
+        let movie = SwfMovie::empty(10);
+        let item = ExportItem {
+            id: 5,
+            name: Some("walk".into()),
+            content,
+        };
+        let provenance = Provenance {
+            movie: &movie,
+            names: &["walk".to_string()],
+            dependencies: &[2, 3],
+            variant: &ColorVariant::default(),
+        };
+        let file =
+            write_manifest(&item, &provenance, &Plugin, services, appearance, files).unwrap();
+        assert_eq!(file.name, "walk.manifest.json");
+        serde_json::from_slice(&file.data).unwrap()
+    }
+
+    #[test]
+    fn frames_rendered_by_the_plugin_are_reused() {
+        let mut appearance = Appearance::default();
+        appearance.add_frame(1, &red_pixel());
+        appearance.add_frame(3, &red_pixel());
+        let mut services = RedPixels {
+            num_frames: 3,
+            drawn: vec![],
+        };
+
+        let manifest = manifest(
+            ExportContent::Symbol(SwfMovie::empty(10)),
+            &mut services,
+            appearance,
+            &[],
+        );
+        assert_eq!(services.drawn, [2]);
+        assert_eq!(manifest["item"]["kind"], "symbol");
+        assert_eq!(manifest["item"]["frameCount"], 3);
+        assert_eq!(manifest["item"]["palette"]["uniqueColors"], 1);
+        assert_eq!(
+            manifest["item"]["palette"]["dominant"][0]["color"],
+            "#ff0000"
+        );
+    }
+
+    #[test]
+    fn items_and_files_are_described() {
+        let image = ExportImage {
+            width: 2,
+            height: 1,
+            rgba: vec![0, 0, 255, 255, 0, 0, 0, 0],
+        };
+        let files = [ExportedFile {
+            name: "walk.png".into(),
+            data: vec![1, 2, 3],
+        }];
+        let mut services = RedPixels {
+            num_frames: 1,
+            drawn: vec![],
+        };
+
+        let manifest = manifest(
+            ExportContent::Bitmap(image),
+            &mut services,
+            Appearance::default(),
+            &files,
+        );
+        assert!(services.drawn.is_empty());
+
+        let item = &manifest["item"];
+        assert_eq!(item["characterId"], 5);
+        assert_eq!(item["kind"], "bitmap");
+        assert_eq!(item["exportNames"], json!(["walk"]));
+        assert_eq!(item["dependencies"], json!([2, 3]));
+        assert_eq!(
+            item["bounds"],
+            json!({ "xMin": 0.0, "yMin": 0.0, "xMax": 2.0, "yMax": 1.0 })
+        );
+        // The transparent pixel doesn't count.
+        assert_eq!(
+            item["palette"],
+            json!({
+                "uniqueColors": 1,
+                "translucent": false,
+                "dominant": [{ "color": "#0000ff", "share": 1.0 }],
+            })
+        );
+
+        assert_eq!(manifest["exporter"]["format"], "test");
+        assert_eq!(manifest["exporter"]["settings"], json!({ "scale": 1 }));
+        assert_eq!(manifest["files"][0]["name"], "walk.png");
+        assert_eq!(manifest["files"][0]["size"], 3);
+        #[cfg(feature = "sha2")]
+        assert_eq!(
+            manifest["files"][0]["sha256"],
+            "039058c6f2c0cb492c533b0a4d14ef77cc0f78abccced5287d84a1a2011cfb81"
+        );
+        #[cfg(not(feature = "sha2"))]
+        assert_eq!(manifest["files"][0]["sha256"], Value::Null);
+    }
+}
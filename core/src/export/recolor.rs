@@ -0,0 +1,153 @@
+//! Recoloring items as they're exported, such as to make every color variant of a symbol.
+
+use super::ExportImage;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use swf::{Color, ColorTransform};
+
+/// A change of colors applied to an item before it's exported.
+///
+/// This changes what's rendered and the pixels of bitmaps, but not symbols extracted as movies.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Recolor {
+    /// Multiplies and offsets colors, as `DisplayObject.transform.colorTransform` does.
+    ///
+    /// Rendered frames get this through the same pipeline as content's own color transforms.
+    Transform(ColorTransform),
+
+    /// Replaces some colors with others.
+    Palette(PaletteMap),
+}
+
+impl Default for Recolor {
+    fn default() -> Self {
+        Recolor::Transform(ColorTransform::IDENTITY)
+    }
+}
+
+impl Recolor {
+    pub fn is_identity(&self) -> bool {
+        match self {
+            Recolor::Transform(transform) => *transform == ColorTransform::IDENTITY,
+            Recolor::Palette(palette) => palette.colors.is_empty(),
+        }
+    }
+
+    /// Recolors an image in place.
+    pub fn apply(&self, image: &mut ExportImage) {
+        if self.is_identity() {
+            return;
+        }
+
+        for pixel in image.rgba.chunks_exact_mut(4) {
+            let color = Color {
+                r: pixel[0],
+                g: pixel[1],
+                b: pixel[2],
+                a: pixel[3],
+            };
+            let color = match self {
+                Recolor::Transform(transform) => transform * color,
+                Recolor::Palette(palette) => palette.map(color),
+            };
+            pixel.copy_from_slice(&[color.r, color.g, color.b, color.a]);
+        }
+    }
+
+    pub(super) fn to_json(&self) -> Value {
+        match self {
+            Recolor::Transform(transform) => json!({
+                "colorTransform": {
+                    "multiply": transform.mult_rgba_normalized(),
+                    "add": [transform.r_add, transform.g_add, transform.b_add, transform.a_add],
+                },
+            }),
+            Recolor::Palette(palette) => json!({
+                "palette": {
+                    "colors": palette
+                        .colors
+                        .iter()
+                        .map(|(from, to)| (format!("#{from:06x}"), json!(format!("#{to:06x}"))))
+                        .collect::<serde_json::Map<_, _>>(),
+                    "tolerance": palette.tolerance,
+                },
+            }),
+        }
+    }
+}
+
+/// A lookup table from colors to the colors that replace them, as `0xRRGGBB`.
+///
+/// Alpha is kept as it is, and colors not in the table are left alone.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PaletteMap {
+    colors: BTreeMap<u32, u32>,
+    tolerance: u8,
+}
+
+impl PaletteMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replaces `from` with `to`.
+    pub fn with_color(mut self, from: u32, to: u32) -> Self {
+        self.colors.insert(from & 0xFFFFFF, to & 0xFFFFFF);
+        self
+    }
+
+    /// Also replaces colors whose channels are all within `tolerance` of a color in the table,
+    /// such as the antialiased edges of a shape. The nearest such color is used.
+    pub fn with_tolerance(mut self, tolerance: u8) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    fn map(&self, color: Color) -> Color {
+        if color.a == 0 {
+            return color;
+        }
+
+        let rgb = color.to_rgb();
+        let replacement = self.colors.get(&rgb).copied().or_else(|| {
+            if self.tolerance == 0 {
+                return None;
+            }
+            self.colors
+                .iter()
+                .map(|(&from, &to)| (channel_distance(from, rgb), to))
+                .filter(|&(distance, _)| distance <= self.tolerance)
+                .min_by_key(|&(distance, _)| distance)
+                .map(|(_, to)| to)
+        });
+        match replacement {
+            Some(to) => Color::from_rgb(to, color.a),
+            None => color,
+        }
+    }
+}
+
+/// The largest difference between any channel of two `0xRRGGBB` colors.
+fn channel_distance(a: u32, b: u32) -> u8 {
+    let a = a.to_be_bytes();
+    let b = b.to_be_bytes();
+    (1..4).map(|i| a[i].abs_diff(b[i])).max().unwrap_or(0)
+}
+
+/// A named recoloring, exported with its name appended to the item's.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ColorVariant {
+    /// Appended to the names of the exported files. If empty, the names are left as they are.
+    pub name: String,
+
+    pub recolor: Recolor,
+}
+
+impl ColorVariant {
+    pub fn new(name: impl Into<String>, recolor: Recolor) -> Self {
+        Self {
+            name: name.into(),
+            recolor,
+        }
+    }
+}
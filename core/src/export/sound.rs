@@ -0,0 +1,131 @@
+//! Sounds, as MP3 files if they're stored that way and as WAV files otherwise.
+
+use super::{ExportContent, ExportItem, ExportServices, ExportedFile, ItemExporterPlugin};
+use swf::{AudioCompression, Sound};
+
+/// Exports sounds without re-encoding them where possible.
+///
+/// MP3 sounds are written out as they are, and uncompressed ones only gain a WAV header. Other
+/// compressions are decoded to 16-bit WAV, which needs the `audio` feature.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SoundExporter;
+
+impl ItemExporterPlugin for SoundExporter {
+    fn format_name(&self) -> &str {
+        "sound"
+    }
+
+    fn supports(&self, item: &ExportItem<'_>) -> bool {
+        matches!(item.content, ExportContent::Sound(_))
+    }
+
+    fn export(
+        &self,
+        item: &ExportItem<'_>,
+        _services: &mut dyn ExportServices,
+    ) -> Result<Vec<ExportedFile>, Box<dyn std::error::Error + Send + Sync>> {
+        let ExportContent::Sound(sound) = &item.content else {
+            return Err("Only sounds can be exported as sounds".into());
+        };
+        let (extension, data) = match sound.format.compression {
+            // MP3 data starts with the number of samples to skip, which isn't part of the stream.
+            AudioCompression::Mp3 => ("mp3", sound.data.get(2..).unwrap_or_default().to_vec()),
+            AudioCompression::Uncompressed | AudioCompression::UncompressedUnknownEndian => {
+                let format = WavFormat {
+                    sample_rate: sound.format.sample_rate.into(),
+                    num_channels: if sound.format.is_stereo { 2 } else { 1 },
+                    bits_per_sample: if sound.format.is_16_bit { 16 } else { 8 },
+                };
+                ("wav", write_wav(format, sound.data))
+            }
+            _ => ("wav", decode_to_wav(sound)?),
+        };
+        Ok(vec![ExportedFile {
+            name: format!("{}.{extension}", item.file_stem()),
+            data,
+        }])
+    }
+}
+
+#[cfg(feature = "audio")]
+fn decode_to_wav(sound: &Sound<'_>) -> Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync>> {
+    use crate::backend::audio::decoders::make_decoder;
+
+    let decoder = make_decoder(&sound.format, std::io::Cursor::new(sound.data.to_vec()))?;
+    let format = WavFormat {
+        sample_rate: decoder.sample_rate().into(),
+        num_channels: decoder.num_channels().into(),
+        bits_per_sample: 16,
+    };
+    // Decoders duplicate mono samples across both channels, so only the left one is kept.
+    let stereo = format.num_channels == 2;
+    let mut samples = vec![];
+    for [left, right] in decoder {
+        samples.extend_from_slice(&left.to_le_bytes());
+        if stereo {
+            samples.extend_from_slice(&right.to_le_bytes());
+        }
+    }
+    Ok(write_wav(format, &samples))
+}
+
+#[cfg(not(feature = "audio"))]
+fn decode_to_wav(sound: &Sound<'_>) -> Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync>> {
+    Err(format!(
+        "Decoding {:?} sounds needs the audio feature",
+        sound.format.compression
+    )
+    .into())
+}
+
+#[derive(Debug, Clone, Copy)]
+struct WavFormat {
+    sample_rate: u32,
+    num_channels: u16,
+    bits_per_sample: u16,
+}
+
+/// Wraps little-endian PCM samples in a WAV header. As in WAV, 8-bit samples are unsigned.
+fn write_wav(format: WavFormat, samples: &[u8]) -> Vec<u8> {
+    let block_align = format.num_channels * format.bits_per_sample / 8;
+    let byte_rate = format.sample_rate * u32::from(block_align);
+    let data_len = samples.len() as u32;
+
+    let mut wav = Vec::with_capacity(44 + samples.len());
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+    wav.extend_from_slice(b"WAVEfmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes());
+    wav.extend_from_slice(&1u16.to_le_bytes());
+    wav.extend_from_slice(&format.num_channels.to_le_bytes());
+    wav.extend_from_slice(&format.sample_rate.to_le_bytes());
+    wav.extend_from_slice(&byte_rate.to_le_bytes());
+    wav.extend_from_slice(&block_align.to_le_bytes());
+    wav.extend_from_slice(&format.bits_per_sample.to_le_bytes());
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&data_len.to_le_bytes());
+    wav.extend_from_slice(samples);
+    wav
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wav_header() {
+        let format = WavFormat {
+            sample_rate: 22050,
+            num_channels: 2,
+            bits_per_sample: 16,
+        };
+        let wav = write_wav(format, &[1, 2, 3, 4]);
+        assert_eq!(wav.len(), 48);
+        assert_eq!(&wav[0..4], b"RIFF");
+        assert_eq!(u32::from_le_bytes(wav[4..8].try_into().unwrap()), 40);
+        assert_eq!(u32::from_le_bytes(wav[28..32].try_into().unwrap()), 88200);
+        assert_eq!(u16::from_le_bytes(wav[32..34].try_into().unwrap()), 4);
+        assert_eq!(&wav[36..40], b"data");
+        assert_eq!(&wav[44..], [1, 2, 3, 4]);
+    }
+}
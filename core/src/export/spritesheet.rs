@@ -0,0 +1,479 @@
+//! Spritesheets: every frame of a symbol packed into one texture atlas.
+
+use super::{
+    encode_png, ExportContent, ExportImage, ExportItem, ExportServices, ExportedFile,
+    ItemExporterPlugin,
+};
+use serde::Serialize;
+use serde_json::{json, Map};
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+/// Exports the frames of a symbol as a PNG texture atlas, described by JSON in TexturePacker's
+/// "JSON (Hash)" format, which most engines can import.
+///
+/// Frames share one source size, so the symbol's registration point (given as each frame's
+/// `pivot`) stays in place throughout the animation. Identical frames are only packed once.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpritesheetExporter {
+    /// How much to scale the symbol by when rendering it.
+    pub scale: f64,
+
+    /// Transparent pixels left between frames in the atlas.
+    pub padding: u32,
+
+    /// Whether transparent borders are cut off frames.
+    pub trim: bool,
+
+    /// Whether frames may be rotated by 90 degrees clockwise to pack them tighter.
+    pub allow_rotation: bool,
+
+    /// The largest width or height the atlas may have.
+    pub max_size: u32,
+}
+
+impl Default for SpritesheetExporter {
+    fn default() -> Self {
+        Self {
+            scale: 1.0,
+            padding: 2,
+            trim: true,
+            allow_rotation: true,
+            max_size: 4096,
+        }
+    }
+}
+
+/// A frame, as it will be packed.
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct Sprite {
+    /// The frame's pixels, trimmed if enabled.
+    image: ExportImage,
+
+    /// Where `image` lies in the source size shared by every frame.
+    source_x: u32,
+    source_y: u32,
+}
+
+impl ItemExporterPlugin for SpritesheetExporter {
+    fn format_name(&self) -> &str {
+        "spritesheet"
+    }
+
+    fn settings(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap_or_default()
+    }
+
+    fn supports(&self, item: &ExportItem<'_>) -> bool {
+        !matches!(item.content, ExportContent::Sound(_))
+    }
+
+    fn export(
+        &self,
+        item: &ExportItem<'_>,
+        services: &mut dyn ExportServices,
+    ) -> Result<Vec<ExportedFile>, Box<dyn std::error::Error + Send + Sync>> {
+        let frames: Vec<_> = (1..=services.num_frames())
+            .map(|frame| services.render_frame(frame, self.scale))
+            .collect();
+
+        // The area covered by every frame, relative to the registration point.
+        let (left, top, right, bottom) = frames.iter().flatten().fold(
+            (f64::MAX, f64::MAX, f64::MIN, f64::MIN),
+            |(left, top, right, bottom), frame| {
+                (
+                    left.min(-frame.origin_x),
+                    top.min(-frame.origin_y),
+                    right.max(frame.image.width as f64 - frame.origin_x),
+                    bottom.max(frame.image.height as f64 - frame.origin_y),
+                )
+            },
+        );
+        if left > right {
+            return Err(format!("{} has nothing to render", item.file_stem()).into());
+        }
+        let source_width = ((right - left).ceil() as u32).max(1);
+        let source_height = ((bottom - top).ceil() as u32).max(1);
+
+        let mut sprites = vec![];
+        let mut sprite_indices = HashMap::new();
+        let mut frame_sprites = Vec::with_capacity(frames.len());
+        for frame in &frames {
+            let sprite = match frame {
+                Some(frame) => self.crop(
+                    &frame.image,
+                    (-frame.origin_x - left).round().max(0.0) as u32,
+                    (-frame.origin_y - top).round().max(0.0) as u32,
+                ),
+                None => Sprite {
+                    image: ExportImage {
+                        width: 1,
+                        height: 1,
+                        rgba: vec![0; 4],
+                    },
+                    source_x: 0,
+                    source_y: 0,
+                },
+            };
+            let index = *sprite_indices.entry(sprite.clone()).or_insert_with(|| {
+                sprites.push(sprite);
+                sprites.len() - 1
+            });
+            frame_sprites.push(index);
+        }
+
+        let sizes: Vec<_> = sprites
+            .iter()
+            .map(|sprite| (sprite.image.width, sprite.image.height))
+            .collect();
+        let Some((atlas_width, atlas_height, placements)) =
+            pack(&sizes, self.padding, self.allow_rotation, self.max_size)
+        else {
+            return Err(format!(
+                "The frames of {} don't fit in a {}x{} atlas",
+                item.file_stem(),
+                self.max_size,
+                self.max_size
+            )
+            .into());
+        };
+
+        let mut atlas = vec![0; atlas_width as usize * atlas_height as usize * 4];
+        for (sprite, placement) in sprites.iter().zip(&placements) {
+            let image = &sprite.image;
+            for y in 0..image.height {
+                for x in 0..image.width {
+                    let (atlas_x, atlas_y) = if placement.rotated {
+                        (placement.x + image.height - 1 - y, placement.y + x)
+                    } else {
+                        (placement.x + x, placement.y + y)
+                    };
+                    let from = (y as usize * image.width as usize + x as usize) * 4;
+                    let to = (atlas_y as usize * atlas_width as usize + atlas_x as usize) * 4;
+                    atlas[to..to + 4].copy_from_slice(&image.rgba[from..from + 4]);
+                }
+            }
+        }
+
+        let stem = item.file_stem();
+        let image_name = format!("{stem}.png");
+        let mut frames_json = Map::new();
+        for (frame, &index) in frame_sprites.iter().enumerate() {
+            let sprite = &sprites[index];
+            let placement = &placements[index];
+            let (width, height) = (sprite.image.width, sprite.image.height);
+            let trimmed = sprite.source_x != 0
+                || sprite.source_y != 0
+                || width != source_width
+                || height != source_height;
+            frames_json.insert(
+                format!("{stem}_{:04}", frame + 1),
+                json!({
+                    "frame": { "x": placement.x, "y": placement.y, "w": width, "h": height },
+                    "rotated": placement.rotated,
+                    "trimmed": trimmed,
+                    "spriteSourceSize": {
+                        "x": sprite.source_x,
+                        "y": sprite.source_y,
+                        "w": width,
+                        "h": height,
+                    },
+                    "sourceSize": { "w": source_width, "h": source_height },
+                    "pivot": {
+                        "x": -left / source_width as f64,
+                        "y": -top / source_height as f64,
+                    },
+                }),
+            );
+        }
+        let description = json!({
+            "frames": frames_json,
+            "meta": {
+                "app": "https://ruffle.rs",
+                "version": "1.0",
+                "image": image_name,
+                "format": "RGBA8888",
+                "size": { "w": atlas_width, "h": atlas_height },
+                "scale": self.scale.to_string(),
+            },
+        });
+
+        Ok(vec![
+            ExportedFile {
+                name: image_name,
+                data: encode_png(atlas_width, atlas_height, &atlas)?,
+            },
+            ExportedFile {
+                name: format!("{stem}.json"),
+                data: serde_json::to_vec_pretty(&description)?,
+            },
+        ])
+    }
+}
+
+impl SpritesheetExporter {
+    /// Cuts the transparent borders off a frame drawn at `(x, y)` in the source size.
+    fn crop(&self, image: &ExportImage, x: u32, y: u32) -> Sprite {
+        let opaque = |px: u32, py: u32| image.rgba[(py * image.width + px) as usize * 4 + 3] != 0;
+        let (mut min_x, mut min_y, mut max_x, mut max_y) = (0, 0, image.width, image.height);
+        if self.trim {
+            (min_x, min_y, max_x, max_y) = (image.width, image.height, 0, 0);
+            for py in 0..image.height {
+                for px in 0..image.width {
+                    if opaque(px, py) {
+                        min_x = min_x.min(px);
+                        min_y = min_y.min(py);
+                        max_x = max_x.max(px + 1);
+                        max_y = max_y.max(py + 1);
+                    }
+                }
+            }
+            if min_x >= max_x {
+                // Fully transparent; keep a single pixel.
+                (min_x, min_y, max_x, max_y) = (0, 0, 1, 1);
+            }
+        }
+
+        let width = max_x - min_x;
+        let height = max_y - min_y;
+        let mut rgba = Vec::with_capacity(width as usize * height as usize * 4);
+        for py in min_y..max_y {
+            let start = (py * image.width + min_x) as usize * 4;
+            rgba.extend_from_slice(&image.rgba[start..start + width as usize * 4]);
+        }
+
+        Sprite {
+            image: ExportImage {
+                width,
+                height,
+                rgba,
+            },
+            source_x: x + min_x,
+            source_y: y + min_y,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Rect {
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+}
+
+impl Rect {
+    fn right(&self) -> u32 {
+        self.x + self.width
+    }
+
+    fn bottom(&self) -> u32 {
+        self.y + self.height
+    }
+
+    fn contains(&self, other: &Rect) -> bool {
+        other.x >= self.x
+            && other.y >= self.y
+            && other.right() <= self.right()
+            && other.bottom() <= self.bottom()
+    }
+
+    fn intersects(&self, other: &Rect) -> bool {
+        self.x < other.right()
+            && other.x < self.right()
+            && self.y < other.bottom()
+            && other.y < self.bottom()
+    }
+}
+
+/// Where a sprite ended up in the atlas.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Placement {
+    x: u32,
+    y: u32,
+    rotated: bool,
+}
+
+/// Packs sprites into the smallest power-of-two atlas that fits them, returning its size and
+/// where each sprite went.
+fn pack(
+    sizes: &[(u32, u32)],
+    padding: u32,
+    allow_rotation: bool,
+    max_size: u32,
+) -> Option<(u32, u32, Vec<Placement>)> {
+    let area: u64 = sizes.iter().map(|&(w, h)| w as u64 * h as u64).sum();
+
+    // Big sprites first, while there's still room for them.
+    let mut order: Vec<usize> = (0..sizes.len()).collect();
+    order.sort_by_key(|&i| Reverse((sizes[i].0.max(sizes[i].1), sizes[i].0.min(sizes[i].1))));
+
+    let mut candidates = vec![];
+    let mut width = 1;
+    while width <= max_size {
+        let mut height = 1;
+        while height <= max_size {
+            if width as u64 * height as u64 >= area {
+                candidates.push((width, height));
+            }
+            height *= 2;
+        }
+        width *= 2;
+    }
+    candidates.sort_by_key(|&(w, h)| (w as u64 * h as u64, w.max(h)));
+
+    'candidates: for (width, height) in candidates {
+        // Padding is added after every sprite, so the atlas is grown by it too, to allow for
+        // the sprites at the right and bottom edges.
+        let mut packer = MaxRects::new(width + padding, height + padding);
+        let mut placements = vec![Placement::default(); sizes.len()];
+        for &i in &order {
+            let (w, h) = sizes[i];
+            match packer.insert(w + padding, h + padding, allow_rotation) {
+                Some(placement) => placements[i] = placement,
+                None => continue 'candidates,
+            }
+        }
+        return Some((width, height, placements));
+    }
+    None
+}
+
+/// The MaxRects bin packing algorithm, placing each sprite where it leaves the shortest side of
+/// free space.
+struct MaxRects {
+    free: Vec<Rect>,
+}
+
+impl MaxRects {
+    fn new(width: u32, height: u32) -> Self {
+        Self {
+            free: vec![Rect {
+                x: 0,
+                y: 0,
+                width,
+                height,
+            }],
+        }
+    }
+
+    fn insert(&mut self, width: u32, height: u32, allow_rotation: bool) -> Option<Placement> {
+        let mut best: Option<((u32, u32), Rect, bool)> = None;
+        for free in &self.free {
+            for (w, h, rotated) in [(width, height, false), (height, width, true)] {
+                if rotated && (!allow_rotation || width == height) {
+                    continue;
+                }
+                if w > free.width || h > free.height {
+                    continue;
+                }
+                let leftover_x = free.width - w;
+                let leftover_y = free.height - h;
+                let score = (leftover_x.min(leftover_y), leftover_x.max(leftover_y));
+                if best.map_or(true, |(best_score, _, _)| score < best_score) {
+                    let used = Rect {
+                        x: free.x,
+                        y: free.y,
+                        width: w,
+                        height: h,
+                    };
+                    best = Some((score, used, rotated));
+                }
+            }
+        }
+
+        let (_, used, rotated) = best?;
+        self.split(used);
+        Some(Placement {
+            x: used.x,
+            y: used.y,
+            rotated,
+        })
+    }
+
+    /// Removes `used` from the free space.
+    fn split(&mut self, used: Rect) {
+        let mut pieces = vec![];
+        self.free.retain(|free| {
+            if !free.intersects(&used) {
+                return true;
+            }
+            if used.x > free.x {
+                pieces.push(Rect {
+                    width: used.x - free.x,
+                    ..*free
+                });
+            }
+            if used.right() < free.right() {
+                pieces.push(Rect {
+                    x: used.right(),
+                    width: free.right() - used.right(),
+                    ..*free
+                });
+            }
+            if used.y > free.y {
+                pieces.push(Rect {
+                    height: used.y - free.y,
+                    ..*free
+                });
+            }
+            if used.bottom() < free.bottom() {
+                pieces.push(Rect {
+                    y: used.bottom(),
+                    height: free.bottom() - used.bottom(),
+                    ..*free
+                });
+            }
+            false
+        });
+        self.free.extend(pieces);
+
+        // Drop free areas that lie within others, keeping one of any duplicates.
+        let free = std::mem::take(&mut self.free);
+        for (i, rect) in free.iter().enumerate() {
+            let redundant = free
+                .iter()
+                .enumerate()
+                .any(|(j, other)| i != j && other.contains(rect) && (other != rect || j < i));
+            if !redundant {
+                self.free.push(*rect);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pack_without_overlaps() {
+        let sizes = [(30, 10), (10, 30), (16, 16), (16, 16), (40, 8), (5, 5)];
+        let (width, height, placements) = pack(&sizes, 1, true, 1024).unwrap();
+        assert!(width.is_power_of_two() && height.is_power_of_two());
+        assert!(width * height <= 64 * 64);
+
+        let rects: Vec<Rect> = sizes
+            .iter()
+            .zip(&placements)
+            .map(|(&(w, h), placement)| {
+                let (w, h) = if placement.rotated { (h, w) } else { (w, h) };
+                Rect {
+                    x: placement.x,
+                    y: placement.y,
+                    width: w,
+                    height: h,
+                }
+            })
+            .collect();
+        for (i, rect) in rects.iter().enumerate() {
+            assert!(rect.right() <= width && rect.bottom() <= height);
+            for other in &rects[i + 1..] {
+                assert!(!rect.intersects(other));
+            }
+        }
+
+        assert!(pack(&[(100, 100)], 0, true, 64).is_none());
+    }
+}
@@ -0,0 +1,273 @@
+//! Thumbnails: small previews of movies and their library items, such as for a grid of items.
+
+use super::{render_object, ExportImage};
+use crate::context::UpdateContext;
+use crate::display_object::{DisplayObject, MovieClip, TDisplayObject};
+use crate::player::Player;
+use crate::tag_utils::SwfMovie;
+use crate::vminterface::Instantiator;
+use indexmap::IndexMap;
+#[cfg(feature = "sha2")]
+use sha2::{Digest, Sha256};
+use std::hash::Hash;
+use std::sync::{Arc, Weak};
+use swf::{CharacterId, ColorTransform};
+
+/// What to preview.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ThumbnailSubject {
+    /// The first frame of the movie's main timeline, cropped to its stage.
+    Movie,
+
+    /// The first frame of a character of the movie, cropped to its bounds.
+    Symbol(CharacterId),
+}
+
+/// Renders thumbnails and keeps the most recently used ones.
+///
+/// Thumbnails are keyed by a hash of the movie's contents, so reloading the same file keeps
+/// its thumbnails.
+pub struct ThumbnailService {
+    cache: LruCache<ThumbnailKey, Arc<ExportImage>>,
+
+    /// The hashes of the movies seen so far, to avoid rehashing them on every lookup.
+    digests: Vec<(Weak<SwfMovie>, MovieDigest)>,
+}
+
+#[cfg(feature = "sha2")]
+type MovieDigest = [u8; 32];
+
+/// Without `sha2`, a 64-bit hash has to do. Collisions are unlikely among the movies a player
+/// sees, but not impossible.
+#[cfg(not(feature = "sha2"))]
+type MovieDigest = u64;
+
+#[cfg(feature = "sha2")]
+fn hash_movie(data: &[u8]) -> MovieDigest {
+    Sha256::digest(data).into()
+}
+
+#[cfg(not(feature = "sha2"))]
+fn hash_movie(data: &[u8]) -> MovieDigest {
+    use std::hash::Hasher;
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    hasher.write(data);
+    hasher.finish()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct ThumbnailKey {
+    movie: MovieDigest,
+    subject: ThumbnailSubject,
+    size: u32,
+}
+
+impl ThumbnailService {
+    /// The number of thumbnails kept by default.
+    pub const DEFAULT_CAPACITY: usize = 256;
+
+    /// Creates a service that keeps up to `capacity` thumbnails.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            cache: LruCache::new(capacity),
+            digests: vec![],
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.cache.capacity
+    }
+
+    /// Changes how many thumbnails are kept, dropping the least recently used ones if needed.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.cache.set_capacity(capacity);
+    }
+
+    /// Forgets every thumbnail.
+    pub fn clear(&mut self) {
+        self.cache.clear();
+        self.digests.clear();
+    }
+
+    /// Returns a thumbnail of `subject` that fits in a `size` by `size` square, rendering it if
+    /// it isn't cached.
+    ///
+    /// Returns `None` if the subject isn't displayable or is empty.
+    pub(crate) fn thumbnail(
+        &mut self,
+        player: &mut Player,
+        movie: &Arc<SwfMovie>,
+        subject: ThumbnailSubject,
+        size: u32,
+    ) -> Option<Arc<ExportImage>> {
+        let key = ThumbnailKey {
+            movie: self.digest(movie),
+            subject,
+            size,
+        };
+        if let Some(image) = self.cache.get(&key) {
+            return Some(image.clone());
+        }
+
+        // Instantiating the movie or its characters runs their scripts, which mustn't touch the
+        // player the movie is playing in.
+        let image = player.with_isolated_player(movie, |isolated, movie| {
+            isolated.mutate_with_update_context(|context| {
+                render_thumbnail(context, movie, subject, size)
+            })
+        });
+        let image = Arc::new(image?);
+        self.cache.insert(key, image.clone());
+        Some(image)
+    }
+
+    fn digest(&mut self, movie: &Arc<SwfMovie>) -> MovieDigest {
+        self.digests.retain(|(weak, _)| weak.strong_count() > 0);
+        let existing = self
+            .digests
+            .iter()
+            .find(|(weak, _)| std::ptr::eq(weak.as_ptr(), Arc::as_ptr(movie)));
+        if let Some((_, digest)) = existing {
+            return *digest;
+        }
+
+        let digest = hash_movie(movie.data());
+        self.digests.push((Arc::downgrade(movie), digest));
+        digest
+    }
+}
+
+impl Default for ThumbnailService {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_CAPACITY)
+    }
+}
+
+fn render_thumbnail(
+    context: &mut UpdateContext<'_, '_>,
+    movie: &Arc<SwfMovie>,
+    subject: ThumbnailSubject,
+    size: u32,
+) -> Option<ExportImage> {
+    // Movies are cropped to their stage rather than their contents.
+    let (object, stage): (DisplayObject<'_>, _) = match subject {
+        ThumbnailSubject::Movie => {
+            let clip = MovieClip::new_with_data(
+                context.gc_context,
+                0,
+                movie.clone().into(),
+                movie.num_frames(),
+            );
+            (clip.into(), Some(movie.stage_size().clone()))
+        }
+        ThumbnailSubject::Symbol(id) => {
+            let object = context
+                .library
+                .library_for_movie_mut(movie.clone())
+                .instantiate_by_id(id, context.gc_context)
+                .ok()?;
+            (object, None)
+        }
+    };
+    object.post_instantiation(context, None, Instantiator::Movie, false);
+    if let Some(clip) = object.as_movie_clip() {
+        clip.goto_frame(context, 1, true);
+    }
+    let bounds = stage.unwrap_or_else(|| object.bounds());
+
+    let largest_side = bounds.width().max(bounds.height()).to_pixels();
+    if largest_side <= 0.0 {
+        return None;
+    }
+    let scale = f64::from(size) / largest_side;
+    render_object(context, object, bounds, scale, ColorTransform::IDENTITY)
+        .map(|rendered| rendered.image)
+}
+
+/// A map that drops its least recently used entries when it grows past its capacity.
+struct LruCache<K, V> {
+    /// From least to most recently used.
+    entries: IndexMap<K, V>,
+    capacity: usize,
+}
+
+impl<K: Hash + Eq, V> LruCache<K, V> {
+    fn new(capacity: usize) -> Self {
+        Self {
+            entries: IndexMap::new(),
+            capacity,
+        }
+    }
+
+    fn get(&mut self, key: &K) -> Option<&V> {
+        let index = self.entries.get_index_of(key)?;
+        let last = self.entries.len() - 1;
+        self.entries.move_index(index, last);
+        self.entries.get_index(last).map(|(_, value)| value)
+    }
+
+    fn insert(&mut self, key: K, value: V) {
+        self.entries.shift_remove(&key);
+        self.entries.insert(key, value);
+        self.evict();
+    }
+
+    fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        self.evict();
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    fn evict(&mut self) {
+        while self.entries.len() > self.capacity {
+            self.entries.shift_remove_index(0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LruCache, ThumbnailSubject};
+    use crate::player::{Player, PlayerBuilder};
+    use crate::tag_utils::SwfMovie;
+
+    #[test]
+    fn thumbnails_leave_the_player_alone() {
+        let player = PlayerBuilder::new().with_movie(SwfMovie::empty(10)).build();
+        let mut player = player.lock().unwrap();
+        let movie = player.root_movie().clone();
+        let instance_counter = |player: &mut Player| {
+            player.mutate_with_update_context(|context| *context.instance_counter)
+        };
+        let before = instance_counter(&mut player);
+
+        // Instantiating a movie names it, which would take an instance name from the player.
+        player.thumbnail(&movie, ThumbnailSubject::Movie, 64);
+        assert_eq!(instance_counter(&mut player), before);
+    }
+
+    #[test]
+    fn lru_cache() {
+        let mut cache = LruCache::new(2);
+        cache.insert("a", 1);
+        cache.insert("b", 2);
+        assert_eq!(cache.get(&"a"), Some(&1));
+
+        // "b" is now the least recently used.
+        cache.insert("c", 3);
+        assert_eq!(cache.get(&"b"), None);
+        assert_eq!(cache.get(&"a"), Some(&1));
+        assert_eq!(cache.get(&"c"), Some(&3));
+
+        cache.set_capacity(1);
+        assert_eq!(cache.get(&"a"), None);
+        assert_eq!(cache.get(&"c"), Some(&3));
+
+        cache.set_capacity(0);
+        cache.insert("d", 4);
+        assert_eq!(cache.get(&"d"), None);
+    }
+}
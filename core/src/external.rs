@@ -13,6 +13,7 @@ use crate::context::UpdateContext;
 use crate::string::AvmString;
 use gc_arena::Collect;
 use std::collections::BTreeMap;
+use std::rc::Rc;
 
 /// An intermediate format of representing shared data between ActionScript and elsewhere.
 /// Regardless of the capabilities of both sides, all data will be translated to this potentially
@@ -321,10 +322,39 @@ impl FsCommandProvider for NullFsCommandProvider {
     }
 }
 
+/// Lets every stage of a player share one provider.
+impl<T: FsCommandProvider + ?Sized> FsCommandProvider for Rc<T> {
+    fn on_fs_command(&self, command: &str, args: &str) -> bool {
+        (**self).on_fs_command(command, args)
+    }
+}
+
 pub trait ExternalInterfaceProvider {
     fn get_method(&self, name: &str) -> Option<Box<dyn ExternalInterfaceMethod>>;
 
     fn on_callback_available(&self, name: &str);
+
+    /// Runs the script of a `javascript:` URL opened by AVM1 content with `getURL`, without the
+    /// `javascript:` prefix. Returns whether it was handled; if no provider handles it, it's
+    /// navigated to like any other URL.
+    fn run_javascript_url(&self, _script: &str) -> bool {
+        false
+    }
+}
+
+/// Lets every stage of a player share one provider.
+impl<T: ExternalInterfaceProvider + ?Sized> ExternalInterfaceProvider for Rc<T> {
+    fn get_method(&self, name: &str) -> Option<Box<dyn ExternalInterfaceMethod>> {
+        (**self).get_method(name)
+    }
+
+    fn on_callback_available(&self, name: &str) {
+        (**self).on_callback_available(name)
+    }
+
+    fn run_javascript_url(&self, script: &str) -> bool {
+        (**self).run_javascript_url(script)
+    }
 }
 
 pub trait ExternalInterfaceMethod {
@@ -393,4 +423,11 @@ impl<'gc> ExternalInterface<'gc> {
     pub fn invoke_fs_command(&self, command: &str, args: &str) -> bool {
         self.fs_commands.on_fs_command(command, args)
     }
+
+    /// Gives the script of a `javascript:` URL to the first provider that handles it.
+    pub fn run_javascript_url(&self, script: &str) -> bool {
+        self.providers
+            .iter()
+            .any(|provider| provider.run_javascript_url(script))
+    }
 }
@@ -1,11 +1,12 @@
 use crate::avm1::Avm1;
-use crate::avm1::Value;
+use crate::avm1::{Activation, ActivationIdentifier, TObject, Value};
 use crate::context::UpdateContext;
 pub use crate::display_object::{
     DisplayObject, TDisplayObject, TDisplayObjectContainer, TextSelection,
 };
 use gc_arena::lock::GcLock;
 use gc_arena::{Collect, Mutation};
+use swf::Twips;
 
 #[derive(Clone, Copy, Collect)]
 #[collect(no_drop)]
@@ -66,4 +67,255 @@ impl<'gc> FocusTracker<'gc> {
             }
         }
     }
+
+    /// Moves focus to the next object in the tab order, or to the previous one if `reverse`
+    /// is set, as pressing Tab does in AVM1 movies. Focus wraps around at either end.
+    ///
+    /// The V2 components' focus manager relies on this to notice the user tabbing between
+    /// components, through `Selection.onSetFocus`.
+    pub fn cycle(&self, context: &mut UpdateContext<'_, 'gc>, reverse: bool) {
+        let order = tab_order(context);
+        if order.is_empty() {
+            return;
+        }
+
+        let current = self
+            .get()
+            .and_then(|focus| order.iter().position(|o| DisplayObject::ptr_eq(*o, focus)));
+        let next = match (current, reverse) {
+            (Some(i), false) => (i + 1) % order.len(),
+            (Some(i), true) => (i + order.len() - 1) % order.len(),
+            (None, false) => 0,
+            (None, true) => order.len() - 1,
+        };
+        self.set(Some(order[next]), context);
+    }
+}
+
+/// The objects that Tab moves focus between in AVM1 movies, in order.
+///
+/// If any object has a `tabIndex`, only those objects are included, ordered by it.
+/// Otherwise objects are ordered by position, from top to bottom and then left to right.
+pub fn tab_order<'gc>(context: &mut UpdateContext<'_, 'gc>) -> Vec<DisplayObject<'gc>> {
+    let mut candidates = vec![];
+    for level in context.stage.iter_render_list() {
+        collect_tab_candidates(context, level, &mut candidates);
+    }
+
+    if candidates.iter().any(|(_, tab_index)| tab_index.is_some()) {
+        let mut indexed: Vec<_> = candidates
+            .into_iter()
+            .filter_map(|(object, tab_index)| Some((tab_index?, object)))
+            .collect();
+        indexed.sort_by_key(|(tab_index, _)| *tab_index);
+        indexed.into_iter().map(|(_, object)| object).collect()
+    } else {
+        let mut positioned: Vec<_> = candidates
+            .into_iter()
+            .map(|(object, _)| (tab_position(object), object))
+            .collect();
+        positioned.sort_by_key(|(position, _)| *position);
+        positioned.into_iter().map(|(_, object)| object).collect()
+    }
+}
+
+/// Adds `object` and its descendants that are in the tab order to `candidates`,
+/// along with their `tabIndex`.
+fn collect_tab_candidates<'gc>(
+    context: &mut UpdateContext<'_, 'gc>,
+    object: DisplayObject<'gc>,
+    candidates: &mut Vec<(DisplayObject<'gc>, Option<i32>)>,
+) {
+    if !object.visible() || object.as_interactive().is_none() {
+        return;
+    }
+
+    let tab_enabled = match avm1_property(context, object, "tabEnabled") {
+        Value::Undefined => {
+            object.as_avm1_button().is_some()
+                || object
+                    .as_edit_text()
+                    .map_or(false, |text| text.is_editable())
+                || object
+                    .as_movie_clip()
+                    .map_or(false, |clip| clip.is_button_mode(context))
+        }
+        value => value.as_bool(object.swf_version()),
+    };
+    if tab_enabled {
+        let tab_index = match avm1_property(context, object, "tabIndex") {
+            Value::Number(tab_index) if tab_index >= 0.0 => Some(tab_index as i32),
+            _ => None,
+        };
+        candidates.push((object, tab_index));
+    }
+
+    if let Some(container) = object.as_container() {
+        let tab_children = match avm1_property(context, object, "tabChildren") {
+            Value::Undefined => true,
+            value => value.as_bool(object.swf_version()),
+        };
+        if tab_children {
+            for child in container.iter_render_list() {
+                collect_tab_candidates(context, child, candidates);
+            }
+        }
+    }
+}
+
+/// Where an object is for the automatic tab order: the top left of its bounds,
+/// or its origin if it has no bounds.
+fn tab_position(object: DisplayObject<'_>) -> (Twips, Twips) {
+    let bounds = object.world_bounds();
+    if bounds.is_valid() {
+        (bounds.y_min, bounds.x_min)
+    } else {
+        let matrix = object.local_to_global_matrix();
+        (matrix.ty, matrix.tx)
+    }
+}
+
+fn avm1_property<'gc>(
+    context: &mut UpdateContext<'_, 'gc>,
+    object: DisplayObject<'gc>,
+    name: &'static str,
+) -> Value<'gc> {
+    let Value::Object(object) = object.object() else {
+        return Value::Undefined;
+    };
+    let mut activation = Activation::from_stub(
+        context.reborrow(),
+        ActivationIdentifier::root("[Tab Order]"),
+    );
+    object
+        .get(name, &mut activation)
+        .unwrap_or(Value::Undefined)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::tab_order;
+    use crate::avm1::{Activation, ActivationIdentifier, ExecutionReason, TObject, Value};
+    use crate::context::UpdateContext;
+    use crate::display_object::{DisplayObject, TDisplayObject, TDisplayObjectContainer};
+    use crate::player::PlayerBuilder;
+    use crate::tag_utils::SwfMovie;
+
+    fn with_context(test: impl for<'a, 'gc> FnOnce(&mut UpdateContext<'a, 'gc>)) {
+        let player = PlayerBuilder::new().with_movie(SwfMovie::empty(8)).build();
+        let mut player = player.lock().unwrap();
+        player.mutate_with_update_context(test);
+    }
+
+    /// Creates an empty clip on the root timeline and sets AVM1 properties on it.
+    fn create_clip<'gc>(
+        context: &mut UpdateContext<'_, 'gc>,
+        name: &'static str,
+        properties: &[(&'static str, Value<'gc>)],
+    ) {
+        let root = context.stage.root_clip().unwrap();
+        let depth = root.as_container().unwrap().num_children() as i32;
+        let mut activation = Activation::from_nothing(
+            context.reborrow(),
+            ActivationIdentifier::root("[Test]"),
+            root,
+        );
+        let root = root.object().coerce_to_object(&mut activation);
+        let clip = root
+            .call_method(
+                "createEmptyMovieClip".into(),
+                &[name.into(), depth.into()],
+                &mut activation,
+                ExecutionReason::Special,
+            )
+            .unwrap()
+            .coerce_to_object(&mut activation);
+        for (key, value) in properties {
+            clip.set(*key, *value, &mut activation).unwrap();
+        }
+    }
+
+    fn names(objects: &[DisplayObject<'_>]) -> Vec<String> {
+        objects.iter().map(|o| o.name().to_string()).collect()
+    }
+
+    #[test]
+    fn tab_order_goes_by_position() {
+        with_context(|context| {
+            create_clip(
+                context,
+                "low",
+                &[("tabEnabled", true.into()), ("_y", 100.into())],
+            );
+            create_clip(
+                context,
+                "right",
+                &[("tabEnabled", true.into()), ("_x", 50.into())],
+            );
+            create_clip(context, "left", &[("tabEnabled", true.into())]);
+            create_clip(context, "plain", &[]);
+            create_clip(
+                context,
+                "hidden",
+                &[("tabEnabled", true.into()), ("_visible", false.into())],
+            );
+
+            let order = tab_order(context);
+            assert_eq!(names(&order), ["left", "right", "low"]);
+        });
+    }
+
+    #[test]
+    fn tab_index_replaces_the_automatic_order() {
+        with_context(|context| {
+            create_clip(
+                context,
+                "second",
+                &[("tabEnabled", true.into()), ("tabIndex", 2.into())],
+            );
+            create_clip(
+                context,
+                "first",
+                &[("tabEnabled", true.into()), ("tabIndex", 1.into())],
+            );
+            create_clip(context, "unindexed", &[("tabEnabled", true.into())]);
+            create_clip(
+                context,
+                "disabled",
+                &[("tabEnabled", false.into()), ("tabIndex", 0.into())],
+            );
+
+            let order = tab_order(context);
+            assert_eq!(names(&order), ["first", "second"]);
+        });
+    }
+
+    #[test]
+    fn tab_cycles_focus() {
+        with_context(|context| {
+            create_clip(
+                context,
+                "a",
+                &[("tabEnabled", true.into()), ("tabIndex", 1.into())],
+            );
+            create_clip(
+                context,
+                "b",
+                &[("tabEnabled", true.into()), ("tabIndex", 2.into())],
+            );
+            let tracker = context.focus_tracker;
+            let focus = |context: &mut UpdateContext<'_, '_>| {
+                context.focus_tracker.get().map(|o| o.name().to_string())
+            };
+
+            tracker.cycle(context, false);
+            assert_eq!(focus(context).as_deref(), Some("a"));
+            tracker.cycle(context, false);
+            assert_eq!(focus(context).as_deref(), Some("b"));
+            tracker.cycle(context, false);
+            assert_eq!(focus(context).as_deref(), Some("a"));
+            tracker.cycle(context, true);
+            assert_eq!(focus(context).as_deref(), Some("b"));
+        });
+    }
 }
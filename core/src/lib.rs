@@ -24,12 +24,15 @@ mod binary_data;
 pub mod bitmap;
 pub mod buffer;
 mod character;
+mod clock;
 pub mod context;
 pub mod context_menu;
 mod drawing;
 mod ecma_conversions;
 pub(crate) mod either;
+pub mod embed;
 pub mod events;
+pub mod export;
 pub mod focus_tracker;
 mod font;
 mod frame_lifecycle;
@@ -38,16 +41,28 @@ mod library;
 pub mod limits;
 pub mod loader;
 mod locale;
+pub mod log_filter;
+pub mod native_extension;
+pub mod native_menu;
+pub mod net_group;
+pub mod net_status;
+mod number_format;
+pub mod package;
 pub mod pixel_bender;
 mod player;
+pub mod player_version;
+pub mod policy_file;
 mod prelude;
 pub mod socket;
+mod sql;
 mod streams;
 pub mod string;
 pub mod tag_utils;
+mod telemetry;
 pub mod timer;
 mod types;
 mod vminterface;
+pub mod web_view;
 mod xml;
 
 pub mod backend;
@@ -65,7 +80,7 @@ pub use events::PlayerEvent;
 pub use font::DefaultFont;
 pub use indexmap;
 pub use loader::LoadBehavior;
-pub use player::{Player, PlayerBuilder, StaticCallstack};
+pub use player::{Player, PlayerBuilder, StageArea, StageId, StaticCallstack};
 pub use ruffle_render::backend::ViewportDimensions;
 pub use swf;
 pub use swf::Color;
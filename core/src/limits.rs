@@ -62,6 +62,13 @@ impl ExecutionLimit {
         }
     }
 
+    /// Check if the time limit has already run out, without executing any operations.
+    ///
+    /// An unlimited execution limit never runs out.
+    pub fn is_time_exhausted(&self, context: &UpdateContext<'_, '_>) -> bool {
+        self.current_oplimit.is_some() && context.update_start.elapsed() >= self.time_limit
+    }
+
     /// Check if the execution of a certain number of operations has exceeded
     /// the execution limit.
     ///
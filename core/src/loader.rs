@@ -5,32 +5,41 @@ use crate::avm1::{Activation, ActivationIdentifier};
 use crate::avm1::{Attribute, Avm1};
 use crate::avm1::{Object, SoundObject, TObject, Value};
 use crate::avm2::bytearray::ByteArrayStorage;
+use crate::avm2::error::{illegal_operation_error, security_error};
 use crate::avm2::object::{
-    BitmapDataObject, ByteArrayObject, EventObject as Avm2EventObject, LoaderStream, TObject as _,
+    ArrayObject as Avm2ArrayObject, BitmapDataObject, ByteArrayObject,
+    EventObject as Avm2EventObject, LoaderStream, TObject as _,
 };
 use crate::avm2::{
-    Activation as Avm2Activation, Avm2, Domain as Avm2Domain, Object as Avm2Object,
-    Value as Avm2Value,
+    Activation as Avm2Activation, ArrayStorage, Avm2, Domain as Avm2Domain, Error as Avm2Error,
+    Object as Avm2Object, Value as Avm2Value,
 };
-use crate::backend::navigator::{OwnedFuture, Request};
+use crate::backend::navigator::{BodyChunk, ErrorResponse, OwnedFuture, Request, SuccessResponse};
 use crate::bitmap::bitmap_data::Color;
 use crate::bitmap::bitmap_data::{BitmapData, BitmapDataWrapper};
 use crate::context::{ActionQueue, ActionType, UpdateContext};
 use crate::display_object::{
-    DisplayObject, MovieClip, TDisplayObject, TDisplayObjectContainer, TInteractiveObject,
+    DisplayObject, DisplayObjectContainer, MovieClip, TDisplayObject, TDisplayObjectContainer,
+    TInteractiveObject,
 };
 use crate::events::ClipEvent;
 use crate::frame_lifecycle::catchup_display_object_to_frame;
 use crate::limits::ExecutionLimit;
+use crate::package;
 use crate::player::Player;
 use crate::streams::NetStream;
 use crate::string::AvmString;
 use crate::tag_utils::SwfMovie;
 use crate::vminterface::Instantiator;
 use encoding_rs::UTF_8;
+use fnv::FnvHashMap;
 use gc_arena::{Collect, GcCell};
 use generational_arena::{Arena, Index};
+use indexmap::IndexMap;
+use instant::Instant;
+use ruffle_render::bitmap::Bitmap;
 use ruffle_render::utils::{determine_jpeg_tag_format, JpegTagFormat};
+use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::sync::{Arc, Mutex, Weak};
 use std::time::Duration;
@@ -42,7 +51,8 @@ pub type Handle = Index;
 
 /// How Ruffle should load movies.
 #[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum LoadBehavior {
     /// Allow movies to execute before they have finished loading.
     ///
@@ -74,6 +84,8 @@ pub enum ContentType {
     Jpeg,
     Png,
     Gif,
+    JpegXr,
+    Zip,
     Unknown,
 }
 
@@ -83,6 +95,7 @@ impl From<JpegTagFormat> for ContentType {
             JpegTagFormat::Jpeg => Self::Jpeg,
             JpegTagFormat::Png => Self::Png,
             JpegTagFormat::Gif => Self::Gif,
+            JpegTagFormat::JpegXr => Self::JpegXr,
             JpegTagFormat::Unknown => Self::Unknown,
         }
     }
@@ -95,15 +108,19 @@ impl fmt::Display for ContentType {
             Self::Jpeg => write!(f, "JPEG"),
             Self::Png => write!(f, "PNG"),
             Self::Gif => write!(f, "GIF"),
+            Self::JpegXr => write!(f, "JPEG XR"),
+            Self::Zip => write!(f, "ZIP"),
             Self::Unknown => write!(f, "Unknown"),
         }
     }
 }
 
 impl ContentType {
-    fn sniff(data: &[u8]) -> ContentType {
+    pub(crate) fn sniff(data: &[u8]) -> ContentType {
         if read_compression_type(data).is_ok() {
             ContentType::Swf
+        } else if package::is_zip(data) {
+            ContentType::Zip
         } else {
             determine_jpeg_tag_format(data).into()
         }
@@ -162,6 +179,10 @@ pub enum Error {
     #[error("Could not fetch: {0}")]
     FetchError(String),
 
+    /// The response was successful, but had no body, as with `204 No Content`.
+    #[error("Response has no body")]
+    EmptyBody,
+
     #[error("Invalid SWF: {0}")]
     InvalidSwf(#[from] crate::tag_utils::Error),
 
@@ -191,12 +212,50 @@ impl From<crate::avm1::Error<'_>> for Error {
     }
 }
 
+/// How many finished movie loads to keep the metrics of.
+const MAX_LOAD_METRICS: usize = 256;
+
+/// How many ticks in a row a movie loader may be passed over for more urgent ones, before it's
+/// preloaded first.
+const MAX_SKIPPED_PRELOADS: u32 = 10;
+
+/// How urgently the tags of a loaded movie are preloaded, from most to least urgent.
+///
+/// Once the preloading budget of a tick runs out, loaders of less urgent classes wait for the
+/// next tick, so that huge movies loading in the background don't hold up the player.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PreloadPriority {
+    /// Loads replacing the root movie, such as `loadMovieNum` into `_level0`.
+    Root,
+
+    /// Loads into clips that are on the stage and visible.
+    Visible,
+
+    /// Every other load, such as one into a `Loader` that isn't on the stage.
+    Offscreen,
+}
+
 /// Holds all in-progress loads for the player.
-pub struct LoadManager<'gc>(Arena<Loader<'gc>>);
+pub struct LoadManager<'gc> {
+    loaders: Arena<Loader<'gc>>,
+
+    /// How many ticks in a row each movie loader was passed over while preloading.
+    skipped_preloads: FnvHashMap<Handle, u32>,
+
+    /// The most bytes each movie loader has reported as loaded.
+    ///
+    /// Bytes are reported as they arrive, and then again as they get preloaded, so this
+    /// keeps the second pass from reporting less than the first.
+    reported_progress: FnvHashMap<Handle, usize>,
+
+    /// The metrics of the most recent movie loads, from oldest to newest. These are kept after
+    /// their loaders are removed.
+    metrics: IndexMap<Handle, LoadMetrics>,
+}
 
 unsafe impl<'gc> Collect for LoadManager<'gc> {
     fn trace(&self, cc: &gc_arena::Collection) {
-        for (_, loader) in self.0.iter() {
+        for (_, loader) in self.loaders.iter() {
             loader.trace(cc)
         }
     }
@@ -205,7 +264,12 @@ unsafe impl<'gc> Collect for LoadManager<'gc> {
 impl<'gc> LoadManager<'gc> {
     /// Construct a new `LoadManager`.
     pub fn new() -> Self {
-        Self(Arena::new())
+        Self {
+            loaders: Arena::new(),
+            skipped_preloads: FnvHashMap::default(),
+            reported_progress: FnvHashMap::default(),
+            metrics: IndexMap::new(),
+        }
     }
 
     /// Add a new loader to the `LoadManager`.
@@ -218,13 +282,14 @@ impl<'gc> LoadManager<'gc> {
     /// Movie loaders are removed automatically after the loader status is set
     /// accordingly.
     pub fn add_loader(&mut self, loader: Loader<'gc>) -> Handle {
-        let handle = self.0.insert(loader);
+        let handle = self.loaders.insert(loader);
         match self.get_loader_mut(handle).unwrap() {
             Loader::RootMovie { self_handle, .. }
             | Loader::Movie { self_handle, .. }
             | Loader::Form { self_handle, .. }
             | Loader::LoadVars { self_handle, .. }
             | Loader::LoadURLLoader { self_handle, .. }
+            | Loader::LoadURLStream { self_handle, .. }
             | Loader::SoundAvm1 { self_handle, .. }
             | Loader::SoundAvm2 { self_handle, .. }
             | Loader::NetStream { self_handle, .. }
@@ -236,17 +301,58 @@ impl<'gc> LoadManager<'gc> {
     /// Remove a completed loader.
     /// This is used to remove a loader after the loading or unloading process has completed.
     pub fn remove_loader(&mut self, handle: Handle) {
-        self.0.remove(handle);
+        self.loaders.remove(handle);
+        self.skipped_preloads.remove(&handle);
+        self.reported_progress.remove(&handle);
+    }
+
+    /// Record that a movie loader got `cur_len` bytes of its movie, returning whether that's
+    /// news to scripts.
+    ///
+    /// Bytes that were reported as they arrived aren't reported again while preloading.
+    fn record_progress(&mut self, handle: Handle, cur_len: usize, total_len: usize) -> bool {
+        let reported = self.reported_progress.entry(handle).or_default();
+        if total_len > 0 && cur_len < *reported {
+            return false;
+        }
+        *reported = cur_len;
+        true
+    }
+
+    /// Retrieve how the movie load of a handle went so far, if it's one of the most recent ones.
+    ///
+    /// Unlike the loader itself, this is still available after the load finishes.
+    pub fn metrics(&self, handle: Handle) -> Option<&LoadMetrics> {
+        self.metrics.get(&handle)
+    }
+
+    /// Retrieve the metrics of the most recent movie loads, from oldest to newest.
+    pub fn all_metrics(&self) -> impl Iterator<Item = (Handle, &LoadMetrics)> {
+        self.metrics
+            .iter()
+            .map(|(handle, metrics)| (*handle, metrics))
+    }
+
+    fn metrics_mut(&mut self, handle: Handle) -> Option<&mut LoadMetrics> {
+        self.metrics.get_mut(&handle)
+    }
+
+    /// Start recording the metrics of a new movie load.
+    fn start_metrics(&mut self, handle: Handle) {
+        self.metrics.insert(handle, LoadMetrics::new());
+        if self.metrics.len() > MAX_LOAD_METRICS {
+            self.metrics.shift_remove_index(0);
+        }
     }
 
     /// Retrieve a loader by handle.
     pub fn get_loader(&self, handle: Handle) -> Option<&Loader<'gc>> {
-        self.0.get(handle)
+        self.loaders.get(handle)
     }
 
     /// Retrieve a loader by handle for mutation.
     pub fn get_loader_mut(&mut self, handle: Handle) -> Option<&mut Loader<'gc>> {
-        self.0.get_mut(handle)
+        self.loaders.get_mut(handle)
     }
 
     /// Kick off the root movie load.
@@ -285,8 +391,10 @@ impl<'gc> LoadManager<'gc> {
             vm_data,
             loader_status: LoaderStatus::Pending,
             movie: None,
+            from_bytes: false,
         };
         let handle = self.add_loader(loader);
+        self.start_metrics(handle);
         let loader = self.get_loader_mut(handle).unwrap();
         loader.movie_loader(player, request, loader_url)
     }
@@ -307,8 +415,10 @@ impl<'gc> LoadManager<'gc> {
             vm_data,
             loader_status: LoaderStatus::Pending,
             movie: None,
+            from_bytes: true,
         };
         let handle = self.add_loader(loader);
+        self.start_metrics(handle);
         let loader = self.get_loader_mut(handle).unwrap();
         loader.movie_loader_bytes(player, bytes)
     }
@@ -320,14 +430,14 @@ impl<'gc> LoadManager<'gc> {
     pub fn movie_clip_on_load(&mut self, queue: &mut ActionQueue<'gc>) {
         let mut invalidated_loaders = vec![];
 
-        for (index, loader) in self.0.iter_mut().rev() {
+        for (index, loader) in self.loaders.iter_mut().rev() {
             if loader.movie_clip_loaded(queue) {
                 invalidated_loaders.push(index);
             }
         }
 
         for index in invalidated_loaders {
-            self.0.remove(index);
+            self.remove_loader(index);
         }
     }
 
@@ -387,6 +497,40 @@ impl<'gc> LoadManager<'gc> {
         loader.load_url_loader(player, request, data_format)
     }
 
+    /// Kick off a data load into a `URLStream`.
+    ///
+    /// Returns the loader's async process, which you will need to spawn.
+    pub fn load_data_into_url_stream(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        target_object: Avm2Object<'gc>,
+        buffer: Avm2Object<'gc>,
+        request: Request,
+    ) -> OwnedFuture<(), Error> {
+        let loader = Loader::LoadURLStream {
+            self_handle: None,
+            target_object,
+            buffer,
+        };
+        let handle = self.add_loader(loader);
+        let loader = self.get_loader_mut(handle).unwrap();
+        loader.load_url_stream(player, request)
+    }
+
+    /// Find the load into a `URLStream` that is still in progress, if there is one.
+    pub fn url_stream_loader(&self, target: Avm2Object<'gc>) -> Option<Handle> {
+        self.loaders
+            .iter()
+            .find_map(|(handle, loader)| match loader {
+                Loader::LoadURLStream { target_object, .. }
+                    if Avm2Object::ptr_eq(*target_object, target) =>
+                {
+                    Some(handle)
+                }
+                _ => None,
+            })
+    }
+
     /// Kick off an AVM1 audio load.
     ///
     /// Returns the loader's async process, which you will need to spawn.
@@ -439,29 +583,123 @@ impl<'gc> LoadManager<'gc> {
         loader.stream_loader(player, request)
     }
 
-    /// Process tags on all loaders in the Parsing phase.
+    /// Process tags on all loaders in the Parsing phase, most urgent first.
+    ///
+    /// Every loader of a priority class gets a turn, but once `limit` runs out, loaders of less
+    /// urgent classes carry their turn over to the next tick. A loader that has been passed over
+    /// too many times in a row is preloaded first, so that none of them stall forever.
     ///
     /// Returns true if *all* loaders finished preloading.
     pub fn preload_tick(context: &mut UpdateContext<'_, 'gc>, limit: &mut ExecutionLimit) -> bool {
         let mut did_finish = true;
-        let handles: Vec<_> = context.load_manager.0.iter().map(|(h, _)| h).collect();
-
-        for handle in handles {
-            let status = match context.load_manager.get_loader(handle) {
-                Some(Loader::Movie { loader_status, .. }) => Some(loader_status),
+        let parsing: Vec<_> = context
+            .load_manager
+            .loaders
+            .iter()
+            .filter_map(|(handle, loader)| match loader {
+                Loader::Movie {
+                    loader_status: LoaderStatus::Parsing,
+                    target_clip,
+                    vm_data,
+                    ..
+                } => Some((handle, *target_clip, *vm_data)),
                 _ => None,
-            };
+            })
+            .collect();
+
+        let loaders: Vec<_> = parsing
+            .into_iter()
+            .map(|(handle, target_clip, vm_data)| {
+                (
+                    handle,
+                    Self::preload_priority(context, target_clip, vm_data),
+                )
+            })
+            .collect();
+        let loaders = context.load_manager.preload_order(loaders);
 
-            if matches!(status, Some(LoaderStatus::Parsing)) {
-                match Loader::preload_tick(handle, context, limit, 0, false) {
-                    Ok(f) => did_finish = did_finish && f,
-                    Err(e) => tracing::error!("Error encountered while preloading movie: {}", e),
-                }
+        let mut current_priority = None;
+        for (handle, priority) in loaders {
+            if current_priority.is_some_and(|current| current < priority)
+                && limit.is_time_exhausted(context)
+            {
+                context.load_manager.defer_preload(handle);
+                did_finish = false;
+                continue;
+            }
+            current_priority = Some(priority);
+            context.load_manager.skipped_preloads.remove(&handle);
+
+            match Loader::preload_tick(handle, context, limit, 0, false) {
+                Ok(f) => did_finish = did_finish && f,
+                Err(e) => tracing::error!("Error encountered while preloading movie: {}", e),
             }
         }
 
         did_finish
     }
+
+    /// Sorts loaders into the order they are preloaded in, most urgent first.
+    ///
+    /// Loaders that were passed over too many times in a row are moved to the front, as if
+    /// they were loading the root movie.
+    fn preload_order(
+        &self,
+        mut loaders: Vec<(Handle, PreloadPriority)>,
+    ) -> Vec<(Handle, PreloadPriority)> {
+        for (handle, priority) in &mut loaders {
+            let skipped = self.skipped_preloads.get(handle);
+            if skipped.is_some_and(|&n| n >= MAX_SKIPPED_PRELOADS) {
+                *priority = PreloadPriority::Root;
+            }
+        }
+
+        // This is a stable sort, so loaders keep the order they were started in.
+        loaders.sort_by_key(|&(_, priority)| priority);
+        loaders
+    }
+
+    /// Records that a loader was passed over for more urgent ones this tick.
+    fn defer_preload(&mut self, handle: Handle) {
+        *self.skipped_preloads.entry(handle).or_default() += 1;
+    }
+
+    /// How urgently a movie loaded into `target_clip` should be preloaded.
+    fn preload_priority(
+        context: &UpdateContext<'_, 'gc>,
+        target_clip: DisplayObject<'gc>,
+        vm_data: MovieLoaderVMData<'gc>,
+    ) -> PreloadPriority {
+        if context
+            .stage
+            .root_clip()
+            .is_some_and(|root| DisplayObject::ptr_eq(root, target_clip))
+        {
+            return PreloadPriority::Root;
+        }
+
+        // AVM2 movies aren't added to their `Loader` until they have loaded, so the `Loader`
+        // itself is what's on the stage.
+        let shown = match vm_data {
+            MovieLoaderVMData::Avm2 { loader_info, .. } => loader_info
+                .as_loader_info_object()
+                .and_then(|loader_info| loader_info.loader())
+                .and_then(|loader| loader.as_display_object()),
+            MovieLoaderVMData::Avm1 { .. } => Some(target_clip),
+        };
+        let Some(shown) = shown.filter(|shown| shown.is_on_stage(context)) else {
+            return PreloadPriority::Offscreen;
+        };
+
+        let mut node = Some(shown);
+        while let Some(object) = node {
+            if !object.visible() {
+                return PreloadPriority::Offscreen;
+            }
+            node = object.parent();
+        }
+        PreloadPriority::Visible
+    }
 }
 
 impl<'gc> Default for LoadManager<'gc> {
@@ -470,6 +708,59 @@ impl<'gc> Default for LoadManager<'gc> {
     }
 }
 
+/// Timings and sizes of a movie load, to find the ones slowing content down.
+#[derive(Debug, Clone)]
+pub struct LoadMetrics {
+    /// When the load started.
+    started: Instant,
+
+    /// The URL of the movie, once it has been fetched.
+    pub url: Option<String>,
+
+    /// How long it took to fetch the movie. `None` for movies loaded from bytes.
+    pub fetch_duration: Option<Duration>,
+
+    /// The size of the movie as it was fetched, before being decompressed.
+    pub compressed_bytes: usize,
+
+    /// How long it took to decompress the movie and read its header.
+    pub decompress_duration: Option<Duration>,
+
+    /// How long preloading the tags of the movie took so far, across every tick.
+    pub preload_duration: Duration,
+
+    /// How long after the start of the load the first frame of the movie was ready.
+    pub time_to_first_frame: Option<Duration>,
+
+    /// How long after the start of the load the movie finished preloading.
+    pub time_to_complete: Option<Duration>,
+
+    /// How many characters preloading the movie added to its library.
+    pub decoded_characters: usize,
+}
+
+impl LoadMetrics {
+    fn new() -> Self {
+        Self {
+            started: Instant::now(),
+            url: None,
+            fetch_duration: None,
+            compressed_bytes: 0,
+            decompress_duration: None,
+            preload_duration: Duration::ZERO,
+            time_to_first_frame: None,
+            time_to_complete: None,
+            decoded_characters: 0,
+        }
+    }
+
+    /// How fast the movie was fetched, in bytes per second.
+    pub fn bytes_per_second(&self) -> Option<f64> {
+        let seconds = self.fetch_duration?.as_secs_f64();
+        (seconds > 0.0).then(|| self.compressed_bytes as f64 / seconds)
+    }
+}
+
 /// The completion status of a `Loader` loading a movie.
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum LoaderStatus {
@@ -540,6 +831,10 @@ pub enum Loader<'gc> {
         /// completed and we expect the Player to periodically tick preload
         /// until loading completes.
         movie: Option<Arc<SwfMovie>>,
+
+        /// Whether the movie was given by `loadBytes` rather than fetched, and so runs in the
+        /// sandbox of the movie that loaded it.
+        from_bytes: bool,
     },
 
     /// Loader that is loading form data into an AVM1 object scope.
@@ -573,6 +868,20 @@ pub enum Loader<'gc> {
         target_object: Avm2Object<'gc>,
     },
 
+    /// Loader that is streaming data into a `URLStream`.
+    /// Unlike `LoadURLLoader`, data is made available as it arrives.
+    LoadURLStream {
+        /// The handle to refer to this loader instance.
+        #[collect(require_static)]
+        self_handle: Option<Handle>,
+
+        /// The target `URLStream` that receives events.
+        target_object: Avm2Object<'gc>,
+
+        /// The `ByteArray` that received data is appended to.
+        buffer: Avm2Object<'gc>,
+    },
+
     /// Loader that is loading an MP3 into an AVM1 Sound object.
     SoundAvm1 {
         /// The handle to refer to this loader instance.
@@ -614,6 +923,38 @@ pub enum Loader<'gc> {
     },
 }
 
+/// Wait for any URL policy files being loaded from the server that `request` is for.
+async fn wait_for_policy_files(player: &Mutex<Player>, request: &Request) {
+    let loading = player.lock().unwrap().mutate_with_update_context(|uc| {
+        match uc.navigator.resolve_url(request.url()) {
+            Ok(url) => uc.sockets.policy_files().url_policy_files_loading(&url),
+            Err(_) => vec![],
+        }
+    });
+    for loaded in loading {
+        // The sender is dropped, closing the channel, once the policy file has loaded.
+        let _ = loaded.recv().await;
+    }
+}
+
+/// The code of the security error that stops content from `content_url` being parented to a
+/// container of a movie from `parent_url`, if it may not be.
+///
+/// Network content has to come from the same origin as the container, and local content can't
+/// be mixed with network content.
+fn sandbox_violation(content_url: &str, parent_url: &str) -> Option<u32> {
+    let (Ok(content_url), Ok(parent_url)) = (Url::parse(content_url), Url::parse(parent_url))
+    else {
+        return None;
+    };
+    let (content, parent) = (content_url.origin(), parent_url.origin());
+    match (content.is_tuple(), parent.is_tuple()) {
+        (true, true) if content != parent => Some(2059),
+        (true, false) | (false, true) => Some(2060),
+        _ => None,
+    }
+}
+
 impl<'gc> Loader<'gc> {
     /// Process tags on a loaded movie.
     ///
@@ -655,8 +996,23 @@ impl<'gc> Loader<'gc> {
 
         let mc = mc.as_movie_clip().unwrap();
 
+        let preload_start = Instant::now();
         let did_finish = mc.preload(context, limit);
 
+        if let Some(metrics) = context.load_manager.metrics_mut(handle) {
+            metrics.preload_duration += preload_start.elapsed();
+            if metrics.time_to_first_frame.is_none() && mc.frames_loaded() >= 1 {
+                metrics.time_to_first_frame = Some(metrics.started.elapsed());
+            }
+            if did_finish && metrics.time_to_complete.is_none() {
+                metrics.time_to_complete = Some(metrics.started.elapsed());
+                metrics.decoded_characters = context
+                    .library
+                    .library_for_movie(mc.movie())
+                    .map_or(0, |library| library.characters().len());
+            }
+        }
+
         Loader::movie_loader_progress(
             handle,
             context,
@@ -691,6 +1047,7 @@ impl<'gc> Loader<'gc> {
             .expect("Could not upgrade weak reference to player");
 
         Box::pin(async move {
+            wait_for_policy_files(&player, &request).await;
             let fetch = player.lock().unwrap().navigator().fetch(request);
 
             let response = fetch.await.map_err(|error| {
@@ -715,7 +1072,18 @@ impl<'gc> Loader<'gc> {
                 .map(|u| u.to_string())
                 .unwrap_or(swf_url);
 
-            let mut movie = SwfMovie::from_data(&response.body, spoofed_or_swf_url, None)?;
+            // Packaged movies are played from inside their archive.
+            let body = match ContentType::sniff(&response.body) {
+                #[cfg(feature = "zip")]
+                ContentType::Zip => {
+                    package::extract_main_swf(&response.body).unwrap_or(response.body)
+                }
+                _ => response.body,
+            };
+
+            let strictness = player.lock().unwrap().tag_strictness();
+            let mut movie =
+                SwfMovie::from_data_with_strictness(&body, spoofed_or_swf_url, None, strictness)?;
             on_metadata(movie.header());
             movie.append_parameters(parameters);
             player.lock().unwrap().set_root_movie(movie);
@@ -749,16 +1117,47 @@ impl<'gc> Loader<'gc> {
             let request_url = request.url().to_string();
             let resolved_url = player.lock().unwrap().navigator().resolve_url(&request_url);
 
-            let fetch = player.lock().unwrap().navigator().fetch(request);
+            wait_for_policy_files(&player, &request).await;
+
+            let fetch_start = Instant::now();
+            let (body_sender, body_receiver) = async_channel::unbounded();
+            let (response_sender, response_receiver) = async_channel::bounded(1);
+
+            // The fetch runs as its own task, so that progress can be reported
+            // while the movie is still arriving.
+            let fetch = player
+                .lock()
+                .unwrap()
+                .navigator()
+                .fetch_stream(request, body_sender);
+            player
+                .lock()
+                .unwrap()
+                .navigator()
+                .spawn_future(Box::pin(async move {
+                    let _ = response_sender.send(fetch.await).await;
+                    Ok(())
+                }));
 
             let mut replacing_root_movie = false;
             player.lock().unwrap().update(|uc| -> Result<(), Error> {
-                let clip = match uc.load_manager.get_loader(handle) {
-                    Some(Loader::Movie { target_clip, .. }) => *target_clip,
+                let (clip, vm_data) = match uc.load_manager.get_loader(handle) {
+                    Some(Loader::Movie {
+                        target_clip,
+                        vm_data,
+                        ..
+                    }) => (*target_clip, *vm_data),
                     None => return Err(Error::Cancelled),
                     _ => unreachable!(),
                 };
 
+                // A `Loader` may be reused, so forget what arrived for its previous movie.
+                if let MovieLoaderVMData::Avm2 { loader_info, .. } = vm_data {
+                    if let Some(loader_info) = loader_info.as_loader_info_object() {
+                        loader_info.set_streamed_bytes(uc.gc_context, 0);
+                    }
+                }
+
                 replacing_root_movie = uc
                     .stage
                     .root_clip()
@@ -777,15 +1176,58 @@ impl<'gc> Loader<'gc> {
                 Loader::movie_loader_start(handle, uc)
             })?;
 
-            match fetch.await {
+            let mut body = vec![];
+            let mut total_len = 0;
+            // The body channel closes once the fetch has finished, successfully or not.
+            while let Ok(chunk) = body_receiver.recv().await {
+                match chunk {
+                    BodyChunk::Length(length) => total_len = length,
+                    BodyChunk::Data(data) => {
+                        body.extend_from_slice(&data);
+                        // Once the whole movie is here, it gets reported as it's preloaded.
+                        if !replacing_root_movie && (body.len() < total_len || total_len == 0) {
+                            player.lock().unwrap().update(|uc| {
+                                Loader::movie_loader_stream_progress(
+                                    handle,
+                                    uc,
+                                    body.len(),
+                                    total_len,
+                                )
+                            })?;
+                        }
+                    }
+                }
+            }
+
+            let response = response_receiver
+                .recv()
+                .await
+                .map_err(|_| Error::Cancelled)?
+                .map(|response| SuccessResponse { body, ..response });
+            // This mustn't run any actions, which would otherwise happen before the movie arrives.
+            player.lock().unwrap().mutate_with_update_context(|uc| {
+                if let Some(metrics) = uc.load_manager.metrics_mut(handle) {
+                    metrics.fetch_duration = Some(fetch_start.elapsed());
+                }
+            });
+
+            match response {
                 Ok(response) if replacing_root_movie => {
                     ContentType::sniff(&response.body).expect(ContentType::Swf)?;
 
-                    let movie = SwfMovie::from_data(&response.body, response.url, loader_url)?;
+                    let strictness = player.lock().unwrap().tag_strictness();
+                    let movie = SwfMovie::from_data_with_strictness(
+                        &response.body,
+                        response.url,
+                        loader_url,
+                        strictness,
+                    )?;
                     player.lock().unwrap().set_root_movie(movie);
                     return Ok(());
                 }
                 Ok(response) => {
+                    let decoded_image =
+                        Loader::decode_image_on_load(handle, &player, &response.body).await?;
                     Loader::movie_loader_data(
                         handle,
                         player,
@@ -794,6 +1236,7 @@ impl<'gc> Loader<'gc> {
                         response.status,
                         response.redirected,
                         loader_url,
+                        decoded_image,
                     )?;
                 }
                 Err(response) => {
@@ -815,6 +1258,7 @@ impl<'gc> Loader<'gc> {
                             handle,
                             uc,
                             "Movie loader error".into(),
+                            0,
                             status_code,
                             redirected,
                             response.url,
@@ -871,12 +1315,28 @@ impl<'gc> Loader<'gc> {
             if replacing_root_movie {
                 ContentType::sniff(&bytes).expect(ContentType::Swf)?;
 
-                let movie = SwfMovie::from_data(&bytes, "file:///".into(), None)?;
+                let strictness = player.lock().unwrap().tag_strictness();
+                let movie = SwfMovie::from_data_with_strictness(
+                    &bytes,
+                    "file:///".into(),
+                    None,
+                    strictness,
+                )?;
                 player.lock().unwrap().set_root_movie(movie);
                 return Ok(());
             }
 
-            Loader::movie_loader_data(handle, player, &bytes, "file:///".into(), 0, false, None)
+            let decoded_image = Loader::decode_image_on_load(handle, &player, &bytes).await?;
+            Loader::movie_loader_data(
+                handle,
+                player,
+                &bytes,
+                "file:///".into(),
+                0,
+                false,
+                None,
+                decoded_image,
+            )
         })
     }
 
@@ -895,6 +1355,7 @@ impl<'gc> Loader<'gc> {
             .expect("Could not upgrade weak reference to player");
 
         Box::pin(async move {
+            wait_for_policy_files(&player, &request).await;
             let fetch = player.lock().unwrap().navigator().fetch(request);
 
             let response = fetch.await.map_err(|e| e.error)?;
@@ -958,6 +1419,7 @@ impl<'gc> Loader<'gc> {
             .expect("Could not upgrade weak reference to player");
 
         Box::pin(async move {
+            wait_for_policy_files(&player, &request).await;
             let fetch = player.lock().unwrap().navigator().fetch(request);
 
             let data = fetch.await;
@@ -1060,6 +1522,7 @@ impl<'gc> Loader<'gc> {
             .expect("Could not upgrade weak reference to player");
 
         Box::pin(async move {
+            wait_for_policy_files(&player, &request).await;
             let fetch = player.lock().unwrap().navigator().fetch(request);
             let response = fetch.await;
 
@@ -1104,7 +1567,7 @@ impl<'gc> Loader<'gc> {
                 }
 
                 match response {
-                    Ok(response) => {
+                    Ok(mut response) => {
                         let total_len = response.body.len();
 
                         // FIXME - the "open" event should be fired earlier, just before
@@ -1119,27 +1582,32 @@ impl<'gc> Loader<'gc> {
                         let open_evt =
                             Avm2EventObject::bare_default_event(&mut activation.context, "open");
                         Avm2::dispatch_event(&mut activation.context, open_evt, target);
-                        set_data(response.body, &mut activation, target, data_format);
+                        let body = std::mem::take(&mut response.body);
+                        set_data(body, &mut activation, target, data_format);
 
                         // FIXME - we should fire "progress" events as we receive data, not
                         // just at the end
-                        let progress_evt = activation
-                            .avm2()
-                            .classes()
-                            .progressevent
-                            .construct(
-                                &mut activation,
-                                &[
-                                    "progress".into(),
-                                    false.into(),
-                                    false.into(),
-                                    total_len.into(),
-                                    total_len.into(),
-                                ],
-                            )
-                            .map_err(|e| Error::Avm2Error(e.to_string()))?;
-
-                        Avm2::dispatch_event(&mut activation.context, progress_evt, target);
+                        // An empty body, as with `204 No Content` or a HEAD request, still
+                        // completes with empty data, but there is no progress to report.
+                        if total_len > 0 {
+                            let progress_evt = activation
+                                .avm2()
+                                .classes()
+                                .progressevent
+                                .construct(
+                                    &mut activation,
+                                    &[
+                                        "progress".into(),
+                                        false.into(),
+                                        false.into(),
+                                        total_len.into(),
+                                        total_len.into(),
+                                    ],
+                                )
+                                .map_err(|e| Error::Avm2Error(e.to_string()))?;
+
+                            Avm2::dispatch_event(&mut activation.context, progress_evt, target);
+                        }
 
                         let http_status_evt = activation
                             .avm2()
@@ -1156,6 +1624,12 @@ impl<'gc> Loader<'gc> {
                                 ],
                             )
                             .map_err(|e| Error::Avm2Error(e.to_string()))?;
+                        Self::set_http_status_response(
+                            &mut activation,
+                            http_status_evt,
+                            &response.url,
+                            Some(&response),
+                        )?;
 
                         Avm2::dispatch_event(&mut activation.context, http_status_evt, target);
 
@@ -1192,6 +1666,12 @@ impl<'gc> Loader<'gc> {
                                 ],
                             )
                             .map_err(|e| Error::Avm2Error(e.to_string()))?;
+                        Self::set_http_status_response(
+                            &mut activation,
+                            http_status_evt,
+                            &response.url,
+                            None,
+                        )?;
 
                         Avm2::dispatch_event(&mut activation.context, http_status_evt, target);
 
@@ -1220,6 +1700,282 @@ impl<'gc> Loader<'gc> {
         })
     }
 
+    /// Creates a future for a `URLStream` load call.
+    fn load_url_stream(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        request: Request,
+    ) -> OwnedFuture<(), Error> {
+        let handle = match self {
+            Loader::LoadURLStream { self_handle, .. } => {
+                self_handle.expect("Loader not self-introduced")
+            }
+            _ => return Box::pin(async { Err(Error::NotLoadDataLoader) }),
+        };
+
+        let player = player
+            .upgrade()
+            .expect("Could not upgrade weak reference to player");
+
+        Box::pin(async move {
+            let (body_sender, body_receiver) = async_channel::unbounded();
+            let (response_sender, response_receiver) = async_channel::bounded(1);
+
+            wait_for_policy_files(&player, &request).await;
+
+            // The fetch runs as its own task, so that we can process the body
+            // while it is still arriving.
+            let fetch = player
+                .lock()
+                .unwrap()
+                .navigator()
+                .fetch_stream(request, body_sender);
+            player
+                .lock()
+                .unwrap()
+                .navigator()
+                .spawn_future(Box::pin(async move {
+                    let _ = response_sender.send(fetch.await).await;
+                    Ok(())
+                }));
+
+            let mut bytes_loaded = 0;
+            let mut bytes_total = 0;
+            // The body channel closes once the fetch has finished, successfully or not.
+            // Returning early drops the receiver, which stops the fetch of a closed stream.
+            while let Ok(chunk) = body_receiver.recv().await {
+                let chunk = match chunk {
+                    BodyChunk::Length(length) => {
+                        bytes_total = length;
+                        continue;
+                    }
+                    BodyChunk::Data(data) if data.is_empty() => continue,
+                    BodyChunk::Data(data) => data,
+                };
+                let is_first_chunk = bytes_loaded == 0;
+                bytes_loaded += chunk.len();
+                player.lock().unwrap().update(|uc| {
+                    Self::url_stream_progress(
+                        uc,
+                        handle,
+                        &chunk,
+                        bytes_loaded,
+                        bytes_total,
+                        is_first_chunk,
+                    )
+                })?;
+            }
+
+            let response = response_receiver
+                .recv()
+                .await
+                .map_err(|_| Error::Cancelled)?;
+
+            player.lock().unwrap().update(|uc| {
+                let target = match uc.load_manager.get_loader(handle) {
+                    Some(&Loader::LoadURLStream { target_object, .. }) => target_object,
+                    None => return Err(Error::Cancelled),
+                    _ => unreachable!(),
+                };
+
+                let mut activation = Avm2Activation::from_nothing(uc.reborrow());
+
+                // A successful response without a body was still opened.
+                if bytes_loaded == 0 && response.is_ok() {
+                    let open_evt =
+                        Avm2EventObject::bare_default_event(&mut activation.context, "open");
+                    Avm2::dispatch_event(&mut activation.context, open_evt, target);
+                    if activation.context.load_manager.get_loader(handle).is_none() {
+                        return Ok(());
+                    }
+                }
+
+                let (status, redirected) = match &response {
+                    Ok(response) => (response.status, response.redirected),
+                    Err(ErrorResponse {
+                        error: Error::HttpNotOk(_, status, redirected),
+                        ..
+                    }) => (*status, *redirected),
+                    Err(_) => (0, false),
+                };
+                let url = match &response {
+                    Ok(response) => response.url.as_str(),
+                    Err(response) => response.url.as_str(),
+                };
+                let http_status_evt = activation
+                    .avm2()
+                    .classes()
+                    .httpstatusevent
+                    .construct(
+                        &mut activation,
+                        &[
+                            "httpStatus".into(),
+                            false.into(),
+                            false.into(),
+                            status.into(),
+                            redirected.into(),
+                        ],
+                    )
+                    .map_err(|e| Error::Avm2Error(e.to_string()))?;
+                Self::set_http_status_response(
+                    &mut activation,
+                    http_status_evt,
+                    url,
+                    response.as_ref().ok(),
+                )?;
+                Avm2::dispatch_event(&mut activation.context, http_status_evt, target);
+
+                // Any of the handlers may have closed the stream, or started another load.
+                if activation.context.load_manager.get_loader(handle).is_none() {
+                    return Ok(());
+                }
+
+                if response.is_ok() {
+                    let complete_evt =
+                        Avm2EventObject::bare_default_event(&mut activation.context, "complete");
+                    Avm2::dispatch_event(&mut activation.context, complete_evt, target);
+                } else {
+                    // FIXME - Match the exact error message generated by Flash
+                    let io_error_evt = activation
+                        .avm2()
+                        .classes()
+                        .ioerrorevent
+                        .construct(
+                            &mut activation,
+                            &[
+                                "ioError".into(),
+                                false.into(),
+                                false.into(),
+                                "Error #2032: Stream Error".into(),
+                                2032.into(),
+                            ],
+                        )
+                        .map_err(|e| Error::Avm2Error(e.to_string()))?;
+                    Avm2::dispatch_event(&mut activation.context, io_error_evt, target);
+                }
+
+                // The stream stays connected until its final event has been handled.
+                activation.context.load_manager.remove_loader(handle);
+
+                Ok(())
+            })
+        })
+    }
+
+    /// Appends a chunk of received data to a `URLStream`'s buffer, and notifies it.
+    fn url_stream_progress(
+        uc: &mut UpdateContext<'_, 'gc>,
+        handle: Handle,
+        chunk: &[u8],
+        bytes_loaded: usize,
+        bytes_total: usize,
+        is_first_chunk: bool,
+    ) -> Result<(), Error> {
+        let (target, buffer) = match uc.load_manager.get_loader(handle) {
+            Some(&Loader::LoadURLStream {
+                target_object,
+                buffer,
+                ..
+            }) => (target_object, buffer),
+            // The stream was closed, or another load was started on it.
+            None => return Err(Error::Cancelled),
+            _ => unreachable!(),
+        };
+
+        let mut activation = Avm2Activation::from_nothing(uc.reborrow());
+
+        if is_first_chunk {
+            let open_evt = Avm2EventObject::bare_default_event(&mut activation.context, "open");
+            Avm2::dispatch_event(&mut activation.context, open_evt, target);
+            if activation.context.load_manager.get_loader(handle).is_none() {
+                return Err(Error::Cancelled);
+            }
+        }
+
+        if let Some(mut storage) = buffer.as_bytearray_mut(activation.context.gc_context) {
+            // Appending doesn't move the read position, so unread data is kept.
+            let end = storage.len();
+            storage
+                .write_at(chunk, end)
+                .map_err(|_| Error::Avm2Error("URLStream buffer is full".to_string()))?;
+        }
+
+        // The total length is 0 when the server didn't say what it is.
+        let progress_evt = activation
+            .avm2()
+            .classes()
+            .progressevent
+            .construct(
+                &mut activation,
+                &[
+                    "progress".into(),
+                    false.into(),
+                    false.into(),
+                    bytes_loaded.into(),
+                    bytes_total.into(),
+                ],
+            )
+            .map_err(|e| Error::Avm2Error(e.to_string()))?;
+        Avm2::dispatch_event(&mut activation.context, progress_evt, target);
+
+        Ok(())
+    }
+
+    /// Fills in where an `HTTPStatusEvent` came from, and the headers of the response.
+    ///
+    /// A redirect that wasn't followed always gets a `Location` header, even if the backend
+    /// couldn't see the response's headers.
+    fn set_http_status_response(
+        activation: &mut Avm2Activation<'_, 'gc>,
+        http_status_evt: Avm2Object<'gc>,
+        url: &str,
+        response: Option<&SuccessResponse>,
+    ) -> Result<(), Error> {
+        let response_url = AvmString::new_utf8(activation.context.gc_context, url);
+        http_status_evt
+            .set_public_property("responseURL", response_url.into(), activation)
+            .map_err(|e| Error::Avm2Error(e.to_string()))?;
+
+        let mut response_headers: Vec<(&str, &str)> = response
+            .map(|response| {
+                response
+                    .headers
+                    .iter()
+                    .map(|(name, value)| (name.as_str(), value.as_str()))
+                    .collect()
+            })
+            .unwrap_or_default();
+        if let Some(redirect_url) = response.and_then(|response| response.redirect_url.as_deref()) {
+            if !response_headers
+                .iter()
+                .any(|(name, _)| name.eq_ignore_ascii_case("Location"))
+            {
+                response_headers.push(("Location", redirect_url));
+            }
+        }
+
+        let mut headers = Vec::new();
+        for (name, value) in response_headers {
+            let name = AvmString::new_utf8(activation.context.gc_context, name);
+            let value = AvmString::new_utf8(activation.context.gc_context, value);
+            let header = activation
+                .avm2()
+                .classes()
+                .urlrequestheader
+                .construct(activation, &[name.into(), value.into()])
+                .map_err(|e| Error::Avm2Error(e.to_string()))?;
+            headers.push(Some(header.into()));
+        }
+        let headers =
+            Avm2ArrayObject::from_storage(activation, ArrayStorage::from_storage(headers))
+                .map_err(|e| Error::Avm2Error(e.to_string()))?;
+        http_status_evt
+            .set_public_property("responseHeaders", headers.into(), activation)
+            .map_err(|e| Error::Avm2Error(e.to_string()))?;
+
+        Ok(())
+    }
+
     /// Creates a future for a Sound load call.
     fn sound_loader_avm1(
         &mut self,
@@ -1239,6 +1995,7 @@ impl<'gc> Loader<'gc> {
             .expect("Could not upgrade weak reference to player");
 
         Box::pin(async move {
+            wait_for_policy_files(&player, &request).await;
             let fetch = player.lock().unwrap().navigator().fetch(request);
             let data = fetch.await;
 
@@ -1251,9 +2008,13 @@ impl<'gc> Loader<'gc> {
                     _ => return Err(Error::NotSoundLoader),
                 };
 
+                // An empty body fails the load, rather than registering a silent sound.
                 let success = data
                     .map_err(|e| e.error)
                     .and_then(|data| {
+                        if data.body.is_empty() {
+                            return Err(Error::EmptyBody);
+                        }
                         let handle = uc.audio.register_mp3(&data.body)?;
                         sound_object.set_sound(uc.gc_context, Some(handle));
                         let duration = uc
@@ -1302,6 +2063,7 @@ impl<'gc> Loader<'gc> {
             .expect("Could not upgrade weak reference to player");
 
         Box::pin(async move {
+            wait_for_policy_files(&player, &request).await;
             let fetch = player.lock().unwrap().navigator().fetch(request);
             let response = fetch.await;
 
@@ -1313,9 +2075,16 @@ impl<'gc> Loader<'gc> {
                     _ => return Err(Error::NotSoundLoader),
                 };
 
-                match response {
-                    Ok(response) => {
-                        let handle = uc.audio.register_mp3(&response.body)?;
+                // An empty or undecodable body fails the load with an `ioError`, like a failed fetch.
+                let sound = response.map_err(|e| e.error).and_then(|response| {
+                    if response.body.is_empty() {
+                        return Err(Error::EmptyBody);
+                    }
+                    Ok(uc.audio.register_mp3(&response.body)?)
+                });
+
+                match sound {
+                    Ok(handle) => {
                         if let Err(e) = sound_object
                             .as_sound_object()
                             .expect("Not a sound object")
@@ -1336,7 +2105,9 @@ impl<'gc> Loader<'gc> {
                         );
                         Avm2::dispatch_event(uc, complete_evt, sound_object);
                     }
-                    Err(_err) => {
+                    Err(err) => {
+                        tracing::warn!("Sound failed to load: {err}");
+
                         // FIXME: Match the exact error message generated by Flash.
                         let mut activation = Avm2Activation::from_nothing(uc.reborrow());
                         let io_error_evt_cls = activation.avm2().classes().ioerrorevent;
@@ -1379,6 +2150,7 @@ impl<'gc> Loader<'gc> {
             .expect("Could not upgrade weak reference to player");
 
         Box::pin(async move {
+            wait_for_policy_files(&player, &request).await;
             let fetch = player.lock().unwrap().navigator().fetch(request);
             let response = fetch.await;
 
@@ -1391,12 +2163,16 @@ impl<'gc> Loader<'gc> {
                 };
 
                 match response {
+                    Ok(response) if response.body.is_empty() => {
+                        stream.reset_buffer(uc);
+                        stream.report_error(uc, Error::EmptyBody);
+                    }
                     Ok(mut response) => {
                         stream.reset_buffer(uc);
                         stream.load_buffer(uc, &mut response.body);
                     }
                     Err(response) => {
-                        stream.report_error(response.error);
+                        stream.report_error(uc, response.error);
                     }
                 }
 
@@ -1455,17 +2231,28 @@ impl<'gc> Loader<'gc> {
         status: u16,
         redirected: bool,
         loader_url: Option<String>,
+        decoded_image: Option<Bitmap>,
     ) -> Result<(), Error> {
         let sniffed_type = ContentType::sniff(data);
         let length = data.len();
 
-        if sniffed_type == ContentType::Unknown {
+        if sniffed_type == ContentType::Unknown && length > 0 {
             if let Ok(data) = extract_swz(data) {
                 return Self::movie_loader_data(
-                    handle, player, &data, url, status, redirected, loader_url,
+                    handle, player, &data, url, status, redirected, loader_url, None,
                 );
             }
         }
+        #[cfg(feature = "zip")]
+        if sniffed_type == ContentType::Zip {
+            if let Some(data) = package::extract_main_swf(data) {
+                return Self::movie_loader_data(
+                    handle, player, &data, url, status, redirected, loader_url, None,
+                );
+            }
+        }
+
+        let strictness = player.lock().unwrap().tag_strictness();
         player.lock().unwrap().update(|uc| {
             let (clip, vm_data) = match uc.load_manager.get_loader(handle) {
                 Some(Loader::Movie {
@@ -1501,13 +2288,26 @@ impl<'gc> Loader<'gc> {
                 None
             };
 
+            let decompress_start = Instant::now();
             let movie = match sniffed_type {
-                ContentType::Swf => Arc::new(SwfMovie::from_data(data, url.clone(), loader_url)?),
+                ContentType::Swf => Arc::new(SwfMovie::from_data_with_strictness(
+                    data,
+                    url.clone(),
+                    loader_url,
+                    strictness,
+                )?),
                 ContentType::Gif | ContentType::Jpeg | ContentType::Png => {
                     Arc::new(SwfMovie::from_loaded_image(url.clone(), length))
                 }
-                ContentType::Unknown => Arc::new(SwfMovie::error_movie(url.clone())),
+                ContentType::JpegXr | ContentType::Zip | ContentType::Unknown => {
+                    Arc::new(SwfMovie::error_movie(url.clone()))
+                }
             };
+            if let Some(metrics) = activation.context.load_manager.metrics_mut(handle) {
+                metrics.url = Some(url.clone());
+                metrics.compressed_bytes = length;
+                metrics.decompress_duration = Some(decompress_start.elapsed());
+            }
 
             match activation.context.load_manager.get_loader_mut(handle) {
                 Some(Loader::Movie {
@@ -1605,7 +2405,10 @@ impl<'gc> Loader<'gc> {
 
                     // This will construct AVM2-side objects even under AVM1, but it doesn't matter,
                     // since Bitmap and BitmapData never have AVM1-side objects.
-                    let bitmap = ruffle_render::utils::decode_define_bits_jpeg(data, None)?;
+                    let bitmap = match decoded_image {
+                        Some(bitmap) => bitmap,
+                        None => ruffle_render::utils::decode_define_bits_jpeg(data, None, 0.0)?,
+                    };
 
                     let transparency = true;
                     let bitmap_data = BitmapData::new_with_pixels(
@@ -1643,7 +2446,7 @@ impl<'gc> Loader<'gc> {
                         redirected,
                     )?;
                 }
-                ContentType::Unknown => {
+                ContentType::JpegXr | ContentType::Zip | ContentType::Unknown => {
                     if activation.context.is_action_script_3() {
                         Loader::movie_loader_progress(
                             handle,
@@ -1651,13 +2454,21 @@ impl<'gc> Loader<'gc> {
                             length,
                             length,
                         )?;
+                        // Flash can show JPEG XR images, so the reason they fail to load is
+                        // told apart from files that really are of an unknown type.
+                        let (message, error_id) = if sniffed_type == ContentType::JpegXr {
+                            (format!("JPEG XR images are not supported. URL: {url}"), 0)
+                        } else {
+                            (
+                                format!("Error #2124: Loaded file is an unknown type. URL: {url}"),
+                                2124,
+                            )
+                        };
                         Loader::movie_loader_error(
                             handle,
                             uc,
-                            AvmString::new_utf8(
-                                uc.gc_context,
-                                &format!("Error #2124: Loaded file is an unknown type. URL: {url}"),
-                            ),
+                            AvmString::new_utf8(uc.gc_context, &message),
+                            error_id,
                             status,
                             redirected,
                             url,
@@ -1685,6 +2496,151 @@ impl<'gc> Loader<'gc> {
         }) //TODO: content sniffing errors need to be reported somehow
     }
 
+    /// Checks that loaded content may be parented to the `requestedContentParent` of its
+    /// `LoaderContext`, returning the error Flash reports if it can't.
+    fn check_requested_parent(
+        activation: &mut Avm2Activation<'_, 'gc>,
+        requested_parent: DisplayObjectContainer<'gc>,
+        loader: DisplayObjectContainer<'gc>,
+        movie: &SwfMovie,
+        from_bytes: bool,
+        content: DisplayObject<'gc>,
+    ) -> Result<(), Avm2Error<'gc>> {
+        // Loaders refuse children of their own.
+        if let DisplayObjectContainer::LoaderDisplay(_) = requested_parent {
+            return Err(Avm2Error::AvmError(illegal_operation_error(
+                activation,
+                "Error #2069: The Loader class does not implement this method.",
+                2069,
+            )?));
+        }
+
+        if content
+            .as_movie_clip()
+            .is_some_and(|clip| !clip.movie().is_action_script_3())
+        {
+            return Err(Avm2Error::AvmError(illegal_operation_error(
+                activation,
+                "Error #2180: It is illegal to move AVM1 content (AS1 or AS2) to a different \
+                 part of the displayList when it has been loaded into AVM2 (AS3) content.",
+                2180,
+            )?));
+        }
+
+        let loader_movie = DisplayObject::from(loader).movie();
+        let content_url = if from_bytes {
+            loader_movie.url()
+        } else {
+            movie.url()
+        };
+        let parent_movie = DisplayObject::from(requested_parent).movie();
+        if let Some(code) = sandbox_violation(content_url, parent_movie.url()) {
+            let message = format!(
+                "Error #{code}: Security sandbox violation: caller {content_url} cannot access {}.",
+                parent_movie.url()
+            );
+            let error = security_error(activation, &message, code)?;
+            return Err(Avm2Error::AvmError(error));
+        }
+
+        Ok(())
+    }
+
+    /// Decodes a loaded image as soon as it arrives if the load's `LoaderContext` asks for
+    /// `ImageDecodingPolicy.ON_LOAD`, without holding up the player.
+    ///
+    /// The image is decoded on a thread of its own, except on the web, where there are none to
+    /// spare. Other images are decoded once the player gets to them.
+    async fn decode_image_on_load(
+        handle: Handle,
+        player: &Mutex<Player>,
+        data: &[u8],
+    ) -> Result<Option<Bitmap>, Error> {
+        let is_image = matches!(
+            ContentType::sniff(data),
+            ContentType::Gif | ContentType::Jpeg | ContentType::Png
+        );
+        if !is_image
+            || !player
+                .lock()
+                .unwrap()
+                .update(|uc| Loader::decodes_images_on_load(handle, uc))
+        {
+            return Ok(None);
+        }
+
+        #[cfg(not(target_family = "wasm"))]
+        let bitmap = {
+            let (sender, receiver) = async_channel::bounded(1);
+            let data = data.to_vec();
+            std::thread::spawn(move || {
+                let _ = sender.try_send(ruffle_render::utils::decode_define_bits_jpeg(
+                    &data, None, 0.0,
+                ));
+            });
+            receiver.recv().await.map_err(|_| Error::Cancelled)??
+        };
+        #[cfg(target_family = "wasm")]
+        let bitmap = ruffle_render::utils::decode_define_bits_jpeg(data, None, 0.0)?;
+        Ok(Some(bitmap))
+    }
+
+    /// Whether the `LoaderContext` of an AVM2 load asks for images to be decoded as soon as
+    /// they arrive (`ImageDecodingPolicy.ON_LOAD`).
+    fn decodes_images_on_load(handle: Handle, uc: &mut UpdateContext<'_, 'gc>) -> bool {
+        let context = match uc.load_manager.get_loader(handle) {
+            Some(Loader::Movie {
+                vm_data:
+                    MovieLoaderVMData::Avm2 {
+                        context: Some(context),
+                        ..
+                    },
+                ..
+            }) => *context,
+            _ => return false,
+        };
+
+        let mut activation = Avm2Activation::from_nothing(uc.reborrow());
+        context
+            .get_public_property("imageDecodingPolicy", &mut activation)
+            .and_then(|policy| policy.coerce_to_string(&mut activation))
+            .is_ok_and(|policy| &*policy == b"onLoad")
+    }
+
+    /// Report that part of a movie has arrived, before any of it can be parsed.
+    fn movie_loader_stream_progress(
+        handle: Index,
+        uc: &mut UpdateContext<'_, 'gc>,
+        loaded_len: usize,
+        total_len: usize,
+    ) -> Result<(), Error> {
+        let vm_data = match uc.load_manager.get_loader(handle) {
+            Some(Loader::Movie { vm_data, .. }) => *vm_data,
+            None => return Err(Error::Cancelled),
+            _ => unreachable!(),
+        };
+
+        if let MovieLoaderVMData::Avm2 { loader_info, .. } = vm_data {
+            let loader_info = *loader_info.as_loader_info_object().unwrap();
+            if loader_info.streamed_bytes() == 0 {
+                // As when the movie arrives all at once, the first 'progress' event
+                // only knows 'bytesTotal', which comes from a fake movie.
+                let fake_movie = Arc::new(SwfMovie::empty_fake_compressed_len(
+                    uc.swf.version(),
+                    total_len,
+                ));
+                loader_info.set_loader_stream(
+                    LoaderStream::NotYetLoaded(fake_movie, None, false),
+                    uc.gc_context,
+                );
+                Loader::movie_loader_progress(handle, uc, 0, total_len)?;
+            }
+            loader_info.set_streamed_bytes(uc.gc_context, loaded_len);
+        }
+
+        Loader::movie_loader_progress(handle, uc, loaded_len, total_len)
+    }
+
     /// Report a movie loader progress event to script code.
     ///
     /// The current and total length are always reported as compressed lengths.
@@ -1710,6 +2666,10 @@ impl<'gc> Loader<'gc> {
             _ => unreachable!(),
         };
 
+        if !uc.load_manager.record_progress(handle, cur_len, total_len) {
+            return Ok(());
+        }
+
         match vm_data {
             MovieLoaderVMData::Avm1 { broadcaster } => {
                 if let Some(broadcaster) = broadcaster {
@@ -1761,13 +2721,15 @@ impl<'gc> Loader<'gc> {
         status: u16,
         redirected: bool,
     ) -> Result<(), Error> {
-        let (target_clip, vm_data, movie) = match uc.load_manager.get_loader_mut(handle) {
+        let loader = uc.load_manager.get_loader_mut(handle);
+        let (target_clip, vm_data, movie, from_bytes) = match loader {
             Some(Loader::Movie {
                 target_clip,
                 movie,
                 vm_data,
+                from_bytes,
                 ..
-            }) => (*target_clip, *vm_data, movie.clone()),
+            }) => (*target_clip, *vm_data, movie.clone(), *from_bytes),
             None => return Err(Error::Cancelled),
             _ => unreachable!(),
         };
@@ -1824,14 +2786,20 @@ impl<'gc> Loader<'gc> {
             }
         }
 
-        if let MovieLoaderVMData::Avm2 { loader_info, .. } = vm_data {
+        if let MovieLoaderVMData::Avm2 {
+            loader_info,
+            context,
+            ..
+        } = vm_data
+        {
+            let movie = movie.unwrap();
             let domain = uc
                 .library
-                .library_for_movie(movie.unwrap())
+                .library_for_movie(movie.clone())
                 .unwrap()
                 .avm2_domain();
             let mut activation = Avm2Activation::from_domain(uc.reborrow(), domain);
-            let mut loader = loader_info
+            let loader = loader_info
                 .get_public_property("loader", &mut activation)
                 .map_err(|e| Error::Avm2Error(e.to_string()))?
                 .as_object()
@@ -1841,6 +2809,33 @@ impl<'gc> Loader<'gc> {
                 .as_container()
                 .unwrap();
 
+            let requested_parent = context
+                .and_then(|o| {
+                    o.get_public_property("requestedContentParent", &mut activation)
+                        .ok()
+                })
+                .and_then(|v| v.as_object())
+                .and_then(|o| o.as_display_object())
+                .and_then(|d| d.as_container());
+            // Content that can't go where it was asked to stays with its loader, and the
+            // reason is reported with an `asyncError` event.
+            let mut parent = loader;
+            let mut parent_error = None;
+            if let Some(requested_parent) = requested_parent {
+                match Loader::check_requested_parent(
+                    &mut activation,
+                    requested_parent,
+                    loader,
+                    &movie,
+                    from_bytes,
+                    dobj.unwrap(),
+                ) {
+                    Ok(()) => parent = requested_parent,
+                    Err(Avm2Error::AvmError(error)) => parent_error = Some(error),
+                    Err(e) => return Err(Error::Avm2Error(e.to_string())),
+                }
+            }
+
             // Note that we do *not* use the 'addChild' method here:
             // Per the flash docs, our implementation always throws
             // an 'unsupported' error. Also, the AVM2 side of our movie
@@ -1848,7 +2843,33 @@ impl<'gc> Loader<'gc> {
             // frame constructor will see an 'added' event immediately, and
             // an 'addedToStage' event *after* the constructor finishes
             // when we add the movie as a child of the loader.
-            loader.insert_at_index(&mut activation.context, dobj.unwrap(), 0);
+            //
+            // A requested parent keeps its existing children below the content.
+            let index = if DisplayObject::ptr_eq(parent.into(), loader.into()) {
+                0
+            } else {
+                parent.num_children()
+            };
+            parent.insert_at_index(&mut activation.context, dobj.unwrap(), index);
+
+            if let Some(error) = parent_error {
+                let text = error
+                    .as_object()
+                    .map(|error| error.get_public_property("message", &mut activation))
+                    .transpose()
+                    .map_err(|e| Error::Avm2Error(e.to_string()))?
+                    .unwrap_or(Avm2Value::Null);
+                let async_error_evt = activation
+                    .avm2()
+                    .classes()
+                    .asyncerrorevent
+                    .construct(
+                        &mut activation,
+                        &["asyncError".into(), false.into(), false.into(), text, error],
+                    )
+                    .map_err(|e| Error::Avm2Error(e.to_string()))?;
+                Avm2::dispatch_event(&mut activation.context, async_error_evt, loader_info);
+            }
         } else if let Some(dobj) = dobj {
             // This is a load of an image into AVM1 - add it as a child of the target clip.
             if dobj.as_movie_clip().is_none() {
@@ -1908,6 +2929,7 @@ impl<'gc> Loader<'gc> {
         handle: Index,
         uc: &mut UpdateContext<'_, 'gc>,
         msg: AvmString<'gc>,
+        error_id: u32,
         status: u16,
         redirected: bool,
         swf_url: String,
@@ -1980,7 +3002,7 @@ impl<'gc> Loader<'gc> {
                             false.into(),
                             false.into(),
                             msg.into(),
-                            0.into(),
+                            error_id.into(),
                         ],
                     )
                     .map_err(|e| Error::Avm2Error(e.to_string()))?;
@@ -2108,3 +3130,135 @@ impl<'gc> Loader<'gc> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        sandbox_violation, Handle, LoadManager, LoadMetrics, PreloadPriority, MAX_LOAD_METRICS,
+        MAX_SKIPPED_PRELOADS,
+    };
+    use std::time::Duration;
+
+    #[test]
+    fn content_may_only_be_parented_within_its_sandbox() {
+        let parent = "https://example.com/main.swf";
+        assert_eq!(sandbox_violation("https://example.com/a.swf", parent), None);
+        assert_eq!(
+            sandbox_violation("https://cdn.example.com/a.swf", parent),
+            Some(2059)
+        );
+        assert_eq!(
+            sandbox_violation("http://example.com/a.swf", parent),
+            Some(2059)
+        );
+        assert_eq!(sandbox_violation("file:///a.swf", parent), Some(2060));
+        assert_eq!(
+            sandbox_violation(parent, "file:///C:/games/main.swf"),
+            Some(2060)
+        );
+        assert_eq!(
+            sandbox_violation("file:///a.swf", "file:///b/main.swf"),
+            None
+        );
+    }
+
+    #[test]
+    fn metrics_of_old_loads_are_dropped() {
+        let handle = |index| Handle::from_raw_parts(index, 0);
+        let mut load_manager = LoadManager::new();
+        for index in 0..=MAX_LOAD_METRICS {
+            load_manager.start_metrics(handle(index));
+        }
+
+        assert!(load_manager.metrics(handle(0)).is_none());
+        assert!(load_manager.metrics(handle(1)).is_some());
+        assert!(load_manager.metrics(handle(MAX_LOAD_METRICS)).is_some());
+        assert_eq!(load_manager.all_metrics().count(), MAX_LOAD_METRICS);
+        assert_eq!(
+            load_manager.all_metrics().next().map(|(handle, _)| handle),
+            Some(handle(1))
+        );
+    }
+
+    #[test]
+    fn preloads_are_ordered_by_priority() {
+        let handle = |index| Handle::from_raw_parts(index, 0);
+        let load_manager = LoadManager::new();
+        let order = load_manager.preload_order(vec![
+            (handle(0), PreloadPriority::Offscreen),
+            (handle(1), PreloadPriority::Visible),
+            (handle(2), PreloadPriority::Root),
+            (handle(3), PreloadPriority::Visible),
+        ]);
+
+        assert_eq!(
+            order,
+            vec![
+                (handle(2), PreloadPriority::Root),
+                (handle(1), PreloadPriority::Visible),
+                (handle(3), PreloadPriority::Visible),
+                (handle(0), PreloadPriority::Offscreen),
+            ]
+        );
+    }
+
+    #[test]
+    fn deferred_preloads_are_eventually_prioritized() {
+        let handle = |index| Handle::from_raw_parts(index, 0);
+        let mut load_manager = LoadManager::new();
+        let loaders = vec![
+            (handle(0), PreloadPriority::Visible),
+            (handle(1), PreloadPriority::Offscreen),
+        ];
+
+        for _ in 1..MAX_SKIPPED_PRELOADS {
+            load_manager.defer_preload(handle(1));
+        }
+        assert_eq!(load_manager.preload_order(loaders.clone())[0].0, handle(0));
+
+        load_manager.defer_preload(handle(1));
+        assert_eq!(
+            load_manager.preload_order(loaders.clone())[0],
+            (handle(1), PreloadPriority::Root)
+        );
+
+        // Finished loaders are forgotten.
+        load_manager.remove_loader(handle(1));
+        assert_eq!(load_manager.preload_order(loaders)[0].0, handle(0));
+    }
+
+    #[test]
+    fn progress_is_only_reported_once() {
+        let handle = |index| Handle::from_raw_parts(index, 0);
+        let mut load_manager = LoadManager::new();
+
+        // As the movie arrives...
+        assert!(load_manager.record_progress(handle(0), 0, 300));
+        assert!(load_manager.record_progress(handle(0), 100, 300));
+        assert!(load_manager.record_progress(handle(0), 300, 300));
+
+        // ...and as it gets preloaded.
+        assert!(!load_manager.record_progress(handle(0), 150, 300));
+        assert!(load_manager.record_progress(handle(0), 300, 300));
+
+        // Other loaders, and loads of unknown length, aren't affected.
+        assert!(load_manager.record_progress(handle(1), 150, 300));
+        assert!(load_manager.record_progress(handle(0), 150, 0));
+
+        load_manager.remove_loader(handle(1));
+        assert!(load_manager.record_progress(handle(1), 0, 300));
+    }
+
+    #[test]
+    fn bytes_per_second() {
+        let mut metrics = LoadMetrics::new();
+        assert_eq!(metrics.bytes_per_second(), None);
+
+        metrics.compressed_bytes = 1000;
+        metrics.fetch_duration = Some(Duration::from_millis(500));
+        assert_eq!(metrics.bytes_per_second(), Some(2000.0));
+
+        metrics.fetch_duration = Some(Duration::ZERO);
+        assert_eq!(metrics.bytes_per_second(), None);
+    }
+}
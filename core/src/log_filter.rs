@@ -0,0 +1,240 @@
+//! Choosing how much each part of Ruffle logs.
+//!
+//! Ruffle logs through `tracing`, whose targets are module paths. A `LogFilter` groups those
+//! modules into a few subsystems, so that e.g. networking can be traced without also tracing
+//! every AVM2 opcode. Frontends turn it into `tracing_subscriber` filter directives.
+
+use std::fmt;
+use std::str::FromStr;
+use tracing::level_filters::LevelFilter;
+
+/// A part of Ruffle whose logging can be controlled on its own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LogSubsystem {
+    /// Loading movies and other content.
+    Loader,
+    Avm1,
+    Avm2,
+    Render,
+
+    /// Requests, sockets, streams and peer-to-peer groups.
+    Net,
+
+    /// Frame and render timings, and metrics sent by movies, when telemetry is enabled.
+    Telemetry,
+}
+
+impl LogSubsystem {
+    pub const ALL: [Self; 6] = [
+        Self::Loader,
+        Self::Avm1,
+        Self::Avm2,
+        Self::Render,
+        Self::Net,
+        Self::Telemetry,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Loader => "loader",
+            Self::Avm1 => "avm1",
+            Self::Avm2 => "avm2",
+            Self::Render => "render",
+            Self::Net => "net",
+            Self::Telemetry => "telemetry",
+        }
+    }
+
+    /// The `tracing` targets that belong to this subsystem. Each one also covers its submodules.
+    pub fn targets(self) -> &'static [&'static str] {
+        match self {
+            Self::Loader => &[
+                "ruffle_core::loader",
+                "ruffle_core::package",
+                "ruffle_core::tag_utils",
+            ],
+            Self::Avm1 => &["ruffle_core::avm1"],
+            Self::Avm2 => &["ruffle_core::avm2"],
+            Self::Render => &[
+                "ruffle_render",
+                "ruffle_render_wgpu",
+                "wgpu_core",
+                "wgpu_hal",
+            ],
+            Self::Net => &[
+                "ruffle_core::backend::navigator",
+                "ruffle_core::net_group",
+                "ruffle_core::socket",
+                "ruffle_core::streams",
+                "ruffle_desktop::backends::navigator",
+            ],
+            Self::Telemetry => &["ruffle_core::telemetry"],
+        }
+    }
+}
+
+impl fmt::Display for LogSubsystem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for LogSubsystem {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|subsystem| subsystem.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| {
+                let names: Vec<_> = Self::ALL.iter().map(|s| s.name()).collect();
+                format!(
+                    "unknown log subsystem '{s}', expected one of: {}",
+                    names.join(", ")
+                )
+            })
+    }
+}
+
+/// How log messages are written.
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LogFormat {
+    /// Human-readable lines.
+    #[default]
+    Text,
+
+    /// One JSON object per line, for other tools to filter.
+    Json,
+}
+
+/// How verbose logging is, overall and per subsystem.
+///
+/// Written as a comma-separated list of a default level and `subsystem=level` pairs, such as
+/// `warn,avm2=debug,net=trace`. Levels are `off`, `error`, `warn`, `info`, `debug` and `trace`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct LogFilter {
+    /// The level of everything not given a level of its own.
+    pub default: Option<LevelFilter>,
+
+    /// Levels of individual subsystems. Later entries win.
+    pub subsystems: Vec<(LogSubsystem, LevelFilter)>,
+}
+
+impl LogFilter {
+    pub fn with_subsystem(mut self, subsystem: LogSubsystem, level: LevelFilter) -> Self {
+        self.subsystems.push((subsystem, level));
+        self
+    }
+
+    /// The level given to `subsystem`, if it has one of its own.
+    pub fn subsystem_level(&self, subsystem: LogSubsystem) -> Option<LevelFilter> {
+        self.subsystems
+            .iter()
+            .rev()
+            .find(|(s, _)| *s == subsystem)
+            .map(|(_, level)| *level)
+    }
+
+    /// Gives `subsystem` its own level, or makes it use the default level again.
+    pub fn set_subsystem_level(&mut self, subsystem: LogSubsystem, level: Option<LevelFilter>) {
+        self.subsystems.retain(|(s, _)| *s != subsystem);
+        if let Some(level) = level {
+            self.subsystems.push((subsystem, level));
+        }
+    }
+
+    /// The filter as `tracing_subscriber::EnvFilter` directives.
+    pub fn directives(&self) -> String {
+        let level_name = |level: LevelFilter| level.to_string().to_ascii_lowercase();
+        let mut directives = vec![];
+        if let Some(default) = self.default {
+            directives.push(level_name(default));
+        }
+        for (subsystem, level) in &self.subsystems {
+            for target in subsystem.targets() {
+                directives.push(format!("{target}={}", level_name(*level)));
+            }
+        }
+        directives.join(",")
+    }
+}
+
+impl fmt::Display for LogFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let level_name = |level: LevelFilter| level.to_string().to_ascii_lowercase();
+        let mut directives = vec![];
+        if let Some(default) = self.default {
+            directives.push(level_name(default));
+        }
+        for (subsystem, level) in &self.subsystems {
+            directives.push(format!("{subsystem}={}", level_name(*level)));
+        }
+        f.write_str(&directives.join(","))
+    }
+}
+
+impl FromStr for LogFilter {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut filter = Self::default();
+        for directive in s.split(',').map(str::trim).filter(|d| !d.is_empty()) {
+            let parse_level = |level: &str| {
+                LevelFilter::from_str(level).map_err(|_| format!("invalid log level '{level}'"))
+            };
+            match directive.split_once('=') {
+                Some((subsystem, level)) => {
+                    filter
+                        .subsystems
+                        .push((subsystem.trim().parse()?, parse_level(level.trim())?));
+                }
+                None => filter.default = Some(parse_level(directive)?),
+            }
+        }
+        Ok(filter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LogFilter, LogSubsystem};
+    use tracing::level_filters::LevelFilter;
+
+    #[test]
+    fn parse_filter() {
+        let filter: LogFilter = "warn, avm2=debug,NET=off".parse().unwrap();
+        assert_eq!(
+            filter,
+            LogFilter {
+                default: Some(LevelFilter::WARN),
+                subsystems: vec![],
+            }
+            .with_subsystem(LogSubsystem::Avm2, LevelFilter::DEBUG)
+            .with_subsystem(LogSubsystem::Net, LevelFilter::OFF)
+        );
+        assert!(filter
+            .directives()
+            .starts_with("warn,ruffle_core::avm2=debug,ruffle_core::backend::navigator=off"));
+
+        assert_eq!(filter.to_string(), "warn,avm2=debug,net=off");
+        assert_eq!(filter.to_string().parse::<LogFilter>(), Ok(filter));
+
+        assert!("avm3=debug".parse::<LogFilter>().is_err());
+        assert!("avm2=loud".parse::<LogFilter>().is_err());
+    }
+
+    #[test]
+    fn set_subsystem_level() {
+        let mut filter: LogFilter = "info,net=debug,avm1=warn,net=trace".parse().unwrap();
+        assert_eq!(
+            filter.subsystem_level(LogSubsystem::Net),
+            Some(LevelFilter::TRACE)
+        );
+        assert_eq!(filter.subsystem_level(LogSubsystem::Avm2), None);
+
+        filter.set_subsystem_level(LogSubsystem::Net, Some(LevelFilter::ERROR));
+        filter.set_subsystem_level(LogSubsystem::Avm1, None);
+        assert_eq!(filter.to_string(), "info,net=error");
+    }
+}
@@ -0,0 +1,234 @@
+//! Host implementations of AIR native extensions (`flash.external.ExtensionContext`).
+//!
+//! Native extensions ship platform libraries that Ruffle can't load. Instead, the embedder
+//! registers Rust implementations for the extension IDs it wants to support; content asking for
+//! any other extension gets a `null` context, just as if the extension weren't packaged.
+
+use crate::{
+    avm2::{Activation as Avm2Activation, Avm2, Object as Avm2Object},
+    context::UpdateContext,
+    external::Value,
+    string::AvmString,
+};
+use gc_arena::Collect;
+use generational_arena::{Arena, Index};
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::sync::mpsc::{channel, Receiver, Sender};
+
+pub type ExtensionContextHandle = Index;
+
+/// An implementation of a native extension, standing in for its native library.
+pub trait NativeExtension {
+    /// Creates a context, as `ExtensionContext.createExtensionContext` does.
+    ///
+    /// Returns `None` if the extension has no context of this type. `events` dispatches status
+    /// events on the context, from any thread, for as long as the context is alive.
+    fn create_context(
+        &self,
+        context_type: &str,
+        events: StatusEventSender,
+    ) -> Option<Box<dyn NativeExtensionContext>>;
+}
+
+/// Lets the same extension be registered under several IDs, or with several players.
+impl<T: NativeExtension + ?Sized> NativeExtension for Rc<T> {
+    fn create_context(
+        &self,
+        context_type: &str,
+        events: StatusEventSender,
+    ) -> Option<Box<dyn NativeExtensionContext>> {
+        (**self).create_context(context_type, events)
+    }
+}
+
+/// A single context of a native extension.
+pub trait NativeExtensionContext {
+    /// Calls one of the context's functions, as `ExtensionContext.call` does.
+    ///
+    /// Returns `None` if there is no function with this name.
+    fn call(&mut self, name: &str, args: &[Value]) -> Option<Value>;
+
+    /// Called when content disposes of the context.
+    fn dispose(&mut self) {}
+}
+
+/// A `StatusEvent` sent by an extension, as `FREDispatchStatusEventAsync` does.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatusEvent {
+    pub code: String,
+    pub level: String,
+}
+
+/// The context's end of `FREDispatchStatusEventAsync`.
+///
+/// Events are delivered to the `ExtensionContext` object on the player's next update.
+#[derive(Clone)]
+pub struct StatusEventSender {
+    handle: ExtensionContextHandle,
+    sender: Sender<(ExtensionContextHandle, StatusEvent)>,
+}
+
+impl StatusEventSender {
+    /// Returns `false` once the player that created the context has been dropped.
+    pub fn dispatch(&self, code: impl Into<String>, level: impl Into<String>) -> bool {
+        let event = StatusEvent {
+            code: code.into(),
+            level: level.into(),
+        };
+        self.sender.send((self.handle, event)).is_ok()
+    }
+}
+
+/// Why an `ExtensionContext.call` failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CallError {
+    /// The context was disposed, or never created.
+    Disposed,
+    /// The context has no function with the given name.
+    NoSuchFunction,
+}
+
+#[derive(Collect)]
+#[collect(no_drop)]
+struct ActiveContext<'gc> {
+    target: Avm2Object<'gc>,
+    /// `None` while `NativeExtension::create_context` runs, which needs the handle to exist.
+    #[collect(require_static)]
+    context: Option<Box<dyn NativeExtensionContext>>,
+}
+
+/// Manages registered extensions and the contexts created from them.
+pub struct NativeExtensions<'gc> {
+    extensions: HashMap<String, Box<dyn NativeExtension>>,
+    contexts: Arena<ActiveContext<'gc>>,
+
+    receiver: Receiver<(ExtensionContextHandle, StatusEvent)>,
+    sender: Sender<(ExtensionContextHandle, StatusEvent)>,
+}
+
+unsafe impl<'gc> Collect for NativeExtensions<'gc> {
+    fn trace(&self, cc: &gc_arena::Collection) {
+        for (_, context) in self.contexts.iter() {
+            context.trace(cc)
+        }
+    }
+}
+
+impl<'gc> NativeExtensions<'gc> {
+    pub fn new(extensions: HashMap<String, Box<dyn NativeExtension>>) -> Self {
+        let (sender, receiver) = channel();
+
+        Self {
+            extensions,
+            contexts: Arena::new(),
+            receiver,
+            sender,
+        }
+    }
+
+    pub fn register(&mut self, id: String, extension: Box<dyn NativeExtension>) {
+        self.extensions.insert(id, extension);
+    }
+
+    /// Whether an implementation is registered for `id`.
+    pub fn is_available(&self, id: &str) -> bool {
+        self.extensions.contains_key(id)
+    }
+
+    fn find(&self, target: Avm2Object<'gc>) -> Option<ExtensionContextHandle> {
+        self.contexts
+            .iter()
+            .find(|(_, context)| Avm2Object::ptr_eq(context.target, target))
+            .map(|(handle, _)| handle)
+    }
+
+    /// Creates a context of extension `id` for the `ExtensionContext` object `target`.
+    ///
+    /// Returns `false` if the extension isn't registered or refuses the context type.
+    pub fn create_context(
+        &mut self,
+        target: Avm2Object<'gc>,
+        id: &str,
+        context_type: &str,
+    ) -> bool {
+        let Some(extension) = self.extensions.get(id) else {
+            tracing::warn!("Content asked for unavailable native extension {id}");
+            return false;
+        };
+
+        // The status event sender refers to the context by handle, so the slot is taken
+        // before the extension builds the context, and given back if it declines.
+        let handle = self.contexts.insert(ActiveContext {
+            target,
+            context: None,
+        });
+        let events = StatusEventSender {
+            handle,
+            sender: self.sender.clone(),
+        };
+        match extension.create_context(context_type, events) {
+            Some(context) => {
+                self.contexts[handle].context = Some(context);
+                true
+            }
+            None => {
+                self.contexts.remove(handle);
+                false
+            }
+        }
+    }
+
+    pub fn call(
+        &mut self,
+        target: Avm2Object<'gc>,
+        name: &str,
+        args: &[Value],
+    ) -> Result<Value, CallError> {
+        let handle = self.find(target).ok_or(CallError::Disposed)?;
+        let context = self.contexts[handle]
+            .context
+            .as_mut()
+            .ok_or(CallError::Disposed)?;
+        context.call(name, args).ok_or(CallError::NoSuchFunction)
+    }
+
+    pub fn dispose(&mut self, target: Avm2Object<'gc>) {
+        if let Some(handle) = self.find(target) {
+            if let Some(mut context) = self.contexts.remove(handle).and_then(|c| c.context) {
+                context.dispose();
+            }
+        }
+    }
+
+    /// Dispatches the status events sent by extensions since the last update.
+    pub fn update_native_extensions(context: &mut UpdateContext<'_, 'gc>) {
+        let mut events = vec![];
+        while let Ok((handle, event)) = context.native_extensions.receiver.try_recv() {
+            // Events for disposed contexts are dropped, as in AIR.
+            if let Some(active) = context.native_extensions.contexts.get(handle) {
+                events.push((active.target, event));
+            }
+        }
+
+        for (target, event) in events {
+            let mut activation = Avm2Activation::from_nothing(context.reborrow());
+            let code = AvmString::new_utf8(activation.context.gc_context, event.code);
+            let level = AvmString::new_utf8(activation.context.gc_context, event.level);
+            let event = activation.avm2().classes().statusevent.construct(
+                &mut activation,
+                &[
+                    "status".into(),
+                    false.into(),
+                    false.into(),
+                    code.into(),
+                    level.into(),
+                ],
+            );
+            match event {
+                Ok(event) => Avm2::dispatch_event(&mut activation.context, event, target),
+                Err(e) => tracing::error!("Couldn't create StatusEvent: {e:?}"),
+            }
+        }
+    }
+}
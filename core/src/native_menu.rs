@@ -0,0 +1,29 @@
+//! The menu AIR content sets with `NativeWindow.menu`.
+//!
+//! The host is given a plain description of the menu, and reports selections back by item id.
+//! The `NativeMenuItem` objects are kept here, so selections reach the items that were shown
+//! even if content rearranges the menu in the meantime.
+
+use crate::avm2::Object as Avm2Object;
+use gc_arena::Collect;
+
+#[derive(Collect, Default)]
+#[collect(no_drop)]
+pub struct NativeMenuState<'gc> {
+    /// The shown `NativeMenuItem`s, indexed by id.
+    items: Vec<Avm2Object<'gc>>,
+}
+
+impl<'gc> NativeMenuState<'gc> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_items(&mut self, items: Vec<Avm2Object<'gc>>) {
+        self.items = items;
+    }
+
+    pub fn item(&self, id: usize) -> Option<Avm2Object<'gc>> {
+        self.items.get(id).copied()
+    }
+}
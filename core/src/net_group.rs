@@ -0,0 +1,554 @@
+//! Local emulation of RTMFP peer-to-peer groups (`flash.net.NetGroup`).
+//!
+//! There is no RTMFP server to introduce peers to each other, so group members find each other
+//! by multicasting small packets on the local network instead. The backend only moves datagrams
+//! around; groups, peers and messages are all handled here.
+//!
+//! Only Ruffle instances can talk to each other this way - the packets have nothing in common
+//! with real RTMFP.
+
+use crate::{
+    avm2::{
+        globals::flash::net::net_group::decode_message, Activation as Avm2Activation, Avm2,
+        EventObject, Object as Avm2Object, TObject as Avm2TObject, Value as Avm2Value,
+    },
+    backend::navigator::NavigatorBackend,
+    context::UpdateContext,
+    net_status::NetStatus,
+    string::AvmString,
+};
+use async_channel::{unbounded, Sender as AsyncSender};
+use gc_arena::Collect;
+use generational_arena::{Arena, Index};
+use rand::{rngs::SmallRng, Rng};
+use std::{
+    net::{Ipv4Addr, SocketAddr, SocketAddrV4},
+    sync::mpsc::{channel, Receiver, Sender},
+};
+
+pub type NetGroupHandle = Index;
+
+/// The multicast address used by groupspecs that don't name one.
+pub const DEFAULT_MULTICAST_ADDRESS: SocketAddr =
+    SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::new(239, 254, 254, 2), 30304));
+
+const PACKET_MAGIC: &[u8; 4] = b"RP2P";
+
+#[derive(Debug)]
+pub enum NetGroupAction {
+    /// Whether the backend managed to join the multicast group.
+    Connect(NetGroupHandle, bool),
+    Packet(NetGroupHandle, Vec<u8>),
+}
+
+/// The parts of a groupspec that matter to the local emulation.
+///
+/// `GroupSpecifier` builds groupspecs as `G:` followed by URL-encoded options.
+/// Anything else is treated as an opaque group name.
+#[derive(Debug, PartialEq, Eq)]
+struct GroupSpec {
+    /// Identifies the group in packets: the groupspec without its authorizations.
+    id: String,
+    multicast_address: SocketAddr,
+}
+
+impl GroupSpec {
+    fn parse(spec: &str) -> Self {
+        let options = spec.strip_prefix("G:").unwrap_or(spec);
+        let mut id = vec![];
+        let mut multicast_address = None;
+
+        for (key, value) in url::form_urlencoded::parse(options.as_bytes()) {
+            if key.starts_with("auth.") {
+                continue;
+            }
+            if key == "ipmulticast" && multicast_address.is_none() {
+                multicast_address = value.parse().ok();
+            }
+            id.push(if value.is_empty() {
+                key.into_owned()
+            } else {
+                format!("{key}={value}")
+            });
+        }
+
+        Self {
+            id: id.join("&"),
+            multicast_address: multicast_address.unwrap_or(DEFAULT_MULTICAST_ADDRESS),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum PacketKind {
+    /// Announces a peer to the group, or answers such an announcement.
+    Hello,
+    Bye,
+    Post,
+    Direct,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Packet<'a> {
+    kind: PacketKind,
+    group: &'a str,
+    from: &'a str,
+    /// The peer this packet is meant for, or empty if it's meant for everyone.
+    to: &'a str,
+    payload: &'a [u8],
+}
+
+impl<'a> Packet<'a> {
+    fn encode(&self) -> Vec<u8> {
+        let mut bytes = PACKET_MAGIC.to_vec();
+        bytes.push(self.kind as u8);
+        for field in [self.group, self.from, self.to] {
+            bytes.extend((field.len() as u16).to_be_bytes());
+            bytes.extend(field.as_bytes());
+        }
+        bytes.extend(self.payload);
+        bytes
+    }
+
+    fn decode(bytes: &'a [u8]) -> Option<Self> {
+        fn read_str<'a>(bytes: &mut &'a [u8]) -> Option<&'a str> {
+            let length = u16::from_be_bytes([*bytes.first()?, *bytes.get(1)?]) as usize;
+            let field = bytes.get(2..2 + length)?;
+            *bytes = &bytes[2 + length..];
+            std::str::from_utf8(field).ok()
+        }
+
+        let mut bytes = bytes.strip_prefix(PACKET_MAGIC)?;
+        let kind = match bytes.first()? {
+            0 => PacketKind::Hello,
+            1 => PacketKind::Bye,
+            2 => PacketKind::Post,
+            3 => PacketKind::Direct,
+            _ => return None,
+        };
+        bytes = &bytes[1..];
+
+        Some(Self {
+            kind,
+            group: read_str(&mut bytes)?,
+            from: read_str(&mut bytes)?,
+            to: read_str(&mut bytes)?,
+            payload: bytes,
+        })
+    }
+}
+
+/// Where `NetGroup.sendToNeighbor` should send a message.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum NeighborDirection {
+    Increasing,
+    Decreasing,
+}
+
+/// Where a direct message ended up.
+#[derive(Debug, PartialEq, Eq)]
+pub enum SendResult {
+    Sent,
+    /// We are the nearest member to the requested address.
+    Local,
+    NoRoute,
+}
+
+/// Approximates the position of a group address on the group's ring.
+fn ring_position(address: &str) -> u64 {
+    address
+        .get(..16)
+        .and_then(|prefix| u64::from_str_radix(prefix, 16).ok())
+        .unwrap_or(0)
+}
+
+fn ring_distance(a: u64, b: u64) -> u64 {
+    a.wrapping_sub(b).min(b.wrapping_sub(a))
+}
+
+/// Identifies a posting the same way on every peer, like Flash's (SHA-256 based) message IDs.
+fn message_id(payload: &[u8]) -> String {
+    // FNV-1a: any hash works, as long as it doesn't change between builds.
+    let hash = payload.iter().fold(0xcbf29ce484222325u64, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    });
+    format!("{hash:016X}")
+}
+
+#[derive(Collect)]
+#[collect(no_drop)]
+struct NetGroup<'gc> {
+    target: Avm2Object<'gc>,
+    /// The `NetConnection` that connection status is reported on.
+    connection: Avm2Object<'gc>,
+    #[collect(require_static)]
+    group: String,
+    #[collect(require_static)]
+    sender: AsyncSender<Vec<u8>>,
+    #[collect(require_static)]
+    connected: bool,
+    /// Peer IDs of the other members we know about.
+    #[collect(require_static)]
+    neighbors: Vec<String>,
+}
+
+/// Every NetGroup joined by the player, and our identity as a member of them.
+pub struct NetGroups<'gc> {
+    groups: Arena<NetGroup<'gc>>,
+
+    receiver: Receiver<NetGroupAction>,
+    sender: Sender<NetGroupAction>,
+
+    /// Our own peer ID, chosen the first time it's needed.
+    peer_id: Option<String>,
+}
+
+unsafe impl<'gc> Collect for NetGroups<'gc> {
+    fn trace(&self, cc: &gc_arena::Collection) {
+        for (_, group) in self.groups.iter() {
+            group.trace(cc)
+        }
+    }
+}
+
+impl<'gc> NetGroups<'gc> {
+    pub fn empty() -> Self {
+        let (sender, receiver) = channel();
+
+        Self {
+            groups: Arena::new(),
+            receiver,
+            sender,
+            peer_id: None,
+        }
+    }
+
+    /// Our peer ID, as used for `NetConnection.nearID` and as our group address.
+    pub fn peer_id(&mut self, rng: &mut SmallRng) -> &str {
+        self.peer_id.get_or_insert_with(|| {
+            (0..32)
+                .map(|_| format!("{:02x}", rng.gen::<u8>()))
+                .collect()
+        })
+    }
+
+    fn find(&self, target: Avm2Object<'gc>) -> Option<(NetGroupHandle, &NetGroup<'gc>)> {
+        self.groups
+            .iter()
+            .find(|(_, group)| Avm2Object::ptr_eq(group.target, target))
+    }
+
+    fn send(&self, group: &NetGroup<'gc>, kind: PacketKind, to: &str, payload: &[u8]) {
+        let packet = Packet {
+            kind,
+            group: &group.group,
+            from: self.peer_id.as_deref().unwrap_or_default(),
+            to,
+            payload,
+        };
+        let _ = group.sender.send_blocking(packet.encode());
+    }
+
+    pub fn join(
+        &mut self,
+        backend: &mut dyn NavigatorBackend,
+        rng: &mut SmallRng,
+        target: Avm2Object<'gc>,
+        connection: Avm2Object<'gc>,
+        groupspec: &str,
+    ) {
+        self.peer_id(rng);
+        self.close(target);
+
+        let spec = GroupSpec::parse(groupspec);
+        let (sender, receiver) = unbounded();
+        let handle = self.groups.insert(NetGroup {
+            target,
+            connection,
+            group: spec.id,
+            sender,
+            connected: false,
+            neighbors: vec![],
+        });
+
+        // Membership only starts once the backend has joined the multicast group and sent
+        // NetGroupAction::Connect; until then, the group has no neighbors to talk to.
+        backend.join_net_group(
+            spec.multicast_address,
+            handle,
+            receiver,
+            self.sender.clone(),
+        );
+    }
+
+    pub fn close(&mut self, target: Avm2Object<'gc>) {
+        if let Some((handle, group)) = self.find(target) {
+            if group.connected {
+                self.send(group, PacketKind::Bye, "", &[]);
+            }
+            // NOTE: Dropping the sender makes the backend leave the multicast group.
+            self.groups.remove(handle);
+        }
+    }
+
+    pub fn neighbor_count(&self, target: Avm2Object<'gc>) -> usize {
+        self.find(target)
+            .map_or(0, |(_, group)| group.neighbors.len())
+    }
+
+    /// Posts a message to every member, returning its message ID.
+    pub fn post(&self, target: Avm2Object<'gc>, payload: &[u8]) -> Option<String> {
+        let (_, group) = self.find(target).filter(|(_, group)| group.connected)?;
+        self.send(group, PacketKind::Post, "", payload);
+        Some(message_id(payload))
+    }
+
+    pub fn send_to_all_neighbors(&self, target: Avm2Object<'gc>, payload: &[u8]) -> SendResult {
+        match self.find(target) {
+            Some((_, group)) if !group.neighbors.is_empty() => {
+                self.send(group, PacketKind::Direct, "", payload);
+                SendResult::Sent
+            }
+            _ => SendResult::NoRoute,
+        }
+    }
+
+    pub fn send_to_neighbor(
+        &self,
+        target: Avm2Object<'gc>,
+        direction: NeighborDirection,
+        payload: &[u8],
+    ) -> SendResult {
+        let Some((_, group)) = self.find(target) else {
+            return SendResult::NoRoute;
+        };
+        let own = ring_position(self.peer_id.as_deref().unwrap_or_default());
+        let neighbor = group.neighbors.iter().min_by_key(|peer| {
+            let position = ring_position(peer);
+            match direction {
+                NeighborDirection::Increasing => position.wrapping_sub(own),
+                NeighborDirection::Decreasing => own.wrapping_sub(position),
+            }
+        });
+
+        match neighbor {
+            Some(neighbor) => {
+                self.send(group, PacketKind::Direct, neighbor, payload);
+                SendResult::Sent
+            }
+            None => SendResult::NoRoute,
+        }
+    }
+
+    /// Sends a message to the member whose group address is nearest to `address`,
+    /// which may be ourselves.
+    pub fn send_to_nearest(
+        &self,
+        target: Avm2Object<'gc>,
+        address: &str,
+        payload: &[u8],
+    ) -> SendResult {
+        let Some((_, group)) = self.find(target).filter(|(_, group)| group.connected) else {
+            return SendResult::NoRoute;
+        };
+        let own = self.peer_id.as_deref().unwrap_or_default();
+        let position = ring_position(address);
+        let nearest = group
+            .neighbors
+            .iter()
+            .map(String::as_str)
+            .chain(std::iter::once(own))
+            .min_by_key(|peer| ring_distance(ring_position(peer), position))
+            .unwrap_or(own);
+
+        if nearest == own {
+            SendResult::Local
+        } else {
+            self.send(group, PacketKind::Direct, nearest, payload);
+            SendResult::Sent
+        }
+    }
+
+    pub fn update_net_groups(context: &mut UpdateContext<'_, 'gc>) {
+        let mut actions = vec![];
+
+        while let Ok(action) = context.net_groups.receiver.try_recv() {
+            actions.push(action)
+        }
+
+        for action in actions {
+            match action {
+                NetGroupAction::Connect(handle, true) => {
+                    let Some(group) = context.net_groups.groups.get_mut(handle) else {
+                        // Closed before the backend finished joining.
+                        continue;
+                    };
+                    group.connected = true;
+                    let (target, connection) = (group.target, group.connection);
+                    context.net_groups.send(
+                        &context.net_groups.groups[handle],
+                        PacketKind::Hello,
+                        "",
+                        &[],
+                    );
+
+                    let mut activation = Avm2Activation::from_nothing(context.reborrow());
+                    dispatch_net_status(
+                        &mut activation,
+                        connection,
+                        NetStatus::GroupConnectSuccess,
+                        &[("group", target.into())],
+                    );
+                }
+                NetGroupAction::Connect(handle, false) => {
+                    let Some(group) = context.net_groups.groups.remove(handle) else {
+                        continue;
+                    };
+
+                    let mut activation = Avm2Activation::from_nothing(context.reborrow());
+                    dispatch_net_status(
+                        &mut activation,
+                        group.connection,
+                        NetStatus::GroupConnectFailed,
+                        &[("group", group.target.into())],
+                    );
+                }
+                NetGroupAction::Packet(handle, bytes) => {
+                    Self::handle_packet(context, handle, &bytes);
+                }
+            }
+        }
+    }
+
+    fn handle_packet(context: &mut UpdateContext<'_, 'gc>, handle: NetGroupHandle, bytes: &[u8]) {
+        let Some(packet) = Packet::decode(bytes) else {
+            return;
+        };
+        let own = context.net_groups.peer_id.clone().unwrap_or_default();
+        let Some(group) = context.net_groups.groups.get_mut(handle) else {
+            return;
+        };
+        if packet.group != group.group
+            || packet.from == own
+            || !(packet.to.is_empty() || packet.to == own)
+        {
+            return;
+        }
+
+        let target = group.target;
+        let is_new = !group.neighbors.iter().any(|peer| peer == packet.from);
+        if packet.kind == PacketKind::Bye {
+            group.neighbors.retain(|peer| peer != packet.from);
+        } else if is_new {
+            group.neighbors.push(packet.from.to_string());
+        }
+        if packet.kind == PacketKind::Hello && packet.to.is_empty() {
+            // Introduce ourselves to the newcomer.
+            context.net_groups.send(
+                &context.net_groups.groups[handle],
+                PacketKind::Hello,
+                packet.from,
+                &[],
+            );
+        }
+
+        let mut activation = Avm2Activation::from_nothing(context.reborrow());
+        let from = AvmString::new_utf8(activation.gc(), packet.from);
+
+        if packet.kind == PacketKind::Bye {
+            if !is_new {
+                dispatch_net_status(
+                    &mut activation,
+                    target,
+                    NetStatus::GroupNeighborDisconnect,
+                    &[("neighbor", from.into()), ("peerID", from.into())],
+                );
+            }
+            return;
+        }
+
+        if is_new {
+            dispatch_net_status(
+                &mut activation,
+                target,
+                NetStatus::GroupNeighborConnect,
+                &[("neighbor", from.into()), ("peerID", from.into())],
+            );
+        }
+
+        match packet.kind {
+            PacketKind::Post => {
+                let message = decode_message(&mut activation, packet.payload);
+                let message_id = AvmString::new_utf8(activation.gc(), message_id(packet.payload));
+                dispatch_net_status(
+                    &mut activation,
+                    target,
+                    NetStatus::GroupPostingNotify,
+                    &[("message", message), ("messageID", message_id.into())],
+                );
+            }
+            PacketKind::Direct => {
+                let message = decode_message(&mut activation, packet.payload);
+                dispatch_net_status(
+                    &mut activation,
+                    target,
+                    NetStatus::GroupSendToNotify,
+                    &[
+                        ("message", message),
+                        ("from", from.into()),
+                        ("fromLocal", false.into()),
+                    ],
+                );
+            }
+            PacketKind::Hello | PacketKind::Bye => {}
+        }
+    }
+}
+
+/// Dispatches a `netStatus` event for `status`, with `extra` added to its `info`.
+pub fn dispatch_net_status<'gc>(
+    activation: &mut Avm2Activation<'_, 'gc>,
+    target: Avm2Object<'gc>,
+    status: NetStatus,
+    extra: &[(&'static str, Avm2Value<'gc>)],
+) {
+    let event = EventObject::net_status_event(activation, "netStatus", status.info(None));
+    if let Ok(Avm2Value::Object(info)) = event.get_public_property("info", activation) {
+        for &(key, value) in extra {
+            let _ = info.set_public_property(key, value, activation);
+        }
+    }
+    Avm2::dispatch_event(&mut activation.context, event, target);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GroupSpec, Packet, PacketKind, DEFAULT_MULTICAST_ADDRESS};
+
+    #[test]
+    fn packets_round_trip() {
+        let packet = Packet {
+            kind: PacketKind::Direct,
+            group: "name=room",
+            from: "0a1b",
+            to: "",
+            payload: &[1, 2, 3],
+        };
+        let bytes = packet.encode();
+        assert_eq!(Packet::decode(&bytes), Some(packet));
+        assert_eq!(Packet::decode(&bytes[..8]), None);
+        assert_eq!(Packet::decode(b"nope"), None);
+    }
+
+    #[test]
+    fn groupspecs_ignore_authorizations() {
+        let spec =
+            GroupSpec::parse("G:name=room&posting&ipmulticast=225.225.0.1%3A30303&auth.posting=pw");
+        assert_eq!(spec.id, "name=room&posting&ipmulticast=225.225.0.1:30303");
+        assert_eq!(spec.multicast_address, "225.225.0.1:30303".parse().unwrap());
+
+        let spec = GroupSpec::parse("G:0101010c");
+        assert_eq!(spec.id, "0101010c");
+        assert_eq!(spec.multicast_address, DEFAULT_MULTICAST_ADDRESS);
+    }
+}
@@ -0,0 +1,139 @@
+//! The `info` objects given to `netStatus` listeners and `onStatus` handlers.
+//!
+//! Scripts tell these apart by comparing `info.code` against Flash's exact strings, so every code
+//! we report is listed here along with the level Flash reports it at.
+
+use crate::string::AvmString;
+
+/// A status code of `NetConnection`, `NetStream` or `NetGroup`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NetStatus {
+    CallBadVersion,
+    CallFailed,
+    CallProhibited,
+    ConnectClosed,
+    ConnectFailed,
+    ConnectRejected,
+    ConnectSuccess,
+
+    StreamBufferEmpty,
+    StreamBufferFlush,
+    StreamBufferFull,
+    StreamFailed,
+    StreamPauseNotify,
+    StreamPlayFailed,
+    StreamPlayFileStructureInvalid,
+    StreamPlayStart,
+    StreamPlayStop,
+    StreamPlayStreamNotFound,
+    StreamSeekComplete,
+    StreamSeekFailed,
+    StreamSeekInvalidTime,
+    StreamSeekNotify,
+    StreamSeekStartNotify,
+    StreamUnpauseNotify,
+
+    GroupConnectFailed,
+    GroupConnectRejected,
+    GroupConnectSuccess,
+    GroupNeighborConnect,
+    GroupNeighborDisconnect,
+    GroupPostingNotify,
+    GroupSendToNotify,
+}
+
+impl NetStatus {
+    /// The value of `info.code`.
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::CallBadVersion => "NetConnection.Call.BadVersion",
+            Self::CallFailed => "NetConnection.Call.Failed",
+            Self::CallProhibited => "NetConnection.Call.Prohibited",
+            Self::ConnectClosed => "NetConnection.Connect.Closed",
+            Self::ConnectFailed => "NetConnection.Connect.Failed",
+            Self::ConnectRejected => "NetConnection.Connect.Rejected",
+            Self::ConnectSuccess => "NetConnection.Connect.Success",
+            Self::StreamBufferEmpty => "NetStream.Buffer.Empty",
+            Self::StreamBufferFlush => "NetStream.Buffer.Flush",
+            Self::StreamBufferFull => "NetStream.Buffer.Full",
+            Self::StreamFailed => "NetStream.Failed",
+            Self::StreamPauseNotify => "NetStream.Pause.Notify",
+            Self::StreamPlayFailed => "NetStream.Play.Failed",
+            Self::StreamPlayFileStructureInvalid => "NetStream.Play.FileStructureInvalid",
+            Self::StreamPlayStart => "NetStream.Play.Start",
+            Self::StreamPlayStop => "NetStream.Play.Stop",
+            Self::StreamPlayStreamNotFound => "NetStream.Play.StreamNotFound",
+            Self::StreamSeekComplete => "NetStream.Seek.Complete",
+            Self::StreamSeekFailed => "NetStream.Seek.Failed",
+            Self::StreamSeekInvalidTime => "NetStream.Seek.InvalidTime",
+            Self::StreamSeekNotify => "NetStream.Seek.Notify",
+            Self::StreamSeekStartNotify => "NetStream.SeekStart.Notify",
+            Self::StreamUnpauseNotify => "NetStream.Unpause.Notify",
+            Self::GroupConnectFailed => "NetGroup.Connect.Failed",
+            Self::GroupConnectRejected => "NetGroup.Connect.Rejected",
+            Self::GroupConnectSuccess => "NetGroup.Connect.Success",
+            Self::GroupNeighborConnect => "NetGroup.Neighbor.Connect",
+            Self::GroupNeighborDisconnect => "NetGroup.Neighbor.Disconnect",
+            Self::GroupPostingNotify => "NetGroup.Posting.Notify",
+            Self::GroupSendToNotify => "NetGroup.SendTo.Notify",
+        }
+    }
+
+    /// The value of `info.level`: either `status` or `error`.
+    pub fn level(self) -> &'static str {
+        match self {
+            Self::CallBadVersion
+            | Self::CallFailed
+            | Self::CallProhibited
+            | Self::ConnectFailed
+            | Self::ConnectRejected
+            | Self::StreamFailed
+            | Self::StreamPlayFailed
+            | Self::StreamPlayFileStructureInvalid
+            | Self::StreamPlayStreamNotFound
+            | Self::StreamSeekFailed
+            | Self::StreamSeekInvalidTime
+            | Self::GroupConnectFailed
+            | Self::GroupConnectRejected => "error",
+            _ => "status",
+        }
+    }
+
+    pub fn is_error(self) -> bool {
+        self.level() == "error"
+    }
+
+    /// The string properties of the `info` object, in the order Flash defines them.
+    ///
+    /// Flash puts `description` first when there is one, and otherwise starts with `code`.
+    pub fn info<'gc>(
+        self,
+        description: Option<AvmString<'gc>>,
+    ) -> Vec<(&'static str, AvmString<'gc>)> {
+        match description {
+            Some(description) => vec![
+                ("description", description),
+                ("level", self.level().into()),
+                ("code", self.code().into()),
+            ],
+            None => vec![("code", self.code().into()), ("level", self.level().into())],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NetStatus;
+
+    #[test]
+    fn info_order() {
+        let info = NetStatus::StreamSeekComplete.info(Some("Seek Complete -1".into()));
+        let keys: Vec<_> = info.iter().map(|(key, _)| *key).collect();
+        assert_eq!(keys, ["description", "level", "code"]);
+        assert_eq!(info[2].1.to_string(), "NetStream.Seek.Complete");
+
+        let info = NetStatus::CallFailed.info(None);
+        assert_eq!(info[0].1.to_string(), "NetConnection.Call.Failed");
+        assert_eq!(info[1].1.to_string(), "error");
+    }
+}
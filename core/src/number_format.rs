@@ -0,0 +1,617 @@
+//! Number to String conversions shared by the AVMs
+//!
+//! These follow avmplus, which implements ECMA-262's `Number.prototype` formatting
+//! methods exactly: digits are taken from the exact binary value of the number, and ties
+//! are rounded away from zero rather than to even.
+//!
+//! AVM1 instead prints numbers with its own legacy algorithm; see `to_string_avm1`.
+
+use std::borrow::Cow;
+use std::io::Write;
+
+/// The decimal digits of a positive, finite number, along with the power of ten of the
+/// first one (`value = d.ddd * 10^exponent`).
+///
+/// Digits are stored as values, not ASCII, and never have trailing zeros.
+struct Digits {
+    digits: Vec<u8>,
+    exponent: i32,
+}
+
+impl Digits {
+    fn zero() -> Self {
+        Self {
+            digits: vec![],
+            exponent: 0,
+        }
+    }
+
+    fn parse(formatted: &str) -> Self {
+        let (mantissa, exponent) = formatted.split_once('e').unwrap_or((formatted, "0"));
+        let mut digits: Vec<u8> = mantissa
+            .bytes()
+            .filter(u8::is_ascii_digit)
+            .map(|d| d - b'0')
+            .collect();
+        while digits.len() > 1 && digits.last() == Some(&0) {
+            digits.pop();
+        }
+
+        Self {
+            digits,
+            exponent: exponent.parse().unwrap_or(0),
+        }
+    }
+
+    /// The shortest digits that round-trip back to `n`.
+    fn shortest(n: f64) -> Self {
+        Self::parse(&format!("{n:e}"))
+    }
+
+    /// Every digit of `n`. A double never has more than 767 significant digits.
+    fn exact(n: f64) -> Self {
+        Self::parse(&format!("{n:.767e}"))
+    }
+
+    /// The digit multiplied by `10^power`.
+    fn digit_at(&self, power: i32) -> u8 {
+        let index = self.exponent - power;
+        if index < 0 {
+            0
+        } else {
+            self.digits.get(index as usize).copied().unwrap_or(0)
+        }
+    }
+
+    /// Rounds to `count` significant digits, with ties going up.
+    ///
+    /// A `count` of zero or less rounds to a multiple of `10^(exponent + 1 - count)`, which
+    /// may leave no digits at all.
+    fn round(mut self, count: i32) -> Self {
+        if count < 0 {
+            self.digits.clear();
+            return self;
+        }
+
+        let count = count as usize;
+        let round_up = self.digits.get(count).map_or(false, |&d| d >= 5);
+        self.digits.truncate(count);
+        if round_up {
+            loop {
+                match self.digits.last_mut() {
+                    Some(9) => {
+                        self.digits.pop();
+                    }
+                    Some(d) => {
+                        *d += 1;
+                        break;
+                    }
+                    None => {
+                        // Carried past the first digit, e.g. 9.99 -> 10.0
+                        self.digits.push(1);
+                        self.exponent += 1;
+                        break;
+                    }
+                }
+            }
+        }
+        self
+    }
+
+    /// Writes the significant digits in `range`, padding with zeros past the last one.
+    fn write_padded(&self, out: &mut String, range: std::ops::Range<usize>) {
+        for i in range {
+            out.push((b'0' + self.digits.get(i).copied().unwrap_or(0)) as char);
+        }
+    }
+
+    /// Writes `d.ddde+x`, with `count` significant digits.
+    fn write_exponential(&self, out: &mut String, count: usize) {
+        self.write_padded(out, 0..1);
+        if count > 1 {
+            out.push('.');
+            self.write_padded(out, 1..count);
+        }
+        out.push('e');
+        out.push(if self.exponent < 0 { '-' } else { '+' });
+        out.push_str(&self.exponent.unsigned_abs().to_string());
+    }
+}
+
+/// Formats the sign and special values, leaving positive finite numbers to `f`.
+fn format_signed(n: f64, f: impl FnOnce(&mut String, f64)) -> String {
+    if n.is_nan() {
+        return "NaN".to_string();
+    }
+
+    let mut out = String::new();
+    if n < 0.0 {
+        out.push('-');
+    }
+    if n.is_infinite() {
+        out.push_str("Infinity");
+    } else {
+        f(&mut out, n.abs());
+    }
+    out
+}
+
+/// Converts a number to a string, as `String(n)` does in AVM2.
+///
+/// This uses the shortest digits that convert back to the same number, switching to
+/// exponential notation below `1e-6` and from `1e21`.
+pub fn to_string(n: f64) -> String {
+    format_signed(n, |out, n| {
+        if n == 0.0 {
+            out.push('0');
+            return;
+        }
+
+        let digits = Digits::shortest(n);
+        let count = digits.digits.len() as i32;
+        // The number of digits before the decimal point, as ECMA-262 calls it.
+        let point = digits.exponent + 1;
+
+        if count <= point && point <= 21 {
+            digits.write_padded(out, 0..point as usize);
+        } else if 0 < point && point <= 21 {
+            digits.write_padded(out, 0..point as usize);
+            out.push('.');
+            digits.write_padded(out, point as usize..count as usize);
+        } else if -6 < point && point <= 0 {
+            out.push_str("0.");
+            out.extend(std::iter::repeat('0').take(-point as usize));
+            digits.write_padded(out, 0..count as usize);
+        } else {
+            digits.write_exponential(out, count as usize);
+        }
+    })
+}
+
+/// Implements `Number.toFixed`, with `fraction_digits` digits after the decimal point.
+pub fn to_fixed(n: f64, fraction_digits: usize) -> String {
+    if n.abs() >= 1e21 {
+        return to_string(n);
+    }
+
+    let fraction_digits = fraction_digits as i32;
+    format_signed(n, |out, n| {
+        let digits = if n == 0.0 {
+            Digits::zero()
+        } else {
+            let digits = Digits::exact(n);
+            let count = digits.exponent + 1 + fraction_digits;
+            digits.round(count)
+        };
+
+        for power in (0..=digits.exponent.max(0)).rev() {
+            out.push((b'0' + digits.digit_at(power)) as char);
+        }
+        if fraction_digits > 0 {
+            out.push('.');
+            for power in 1..=fraction_digits {
+                out.push((b'0' + digits.digit_at(-power)) as char);
+            }
+        }
+    })
+}
+
+/// Implements `Number.toExponential`, with `fraction_digits` digits after the decimal point.
+pub fn to_exponential(n: f64, fraction_digits: usize) -> String {
+    format_signed(n, |out, n| {
+        let digits = if n == 0.0 {
+            Digits::zero()
+        } else {
+            Digits::exact(n).round(fraction_digits as i32 + 1)
+        };
+        digits.write_exponential(out, fraction_digits + 1);
+    })
+}
+
+/// Implements `Number.toPrecision`, with `precision` significant digits.
+pub fn to_precision(n: f64, precision: usize) -> String {
+    format_signed(n, |out, n| {
+        let digits = if n == 0.0 {
+            Digits::zero()
+        } else {
+            Digits::exact(n).round(precision as i32)
+        };
+        let exponent = digits.exponent;
+
+        if exponent < -6 || exponent >= precision as i32 {
+            digits.write_exponential(out, precision);
+        } else if exponent >= 0 {
+            let point = exponent as usize + 1;
+            digits.write_padded(out, 0..point);
+            if point < precision {
+                out.push('.');
+                digits.write_padded(out, point..precision);
+            }
+        } else {
+            out.push_str("0.");
+            out.extend(std::iter::repeat('0').take((-exponent - 1) as usize));
+            digits.write_padded(out, 0..precision);
+        }
+    })
+}
+
+/// Converts the integer part of a number to a string in the given radix, as
+/// `Number.toString(radix)` does for radixes other than 10.
+///
+/// Every digit is exact, even for numbers beyond 2^53.
+pub fn to_radix(n: f64, radix: u32) -> String {
+    debug_assert!((2..=36).contains(&radix));
+
+    format_signed(n, |out, n| {
+        // The integer as 32-bit limbs, least significant first.
+        let mut limbs = {
+            let n = n.trunc();
+            let bits = n.to_bits();
+            let exponent = ((bits >> 52) & 0x7ff) as i32 - 1075;
+            let mantissa = (bits & ((1 << 52) - 1)) | (1 << 52);
+            if n < 1.0 {
+                vec![]
+            } else if exponent <= 0 {
+                let n = mantissa >> -exponent;
+                vec![n as u32, (n >> 32) as u32]
+            } else {
+                let mut limbs = vec![0; exponent as usize / 32];
+                let n = (mantissa as u128) << (exponent % 32);
+                limbs.extend([n as u32, (n >> 32) as u32, (n >> 64) as u32]);
+                limbs
+            }
+        };
+
+        let mut digits = vec![];
+        loop {
+            while limbs.last() == Some(&0) {
+                limbs.pop();
+            }
+            if limbs.is_empty() {
+                break;
+            }
+
+            // Divide by the radix, keeping the remainder as the next digit.
+            let mut remainder = 0u64;
+            for limb in limbs.iter_mut().rev() {
+                let value = (remainder << 32) | *limb as u64;
+                *limb = (value / radix as u64) as u32;
+                remainder = value % radix as u64;
+            }
+            digits.push(std::char::from_digit(remainder as u32, radix).unwrap_or('0'));
+        }
+
+        if digits.is_empty() {
+            out.push('0');
+        }
+        out.extend(digits.into_iter().rev());
+    })
+}
+
+/// Calculate `value * 10^exp` through repeated multiplication or division.
+pub fn decimal_shift(mut value: f64, mut exp: i32) -> f64 {
+    let mut base: f64 = 10.0;
+    // The multiply and division branches are intentionally separate to match Flash's behavior.
+    if exp > 0 {
+        while exp > 0 {
+            if (exp & 1) != 0 {
+                value *= base;
+            }
+            exp >>= 1;
+            base *= base;
+        }
+    } else {
+        // Avoid overflow when `exp == i32::MIN`.
+        let mut exp = exp.unsigned_abs();
+        while exp > 0 {
+            if (exp & 1) != 0 {
+                value /= base;
+            }
+            exp >>= 1;
+            base *= base;
+        }
+    };
+    value
+}
+
+/// Converts a number to a string, as AVM1 does.
+///
+/// Unlike AVM2, this is (hopefully) the same output as Flash AVM1, including its bugs.
+/// 15 digits are displayed (not including leading 0s in a decimal <1).
+/// Exponential notation is used for numbers <= 1e-5 and >= 1e15.
+/// Rounding done with ties rounded away from zero.
+/// NAN returns `"NaN"`, and infinity returns `"Infinity"`.
+#[allow(clippy::approx_constant)]
+pub fn to_string_avm1(mut n: f64) -> Cow<'static, str> {
+    if n.is_nan() {
+        Cow::Borrowed("NaN")
+    } else if n == f64::INFINITY {
+        Cow::Borrowed("Infinity")
+    } else if n == f64::NEG_INFINITY {
+        Cow::Borrowed("-Infinity")
+    } else if n == 0.0 {
+        Cow::Borrowed("0")
+    } else if n >= -2147483648.0 && n <= 2147483647.0 && n.fract() == 0.0 {
+        // Fast path for integers.
+        (n as i32).to_string().into()
+    } else {
+        // AVM1 f64 -> String (also trying to reproduce bugs).
+        // Flash Player's AVM1 does this in a straightforward way, shifting the float into the
+        // range of [0.0, 10.0), repeatedly multiplying by 10 to extract digits, and then finally
+        // rounding the result. However, the rounding is buggy, when carrying 9.999 -> 10.
+        // For example, -9999999999999999.0 results in "-e+16".
+        let mut buf: Vec<u8> = Vec::with_capacity(25);
+        let is_negative = if n < 0.0 {
+            n = -n;
+            buf.push(b'-');
+            true
+        } else {
+            false
+        };
+
+        // Extract base-2 exponent from double-precision float (11 bits, biased by 1023).
+        const MANTISSA_BITS: u64 = 52;
+        const EXPONENT_MASK: u64 = 0x7ff;
+        const EXPONENT_BIAS: i32 = 1023;
+        let mut exp_base2: i32 =
+            ((n.to_bits() >> MANTISSA_BITS) & EXPONENT_MASK) as i32 - EXPONENT_BIAS;
+
+        if exp_base2 == -EXPONENT_BIAS {
+            // Subnormal float; scale back into normal range and retry getting the exponent.
+            const NORMAL_SCALE: f64 = 1.801439850948198e16; // 2^54
+            let n = n * NORMAL_SCALE;
+            exp_base2 =
+                ((n.to_bits() >> MANTISSA_BITS) & EXPONENT_MASK) as i32 - EXPONENT_BIAS - 54;
+        }
+
+        // Convert to base-10 exponent.
+        const LOG10_2: f64 = 0.301029995663981; // log_10(2) value (less precise than Rust's f64::LOG10_2).
+        let mut exp = f64::round(f64::from(exp_base2) * LOG10_2) as i32;
+
+        // Shift the decimal value so that it's in the range of [0.0, 10.0).
+        let mut mantissa: f64 = decimal_shift(n, -exp);
+
+        // The exponent calculation can be off by 1; try the next exponent if so.
+        if mantissa as i32 == 0 {
+            exp -= 1;
+            mantissa = decimal_shift(n, -exp);
+        }
+        if mantissa as i32 >= 10 {
+            exp += 1;
+            mantissa = decimal_shift(n, -exp);
+        }
+
+        // Generates the next digit character.
+        let mut digit = || {
+            let digit: i32 = mantissa as i32;
+            debug_assert!(digit >= 0 && digit < 10);
+            mantissa -= f64::from(digit);
+            mantissa *= 10.0;
+            b'0' + digit as u8
+        };
+
+        const MAX_DECIMAL_PLACES: i32 = 15;
+        match exp {
+            15.. => {
+                // 1.2345e+15
+                // This case fails to push an extra 0 to handle the rounding 9.9999 -> 10, which
+                // causes the -9999999999999999.0 -> "-e+16" bug later.
+                buf.extend([digit(), b'.']);
+                for _ in 0..MAX_DECIMAL_PLACES - 1 {
+                    buf.push(digit());
+                }
+            }
+            0..=14 => {
+                // 12345.678901234
+                buf.push(b'0');
+                for _ in 0..=exp {
+                    buf.push(digit());
+                }
+                buf.push(b'.');
+                for _ in 0..MAX_DECIMAL_PLACES - exp - 1 {
+                    buf.push(digit());
+                }
+                exp = 0;
+            }
+            -5..=-1 => {
+                // 0.0012345678901234
+                buf.extend_from_slice(b"00.");
+                buf.resize(buf.len() + (-exp) as usize - 1, b'0');
+                for _ in 0..MAX_DECIMAL_PLACES {
+                    buf.push(digit());
+                }
+                exp = 0;
+            }
+            _ => {
+                // 1.345e-15
+                buf.push(b'0');
+                let n = digit();
+                if n != 0 {
+                    buf.push(n);
+                }
+                buf.push(b'.');
+                for _ in 0..MAX_DECIMAL_PLACES - 1 {
+                    buf.push(digit());
+                }
+            }
+        };
+
+        // Rounding: Peek at the next generated digit and round accordingly.
+        // Ties round away from zero.
+        if digit() >= b'5' {
+            // Add 1 to the right-most digit, carrying if we hit a 9.
+            for c in buf.iter_mut().rev() {
+                if *c == b'9' {
+                    *c = b'0';
+                } else if *c >= b'0' {
+                    *c += 1;
+                    break;
+                }
+            }
+        }
+
+        // Trim any trailing zeros and decimal point.
+        while buf.last() == Some(&b'0') {
+            buf.pop();
+        }
+        if buf.last() == Some(&b'.') {
+            buf.pop();
+        }
+
+        let mut start = 0;
+        if exp != 0 {
+            // Write exponent (e+###).
+
+            // Lots of band-aids here to attempt to clean up the rounding above.
+            // Negative values are not correctly handled in the Flash Player, causing several bugs.
+            // PLAYER-SPECIFIC: I think these checks were added in Flash Player 6.
+            // Trim leading zeros.
+            let pos = buf.iter().position(|&c| c != b'0').unwrap_or(buf.len());
+            if pos != 0 {
+                buf.copy_within(pos.., 0);
+                buf.truncate(buf.len() - pos);
+            }
+            if buf.is_empty() {
+                // Fix up 9.99999 being rounded to 0.00000 when there is no space for the carried 1.
+                // If we have no digits, the value was all 0s that were trimmed, so round to 1.
+                buf.push(b'1');
+                exp += 1;
+            } else {
+                // Fix up 100e15 to 1e17.
+                let pos = buf.iter().rposition(|&c| c != b'0').unwrap_or_default();
+                if pos == 0 {
+                    exp += buf.len() as i32 - 1;
+                    buf.truncate(1);
+                }
+            }
+            let _ = write!(&mut buf, "e{exp:+}");
+        }
+
+        // One final band-aid to eliminate any leading zeros.
+        let i = if is_negative { 1 } else { 0 };
+        if buf.get(i) == Some(&b'0') && buf.get(i + 1) != Some(&b'.') {
+            if i > 0 {
+                buf[i] = buf[i - 1];
+            }
+            start = 1;
+        }
+
+        // SAFETY: Buffer is guaranteed to only contain ASCII digits.
+        let s = unsafe { std::str::from_utf8_unchecked(&buf[start..]) };
+        s.to_string().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{to_exponential, to_fixed, to_precision, to_radix, to_string, to_string_avm1};
+
+    #[test]
+    fn shortest() {
+        assert_eq!(to_string(0.0), "0");
+        assert_eq!(to_string(-0.0), "0");
+        assert_eq!(to_string(f64::NAN), "NaN");
+        assert_eq!(to_string(f64::NEG_INFINITY), "-Infinity");
+        assert_eq!(to_string(123.0), "123");
+        assert_eq!(to_string(-1.5), "-1.5");
+        assert_eq!(to_string(0.1 + 0.2), "0.30000000000000004");
+        assert_eq!(to_string(1e20), "100000000000000000000");
+        assert_eq!(to_string(1e21), "1e+21");
+        assert_eq!(to_string(1.2345e25), "1.2345e+25");
+        assert_eq!(to_string(0.000001), "0.000001");
+        assert_eq!(to_string(1.5e-7), "1.5e-7");
+        assert_eq!(to_string(f64::MAX), "1.7976931348623157e+308");
+        assert_eq!(to_string(5e-324), "5e-324");
+    }
+
+    #[test]
+    fn fixed() {
+        assert_eq!(to_fixed(0.0, 2), "0.00");
+        assert_eq!(to_fixed(-0.0, 0), "0");
+        assert_eq!(to_fixed(0.5, 0), "1");
+        assert_eq!(to_fixed(2.5, 0), "3");
+        assert_eq!(to_fixed(-2.5, 0), "-3");
+        assert_eq!(to_fixed(1.005, 2), "1.00");
+        assert_eq!(to_fixed(1.45, 1), "1.4");
+        assert_eq!(to_fixed(99.99, 1), "100.0");
+        assert_eq!(to_fixed(0.0004, 3), "0.000");
+        assert_eq!(to_fixed(0.0005, 3), "0.001");
+        assert_eq!(to_fixed(123.456, 0), "123");
+        assert_eq!(to_fixed(0.1, 20), "0.10000000000000000555");
+        assert_eq!(to_fixed(1e21, 2), "1e+21");
+    }
+
+    #[test]
+    fn exponential() {
+        assert_eq!(to_exponential(0.0, 2), "0.00e+0");
+        assert_eq!(to_exponential(123456.0, 2), "1.23e+5");
+        assert_eq!(to_exponential(-0.00015, 1), "-1.5e-4");
+        assert_eq!(to_exponential(9.96, 1), "1.0e+1");
+        assert_eq!(to_exponential(5.0, 0), "5e+0");
+    }
+
+    #[test]
+    fn precision() {
+        assert_eq!(to_precision(0.0, 3), "0.00");
+        assert_eq!(to_precision(123.456, 4), "123.5");
+        assert_eq!(to_precision(123.456, 2), "1.2e+2");
+        assert_eq!(to_precision(123.0, 3), "123");
+        assert_eq!(to_precision(0.000123, 2), "0.00012");
+        assert_eq!(to_precision(0.0000001234, 2), "1.2e-7");
+        assert_eq!(to_precision(-99.99, 3), "-100");
+        assert_eq!(to_precision(1.0, 21), "1.00000000000000000000");
+    }
+
+    #[test]
+    fn radix() {
+        assert_eq!(to_radix(0.0, 2), "0");
+        assert_eq!(to_radix(0.75, 2), "0");
+        assert_eq!(to_radix(255.9, 16), "ff");
+        assert_eq!(to_radix(-255.0, 16), "-ff");
+        assert_eq!(to_radix(35.0, 36), "z");
+        assert_eq!(to_radix(4294967296.0, 2), format!("1{}", "0".repeat(32)));
+        assert_eq!(to_radix(9007199254740993.0, 10), "9007199254740992");
+        assert_eq!(to_radix(1e21, 10), "1000000000000000000000");
+        assert_eq!(to_radix(2f64.powi(100), 16), format!("1{}", "0".repeat(25)));
+    }
+
+    #[test]
+    fn avm1() {
+        assert_eq!(to_string_avm1(0.0), "0");
+        assert_eq!(to_string_avm1(-0.0), "0");
+        assert_eq!(to_string_avm1(1.0), "1");
+        assert_eq!(to_string_avm1(1.4), "1.4");
+        assert_eq!(to_string_avm1(-990.123), "-990.123");
+        assert_eq!(to_string_avm1(f64::NAN), "NaN");
+        assert_eq!(to_string_avm1(f64::INFINITY), "Infinity");
+        assert_eq!(to_string_avm1(f64::NEG_INFINITY), "-Infinity");
+        assert_eq!(to_string_avm1(9.9999e14), "999990000000000");
+        assert_eq!(to_string_avm1(-9.9999e14), "-999990000000000");
+        assert_eq!(to_string_avm1(1e15), "1e+15");
+        assert_eq!(to_string_avm1(-1e15), "-1e+15");
+        assert_eq!(to_string_avm1(1e-5), "0.00001");
+        assert_eq!(to_string_avm1(-1e-5), "-0.00001");
+        assert_eq!(to_string_avm1(0.999e-5), "9.99e-6");
+        assert_eq!(to_string_avm1(-0.999e-5), "-9.99e-6");
+        assert_eq!(to_string_avm1(0.19999999999999996), "0.2");
+        assert_eq!(to_string_avm1(-0.19999999999999996), "-0.2");
+        assert_eq!(to_string_avm1(100000.12345678912), "100000.123456789");
+        assert_eq!(to_string_avm1(-100000.12345678912), "-100000.123456789");
+        assert_eq!(to_string_avm1(0.8000000000000005), "0.800000000000001");
+        assert_eq!(to_string_avm1(-0.8000000000000005), "-0.800000000000001");
+        assert_eq!(to_string_avm1(0.8300000000000005), "0.83");
+        assert_eq!(to_string_avm1(1e-320), "9.99988867182684e-321");
+        assert_eq!(to_string_avm1(f64::MIN), "-1.79769313486231e+308");
+        assert_eq!(to_string_avm1(f64::MIN_POSITIVE), "2.2250738585072e-308");
+        assert_eq!(to_string_avm1(f64::MAX), "1.79769313486231e+308");
+        assert_eq!(to_string_avm1(5e-324), "4.94065645841247e-324");
+        assert_eq!(to_string_avm1(9.999999999999999), "10");
+        assert_eq!(to_string_avm1(-9.999999999999999), "-10");
+        assert_eq!(to_string_avm1(9999999999999996.0), "1e+16");
+        assert_eq!(to_string_avm1(-9999999999999996.0), "-e+16"); // wat
+        assert_eq!(to_string_avm1(0.000009999999999999996), "1e-5");
+        assert_eq!(to_string_avm1(-0.000009999999999999996), "-10e-6");
+        assert_eq!(to_string_avm1(0.00009999999999999996), "0.0001");
+        assert_eq!(to_string_avm1(-0.00009999999999999996), "-0.0001");
+    }
+}
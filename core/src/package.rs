@@ -0,0 +1,308 @@
+//! Movies distributed inside ZIP archives, such as AIR packages.
+
+use crate::xml::custom_unescape;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+#[cfg(feature = "zip")]
+use std::io::{self, Cursor, Read};
+#[cfg(feature = "zip")]
+use std::path::Path;
+#[cfg(feature = "zip")]
+use zip::read::ZipFile;
+#[cfg(feature = "zip")]
+use zip::ZipArchive;
+
+/// The signature of a ZIP local file header, which every archive starts with.
+pub const ZIP_SIGNATURE: &[u8] = b"PK\x03\x04";
+
+/// Where an AIR package keeps its application descriptor.
+pub const AIR_DESCRIPTOR_PATH: &str = "META-INF/AIR/application.xml";
+
+/// The most we'll decompress from a single file of an archive.
+///
+/// The sizes an archive gives for its files can't be trusted, so this is what bounds the
+/// memory a small archive can make us use.
+pub const MAX_EXTRACTED_LEN: u64 = 256 * 1024 * 1024;
+
+/// The parts of an AIR application descriptor (`application.xml`) needed to
+/// start the application.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AirApplication {
+    /// The unique identifier of the application, such as `com.example.Game`.
+    pub id: String,
+
+    /// The name the application is installed under.
+    pub filename: String,
+
+    /// The display name of the application, in the first language given.
+    pub name: Option<String>,
+
+    pub version: Option<String>,
+
+    pub initial_window: InitialWindow,
+}
+
+/// The `initialWindow` of an AIR application descriptor.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InitialWindow {
+    /// The path of the root content within the application directory.
+    pub content: String,
+
+    pub title: Option<String>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub x: Option<i32>,
+    pub y: Option<i32>,
+    pub visible: Option<bool>,
+    pub resizable: Option<bool>,
+}
+
+impl AirApplication {
+    /// Parses an application descriptor, returning `None` if it doesn't name
+    /// any root content.
+    pub fn parse(xml: &[u8]) -> Option<Self> {
+        let mut reader = Reader::from_reader(xml);
+        let mut application = Self::default();
+        let mut path: Vec<Vec<u8>> = vec![];
+
+        loop {
+            match reader.read_event() {
+                Ok(Event::Start(start)) => path.push(start.local_name().as_ref().to_vec()),
+                Ok(Event::End(_)) => {
+                    path.pop();
+                }
+                Ok(Event::Text(text)) => {
+                    let Ok(text) = custom_unescape(&text, reader.decoder()) else {
+                        continue;
+                    };
+                    application.set(&path, text.trim());
+                }
+                Ok(Event::CData(text)) => {
+                    application.set(&path, String::from_utf8_lossy(&text).trim());
+                }
+                Ok(Event::Eof) => break,
+                Ok(_) => {}
+                Err(e) => {
+                    tracing::warn!("Couldn't parse AIR application descriptor: {}", e);
+                    return None;
+                }
+            }
+        }
+
+        if application.initial_window.content.is_empty() {
+            return None;
+        }
+        Some(application)
+    }
+
+    /// Sets the field at `path`, relative to the document root.
+    fn set(&mut self, path: &[Vec<u8>], value: &str) {
+        if value.is_empty() {
+            return;
+        }
+
+        let window = &mut self.initial_window;
+        match path.get(1..).unwrap_or_default() {
+            [id] if id == b"id" => self.id = value.to_string(),
+            [filename] if filename == b"filename" => self.filename = value.to_string(),
+            // Localized names are given as `<name><text xml:lang="..."/></name>`.
+            [name, ..] if name == b"name" && self.name.is_none() => {
+                self.name = Some(value.to_string())
+            }
+            [version] if version == b"version" || version == b"versionNumber" => {
+                self.version = Some(value.to_string())
+            }
+            [initial_window, field] if initial_window == b"initialWindow" => {
+                match field.as_slice() {
+                    b"content" => window.content = value.to_string(),
+                    b"title" => window.title = Some(value.to_string()),
+                    b"width" => window.width = value.parse().ok(),
+                    b"height" => window.height = value.parse().ok(),
+                    b"x" => window.x = value.parse().ok(),
+                    b"y" => window.y = value.parse().ok(),
+                    b"visible" => window.visible = value.parse().ok(),
+                    b"resizable" => window.resizable = value.parse().ok(),
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Returns whether `data` looks like a ZIP archive.
+pub fn is_zip(data: &[u8]) -> bool {
+    data.starts_with(ZIP_SIGNATURE)
+}
+
+/// Reads the contents of a file in a ZIP archive, failing if it's longer than `max_len`.
+#[cfg(feature = "zip")]
+fn read_file(file: &mut ZipFile<'_>, max_len: u64) -> io::Result<Vec<u8>> {
+    let mut data = vec![];
+    (&mut *file).take(max_len + 1).read_to_end(&mut data)?;
+    if data.len() as u64 > max_len {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{} is too large to extract", file.name()),
+        ));
+    }
+    Ok(data)
+}
+
+/// Reads the application descriptor of an AIR package.
+#[cfg(feature = "zip")]
+pub fn read_air_descriptor(data: &[u8]) -> Option<AirApplication> {
+    let mut archive = ZipArchive::new(Cursor::new(data)).ok()?;
+    let mut descriptor = archive.by_name(AIR_DESCRIPTOR_PATH).ok()?;
+    let xml = read_file(&mut descriptor, MAX_EXTRACTED_LEN).ok()?;
+    AirApplication::parse(&xml)
+}
+
+/// Extracts every file of a ZIP archive into `directory`.
+#[cfg(feature = "zip")]
+pub fn extract_archive(data: &[u8], directory: &Path) -> zip::result::ZipResult<()> {
+    ZipArchive::new(Cursor::new(data))?.extract(directory)
+}
+
+/// Finds the main movie of a ZIP archive, and returns its contents.
+///
+/// For AIR packages, this is the root content named by the application
+/// descriptor. Otherwise, it's the SWF closest to the root of the archive,
+/// with ties going to the one stored first.
+#[cfg(feature = "zip")]
+pub fn extract_main_swf(data: &[u8]) -> Option<Vec<u8>> {
+    let mut archive = ZipArchive::new(Cursor::new(data)).ok()?;
+    if let Some(application) = read_air_descriptor(data) {
+        let content = application.initial_window.content;
+        match archive.by_name(content.trim_start_matches('/')) {
+            Ok(mut file) => match read_file(&mut file, MAX_EXTRACTED_LEN) {
+                Ok(swf) => return Some(swf),
+                Err(e) => tracing::error!("Couldn't extract AIR root content: {}", e),
+            },
+            Err(_) => tracing::warn!("AIR package is missing its root content {}", content),
+        }
+    }
+
+    let index = (0..archive.len())
+        .filter_map(|i| {
+            let file = archive.by_index(i).ok()?;
+            let name = file.name();
+            let is_swf = file.is_file() && name.to_ascii_lowercase().ends_with(".swf");
+            is_swf.then(|| (name.matches('/').count(), i))
+        })
+        .min()?
+        .1;
+
+    let mut file = archive.by_index(index).ok()?;
+    match read_file(&mut file, MAX_EXTRACTED_LEN) {
+        Ok(swf) => Some(swf),
+        Err(e) => {
+            tracing::error!("Couldn't extract {} from ZIP archive: {}", file.name(), e);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "zip")]
+    use super::{extract_main_swf, is_zip, read_file, AIR_DESCRIPTOR_PATH};
+    use super::{AirApplication, InitialWindow};
+    #[cfg(feature = "zip")]
+    use std::io::{Cursor, Write};
+    #[cfg(feature = "zip")]
+    use zip::write::FileOptions;
+    #[cfg(feature = "zip")]
+    use zip::{ZipArchive, ZipWriter};
+
+    #[cfg(feature = "zip")]
+    fn write_zip(files: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut zip = ZipWriter::new(Cursor::new(vec![]));
+        for (name, data) in files {
+            zip.start_file(*name, FileOptions::default()).unwrap();
+            zip.write_all(data).unwrap();
+        }
+        zip.finish().unwrap().into_inner()
+    }
+
+    #[test]
+    #[cfg(feature = "zip")]
+    fn finds_main_swf() {
+        let zip = write_zip(&[
+            ("readme.txt", b"hello"),
+            ("assets/icons.swf", b"icons"),
+            ("Game.SWF", b"game"),
+            ("Other.swf", b"other"),
+        ]);
+        assert!(is_zip(&zip));
+        assert_eq!(extract_main_swf(&zip).as_deref(), Some(&b"game"[..]));
+
+        let zip = write_zip(&[("readme.txt", b"hello")]);
+        assert_eq!(extract_main_swf(&zip), None);
+        assert_eq!(extract_main_swf(b"FWS"), None);
+    }
+
+    const DESCRIPTOR: &[u8] = br#"<?xml version="1.0" encoding="utf-8"?>
+<application xmlns="http://ns.adobe.com/air/application/32.0">
+    <id>com.example.Tool</id>
+    <filename>Tool</filename>
+    <name>
+        <text xml:lang="en">Item Tool</text>
+        <text xml:lang="fr">Outil</text>
+    </name>
+    <versionNumber>1.2.0</versionNumber>
+    <initialWindow>
+        <content>bin/Tool.swf</content>
+        <title><![CDATA[Item & Tool]]></title>
+        <width>800</width>
+        <height>600</height>
+        <visible>true</visible>
+    </initialWindow>
+</application>
+"#;
+
+    #[test]
+    fn parses_air_descriptor() {
+        assert_eq!(
+            AirApplication::parse(DESCRIPTOR),
+            Some(AirApplication {
+                id: "com.example.Tool".to_string(),
+                filename: "Tool".to_string(),
+                name: Some("Item Tool".to_string()),
+                version: Some("1.2.0".to_string()),
+                initial_window: InitialWindow {
+                    content: "bin/Tool.swf".to_string(),
+                    title: Some("Item & Tool".to_string()),
+                    width: Some(800),
+                    height: Some(600),
+                    visible: Some(true),
+                    ..Default::default()
+                },
+            })
+        );
+        assert_eq!(AirApplication::parse(b"<application/>"), None);
+    }
+
+    #[test]
+    #[cfg(feature = "zip")]
+    fn finds_air_root_content() {
+        let zip = write_zip(&[
+            ("Other.swf", b"other"),
+            ("bin/Tool.swf", b"tool"),
+            (AIR_DESCRIPTOR_PATH, DESCRIPTOR),
+        ]);
+        assert_eq!(extract_main_swf(&zip).as_deref(), Some(&b"tool"[..]));
+    }
+
+    #[test]
+    #[cfg(feature = "zip")]
+    fn limits_extracted_length() {
+        let zip = write_zip(&[("Game.swf", &[0; 100])]);
+        let mut archive = ZipArchive::new(Cursor::new(zip)).unwrap();
+        assert!(read_file(&mut archive.by_index(0).unwrap(), 99).is_err());
+        assert_eq!(
+            read_file(&mut archive.by_index(0).unwrap(), 100).unwrap(),
+            vec![0; 100]
+        );
+    }
+}
@@ -1,3 +1,7 @@
+mod stages;
+
+pub use stages::{StageArea, StageId};
+
 use crate::avm1::globals::system::SandboxType;
 use crate::avm1::Attribute;
 use crate::avm1::Avm1;
@@ -7,19 +11,21 @@ use crate::avm1::VariableDumper;
 use crate::avm1::{Activation, ActivationIdentifier};
 use crate::avm1::{ScriptObject, TObject, Value};
 use crate::avm2::{
-    object::LoaderInfoObject, object::TObject as _, Activation as Avm2Activation, Avm2, CallStack,
-    Object as Avm2Object,
+    dispatch_native_menu_select, dispatch_paste, dispatch_window_activation,
+    dispatch_window_bounds_change, object::LoaderInfoObject, object::TObject as _, will_trigger,
+    Activation as Avm2Activation, Avm2, CallStack, Object as Avm2Object,
 };
 use crate::backend::ui::FontDefinition;
 use crate::backend::{
     audio::{AudioBackend, AudioManager},
     log::LogBackend,
     navigator::{NavigatorBackend, Request},
-    storage::StorageBackend,
-    ui::{InputManager, MouseCursor, UiBackend},
+    storage::{SecureStorageBackend, StorageBackend},
+    ui::{DesktopRect, InputManager, MouseCursor, UiBackend},
 };
+use crate::clock::PlayerClock;
 use crate::compatibility_rules::CompatibilityRules;
-use crate::config::Letterbox;
+use crate::config::{Letterbox, PlayerConfig};
 use crate::context::GcContext;
 use crate::context::{ActionQueue, ActionType, RenderContext, UpdateContext};
 use crate::context_menu::{
@@ -30,32 +36,50 @@ use crate::display_object::{
     EditText, InteractiveObject, MovieClip, Stage, StageAlign, StageDisplayState, StageScaleMode,
     TInteractiveObject, WindowMode,
 };
-use crate::events::{ButtonKeyCode, ClipEvent, ClipEventResult, KeyCode, MouseButton, PlayerEvent};
+use crate::events::{
+    ButtonKeyCode, ClipEvent, ClipEventResult, KeyCode, MouseButton, MouseWheelDelta, PlayerEvent,
+    TextControlCode,
+};
+use crate::export::{
+    self, ColorVariant, ExportError, ExportImage, ExportedFile, ExporterPlugins,
+    ItemExporterPlugin, Recolor, ThumbnailService, ThumbnailSubject,
+};
 use crate::external::{ExternalInterface, ExternalInterfaceProvider, NullFsCommandProvider};
 use crate::external::{FsCommandProvider, Value as ExternalValue};
 use crate::focus_tracker::FocusTracker;
 use crate::frame_lifecycle::{run_all_phases_avm2, FramePhase};
 use crate::library::Library;
 use crate::limits::ExecutionLimit;
-use crate::loader::{LoadBehavior, LoadManager};
+use crate::loader::{LoadBehavior, LoadManager, LoadMetrics};
 use crate::locale::get_current_date_time;
+use crate::native_extension::{NativeExtension, NativeExtensions};
+use crate::native_menu::NativeMenuState;
+use crate::net_group::NetGroups;
+use crate::player_version::{EnumerationOrder, PlayerVersion};
 use crate::prelude::*;
-use crate::socket::Sockets;
+use crate::socket::{SocketFraming, SocketPolicy, Sockets};
+use crate::sql::SqlConnections;
 use crate::streams::StreamManager;
 use crate::string::{AvmString, AvmStringInterner};
 use crate::stub::StubCollection;
-use crate::tag_utils::SwfMovie;
+use crate::tag_utils::{SwfMovie, TagStrictness};
+use crate::telemetry;
 use crate::timer::Timers;
 use crate::vminterface::Instantiator;
+use crate::web_view::{WebViewProvider, WebViews};
 use crate::DefaultFont;
 use gc_arena::{Collect, DynamicRootSet, GcCell, Rootable};
 use instant::Instant;
 use rand::{rngs::SmallRng, SeedableRng};
-use ruffle_render::backend::{null::NullRenderer, RenderBackend, ViewportDimensions};
+use ruffle_render::backend::{
+    null::NullRenderer, BitmapCacheEntry, RenderBackend, ViewportDimensions,
+};
 use ruffle_render::commands::CommandList;
 use ruffle_render::quality::StageQuality;
 use ruffle_render::transform::TransformStack;
 use ruffle_video::backend::VideoBackend;
+use ruffle_video::media::MediaDecoderBackend;
+use stages::{StageConfig, Stages};
 use std::cell::RefCell;
 use std::collections::{HashMap, VecDeque};
 use std::ops::DerefMut;
@@ -125,6 +149,12 @@ struct GcRootData<'gc> {
     /// If the mouse is down, the display object that the mouse is currently pressing.
     mouse_pressed_object: Option<InteractiveObject<'gc>>,
 
+    /// If the right mouse button is down, the display object it was pressed over.
+    mouse_right_pressed_object: Option<InteractiveObject<'gc>>,
+
+    /// If the middle mouse button is down, the display object it was pressed over.
+    mouse_middle_pressed_object: Option<InteractiveObject<'gc>>,
+
     /// The object being dragged via a `startDrag` action.
     drag_object: Option<DragObject<'gc>>,
 
@@ -167,11 +197,31 @@ struct GcRootData<'gc> {
 
     sockets: Sockets<'gc>,
 
+    net_groups: NetGroups<'gc>,
+
+    native_extensions: NativeExtensions<'gc>,
+
+    web_views: WebViews<'gc>,
+
+    /// The items of the menu shown with `NativeWindow.menu`.
+    native_menu: NativeMenuState<'gc>,
+
     /// Dynamic root for allowing handles to GC objects to exist outside of the GC.
     dynamic_root: DynamicRootSet<'gc>,
 }
 
 impl<'gc> GcRootData<'gc> {
+    /// The display object that the given non-primary mouse button was pressed over.
+    fn mouse_secondary_pressed_object(
+        &mut self,
+        button: MouseButton,
+    ) -> &mut Option<InteractiveObject<'gc>> {
+        match button {
+            MouseButton::Middle => &mut self.mouse_middle_pressed_object,
+            _ => &mut self.mouse_right_pressed_object,
+        }
+    }
+
     /// Splits out parameters for creating an `UpdateContext`
     /// (because we can borrow fields of `self` independently)
     #[allow(clippy::type_complexity)]
@@ -195,6 +245,10 @@ impl<'gc> GcRootData<'gc> {
         &mut AudioManager<'gc>,
         &mut StreamManager<'gc>,
         &mut Sockets<'gc>,
+        &mut NetGroups<'gc>,
+        &mut NativeExtensions<'gc>,
+        &mut WebViews<'gc>,
+        &mut NativeMenuState<'gc>,
         DynamicRootSet<'gc>,
     ) {
         (
@@ -215,6 +269,10 @@ impl<'gc> GcRootData<'gc> {
             &mut self.audio_manager,
             &mut self.stream_manager,
             &mut self.sockets,
+            &mut self.net_groups,
+            &mut self.native_extensions,
+            &mut self.web_views,
+            &mut self.native_menu,
             self.dynamic_root,
         )
     }
@@ -226,9 +284,11 @@ type Audio = Box<dyn AudioBackend>;
 type Navigator = Box<dyn NavigatorBackend>;
 type Renderer = Box<dyn RenderBackend>;
 type Storage = Box<dyn StorageBackend>;
+type SecureStorage = Box<dyn SecureStorageBackend>;
 type Log = Box<dyn LogBackend>;
 type Ui = Box<dyn UiBackend>;
 type Video = Box<dyn VideoBackend>;
+type Media = Box<dyn MediaDecoderBackend>;
 
 pub struct Player {
     /// The version of the player we're emulating.
@@ -241,7 +301,7 @@ pub struct Player {
     ///   digits. This allows the user to play those old files.
     /// * Player-specific behavior that was not properly versioned in Flash
     ///   Player can be enabled by setting a particular player version.
-    player_version: u8,
+    player_version: PlayerVersion,
 
     swf: Arc<SwfMovie>,
 
@@ -252,9 +312,11 @@ pub struct Player {
     audio: Audio,
     navigator: Navigator,
     storage: Storage,
+    secure_storage: SecureStorage,
     log: Log,
     ui: Ui,
     video: Video,
+    media: Media,
 
     transform_stack: TransformStack,
 
@@ -270,6 +332,15 @@ pub struct Player {
 
     stub_tracker: StubCollection,
 
+    /// SQLite databases opened by AIR content.
+    sql_connections: SqlConnections,
+
+    /// Output formats for `export_item`.
+    exporter_plugins: ExporterPlugins,
+
+    /// Previews rendered by `thumbnail`.
+    thumbnails: ThumbnailService,
+
     /// A time budget for executing frames.
     /// Gained by passage of time between host frames, spent by executing SWF frames.
     /// This is how we support custom SWF framerates
@@ -282,9 +353,22 @@ pub struct Player {
 
     input: InputManager,
 
+    /// The bounds of the host's window when they were last checked, to tell what changed.
+    window_bounds: Option<DesktopRect>,
+
     mouse_in_stage: bool,
     mouse_position: Point<Twips>,
 
+    /// Mouse wheel movement that has not yet added up to a whole line.
+    ///
+    /// Flash only ever reports whole lines of scrolling, so high-resolution
+    /// (pixel-based) wheel events are accumulated here until they do.
+    mouse_wheel_remainder: f64,
+
+    /// Whether the content handled the last right mouse button press itself,
+    /// in which case the context menu should not be shown.
+    right_click_consumed: bool,
+
     /// The current mouse cursor icon.
     mouse_cursor: MouseCursor,
     mouse_cursor_needs_check: bool,
@@ -297,8 +381,11 @@ pub struct Player {
     /// Time remaining until the next timer will fire.
     time_til_next_timer: Option<f64>,
 
-    /// The instant at which the SWF was launched.
-    start_time: Instant,
+    /// The time that movies see, which only advances as the player is ticked.
+    clock: PlayerClock,
+
+    /// Whether frame and render timings are reported as telemetry events.
+    telemetry: bool,
 
     /// The maximum amount of time that can be called before a `Error::ExecutionTimeout`
     /// is raised. This defaults to 15 seconds but can be changed.
@@ -325,9 +412,15 @@ pub struct Player {
     /// Any compatibility rules to apply for this movie.
     compatibility_rules: CompatibilityRules,
 
+    /// How to deal with malformed tags in loaded movies.
+    tag_strictness: TagStrictness,
+
     /// Debug UI windows
     #[cfg(feature = "egui")]
     debug_ui: Rc<RefCell<crate::debug_ui::DebugUi>>,
+
+    /// The stages hosted by this player, and the state of the inactive ones.
+    stages: Stages,
 }
 
 impl Player {
@@ -361,6 +454,11 @@ impl Player {
     /// previous stage contents. If you need to load a new root movie, you
     /// should destroy and recreate the player instance.
     pub fn set_root_movie(&mut self, movie: SwfMovie) {
+        if let Some(stage) = self.polling_stage() {
+            self.with_stage(stage, |player| player.set_root_movie(movie));
+            return;
+        }
+
         if !self.forced_frame_rate {
             self.frame_rate = movie.frame_rate().into();
         }
@@ -373,6 +471,18 @@ impl Player {
             self.frame_rate(),
         );
 
+        // Untrusted local movies are put in whichever local sandbox they ask for.
+        if matches!(
+            self.system.sandbox_type,
+            SandboxType::LocalWithFile | SandboxType::LocalWithNetwork
+        ) {
+            self.system.sandbox_type = if movie.use_network_sandbox() {
+                SandboxType::LocalWithNetwork
+            } else {
+                SandboxType::LocalWithFile
+            };
+        }
+
         self.swf = Arc::new(movie);
         self.instance_counter = 0;
 
@@ -492,6 +602,12 @@ impl Player {
     }
 
     pub fn tick(&mut self, dt: f64) {
+        for stage in self.stage_ids() {
+            self.with_stage(stage, |player| player.tick_stage(dt));
+        }
+    }
+
+    fn tick_stage(&mut self, dt: f64) {
         // Don't run until preloading is complete.
         // TODO: Eventually we want to stream content similar to the Flash player.
         if !self.audio.is_loading_complete() {
@@ -499,6 +615,7 @@ impl Player {
         }
 
         if self.is_playing() {
+            self.clock.tick(dt);
             self.frame_accumulator += dt;
             let frame_rate = self.frame_rate;
             let frame_time = 1000.0 / frame_rate;
@@ -507,11 +624,18 @@ impl Player {
             let mut frame = 0;
 
             while frame < max_frames_per_tick && self.frame_accumulator >= frame_time {
+                // Frames that are catching up see the time they were due to run at.
+                let due = self.clock.tick_time() - (self.frame_accumulator - frame_time);
+                self.clock.set_due(due, due + frame_time);
+                let start = self.clock.now();
                 let timer = Instant::now();
                 self.run_frame();
                 let elapsed = timer.elapsed().as_millis() as f64;
 
                 self.add_frame_timing(elapsed);
+                if self.telemetry {
+                    telemetry::frame(start, self.clock.now() - start);
+                }
 
                 self.frame_accumulator -= frame_time;
                 frame += 1;
@@ -526,7 +650,7 @@ impl Player {
 
             // Now that we're done running code,
             // we can stop pretending that more time passed than actually did.
-            // Note: update_timers(dt) doesn't need to see this either.
+            // Note: the timers don't need to see this either.
             // Timers will run at correct times and see correct time.
             // Also note that in Flash, a blocking busy loop would delay setTimeout
             // and cancel some setInterval callbacks, but here busy loops don't block
@@ -548,8 +672,16 @@ impl Player {
                     * 1000.0
             });
 
+            // Until the next tick, time can pass up to when the next frame is due.
+            let tick_time = self.clock.tick_time();
+            self.clock
+                .set_due(tick_time, tick_time + frame_time - self.frame_accumulator);
+
             self.update_sockets();
-            self.update_timers(dt);
+            self.update_net_groups();
+            self.update_native_extensions();
+            self.update_web_views();
+            self.run_timers();
             self.update(|context| {
                 StreamManager::tick(context, dt);
             });
@@ -751,6 +883,65 @@ impl Player {
         });
     }
 
+    /// Dispatches `select` for the item of the `NativeWindow.menu` with the given id, as reported
+    /// by the UI backend.
+    pub fn run_native_menu_callback(&mut self, id: usize) {
+        self.mutate_with_update_context(|context| {
+            let Some(item) = context.native_menu.item(id) else {
+                return;
+            };
+
+            let mut activation = Avm2Activation::from_nothing(context.reborrow());
+            if let Err(e) = dispatch_native_menu_select(&mut activation, item) {
+                tracing::error!("Unhandled AVM2 error in native menu callback: {e:?}");
+            }
+        });
+    }
+
+    /// Dispatches `move` and `resize` on AIR content's `NativeWindow` for however the host's
+    /// window changed since this was last called. Hosts should call this whenever their window
+    /// moves or resizes.
+    pub fn update_window_bounds(&mut self) {
+        let Some(after) = self.ui.window_bounds() else {
+            return;
+        };
+        let Some(before) = self.window_bounds.replace(after) else {
+            return;
+        };
+        if before == after {
+            return;
+        }
+
+        self.mutate_with_update_context(|context| {
+            if !Self::has_native_window(context) {
+                return;
+            }
+            let mut activation = Avm2Activation::from_nothing(context.reborrow());
+            if let Err(e) = dispatch_window_bounds_change(&mut activation, before, after) {
+                tracing::error!("Unhandled AVM2 error in window bounds event: {e:?}");
+            }
+        });
+    }
+
+    /// Dispatches `activate` or `deactivate` on AIR content's `NativeWindow`. Hosts should call
+    /// this whenever their window gains or loses focus.
+    pub fn set_window_active(&mut self, active: bool) {
+        self.mutate_with_update_context(|context| {
+            if !Self::has_native_window(context) {
+                return;
+            }
+            let mut activation = Avm2Activation::from_nothing(context.reborrow());
+            if let Err(e) = dispatch_window_activation(&mut activation, active) {
+                tracing::error!("Unhandled AVM2 error in window activation event: {e:?}");
+            }
+        });
+    }
+
+    /// Whether the movie gets events from `NativeWindow`, which only AIR content does.
+    fn has_native_window(context: &UpdateContext<'_, '_>) -> bool {
+        context.is_action_script_3() && context.navigator.air_directories().is_some()
+    }
+
     fn run_context_menu_custom_callback<'gc>(
         item: Object<'gc>,
         callback: Object<'gc>,
@@ -839,7 +1030,7 @@ impl Player {
     }
 
     pub fn needs_render(&self) -> bool {
-        self.needs_render
+        self.needs_render || self.stages.any_needs_render()
     }
 
     pub fn background_color(&mut self) -> Option<Color> {
@@ -877,10 +1068,14 @@ impl Player {
     }
 
     pub fn set_viewport_dimensions(&mut self, dimensions: ViewportDimensions) {
-        self.mutate_with_update_context(|context| {
-            context.renderer.set_viewport_dimensions(dimensions);
-            context.stage.build_matrices(context);
-        })
+        self.renderer.set_viewport_dimensions(dimensions);
+        for stage in self.stage_ids() {
+            self.with_stage(stage, |player| {
+                player.mutate_with_update_context(|context| {
+                    context.stage.build_matrices(context);
+                })
+            });
+        }
     }
 
     pub fn set_show_menu(&mut self, show_menu: bool) {
@@ -930,6 +1125,15 @@ impl Player {
     /// 8. Mouse state is updated. This triggers button rollovers, which are a
     ///    second wave of event processing.
     pub fn handle_event(&mut self, event: PlayerEvent) {
+        let event = if let PlayerEvent::MouseWheel { delta } = event {
+            match self.accumulate_mouse_wheel(delta) {
+                Some(delta) => PlayerEvent::MouseWheel { delta },
+                None => return,
+            }
+        } else {
+            event
+        };
+
         let prev_is_mouse_down = self.input.is_mouse_down();
         self.input.handle_event(&event);
         let is_mouse_button_changed = self.input.is_mouse_down() != prev_is_mouse_down;
@@ -1109,6 +1313,18 @@ impl Player {
                 if let PlayerEvent::TextControl { code } = event {
                     if let Some(text) = context.focus_tracker.get().and_then(|o| o.as_edit_text()) {
                         text.text_control_input(code, context);
+                    } else if code == TextControlCode::Paste && context.is_action_script_3() {
+                        // Text fields paste by themselves, without a `paste` event.
+                        let mut activation = Avm2Activation::from_nothing(context.reborrow());
+                        let target = activation
+                            .context
+                            .focus_tracker
+                            .get()
+                            .unwrap_or_else(|| activation.context.stage.into())
+                            .object2()
+                            .coerce_to_object(&mut activation)
+                            .expect("DisplayObject is not an object!");
+                        dispatch_paste(&mut activation, target);
                     }
                 }
             }
@@ -1141,7 +1357,14 @@ impl Player {
                 ),
                 PlayerEvent::MouseWheel { delta } => {
                     let delta = Value::from(delta.lines());
-                    (None, Some(("Mouse", "onMouseWheel", vec![delta])))
+                    let scroll_target = context
+                        .mouse_over_object
+                        .map(|o| o.as_displayobject().object())
+                        .unwrap_or(Value::Undefined);
+                    (
+                        None,
+                        Some(("Mouse", "onMouseWheel", vec![delta, scroll_target])),
+                    )
                 }
                 _ => (None, None),
             };
@@ -1171,6 +1394,20 @@ impl Player {
             }
 
             Self::run_actions(context);
+
+            // Tab moves focus between objects in AVM1 movies, once `Key` listeners have run.
+            if let PlayerEvent::KeyDown {
+                key_code: KeyCode::Tab,
+                ..
+            } = event
+            {
+                if !context.is_action_script_3() {
+                    let reverse = context.input.is_key_down(KeyCode::Shift);
+                    let tracker = context.focus_tracker;
+                    tracker.cycle(context, reverse);
+                    Self::run_actions(context);
+                }
+            }
         });
 
         // Update mouse state.
@@ -1206,20 +1443,44 @@ impl Player {
 
         if let PlayerEvent::MouseWheel { delta } = event {
             self.mutate_with_update_context(|context| {
+                let event = ClipEvent::MouseWheel { delta };
                 if let Some(over_object) = context.mouse_over_object {
                     if context.is_action_script_3()
                         || !over_object.as_displayobject().avm1_removed()
                     {
-                        over_object.handle_clip_event(context, ClipEvent::MouseWheel { delta });
+                        over_object.handle_clip_event(context, event);
+                        if context.is_action_script_3() {
+                            over_object.event_dispatch_to_avm2(context, event);
+                        }
+                        if let Some(text) = over_object.as_displayobject().as_edit_text() {
+                            text.scroll_by_mouse_wheel(delta, context);
+                        }
                     }
                 } else {
-                    context
-                        .stage
-                        .handle_clip_event(context, ClipEvent::MouseWheel { delta });
+                    context.stage.handle_clip_event(context, event);
+                    if context.is_action_script_3() {
+                        context.stage.event_dispatch_to_avm2(context, event);
+                    }
                 }
             });
         }
 
+        if let PlayerEvent::MouseDown {
+            button: button @ (MouseButton::Right | MouseButton::Middle),
+            ..
+        } = event
+        {
+            self.handle_secondary_mouse_down(button);
+        }
+
+        if let PlayerEvent::MouseUp {
+            button: button @ (MouseButton::Right | MouseButton::Middle),
+            ..
+        } = event
+        {
+            self.handle_secondary_mouse_up(button);
+        }
+
         if let PlayerEvent::MouseLeave = event {
             if self.update_mouse_state(is_mouse_button_changed, true) {
                 self.needs_render = true;
@@ -1227,6 +1488,102 @@ impl Player {
         }
     }
 
+    /// Dispatches `rightMouseDown`/`middleMouseDown` to the object under the cursor.
+    fn handle_secondary_mouse_down(&mut self, button: MouseButton) {
+        self.gc_arena.borrow().mutate(|gc_context, gc_root| {
+            let mut root_data = gc_root.data.write(gc_context);
+            let hovered = root_data.mouse_hovered_object;
+            *root_data.mouse_secondary_pressed_object(button) = hovered;
+        });
+
+        let consumed = self.mutate_with_update_context(|context| {
+            if !context.is_action_script_3() || !context.player_version.has_secondary_mouse_events()
+            {
+                return false;
+            }
+
+            let target = context
+                .mouse_over_object
+                .unwrap_or_else(|| context.stage.into());
+            target.event_dispatch_to_avm2(context, ClipEvent::SecondaryPress { button });
+            Self::run_actions(context);
+
+            // Content that listens for right clicks replaces the context menu.
+            let Avm2Value::Object(target) = target.as_displayobject().object2() else {
+                return false;
+            };
+            let mut activation = Avm2Activation::from_nothing(context.reborrow());
+            ["rightMouseDown", "rightClick"]
+                .into_iter()
+                .any(|event_type| {
+                    will_trigger(&mut activation, target, event_type.into()).unwrap_or(false)
+                })
+        });
+
+        if button == MouseButton::Right {
+            self.right_click_consumed = consumed;
+        }
+    }
+
+    /// Dispatches `rightMouseUp`/`middleMouseUp` to the object under the cursor, followed
+    /// by `rightClick`/`middleClick` if the button was also pressed over that object.
+    fn handle_secondary_mouse_up(&mut self, button: MouseButton) {
+        let click = self.gc_arena.borrow().mutate(|gc_context, gc_root| {
+            let mut root_data = gc_root.data.write(gc_context);
+            let pressed = root_data.mouse_secondary_pressed_object(button).take();
+            match (pressed, root_data.mouse_hovered_object) {
+                (Some(pressed), Some(hovered)) => InteractiveObject::ptr_eq(pressed, hovered),
+                (None, None) => true,
+                _ => false,
+            }
+        });
+
+        self.mutate_with_update_context(|context| {
+            if !context.is_action_script_3() || !context.player_version.has_secondary_mouse_events()
+            {
+                return;
+            }
+
+            let target = context
+                .mouse_over_object
+                .unwrap_or_else(|| context.stage.into());
+            target.event_dispatch_to_avm2(context, ClipEvent::SecondaryRelease { button, click });
+            Self::run_actions(context);
+        });
+    }
+
+    /// Whether the content consumed the last right mouse button press, meaning
+    /// that the frontend should not show the context menu for it.
+    pub fn right_click_consumed(&self) -> bool {
+        self.right_click_consumed
+    }
+
+    /// Accumulate a mouse wheel movement reported by the UI backend.
+    ///
+    /// Returns the number of whole lines to scroll by, or `None` if the
+    /// accumulated movement does not yet add up to a line. Changing scroll
+    /// direction discards any leftover movement.
+    fn accumulate_mouse_wheel(&mut self, delta: MouseWheelDelta) -> Option<MouseWheelDelta> {
+        let lines = delta.lines();
+        if lines.is_nan() || lines == 0.0 {
+            return None;
+        }
+
+        if lines.signum() != self.mouse_wheel_remainder.signum() {
+            self.mouse_wheel_remainder = 0.0;
+        }
+
+        self.mouse_wheel_remainder += lines;
+        let whole_lines = self.mouse_wheel_remainder.trunc();
+        self.mouse_wheel_remainder -= whole_lines;
+
+        if whole_lines == 0.0 {
+            None
+        } else {
+            Some(MouseWheelDelta::Lines(whole_lines))
+        }
+    }
+
     /// Update dragged object, if any.
     pub fn update_drag(context: &mut UpdateContext<'_, '_>) {
         let mouse_position = *context.mouse_position;
@@ -1655,6 +2012,29 @@ impl Player {
 
     #[instrument(level = "debug", skip_all)]
     pub fn render(&mut self) {
+        let start = self.clock.now();
+
+        let mut cache_draws = vec![];
+        let mut commands = CommandList::new();
+        for stage in self.stage_ids() {
+            if let Some((stage_cache_draws, stage_commands)) =
+                self.with_stage(stage, Self::render_stage)
+            {
+                cache_draws.extend(stage_cache_draws);
+                commands.commands.extend(stage_commands.commands);
+            }
+        }
+
+        self.renderer
+            .submit_frame(Color::TRANSPARENT, commands, cache_draws);
+
+        if self.telemetry {
+            telemetry::render(start, self.clock.now() - start);
+        }
+    }
+
+    /// Draw the active stage, without submitting it to the renderer.
+    fn render_stage(&mut self) -> (Vec<BitmapCacheEntry>, CommandList) {
         let invalidated = self
             .gc_arena
             .borrow()
@@ -1687,8 +2067,9 @@ impl Player {
 
             stage.render(&mut render_context);
 
+            // The debug UI only knows about objects on the main stage.
             #[cfg(feature = "egui")]
-            {
+            if self.stages.active() == StageId::MAIN {
                 let debug_ui = self.debug_ui.clone();
                 debug_ui
                     .borrow_mut()
@@ -1706,10 +2087,8 @@ impl Player {
             (cache_draws, commands)
         });
 
-        self.renderer
-            .submit_frame(Color::TRANSPARENT, commands, cache_draws);
-
         self.needs_render = false;
+        (cache_draws, commands)
     }
 
     /// The current frame of the main timeline, if available.
@@ -1864,6 +2243,15 @@ impl Player {
     where
         F: for<'a, 'gc> FnOnce(&mut UpdateContext<'a, 'gc>) -> R,
     {
+        // Futures spawned by additional stages need to find their way back to them.
+        let mut stage_navigator;
+        let navigator: &mut dyn NavigatorBackend = if self.stages.active() == StageId::MAIN {
+            self.navigator.deref_mut()
+        } else {
+            stage_navigator = self.stages.navigator(self.navigator.deref_mut());
+            &mut stage_navigator
+        };
+
         self.gc_arena.borrow().mutate(|gc_context, gc_root| {
             let mut root_data = gc_root.data.write(gc_context);
             let mouse_hovered_object = root_data.mouse_hovered_object;
@@ -1889,6 +2277,10 @@ impl Player {
                 audio_manager,
                 stream_manager,
                 sockets,
+                net_groups,
+                native_extensions,
+                web_views,
+                native_menu,
                 dynamic_root,
             ) = root_data.update_context_params();
 
@@ -1899,7 +2291,7 @@ impl Player {
                 rng: &mut self.rng,
                 renderer: self.renderer.deref_mut(),
                 audio: self.audio.deref_mut(),
-                navigator: self.navigator.deref_mut(),
+                navigator,
                 ui: self.ui.deref_mut(),
                 action_queue,
                 gc_context,
@@ -1915,8 +2307,10 @@ impl Player {
                 system: &mut self.system,
                 instance_counter: &mut self.instance_counter,
                 storage: self.storage.deref_mut(),
+                secure_storage: self.secure_storage.deref_mut(),
                 log: self.log.deref_mut(),
                 video: self.video.deref_mut(),
+                media: self.media.deref_mut(),
                 avm1_shared_objects,
                 avm2_shared_objects,
                 unbound_text_fields,
@@ -1926,7 +2320,8 @@ impl Player {
                 avm1,
                 avm2,
                 external_interface,
-                start_time: self.start_time,
+                clock: &mut self.clock,
+                telemetry: self.telemetry,
                 update_start: Instant::now(),
                 max_execution_duration: self.max_execution_duration,
                 focus_tracker,
@@ -1938,8 +2333,13 @@ impl Player {
                 actions_since_timeout_check: &mut self.actions_since_timeout_check,
                 frame_phase: &mut self.frame_phase,
                 stub_tracker: &mut self.stub_tracker,
+                sql_connections: &mut self.sql_connections,
                 stream_manager,
                 sockets,
+                net_groups,
+                native_extensions,
+                web_views,
+                native_menu,
                 dynamic_root,
             };
 
@@ -1953,6 +2353,11 @@ impl Player {
                 update_context
                     .audio
                     .set_frame_rate(*update_context.frame_rate);
+
+                // Don't let time banked at the old frame rate cause a burst of frames
+                // at the new one; at most one frame becomes immediately due.
+                let frame_time = 1000.0 / *update_context.frame_rate;
+                self.frame_accumulator = self.frame_accumulator.min(frame_time);
             }
 
             self.current_frame = update_context
@@ -2000,6 +2405,12 @@ impl Player {
     where
         F: for<'a, 'gc> FnOnce(&mut UpdateContext<'a, 'gc>) -> R,
     {
+        if let Some(stage) = self.polling_stage() {
+            return self
+                .with_stage(stage, |player| player.update(func))
+                .expect("Polling stage should exist");
+        }
+
         let rval = self.mutate_with_update_context(|context| {
             let rval = func(context);
 
@@ -2049,9 +2460,29 @@ impl Player {
 
     /// Update all AVM-based timers (such as created via setInterval).
     /// Returns the approximate amount of time until the next timer tick.
+    ///
+    /// A timer callback that calls `updateAfterEvent` gets rendered before the next timer runs.
+    /// Moves the clock forward by `dt` milliseconds without running any frames, and runs the
+    /// timers that are due.
     pub fn update_timers(&mut self, dt: f64) {
-        self.time_til_next_timer =
-            self.mutate_with_update_context(|context| Timers::update_timers(context, dt));
+        self.clock.tick(dt);
+        self.run_timers();
+    }
+
+    /// Runs the timers that are due by the time the clock has been ticked to.
+    fn run_timers(&mut self) {
+        loop {
+            let (time_til_next_timer, render_requested) =
+                self.mutate_with_update_context(|context| {
+                    let time_til_next_timer = Timers::update_timers(context);
+                    (time_til_next_timer, context.timers.take_render_request())
+                });
+            self.time_til_next_timer = time_til_next_timer;
+            if !render_requested {
+                break;
+            }
+            self.render();
+        }
     }
 
     /// Update connected Sockets.
@@ -2061,16 +2492,184 @@ impl Player {
         })
     }
 
+    /// Update joined NetGroups.
+    pub fn update_net_groups(&mut self) {
+        self.mutate_with_update_context(|context| {
+            NetGroups::update_net_groups(context);
+        })
+    }
+
+    /// Deliver status events sent by native extensions.
+    pub fn update_native_extensions(&mut self) {
+        self.mutate_with_update_context(|context| {
+            NativeExtensions::update_native_extensions(context);
+        })
+    }
+
+    /// Deliver page load events sent by web views.
+    pub fn update_web_views(&mut self) {
+        self.mutate_with_update_context(|context| {
+            WebViews::update_web_views(context);
+        })
+    }
+
+    /// Adds an output format for `export_item`, replacing any plugin with the same format name.
+    pub fn register_exporter_plugin(&mut self, plugin: Box<dyn ItemExporterPlugin>) {
+        self.exporter_plugins.register(plugin);
+    }
+
+    /// The formats `export_item` can export to.
+    pub fn export_formats(&self) -> Vec<String> {
+        self.exporter_plugins.formats().map(String::from).collect()
+    }
+
+    /// The root movie, as loaded with `PlayerBuilder::with_movie` or `set_root_movie`.
+    pub fn root_movie(&self) -> &Arc<SwfMovie> {
+        &self.swf
+    }
+
+    /// Exports the character with the given ID from `movie`, using the plugin for `format`.
+    ///
+    /// The plugin's files are followed by a `<name>.manifest.json` describing the item and how
+    /// it was exported. The item is rendered by a separate player, so its scripts don't affect
+    /// this one.
+    pub fn export_item(
+        &mut self,
+        movie: &Arc<SwfMovie>,
+        id: CharacterId,
+        format: &str,
+    ) -> Result<Vec<ExportedFile>, ExportError> {
+        self.export_item_variants(movie, id, format, &[ColorVariant::default()])
+    }
+
+    /// Like `export_item`, but recolors the item first.
+    pub fn export_item_recolored(
+        &mut self,
+        movie: &Arc<SwfMovie>,
+        id: CharacterId,
+        format: &str,
+        recolor: Recolor,
+    ) -> Result<Vec<ExportedFile>, ExportError> {
+        self.export_item_variants(movie, id, format, &[ColorVariant::new("", recolor)])
+    }
+
+    /// Like `export_item`, but exports each of `variants` of the item, with the variant's name
+    /// appended to the item's.
+    ///
+    /// The item is only looked up and extracted once for all of them. Asking for no variants
+    /// is an error, as is recoloring a sound, or a symbol that the plugin doesn't render.
+    pub fn export_item_variants(
+        &mut self,
+        movie: &Arc<SwfMovie>,
+        id: CharacterId,
+        format: &str,
+        variants: &[ColorVariant],
+    ) -> Result<Vec<ExportedFile>, ExportError> {
+        // Taken out while exporting, as the plugin runs with the update context borrowed.
+        let plugins = std::mem::take(&mut self.exporter_plugins);
+        let result = match plugins.get(format) {
+            Some(plugin) => self.export_item_with(movie, id, plugin, variants),
+            None => Err(ExportError::UnknownFormat(format.to_string())),
+        };
+        self.exporter_plugins = plugins;
+        result
+    }
+
+    /// Like `export_item_variants`, but exports with `plugin` instead of a registered one.
+    ///
+    /// This suits one-off settings, which registering a plugin would keep for later exports.
+    pub fn export_item_with(
+        &mut self,
+        movie: &Arc<SwfMovie>,
+        id: CharacterId,
+        plugin: &dyn ItemExporterPlugin,
+        variants: &[ColorVariant],
+    ) -> Result<Vec<ExportedFile>, ExportError> {
+        // Rendering the item runs its scripts, which mustn't touch the movie that is playing.
+        self.with_isolated_player(movie, |isolated, movie| {
+            isolated.mutate_with_update_context(|context| {
+                export::export_item(context, plugin, movie, id, variants)
+            })
+        })
+    }
+
+    /// Renders a preview of `movie` or one of its characters that fits in a `size` by `size`
+    /// square, or returns the cached one.
+    ///
+    /// The preview is rendered by a separate player, so scripts it runs don't affect this one.
+    pub fn thumbnail(
+        &mut self,
+        movie: &Arc<SwfMovie>,
+        subject: ThumbnailSubject,
+        size: u32,
+    ) -> Option<Arc<ExportImage>> {
+        // Taken out while rendering, as it needs the player.
+        let mut thumbnails = std::mem::take(&mut self.thumbnails);
+        let result = thumbnails.thumbnail(self, movie, subject, size);
+        self.thumbnails = thumbnails;
+        result
+    }
+
+    /// Runs `f` on a throwaway player that has loaded a copy of `movie`, lending it this
+    /// player's renderer so that it can draw.
+    ///
+    /// Whatever scripts run while instantiating or rendering `movie` can't affect this player.
+    /// `f` is given the copy of the movie, which is the one the throwaway player's library
+    /// knows.
+    pub(crate) fn with_isolated_player<R>(
+        &mut self,
+        movie: &SwfMovie,
+        f: impl FnOnce(&mut Player, &Arc<SwfMovie>) -> R,
+    ) -> R {
+        let dimensions = self.renderer.viewport_dimensions();
+        let quality = self.mutate_with_update_context(|context| context.stage.quality());
+
+        let mut builder = PlayerBuilder::new()
+            .with_movie(movie.clone())
+            .with_player_version(Some(self.player_version))
+            .with_quality(quality);
+        builder.renderer = Some(std::mem::replace(
+            &mut self.renderer,
+            Box::new(NullRenderer::new(dimensions)),
+        ));
+        let isolated = builder.build();
+        let mut isolated = isolated.lock().unwrap();
+        isolated.preload(&mut ExecutionLimit::none());
+        let movie = isolated.root_movie().clone();
+
+        // The renderer has to be given back even if `f` panics.
+        let result =
+            std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| f(&mut isolated, &movie)));
+        self.renderer = std::mem::replace(
+            &mut isolated.renderer,
+            Box::new(NullRenderer::new(dimensions)),
+        );
+        result.unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+    }
+
+    /// The cache of previews rendered by `thumbnail`.
+    pub fn thumbnails_mut(&mut self) -> &mut ThumbnailService {
+        &mut self.thumbnails
+    }
+
     /// Returns whether this player consumes mouse wheel events.
     /// Used by web to prevent scrolling.
     pub fn should_prevent_scrolling(&mut self) -> bool {
         self.mutate_with_update_context(|context| context.avm1.has_mouse_listener())
     }
 
+    /// Adds an External Interface provider to every stage, including the ones added later.
     pub fn add_external_interface(&mut self, provider: Box<dyn ExternalInterfaceProvider>) {
-        self.mutate_with_update_context(|context| {
-            context.external_interface.add_provider(provider)
-        });
+        let provider: Rc<dyn ExternalInterfaceProvider> = Rc::from(provider);
+        self.stages.add_external_interface(provider.clone());
+        for id in self.stage_ids() {
+            let provider = provider.clone();
+            self.with_stage(id, |player| {
+                player.mutate_with_update_context(|context| {
+                    context.external_interface.add_provider(Box::new(provider))
+                });
+            });
+        }
     }
 
     pub fn call_internal_interface(
@@ -2095,6 +2694,21 @@ impl Player {
         &self.compatibility_rules
     }
 
+    pub fn tag_strictness(&self) -> TagStrictness {
+        self.tag_strictness
+    }
+
+    /// The metrics of the most recent movie loads, from oldest to newest.
+    pub fn load_metrics(&mut self) -> Vec<LoadMetrics> {
+        self.mutate_with_update_context(|context| {
+            context
+                .load_manager
+                .all_metrics()
+                .map(|(_, metrics)| metrics.clone())
+                .collect()
+        })
+    }
+
     pub fn log_backend(&self) -> &Log {
         &self.log
     }
@@ -2129,6 +2743,26 @@ impl Player {
             context.library.set_default_font(font, names);
         });
     }
+
+    /// Register the built-in fallback font, if there is one, on the active stage.
+    fn register_default_fonts(&mut self) {
+        #[cfg(feature = "default_font")]
+        {
+            let mut font_reader = swf::read::Reader::new(FALLBACK_DEVICE_FONT_TAG, 8);
+            let font_tag = font_reader
+                .read_define_font_2(3)
+                .expect("Built-in font should compile");
+            self.register_device_font(FontDefinition::SwfTag(font_tag, font_reader.encoding()));
+            self.set_default_font(DefaultFont::Sans, vec!["Noto Sans".to_string()]);
+            self.set_default_font(DefaultFont::Serif, vec!["Noto Sans".to_string()]);
+            self.set_default_font(DefaultFont::Typewriter, vec!["Noto Sans".to_string()]);
+        }
+    }
+
+    /// The additional stage whose future is being polled, if it isn't the active one.
+    fn polling_stage(&self) -> Option<StageId> {
+        stages::polling_stage().filter(|stage| *stage != self.stages.active())
+    }
 }
 
 /// Player factory, which can be used to configure the aspects of a Ruffle player.
@@ -2141,8 +2775,10 @@ pub struct PlayerBuilder {
     navigator: Option<Navigator>,
     renderer: Option<Renderer>,
     storage: Option<Storage>,
+    secure_storage: Option<SecureStorage>,
     ui: Option<Ui>,
     video: Option<Video>,
+    media: Option<Media>,
 
     // Misc. player configuration
     autoplay: bool,
@@ -2159,12 +2795,20 @@ pub struct PlayerBuilder {
     load_behavior: LoadBehavior,
     spoofed_url: Option<String>,
     compatibility_rules: CompatibilityRules,
-    player_version: Option<u8>,
+    tag_strictness: TagStrictness,
+    player_version: Option<PlayerVersion>,
+    telemetry: bool,
     quality: StageQuality,
     sandbox_type: SandboxType,
     frame_rate: Option<f64>,
+    socket_framing: SocketFraming,
+    socket_policy: SocketPolicy,
     external_interface_providers: Vec<Box<dyn ExternalInterfaceProvider>>,
     fs_command_provider: Box<dyn FsCommandProvider>,
+    native_extensions: HashMap<String, Box<dyn NativeExtension>>,
+    web_view_provider: Option<Box<dyn WebViewProvider>>,
+    exporter_plugins: ExporterPlugins,
+    thumbnail_capacity: usize,
 }
 
 impl PlayerBuilder {
@@ -2182,8 +2826,10 @@ impl PlayerBuilder {
             navigator: None,
             renderer: None,
             storage: None,
+            secure_storage: None,
             ui: None,
             video: None,
+            media: None,
 
             autoplay: false,
             align: StageAlign::default(),
@@ -2204,12 +2850,20 @@ impl PlayerBuilder {
             load_behavior: LoadBehavior::Streaming,
             spoofed_url: None,
             compatibility_rules: CompatibilityRules::default(),
+            tag_strictness: TagStrictness::default(),
             player_version: None,
+            telemetry: false,
             quality: StageQuality::High,
             sandbox_type: SandboxType::LocalTrusted,
             frame_rate: None,
+            socket_framing: SocketFraming::None,
+            socket_policy: SocketPolicy::default(),
             external_interface_providers: vec![],
             fs_command_provider: Box::new(NullFsCommandProvider),
+            native_extensions: HashMap::new(),
+            web_view_provider: None,
+            exporter_plugins: ExporterPlugins::new(),
+            thumbnail_capacity: ThumbnailService::DEFAULT_CAPACITY,
         }
     }
 
@@ -2255,6 +2909,16 @@ impl PlayerBuilder {
         self
     }
 
+    /// Sets the secure storage backend of the player, used by AIR's `EncryptedLocalStore`.
+    #[inline]
+    pub fn with_secure_storage(
+        mut self,
+        secure_storage: impl 'static + SecureStorageBackend,
+    ) -> Self {
+        self.secure_storage = Some(Box::new(secure_storage));
+        self
+    }
+
     /// Sets the UI backend of the player.
     #[inline]
     pub fn with_ui(mut self, ui: impl 'static + UiBackend) -> Self {
@@ -2269,6 +2933,13 @@ impl PlayerBuilder {
         self
     }
 
+    /// Sets the media decoder backend of the player.
+    #[inline]
+    pub fn with_media_decoder(mut self, media: impl 'static + MediaDecoderBackend) -> Self {
+        self.media = Some(Box::new(media));
+        self
+    }
+
     /// Sets the stage scale mode and optionally prevents movies from changing it.
     #[inline]
     pub fn with_align(mut self, align: StageAlign, force: bool) -> Self {
@@ -2350,12 +3021,26 @@ impl PlayerBuilder {
         self
     }
 
-    /// Configures the target player version.
-    pub fn with_player_version(mut self, version: Option<u8>) -> Self {
+    /// Configures how malformed tags in loaded movies are dealt with.
+    pub fn with_tag_strictness(mut self, tag_strictness: TagStrictness) -> Self {
+        self.tag_strictness = tag_strictness;
+        self
+    }
+
+    /// Configures the target player version, and with it the behaviors that differ
+    /// between Flash Player releases.
+    pub fn with_player_version(mut self, version: Option<PlayerVersion>) -> Self {
         self.player_version = version;
         self
     }
 
+    /// Sets whether frame and render timings, and the metrics movies send through
+    /// `flash.profiler.Telemetry`, are logged as telemetry events.
+    pub fn with_telemetry(mut self, telemetry: bool) -> Self {
+        self.telemetry = telemetry;
+        self
+    }
+
     /// Configures the security sandbox type (default is `SandboxType::LocalTrusted`)
     pub fn with_sandbox_type(mut self, sandbox_type: SandboxType) -> Self {
         self.sandbox_type = sandbox_type;
@@ -2368,6 +3053,56 @@ impl PlayerBuilder {
         self
     }
 
+    /// Sets how data received by AVM2 `Socket`s is split into `socketData` events.
+    /// `XMLSocket`s always deliver one event per message.
+    pub fn with_socket_framing(mut self, framing: SocketFraming) -> Self {
+        self.socket_framing = framing;
+        self
+    }
+
+    /// Sets which hosts must serve a socket policy file before sockets may connect to them.
+    pub fn with_socket_policy(mut self, policy: SocketPolicy) -> Self {
+        self.socket_policy = policy;
+        self
+    }
+
+    /// Applies every setting of `config`, such as one loaded from a file.
+    ///
+    /// The URL rewrite rules of the config replace any compatibility rules set so far.
+    pub fn with_config(mut self, config: PlayerConfig) -> Self {
+        self.compatibility_rules = config.compatibility_rules();
+        self.load_behavior = config.load_behavior;
+        self.sandbox_type = config.sandbox_type;
+        self.spoofed_url = config.spoofed_url;
+        self.quality = config.quality;
+        self.frame_rate = config.frame_rate;
+        self.player_version = config.player_version;
+        self.telemetry = config.telemetry;
+        self.tag_strictness = config.tag_strictness;
+        self.socket_policy = config.socket_policy;
+        self
+    }
+
+    /// The settings the player would currently be built with, to save and reproduce this run.
+    pub fn config(&self) -> PlayerConfig {
+        PlayerConfig {
+            load_behavior: self.load_behavior,
+            sandbox_type: self.sandbox_type,
+            spoofed_url: self.spoofed_url.clone(),
+            url_rewrite_rules: self
+                .compatibility_rules
+                .swf_domain_rewrite_rules()
+                .cloned()
+                .collect(),
+            quality: self.quality,
+            frame_rate: self.frame_rate,
+            player_version: self.player_version,
+            telemetry: self.telemetry,
+            tag_strictness: self.tag_strictness,
+            socket_policy: self.socket_policy.clone(),
+        }
+    }
+
     /// Adds an External Interface provider for movies to communicate with
     pub fn with_external_interface(mut self, provider: Box<dyn ExternalInterfaceProvider>) -> Self {
         self.external_interface_providers.push(provider);
@@ -2380,14 +3115,52 @@ impl PlayerBuilder {
         self
     }
 
+    /// Provides an implementation of the AIR native extension with the given ID
+    pub fn with_native_extension(
+        mut self,
+        id: impl Into<String>,
+        extension: Box<dyn NativeExtension>,
+    ) -> Self {
+        self.native_extensions.insert(id.into(), extension);
+        self
+    }
+
+    /// Sets the web view provider used by AIR's `HTMLLoader`
+    pub fn with_web_view_provider(mut self, provider: Box<dyn WebViewProvider>) -> Self {
+        self.web_view_provider = Some(provider);
+        self
+    }
+
+    /// Adds an output format for `Player::export_item`
+    pub fn with_exporter_plugin(mut self, plugin: Box<dyn ItemExporterPlugin>) -> Self {
+        self.exporter_plugins.register(plugin);
+        self
+    }
+
+    /// Sets how many previews `Player::thumbnail` keeps.
+    pub fn with_thumbnail_capacity(mut self, capacity: usize) -> Self {
+        self.thumbnail_capacity = capacity;
+        self
+    }
+
+    #[allow(clippy::too_many_arguments)]
     fn create_gc_root<'gc>(
         gc_context: &'gc gc_arena::Mutation<'gc>,
-        player_version: u8,
+        player_version: PlayerVersion,
+        enumeration_order: EnumerationOrder,
         fullscreen: bool,
         fake_movie: Arc<SwfMovie>,
+        socket_framing: SocketFraming,
+        socket_policy: SocketPolicy,
         external_interface_providers: Vec<Box<dyn ExternalInterfaceProvider>>,
         fs_command_provider: Box<dyn FsCommandProvider>,
+        native_extensions: HashMap<String, Box<dyn NativeExtension>>,
+        web_view_provider: Option<Box<dyn WebViewProvider>>,
     ) -> GcRoot<'gc> {
+        let mut sockets = Sockets::empty();
+        sockets.set_default_framing(socket_framing);
+        sockets.set_policy(socket_policy);
+
         let mut interner = AvmStringInterner::new();
         let mut init = GcContext {
             gc_context,
@@ -2403,7 +3176,7 @@ impl PlayerBuilder {
                     audio_manager: AudioManager::new(),
                     action_queue: ActionQueue::new(),
                     avm1: Avm1::new(&mut init, player_version),
-                    avm2: Avm2::new(&mut init, player_version),
+                    avm2: Avm2::new(&mut init, player_version, enumeration_order),
                     interner,
                     current_context_menu: None,
                     drag_object: None,
@@ -2416,13 +3189,19 @@ impl PlayerBuilder {
                     load_manager: LoadManager::new(),
                     mouse_hovered_object: None,
                     mouse_pressed_object: None,
+                    mouse_right_pressed_object: None,
+                    mouse_middle_pressed_object: None,
                     avm1_shared_objects: HashMap::new(),
                     avm2_shared_objects: HashMap::new(),
                     stage: Stage::empty(gc_context, fullscreen, fake_movie),
                     timers: Timers::new(),
                     unbound_text_fields: Vec::new(),
                     stream_manager: StreamManager::new(),
-                    sockets: Sockets::empty(),
+                    sockets,
+                    net_groups: NetGroups::empty(),
+                    native_extensions: NativeExtensions::new(native_extensions),
+                    web_views: WebViews::new(web_view_provider),
+                    native_menu: NativeMenuState::new(),
                     dynamic_root,
                 },
             ),
@@ -2452,19 +3231,53 @@ impl PlayerBuilder {
         let storage = self
             .storage
             .unwrap_or_else(|| Box::new(storage::MemoryStorageBackend::new()));
+        let secure_storage = self
+            .secure_storage
+            .unwrap_or_else(|| Box::new(storage::MemorySecureStorageBackend::new()));
         let ui = self
             .ui
             .unwrap_or_else(|| Box::new(ui::NullUiBackend::new()));
         let video = self
             .video
             .unwrap_or_else(|| Box::new(null::NullVideoBackend::new()));
+        let media = self
+            .media
+            .unwrap_or_else(|| Box::new(null::NullMediaDecoderBackend::new()));
 
-        let player_version = self.player_version.unwrap_or(NEWEST_PLAYER_VERSION);
+        let player_version = self
+            .player_version
+            .unwrap_or_else(|| NEWEST_PLAYER_VERSION.into());
 
         // Instantiate the player.
-        let fake_movie = Arc::new(SwfMovie::empty(player_version));
+        let fake_movie = Arc::new(SwfMovie::empty(player_version.major));
         let frame_rate = self.frame_rate.unwrap_or(12.0);
         let forced_frame_rate = self.frame_rate.is_some();
+        let window_bounds = ui.window_bounds();
+        let stage_config = StageConfig {
+            enumeration_order: EnumerationOrder::for_player_version(self.player_version),
+            socket_framing: self.socket_framing,
+            socket_policy: self.socket_policy,
+            external_interface_providers: self
+                .external_interface_providers
+                .into_iter()
+                .map(Rc::from)
+                .collect(),
+            fs_command_provider: Rc::from(self.fs_command_provider),
+            native_extensions: self
+                .native_extensions
+                .into_iter()
+                .map(|(id, extension)| (id, Rc::from(extension)))
+                .collect(),
+            web_view_provider: self.web_view_provider.map(Rc::from),
+        };
+        let gc_arena = Rc::new(RefCell::new(GcArena::new(|gc_context| {
+            stage_config.create_gc_root(
+                gc_context,
+                player_version,
+                self.fullscreen,
+                fake_movie.clone(),
+            )
+        })));
         let player = Arc::new_cyclic(|self_ref| {
             Mutex::new(Player {
                 // Backends
@@ -2473,8 +3286,10 @@ impl PlayerBuilder {
                 navigator,
                 renderer,
                 storage,
+                secure_storage,
                 ui,
                 video,
+                media,
 
                 // SWF info
                 swf: fake_movie.clone(),
@@ -2486,7 +3301,8 @@ impl PlayerBuilder {
                 frame_phase: Default::default(),
                 frame_accumulator: 0.0,
                 recent_run_frame_timings: VecDeque::with_capacity(10),
-                start_time: Instant::now(),
+                clock: PlayerClock::new(),
+                telemetry: self.telemetry,
                 time_offset: 0,
                 time_til_next_timer: None,
                 max_execution_duration: self.max_execution_duration,
@@ -2494,8 +3310,11 @@ impl PlayerBuilder {
 
                 // Input
                 input: Default::default(),
+                window_bounds,
                 mouse_in_stage: true,
                 mouse_position: Point::ZERO,
+                mouse_wheel_remainder: 0.0,
+                right_click_consumed: false,
                 mouse_cursor: MouseCursor::Arrow,
                 mouse_cursor_needs_check: false,
 
@@ -2511,39 +3330,24 @@ impl PlayerBuilder {
                 load_behavior: self.load_behavior,
                 spoofed_url: self.spoofed_url.clone(),
                 compatibility_rules: self.compatibility_rules.clone(),
+                tag_strictness: self.tag_strictness,
                 stub_tracker: StubCollection::new(),
+                sql_connections: SqlConnections::new(),
+                exporter_plugins: self.exporter_plugins,
+                thumbnails: ThumbnailService::new(self.thumbnail_capacity),
                 #[cfg(feature = "egui")]
                 debug_ui: Default::default(),
+                stages: Stages::new(stage_config),
 
                 // GC data
-                gc_arena: Rc::new(RefCell::new(GcArena::new(|gc_context| {
-                    Self::create_gc_root(
-                        gc_context,
-                        player_version,
-                        self.fullscreen,
-                        fake_movie.clone(),
-                        self.external_interface_providers,
-                        self.fs_command_provider,
-                    )
-                }))),
+                gc_arena,
             })
         });
 
         // Finalize configuration and load the movie.
         let mut player_lock = player.lock().unwrap();
 
-        #[cfg(feature = "default_font")]
-        {
-            let mut font_reader = swf::read::Reader::new(FALLBACK_DEVICE_FONT_TAG, 8);
-            let font_tag = font_reader
-                .read_define_font_2(3)
-                .expect("Built-in font should compile");
-            player_lock
-                .register_device_font(FontDefinition::SwfTag(font_tag, font_reader.encoding()));
-            player_lock.set_default_font(DefaultFont::Sans, vec!["Noto Sans".to_string()]);
-            player_lock.set_default_font(DefaultFont::Serif, vec!["Noto Sans".to_string()]);
-            player_lock.set_default_font(DefaultFont::Typewriter, vec!["Noto Sans".to_string()]);
-        }
+        player_lock.register_default_fonts();
 
         player_lock.mutate_with_update_context(|context| {
             Avm2::load_player_globals(context).expect("Unable to load AVM2 globals");
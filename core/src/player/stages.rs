@@ -0,0 +1,728 @@
+//! Additional stages hosted by a single player.
+//!
+//! Every stage has its own root movie, GC arena and input state, but all of
+//! them share the player's backends. Only one stage is *active* at a time:
+//! the state of the others is parked in a `StageState`, and swapped into
+//! the `Player` whenever one of them needs to run.
+
+use super::{GcArena, GcRoot, Player, PlayerBuilder};
+use crate::avm2::Avm2;
+use crate::backend::navigator::{
+    AirDirectories, BodyChunk, ErrorResponse, NavigationMethod, NavigatorBackend, OwnedFuture,
+    Request, SuccessResponse,
+};
+use crate::backend::ui::{InputManager, MouseCursor};
+use crate::clock::PlayerClock;
+use crate::external::{ExternalInterfaceProvider, FsCommandProvider};
+use crate::frame_lifecycle::FramePhase;
+use crate::loader::Error;
+use crate::native_extension::NativeExtension;
+use crate::net_group::{NetGroupAction, NetGroupHandle};
+use crate::player_version::{EnumerationOrder, PlayerVersion};
+use crate::prelude::*;
+use crate::socket::{SocketAction, SocketFraming, SocketHandle, SocketPolicy};
+use crate::tag_utils::SwfMovie;
+use crate::vminterface::Instantiator;
+use crate::web_view::WebViewProvider;
+use async_channel::Receiver;
+use indexmap::IndexMap;
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::future::Future;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::mpsc::Sender;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+use url::{ParseError, Url};
+
+thread_local! {
+    /// The stage whose future is currently being polled, if it isn't the main stage.
+    static POLLING_STAGE: Cell<Option<StageId>> = Cell::new(None);
+}
+
+/// Identifies one of the stages hosted by a [`Player`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct StageId(u32);
+
+impl StageId {
+    /// The stage created along with the player, which covers the whole viewport.
+    pub const MAIN: StageId = StageId(0);
+}
+
+/// The part of the viewport an additional stage is drawn into, in physical pixels.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct StageArea {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl StageArea {
+    pub fn contains(&self, x: f64, y: f64) -> bool {
+        x >= self.x as f64
+            && y >= self.y as f64
+            && x < (self.x + self.width) as f64
+            && y < (self.y + self.height) as f64
+    }
+}
+
+/// Everything in a [`Player`] that belongs to one particular stage.
+struct StageState {
+    swf: Arc<SwfMovie>,
+    gc_arena: Rc<RefCell<GcArena>>,
+    is_playing: bool,
+    needs_render: bool,
+    frame_rate: f64,
+    forced_frame_rate: bool,
+    actions_since_timeout_check: u16,
+    frame_phase: FramePhase,
+    frame_accumulator: f64,
+    recent_run_frame_timings: VecDeque<f64>,
+    time_offset: u32,
+    input: InputManager,
+    mouse_in_stage: bool,
+    mouse_position: Point<Twips>,
+    mouse_wheel_remainder: f64,
+    right_click_consumed: bool,
+    mouse_cursor: MouseCursor,
+    mouse_cursor_needs_check: bool,
+    instance_counter: i32,
+    time_til_next_timer: Option<f64>,
+    clock: PlayerClock,
+    current_frame: Option<u16>,
+}
+
+impl StageState {
+    fn new(swf: Arc<SwfMovie>, gc_arena: Rc<RefCell<GcArena>>, is_playing: bool) -> Self {
+        Self {
+            swf,
+            gc_arena,
+            is_playing,
+            needs_render: true,
+            frame_rate: 12.0,
+            forced_frame_rate: false,
+            actions_since_timeout_check: 0,
+            frame_phase: Default::default(),
+            frame_accumulator: 0.0,
+            recent_run_frame_timings: VecDeque::with_capacity(10),
+            time_offset: 0,
+            input: Default::default(),
+            mouse_in_stage: false,
+            mouse_position: Point::ZERO,
+            mouse_wheel_remainder: 0.0,
+            right_click_consumed: false,
+            mouse_cursor: MouseCursor::Arrow,
+            mouse_cursor_needs_check: false,
+            instance_counter: 0,
+            time_til_next_timer: None,
+            clock: PlayerClock::new(),
+            current_frame: None,
+        }
+    }
+
+    /// Exchange this state with the one currently held by the player.
+    fn swap(&mut self, player: &mut Player) {
+        use std::mem::swap;
+
+        swap(&mut self.swf, &mut player.swf);
+        swap(&mut self.gc_arena, &mut player.gc_arena);
+        swap(&mut self.is_playing, &mut player.is_playing);
+        swap(&mut self.needs_render, &mut player.needs_render);
+        swap(&mut self.frame_rate, &mut player.frame_rate);
+        swap(&mut self.forced_frame_rate, &mut player.forced_frame_rate);
+        swap(
+            &mut self.actions_since_timeout_check,
+            &mut player.actions_since_timeout_check,
+        );
+        swap(&mut self.frame_phase, &mut player.frame_phase);
+        swap(&mut self.frame_accumulator, &mut player.frame_accumulator);
+        swap(
+            &mut self.recent_run_frame_timings,
+            &mut player.recent_run_frame_timings,
+        );
+        swap(&mut self.time_offset, &mut player.time_offset);
+        swap(&mut self.input, &mut player.input);
+        swap(&mut self.mouse_in_stage, &mut player.mouse_in_stage);
+        swap(&mut self.mouse_position, &mut player.mouse_position);
+        swap(
+            &mut self.mouse_wheel_remainder,
+            &mut player.mouse_wheel_remainder,
+        );
+        swap(
+            &mut self.right_click_consumed,
+            &mut player.right_click_consumed,
+        );
+        swap(&mut self.mouse_cursor, &mut player.mouse_cursor);
+        swap(
+            &mut self.mouse_cursor_needs_check,
+            &mut player.mouse_cursor_needs_check,
+        );
+        swap(&mut self.instance_counter, &mut player.instance_counter);
+        swap(
+            &mut self.time_til_next_timer,
+            &mut player.time_til_next_timer,
+        );
+        swap(&mut self.clock, &mut player.clock);
+        swap(&mut self.current_frame, &mut player.current_frame);
+    }
+}
+
+/// How the [`PlayerBuilder`] configured the player, kept so that every
+/// additional stage is set up just like the main one.
+pub(super) struct StageConfig {
+    pub(super) enumeration_order: EnumerationOrder,
+    pub(super) socket_framing: SocketFraming,
+    pub(super) socket_policy: SocketPolicy,
+    pub(super) external_interface_providers: Vec<Rc<dyn ExternalInterfaceProvider>>,
+    pub(super) fs_command_provider: Rc<dyn FsCommandProvider>,
+    pub(super) native_extensions: HashMap<String, Rc<dyn NativeExtension>>,
+    pub(super) web_view_provider: Option<Rc<dyn WebViewProvider>>,
+}
+
+impl StageConfig {
+    /// Create the GC root of a stage, sharing the providers with every other stage.
+    pub(super) fn create_gc_root<'gc>(
+        &self,
+        gc_context: &'gc gc_arena::Mutation<'gc>,
+        player_version: PlayerVersion,
+        fullscreen: bool,
+        fake_movie: Arc<SwfMovie>,
+    ) -> GcRoot<'gc> {
+        PlayerBuilder::create_gc_root(
+            gc_context,
+            player_version,
+            self.enumeration_order,
+            fullscreen,
+            fake_movie,
+            self.socket_framing,
+            self.socket_policy.clone(),
+            self.external_interface_providers
+                .iter()
+                .map(|provider| Box::new(provider.clone()) as Box<dyn ExternalInterfaceProvider>)
+                .collect(),
+            Box::new(self.fs_command_provider.clone()),
+            self.native_extensions
+                .iter()
+                .map(|(id, extension)| {
+                    (
+                        id.clone(),
+                        Box::new(extension.clone()) as Box<dyn NativeExtension>,
+                    )
+                })
+                .collect(),
+            self.web_view_provider
+                .clone()
+                .map(|provider| Box::new(provider) as Box<dyn WebViewProvider>),
+        )
+    }
+}
+
+struct ParkedStage {
+    id: StageId,
+    state: StageState,
+}
+
+/// Bookkeeping for the stages of a [`Player`].
+pub(super) struct Stages {
+    /// The stage whose state is currently held by the player.
+    active: StageId,
+
+    /// The state of every other stage.
+    parked: Vec<ParkedStage>,
+
+    /// The additional stages and their areas, in the order they're drawn.
+    areas: Vec<(StageId, StageArea)>,
+
+    /// Stages that have been removed, so that their pending futures can be dropped.
+    removed: Rc<RefCell<HashSet<StageId>>>,
+
+    config: StageConfig,
+
+    next_id: u32,
+}
+
+impl Stages {
+    pub(super) fn new(config: StageConfig) -> Self {
+        Self {
+            active: StageId::MAIN,
+            parked: Vec::new(),
+            areas: Vec::new(),
+            removed: Default::default(),
+            config,
+            next_id: 1,
+        }
+    }
+
+    /// Give stages added from now on this External Interface provider as well.
+    pub(super) fn add_external_interface(&mut self, provider: Rc<dyn ExternalInterfaceProvider>) {
+        self.config.external_interface_providers.push(provider);
+    }
+
+    pub(super) fn active(&self) -> StageId {
+        self.active
+    }
+
+    /// Wrap the navigator so that futures spawned through it run against the active stage.
+    pub(super) fn navigator<'a>(
+        &self,
+        navigator: &'a mut dyn NavigatorBackend,
+    ) -> StageNavigator<'a> {
+        StageNavigator {
+            navigator,
+            stage: self.active,
+            removed: self.removed.clone(),
+        }
+    }
+
+    pub(super) fn any_needs_render(&self) -> bool {
+        self.parked.iter().any(|parked| parked.state.needs_render)
+    }
+}
+
+/// The stage that the future being polled on this thread belongs to, if any.
+pub(super) fn polling_stage() -> Option<StageId> {
+    POLLING_STAGE.with(Cell::get)
+}
+
+/// A future spawned by an additional stage.
+///
+/// While it is polled, calls into the player are redirected to its stage.
+struct StageFuture {
+    future: OwnedFuture<(), Error>,
+    stage: StageId,
+    removed: Rc<RefCell<HashSet<StageId>>>,
+}
+
+impl Future for StageFuture {
+    type Output = Result<(), Error>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if self.removed.borrow().contains(&self.stage) {
+            return Poll::Ready(Ok(()));
+        }
+
+        let _polling = PollingStage::enter(self.stage);
+        self.future.as_mut().poll(cx)
+    }
+}
+
+/// Marks a stage as the one being polled, until dropped.
+///
+/// The previous stage is restored even if polling panics, so that a
+/// crashing future can't leave the player redirected to its stage.
+struct PollingStage(Option<StageId>);
+
+impl PollingStage {
+    fn enter(stage: StageId) -> Self {
+        Self(POLLING_STAGE.with(|polling| polling.replace(Some(stage))))
+    }
+}
+
+impl Drop for PollingStage {
+    fn drop(&mut self) {
+        POLLING_STAGE.with(|polling| polling.set(self.0));
+    }
+}
+
+/// A navigator that tags the futures it spawns with the stage that spawned them.
+pub(super) struct StageNavigator<'a> {
+    navigator: &'a mut dyn NavigatorBackend,
+    stage: StageId,
+    removed: Rc<RefCell<HashSet<StageId>>>,
+}
+
+impl NavigatorBackend for StageNavigator<'_> {
+    fn navigate_to_url(
+        &self,
+        url: &str,
+        target: &str,
+        vars_method: Option<(NavigationMethod, IndexMap<String, String>)>,
+    ) {
+        self.navigator.navigate_to_url(url, target, vars_method)
+    }
+
+    fn fetch(&self, request: Request) -> OwnedFuture<SuccessResponse, ErrorResponse> {
+        self.navigator.fetch(request)
+    }
+
+    fn fetch_stream(
+        &self,
+        request: Request,
+        body_sender: async_channel::Sender<BodyChunk>,
+    ) -> OwnedFuture<SuccessResponse, ErrorResponse> {
+        self.navigator.fetch_stream(request, body_sender)
+    }
+
+    fn resolve_url(&self, url: &str) -> Result<Url, ParseError> {
+        self.navigator.resolve_url(url)
+    }
+
+    fn air_directories(&self) -> Option<&AirDirectories> {
+        self.navigator.air_directories()
+    }
+
+    fn spawn_future(&mut self, future: OwnedFuture<(), Error>) {
+        self.navigator.spawn_future(Box::pin(StageFuture {
+            future,
+            stage: self.stage,
+            removed: self.removed.clone(),
+        }));
+    }
+
+    fn pre_process_url(&self, url: Url) -> Url {
+        self.navigator.pre_process_url(url)
+    }
+
+    fn connect_socket(
+        &mut self,
+        host: String,
+        port: u16,
+        require_policy_file: bool,
+        timeout: Duration,
+        handle: SocketHandle,
+        receiver: Receiver<Vec<u8>>,
+        sender: Sender<SocketAction>,
+    ) {
+        self.navigator.connect_socket(
+            host,
+            port,
+            require_policy_file,
+            timeout,
+            handle,
+            receiver,
+            sender,
+        )
+    }
+
+    fn fetch_socket_policy(
+        &self,
+        host: String,
+        port: u16,
+        timeout: Duration,
+    ) -> OwnedFuture<String, Error> {
+        self.navigator.fetch_socket_policy(host, port, timeout)
+    }
+
+    fn join_net_group(
+        &mut self,
+        address: SocketAddr,
+        handle: NetGroupHandle,
+        receiver: Receiver<Vec<u8>>,
+        sender: Sender<NetGroupAction>,
+    ) {
+        self.navigator
+            .join_net_group(address, handle, receiver, sender)
+    }
+}
+
+impl Player {
+    /// Add another stage to this player, showing `movie` in the given area of the viewport.
+    ///
+    /// The new stage shares this player's backends, but has its own root
+    /// movie, timers, input and ActionScript state. It is ticked and rendered
+    /// along with the main stage; input has to be routed to it with
+    /// [`Player::with_stage`].
+    pub fn add_stage(&mut self, movie: SwfMovie, area: StageArea) -> StageId {
+        let id = StageId(self.stages.next_id);
+        self.stages.next_id += 1;
+
+        let player_version = self.player_version;
+        let fake_movie = Arc::new(SwfMovie::empty(player_version.major));
+        let gc_arena = Rc::new(RefCell::new(GcArena::new(|gc_context| {
+            self.stages
+                .config
+                .create_gc_root(gc_context, player_version, false, fake_movie.clone())
+        })));
+
+        // New stages start out configured like the active one.
+        let (align, scale_mode, quality, letterbox) = self.mutate_with_update_context(|context| {
+            let stage = context.stage;
+            (
+                stage.align(),
+                stage.scale_mode(),
+                stage.quality(),
+                stage.letterbox(),
+            )
+        });
+
+        self.stages.parked.push(ParkedStage {
+            id,
+            state: StageState::new(fake_movie, gc_arena, self.is_playing),
+        });
+        self.stages.areas.push((id, area));
+
+        self.with_stage(id, |player| {
+            player.register_default_fonts();
+            player.mutate_with_update_context(|context| {
+                Avm2::load_player_globals(context).expect("Unable to load AVM2 globals");
+                let stage = context.stage;
+                stage.set_viewport_area(context.gc_context, Some(area));
+                stage.set_align(context, align);
+                stage.set_scale_mode(context, scale_mode);
+                stage.set_quality(context, quality);
+                stage.set_letterbox(context.gc_context, letterbox);
+                stage.post_instantiation(context, None, Instantiator::Movie, false);
+                stage.build_matrices(context);
+            });
+            player.gc_arena.borrow().mutate(|context, root| {
+                let call_stack = root.data.read().avm2.call_stack();
+                root.callstack.write(context).avm2 = Some(call_stack);
+            });
+            player.set_root_movie(movie);
+        });
+
+        id
+    }
+
+    /// Remove a stage added with [`Player::add_stage`].
+    ///
+    /// Returns `false` if there is no such stage, or if it is the main or the active stage.
+    pub fn remove_stage(&mut self, id: StageId) -> bool {
+        if id == StageId::MAIN || id == self.stages.active {
+            return false;
+        }
+        if self
+            .with_stage(id, |player| {
+                player.update(|context| context.stop_all_sounds())
+            })
+            .is_none()
+        {
+            return false;
+        }
+
+        self.stages.parked.retain(|parked| parked.id != id);
+        self.stages.areas.retain(|(area_id, _)| *area_id != id);
+        self.stages.removed.borrow_mut().insert(id);
+        true
+    }
+
+    /// All stages of this player, in the order they're drawn.
+    pub fn stage_ids(&self) -> Vec<StageId> {
+        std::iter::once(StageId::MAIN)
+            .chain(self.stages.areas.iter().map(|(id, _)| *id))
+            .collect()
+    }
+
+    /// The stage drawn at the given viewport position, for routing mouse input.
+    pub fn stage_at(&self, x: f64, y: f64) -> StageId {
+        self.stages
+            .areas
+            .iter()
+            .rev()
+            .find(|(_, area)| area.contains(x, y))
+            .map_or(StageId::MAIN, |(id, _)| *id)
+    }
+
+    /// Move or resize an additional stage.
+    pub fn set_stage_area(&mut self, id: StageId, area: StageArea) {
+        let Some(entry) = self
+            .stages
+            .areas
+            .iter_mut()
+            .find(|(area_id, _)| *area_id == id)
+        else {
+            return;
+        };
+        entry.1 = area;
+        self.with_stage(id, |player| {
+            player.mutate_with_update_context(|context| {
+                context
+                    .stage
+                    .set_viewport_area(context.gc_context, Some(area));
+                context.stage.build_matrices(context);
+            });
+            player.needs_render = true;
+        });
+    }
+
+    /// Run `f` with the given stage as the active one.
+    ///
+    /// Every method of the player acts on the active stage, so this is how
+    /// events are sent to, and properties read from, additional stages.
+    /// Returns `None` if there is no such stage.
+    pub fn with_stage<R>(&mut self, id: StageId, f: impl FnOnce(&mut Self) -> R) -> Option<R> {
+        if id == self.stages.active {
+            return Some(f(self));
+        }
+
+        let index = self
+            .stages
+            .parked
+            .iter()
+            .position(|parked| parked.id == id)?;
+        let mut parked = self.stages.parked.remove(index);
+        parked.state.swap(self);
+        parked.id = std::mem::replace(&mut self.stages.active, id);
+
+        let mut active = ActiveStage {
+            player: self,
+            previous: Some(parked),
+            index,
+        };
+        Some(f(&mut *active.player))
+    }
+}
+
+/// A stage made active by [`Player::with_stage`].
+///
+/// Dropping it parks the stage again and reactivates the previous one, even
+/// if running `f` panicked, so that the player is never left with the state
+/// of the wrong stage.
+struct ActiveStage<'a> {
+    player: &'a mut Player,
+
+    /// The state of the stage that was active before.
+    previous: Option<ParkedStage>,
+
+    /// Where the stage was in the list of parked stages.
+    index: usize,
+}
+
+impl Drop for ActiveStage<'_> {
+    fn drop(&mut self) {
+        let Some(mut parked) = self.previous.take() else {
+            return;
+        };
+        parked.state.swap(self.player);
+        parked.id = std::mem::replace(&mut self.player.stages.active, parked.id);
+        let index = self.index.min(self.player.stages.parked.len());
+        self.player.stages.parked.insert(index, parked);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{KeyCode, PlayerEvent};
+    use crate::socket::SocketPolicyOverride;
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+    use std::sync::Mutex;
+
+    const AREA: StageArea = StageArea {
+        x: 100,
+        y: 50,
+        width: 200,
+        height: 100,
+    };
+
+    struct CountingFsCommands(Rc<Cell<u32>>);
+
+    impl FsCommandProvider for CountingFsCommands {
+        fn on_fs_command(&self, _command: &str, _args: &str) -> bool {
+            self.0.set(self.0.get() + 1);
+            true
+        }
+    }
+
+    fn build(builder: PlayerBuilder) -> (Arc<Mutex<Player>>, StageId) {
+        let player = builder
+            .with_movie(SwfMovie::empty(32))
+            .with_autoplay(true)
+            .build();
+        let stage = player.lock().unwrap().add_stage(SwfMovie::empty(32), AREA);
+        (player, stage)
+    }
+
+    #[test]
+    fn stages_keep_the_builders_configuration() {
+        let policy = SocketPolicy {
+            require_policy_files: true,
+            overrides: vec![SocketPolicyOverride {
+                host: "example.com".to_string(),
+                port: None,
+                require_policy_file: false,
+            }],
+        };
+        let fs_commands = Rc::new(Cell::new(0));
+        let (player, stage) = build(
+            PlayerBuilder::new()
+                .with_socket_policy(policy.clone())
+                .with_fs_commands(Box::new(CountingFsCommands(fs_commands.clone()))),
+        );
+        let mut player = player.lock().unwrap();
+
+        for id in [StageId::MAIN, stage] {
+            player.with_stage(id, |player| {
+                player.mutate_with_update_context(|context| {
+                    assert_eq!(context.sockets.policy(), &policy);
+                    assert!(context.external_interface.invoke_fs_command("quit", ""));
+                });
+            });
+        }
+        assert_eq!(fs_commands.get(), 2);
+    }
+
+    #[test]
+    fn ticking_runs_every_stage() {
+        let (player, stage) = build(PlayerBuilder::new());
+        let mut player = player.lock().unwrap();
+
+        player.tick(1000.0);
+        for id in [StageId::MAIN, stage] {
+            let now = player.with_stage(id, |player| player.clock.now()).unwrap();
+            assert!(now >= 1000.0, "stage {id:?} is at {now}");
+        }
+    }
+
+    #[test]
+    fn rendering_draws_every_stage() {
+        let (player, stage) = build(PlayerBuilder::new());
+        let mut player = player.lock().unwrap();
+
+        assert!(player
+            .with_stage(stage, |player| player.needs_render)
+            .unwrap());
+        player.render();
+        assert!(!player.needs_render());
+    }
+
+    #[test]
+    fn input_goes_to_the_stage_it_is_routed_to() {
+        let (player, stage) = build(PlayerBuilder::new());
+        let mut player = player.lock().unwrap();
+
+        assert_eq!(player.stage_at(150.0, 100.0), stage);
+        assert_eq!(player.stage_at(50.0, 100.0), StageId::MAIN);
+
+        let event = PlayerEvent::KeyDown {
+            key_code: KeyCode::A,
+            key_char: Some('a'),
+        };
+        player.with_stage(stage, |player| player.handle_event(event));
+        assert!(!player.input.is_key_down(KeyCode::A));
+        assert!(player
+            .with_stage(stage, |player| player.input.is_key_down(KeyCode::A))
+            .unwrap());
+    }
+
+    #[test]
+    fn removed_stages_are_gone() {
+        let (player, stage) = build(PlayerBuilder::new());
+        let mut player = player.lock().unwrap();
+
+        assert!(!player.remove_stage(StageId::MAIN));
+        assert!(player.remove_stage(stage));
+        assert!(!player.remove_stage(stage));
+        assert_eq!(player.stage_ids(), vec![StageId::MAIN]);
+        assert!(player.with_stage(stage, |_| ()).is_none());
+        player.tick(100.0);
+        player.render();
+    }
+
+    #[test]
+    fn panicking_on_a_stage_reactivates_the_previous_one() {
+        let (player, stage) = build(PlayerBuilder::new());
+        let mut player = player.lock().unwrap_or_else(|e| e.into_inner());
+
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            player.with_stage(stage, |_| panic!("stage crashed"));
+        }));
+        assert!(result.is_err());
+        assert_eq!(player.stages.active(), StageId::MAIN);
+        assert!(player.with_stage(stage, |_| ()).is_some());
+    }
+}
@@ -0,0 +1,168 @@
+//! Emulating the behavior of particular Flash Player releases.
+//!
+//! Some content checks which player it runs in and takes different code paths, or only works
+//! because of how an older player behaved. A `PlayerVersion` is the release being emulated, and
+//! knows which of those differences apply to it.
+
+use crate::avm2::bytearray::ObjectEncoding;
+use gc_arena::Collect;
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+/// A Flash Player release, such as `10.3` or `32.0`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Collect)]
+#[collect(require_static)]
+pub struct PlayerVersion {
+    pub major: u8,
+    pub minor: u8,
+}
+
+/// The order that `for..in` visits the dynamic properties of AVM2 objects in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Collect)]
+#[collect(require_static)]
+pub enum EnumerationOrder {
+    /// The order the properties were added in.
+    Insertion,
+
+    /// The order of the hashtables Flash Player keeps properties in, which puts properties
+    /// named by small integers in numeric order. Other names can't be placed exactly where
+    /// Flash Player would place them, as it hashes them by their address in memory.
+    Hash,
+}
+
+impl EnumerationOrder {
+    /// Flash Player's order when emulating a particular release, as content made for it may
+    /// depend on that order. Otherwise, the more predictable order of insertion.
+    pub fn for_player_version(player_version: Option<PlayerVersion>) -> Self {
+        if player_version.is_some() {
+            Self::Hash
+        } else {
+            Self::Insertion
+        }
+    }
+}
+
+impl PlayerVersion {
+    pub const fn new(major: u8, minor: u8) -> Self {
+        Self { major, minor }
+    }
+
+    /// The encoding that `ByteArray` and `Socket` serialize objects with by default.
+    ///
+    /// AMF3 was introduced along with ActionScript 3 in Flash Player 9.
+    pub fn default_object_encoding(self) -> ObjectEncoding {
+        if self >= Self::new(9, 0) {
+            ObjectEncoding::Amf3
+        } else {
+            ObjectEncoding::Amf0
+        }
+    }
+
+    /// Whether `rightMouseDown`, `middleClick` and the other events of the secondary mouse
+    /// buttons are dispatched, which also lets content replace the context menu.
+    pub fn has_secondary_mouse_events(self) -> bool {
+        self >= Self::new(11, 2)
+    }
+
+    /// The version as reported by `Capabilities.version`, after the platform.
+    pub fn capabilities_version(self) -> String {
+        format!("{},{},0,0", self.major, self.minor)
+    }
+}
+
+impl From<u8> for PlayerVersion {
+    fn from(major: u8) -> Self {
+        Self::new(major, 0)
+    }
+}
+
+impl fmt::Display for PlayerVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+impl FromStr for PlayerVersion {
+    type Err = String;
+
+    /// Parses `11`, `11.2` or `11,2,202,235`. Build numbers are ignored.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.trim().split(['.', ',']);
+        let mut next = |required| match parts.next() {
+            Some(part) => part
+                .trim()
+                .parse::<u8>()
+                .map_err(|_| format!("invalid player version '{s}'")),
+            None if required => Err(format!("invalid player version '{s}'")),
+            None => Ok(0),
+        };
+        Ok(Self::new(next(true)?, next(false)?))
+    }
+}
+
+impl Serialize for PlayerVersion {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for PlayerVersion {
+    /// Accepts either a major version number, such as `32`, or a string such as `"11.2"`.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct PlayerVersionVisitor;
+
+        impl Visitor<'_> for PlayerVersionVisitor {
+            type Value = PlayerVersion;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a player version such as 32 or \"11.2\"")
+            }
+
+            fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+                u8::try_from(v)
+                    .map(PlayerVersion::from)
+                    .map_err(|_| E::invalid_value(de::Unexpected::Unsigned(v), &self))
+            }
+
+            fn visit_i64<E: de::Error>(self, v: i64) -> Result<Self::Value, E> {
+                u8::try_from(v)
+                    .map(PlayerVersion::from)
+                    .map_err(|_| E::invalid_value(de::Unexpected::Signed(v), &self))
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                v.parse().map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_any(PlayerVersionVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PlayerVersion;
+    use crate::avm2::bytearray::ObjectEncoding;
+
+    #[test]
+    fn player_version() {
+        assert_eq!("32".parse(), Ok(PlayerVersion::new(32, 0)));
+        assert_eq!("11.2".parse(), Ok(PlayerVersion::new(11, 2)));
+        assert_eq!("10,3,181,14".parse(), Ok(PlayerVersion::new(10, 3)));
+        assert!("eleven".parse::<PlayerVersion>().is_err());
+        assert_eq!(PlayerVersion::new(10, 3).to_string(), "10.3");
+
+        assert_eq!(
+            PlayerVersion::new(8, 0).default_object_encoding(),
+            ObjectEncoding::Amf0
+        );
+        assert_eq!(
+            PlayerVersion::new(10, 3).default_object_encoding(),
+            ObjectEncoding::Amf3
+        );
+        assert!(!PlayerVersion::new(10, 3).has_secondary_mouse_events());
+        assert!(PlayerVersion::new(32, 0).has_secondary_mouse_events());
+    }
+}
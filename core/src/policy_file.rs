@@ -0,0 +1,430 @@
+//! Policy files loaded with `Security.loadPolicyFile`.
+
+use crate::backend::navigator::Request;
+use crate::compatibility_rules::domain_matches;
+use crate::context::UpdateContext;
+use crate::tag_utils::SwfMovie;
+use async_channel::{Receiver as AsyncReceiver, Sender as AsyncSender};
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use std::time::Duration;
+use url::{Origin, Url};
+
+/// The largest policy file accepted, as in Flash.
+pub const MAX_POLICY_FILE_SIZE: usize = 20 * 1024;
+
+/// How long to wait for a socket policy file before giving up on it.
+const POLICY_TIMEOUT: Duration = Duration::from_secs(3);
+
+enum PolicyFileState {
+    Loading,
+    Loaded(String),
+    Failed,
+}
+
+/// A socket policy file that content asked for at a non-default port.
+struct SocketPolicyFile {
+    host: String,
+    port: u16,
+    state: PolicyFileState,
+}
+
+/// A URL policy file that content asked for.
+struct UrlPolicyFile {
+    url: Url,
+
+    /// Closed once the policy file has finished loading, successfully or not.
+    loaded: AsyncReceiver<()>,
+}
+
+/// Keeps track of the policy files content has loaded.
+///
+/// Socket connections to a host wait for its policy files to finish loading,
+/// after which any of them may allow the connection. Otherwise, the navigator
+/// falls back to asking the master policy port and the port being connected to.
+///
+/// Likewise, loads from a server wait for the URL policy files requested from it.
+#[derive(Default)]
+pub struct PolicyManager {
+    socket_policy_files: Vec<SocketPolicyFile>,
+    url_policy_files: Vec<UrlPolicyFile>,
+}
+
+impl PolicyManager {
+    /// Start loading the socket policy file at `host:port`.
+    ///
+    /// Returns `false` if it has already been asked for.
+    pub fn start_loading(&mut self, host: &str, port: u16) -> bool {
+        if self
+            .socket_policy_files
+            .iter()
+            .any(|file| file.port == port && file.host.eq_ignore_ascii_case(host))
+        {
+            return false;
+        }
+        self.socket_policy_files.push(SocketPolicyFile {
+            host: host.to_string(),
+            port,
+            state: PolicyFileState::Loading,
+        });
+        true
+    }
+
+    pub fn finish_loading(&mut self, host: &str, port: u16, policy: Option<String>) {
+        if let Some(file) = self
+            .socket_policy_files
+            .iter_mut()
+            .find(|file| file.port == port && file.host.eq_ignore_ascii_case(host))
+        {
+            file.state = match policy {
+                Some(policy) => PolicyFileState::Loaded(policy),
+                None => PolicyFileState::Failed,
+            };
+        }
+    }
+
+    /// Whether a policy file for `host` is still loading, which connections to it must wait for.
+    pub fn is_loading(&self, host: &str) -> bool {
+        self.socket_policy_files.iter().any(|file| {
+            matches!(file.state, PolicyFileState::Loading) && file.host.eq_ignore_ascii_case(host)
+        })
+    }
+
+    /// The port of a loaded policy file from `host` that allows content from `domain` to
+    /// connect to `port`, if any.
+    pub fn allowing_policy_port(&self, host: &str, domain: Option<&str>, port: u16) -> Option<u16> {
+        self.socket_policy_files
+            .iter()
+            .find(|file| match &file.state {
+                PolicyFileState::Loaded(policy) => {
+                    file.host.eq_ignore_ascii_case(host) && policy_allows_port(policy, domain, port)
+                }
+                _ => false,
+            })
+            .map(|file| file.port)
+    }
+
+    /// Start loading the URL policy file at `url`.
+    ///
+    /// Returns a sender to drop once it has loaded, or `None` if it has already been asked for.
+    pub fn start_loading_url(&mut self, url: &Url) -> Option<AsyncSender<()>> {
+        if self.url_policy_files.iter().any(|file| file.url == *url) {
+            return None;
+        }
+        let (sender, loaded) = async_channel::bounded(1);
+        self.url_policy_files.push(UrlPolicyFile {
+            url: url.clone(),
+            loaded,
+        });
+        Some(sender)
+    }
+
+    /// The URL policy files still loading from the server `url` is on, which loads from it must wait for.
+    ///
+    /// Each of them finishes loading once its receiver is closed.
+    pub fn url_policy_files_loading(&self, url: &Url) -> Vec<AsyncReceiver<()>> {
+        let origin = url.origin();
+        self.url_policy_files
+            .iter()
+            .filter(|file| !file.loaded.is_closed() && same_origin(&file.url.origin(), &origin))
+            .map(|file| file.loaded.clone())
+            .collect()
+    }
+}
+
+/// The domain that socket policy files have to allow for `movie` to connect, or `None` for
+/// local content.
+pub fn content_domain(movie: &SwfMovie) -> Option<String> {
+    let url = Url::parse(movie.url()).ok()?;
+    url.host_str().map(str::to_string)
+}
+
+fn same_origin(a: &Origin, b: &Origin) -> bool {
+    a.is_tuple() && a == b
+}
+
+/// Load a policy file, as `Security.loadPolicyFile` does.
+///
+/// `xmlsocket://host:port` URLs name socket policy files, which sockets connecting to
+/// that host wait for. Any other URL names a URL policy file, which later loads from
+/// the same server wait for.
+pub fn load_policy_file(context: &mut UpdateContext<'_, '_>, url: &str) {
+    let url = match context.navigator.resolve_url(url) {
+        Ok(url) => url,
+        Err(e) => {
+            tracing::warn!("Invalid policy file URL {url:?}: {e}");
+            return;
+        }
+    };
+
+    if url.scheme() == "xmlsocket" {
+        let (Some(host), Some(port)) = (url.host_str(), url.port()) else {
+            tracing::warn!("Socket policy file URL {url} needs a host and a port");
+            return;
+        };
+        let host = host.to_string();
+        if !context
+            .sockets
+            .policy_files_mut()
+            .start_loading(&host, port)
+        {
+            return;
+        }
+
+        tracing::info!("Loading socket policy file from {host}:{port}");
+        let player = context.player.clone();
+        let fetch = context
+            .navigator
+            .fetch_socket_policy(host.clone(), port, POLICY_TIMEOUT);
+        context.navigator.spawn_future(Box::pin(async move {
+            let policy = match fetch.await {
+                Ok(policy) => Some(policy),
+                Err(e) => {
+                    tracing::warn!("Couldn't load socket policy file from {host}:{port}: {e}");
+                    None
+                }
+            };
+            let player = player
+                .upgrade()
+                .expect("Could not upgrade weak reference to player");
+            player.lock().unwrap().update(|context| {
+                context
+                    .sockets
+                    .policy_file_loaded(&mut *context.navigator, &host, port, policy);
+            });
+            Ok(())
+        }));
+    } else {
+        let Some(loaded) = context.sockets.policy_files_mut().start_loading_url(&url) else {
+            return;
+        };
+
+        let fetch = context.navigator.fetch(Request::get(url.to_string()));
+        context.navigator.spawn_future(Box::pin(async move {
+            match fetch.await {
+                Ok(_) => tracing::info!("Loaded policy file {url}"),
+                Err(response) => {
+                    tracing::warn!("Couldn't load policy file {url}: {}", response.error)
+                }
+            }
+            drop(loaded);
+            Ok(())
+        }));
+    }
+}
+
+/// Whether any `<allow-access-from>` of `policy` lets content from `domain` connect to `port`.
+///
+/// `domain` is the host the content was loaded from, or `None` for local content, which only
+/// entries for every domain (`*`) apply to. Ports may be listed separated by commas, as ranges
+/// like `1000-2000`, or as `*` for all. Malformed policy files allow nothing.
+pub fn policy_allows_port(policy: &str, domain: Option<&str>, port: u16) -> bool {
+    allow_access_from(policy).iter().any(|entry| {
+        let domain_allowed = match domain {
+            Some(domain) => domain_matches(&entry.domain, domain),
+            None => entry.domain == "*",
+        };
+        domain_allowed && ports_include(&entry.to_ports, port)
+    })
+}
+
+/// An `<allow-access-from>` entry of a policy file.
+struct AllowAccessFrom {
+    domain: String,
+    to_ports: String,
+}
+
+/// The `<allow-access-from>` entries directly inside the `<cross-domain-policy>` root of
+/// `policy`, or none at all if it isn't well-formed.
+fn allow_access_from(policy: &str) -> Vec<AllowAccessFrom> {
+    let mut reader = Reader::from_str(policy);
+    let mut entries = vec![];
+    let mut depth: usize = 0;
+    let mut in_policy = false;
+    loop {
+        let (element, is_empty) = match reader.read_event() {
+            Ok(Event::Start(element)) => (element, false),
+            Ok(Event::Empty(element)) => (element, true),
+            Ok(Event::End(_)) => {
+                depth = usize::saturating_sub(depth, 1);
+                continue;
+            }
+            Ok(Event::Eof) => return entries,
+            Ok(_) => continue,
+            Err(e) => {
+                tracing::warn!("Malformed policy file: {e}");
+                return vec![];
+            }
+        };
+
+        let name = element.name();
+        if depth == 0 {
+            in_policy = name.as_ref() == b"cross-domain-policy";
+        } else if depth == 1 && in_policy && name.as_ref() == b"allow-access-from" {
+            let mut domain = None;
+            let mut to_ports = None;
+            for attribute in element.attributes() {
+                let Ok(attribute) = attribute else {
+                    return vec![];
+                };
+                let Ok(value) = attribute.unescape_value() else {
+                    return vec![];
+                };
+                match attribute.key.as_ref() {
+                    b"domain" => domain = Some(value.into_owned()),
+                    b"to-ports" => to_ports = Some(value.into_owned()),
+                    _ => {}
+                }
+            }
+            if let (Some(domain), Some(to_ports)) = (domain, to_ports) {
+                entries.push(AllowAccessFrom { domain, to_ports });
+            }
+        }
+        if !is_empty {
+            depth += 1;
+        }
+    }
+}
+
+fn ports_include(ports: &str, port: u16) -> bool {
+    ports
+        .split(',')
+        .map(str::trim)
+        .any(|ports| match ports.split_once('-') {
+            Some((from, to)) => match (from.trim().parse::<u16>(), to.trim().parse::<u16>()) {
+                (Ok(from), Ok(to)) => (from..=to).contains(&port),
+                _ => false,
+            },
+            None => ports == "*" || ports.parse() == Ok(port),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{policy_allows_port, PolicyManager};
+    use url::Url;
+
+    #[test]
+    fn policy_ports() {
+        let policy = r#"<?xml version="1.0"?>
+            <!DOCTYPE cross-domain-policy SYSTEM "/xml/dtds/cross-domain-policy.dtd">
+            <cross-domain-policy>
+                <site-control permitted-cross-domain-policies="master-only"/>
+                <allow-access-from domain="*" to-ports="80, 1000-2000 ,3000" />
+            </cross-domain-policy>"#;
+        let allows = |port| policy_allows_port(policy, Some("example.com"), port);
+        assert!(allows(80));
+        assert!(allows(1000));
+        assert!(allows(1500));
+        assert!(allows(2000));
+        assert!(allows(3000));
+        assert!(!allows(81));
+        assert!(!allows(2001));
+
+        let every_port = r#"<cross-domain-policy><allow-access-from domain="*" to-ports='*'/></cross-domain-policy>"#;
+        assert!(policy_allows_port(every_port, Some("example.com"), 12345));
+        assert!(policy_allows_port(every_port, None, 12345));
+    }
+
+    #[test]
+    fn policy_domains() {
+        let policy = r#"<cross-domain-policy>
+                <allow-access-from domain="*.example.com" to-ports="5000"/>
+                <allow-access-from domain="example.net" to-ports="6000"/>
+            </cross-domain-policy>"#;
+        assert!(policy_allows_port(policy, Some("example.com"), 5000));
+        assert!(policy_allows_port(policy, Some("www.example.com"), 5000));
+        assert!(!policy_allows_port(policy, Some("example.org"), 5000));
+        assert!(!policy_allows_port(policy, Some("example.net"), 5000));
+        assert!(policy_allows_port(policy, Some("EXAMPLE.NET"), 6000));
+        assert!(!policy_allows_port(policy, Some("www.example.net"), 6000));
+        // Local content is only allowed by entries for every domain.
+        assert!(!policy_allows_port(policy, None, 5000));
+    }
+
+    #[test]
+    fn policy_structure() {
+        // Only whole attributes count, not ones that merely end with the right name.
+        let policy = r#"<cross-domain-policy><allow-access-from domain="*" secure="false" not-to-ports="5000"/></cross-domain-policy>"#;
+        assert!(!policy_allows_port(policy, Some("example.com"), 5000));
+
+        // Entries have to be directly inside the root element.
+        let policy = r#"<cross-domain-policy><other><allow-access-from domain="*" to-ports="5000"/></other></cross-domain-policy>"#;
+        assert!(!policy_allows_port(policy, Some("example.com"), 5000));
+        let policy = r#"<other><allow-access-from domain="*" to-ports="5000"/></other>"#;
+        assert!(!policy_allows_port(policy, Some("example.com"), 5000));
+
+        // Nor do entries in comments.
+        let policy = r#"<cross-domain-policy><!-- <allow-access-from domain="*" to-ports="5000"/> --></cross-domain-policy>"#;
+        assert!(!policy_allows_port(policy, Some("example.com"), 5000));
+
+        // Malformed files allow nothing.
+        let policy =
+            r#"<cross-domain-policy><allow-access-from domain="*" to-ports="5000"/></wrong>"#;
+        assert!(!policy_allows_port(policy, Some("example.com"), 5000));
+    }
+
+    #[test]
+    fn socket_policy_files() {
+        let mut manager = PolicyManager::default();
+        assert!(!manager.is_loading("example.com"));
+        assert!(manager.start_loading("example.com", 8430));
+        assert!(!manager.start_loading("Example.com", 8430));
+        assert!(manager.start_loading("example.com", 8431));
+        assert!(manager.is_loading("EXAMPLE.COM"));
+        assert!(!manager.is_loading("example.org"));
+
+        manager.finish_loading("example.com", 8430, None);
+        assert!(manager.is_loading("example.com"));
+        assert_eq!(
+            manager.allowing_policy_port("example.com", Some("example.net"), 5000),
+            None
+        );
+
+        manager.finish_loading(
+            "example.com",
+            8431,
+            Some(
+                r#"<cross-domain-policy><allow-access-from domain="*" to-ports="4000-5000,6000"/></cross-domain-policy>"#
+                    .to_string(),
+            ),
+        );
+        assert!(!manager.is_loading("example.com"));
+        assert_eq!(
+            manager.allowing_policy_port("example.com", Some("example.net"), 5000),
+            Some(8431)
+        );
+        assert_eq!(
+            manager.allowing_policy_port("example.com", Some("example.net"), 6000),
+            Some(8431)
+        );
+        assert_eq!(
+            manager.allowing_policy_port("example.com", Some("example.net"), 5001),
+            None
+        );
+        assert_eq!(
+            manager.allowing_policy_port("example.org", Some("example.net"), 5000),
+            None
+        );
+    }
+
+    #[test]
+    fn url_policy_files() {
+        let mut manager = PolicyManager::default();
+        let policy_url = Url::parse("http://example.com/data/crossdomain.xml").unwrap();
+        let loaded = manager.start_loading_url(&policy_url).unwrap();
+        assert!(manager.start_loading_url(&policy_url).is_none());
+
+        let same_server = Url::parse("http://example.com/image.png").unwrap();
+        let other_port = Url::parse("http://example.com:8080/image.png").unwrap();
+        let other_host = Url::parse("http://example.org/image.png").unwrap();
+        assert_eq!(manager.url_policy_files_loading(&same_server).len(), 1);
+        assert!(manager.url_policy_files_loading(&other_port).is_empty());
+        assert!(manager.url_policy_files_loading(&other_host).is_empty());
+
+        let waiting = manager.url_policy_files_loading(&same_server);
+        drop(loaded);
+        assert!(waiting[0].is_closed());
+        assert!(manager.url_policy_files_loading(&same_server).is_empty());
+    }
+}
@@ -8,12 +8,15 @@ use crate::{
         TObject as Avm2TObject,
     },
     backend::navigator::NavigatorBackend,
+    compatibility_rules::domain_matches,
     context::UpdateContext,
+    policy_file::PolicyManager,
     string::AvmString,
 };
-use async_channel::{unbounded, Sender as AsyncSender};
+use async_channel::{unbounded, Receiver as AsyncReceiver, Sender as AsyncSender};
 use gc_arena::Collect;
 use generational_arena::{Arena, Index};
+use serde::{Deserialize, Serialize};
 use std::{
     cell::RefCell,
     sync::mpsc::{channel, Receiver, Sender},
@@ -29,22 +32,166 @@ enum SocketKind<'gc> {
     Avm1(Avm1Object<'gc>),
 }
 
+/// How incoming data is split into messages before being handed to the VM.
+///
+/// Messages are delivered whole, including their terminator or length prefix, so that
+/// content reading them sees the exact same bytes - just in fewer, larger events.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum SocketFraming {
+    /// Data is delivered as soon as it arrives.
+    #[default]
+    None,
+
+    /// Messages end with a null byte, as used by `XMLSocket`.
+    NullTerminated,
+
+    /// Messages start with their length as a big-endian 16-bit integer.
+    LengthPrefixed16,
+
+    /// Messages start with their length as a big-endian 32-bit integer.
+    LengthPrefixed32,
+}
+
+/// The longest message a framed socket buffers, including its length prefix or terminator.
+///
+/// A peer sending, or announcing, a longer message gets disconnected instead of making the
+/// player buffer it.
+pub const MAX_MESSAGE_LEN: usize = 16 * 1024 * 1024;
+
+impl SocketFraming {
+    /// Whether the partial message left in `buffer` by `take_messages` is, or is announced
+    /// to be, longer than `MAX_MESSAGE_LEN`.
+    fn exceeds_max_len(self, buffer: &[u8]) -> bool {
+        let length = match self {
+            // Data isn't buffered, and 16-bit prefixes can't announce anything too long.
+            Self::None | Self::LengthPrefixed16 => return false,
+            Self::NullTerminated => buffer.len(),
+            Self::LengthPrefixed32 => match buffer.get(..4) {
+                Some(prefix) => {
+                    4 + u32::from_be_bytes([prefix[0], prefix[1], prefix[2], prefix[3]]) as usize
+                }
+                None => 0,
+            },
+        };
+        length > MAX_MESSAGE_LEN
+    }
+
+    /// Moves every complete message out of `buffer`, leaving any partial one behind.
+    fn take_messages(self, buffer: &mut Vec<u8>) -> Vec<Vec<u8>> {
+        let mut messages = vec![];
+        loop {
+            let length = match self {
+                Self::None => Some(buffer.len()).filter(|&length| length > 0),
+                Self::NullTerminated => buffer.iter().position(|&b| b == 0).map(|i| i + 1),
+                Self::LengthPrefixed16 => buffer
+                    .get(..2)
+                    .map(|prefix| 2 + u16::from_be_bytes([prefix[0], prefix[1]]) as usize),
+                Self::LengthPrefixed32 => buffer.get(..4).map(|prefix| {
+                    4 + u32::from_be_bytes([prefix[0], prefix[1], prefix[2], prefix[3]]) as usize
+                }),
+            };
+            match length {
+                Some(length) if length <= buffer.len() => {
+                    messages.push(buffer.drain(..length).collect())
+                }
+                _ => return messages,
+            }
+        }
+    }
+}
+
+/// Decides whether a host must serve a socket policy file before a socket may connect to it.
+///
+/// Flash asks port 843, and then the port being connected to, for a policy file allowing the
+/// connection. Archived servers often no longer serve one, so this can be turned off as a whole
+/// or for individual hosts.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SocketPolicy {
+    /// Whether hosts without an override need a policy file.
+    pub require_policy_files: bool,
+
+    /// Exceptions for particular hosts. The first matching one is used.
+    pub overrides: Vec<SocketPolicyOverride>,
+}
+
+/// Whether a particular host needs a socket policy file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SocketPolicyOverride {
+    /// The host, which may start with `*.` to also match its subdomains.
+    pub host: String,
+
+    /// The port, or every port of the host if not set.
+    #[serde(default)]
+    pub port: Option<u16>,
+
+    pub require_policy_file: bool,
+}
+
+impl SocketPolicy {
+    /// Whether a connection to `host:port` needs a policy file, logging why.
+    pub fn requires_policy_file(&self, host: &str, port: u16) -> bool {
+        let matching = self.overrides.iter().find(|o| {
+            domain_matches(&o.host, host) && o.port.map_or(true, |o_port| o_port == port)
+        });
+        match matching {
+            Some(o) => {
+                tracing::info!(
+                    "Socket policy file for {host}:{port} is {} by the override for {}{}",
+                    if o.require_policy_file {
+                        "required"
+                    } else {
+                        "bypassed"
+                    },
+                    o.host,
+                    o.port.map(|port| format!(":{port}")).unwrap_or_default(),
+                );
+                o.require_policy_file
+            }
+            None => {
+                if self.require_policy_files {
+                    tracing::info!("Socket policy file for {host}:{port} is required");
+                }
+                self.require_policy_files
+            }
+        }
+    }
+}
+
 #[derive(Collect)]
 #[collect(no_drop)]
 struct Socket<'gc> {
     target: SocketKind<'gc>,
     sender: RefCell<AsyncSender<Vec<u8>>>,
+    #[collect(require_static)]
+    framing: SocketFraming,
+    /// Received data that doesn't make up a whole message yet.
+    pending: RefCell<Vec<u8>>,
 }
 
 impl<'gc> Socket<'gc> {
-    fn new(target: SocketKind<'gc>, sender: AsyncSender<Vec<u8>>) -> Self {
+    fn new(target: SocketKind<'gc>, sender: AsyncSender<Vec<u8>>, framing: SocketFraming) -> Self {
         Self {
             target,
             sender: RefCell::new(sender),
+            framing,
+            pending: RefCell::new(vec![]),
         }
     }
 }
 
+/// A connection waiting for the socket policy files of its host to load.
+struct DeferredConnection {
+    handle: SocketHandle,
+    host: String,
+    port: u16,
+    /// The domain of the content connecting, which policy files have to allow.
+    domain: Option<String>,
+    timeout: Duration,
+    receiver: AsyncReceiver<Vec<u8>>,
+}
+
 #[derive(Debug)]
 pub enum ConnectionState {
     Connected,
@@ -65,6 +212,17 @@ pub struct Sockets<'gc> {
 
     receiver: Receiver<SocketAction>,
     sender: Sender<SocketAction>,
+
+    /// The framing used by AVM2 `Socket`s that don't ask for a specific one.
+    default_framing: SocketFraming,
+
+    policy: SocketPolicy,
+
+    /// The policy files loaded with `Security.loadPolicyFile`.
+    policy_files: PolicyManager,
+
+    /// Connections made while a policy file for their host was loading.
+    deferred: Vec<DeferredConnection>,
 }
 
 unsafe impl<'gc> Collect for Sockets<'gc> {
@@ -83,29 +241,56 @@ impl<'gc> Sockets<'gc> {
             sockets: Arena::new(),
             receiver,
             sender,
+            default_framing: SocketFraming::None,
+            policy: SocketPolicy::default(),
+            policy_files: PolicyManager::default(),
+            deferred: vec![],
         }
     }
 
+    pub fn set_default_framing(&mut self, framing: SocketFraming) {
+        self.default_framing = framing;
+    }
+
+    pub fn set_policy(&mut self, policy: SocketPolicy) {
+        self.policy = policy;
+    }
+
+    pub fn policy(&self) -> &SocketPolicy {
+        &self.policy
+    }
+
+    pub fn policy_files(&self) -> &PolicyManager {
+        &self.policy_files
+    }
+
+    pub fn policy_files_mut(&mut self) -> &mut PolicyManager {
+        &mut self.policy_files
+    }
+
     pub fn connect_avm2(
         &mut self,
         backend: &mut dyn NavigatorBackend,
         target: SocketObject<'gc>,
         host: String,
         port: u16,
+        domain: Option<String>,
     ) {
         let (sender, receiver) = unbounded();
 
-        let socket = Socket::new(SocketKind::Avm2(target), sender);
+        let framing = target.framing().unwrap_or(self.default_framing);
+        let socket = Socket::new(SocketKind::Avm2(target), sender, framing);
         let handle = self.sockets.insert(socket);
-
-        // NOTE: This call will send SocketAction::Connect to sender with connection status.
-        backend.connect_socket(
-            host,
-            port,
-            Duration::from_millis(target.timeout().into()),
-            handle,
-            receiver,
-            self.sender.clone(),
+        self.start_connection(
+            backend,
+            DeferredConnection {
+                handle,
+                host,
+                port,
+                domain,
+                timeout: Duration::from_millis(target.timeout().into()),
+                receiver,
+            },
         );
 
         if let Some(existing_handle) = target.set_handle(handle) {
@@ -121,6 +306,7 @@ impl<'gc> Sockets<'gc> {
         target: Avm1Object<'gc>,
         host: String,
         port: u16,
+        domain: Option<String>,
     ) {
         let (sender, receiver) = unbounded();
 
@@ -129,17 +315,22 @@ impl<'gc> Sockets<'gc> {
             None => return,
         };
 
-        let socket = Socket::new(SocketKind::Avm1(target), sender);
+        let socket = Socket::new(
+            SocketKind::Avm1(target),
+            sender,
+            SocketFraming::NullTerminated,
+        );
         let handle = self.sockets.insert(socket);
-
-        // NOTE: This call will send SocketAction::Connect to sender with connection status.
-        backend.connect_socket(
-            host,
-            port,
-            Duration::from_millis(xml_socket.timeout().into()),
-            handle,
-            receiver,
-            self.sender.clone(),
+        self.start_connection(
+            backend,
+            DeferredConnection {
+                handle,
+                host,
+                port,
+                domain,
+                timeout: Duration::from_millis(xml_socket.timeout().into()),
+                receiver,
+            },
         );
 
         if let Some(existing_handle) = xml_socket.set_handle(handle) {
@@ -149,6 +340,83 @@ impl<'gc> Sockets<'gc> {
         }
     }
 
+    /// Connect now, or once the policy files being loaded for the host are done if one is needed.
+    fn start_connection(
+        &mut self,
+        backend: &mut dyn NavigatorBackend,
+        connection: DeferredConnection,
+    ) {
+        let require_policy_file = self
+            .policy
+            .requires_policy_file(&connection.host, connection.port);
+        if require_policy_file && self.policy_files.is_loading(&connection.host) {
+            tracing::info!(
+                "Connection to {}:{} waits for socket policy files to load",
+                connection.host,
+                connection.port
+            );
+            self.deferred.push(connection);
+        } else {
+            self.connect(backend, connection, require_policy_file);
+        }
+    }
+
+    fn connect(
+        &self,
+        backend: &mut dyn NavigatorBackend,
+        connection: DeferredConnection,
+        mut require_policy_file: bool,
+    ) {
+        if require_policy_file {
+            if let Some(policy_port) = self.policy_files.allowing_policy_port(
+                &connection.host,
+                connection.domain.as_deref(),
+                connection.port,
+            ) {
+                tracing::info!(
+                    "Connection to {}:{} is allowed by the socket policy file at port {policy_port}",
+                    connection.host,
+                    connection.port
+                );
+                require_policy_file = false;
+            }
+        }
+
+        // NOTE: This call will send SocketAction::Connect to sender with connection status.
+        backend.connect_socket(
+            connection.host,
+            connection.port,
+            require_policy_file,
+            connection.timeout,
+            connection.handle,
+            connection.receiver,
+            self.sender.clone(),
+        );
+    }
+
+    /// Record a socket policy file as loaded (or failed), and make the connections that were waiting on it.
+    pub fn policy_file_loaded(
+        &mut self,
+        backend: &mut dyn NavigatorBackend,
+        host: &str,
+        port: u16,
+        policy: Option<String>,
+    ) {
+        self.policy_files.finish_loading(host, port, policy);
+
+        let (ready, waiting) = std::mem::take(&mut self.deferred)
+            .into_iter()
+            .partition::<Vec<_>, _>(|connection| !self.policy_files.is_loading(&connection.host));
+        self.deferred = waiting;
+
+        for connection in ready {
+            // The socket may have been closed while it was waiting, which drops its sender.
+            if !connection.receiver.is_closed() {
+                self.connect(backend, connection, true);
+            }
+        }
+    }
+
     pub fn is_connected(&self, handle: SocketHandle) -> bool {
         matches!(self.sockets.get(handle), Some(Socket { .. }))
     }
@@ -160,21 +428,13 @@ impl<'gc> Sockets<'gc> {
     }
 
     pub fn close(&mut self, handle: SocketHandle) {
-        if let Some(Socket { sender, target }) = self.sockets.remove(handle) {
+        if let Some(Socket { sender, target, .. }) = self.sockets.remove(handle) {
             drop(sender); // NOTE: By dropping the sender, the reading task will close automatically.
 
             // Clear the buffers if the connection was closed.
-            match target {
-                SocketKind::Avm1(target) => {
-                    let target =
-                        XmlSocket::cast(target.into()).expect("target should be XmlSocket");
-
-                    target.read_buffer().clear();
-                }
-                SocketKind::Avm2(target) => {
-                    target.read_buffer().clear();
-                    target.write_buffer().clear();
-                }
+            if let SocketKind::Avm2(target) = target {
+                target.read_buffer().clear();
+                target.write_buffer().clear();
             }
         }
     }
@@ -274,9 +534,18 @@ impl<'gc> Sockets<'gc> {
                         }
                     }
                 }
-                SocketAction::Data(handle, mut data) => {
-                    let target = match context.sockets.sockets.get(handle) {
-                        Some(socket) => socket.target,
+                SocketAction::Data(handle, data) => {
+                    let (target, messages, too_long) = match context.sockets.sockets.get(handle) {
+                        Some(socket) => {
+                            let mut pending = socket.pending.borrow_mut();
+                            pending.extend(data);
+                            let messages = socket.framing.take_messages(&mut pending);
+                            (
+                                socket.target,
+                                messages,
+                                socket.framing.exceeds_max_len(&pending),
+                            )
+                        }
                         // Socket must have been closed before we could send event.
                         None => continue,
                     };
@@ -285,31 +554,33 @@ impl<'gc> Sockets<'gc> {
                         SocketKind::Avm2(target) => {
                             let mut activation = Avm2Activation::from_nothing(context.reborrow());
 
-                            let bytes_loaded = data.len();
-                            target.read_buffer().extend(data);
-
-                            let progress_evt = activation
-                                .avm2()
-                                .classes()
-                                .progressevent
-                                .construct(
-                                    &mut activation,
-                                    &[
-                                        "socketData".into(),
-                                        false.into(),
-                                        false.into(),
-                                        bytes_loaded.into(),
-                                        //NOTE: bytesTotal is not used by socketData event.
-                                        0.into(),
-                                    ],
-                                )
-                                .expect("ProgressEvent should be constructed");
+                            for message in messages {
+                                let bytes_loaded = message.len();
+                                target.read_buffer().extend(message);
 
-                            Avm2::dispatch_event(
-                                &mut activation.context,
-                                progress_evt,
-                                target.into(),
-                            );
+                                let progress_evt = activation
+                                    .avm2()
+                                    .classes()
+                                    .progressevent
+                                    .construct(
+                                        &mut activation,
+                                        &[
+                                            "socketData".into(),
+                                            false.into(),
+                                            false.into(),
+                                            bytes_loaded.into(),
+                                            //NOTE: bytesTotal is not used by socketData event.
+                                            0.into(),
+                                        ],
+                                    )
+                                    .expect("ProgressEvent should be constructed");
+
+                                Avm2::dispatch_event(
+                                    &mut activation.context,
+                                    progress_evt,
+                                    target.into(),
+                                );
+                            }
                         }
                         SocketKind::Avm1(target) => {
                             let mut activation = Avm1Activation::from_stub(
@@ -317,59 +588,30 @@ impl<'gc> Sockets<'gc> {
                                 ActivationIdentifier::root("[XMLSocket]"),
                             );
 
-                            // NOTE: This is enforced in connect_avm1() function.
-                            let xml_socket =
-                                XmlSocket::cast(target.into()).expect("target should be XmlSocket");
-
-                            // Check if the current received packet includes a null byte.
-                            if let Some((index, _)) = data.iter().enumerate().find(|(_, &b)| b == 0)
-                            {
-                                // Received payload contains a null byte, so take data from sockets read buffer and append message data ontop.
-                                let mut buffer = xml_socket
-                                    .read_buffer()
-                                    .drain(..)
-                                    .chain(data.drain(..index))
-                                    .collect::<Vec<_>>();
-
-                                // Now we loop to check for more null bytes.
-                                loop {
-                                    // Remove null byte.
-                                    data.drain(..1);
-
-                                    // Create message from the buffer.
-                                    let message =
-                                        AvmString::new_utf8_bytes(activation.gc(), &buffer);
-
-                                    // Call the event handler.
-                                    let _ = target.call_method(
-                                        "onData".into(),
-                                        &[message.into()],
-                                        &mut activation,
-                                        ExecutionReason::Special,
-                                    );
-
-                                    // Check if we have another null byte in the same payload.
-                                    if let Some((index, _)) =
-                                        data.iter().enumerate().find(|(_, &b)| b == 0)
-                                    {
-                                        // Because data in XmlSocket::read_buffer() has already been consumed
-                                        // we do not need to access it again.
-                                        buffer = data.drain(..index).collect::<Vec<_>>();
-                                    } else {
-                                        // No more messages in the payload, so exit the loop.
-                                        break;
-                                    }
-                                }
-
-                                // Check if we have leftover bytes.
-                                if !data.is_empty() {
-                                    // We had leftover bytes, so append them to XmlSocket internal read buffer,
-                                    // to be used when the next packet arrives.
-                                    xml_socket.read_buffer().extend(data);
-                                }
+                            for mut message in messages {
+                                // Remove the null byte terminating the message.
+                                message.pop();
+                                let message = AvmString::new_utf8_bytes(activation.gc(), &message);
+
+                                let _ = target.call_method(
+                                    "onData".into(),
+                                    &[message.into()],
+                                    &mut activation,
+                                    ExecutionReason::Special,
+                                );
                             }
                         }
                     }
+
+                    if too_long {
+                        tracing::warn!(
+                            "Closing socket, as its peer sent a message longer than {MAX_MESSAGE_LEN} bytes"
+                        );
+                        // Dropping the socket drops its sender, which disconnects it.
+                        if let Some(socket) = context.sockets.sockets.remove(handle) {
+                            Self::dispatch_close(context, socket.target);
+                        }
+                    }
                 }
                 SocketAction::Close(handle) => {
                     let target = match context.sockets.sockets.remove(handle) {
@@ -378,40 +620,170 @@ impl<'gc> Sockets<'gc> {
                         None => continue,
                     };
 
-                    match target {
-                        SocketKind::Avm2(target) => {
-                            let mut activation = Avm2Activation::from_nothing(context.reborrow());
-
-                            // Clear the buffers if the connection was closed.
-                            target.read_buffer().clear();
-                            target.write_buffer().clear();
-
-                            let close_evt =
-                                EventObject::bare_default_event(&mut activation.context, "close");
-                            Avm2::dispatch_event(&mut activation.context, close_evt, target.into());
-                        }
-                        SocketKind::Avm1(target) => {
-                            let mut activation = Avm1Activation::from_stub(
-                                context.reborrow(),
-                                ActivationIdentifier::root("[XMLSocket]"),
-                            );
+                    Self::dispatch_close(context, target);
+                }
+            }
+        }
+    }
 
-                            // Clear the read buffer if the connection was closed.
-                            let socket =
-                                XmlSocket::cast(target.into()).expect("target should be XmlSocket");
+    /// Tells the content that the connection of a removed socket was closed.
+    fn dispatch_close(context: &mut UpdateContext<'_, 'gc>, target: SocketKind<'gc>) {
+        match target {
+            SocketKind::Avm2(target) => {
+                let mut activation = Avm2Activation::from_nothing(context.reborrow());
 
-                            socket.read_buffer().clear();
+                // Clear the buffers if the connection was closed.
+                target.read_buffer().clear();
+                target.write_buffer().clear();
 
-                            let _ = target.call_method(
-                                "onClose".into(),
-                                &[],
-                                &mut activation,
-                                ExecutionReason::Special,
-                            );
-                        }
-                    }
-                }
+                let close_evt = EventObject::bare_default_event(&mut activation.context, "close");
+                Avm2::dispatch_event(&mut activation.context, close_evt, target.into());
+            }
+            SocketKind::Avm1(target) => {
+                let mut activation = Avm1Activation::from_stub(
+                    context.reborrow(),
+                    ActivationIdentifier::root("[XMLSocket]"),
+                );
+
+                let _ = target.call_method(
+                    "onClose".into(),
+                    &[],
+                    &mut activation,
+                    ExecutionReason::Special,
+                );
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        ConnectionState, DeferredConnection, SocketAction, SocketFraming, SocketPolicy, Sockets,
+        MAX_MESSAGE_LEN,
+    };
+    use crate::backend::navigator::NullNavigatorBackend;
+    use async_channel::{unbounded, Sender as AsyncSender};
+    use generational_arena::Index;
+    use std::time::Duration;
+
+    fn connection(index: usize, host: &str) -> (DeferredConnection, AsyncSender<Vec<u8>>) {
+        let (sender, receiver) = unbounded();
+        let connection = DeferredConnection {
+            handle: Index::from_raw_parts(index, 0),
+            host: host.to_string(),
+            port: 5000,
+            domain: Some("example.net".to_string()),
+            timeout: Duration::from_secs(1),
+            receiver,
+        };
+        (connection, sender)
+    }
+
+    /// The handles of the sockets passed to the navigator so far, in order.
+    fn connected(sockets: &Sockets<'_>) -> Vec<usize> {
+        sockets
+            .receiver
+            .try_iter()
+            .map(|action| match action {
+                SocketAction::Connect(handle, ConnectionState::Failed) => handle.into_raw_parts().0,
+                action => panic!("Unexpected socket action {action:?}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn connections_wait_for_policy_files() {
+        let mut backend = NullNavigatorBackend::new();
+        let mut sockets = Sockets::empty();
+        sockets.set_policy(SocketPolicy {
+            require_policy_files: true,
+            overrides: vec![],
+        });
+        assert!(sockets
+            .policy_files_mut()
+            .start_loading("example.com", 8430));
+        assert!(sockets
+            .policy_files_mut()
+            .start_loading("example.com", 8431));
+
+        let (first, _first_sender) = connection(1, "example.com");
+        let (closed, closed_sender) = connection(2, "example.com");
+        let (other_host, _other_host_sender) = connection(3, "example.org");
+        let (second, _second_sender) = connection(4, "EXAMPLE.COM");
+        sockets.start_connection(&mut backend, first);
+        sockets.start_connection(&mut backend, closed);
+        sockets.start_connection(&mut backend, other_host);
+        sockets.start_connection(&mut backend, second);
+        assert_eq!(connected(&sockets), vec![3]);
+
+        // Closing a socket drops its sender.
+        drop(closed_sender);
+
+        sockets.policy_file_loaded(&mut backend, "example.com", 8430, None);
+        assert_eq!(connected(&sockets), Vec::<usize>::new());
+
+        sockets.policy_file_loaded(
+            &mut backend,
+            "example.com",
+            8431,
+            Some(
+                r#"<cross-domain-policy><allow-access-from domain="*" to-ports="5000"/></cross-domain-policy>"#
+                    .to_string(),
+            ),
+        );
+        assert_eq!(connected(&sockets), vec![1, 4]);
+        assert_eq!(
+            sockets
+                .policy_files()
+                .allowing_policy_port("example.com", Some("example.net"), 5000),
+            Some(8431)
+        );
+    }
+
+    #[test]
+    fn framing_reassembles_messages() {
+        let mut buffer = b"<a/>\0<b".to_vec();
+        assert_eq!(
+            SocketFraming::NullTerminated.take_messages(&mut buffer),
+            vec![b"<a/>\0".to_vec()]
+        );
+        buffer.extend(b"/>\0");
+        assert_eq!(
+            SocketFraming::NullTerminated.take_messages(&mut buffer),
+            vec![b"<b/>\0".to_vec()]
+        );
+        assert!(buffer.is_empty());
+
+        let mut buffer = vec![0, 2, 1, 2, 0, 1, 3, 0];
+        assert_eq!(
+            SocketFraming::LengthPrefixed16.take_messages(&mut buffer),
+            vec![vec![0, 2, 1, 2], vec![0, 1, 3]]
+        );
+        assert_eq!(buffer, vec![0]);
+
+        let mut buffer = vec![0, 0, 0, 1, 7];
+        assert_eq!(
+            SocketFraming::LengthPrefixed32.take_messages(&mut buffer),
+            vec![vec![0, 0, 0, 1, 7]]
+        );
+    }
+
+    #[test]
+    fn framing_limits_message_length() {
+        let announced = |length: u32| {
+            let mut buffer = length.to_be_bytes().to_vec();
+            buffer.push(0);
+            SocketFraming::LengthPrefixed32.exceeds_max_len(&buffer)
+        };
+        assert!(!announced(MAX_MESSAGE_LEN as u32 - 4));
+        assert!(announced(MAX_MESSAGE_LEN as u32 - 3));
+        assert!(announced(u32::MAX));
+        assert!(!SocketFraming::LengthPrefixed32.exceeds_max_len(&[0xff, 0xff]));
+
+        let unterminated = vec![b'a'; MAX_MESSAGE_LEN + 1];
+        assert!(SocketFraming::NullTerminated.exceeds_max_len(&unterminated));
+        assert!(!SocketFraming::NullTerminated.exceeds_max_len(&unterminated[1..]));
+        assert!(!SocketFraming::None.exceeds_max_len(&unterminated));
+    }
+}
@@ -0,0 +1,501 @@
+//! SQLite databases behind AIR's `flash.data` API.
+//!
+//! Databases are only available with the `filesystem` feature. Without it, every attempt to
+//! open one fails, as if the file couldn't be accessed.
+//!
+//! Each database is run by a thread of its own, so that the statements of asynchronous
+//! connections don't hold up the player.
+
+use std::collections::HashMap;
+use std::path::Path;
+#[cfg(feature = "filesystem")]
+use std::sync::atomic::{AtomicBool, Ordering};
+#[cfg(feature = "filesystem")]
+use std::sync::mpsc::{channel, Receiver, Sender};
+#[cfg(feature = "filesystem")]
+use std::sync::Arc;
+
+pub type SqlConnectionHandle = u32;
+
+/// Identifies a statement run by `SqlConnections::execute_async`.
+pub type SqlRequestId = u32;
+
+/// A value stored in, or bound to, an SQL statement.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SqlValue {
+    Null,
+    Integer(i64),
+    Real(f64),
+    Text(String),
+    Blob(Vec<u8>),
+}
+
+/// How a database is opened, as `SQLMode` names it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SqlOpenMode {
+    /// Read-write, creating the database if it doesn't exist.
+    Create,
+    Read,
+    Update,
+}
+
+impl SqlOpenMode {
+    pub fn parse(mode: &str) -> Option<Self> {
+        match mode {
+            "create" => Some(Self::Create),
+            "read" => Some(Self::Read),
+            "update" => Some(Self::Update),
+            _ => None,
+        }
+    }
+}
+
+/// A parameter of `SQLStatement.parameters`, keyed as ActionScript does.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SqlParameter {
+    /// A `:name` or `@name` parameter, including its prefix.
+    Named(String),
+    /// A zero-based `?` parameter.
+    Index(usize),
+}
+
+/// The outcome of executing a statement.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SqlResult {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<SqlValue>>,
+    pub rows_affected: u64,
+    pub last_insert_row_id: i64,
+}
+
+/// The outcome of a statement run in the background, with the request it answers.
+pub type SqlResponse = (SqlRequestId, Result<SqlResult, String>);
+
+/// A statement for a connection's thread to run.
+#[cfg(feature = "filesystem")]
+struct SqlJob {
+    request: SqlRequestId,
+    sql: String,
+    parameters: Vec<(SqlParameter, SqlValue)>,
+    reply: Sender<SqlResponse>,
+}
+
+/// An open database, run by a thread of its own.
+#[cfg(feature = "filesystem")]
+struct Connection {
+    jobs: Sender<SqlJob>,
+
+    /// Where the thread sends the outcomes of asynchronous statements.
+    responses: (Sender<SqlResponse>, Receiver<SqlResponse>),
+
+    /// Whether the last statement run left the database inside an explicit transaction.
+    in_transaction: Arc<AtomicBool>,
+
+    next_request: SqlRequestId,
+}
+
+/// Without the `filesystem` feature, no connection can ever be opened.
+#[cfg(not(feature = "filesystem"))]
+type Connection = std::convert::Infallible;
+
+/// The databases opened by `SQLConnection`s, identified by handles.
+#[derive(Default)]
+pub struct SqlConnections {
+    connections: HashMap<SqlConnectionHandle, Connection>,
+    #[cfg(feature = "filesystem")]
+    next_handle: SqlConnectionHandle,
+}
+
+impl SqlConnections {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether databases can be opened at all.
+    pub fn is_supported() -> bool {
+        cfg!(feature = "filesystem")
+    }
+
+    pub fn is_open(&self, handle: SqlConnectionHandle) -> bool {
+        self.connections.contains_key(&handle)
+    }
+
+    /// Closes a connection. Its thread finishes any statements that were already sent to it.
+    pub fn close(&mut self, handle: SqlConnectionHandle) {
+        self.connections.remove(&handle);
+    }
+
+    #[cfg(feature = "filesystem")]
+    fn connection(&mut self, handle: SqlConnectionHandle) -> Result<&mut Connection, String> {
+        self.connections
+            .get_mut(&handle)
+            .ok_or_else(|| "The database connection isn't open".to_string())
+    }
+
+    /// Opens the database at `path`, or a new in-memory database if there is no path.
+    #[cfg(feature = "filesystem")]
+    pub fn open(
+        &mut self,
+        path: Option<&Path>,
+        mode: SqlOpenMode,
+    ) -> Result<SqlConnectionHandle, String> {
+        use rusqlite::OpenFlags;
+
+        let flags = match mode {
+            SqlOpenMode::Create => {
+                OpenFlags::SQLITE_OPEN_READ_WRITE | OpenFlags::SQLITE_OPEN_CREATE
+            }
+            SqlOpenMode::Read => OpenFlags::SQLITE_OPEN_READ_ONLY,
+            SqlOpenMode::Update => OpenFlags::SQLITE_OPEN_READ_WRITE,
+        };
+        let database = match path {
+            Some(path) => {
+                rusqlite::Connection::open_with_flags(path, flags | OpenFlags::SQLITE_OPEN_NO_MUTEX)
+            }
+            None => rusqlite::Connection::open_in_memory(),
+        }
+        .map_err(|e| e.to_string())?;
+
+        let (jobs, job_receiver) = channel();
+        let in_transaction = Arc::new(AtomicBool::new(false));
+        let thread_in_transaction = in_transaction.clone();
+        std::thread::Builder::new()
+            .name("SQLConnection".to_string())
+            .spawn(move || run_jobs(database, job_receiver, thread_in_transaction))
+            .map_err(|e| e.to_string())?;
+
+        // Handle 0 is never used, so ActionScript can use it for "not open".
+        self.next_handle = self.next_handle.wrapping_add(1).max(1);
+        self.connections.insert(
+            self.next_handle,
+            Connection {
+                jobs,
+                responses: channel(),
+                in_transaction,
+                next_request: 0,
+            },
+        );
+        Ok(self.next_handle)
+    }
+
+    #[cfg(not(feature = "filesystem"))]
+    pub fn open(
+        &mut self,
+        _path: Option<&Path>,
+        _mode: SqlOpenMode,
+    ) -> Result<SqlConnectionHandle, String> {
+        Err("SQL databases aren't supported in this build".to_string())
+    }
+
+    /// Sends a statement to the connection's thread, binding `parameters` first, and returns
+    /// the request its outcome will answer.
+    #[cfg(feature = "filesystem")]
+    fn send(
+        &mut self,
+        handle: SqlConnectionHandle,
+        sql: &str,
+        parameters: &[(SqlParameter, SqlValue)],
+        reply: Option<Sender<SqlResponse>>,
+    ) -> Result<SqlRequestId, String> {
+        let connection = self.connection(handle)?;
+        let request = connection.next_request;
+        connection.next_request = connection.next_request.wrapping_add(1);
+        let job = SqlJob {
+            request,
+            sql: sql.to_string(),
+            parameters: parameters.to_vec(),
+            reply: reply.unwrap_or_else(|| connection.responses.0.clone()),
+        };
+        connection
+            .jobs
+            .send(job)
+            .map_err(|_| "The database connection was lost".to_string())?;
+        Ok(request)
+    }
+
+    /// Runs a single statement and waits for its outcome.
+    #[cfg(feature = "filesystem")]
+    pub fn execute(
+        &mut self,
+        handle: SqlConnectionHandle,
+        sql: &str,
+        parameters: &[(SqlParameter, SqlValue)],
+    ) -> Result<SqlResult, String> {
+        let (reply, response) = channel();
+        self.send(handle, sql, parameters, Some(reply))?;
+        match response.recv() {
+            Ok((_, result)) => result,
+            Err(_) => Err("The database connection was lost".to_string()),
+        }
+    }
+
+    #[cfg(not(feature = "filesystem"))]
+    pub fn execute(
+        &mut self,
+        _handle: SqlConnectionHandle,
+        _sql: &str,
+        _parameters: &[(SqlParameter, SqlValue)],
+    ) -> Result<SqlResult, String> {
+        Err("The database connection isn't open".to_string())
+    }
+
+    /// Starts running a single statement in the background. Its outcome is given by
+    /// `take_responses` once it's done.
+    #[cfg(feature = "filesystem")]
+    pub fn execute_async(
+        &mut self,
+        handle: SqlConnectionHandle,
+        sql: &str,
+        parameters: &[(SqlParameter, SqlValue)],
+    ) -> Result<SqlRequestId, String> {
+        self.send(handle, sql, parameters, None)
+    }
+
+    #[cfg(not(feature = "filesystem"))]
+    pub fn execute_async(
+        &mut self,
+        _handle: SqlConnectionHandle,
+        _sql: &str,
+        _parameters: &[(SqlParameter, SqlValue)],
+    ) -> Result<SqlRequestId, String> {
+        Err("The database connection isn't open".to_string())
+    }
+
+    /// The outcomes of the statements started with `execute_async` that finished since the
+    /// last call, in the order they were run.
+    #[cfg(feature = "filesystem")]
+    pub fn take_responses(&mut self, handle: SqlConnectionHandle) -> Vec<SqlResponse> {
+        self.connection(handle)
+            .map(|connection| connection.responses.1.try_iter().collect())
+            .unwrap_or_default()
+    }
+
+    #[cfg(not(feature = "filesystem"))]
+    pub fn take_responses(&mut self, _handle: SqlConnectionHandle) -> Vec<SqlResponse> {
+        vec![]
+    }
+
+    /// Whether the connection is inside an explicit transaction.
+    #[cfg(feature = "filesystem")]
+    pub fn in_transaction(&mut self, handle: SqlConnectionHandle) -> bool {
+        self.connection(handle).map_or(false, |connection| {
+            connection.in_transaction.load(Ordering::Relaxed)
+        })
+    }
+
+    #[cfg(not(feature = "filesystem"))]
+    pub fn in_transaction(&mut self, _handle: SqlConnectionHandle) -> bool {
+        false
+    }
+}
+
+/// Runs the statements sent to a connection, until the connection is closed.
+#[cfg(feature = "filesystem")]
+fn run_jobs(
+    database: rusqlite::Connection,
+    jobs: Receiver<SqlJob>,
+    in_transaction: Arc<AtomicBool>,
+) {
+    for job in jobs {
+        let result = execute_statement(&database, &job.sql, &job.parameters);
+        in_transaction.store(!database.is_autocommit(), Ordering::Relaxed);
+        let _ = job.reply.send((job.request, result));
+    }
+}
+
+/// Runs a single statement, binding `parameters` first.
+#[cfg(feature = "filesystem")]
+fn execute_statement(
+    database: &rusqlite::Connection,
+    sql: &str,
+    parameters: &[(SqlParameter, SqlValue)],
+) -> Result<SqlResult, String> {
+    use rusqlite::types::ValueRef;
+
+    let mut statement = database.prepare(sql).map_err(|e| e.to_string())?;
+    for (parameter, value) in parameters {
+        let index = match parameter {
+            SqlParameter::Named(name) => statement
+                .parameter_index(name)
+                .map_err(|e| e.to_string())?
+                .ok_or_else(|| format!("The statement has no parameter named {name}"))?,
+            SqlParameter::Index(index) => index + 1,
+        };
+        statement
+            .raw_bind_parameter(index, value)
+            .map_err(|e| e.to_string())?;
+    }
+
+    let columns: Vec<String> = statement
+        .column_names()
+        .into_iter()
+        .map(String::from)
+        .collect();
+
+    let changes_before = total_changes(database);
+    let mut rows = vec![];
+    let mut query = statement.raw_query();
+    while let Some(row) = query.next().map_err(|e| e.to_string())? {
+        let mut values = Vec::with_capacity(columns.len());
+        for i in 0..columns.len() {
+            values.push(match row.get_ref(i).map_err(|e| e.to_string())? {
+                ValueRef::Null => SqlValue::Null,
+                ValueRef::Integer(value) => SqlValue::Integer(value),
+                ValueRef::Real(value) => SqlValue::Real(value),
+                ValueRef::Text(value) => {
+                    SqlValue::Text(String::from_utf8_lossy(value).into_owned())
+                }
+                ValueRef::Blob(value) => SqlValue::Blob(value.to_vec()),
+            });
+        }
+        rows.push(values);
+    }
+    drop(query);
+
+    // SQLite only counts the rows changed by the last statement that changed any, which
+    // may not be this one.
+    let rows_affected = if total_changes(database) != changes_before {
+        database.changes()
+    } else {
+        0
+    };
+
+    Ok(SqlResult {
+        columns,
+        rows,
+        rows_affected,
+        last_insert_row_id: database.last_insert_rowid(),
+    })
+}
+
+/// How many rows every statement run on the database so far has changed.
+#[cfg(feature = "filesystem")]
+fn total_changes(database: &rusqlite::Connection) -> i32 {
+    // SAFETY: The handle stays valid for as long as the connection is borrowed, and SQLite
+    // only reads from it here.
+    unsafe { rusqlite::ffi::sqlite3_total_changes(database.handle()) }
+}
+
+#[cfg(feature = "filesystem")]
+impl rusqlite::ToSql for SqlValue {
+    fn to_sql(&self) -> rusqlite::Result<rusqlite::types::ToSqlOutput<'_>> {
+        use rusqlite::types::{ToSqlOutput, ValueRef};
+
+        Ok(ToSqlOutput::Borrowed(match self {
+            SqlValue::Null => ValueRef::Null,
+            SqlValue::Integer(value) => ValueRef::Integer(*value),
+            SqlValue::Real(value) => ValueRef::Real(*value),
+            SqlValue::Text(value) => ValueRef::Text(value.as_bytes()),
+            SqlValue::Blob(value) => ValueRef::Blob(value),
+        }))
+    }
+}
+
+#[cfg(all(test, feature = "filesystem"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn in_memory_database() {
+        let mut connections = SqlConnections::new();
+        let handle = connections.open(None, SqlOpenMode::Create).unwrap();
+        let run = |connections: &mut SqlConnections, sql, parameters: &[_]| {
+            connections.execute(handle, sql, parameters).unwrap()
+        };
+
+        run(
+            &mut connections,
+            "CREATE TABLE items (id INTEGER PRIMARY KEY, name TEXT, data BLOB)",
+            &[],
+        );
+        run(&mut connections, "BEGIN", &[]);
+        assert!(connections.in_transaction(handle));
+        let insert = run(
+            &mut connections,
+            "INSERT INTO items (name, data) VALUES (:name, ?)",
+            &[
+                (
+                    SqlParameter::Named(":name".into()),
+                    SqlValue::Text("hat".into()),
+                ),
+                (SqlParameter::Index(1), SqlValue::Blob(vec![1, 2])),
+            ],
+        );
+        assert_eq!(insert.rows_affected, 1);
+        assert_eq!(insert.last_insert_row_id, 1);
+        run(&mut connections, "COMMIT", &[]);
+        assert!(!connections.in_transaction(handle));
+
+        let select = run(&mut connections, "SELECT * FROM items", &[]);
+        assert_eq!(select.columns, ["id", "name", "data"]);
+        assert_eq!(
+            select.rows,
+            [vec![
+                SqlValue::Integer(1),
+                SqlValue::Text("hat".into()),
+                SqlValue::Blob(vec![1, 2]),
+            ]]
+        );
+        assert_eq!(select.rows_affected, 0);
+
+        assert!(connections
+            .execute(handle, "SELECT * FROM missing", &[])
+            .is_err());
+        connections.close(handle);
+        assert!(!connections.is_open(handle));
+    }
+
+    #[test]
+    fn rows_affected_by_each_statement() {
+        let mut connections = SqlConnections::new();
+        let handle = connections.open(None, SqlOpenMode::Create).unwrap();
+        let mut rows_affected = |sql| connections.execute(handle, sql, &[]).unwrap().rows_affected;
+
+        assert_eq!(rows_affected("CREATE TABLE items (name TEXT)"), 0);
+        assert_eq!(
+            rows_affected("INSERT INTO items VALUES ('hat'), ('shirt')"),
+            2
+        );
+        // Neither of these change any rows, so the insert mustn't be counted again.
+        assert_eq!(rows_affected("CREATE TABLE colors (name TEXT)"), 0);
+        assert_eq!(
+            rows_affected("UPDATE items SET name = 'cap' WHERE name = 'shoes'"),
+            0
+        );
+        assert_eq!(rows_affected("DELETE FROM items"), 2);
+    }
+
+    #[test]
+    fn asynchronous_statements() {
+        let mut connections = SqlConnections::new();
+        let handle = connections.open(None, SqlOpenMode::Create).unwrap();
+
+        let requests = [
+            "CREATE TABLE items (name TEXT)",
+            "INSERT INTO items VALUES ('hat')",
+            "SELECT * FROM missing",
+            "SELECT name FROM items",
+        ]
+        .map(|sql| connections.execute_async(handle, sql, &[]).unwrap());
+
+        // Running a statement synchronously waits for those before it.
+        connections
+            .execute(handle, "SELECT * FROM items", &[])
+            .unwrap();
+        let responses = connections.take_responses(handle);
+        assert_eq!(
+            responses
+                .iter()
+                .map(|(request, _)| *request)
+                .collect::<Vec<_>>(),
+            requests
+        );
+        assert_eq!(responses[1].1.as_ref().unwrap().rows_affected, 1);
+        assert!(responses[2].1.is_err());
+        assert_eq!(
+            responses[3].1.as_ref().unwrap().rows,
+            [vec![SqlValue::Text("hat".into())]]
+        );
+        assert!(connections.take_responses(handle).is_empty());
+    }
+}
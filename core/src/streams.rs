@@ -1,5 +1,7 @@
 //! NetStream implementation
 
+mod mp4;
+
 use crate::avm1::{
     Activation as Avm1Activation, ActivationIdentifier as Avm1ActivationIdentifier,
     ExecutionReason as Avm1ExecutionReason, FlvValueAvm1Ext, ScriptObject as Avm1ScriptObject,
@@ -18,18 +20,23 @@ use crate::buffer::{Buffer, Slice, Substream, SubstreamError};
 use crate::context::UpdateContext;
 use crate::display_object::MovieClip;
 use crate::loader::Error;
+use crate::net_status::NetStatus;
 use crate::string::AvmString;
 use crate::vminterface::AvmObject;
 use flv_rs::{
     AudioData as FlvAudioData, AudioDataType as FlvAudioDataType, Error as FlvError, FlvReader,
     FrameType as FlvFrameType, Header as FlvHeader, ScriptData as FlvScriptData,
     SoundFormat as FlvSoundFormat, SoundRate as FlvSoundRate, SoundSize as FlvSoundSize,
-    SoundType as FlvSoundType, Tag as FlvTag, TagData as FlvTagData, Value as FlvValue,
-    VideoData as FlvVideoData, VideoPacket as FlvVideoPacket,
+    SoundType as FlvSoundType, Tag as FlvTag, TagData as FlvTagData, TypeFlags as FlvTypeFlags,
+    Value as FlvValue, Variable as FlvVariable, VideoData as FlvVideoData,
+    VideoPacket as FlvVideoPacket,
 };
 use gc_arena::{Collect, GcCell, Mutation};
-use ruffle_render::bitmap::BitmapInfo;
-use ruffle_video::frame::EncodedFrame;
+use mp4::{Mp4Error, Mp4Movie, TrackKind as Mp4TrackKind};
+use ruffle_render::bitmap::{BitmapHandle, BitmapInfo, PixelRegion};
+use ruffle_video::error::Error as VideoError;
+use ruffle_video::frame::{DecodedFrame, EncodedFrame};
+use ruffle_video::media::{DecodedAudio, MediaCodec, MediaStreamHandle};
 use ruffle_video::VideoStreamHandle;
 use std::cmp::max;
 use std::io::{Seek, SeekFrom};
@@ -47,6 +54,9 @@ enum NetstreamError {
 
     #[error("Unknown codec")]
     UnknownCodec,
+
+    #[error("Media decoding failed because {0}")]
+    MediaError(VideoError),
 }
 
 impl From<DecodeError> for NetstreamError {
@@ -61,6 +71,12 @@ impl From<SubstreamError> for NetstreamError {
     }
 }
 
+impl From<VideoError> for NetstreamError {
+    fn from(err: VideoError) -> NetstreamError {
+        NetstreamError::MediaError(err)
+    }
+}
+
 /// Manager for all media streams.
 ///
 /// This does *not* handle data transport; which is delegated to `LoadManager`.
@@ -176,6 +192,41 @@ pub enum NetStreamType {
         /// frame IDs ourselves for various API related purposes.
         frame_id: u32,
     },
+
+    /// The stream is an MP4 or F4V, with H.264 video and/or AAC audio.
+    Mp4 {
+        movie: Mp4Movie,
+
+        /// The index of the next sample to play.
+        next_sample: usize,
+    },
+}
+
+/// An action of `NetStream.appendBytesAction`, which tells the parser how to
+/// treat the bytes appended after it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AppendBytesAction {
+    /// Discard the buffer and expect a new FLV file, starting with its header.
+    ResetBegin,
+
+    /// Discard the buffer and expect FLV tags continuing the current file,
+    /// usually from the keyframe that was just seeked to.
+    ResetSeek,
+
+    /// No more bytes will be appended, so the stream should end once it
+    /// runs out of buffered data.
+    EndSequence,
+}
+
+impl AppendBytesAction {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "resetBegin" => Some(Self::ResetBegin),
+            "resetSeek" => Some(Self::ResetSeek),
+            "endSequence" => Some(Self::EndSequence),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Clone, Debug, Collect)]
@@ -241,6 +292,65 @@ pub struct NetStreamData<'gc> {
 
     /// True if the stream should play when ticked.
     playing: bool,
+
+    /// True if the stream is in data generation mode, where media is fed to
+    /// it with `appendBytes` rather than downloaded.
+    ///
+    /// Running out of data in this mode only ends the stream once
+    /// `end_of_sequence` is set; until then it waits for more bytes.
+    data_generation: bool,
+
+    /// True if `appendBytesAction("endSequence")` was called since the last
+    /// reset.
+    end_of_sequence: bool,
+
+    /// The amount in milliseconds added to tag timestamps to get stream time.
+    ///
+    /// This is only ever nonzero in data generation mode, where each reset
+    /// may start a new timescale.
+    timestamp_offset: f64,
+
+    /// True if the next tag starts a new timescale, which should continue
+    /// from the current stream time.
+    timescale_discontinuity: bool,
+
+    /// The deblocking filter video is decoded with, as set on the `Video`
+    /// this stream is attached to.
+    #[collect(require_static)]
+    deblocking: VideoDeblocking,
+
+    /// The media backend's H.264 decoder for this stream, if one was set up.
+    #[collect(require_static)]
+    media_video: Option<MediaStreamHandle>,
+
+    /// The media backend's AAC decoder for this stream, if one was set up.
+    #[collect(require_static)]
+    media_audio: Option<MediaStreamHandle>,
+
+    /// The bitmap H.264 frames are uploaded to.
+    #[collect(require_static)]
+    media_bitmap: Option<BitmapHandle>,
+
+    /// Decoded AAC audio, which the audio stream plays from instead of
+    /// `buffer`.
+    #[collect(require_static)]
+    pcm_buffer: Buffer,
+
+    /// The end of the last AAC sample in `buffer` that was decoded.
+    ///
+    /// Lookahead makes us encounter the same samples more than once, and
+    /// unlike other audio they can't just be checked against the audio
+    /// stream's chunks.
+    aac_decoded_until: usize,
+
+    /// Audio copied out of `buffer` in data generation mode, which the audio
+    /// stream plays from instead, so that `buffer` can drop tags once played.
+    #[collect(require_static)]
+    appended_audio: Buffer,
+
+    /// The end of the last audio tag in `buffer` that was copied to
+    /// `appended_audio`.
+    appended_audio_until: usize,
 }
 
 impl<'gc> NetStream<'gc> {
@@ -264,6 +374,18 @@ impl<'gc> NetStream<'gc> {
                 sound_instance: None,
                 attached_to: None,
                 playing: false,
+                data_generation: false,
+                end_of_sequence: false,
+                timestamp_offset: 0.0,
+                timescale_discontinuity: false,
+                deblocking: VideoDeblocking::UseVideoPacketValue,
+                media_video: None,
+                media_audio: None,
+                media_bitmap: None,
+                pcm_buffer: Buffer::new(),
+                aac_decoded_until: 0,
+                appended_audio: Buffer::new(),
+                appended_audio_until: 0,
             },
         ))
     }
@@ -291,6 +413,21 @@ impl<'gc> NetStream<'gc> {
     pub fn reset_buffer(self, context: &mut UpdateContext<'_, 'gc>) {
         let mut write = self.0.write(context.gc_context);
 
+        Self::discard_buffer(context, &mut write);
+        Self::release_media_decoders(context, &mut write);
+        write.stream_type = None;
+        write.stream_time = 0.0;
+        write.queued_seek_time = None;
+        write.end_of_sequence = false;
+        write.timestamp_offset = 0.0;
+        write.timescale_discontinuity = false;
+    }
+
+    /// Drop all buffered data, and stop any sound playing from it.
+    ///
+    /// The stream type is kept, so that callers can decide whether the next
+    /// data continues the same stream or not.
+    fn discard_buffer(context: &mut UpdateContext<'_, 'gc>, write: &mut NetStreamData<'gc>) {
         if let Some(instance) = write.sound_instance {
             // We stop the sound twice because sounds may have either been
             // played through the audio manager or through the backend directly
@@ -303,11 +440,60 @@ impl<'gc> NetStream<'gc> {
         write.buffer = Buffer::new();
         write.offset = 0;
         write.preload_offset = 0;
-        write.stream_type = None;
-        write.stream_time = 0.0;
-        write.queued_seek_time = None;
         write.audio_stream = None;
         write.sound_instance = None;
+        write.pcm_buffer = Buffer::new();
+        write.aac_decoded_until = 0;
+        write.appended_audio = Buffer::new();
+        write.appended_audio_until = 0;
+    }
+
+    /// Drop the tags a stream in data generation mode has played from the
+    /// start of its buffer, as the application may append bytes for as long
+    /// as it likes.
+    ///
+    /// Positions in the buffer all move back by the amount dropped. Audio is
+    /// played from `pcm_buffer` or `appended_audio` in this mode, so no sound
+    /// refers to the dropped bytes.
+    fn trim_appended_buffer(write: &mut NetStreamData<'gc>) {
+        let consumed = write.offset;
+        if !write.data_generation
+            || !matches!(write.stream_type, Some(NetStreamType::Flv { .. }))
+            || consumed == 0
+        {
+            return;
+        }
+
+        // Copying what's left is cheap as long as it's no bigger than what's
+        // dropped, which keeps appending large chunks at once linear.
+        let remaining = write.buffer.len() - consumed;
+        if remaining > consumed {
+            return;
+        }
+
+        let rest = write
+            .buffer
+            .get(consumed..)
+            .map(|rest| rest.data().to_vec())
+            .unwrap_or_default();
+        write.buffer = Buffer::from(rest);
+        write.offset = 0;
+        write.preload_offset = write.preload_offset.saturating_sub(consumed);
+        write.aac_decoded_until = write.aac_decoded_until.saturating_sub(consumed);
+        write.appended_audio_until = write.appended_audio_until.saturating_sub(consumed);
+    }
+
+    /// Drop the media backend's decoders, which a new stream sets up afresh.
+    fn release_media_decoders(
+        context: &mut UpdateContext<'_, 'gc>,
+        write: &mut NetStreamData<'gc>,
+    ) {
+        if let Some(stream) = write.media_video.take() {
+            context.media.unregister_media_stream(stream);
+        }
+        if let Some(stream) = write.media_audio.take() {
+            context.media.unregister_media_stream(stream);
+        }
     }
 
     /// Append data to the `NetStream`'s current internal buffer.
@@ -323,14 +509,93 @@ impl<'gc> NetStream<'gc> {
         self.0.write(context.gc_context).buffer.append(data);
 
         // NOTE: The onMetaData event triggers before this event in Flash due to its streaming behavior.
-        self.trigger_status_event(
-            context,
-            vec![("code", "NetStream.Buffer.Full"), ("level", "status")],
-        );
+        self.trigger_status_event(context, NetStatus::StreamBufferFull, None);
+    }
+
+    /// Append bytes fed to the stream in data generation mode.
+    ///
+    /// The bytes are parsed as the parser expects after the last
+    /// `append_bytes_action`: an FLV header if the stream was just reset to
+    /// the beginning, and FLV tags otherwise. Bytes appended outside of data
+    /// generation mode are ignored.
+    pub fn append_bytes(self, context: &mut UpdateContext<'_, 'gc>, bytes: &[u8]) {
+        let mut write = self.0.write(context.gc_context);
+        if !write.data_generation {
+            tracing::warn!("NetStream.appendBytes called outside of data generation mode");
+            return;
+        }
+
+        write.buffer.extend_from_slice(bytes);
+        drop(write);
+
+        StreamManager::activate(context, self);
+    }
+
+    /// Change how the bytes appended after this are parsed, or mark the end
+    /// of the appended data.
+    pub fn append_bytes_action(
+        self,
+        context: &mut UpdateContext<'_, 'gc>,
+        action: AppendBytesAction,
+    ) {
+        let mut write = self.0.write(context.gc_context);
+        if !write.data_generation {
+            tracing::warn!("NetStream.appendBytesAction called outside of data generation mode");
+            return;
+        }
+
+        match action {
+            AppendBytesAction::ResetBegin => {
+                Self::discard_buffer(context, &mut write);
+                Self::release_media_decoders(context, &mut write);
+                write.stream_type = None;
+                write.end_of_sequence = false;
+                write.timescale_discontinuity = true;
+            }
+            AppendBytesAction::ResetSeek => {
+                Self::discard_buffer(context, &mut write);
+                let (header, video_stream, frame_id) = match write.stream_type.take() {
+                    Some(NetStreamType::Flv {
+                        header,
+                        video_stream,
+                        frame_id,
+                    }) => (header, video_stream, frame_id),
+                    // Tags are all we'll get, so assume they'd have come with
+                    // a header that allows anything.
+                    _ => (
+                        FlvHeader {
+                            version: 1,
+                            type_flags: FlvTypeFlags::HAS_AUDIO | FlvTypeFlags::HAS_VIDEO,
+                            data_offset: 9,
+                        },
+                        None,
+                        0,
+                    ),
+                };
+                write.stream_type = Some(NetStreamType::Flv {
+                    header,
+                    video_stream,
+                    frame_id,
+                });
+
+                // Appended tags start with the tag itself, while our parser
+                // expects each one to follow the size of the tag before it.
+                write.buffer.extend_from_slice(&[0; 4]);
+                write.end_of_sequence = false;
+                write.timescale_discontinuity = true;
+            }
+            AppendBytesAction::EndSequence => write.end_of_sequence = true,
+        }
+        drop(write);
+
+        StreamManager::activate(context, self);
     }
 
-    pub fn report_error(self, _error: Error) {
-        //TODO: Report an `asyncError` to AVM1 or 2.
+    /// Reports that the media requested by `play` couldn't be fetched.
+    pub fn report_error(self, context: &mut UpdateContext<'_, 'gc>, error: Error) {
+        tracing::warn!("NetStream failed to load its media: {error}");
+        self.0.write(context.gc_context).playing = false;
+        self.trigger_status_event(context, NetStatus::StreamPlayStreamNotFound, None);
     }
 
     pub fn bytes_loaded(self) -> usize {
@@ -357,14 +622,7 @@ impl<'gc> NetStream<'gc> {
                 context.gc_context,
                 format!("Start Seeking {}", offset as u64),
             );
-            self.trigger_status_event(
-                context,
-                vec![
-                    ("description", trigger),
-                    ("level", "status".into()),
-                    ("code", "NetStream.SeekStart.Notify".into()),
-                ],
-            );
+            self.trigger_status_event(context, NetStatus::StreamSeekStartNotify, Some(trigger));
         }
     }
 
@@ -387,13 +645,14 @@ impl<'gc> NetStream<'gc> {
     pub fn execute_seek(self, context: &mut UpdateContext<'_, 'gc>, offset: f64) {
         #![allow(clippy::explicit_auto_deref)] //Erroneous lint
 
-        self.trigger_status_event(
-            context,
-            vec![("code", "NetStream.Seek.Notify"), ("level", "status")],
-        );
+        self.trigger_status_event(context, NetStatus::StreamSeekNotify, None);
 
         // Ensure the container stream type is known before continuing.
-        if self.0.read().stream_type.is_none() && !self.sniff_stream_type(context) {
+        // Appended data may not have arrived yet, which doesn't stop a seek.
+        let read = self.0.read();
+        let needs_sniffing = read.stream_type.is_none() && !read.data_generation;
+        drop(read);
+        if needs_sniffing && !self.sniff_stream_type(context) {
             return;
         }
 
@@ -406,8 +665,16 @@ impl<'gc> NetStream<'gc> {
             write.sound_instance = None;
             write.audio_stream = None;
         }
-
-        if matches!(write.stream_type, Some(NetStreamType::Flv { .. })) {
+        write.pcm_buffer = Buffer::new();
+        write.aac_decoded_until = 0;
+        write.appended_audio = Buffer::new();
+        write.appended_audio_until = 0;
+
+        if write.data_generation {
+            // The application feeds us the data for the new position itself,
+            // after resetting the parser with `appendBytesAction`.
+            write.stream_time = offset;
+        } else if matches!(write.stream_type, Some(NetStreamType::Flv { .. })) {
             let slice = write.buffer.to_full_slice();
             let buffer = slice.data();
             let mut reader = FlvReader::from_parts(&*buffer, write.offset);
@@ -472,6 +739,14 @@ impl<'gc> NetStream<'gc> {
             write.offset = reader
                 .stream_position()
                 .expect("FLV reader stream position") as usize;
+        } else if let Some(NetStreamType::Mp4 {
+            movie, next_sample, ..
+        }) = &mut write.stream_type
+        {
+            // Decoding has to start from a sync sample, so snap back to one.
+            *next_sample = movie.seek_index(offset);
+            let time = movie.samples.get(*next_sample).map_or(0.0, |s| s.time);
+            write.stream_time = time;
         }
 
         drop(write);
@@ -479,11 +754,8 @@ impl<'gc> NetStream<'gc> {
         if context.is_action_script_3() {
             self.trigger_status_event(
                 context,
-                vec![
-                    ("description", "Seek Complete -1"),
-                    ("level", "status"),
-                    ("code", "NetStream.Seek.Complete"),
-                ],
+                NetStatus::StreamSeekComplete,
+                Some("Seek Complete -1".into()),
             );
         }
     }
@@ -491,8 +763,8 @@ impl<'gc> NetStream<'gc> {
     /// Start playing media from this NetStream.
     ///
     /// If `name` is specified, this will also trigger streaming download of
-    /// the given resource. Otherwise, the stream will play whatever data is
-    /// available in the buffer.
+    /// the given resource. Otherwise, the stream enters data generation mode
+    /// and plays whatever data is appended with `append_bytes`.
     pub fn play(self, context: &mut UpdateContext<'_, 'gc>, name: Option<AvmString<'gc>>) {
         if let Some(name) = name {
             let request = if let Ok(stream_url) =
@@ -505,20 +777,23 @@ impl<'gc> NetStream<'gc> {
             let mut write = self.0.write(context.gc_context);
             write.url = Some(request.url().to_string());
             write.preload_offset = 0;
+            write.data_generation = false;
             let future = context
                 .load_manager
                 .load_netstream(context.player.clone(), self, request);
 
             context.navigator.spawn_future(future);
+        } else {
+            self.reset_buffer(context);
+            let mut write = self.0.write(context.gc_context);
+            write.url = None;
+            write.data_generation = true;
         }
 
         self.0.write(context.gc_context).playing = true;
         StreamManager::activate(context, self);
 
-        self.trigger_status_event(
-            context,
-            vec![("code", "NetStream.Play.Start"), ("level", "status")],
-        );
+        self.trigger_status_event(context, NetStatus::StreamPlayStart, None);
     }
 
     /// Pause stream playback.
@@ -530,19 +805,20 @@ impl<'gc> NetStream<'gc> {
         if notify {
             self.trigger_status_event(
                 context,
-                vec![
-                    ("description", "Pausing"),
-                    ("level", "status"),
-                    ("code", "NetStream.Pause.Notify"),
-                ],
+                NetStatus::StreamPauseNotify,
+                Some("Pausing".into()),
             );
         }
     }
 
     /// Resume stream playback.
-    pub fn resume(self, context: &mut UpdateContext<'_, 'gc>) {
+    pub fn resume(self, context: &mut UpdateContext<'_, 'gc>, notify: bool) {
         self.0.write(context.gc_context).playing = true;
         StreamManager::activate(context, self);
+
+        if notify {
+            self.trigger_status_event(context, NetStatus::StreamUnpauseNotify, None);
+        }
     }
 
     /// Resume stream playback if paused, pause otherwise.
@@ -555,6 +831,25 @@ impl<'gc> NetStream<'gc> {
         }
     }
 
+    /// Change the deblocking filter the stream's video is decoded with.
+    pub fn set_deblocking(self, context: &mut UpdateContext<'_, 'gc>, deblocking: VideoDeblocking) {
+        let mut write = self.0.write(context.gc_context);
+        write.deblocking = deblocking;
+
+        if let Some(NetStreamType::Flv {
+            video_stream: Some(video_stream),
+            ..
+        }) = write.stream_type
+        {
+            if let Err(e) = context
+                .video
+                .set_video_stream_deblocking(video_stream, deblocking)
+            {
+                tracing::error!("Got error when changing NetStream deblocking: {}", e);
+            }
+        }
+    }
+
     /// Indicates that this `NetStream`'s audio was detached from a `MovieClip` (AVM1)
     pub fn was_detached(self, context: &mut UpdateContext<'_, 'gc>) {
         let mut write = self.0.write(context.gc_context);
@@ -593,15 +888,42 @@ impl<'gc> NetStream<'gc> {
     /// must reference the underlying backing buffer.
     fn flv_audio_tag(
         self,
+        context: &mut UpdateContext<'_, 'gc>,
         write: &mut NetStreamData<'gc>,
         slice: &Slice,
         audio_data: FlvAudioData<'_>,
     ) -> Result<(), NetstreamError> {
-        let data = match audio_data.data {
+        match audio_data.data {
+            FlvAudioDataType::AacSequenceHeader(config) => {
+                return Self::register_media_decoder(context, write, MediaCodec::Aac, config);
+            }
+            FlvAudioDataType::AacRaw(sample) => {
+                return Self::aac_sample(context, write, slice, sample);
+            }
+            FlvAudioDataType::Raw(_) => {}
+        }
+
+        let mut data = match audio_data.data {
             FlvAudioDataType::Raw(data)
             | FlvAudioDataType::AacSequenceHeader(data)
             | FlvAudioDataType::AacRaw(data) => slice.to_subslice(data),
         };
+        if write.data_generation {
+            // Played tags get dropped from `buffer`, so the audio stream
+            // needs its own copy of them.
+            if data.start() < write.appended_audio_until {
+                // Reject repeats of tags we've looked ahead to before.
+                return Ok(());
+            }
+            write.appended_audio_until = data.end();
+
+            let start = write.appended_audio.len();
+            write.appended_audio.extend_from_slice(&data.data());
+            data = write
+                .appended_audio
+                .get(start..)
+                .expect("audio was just appended");
+        }
         let substream = match &mut write.audio_stream {
             Some((substream, _sound_stream_info)) => {
                 if substream
@@ -623,7 +945,7 @@ impl<'gc> NetStream<'gc> {
             }
             audio_stream => {
                 // None
-                let substream = Substream::new(slice.buffer().clone());
+                let substream = Substream::new(data.buffer().clone());
                 let swf_format = SoundFormat {
                     compression: match audio_data.format {
                         FlvSoundFormat::LinearPCMPlatformEndian => {
@@ -675,6 +997,133 @@ impl<'gc> NetStream<'gc> {
         Ok(substream.append(data)?)
     }
 
+    /// Set up the media backend's decoder for `codec`, if there isn't one
+    /// already.
+    fn register_media_decoder(
+        context: &mut UpdateContext<'_, 'gc>,
+        write: &mut NetStreamData<'gc>,
+        codec: MediaCodec,
+        config: &[u8],
+    ) -> Result<(), NetstreamError> {
+        let decoder = match codec {
+            MediaCodec::H264 => &mut write.media_video,
+            MediaCodec::Aac => &mut write.media_audio,
+        };
+        if decoder.is_none() {
+            *decoder = Some(context.media.register_media_stream(codec, config)?);
+        }
+
+        Ok(())
+    }
+
+    /// Decode an AAC sample and queue the decoded audio on the audio stream.
+    ///
+    /// `write` must be an active borrow of the current `NetStream`. `slice`
+    /// must reference the underlying backing buffer, which `sample` is part
+    /// of.
+    fn aac_sample(
+        context: &mut UpdateContext<'_, 'gc>,
+        write: &mut NetStreamData<'gc>,
+        slice: &Slice,
+        sample: &[u8],
+    ) -> Result<(), NetstreamError> {
+        let position = slice.to_subslice(sample);
+        if position.start() < write.aac_decoded_until {
+            // Reject repeats of samples we've looked ahead to before.
+            return Ok(());
+        }
+        write.aac_decoded_until = position.end();
+
+        let decoder = write.media_audio.ok_or(NetstreamError::UnknownCodec)?;
+        let decoded = fit_sample_rate(context.media.decode_audio_sample(decoder, sample)?);
+
+        let start = write.pcm_buffer.len();
+        let mut pcm = decoded
+            .samples
+            .iter()
+            .flat_map(|value| value.to_le_bytes())
+            .collect();
+        write.pcm_buffer.append(&mut pcm);
+        let pcm = write
+            .pcm_buffer
+            .get(start..)
+            .expect("decoded audio was just appended");
+
+        let substream = match &mut write.audio_stream {
+            Some((substream, _sound_stream_info)) => substream,
+            audio_stream => {
+                let sound_stream_head = SoundStreamInfo {
+                    wrapping: SoundStreamWrapping::Unwrapped,
+                    stream_format: SoundFormat {
+                        compression: AudioCompression::Uncompressed,
+                        sample_rate: u16::try_from(decoded.sample_rate)
+                            .map_err(|_| NetstreamError::UnknownCodec)?,
+                        is_stereo: decoded.num_channels == 2,
+                        is_16_bit: true,
+                    },
+                    num_samples_per_block: 0,
+                    latency_seek: 0,
+                };
+
+                *audio_stream = Some((Substream::new(write.pcm_buffer.clone()), sound_stream_head));
+
+                &mut audio_stream.as_mut().unwrap().0
+            }
+        };
+
+        Ok(substream.append(pcm)?)
+    }
+
+    /// Decode an H.264 sample and show the frame it yields, if any.
+    fn h264_sample(
+        context: &mut UpdateContext<'_, 'gc>,
+        write: &mut NetStreamData<'gc>,
+        sample: &[u8],
+    ) -> Result<(), NetstreamError> {
+        let decoder = write.media_video.ok_or(NetstreamError::UnknownCodec)?;
+        if let Some(frame) = context.media.decode_video_sample(decoder, sample)? {
+            Self::show_media_frame(context, write, frame)?;
+        }
+
+        Ok(())
+    }
+
+    /// Upload a frame decoded by the media backend to be displayed.
+    fn show_media_frame(
+        context: &mut UpdateContext<'_, 'gc>,
+        write: &mut NetStreamData<'gc>,
+        frame: DecodedFrame,
+    ) -> Result<(), NetstreamError> {
+        let (width, height) = (frame.width(), frame.height());
+        let handle = match &write.media_bitmap {
+            // Frames can change size, in which case we need a new texture.
+            Some(handle)
+                if write.last_decoded_bitmap.as_ref().map_or(false, |info| {
+                    u32::from(info.width) == width && u32::from(info.height) == height
+                }) =>
+            {
+                context
+                    .renderer
+                    .update_texture(handle, frame, PixelRegion::for_whole_size(width, height))
+                    .map_err(VideoError::BitmapError)?;
+                handle.clone()
+            }
+            _ => context
+                .renderer
+                .register_bitmap(frame)
+                .map_err(VideoError::BitmapError)?,
+        };
+
+        write.media_bitmap = Some(handle.clone());
+        write.last_decoded_bitmap = Some(BitmapInfo {
+            handle,
+            width: width as u16,
+            height: height as u16,
+        });
+
+        Ok(())
+    }
+
     /// Determine if the given sound is currently playing.
     fn sound_currently_playing(
         context: &mut UpdateContext<'_, 'gc>,
@@ -702,6 +1151,7 @@ impl<'gc> NetStream<'gc> {
         if !Self::sound_currently_playing(context, &write.sound_instance) {
             write.audio_stream = None;
             write.sound_instance = None;
+            write.appended_audio = Buffer::new();
         }
     }
 
@@ -782,6 +1232,44 @@ impl<'gc> NetStream<'gc> {
                     }
                 }
             }
+            Some(_)
+                if mp4::is_mp4(&buffer)
+                    && (context.media.supports_codec(MediaCodec::H264)
+                        || context.media.supports_codec(MediaCodec::Aac)) =>
+            {
+                match Mp4Movie::parse(&buffer) {
+                    Ok(movie) => {
+                        for track in [&movie.video, &movie.audio].into_iter().flatten() {
+                            if !context.media.supports_codec(track.codec) {
+                                continue;
+                            }
+                            if let Err(e) = Self::register_media_decoder(
+                                context,
+                                &mut write,
+                                track.codec,
+                                &track.config,
+                            ) {
+                                tracing::error!("Got error when registering MP4 decoder: {}", e);
+                            }
+                        }
+                        write.stream_type = Some(NetStreamType::Mp4 {
+                            movie,
+                            next_sample: 0,
+                        });
+                        drop(write);
+                        self.mp4_metadata(context);
+                        true
+                    }
+                    // The movie box may be at the end of the file.
+                    Err(Mp4Error::EndOfData) => false,
+                    Err(e) => {
+                        //TODO: Fire an error event to AS & stop playing too
+                        tracing::error!("MP4 parsing failed: {}", e);
+                        write.preload_offset = 3;
+                        false
+                    }
+                }
+            }
             Some(magic) => {
                 //Unrecognized signature
                 //TODO: Fire an error event to AS & stop playing too
@@ -810,6 +1298,54 @@ impl<'gc> NetStream<'gc> {
         }
     }
 
+    /// Send the `onMetaData` of an MP4 stream to its client.
+    ///
+    /// MP4 files don't carry script data like FLVs do, so we make it up from
+    /// what the movie box describes.
+    fn mp4_metadata(self, context: &mut UpdateContext<'_, 'gc>) {
+        let read = self.0.read();
+        let Some(NetStreamType::Mp4 { movie, .. }) = &read.stream_type else {
+            return;
+        };
+
+        let mut metadata = vec![FlvVariable {
+            name: b"duration",
+            data: FlvValue::Number(movie.duration / 1000.0),
+        }];
+        if let Some(video) = &movie.video {
+            metadata.push(FlvVariable {
+                name: b"videocodecid",
+                data: FlvValue::String(b"avc1"),
+            });
+            if let Some((width, height)) = video.size {
+                metadata.push(FlvVariable {
+                    name: b"width",
+                    data: FlvValue::Number(width.into()),
+                });
+                metadata.push(FlvVariable {
+                    name: b"height",
+                    data: FlvValue::Number(height.into()),
+                });
+            }
+        }
+        if movie.audio.is_some() {
+            metadata.push(FlvVariable {
+                name: b"audiocodecid",
+                data: FlvValue::String(b"mp4a"),
+            });
+        }
+
+        let avm_object = read.avm_object;
+        drop(read);
+        // Any errors while trying to lookup or call AVM2 properties are silently swallowed.
+        let _ = self.handle_script_data(
+            avm_object,
+            context,
+            b"onMetaData",
+            FlvValue::Object(metadata),
+        );
+    }
+
     /// Process a parsed FLV video tag.
     ///
     /// `write` must be an active borrow of the current `NetStream`. `slice`
@@ -857,7 +1393,7 @@ impl<'gc> NetStream<'gc> {
                             1,
                             (8, 8),
                             codec,
-                            VideoDeblocking::UseVideoPacketValue,
+                            write.deblocking,
                         ) {
                             Ok(new_handle) => {
                                 match &mut write.stream_type {
@@ -929,15 +1465,19 @@ impl<'gc> NetStream<'gc> {
             (_, _, FlvVideoPacket::CommandFrame(_command)) => {
                 tracing::warn!("Stub: FLV command frame processing")
             }
-            (_, _, FlvVideoPacket::AvcSequenceHeader(_data)) => {
-                tracing::warn!("Stub: FLV AVC/H.264 Sequence Header processing")
-            }
-            (_, _, FlvVideoPacket::AvcNalu { .. }) => {
-                tracing::warn!("Stub: FLV AVC/H.264 NALU processing")
+            (_, _, FlvVideoPacket::AvcSequenceHeader(config)) => {
+                if let Err(e) =
+                    Self::register_media_decoder(context, write, MediaCodec::H264, config)
+                {
+                    tracing::error!("Got error when registering H.264 decoder: {}", e);
+                }
             }
-            (_, _, FlvVideoPacket::AvcEndOfSequence) => {
-                tracing::warn!("Stub: FLV AVC/H.264 End of Sequence processing")
+            (_, _, FlvVideoPacket::AvcNalu { data, .. }) => {
+                if let Err(e) = Self::h264_sample(context, write, data) {
+                    tracing::error!("Decoding H.264 frame {} failed: {}", frame_id, e);
+                }
             }
+            (_, _, FlvVideoPacket::AvcEndOfSequence) => {}
             (_, None, _) => {
                 tracing::error!(
                     "FLV video tag has invalid codec id {}",
@@ -1025,7 +1565,7 @@ impl<'gc> NetStream<'gc> {
                         num_frames as u32,
                         (width as u16, height as u16),
                         video_codec,
-                        VideoDeblocking::UseVideoPacketValue,
+                        write.deblocking,
                     ) {
                         Ok(stream_handle) => match &mut write.stream_type {
                             Some(NetStreamType::Flv { video_stream, .. }) => {
@@ -1073,6 +1613,8 @@ impl<'gc> NetStream<'gc> {
         let buffer = slice.data();
 
         let end_time = write.stream_time + dt;
+        let mut last_tag_time = None;
+        let mut starved = false;
         let mut end_of_video = false;
         let mut error = false;
         let mut max_lookahead_audio_tags = 5;
@@ -1089,7 +1631,14 @@ impl<'gc> NetStream<'gc> {
                     // after the end & won't ever be set back. We don't want
                     // error states or playback ending to trip until we run
                     // those tags "for realsies"
-                    if !is_lookahead_tag && matches!(e, FlvError::EndOfData) {
+                    if !is_lookahead_tag
+                        && matches!(e, FlvError::EndOfData)
+                        && write.data_generation
+                        && !write.end_of_sequence
+                    {
+                        // More data may yet be appended, so wait for it.
+                        starved = true;
+                    } else if !is_lookahead_tag && matches!(e, FlvError::EndOfData) {
                         //TODO: Check expected total length for streaming / progressive download
                         end_of_video = true;
                     } else if !is_lookahead_tag {
@@ -1102,7 +1651,13 @@ impl<'gc> NetStream<'gc> {
                 }
 
                 let tag = tag.expect("valid tag");
-                is_lookahead_tag = tag.timestamp as f64 >= end_time; //FLV timestamps are also ms
+                if write.timescale_discontinuity {
+                    write.timestamp_offset = write.stream_time - tag.timestamp as f64;
+                    write.timescale_discontinuity = false;
+                }
+
+                let tag_time = tag.timestamp as f64 + write.timestamp_offset; //FLV timestamps are also ms
+                is_lookahead_tag = tag_time >= end_time;
                 if is_lookahead_tag && max_lookahead_audio_tags == 0 {
                     break;
                 }
@@ -1117,7 +1672,8 @@ impl<'gc> NetStream<'gc> {
                             max_lookahead_audio_tags -= 1;
                         }
 
-                        if let Err(e) = self.flv_audio_tag(&mut write, &slice, audio_data) {
+                        if let Err(e) = self.flv_audio_tag(context, &mut write, &slice, audio_data)
+                        {
                             //TODO: Fire an error event at AS.
                             tracing::error!("Error committing sound stream: {}", e);
                         }
@@ -1141,6 +1697,7 @@ impl<'gc> NetStream<'gc> {
                 }
 
                 if !is_lookahead_tag {
+                    last_tag_time = Some(tag_time);
                     write.offset = reader
                         .stream_position()
                         .expect("FLV reader stream position")
@@ -1148,33 +1705,81 @@ impl<'gc> NetStream<'gc> {
                     write.preload_offset = max(write.offset, write.preload_offset);
                 }
             }
+        } else if let Some(NetStreamType::Mp4 {
+            movie,
+            mut next_sample,
+        }) = write.stream_type.take()
+        {
+            let mut index = next_sample;
+
+            loop {
+                let Some(sample) = movie.samples.get(index) else {
+                    end_of_video = !is_lookahead_tag;
+                    break;
+                };
+                index += 1;
+
+                is_lookahead_tag = sample.time >= end_time;
+                if is_lookahead_tag && max_lookahead_audio_tags == 0 {
+                    break;
+                }
+
+                let Some(data) =
+                    buffer.get(sample.offset..sample.offset.saturating_add(sample.size))
+                else {
+                    // Wait for the rest of the file to download.
+                    starved = !is_lookahead_tag;
+                    break;
+                };
+
+                let result = match sample.track {
+                    Mp4TrackKind::Audio if write.media_audio.is_some() => {
+                        if is_lookahead_tag {
+                            max_lookahead_audio_tags -= 1;
+                        }
+
+                        Self::aac_sample(context, &mut write, &slice, data)
+                    }
+                    Mp4TrackKind::Video if write.media_video.is_some() && !is_lookahead_tag => {
+                        Self::h264_sample(context, &mut write, data)
+                    }
+                    Mp4TrackKind::Audio | Mp4TrackKind::Video => Ok(()),
+                };
+                if let Err(e) = result {
+                    tracing::error!("Decoding MP4 sample failed: {}", e);
+                }
+
+                if !is_lookahead_tag {
+                    last_tag_time = Some(sample.time);
+                    next_sample = index;
+                }
+            }
+
+            write.stream_type = Some(NetStreamType::Mp4 { movie, next_sample });
         }
 
-        write.stream_time = end_time;
+        write.stream_time = if starved {
+            // Time stands still until there's something to play.
+            last_tag_time.map_or(write.stream_time, |time| time.max(write.stream_time))
+        } else {
+            end_time
+        };
         if let Err(e) = self.commit_sound_stream(context, &mut write) {
             //TODO: Fire an error event at AS.
             tracing::error!("Error committing sound stream: {}", e);
         }
+        Self::trim_appended_buffer(&mut write);
         drop(write);
 
         if end_of_video {
-            self.trigger_status_event(
-                context,
-                vec![("code", "NetStream.Buffer.Flush"), ("level", "status")],
-            );
-            self.trigger_status_event(
-                context,
-                vec![("code", "NetStream.Play.Stop"), ("level", "status")],
-            );
-            self.trigger_status_event(
-                context,
-                vec![("code", "NetStream.Buffer.Empty"), ("level", "status")],
-            );
+            self.trigger_status_event(context, NetStatus::StreamBufferFlush, None);
+            self.trigger_status_event(context, NetStatus::StreamPlayStop, None);
+            self.trigger_status_event(context, NetStatus::StreamBufferEmpty, None);
             self.pause(context, false);
         }
 
         if error {
-            //TODO: Fire an error event at AS.
+            self.trigger_status_event(context, NetStatus::StreamPlayFileStructureInvalid, None);
             self.pause(context, false);
         }
     }
@@ -1187,8 +1792,10 @@ impl<'gc> NetStream<'gc> {
     pub fn trigger_status_event(
         self,
         context: &mut UpdateContext<'_, 'gc>,
-        values: Vec<(impl Into<AvmString<'gc>>, impl Into<AvmString<'gc>>)>,
+        status: NetStatus,
+        description: Option<AvmString<'gc>>,
     ) {
+        let values = status.info(description);
         let object = self.0.read().avm_object;
         match object {
             Some(AvmObject::Avm1(object)) => {
@@ -1204,7 +1811,7 @@ impl<'gc> NetStream<'gc> {
 
                 for (key, value) in values {
                     info_object
-                        .set(key.into(), Avm1Value::String(value.into()), &mut activation)
+                        .set(key, Avm1Value::String(value), &mut activation)
                         .expect("valid set");
                 }
 
@@ -1284,3 +1891,39 @@ impl<'gc> NetStream<'gc> {
         Ok(())
     }
 }
+
+/// Lower the sample rate of decoded audio to one that a `SoundFormat` can hold.
+///
+/// AAC goes up to 96 kHz, while SWF sounds can't go past 65535 Hz, so such audio
+/// has each run of frames averaged into one.
+fn fit_sample_rate(audio: DecodedAudio) -> DecodedAudio {
+    let max_rate = u32::from(u16::MAX);
+    if audio.sample_rate <= max_rate || audio.num_channels == 0 {
+        return audio;
+    }
+
+    let factor = audio.sample_rate.div_ceil(max_rate) as usize;
+    let channels = usize::from(audio.num_channels);
+    let samples = audio
+        .samples
+        .chunks(factor * channels)
+        .flat_map(|frames| {
+            let num_frames = (frames.len() / channels).max(1) as i32;
+            (0..channels).map(move |channel| {
+                let sum: i32 = frames
+                    .iter()
+                    .skip(channel)
+                    .step_by(channels)
+                    .map(|&sample| i32::from(sample))
+                    .sum();
+                (sum / num_frames) as i16
+            })
+        })
+        .collect();
+
+    DecodedAudio {
+        sample_rate: audio.sample_rate / factor as u32,
+        num_channels: audio.num_channels,
+        samples,
+    }
+}
@@ -0,0 +1,774 @@
+//! Just enough of an MP4 demuxer to play the H.264 and AAC tracks of MP4 and
+//! F4V files.
+//!
+//! Only the sample tables of the `moov` box are read, so fragmented files are
+//! not supported.
+
+use ruffle_video::media::MediaCodec;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Mp4Error {
+    /// The `moov` box hasn't been loaded yet.
+    #[error("End of data")]
+    EndOfData,
+
+    #[error("Invalid {0} box")]
+    InvalidBox(&'static str),
+
+    #[error("No playable tracks")]
+    NoTracks,
+}
+
+/// Whether `data` starts like an MP4 file.
+pub fn is_mp4(data: &[u8]) -> bool {
+    data.get(4..8) == Some(b"ftyp")
+}
+
+/// A track whose codec we can decode.
+#[derive(Clone, Debug)]
+pub struct Mp4Track {
+    pub codec: MediaCodec,
+
+    /// The codec's decoder configuration, from the `avcC` or `esds` box.
+    pub config: Vec<u8>,
+
+    /// The size of the video, for video tracks.
+    pub size: Option<(u16, u16)>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TrackKind {
+    Video,
+    Audio,
+}
+
+/// A single sample of one of the tracks.
+#[derive(Clone, Copy, Debug)]
+pub struct Mp4Sample {
+    pub track: TrackKind,
+
+    /// The position of the sample in the file.
+    pub offset: usize,
+    pub size: usize,
+
+    /// The decoding time of the sample in milliseconds.
+    pub time: f64,
+
+    /// Whether decoding can start at this sample.
+    pub is_sync: bool,
+}
+
+/// The playable parts of an MP4 file.
+#[derive(Clone, Debug)]
+pub struct Mp4Movie {
+    pub video: Option<Mp4Track>,
+    pub audio: Option<Mp4Track>,
+
+    /// The samples of both tracks, in the order they should be decoded.
+    pub samples: Vec<Mp4Sample>,
+
+    /// The duration of the longest track in milliseconds.
+    pub duration: f64,
+}
+
+impl Mp4Movie {
+    /// Parse the `moov` box of an MP4 file.
+    ///
+    /// Yields `EndOfData` if the file hasn't been loaded up to the end of its
+    /// `moov` box yet, which may be at the end of the file.
+    pub fn parse(data: &[u8]) -> Result<Self, Mp4Error> {
+        let mut rest = data;
+        let moov = loop {
+            let (name, contents, next) = next_box(rest)?.ok_or(Mp4Error::EndOfData)?;
+            if name == *b"moov" {
+                break contents;
+            }
+            rest = next;
+        };
+
+        let mut movie = Self {
+            video: None,
+            audio: None,
+            samples: vec![],
+            duration: 0.0,
+        };
+
+        let file_len = declared_len(data);
+        for trak in child_boxes(moov, b"trak")? {
+            let Some(track) = parse_track(trak, file_len)? else {
+                continue;
+            };
+            let slot = match track.kind {
+                TrackKind::Video => &mut movie.video,
+                TrackKind::Audio => &mut movie.audio,
+            };
+            if slot.is_some() {
+                // Flash Player only plays the first track of each kind.
+                continue;
+            }
+            *slot = Some(track.track);
+            movie.duration = movie.duration.max(track.duration);
+            movie.samples.extend(track.samples);
+        }
+
+        if movie.video.is_none() && movie.audio.is_none() {
+            return Err(Mp4Error::NoTracks);
+        }
+
+        movie
+            .samples
+            .sort_by(|a, b| a.time.total_cmp(&b.time).then(a.offset.cmp(&b.offset)));
+        Ok(movie)
+    }
+
+    /// The index of the sample to start playing from to seek to `time`.
+    ///
+    /// This is the last sync sample of the video track at or before `time`,
+    /// or the last audio sample if there is no video.
+    pub fn seek_index(&self, time: f64) -> usize {
+        let seek_track = if self.video.is_some() {
+            TrackKind::Video
+        } else {
+            TrackKind::Audio
+        };
+
+        self.samples
+            .iter()
+            .rposition(|sample| sample.track == seek_track && sample.is_sync && sample.time <= time)
+            .unwrap_or(0)
+    }
+}
+
+struct ParsedTrack {
+    kind: TrackKind,
+    track: Mp4Track,
+    samples: Vec<Mp4Sample>,
+    duration: f64,
+}
+
+/// Parse a `trak` box, if it's a track we can play.
+///
+/// `file_len` is how long the file claims to be, which its samples must fit in.
+fn parse_track(trak: &[u8], file_len: u64) -> Result<Option<ParsedTrack>, Mp4Error> {
+    let mdia = child_box(trak, b"mdia")?.ok_or(Mp4Error::InvalidBox("trak"))?;
+
+    let hdlr = child_box(mdia, b"hdlr")?.ok_or(Mp4Error::InvalidBox("mdia"))?;
+    let kind = match hdlr.get(8..12) {
+        Some(b"vide") => TrackKind::Video,
+        Some(b"soun") => TrackKind::Audio,
+        _ => return Ok(None),
+    };
+
+    let mdhd = child_box(mdia, b"mdhd")?.ok_or(Mp4Error::InvalidBox("mdia"))?;
+    let mut reader = BoxReader::new(mdhd, "mdhd");
+    let (timescale, duration) = if reader.read_u8()? == 1 {
+        reader.skip(3 + 16)?;
+        (reader.read_u32()?, reader.read_u64()?)
+    } else {
+        reader.skip(3 + 8)?;
+        (reader.read_u32()?, reader.read_u32()?.into())
+    };
+    if timescale == 0 {
+        return Err(Mp4Error::InvalidBox("mdhd"));
+    }
+    let to_ms = |time: u64| time as f64 * 1000.0 / timescale as f64;
+
+    let stbl = child_box(mdia, b"minf")?
+        .map(|minf| child_box(minf, b"stbl"))
+        .transpose()?
+        .flatten()
+        .ok_or(Mp4Error::InvalidBox("minf"))?;
+
+    let stsd = child_box(stbl, b"stsd")?.ok_or(Mp4Error::InvalidBox("stbl"))?;
+    let Some(track) = parse_sample_description(stsd)? else {
+        return Ok(None);
+    };
+
+    let sizes = parse_sample_sizes(stbl, file_len)?;
+    let offsets = parse_sample_offsets(stbl, &sizes)?;
+    let times = parse_sample_times(stbl, sizes.len())?;
+    let sync_samples = child_box(stbl, b"stss")?
+        .map(|stss| {
+            let mut reader = BoxReader::new(stss, "stss");
+            reader.skip(4)?;
+            let count = reader.read_u32()?;
+            reader.check_entries(count, 4)?;
+            (0..count)
+                .map(|_| reader.read_u32())
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .transpose()?;
+
+    let samples = sizes
+        .into_iter()
+        .zip(offsets)
+        .zip(times)
+        .enumerate()
+        .map(|(index, ((size, offset), time))| Mp4Sample {
+            track: kind,
+            offset,
+            size,
+            time: to_ms(time),
+            // Sample numbers count from 1, and every sample is a sync sample
+            // if there is no table of them.
+            is_sync: sync_samples
+                .as_ref()
+                .map_or(true, |sync| sync.binary_search(&(index as u32 + 1)).is_ok()),
+        })
+        .collect();
+
+    Ok(Some(ParsedTrack {
+        kind,
+        track,
+        samples,
+        duration: to_ms(duration),
+    }))
+}
+
+/// Parse the first entry of a `stsd` box, if it's of a codec we can decode.
+fn parse_sample_description(stsd: &[u8]) -> Result<Option<Mp4Track>, Mp4Error> {
+    let entries = stsd.get(8..).ok_or(Mp4Error::InvalidBox("stsd"))?;
+    let Some((name, entry, _)) = next_box(entries)? else {
+        return Err(Mp4Error::InvalidBox("stsd"));
+    };
+
+    match &name {
+        b"avc1" | b"avc3" => {
+            let mut reader = BoxReader::new(entry, "avc1");
+            reader.skip(24)?;
+            let width = reader.read_u16()?;
+            let height = reader.read_u16()?;
+            let children = entry.get(78..).ok_or(Mp4Error::InvalidBox("avc1"))?;
+            let config = child_box(children, b"avcC")?.ok_or(Mp4Error::InvalidBox("avc1"))?;
+            Ok(Some(Mp4Track {
+                codec: MediaCodec::H264,
+                config: config.to_vec(),
+                size: Some((width, height)),
+            }))
+        }
+        b"mp4a" => {
+            let mut reader = BoxReader::new(entry, "mp4a");
+            reader.skip(8)?;
+            // QuickTime sound descriptions carry more fields in later versions.
+            let children_start = match reader.read_u16()? {
+                1 => 28 + 16,
+                2 => 28 + 36,
+                _ => 28,
+            };
+            let children = entry
+                .get(children_start..)
+                .ok_or(Mp4Error::InvalidBox("mp4a"))?;
+            let esds = child_box(children, b"esds")?.ok_or(Mp4Error::InvalidBox("mp4a"))?;
+            Ok(parse_esds(esds)?.map(|config| Mp4Track {
+                codec: MediaCodec::Aac,
+                config,
+                size: None,
+            }))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Find the `AudioSpecificConfig` in an `esds` box, if it describes AAC.
+fn parse_esds(esds: &[u8]) -> Result<Option<Vec<u8>>, Mp4Error> {
+    let mut reader = BoxReader::new(esds, "esds");
+    reader.skip(4)?;
+
+    // ES_Descriptor
+    if reader.read_descriptor_header()? != 0x03 {
+        return Err(Mp4Error::InvalidBox("esds"));
+    }
+    reader.skip(2)?;
+    let flags = reader.read_u8()?;
+    if flags & 0x80 != 0 {
+        reader.skip(2)?;
+    }
+    if flags & 0x40 != 0 {
+        let url_len = reader.read_u8()?;
+        reader.skip(url_len.into())?;
+    }
+    if flags & 0x20 != 0 {
+        reader.skip(2)?;
+    }
+
+    // DecoderConfigDescriptor
+    if reader.read_descriptor_header()? != 0x04 {
+        return Err(Mp4Error::InvalidBox("esds"));
+    }
+    let object_type = reader.read_u8()?;
+    if object_type != 0x40 && object_type != 0x66 && object_type != 0x67 {
+        // Not MPEG-4 or MPEG-2 AAC.
+        return Ok(None);
+    }
+    reader.skip(12)?;
+
+    // DecoderSpecificInfo, whose length we need.
+    if reader.read_u8()? != 0x05 {
+        return Err(Mp4Error::InvalidBox("esds"));
+    }
+    let len = reader.read_descriptor_len()?;
+    Ok(Some(reader.read_bytes(len)?.to_vec()))
+}
+
+/// Find the size of each sample.
+///
+/// The sample count is checked against what the box and the file can hold before anything
+/// is allocated for it, as it may be made up.
+fn parse_sample_sizes(stbl: &[u8], file_len: u64) -> Result<Vec<usize>, Mp4Error> {
+    let stsz = child_box(stbl, b"stsz")?.ok_or(Mp4Error::InvalidBox("stbl"))?;
+    let mut reader = BoxReader::new(stsz, "stsz");
+    reader.skip(4)?;
+    let sample_size = reader.read_u32()?;
+    let count = reader.read_u32()?;
+    if sample_size != 0 {
+        // Every sample is the same size, and they all have to fit in the file.
+        if u64::from(count) * u64::from(sample_size) > file_len {
+            return Err(Mp4Error::InvalidBox("stsz"));
+        }
+        return Ok(vec![sample_size as usize; count as usize]);
+    }
+    reader.check_entries(count, 4)?;
+    (0..count)
+        .map(|_| Ok(reader.read_u32()? as usize))
+        .collect()
+}
+
+/// Find where each of the samples of the given sizes is in the file, from the
+/// chunk offsets and the number of samples in each chunk.
+fn parse_sample_offsets(stbl: &[u8], sizes: &[usize]) -> Result<Vec<usize>, Mp4Error> {
+    let chunk_offsets: Vec<u64> = if let Some(stco) = child_box(stbl, b"stco")? {
+        let mut reader = BoxReader::new(stco, "stco");
+        reader.skip(4)?;
+        let chunks = reader.read_u32()?;
+        reader.check_entries(chunks, 4)?;
+        (0..chunks)
+            .map(|_| Ok(reader.read_u32()?.into()))
+            .collect::<Result<_, _>>()?
+    } else if let Some(co64) = child_box(stbl, b"co64")? {
+        let mut reader = BoxReader::new(co64, "co64");
+        reader.skip(4)?;
+        let chunks = reader.read_u32()?;
+        reader.check_entries(chunks, 8)?;
+        (0..chunks)
+            .map(|_| reader.read_u64())
+            .collect::<Result<_, _>>()?
+    } else {
+        return Err(Mp4Error::InvalidBox("stbl"));
+    };
+
+    // Each run of chunks starting at `first_chunk` has the same number of
+    // samples in each chunk.
+    let stsc = child_box(stbl, b"stsc")?.ok_or(Mp4Error::InvalidBox("stbl"))?;
+    let mut reader = BoxReader::new(stsc, "stsc");
+    reader.skip(4)?;
+    let run_count = reader.read_u32()?;
+    reader.check_entries(run_count, 12)?;
+    let runs = (0..run_count)
+        .map(|_| {
+            let first_chunk = reader.read_u32()?;
+            let samples_per_chunk = reader.read_u32()?;
+            reader.skip(4)?;
+            Ok((first_chunk, samples_per_chunk))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut offsets = Vec::with_capacity(sizes.len());
+    for (index, chunk_offset) in chunk_offsets.into_iter().enumerate() {
+        let chunk = index as u32 + 1;
+        let samples_per_chunk = runs
+            .iter()
+            .rev()
+            .find(|(first_chunk, _)| *first_chunk <= chunk)
+            .map_or(0, |(_, samples)| *samples);
+
+        let mut offset = chunk_offset as usize;
+        for _ in 0..samples_per_chunk {
+            let Some(size) = sizes.get(offsets.len()) else {
+                return Ok(offsets);
+            };
+            offsets.push(offset);
+            offset = offset.saturating_add(*size);
+        }
+    }
+
+    if offsets.len() < sizes.len() {
+        return Err(Mp4Error::InvalidBox("stsc"));
+    }
+    Ok(offsets)
+}
+
+/// Find the decoding time of each of `count` samples, in the track's
+/// timescale.
+fn parse_sample_times(stbl: &[u8], count: usize) -> Result<Vec<u64>, Mp4Error> {
+    let stts = child_box(stbl, b"stts")?.ok_or(Mp4Error::InvalidBox("stbl"))?;
+    let mut reader = BoxReader::new(stts, "stts");
+    reader.skip(4)?;
+    let entries = reader.read_u32()?;
+    reader.check_entries(entries, 8)?;
+
+    // `count` has already been checked against the file by `parse_sample_sizes`.
+    let mut times = Vec::with_capacity(count);
+    let mut time = 0u64;
+    for _ in 0..entries {
+        let samples = reader.read_u32()?;
+        let delta = reader.read_u32()?;
+        for _ in 0..samples {
+            if times.len() == count {
+                return Ok(times);
+            }
+            times.push(time);
+            time += u64::from(delta);
+        }
+    }
+
+    // Samples past the end of the table are assumed to be instant.
+    times.resize(count, time);
+    Ok(times)
+}
+
+/// How long the file in `data` says it is, from the sizes of the top-level boxes loaded so far.
+///
+/// This may be more than has been loaded, as later boxes may not have arrived yet.
+fn declared_len(data: &[u8]) -> u64 {
+    let mut len = 0u64;
+    while let Some(rest) = usize::try_from(len).ok().and_then(|len| data.get(len..)) {
+        let Some(size) = rest.get(..4) else {
+            break;
+        };
+        let size = match u32::from_be_bytes(size.try_into().expect("4 bytes")) {
+            // The box extends to the end of the file, however long that is.
+            0 => return u64::MAX,
+            1 => match rest.get(8..16) {
+                Some(size) => u64::from_be_bytes(size.try_into().expect("8 bytes")),
+                None => break,
+            },
+            size => size.into(),
+        };
+        if size < 8 {
+            break;
+        }
+        len = len.saturating_add(size);
+    }
+    len.max(data.len() as u64)
+}
+
+/// The name and contents of a box, and the data following it.
+type Mp4Box<'a> = ([u8; 4], &'a [u8], &'a [u8]);
+
+/// Split the next box off of `data`, returning its name, its contents and
+/// what follows it.
+///
+/// Returns `None` if there's no more data, and `EndOfData` if the box isn't
+/// loaded in full.
+fn next_box(data: &[u8]) -> Result<Option<Mp4Box<'_>>, Mp4Error> {
+    if data.is_empty() {
+        return Ok(None);
+    }
+    let header = data.get(..8).ok_or(Mp4Error::EndOfData)?;
+    let name = [header[4], header[5], header[6], header[7]];
+    let (header_len, len) = match u32::from_be_bytes([header[0], header[1], header[2], header[3]]) {
+        // The box extends to the end of the file.
+        0 => (8, data.len()),
+        1 => {
+            let len = data.get(8..16).ok_or(Mp4Error::EndOfData)?;
+            let len = u64::from_be_bytes(len.try_into().expect("8 bytes"));
+            (16, usize::try_from(len).map_err(|_| Mp4Error::EndOfData)?)
+        }
+        len => (8, len as usize),
+    };
+    if len < header_len {
+        return Err(Mp4Error::InvalidBox("top-level"));
+    }
+
+    let contents = data.get(header_len..len).ok_or(Mp4Error::EndOfData)?;
+    Ok(Some((name, contents, &data[len..])))
+}
+
+/// All the boxes named `name` directly inside of `parent`.
+fn child_boxes<'a>(parent: &'a [u8], name: &[u8; 4]) -> Result<Vec<&'a [u8]>, Mp4Error> {
+    let mut children = vec![];
+    let mut rest = parent;
+    // Boxes inside of a loaded box are always loaded in full.
+    while let Some((child_name, contents, next)) = next_box(rest).map_err(truncated)? {
+        if child_name == *name {
+            children.push(contents);
+        }
+        rest = next;
+    }
+    Ok(children)
+}
+
+/// The first box named `name` directly inside of `parent`.
+fn child_box<'a>(parent: &'a [u8], name: &[u8; 4]) -> Result<Option<&'a [u8]>, Mp4Error> {
+    Ok(child_boxes(parent, name)?.into_iter().next())
+}
+
+fn truncated(error: Mp4Error) -> Mp4Error {
+    match error {
+        Mp4Error::EndOfData => Mp4Error::InvalidBox("nested"),
+        error => error,
+    }
+}
+
+/// Reads the fields of a box, which are all big-endian.
+struct BoxReader<'a> {
+    data: &'a [u8],
+    name: &'static str,
+}
+
+impl<'a> BoxReader<'a> {
+    fn new(data: &'a [u8], name: &'static str) -> Self {
+        Self { data, name }
+    }
+
+    fn read_bytes(&mut self, len: usize) -> Result<&'a [u8], Mp4Error> {
+        let bytes = self
+            .data
+            .get(..len)
+            .ok_or(Mp4Error::InvalidBox(self.name))?;
+        self.data = &self.data[len..];
+        Ok(bytes)
+    }
+
+    /// Check that the rest of the box can hold `count` entries of `entry_len` bytes each.
+    fn check_entries(&self, count: u32, entry_len: usize) -> Result<(), Mp4Error> {
+        if count as usize > self.data.len() / entry_len {
+            return Err(Mp4Error::InvalidBox(self.name));
+        }
+        Ok(())
+    }
+
+    fn skip(&mut self, len: usize) -> Result<(), Mp4Error> {
+        self.read_bytes(len).map(|_| ())
+    }
+
+    fn read_u8(&mut self) -> Result<u8, Mp4Error> {
+        Ok(self.read_bytes(1)?[0])
+    }
+
+    fn read_u16(&mut self) -> Result<u16, Mp4Error> {
+        let bytes = self.read_bytes(2)?;
+        Ok(u16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn read_u32(&mut self) -> Result<u32, Mp4Error> {
+        let bytes = self.read_bytes(4)?;
+        Ok(u32::from_be_bytes(bytes.try_into().expect("4 bytes")))
+    }
+
+    fn read_u64(&mut self) -> Result<u64, Mp4Error> {
+        let bytes = self.read_bytes(8)?;
+        Ok(u64::from_be_bytes(bytes.try_into().expect("8 bytes")))
+    }
+
+    /// Read the length of an MPEG-4 descriptor, which is stored 7 bits at a
+    /// time.
+    fn read_descriptor_len(&mut self) -> Result<usize, Mp4Error> {
+        let mut len = 0;
+        for _ in 0..4 {
+            let byte = self.read_u8()?;
+            len = len << 7 | (byte & 0x7f) as usize;
+            if byte & 0x80 == 0 {
+                break;
+            }
+        }
+        Ok(len)
+    }
+
+    /// Read the tag of an MPEG-4 descriptor, skipping its length.
+    fn read_descriptor_header(&mut self) -> Result<u8, Mp4Error> {
+        let tag = self.read_u8()?;
+        self.read_descriptor_len()?;
+        Ok(tag)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mp4_box(name: &[u8; 4], contents: &[u8]) -> Vec<u8> {
+        let mut data = (contents.len() as u32 + 8).to_be_bytes().to_vec();
+        data.extend_from_slice(name);
+        data.extend_from_slice(contents);
+        data
+    }
+
+    /// A box starting with a version and flags, followed by `u32` fields.
+    fn full_box(name: &[u8; 4], fields: &[u32]) -> Vec<u8> {
+        let mut contents = vec![0; 4];
+        for field in fields {
+            contents.extend_from_slice(&field.to_be_bytes());
+        }
+        mp4_box(name, &contents)
+    }
+
+    /// A video track of 16x16 frames, with samples described by the given `stbl` boxes.
+    fn video_track(tables: &[Vec<u8>]) -> Vec<u8> {
+        let mut hdlr = vec![0; 8];
+        hdlr.extend_from_slice(b"vide");
+        hdlr.extend_from_slice(&[0; 13]);
+
+        // Version 0, times, a timescale of 1000 and a duration of 3000.
+        let mut mdhd = vec![0; 12];
+        mdhd.extend_from_slice(&1000u32.to_be_bytes());
+        mdhd.extend_from_slice(&3000u32.to_be_bytes());
+        mdhd.extend_from_slice(&[0; 4]);
+
+        let mut avc1 = vec![0; 24];
+        avc1.extend_from_slice(&16u16.to_be_bytes());
+        avc1.extend_from_slice(&16u16.to_be_bytes());
+        avc1.resize(78, 0);
+        avc1.extend(mp4_box(b"avcC", &[1, 0x42, 0, 0x1e, 0xff, 0xe0, 0]));
+        let mut stsd = vec![0, 0, 0, 0, 0, 0, 0, 1];
+        stsd.extend(mp4_box(b"avc1", &avc1));
+
+        let mut stbl = mp4_box(b"stsd", &stsd);
+        for table in tables {
+            stbl.extend_from_slice(table);
+        }
+        let minf = mp4_box(b"stbl", &stbl);
+
+        let mut mdia = mp4_box(b"hdlr", &hdlr);
+        mdia.extend(mp4_box(b"mdhd", &mdhd));
+        mdia.extend(mp4_box(b"minf", &minf));
+        mp4_box(b"trak", &mp4_box(b"mdia", &mdia))
+    }
+
+    fn movie(tracks: &[Vec<u8>], mdat_len: usize) -> Vec<u8> {
+        let mut data = mp4_box(b"ftyp", b"isom\0\0\0\0");
+        data.extend(mp4_box(b"moov", &tracks.concat()));
+        data.extend(mp4_box(b"mdat", &vec![0; mdat_len]));
+        data
+    }
+
+    #[test]
+    fn parses_sample_tables() {
+        let data = movie(
+            &[video_track(&[
+                // Three samples of 10, 20 and 30 bytes.
+                full_box(b"stsz", &[0, 3, 10, 20, 30]),
+                // Two chunks, of two samples and then one.
+                full_box(b"stsc", &[2, 1, 2, 1, 2, 1, 1]),
+                full_box(b"stco", &[2, 100, 200]),
+                // A sample every second.
+                full_box(b"stts", &[1, 3, 1000]),
+                // Only the first and last samples are sync samples.
+                full_box(b"stss", &[2, 1, 3]),
+            ])],
+            200,
+        );
+        let movie = Mp4Movie::parse(&data).unwrap();
+
+        let video = movie.video.as_ref().unwrap();
+        assert_eq!(video.codec, MediaCodec::H264);
+        assert_eq!(video.size, Some((16, 16)));
+        assert!(movie.audio.is_none());
+        assert_eq!(movie.duration, 3000.0);
+
+        let samples: Vec<_> = movie
+            .samples
+            .iter()
+            .map(|sample| (sample.offset, sample.size, sample.time, sample.is_sync))
+            .collect();
+        assert_eq!(
+            samples,
+            vec![
+                (100, 10, 0.0, true),
+                (110, 20, 1000.0, false),
+                (200, 30, 2000.0, true)
+            ]
+        );
+        assert_eq!(movie.seek_index(1500.0), 0);
+        assert_eq!(movie.seek_index(2000.0), 2);
+    }
+
+    #[test]
+    fn waits_for_movie_box() {
+        let data = movie(&[video_track(&[])], 0);
+        let moov_end = data.len() - 8;
+        assert!(matches!(
+            Mp4Movie::parse(&data[..moov_end - 1]),
+            Err(Mp4Error::EndOfData)
+        ));
+        assert!(matches!(
+            Mp4Movie::parse(&data[..16]),
+            Err(Mp4Error::EndOfData)
+        ));
+    }
+
+    #[test]
+    fn rejects_sample_counts_that_cannot_fit() {
+        let tables = |stsz| {
+            vec![
+                stsz,
+                full_box(b"stsc", &[1, 1, 1, 1]),
+                full_box(b"stco", &[1, 100]),
+                full_box(b"stts", &[1, 1, 1000]),
+            ]
+        };
+
+        // Billions of samples of the same size can't fit in this file.
+        let data = movie(
+            &[video_track(&tables(full_box(b"stsz", &[4, u32::MAX])))],
+            16,
+        );
+        assert!(matches!(
+            Mp4Movie::parse(&data),
+            Err(Mp4Error::InvalidBox("stsz"))
+        ));
+
+        // The table of sample sizes is shorter than its count.
+        let data = movie(
+            &[video_track(&tables(full_box(b"stsz", &[0, u32::MAX, 10])))],
+            16,
+        );
+        assert!(matches!(
+            Mp4Movie::parse(&data),
+            Err(Mp4Error::InvalidBox("stsz"))
+        ));
+
+        // So is the table of sample times.
+        let data = movie(
+            &[video_track(&[
+                full_box(b"stsz", &[0, 1, 10]),
+                full_box(b"stsc", &[1, 1, 1, 1]),
+                full_box(b"stco", &[1, 100]),
+                full_box(b"stts", &[u32::MAX, 1, 1000]),
+            ])],
+            16,
+        );
+        assert!(matches!(
+            Mp4Movie::parse(&data),
+            Err(Mp4Error::InvalidBox("stts"))
+        ));
+    }
+
+    #[test]
+    fn parses_aac_configuration() {
+        // An ES_Descriptor holding a DecoderConfigDescriptor for MPEG-4 audio,
+        // then the AudioSpecificConfig of AAC-LC at 44.1kHz in stereo.
+        let mut esds = vec![0, 0, 0, 0, 0x03, 25, 0, 1, 0];
+        esds.extend_from_slice(&[0x04, 17, 0x40, 0x15, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+        esds.extend_from_slice(&[0x05, 2, 0x12, 0x10, 0x06, 1, 2]);
+        assert_eq!(parse_esds(&esds).unwrap(), Some(vec![0x12, 0x10]));
+
+        // Other kinds of MPEG-4 audio, such as MP3, aren't AAC.
+        esds[11] = 0x6b;
+        assert_eq!(parse_esds(&esds).unwrap(), None);
+    }
+
+    #[test]
+    fn declared_length_includes_unloaded_boxes() {
+        let data = movie(&[], 1000);
+        assert_eq!(declared_len(&data), data.len() as u64);
+        let header_end = data.len() - 1000;
+        assert_eq!(declared_len(&data[..header_end]), data.len() as u64);
+        // Without the size of the last box, only what's loaded is known.
+        assert_eq!(
+            declared_len(&data[..header_end - 5]),
+            (header_end - 5) as u64
+        );
+    }
+}
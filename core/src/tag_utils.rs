@@ -1,9 +1,19 @@
 use gc_arena::Collect;
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use swf::{CharacterId, Fixed8, HeaderExt, Rectangle, TagCode, Twips};
 use thiserror::Error;
 use url::Url;
 
+mod abc_dump;
+mod build_info;
+mod movie_stats;
+mod symbol_export;
+
+pub use abc_dump::{disassemble_abc, AbcBlock};
+pub use build_info::{BuildInfo, Telemetry};
+pub use movie_stats::SwfMovieStats;
+
 #[derive(Error, Debug)]
 pub enum Error {
     #[error("Couldn't read SWF: {0}")]
@@ -23,6 +33,9 @@ pub enum Error {
 
     #[error("Invalid SWF url")]
     InvalidSwfUrl,
+
+    #[error("Character {0} is not defined in this movie")]
+    CharacterNotFound(CharacterId),
 }
 
 /// Whether or not to end tag decoding.
@@ -34,6 +47,24 @@ pub enum ControlFlow {
     Continue,
 }
 
+/// How to deal with malformed tags when loading a movie.
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TagStrictness {
+    /// Recover as much of the movie as possible.
+    ///
+    /// Tags that fail to parse are skipped with a warning, and a tag that runs past
+    /// the end of a truncated movie is cut short to the data that's there.
+    #[default]
+    Lenient,
+
+    /// Stop at the first malformed tag.
+    ///
+    /// A truncated movie fails to load at all.
+    Strict,
+}
+
 pub type DecodeResult = Result<ControlFlow, Error>;
 pub type SwfStream<'a> = swf::read::Reader<'a>;
 
@@ -63,6 +94,9 @@ pub struct SwfMovie {
 
     /// The compressed length of the entire datastream
     compressed_len: usize,
+
+    /// How malformed tags in this movie are dealt with.
+    strictness: TagStrictness,
 }
 
 impl SwfMovie {
@@ -76,6 +110,7 @@ impl SwfMovie {
             parameters: Vec::new(),
             encoding: swf::UTF_8,
             compressed_len: 0,
+            strictness: TagStrictness::default(),
         }
     }
 
@@ -92,6 +127,7 @@ impl SwfMovie {
             parameters: Vec::new(),
             encoding: swf::UTF_8,
             compressed_len,
+            strictness: TagStrictness::default(),
         }
     }
 
@@ -109,6 +145,7 @@ impl SwfMovie {
             parameters: Vec::new(),
             encoding: swf::UTF_8,
             compressed_len: 0,
+            strictness: TagStrictness::default(),
         }
     }
 
@@ -131,9 +168,21 @@ impl SwfMovie {
         swf_data: &[u8],
         url: String,
         loader_url: Option<String>,
+    ) -> Result<Self, Error> {
+        Self::from_data_with_strictness(swf_data, url, loader_url, TagStrictness::default())
+    }
+
+    /// Construct a movie based on the contents of the SWF datastream, dealing with
+    /// malformed tags as `strictness` says.
+    pub fn from_data_with_strictness(
+        swf_data: &[u8],
+        url: String,
+        loader_url: Option<String>,
+        strictness: TagStrictness,
     ) -> Result<Self, Error> {
         let compressed_len = swf_data.len();
-        let swf_buf = swf::read::decompress_swf(swf_data)?;
+        let mut swf_buf = swf::read::decompress_swf(swf_data)?;
+        check_tag_stream(&mut swf_buf.data, swf_buf.header.version(), strictness)?;
         let encoding = swf::SwfStr::encoding_for_version(swf_buf.header.version());
         let mut movie = Self {
             header: swf_buf.header,
@@ -143,6 +192,7 @@ impl SwfMovie {
             parameters: Vec::new(),
             encoding,
             compressed_len,
+            strictness,
         };
         movie.append_parameters_from_url();
         Ok(movie)
@@ -158,6 +208,7 @@ impl SwfMovie {
             parameters: Vec::new(),
             encoding: swf::UTF_8,
             compressed_len: length,
+            strictness: TagStrictness::default(),
         };
         movie.append_parameters_from_url();
         movie
@@ -179,6 +230,36 @@ impl SwfMovie {
         }
     }
 
+    /// Construct a copy of this movie with a different tag stream, such as a
+    /// trimmed or patched version of `data`.
+    pub fn with_data(&self, data: Vec<u8>, num_frames: u16) -> Self {
+        let mut header = self.header.clone();
+        header.swf_header_mut().num_frames = num_frames;
+        // The stage size and frame rate don't change, so neither does the header's length.
+        let header_len = (self.header.uncompressed_len() as usize).saturating_sub(self.data.len());
+        let uncompressed_len = header_len + data.len();
+        header.set_uncompressed_len(uncompressed_len as i32);
+
+        Self {
+            header,
+            data,
+            url: self.url.clone(),
+            loader_url: self.loader_url.clone(),
+            parameters: self.parameters.clone(),
+            encoding: self.encoding,
+            // Not known until the movie is written out.
+            compressed_len: uncompressed_len,
+            strictness: self.strictness,
+        }
+    }
+
+    /// Serialize this movie back into an SWF file, compressed the same way as
+    /// the movie it was loaded from.
+    pub fn write_to<W: std::io::Write>(&self, output: W) -> Result<(), Error> {
+        swf::write::write_swf_raw_tags(self.header.swf_header(), &self.data, output)?;
+        Ok(())
+    }
+
     pub fn header(&self) -> &HeaderExt {
         &self.header
     }
@@ -192,6 +273,11 @@ impl SwfMovie {
         &self.data
     }
 
+    /// How malformed tags in this movie are dealt with.
+    pub fn strictness(&self) -> TagStrictness {
+        self.strictness
+    }
+
     /// Returns the suggested string encoding for the given SWF version.
     /// For SWF version 6 and higher, this is always UTF-8.
     /// For SWF version 5 and lower, this is locale-dependent,
@@ -255,6 +341,69 @@ impl SwfMovie {
     pub fn frame_rate(&self) -> Fixed8 {
         self.header.frame_rate()
     }
+
+    /// Whether this movie requests hardware acceleration to blit to the screen.
+    pub fn use_direct_blit(&self) -> bool {
+        self.header.use_direct_blit()
+    }
+
+    /// Whether this movie requests hardware acceleration for compositing.
+    pub fn use_gpu(&self) -> bool {
+        self.header.use_gpu()
+    }
+
+    /// Whether this movie should be placed in the network sandbox, rather than the local
+    /// file sandbox, when run locally.
+    pub fn use_network_sandbox(&self) -> bool {
+        self.header.use_network_sandbox()
+    }
+
+    /// The XMP metadata of this movie, from its Metadata tag.
+    pub fn xmp_metadata(&self) -> Option<&str> {
+        self.header.metadata()
+    }
+
+    /// When this movie was made, according to the `xmp:CreateDate` property of its XMP
+    /// metadata. This is usually an ISO 8601 date, such as `2009-06-22T14:09:07-07:00`.
+    pub fn xmp_create_date(&self) -> Option<&str> {
+        xmp_property(self.xmp_metadata()?, "xmp:CreateDate")
+    }
+}
+
+/// Finds the value of a simple XMP property, written either as an attribute of an
+/// `rdf:Description` or as an element of its own.
+fn xmp_property<'a>(xmp: &'a str, name: &str) -> Option<&'a str> {
+    for (start, _) in xmp.match_indices(name) {
+        let before = &xmp[..start];
+        let after = &xmp[start + name.len()..];
+
+        if before.ends_with('<') {
+            // The element may have attributes of its own, such as `rdf:parseType`, but a name
+            // must end there, or it's another property whose name starts with this one.
+            if !after.starts_with(|c: char| c == '>' || c == '/' || c.is_ascii_whitespace()) {
+                continue;
+            }
+            let end_of_tag = after.find('>')?;
+            if after[..end_of_tag].ends_with('/') {
+                // An empty element has no value.
+                continue;
+            }
+            let value = &after[end_of_tag + 1..];
+            let end = value.find('<')?;
+            return Some(value[..end].trim());
+        }
+
+        if before.ends_with(|c: char| c.is_ascii_whitespace()) {
+            let Some(value) = after.strip_prefix('=') else {
+                continue;
+            };
+            let quote = value.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+            let value = &value[1..];
+            let end = value.find(quote)?;
+            return Some(value[..end].trim());
+        }
+    }
+    None
 }
 
 /// A shared-ownership reference to some portion of an SWF datastream.
@@ -445,6 +594,9 @@ impl SwfSlice {
 /// yielded from the tag callback, and unknown tags. It will *only* return an
 /// error message if the SWF tag itself could not be parsed. Other forms of
 /// irregular decoding will be signalled by returning false.
+///
+/// To recover what's left of a truncated tag, or to stop at the first
+/// malformed one, use `decode_tags_with_strictness`.
 pub fn decode_tags<'a, F>(reader: &mut SwfStream<'a>, mut tag_callback: F) -> Result<bool, Error>
 where
     F: for<'b> FnMut(&'b mut SwfStream<'a>, TagCode, usize) -> Result<ControlFlow, Error>,
@@ -482,3 +634,327 @@ where
 
     Ok(true)
 }
+
+/// Decode tags from a SWF stream reader, dealing with malformed tags as
+/// `strictness` says.
+///
+/// Works like `decode_tags`, except as follows. Decoding will terminate when
+/// the following conditions occur:
+///
+///  * The `tag_callback` calls for the decoding to finish.
+///  * The decoder encounters a tag longer than the underlying SWF slice
+///    (indicated by returning false, or an error when strict)
+///  * The SWF stream is otherwise corrupt or unreadable (indicated as an error
+///    result)
+///  * The `tag_callback` fails, when strict.
+///
+/// When lenient, a tag longer than the SWF slice is still passed to the
+/// callback, cut short to what's left of the slice, and errors yielded from
+/// the callback are logged and skipped over. Unknown tags are always logged.
+/// It will *only* return an error message if the SWF tag itself could not be
+/// parsed. Other forms of irregular decoding will be signalled by returning
+/// false.
+pub fn decode_tags_with_strictness<'a, F>(
+    reader: &mut SwfStream<'a>,
+    strictness: TagStrictness,
+    mut tag_callback: F,
+) -> Result<bool, Error>
+where
+    F: for<'b> FnMut(&'b mut SwfStream<'a>, TagCode, usize) -> Result<ControlFlow, Error>,
+{
+    loop {
+        let (tag_code, tag_len) = reader.read_tag_code_and_length()?;
+        let truncated = tag_len > reader.get_ref().len();
+        if truncated {
+            if strictness == TagStrictness::Strict {
+                tracing::error!("Unexpected EOF when reading tag");
+                *reader.get_mut() = &reader.get_ref()[reader.get_ref().len()..];
+                return Err(
+                    swf::error::Error::invalid_data("Unexpected EOF when reading tag").into(),
+                );
+            }
+            tracing::warn!("Unexpected EOF when reading tag, reading what's left of it");
+        }
+        let tag_len = tag_len.min(reader.get_ref().len());
+
+        let tag_slice = &reader.get_ref()[..tag_len];
+        let end_slice = &reader.get_ref()[tag_len..];
+        if let Some(tag) = TagCode::from_u16(tag_code) {
+            *reader.get_mut() = tag_slice;
+            let result = tag_callback(reader, tag, tag_len);
+
+            match result {
+                Err(e) if strictness == TagStrictness::Strict => {
+                    *reader.get_mut() = end_slice;
+                    return Err(e);
+                }
+                Err(e) => {
+                    tracing::error!("Error running definition tag: {:?}, got {}", tag, e)
+                }
+                Ok(ControlFlow::Exit) => {
+                    *reader.get_mut() = end_slice;
+                    break;
+                }
+                Ok(ControlFlow::Continue) => {}
+            }
+        } else {
+            tracing::warn!("Unknown tag code: {:?}", tag_code);
+        }
+
+        *reader.get_mut() = end_slice;
+        if truncated {
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
+
+/// Checks that the tags of a movie fit within its data.
+///
+/// A truncated movie ends with a tag that claims more data than there is.
+/// When lenient, that tag's length is clamped to what's left and any partial
+/// tag header is dropped, so that the rest of the player can read the movie
+/// as usual. When strict, the movie is rejected.
+fn check_tag_stream(
+    data: &mut Vec<u8>,
+    version: u8,
+    strictness: TagStrictness,
+) -> Result<(), Error> {
+    let mut pos = 0;
+    while pos < data.len() {
+        let mut reader = SwfStream::new(&data[pos..], version);
+        let header = reader.read_tag_code_and_length();
+        let available = reader.get_ref().len();
+        let header_len = data.len() - pos - available;
+
+        let Ok((tag_code, tag_len)) = header else {
+            if strictness == TagStrictness::Strict {
+                return Err(swf::error::Error::invalid_data("Truncated tag header").into());
+            }
+            tracing::warn!("Dropping a truncated tag header at the end of the movie");
+            data.truncate(pos);
+            break;
+        };
+
+        if tag_len > available {
+            if strictness == TagStrictness::Strict {
+                return Err(
+                    swf::error::Error::invalid_data("Unexpected EOF when reading tag").into(),
+                );
+            }
+            tracing::warn!(
+                "Tag {} is {} bytes long, but only {} are left in the movie",
+                tag_code,
+                tag_len,
+                available
+            );
+            // The clamped length is shorter, so it always fits in the existing header.
+            let header = &mut data[pos..pos + header_len];
+            if header_len == 2 {
+                header.copy_from_slice(&((tag_code << 6) | available as u16).to_le_bytes());
+            } else {
+                header[2..].copy_from_slice(&(available as u32).to_le_bytes());
+            }
+            break;
+        }
+
+        if TagCode::from_u16(tag_code) == Some(TagCode::End) {
+            break;
+        }
+        pos += header_len + tag_len;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        decode_tags, decode_tags_with_strictness, xmp_property, ControlFlow, SwfMovie, SwfStream,
+        TagStrictness,
+    };
+    use swf::{Compression, DefineBinaryData, Header, ProductInfo, Tag, TagCode};
+
+    fn write_movie(compression: Compression, tags: &[Tag<'_>]) -> Vec<u8> {
+        let header = Header {
+            compression,
+            num_frames: 1,
+            ..Header::default_with_swf_version(10)
+        };
+        let mut data = vec![];
+        swf::write_swf(&header, tags, &mut data).unwrap();
+        data
+    }
+
+    #[test]
+    fn write_round_trips() {
+        for compression in [Compression::None, Compression::Zlib, Compression::Lzma] {
+            let swf = write_movie(compression, &[Tag::ShowFrame]);
+            let movie = SwfMovie::from_data(&swf, "file:///test.swf".into(), None).unwrap();
+
+            let mut written = vec![];
+            movie.write_to(&mut written).unwrap();
+            let reread = SwfMovie::from_data(&written, "file:///test.swf".into(), None).unwrap();
+            assert_eq!(reread.header().compression(), compression);
+            assert_eq!(reread.data(), movie.data());
+            assert_eq!(reread.uncompressed_len(), movie.uncompressed_len());
+        }
+    }
+
+    #[test]
+    fn build_info() {
+        let product_info = ProductInfo {
+            product_id: 3,
+            edition: 6,
+            major_version: 4,
+            minor_version: 6,
+            build_number: 23201,
+            compilation_date: 1_325_376_000_000,
+        };
+        let tags = [
+            Tag::EnableTelemetry { password_hash: &[] },
+            Tag::ProductInfo(product_info.clone()),
+            Tag::DebugId([0xab; 16]),
+            Tag::ShowFrame,
+        ];
+        let swf = write_movie(Compression::None, &tags);
+        let movie = SwfMovie::from_data(&swf, "file:///test.swf".into(), None).unwrap();
+
+        let info = movie.build_info();
+        assert_eq!(info.product_info, Some(product_info));
+        assert_eq!(
+            info.compiler().as_deref(),
+            Some("Adobe Flex 4.6 build 23201")
+        );
+        assert_eq!(
+            info.debug_id_string().as_deref(),
+            Some("abababab-abab-abab-abab-abababababab")
+        );
+        assert_eq!(info.telemetry.map(|t| t.password_protected), Some(false));
+    }
+
+    #[test]
+    fn xmp_properties() {
+        let element = r#"<rdf:Description rdf:about="" xmlns:xmp="http://ns.adobe.com/xap/1.0/">
+            <xmp:CreateDate>2009-06-22T14:09:07-07:00</xmp:CreateDate>
+        </rdf:Description>"#;
+        assert_eq!(
+            xmp_property(element, "xmp:CreateDate"),
+            Some("2009-06-22T14:09:07-07:00")
+        );
+
+        let attribute = r#"<rdf:Description rdf:about="" xmp:CreateDate='2011-01-05T10:00:00Z'/>"#;
+        assert_eq!(
+            xmp_property(attribute, "xmp:CreateDate"),
+            Some("2011-01-05T10:00:00Z")
+        );
+        assert_eq!(xmp_property(attribute, "xmp:ModifyDate"), None);
+
+        let with_attributes = r#"<rdf:Description rdf:about="">
+            <xmp:CreateDateOld>2001-01-01</xmp:CreateDateOld>
+            <xmp:CreateDate rdf:parseType="Literal">2012-03-04T05:06:07Z</xmp:CreateDate>
+        </rdf:Description>"#;
+        assert_eq!(
+            xmp_property(with_attributes, "xmp:CreateDate"),
+            Some("2012-03-04T05:06:07Z")
+        );
+
+        let empty = r#"<rdf:Description rdf:about=""><xmp:CreateDate/></rdf:Description>"#;
+        assert_eq!(xmp_property(empty, "xmp:CreateDate"), None);
+    }
+
+    #[test]
+    fn with_data_updates_header() {
+        let swf = write_movie(Compression::Zlib, &[Tag::ShowFrame]);
+        let movie = SwfMovie::from_data(&swf, "file:///test.swf".into(), None).unwrap();
+
+        let mut tags = vec![];
+        swf::write::write_tag_list(
+            &[Tag::ShowFrame, Tag::ShowFrame],
+            movie.version(),
+            &mut tags,
+        )
+        .unwrap();
+        let patched = movie.with_data(tags, 2);
+
+        let mut written = vec![];
+        patched.write_to(&mut written).unwrap();
+        let reread = SwfMovie::from_data(&written, "file:///test.swf".into(), None).unwrap();
+        assert_eq!(reread.num_frames(), 2);
+        assert_eq!(reread.data(), patched.data());
+        assert_eq!(reread.uncompressed_len(), patched.uncompressed_len());
+    }
+
+    #[test]
+    fn truncated_movie() {
+        let tags = [
+            Tag::ShowFrame,
+            Tag::DefineBinaryData(DefineBinaryData {
+                id: 1,
+                data: &[0; 100],
+            }),
+        ];
+        let mut swf = write_movie(Compression::None, &tags);
+        // Cut off the `End` tag and most of the binary data.
+        swf.truncate(swf.len() - 50);
+
+        let strict = SwfMovie::from_data_with_strictness(
+            &swf,
+            "file:///test.swf".into(),
+            None,
+            TagStrictness::Strict,
+        );
+        assert!(strict.is_err());
+
+        let movie = SwfMovie::from_data(&swf, "file:///test.swf".into(), None).unwrap();
+        let mut found = vec![];
+        let mut reader = SwfStream::new(movie.data(), movie.version());
+        let _ = decode_tags(&mut reader, |_, tag_code, tag_len| {
+            found.push((tag_code, tag_len));
+            Ok(ControlFlow::Continue)
+        });
+        assert_eq!(
+            found,
+            [(TagCode::ShowFrame, 0), (TagCode::DefineBinaryData, 58)]
+        );
+    }
+
+    #[test]
+    fn decode_truncated_tags() {
+        // A `ShowFrame`, then a `DefineBinaryData` claiming 10 bytes with only 4 left.
+        let data = [0x40, 0x00, 0xff, 0x15, 10, 0, 0, 0, 1, 2, 3, 4];
+
+        let mut found = vec![];
+        let mut reader = SwfStream::new(&data, 10);
+        let result = decode_tags(&mut reader, |_, tag_code, tag_len| {
+            found.push((tag_code, tag_len));
+            Ok(ControlFlow::Continue)
+        });
+        assert!(matches!(result, Ok(false)));
+        assert!(reader.get_ref().is_empty());
+        assert_eq!(found, [(TagCode::ShowFrame, 0)]);
+
+        let mut found = vec![];
+        let mut reader = SwfStream::new(&data, 10);
+        let result = decode_tags_with_strictness(
+            &mut reader,
+            TagStrictness::Lenient,
+            |_, tag_code, tag_len| {
+                found.push((tag_code, tag_len));
+                Ok(ControlFlow::Continue)
+            },
+        );
+        assert!(matches!(result, Ok(false)));
+        assert_eq!(
+            found,
+            [(TagCode::ShowFrame, 0), (TagCode::DefineBinaryData, 4)]
+        );
+
+        let mut reader = SwfStream::new(&data, 10);
+        let result = decode_tags_with_strictness(&mut reader, TagStrictness::Strict, |_, _, _| {
+            Ok(ControlFlow::Continue)
+        });
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,760 @@
+//! Extraction and disassembly of the ABC bytecode in a movie.
+
+use super::{Error, SwfMovie};
+use std::fmt::{self, Write as _};
+use std::path::{Path, PathBuf};
+use swf::avm2::read::Reader;
+use swf::avm2::types::{
+    AbcFile, Index, Method, Multiname, Namespace, NamespaceSet, Op, Trait, TraitKind,
+};
+use swf::extensions::ReadSwfExt;
+use swf::TagCode;
+
+/// How deeply `TypeName`s may nest before we stop resolving them.
+///
+/// Obfuscated files sometimes contain type names that refer to themselves.
+const MAX_MULTINAME_DEPTH: u32 = 8;
+
+/// How deeply sprites may nest before we stop looking inside them for ABC blocks.
+///
+/// Real movies never nest sprites at all, but a crafted one can nest them deeply enough
+/// to overflow the stack.
+const MAX_SPRITE_DEPTH: u32 = 16;
+
+/// A `DoABC` or `DoABC2` tag found in a movie.
+#[derive(Debug, Clone)]
+pub struct AbcBlock<'a> {
+    /// The name given by a `DoABC2` tag, or an empty string.
+    pub name: String,
+
+    /// The raw ABC file.
+    pub data: &'a [u8],
+
+    /// Whether the block is only run once a class in it is needed.
+    pub lazy: bool,
+}
+
+/// Collects every ABC block in a tag stream.
+///
+/// This doesn't trust the structure of the stream: scanning carries on past `End` tags,
+/// descends into sprites (where Flash would never run ABC), and clamps tag lengths that run
+/// past the end of the data, since obfuscators use all of these to hide code.
+///
+/// `depth` is how many sprites the tag stream is nested in.
+fn find_abc_blocks<'a>(data: &'a [u8], version: u8, depth: u32, blocks: &mut Vec<AbcBlock<'a>>) {
+    let mut reader = swf::read::Reader::new(data, version);
+    while !reader.get_ref().is_empty() {
+        let Ok((tag_code, length)) = reader.read_tag_code_and_length() else {
+            break;
+        };
+        let remaining = reader.get_ref();
+        let tag_data = &remaining[..length.min(remaining.len())];
+        *reader.get_mut() = &remaining[tag_data.len()..];
+
+        match TagCode::from_u16(tag_code) {
+            Some(TagCode::DoAbc) => blocks.push(AbcBlock {
+                name: String::new(),
+                data: tag_data,
+                lazy: false,
+            }),
+            Some(TagCode::DoAbc2) if tag_data.len() >= 4 => {
+                let flags =
+                    u32::from_le_bytes([tag_data[0], tag_data[1], tag_data[2], tag_data[3]]);
+                let rest = &tag_data[4..];
+                let name_len = rest.iter().position(|&b| b == 0).unwrap_or(rest.len());
+                blocks.push(AbcBlock {
+                    name: String::from_utf8_lossy(&rest[..name_len]).into_owned(),
+                    data: rest.get(name_len + 1..).unwrap_or_default(),
+                    lazy: flags & 1 != 0,
+                });
+            }
+            Some(TagCode::DefineSprite) if tag_data.len() >= 4 && depth < MAX_SPRITE_DEPTH => {
+                find_abc_blocks(&tag_data[4..], version, depth + 1, blocks);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// A file name for the `index`th block, made safe for any file system.
+fn file_stem(index: usize, block: &AbcBlock<'_>) -> String {
+    let name: String = block
+        .name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .take(64)
+        .collect();
+    if name.is_empty() {
+        format!("{index:02}_abc")
+    } else {
+        format!("{index:02}_{name}")
+    }
+}
+
+impl SwfMovie {
+    /// Returns every ABC block in this movie, in the order they appear in the file.
+    pub fn abc_blocks(&self) -> Vec<AbcBlock<'_>> {
+        let mut blocks = vec![];
+        find_abc_blocks(self.data(), self.version(), 0, &mut blocks);
+        blocks
+    }
+
+    /// Writes every ABC block in this movie to `directory`, along with a disassembly listing
+    /// of each one.
+    ///
+    /// Each block is saved as `NN_name.abc` and `NN_name.txt`, numbered in file order.
+    /// Blocks that can't be parsed still get a listing, noting the error. Returns the paths
+    /// of the written files.
+    pub fn dump_abc(&self, directory: &Path) -> Result<Vec<PathBuf>, Error> {
+        std::fs::create_dir_all(directory)?;
+
+        let mut paths = vec![];
+        for (index, block) in self.abc_blocks().iter().enumerate() {
+            let stem = file_stem(index, block);
+
+            let abc_path = directory.join(format!("{stem}.abc"));
+            std::fs::write(&abc_path, block.data)?;
+            paths.push(abc_path);
+
+            let mut listing = format!("; {} ({} bytes", block.name, block.data.len());
+            if block.lazy {
+                listing.push_str(", lazy");
+            }
+            listing.push_str(")\n");
+            match disassemble_abc(block.data) {
+                Ok(disassembly) => listing.push_str(&disassembly),
+                Err(e) => {
+                    let _ = writeln!(listing, "; couldn't parse ABC: {e}");
+                }
+            }
+
+            let listing_path = directory.join(format!("{stem}.txt"));
+            std::fs::write(&listing_path, listing)?;
+            paths.push(listing_path);
+        }
+
+        Ok(paths)
+    }
+}
+
+/// Parses an ABC file and lists its constant pools, classes, scripts and method bodies.
+pub fn disassemble_abc(data: &[u8]) -> Result<String, swf::error::Error> {
+    let abc = Reader::new(data).read()?;
+    let mut out = String::new();
+    Listing::new(&abc)
+        .write(&mut out)
+        .expect("Writing to a String can't fail");
+    Ok(out)
+}
+
+/// Looks up a 1-based constant pool entry.
+fn pool_entry<T>(pool: &[T], index: u32) -> Option<&T> {
+    pool.get(index.checked_sub(1)? as usize)
+}
+
+struct Listing<'a> {
+    abc: &'a AbcFile,
+
+    /// What each method is, going by the traits and classes that refer to it.
+    method_labels: Vec<Option<String>>,
+}
+
+impl<'a> Listing<'a> {
+    fn new(abc: &'a AbcFile) -> Self {
+        let mut listing = Self {
+            abc,
+            method_labels: vec![None; abc.methods.len()],
+        };
+
+        for (i, script) in abc.scripts.iter().enumerate() {
+            listing.label(script.init_method, format!("script{i}$init"));
+            listing.label_traits(&script.traits, "");
+        }
+        for (instance, class) in abc.instances.iter().zip(&abc.classes) {
+            let name = listing.multiname(instance.name);
+            listing.label(instance.init_method, format!("{name}$iinit"));
+            listing.label(class.init_method, format!("{name}$cinit"));
+            listing.label_traits(&instance.traits, &format!("{name}/"));
+            listing.label_traits(&class.traits, &format!("{name}/static "));
+        }
+
+        listing
+    }
+
+    fn label(&mut self, method: Index<Method>, label: String) {
+        if let Some(slot) = self.method_labels.get_mut(method.0 as usize) {
+            slot.get_or_insert(label);
+        }
+    }
+
+    fn label_traits(&mut self, traits: &[Trait], prefix: &str) {
+        for t in traits {
+            let (method, accessor) = match t.kind {
+                TraitKind::Method { method, .. } => (method, ""),
+                TraitKind::Getter { method, .. } => (method, "get "),
+                TraitKind::Setter { method, .. } => (method, "set "),
+                TraitKind::Function { function, .. } => (function, ""),
+                _ => continue,
+            };
+            let name = self.multiname(t.name);
+            self.label(method, format!("{prefix}{accessor}{name}"));
+        }
+    }
+
+    fn string(&self, index: Index<String>) -> String {
+        if index.0 == 0 {
+            return "*".to_string();
+        }
+        match pool_entry(&self.abc.constant_pool.strings, index.0) {
+            Some(s) => format!("{s:?}"),
+            None => format!("string[{}]?", index.0),
+        }
+    }
+
+    fn namespace(&self, index: Index<Namespace>) -> String {
+        if index.0 == 0 {
+            return "*".to_string();
+        }
+        let (kind, name) = match pool_entry(&self.abc.constant_pool.namespaces, index.0) {
+            Some(Namespace::Namespace(name)) => ("namespace", name),
+            Some(Namespace::Package(name)) => ("package", name),
+            Some(Namespace::PackageInternal(name)) => ("internal", name),
+            Some(Namespace::Protected(name)) => ("protected", name),
+            Some(Namespace::Explicit(name)) => ("explicit", name),
+            Some(Namespace::StaticProtected(name)) => ("staticprotected", name),
+            Some(Namespace::Private(name)) => ("private", name),
+            None => return format!("namespace[{}]?", index.0),
+        };
+        format!("{kind} {}", self.string(*name))
+    }
+
+    fn namespace_set(&self, index: Index<NamespaceSet>) -> String {
+        match pool_entry(&self.abc.constant_pool.namespace_sets, index.0) {
+            Some(set) => {
+                let namespaces: Vec<_> = set.iter().map(|ns| self.namespace(*ns)).collect();
+                format!("[{}]", namespaces.join(", "))
+            }
+            None => format!("ns_set[{}]?", index.0),
+        }
+    }
+
+    /// The namespace part of a QName, left out for the public package.
+    fn qualifier(&self, index: Index<Namespace>) -> String {
+        if let Some(Namespace::Package(name)) =
+            pool_entry(&self.abc.constant_pool.namespaces, index.0)
+        {
+            let package = pool_entry(&self.abc.constant_pool.strings, name.0);
+            return match package.map_or("", String::as_str) {
+                "" => String::new(),
+                package => format!("{package}::"),
+            };
+        }
+        format!("{}::", self.namespace(index))
+    }
+
+    fn multiname(&self, index: Index<Multiname>) -> String {
+        self.multiname_at_depth(index, 0)
+    }
+
+    fn multiname_at_depth(&self, index: Index<Multiname>, depth: u32) -> String {
+        if index.0 == 0 {
+            return "*".to_string();
+        }
+        let Some(multiname) = pool_entry(&self.abc.constant_pool.multinames, index.0) else {
+            return format!("multiname[{}]?", index.0);
+        };
+        let bare_name =
+            |name: Index<String>| match pool_entry(&self.abc.constant_pool.strings, name.0) {
+                Some(s) => s.clone(),
+                None => self.string(name),
+            };
+
+        match multiname {
+            Multiname::QName { namespace, name } => {
+                format!("{}{}", self.qualifier(*namespace), bare_name(*name))
+            }
+            Multiname::QNameA { namespace, name } => {
+                format!("{}@{}", self.qualifier(*namespace), bare_name(*name))
+            }
+            Multiname::RTQName { name } => format!("<rt>::{}", bare_name(*name)),
+            Multiname::RTQNameA { name } => format!("<rt>::@{}", bare_name(*name)),
+            Multiname::RTQNameL => "<rt>::[<rt>]".to_string(),
+            Multiname::RTQNameLA => "<rt>::@[<rt>]".to_string(),
+            Multiname::Multiname {
+                namespace_set,
+                name,
+            } => format!(
+                "{}::{}",
+                self.namespace_set(*namespace_set),
+                bare_name(*name)
+            ),
+            Multiname::MultinameA {
+                namespace_set,
+                name,
+            } => format!(
+                "{}::@{}",
+                self.namespace_set(*namespace_set),
+                bare_name(*name)
+            ),
+            Multiname::MultinameL { namespace_set } => {
+                format!("{}::[<rt>]", self.namespace_set(*namespace_set))
+            }
+            Multiname::MultinameLA { namespace_set } => {
+                format!("{}::@[<rt>]", self.namespace_set(*namespace_set))
+            }
+            Multiname::TypeName { .. } if depth >= MAX_MULTINAME_DEPTH => "...".to_string(),
+            Multiname::TypeName {
+                base_type,
+                parameters,
+            } => {
+                let parameters: Vec<_> = parameters
+                    .iter()
+                    .map(|p| self.multiname_at_depth(*p, depth + 1))
+                    .collect();
+                format!(
+                    "{}.<{}>",
+                    self.multiname_at_depth(*base_type, depth + 1),
+                    parameters.join(", ")
+                )
+            }
+        }
+    }
+
+    fn method_name(&self, index: Index<Method>) -> String {
+        let label = self
+            .method_labels
+            .get(index.0 as usize)
+            .cloned()
+            .flatten()
+            .or_else(|| {
+                let method = self.abc.methods.get(index.0 as usize)?;
+                pool_entry(&self.abc.constant_pool.strings, method.name.0).cloned()
+            });
+        match label {
+            Some(label) if !label.is_empty() => format!("method[{}] {label}", index.0),
+            _ => format!("method[{}]", index.0),
+        }
+    }
+
+    fn write(&self, out: &mut String) -> fmt::Result {
+        let abc = self.abc;
+        let pool = &abc.constant_pool;
+        writeln!(
+            out,
+            "; ABC version {}.{}",
+            abc.major_version, abc.minor_version
+        )?;
+
+        writeln!(out, "\n; constant pool")?;
+        for (i, value) in pool.ints.iter().enumerate() {
+            writeln!(out, "int[{}] = {value}", i + 1)?;
+        }
+        for (i, value) in pool.uints.iter().enumerate() {
+            writeln!(out, "uint[{}] = {value}", i + 1)?;
+        }
+        for (i, value) in pool.doubles.iter().enumerate() {
+            writeln!(out, "double[{}] = {value:?}", i + 1)?;
+        }
+        for (i, value) in pool.strings.iter().enumerate() {
+            writeln!(out, "string[{}] = {value:?}", i + 1)?;
+        }
+        for i in 1..=pool.namespaces.len() as u32 {
+            writeln!(out, "namespace[{i}] = {}", self.namespace(Index::new(i)))?;
+        }
+        for i in 1..=pool.namespace_sets.len() as u32 {
+            writeln!(out, "ns_set[{i}] = {}", self.namespace_set(Index::new(i)))?;
+        }
+        for i in 1..=pool.multinames.len() as u32 {
+            writeln!(out, "multiname[{i}] = {}", self.multiname(Index::new(i)))?;
+        }
+
+        writeln!(out, "\n; classes")?;
+        for (i, instance) in abc.instances.iter().enumerate() {
+            write!(
+                out,
+                "class[{i}] {} extends {}",
+                self.multiname(instance.name),
+                self.multiname(instance.super_name)
+            )?;
+            if !instance.interfaces.is_empty() {
+                let interfaces: Vec<_> = instance
+                    .interfaces
+                    .iter()
+                    .map(|i| self.multiname(*i))
+                    .collect();
+                write!(out, " implements {}", interfaces.join(", "))?;
+            }
+            for (flag, name) in [
+                (instance.is_sealed, "sealed"),
+                (instance.is_final, "final"),
+                (instance.is_interface, "interface"),
+            ] {
+                if flag {
+                    write!(out, " {name}")?;
+                }
+            }
+            writeln!(out)?;
+            writeln!(out, "    iinit {}", self.method_name(instance.init_method))?;
+            self.write_traits(out, &instance.traits, "")?;
+            if let Some(class) = abc.classes.get(i) {
+                writeln!(out, "    cinit {}", self.method_name(class.init_method))?;
+                self.write_traits(out, &class.traits, "static ")?;
+            }
+        }
+
+        writeln!(out, "\n; scripts")?;
+        for (i, script) in abc.scripts.iter().enumerate() {
+            writeln!(
+                out,
+                "script[{i}] init {}",
+                self.method_name(script.init_method)
+            )?;
+            self.write_traits(out, &script.traits, "")?;
+        }
+
+        writeln!(out, "\n; methods")?;
+        for (i, method) in abc.methods.iter().enumerate() {
+            let params: Vec<_> = method
+                .params
+                .iter()
+                .map(|p| self.multiname(p.kind))
+                .collect();
+            write!(
+                out,
+                "\n{}({}): {}",
+                self.method_name(Index::new(i as u32)),
+                params.join(", "),
+                self.multiname(method.return_type)
+            )?;
+            if !method.flags.is_empty() {
+                write!(out, " {:?}", method.flags)?;
+            }
+            writeln!(out)?;
+
+            for body in abc.method_bodies.iter().filter(|b| b.method.0 == i as u32) {
+                writeln!(
+                    out,
+                    "    max_stack {}, num_locals {}, scope_depth {}..{}",
+                    body.max_stack, body.num_locals, body.init_scope_depth, body.max_scope_depth
+                )?;
+                self.write_traits(out, &body.traits, "")?;
+                self.write_code(out, &body.code)?;
+                for e in &body.exceptions {
+                    writeln!(
+                        out,
+                        "    try {:04x}..{:04x} -> {:04x} catch {} as {}",
+                        e.from_offset,
+                        e.to_offset,
+                        e.target_offset,
+                        self.multiname(e.type_name),
+                        self.multiname(e.variable_name)
+                    )?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn write_traits(&self, out: &mut String, traits: &[Trait], prefix: &str) -> fmt::Result {
+        for t in traits {
+            let name = self.multiname(t.name);
+            match &t.kind {
+                TraitKind::Slot { type_name, .. } => {
+                    writeln!(
+                        out,
+                        "    {prefix}var {name}: {}",
+                        self.multiname(*type_name)
+                    )
+                }
+                TraitKind::Const { type_name, .. } => {
+                    writeln!(
+                        out,
+                        "    {prefix}const {name}: {}",
+                        self.multiname(*type_name)
+                    )
+                }
+                TraitKind::Method { method, .. } => {
+                    writeln!(
+                        out,
+                        "    {prefix}method {name} = {}",
+                        self.method_name(*method)
+                    )
+                }
+                TraitKind::Getter { method, .. } => {
+                    writeln!(
+                        out,
+                        "    {prefix}getter {name} = {}",
+                        self.method_name(*method)
+                    )
+                }
+                TraitKind::Setter { method, .. } => {
+                    writeln!(
+                        out,
+                        "    {prefix}setter {name} = {}",
+                        self.method_name(*method)
+                    )
+                }
+                TraitKind::Function { function, .. } => {
+                    writeln!(
+                        out,
+                        "    {prefix}function {name} = {}",
+                        self.method_name(*function)
+                    )
+                }
+                TraitKind::Class { class, .. } => {
+                    writeln!(out, "    {prefix}class {name} = class[{}]", class.0)
+                }
+            }?;
+        }
+        Ok(())
+    }
+
+    fn write_code(&self, out: &mut String, code: &[u8]) -> fmt::Result {
+        let mut reader = Reader::new(code);
+        while !reader.as_slice().is_empty() {
+            let start = reader.pos(code);
+            match reader.read_op() {
+                Ok(op) => {
+                    let end = reader.pos(code);
+                    writeln!(out, "    {start:04x}  {}", self.format_op(&op, start, end))?;
+                }
+                Err(e) => {
+                    // There's no telling where the next instruction would start.
+                    writeln!(out, "    {start:04x}  ; {e}")?;
+                    break;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Formats an instruction, resolving its constant pool operands.
+    ///
+    /// `start` and `end` are the offsets of the instruction, used to turn branch offsets
+    /// into absolute targets.
+    fn format_op(&self, op: &Op, start: usize, end: usize) -> String {
+        let debug = format!("{op:?}");
+        let (variant, fields) = debug.split_once(' ').unwrap_or((debug.as_str(), ""));
+        let mnemonic = variant.to_ascii_lowercase();
+        let pool = &self.abc.constant_pool;
+        let target = |offset: i32| format!("{:04x}", end as i64 + offset as i64);
+
+        let operands = match op {
+            Op::AsType { type_name: index }
+            | Op::Coerce { index }
+            | Op::DeleteProperty { index }
+            | Op::FindDef { index }
+            | Op::FindProperty { index }
+            | Op::FindPropStrict { index }
+            | Op::GetDescendants { index }
+            | Op::GetLex { index }
+            | Op::GetProperty { index }
+            | Op::GetSuper { index }
+            | Op::InitProperty { index }
+            | Op::IsType { index }
+            | Op::SetProperty { index }
+            | Op::SetSuper { index } => self.multiname(*index),
+            Op::CallProperty { index, num_args }
+            | Op::CallPropLex { index, num_args }
+            | Op::CallPropVoid { index, num_args }
+            | Op::CallSuper { index, num_args }
+            | Op::CallSuperVoid { index, num_args }
+            | Op::ConstructProp { index, num_args } => {
+                format!("{}, {num_args}", self.multiname(*index))
+            }
+            Op::CallMethod { index, num_args } | Op::CallStatic { index, num_args } => {
+                format!("{}, {num_args}", self.method_name(*index))
+            }
+            Op::NewFunction { index } => self.method_name(*index),
+            Op::NewClass { index } => format!("class[{}]", index.0),
+            Op::NewCatch { index } => format!("exception[{}]", index.0),
+            Op::PushString { value } => self.string(*value),
+            Op::Dxns { index } => self.string(*index),
+            Op::DebugFile { file_name } => self.string(*file_name),
+            Op::Debug {
+                is_local_register,
+                register_name,
+                register,
+            } => format!(
+                "{}, {register}, {is_local_register}",
+                self.string(*register_name)
+            ),
+            Op::PushInt { value } => match pool_entry(&pool.ints, value.0) {
+                Some(value) => value.to_string(),
+                None => format!("int[{}]?", value.0),
+            },
+            Op::PushUint { value } => match pool_entry(&pool.uints, value.0) {
+                Some(value) => value.to_string(),
+                None => format!("uint[{}]?", value.0),
+            },
+            Op::PushDouble { value } => match pool_entry(&pool.doubles, value.0) {
+                Some(value) => format!("{value:?}"),
+                None => format!("double[{}]?", value.0),
+            },
+            Op::PushNamespace { value } => self.namespace(*value),
+            Op::IfEq { offset }
+            | Op::IfFalse { offset }
+            | Op::IfGe { offset }
+            | Op::IfGt { offset }
+            | Op::IfLe { offset }
+            | Op::IfLt { offset }
+            | Op::IfNe { offset }
+            | Op::IfNge { offset }
+            | Op::IfNgt { offset }
+            | Op::IfNle { offset }
+            | Op::IfNlt { offset }
+            | Op::IfStrictEq { offset }
+            | Op::IfStrictNe { offset }
+            | Op::IfTrue { offset }
+            | Op::Jump { offset } => target(*offset),
+            Op::LookupSwitch {
+                default_offset,
+                case_offsets,
+            } => {
+                // Unlike other branches, these are relative to the start of the instruction.
+                let case = |offset: i32| format!("{:04x}", start as i64 + offset as i64);
+                let cases: Vec<_> = case_offsets.iter().map(|o| case(*o)).collect();
+                format!("{}, [{}]", case(*default_offset), cases.join(", "))
+            }
+            _ => fields
+                .trim_start_matches('{')
+                .trim_end_matches('}')
+                .trim()
+                .to_string(),
+        };
+
+        if operands.is_empty() {
+            mnemonic
+        } else {
+            format!("{mnemonic} {operands}")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{disassemble_abc, MAX_SPRITE_DEPTH};
+    use crate::tag_utils::SwfMovie;
+    use swf::avm2::types::{
+        AbcFile, ConstantPool, Index, Method, MethodBody, MethodFlags, Multiname, Namespace, Script,
+    };
+    use swf::{DoAbc2, DoAbc2Flag, Header, Sprite, SwfStr, Tag};
+
+    fn abc_file() -> Vec<u8> {
+        let abc = AbcFile {
+            major_version: 46,
+            minor_version: 16,
+            constant_pool: ConstantPool {
+                ints: vec![],
+                uints: vec![],
+                doubles: vec![],
+                strings: vec!["".to_string(), "trace".to_string()],
+                namespaces: vec![Namespace::Package(Index::new(1))],
+                namespace_sets: vec![],
+                multinames: vec![Multiname::QName {
+                    namespace: Index::new(1),
+                    name: Index::new(2),
+                }],
+            },
+            methods: vec![Method {
+                name: Index::new(0),
+                params: vec![],
+                return_type: Index::new(0),
+                flags: MethodFlags::empty(),
+            }],
+            metadata: vec![],
+            instances: vec![],
+            classes: vec![],
+            scripts: vec![Script {
+                init_method: Index::new(0),
+                traits: vec![],
+            }],
+            method_bodies: vec![MethodBody {
+                method: Index::new(0),
+                max_stack: 1,
+                num_locals: 1,
+                init_scope_depth: 0,
+                max_scope_depth: 1,
+                // findpropstrict trace; jump +0; returnvoid
+                code: vec![0x5d, 0x01, 0x10, 0x00, 0x00, 0x00, 0x47],
+                exceptions: vec![],
+                traits: vec![],
+            }],
+        };
+        let mut data = vec![];
+        swf::avm2::write::Writer::new(&mut data).write(abc).unwrap();
+        data
+    }
+
+    #[test]
+    fn finds_hidden_blocks() {
+        let abc = abc_file();
+        let tags = [
+            Tag::ShowFrame,
+            // Flash stops at the first `End` tag, but obfuscators hide code past it.
+            Tag::End,
+            Tag::DoAbc2(DoAbc2 {
+                flags: DoAbc2Flag::LAZY_INITIALIZE,
+                name: SwfStr::from_utf8_str("frame1"),
+                data: &abc,
+            }),
+            Tag::DefineSprite(Sprite {
+                id: 1,
+                num_frames: 1,
+                tags: vec![Tag::DoAbc(&abc), Tag::ShowFrame],
+            }),
+        ];
+        let mut swf = vec![];
+        swf::write_swf(&Header::default_with_swf_version(10), &tags, &mut swf).unwrap();
+        let movie = SwfMovie::from_data(&swf, "file:///test.swf".into(), None).unwrap();
+
+        let blocks = movie.abc_blocks();
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].name, "frame1");
+        assert!(blocks[0].lazy);
+        assert_eq!(blocks[0].data, &abc[..]);
+        assert_eq!(blocks[1].name, "");
+        assert!(!blocks[1].lazy);
+        assert_eq!(blocks[1].data, &abc[..]);
+    }
+
+    #[test]
+    fn stops_at_deeply_nested_sprites() {
+        let abc = abc_file();
+        let mut tags = vec![Tag::DoAbc(&abc)];
+        for id in 1..=MAX_SPRITE_DEPTH as u16 * 2 {
+            tags = vec![
+                Tag::DoAbc(&abc),
+                Tag::DefineSprite(Sprite {
+                    id,
+                    num_frames: 1,
+                    tags,
+                }),
+            ];
+        }
+        let mut swf = vec![];
+        swf::write_swf(&Header::default_with_swf_version(10), &tags, &mut swf).unwrap();
+        let movie = SwfMovie::from_data(&swf, "file:///test.swf".into(), None).unwrap();
+
+        // The top level, and then one block for each sprite that is looked into.
+        assert_eq!(movie.abc_blocks().len(), MAX_SPRITE_DEPTH as usize + 1);
+    }
+
+    #[test]
+    fn disassembles_method_bodies() {
+        let listing = disassemble_abc(&abc_file()).unwrap();
+        assert!(listing.contains("string[2] = \"trace\""));
+        assert!(listing.contains("multiname[1] = trace"));
+        assert!(listing.contains("method[0] script0$init(): *"));
+        assert!(listing.contains("    0000  findpropstrict trace\n"));
+        assert!(listing.contains("    0002  jump 0006\n"));
+        assert!(listing.contains("    0006  returnvoid\n"));
+    }
+}
@@ -0,0 +1,96 @@
+//! The informational tags compilers leave in a movie, describing how it was built.
+
+use super::SwfMovie;
+use swf::extensions::ReadSwfExt;
+use swf::{DebugId, ProductInfo, TagCode};
+
+/// What a movie says about the tools that built it.
+///
+/// None of this affects playback; it's only useful to tell apart movies made by different
+/// toolchains.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BuildInfo {
+    /// The compiler that built the movie, from its `ProductInfo` tag. Only written by mxmlc.
+    pub product_info: Option<ProductInfo>,
+
+    /// The UUID of a debug build, from its `DebugId` tag.
+    pub debug_id: Option<DebugId>,
+
+    /// Whether the movie allows Adobe Scout to profile it, from its `EnableTelemetry` tag.
+    pub telemetry: Option<Telemetry>,
+}
+
+/// The settings of an `EnableTelemetry` tag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Telemetry {
+    /// Whether profiling needs a password.
+    pub password_protected: bool,
+}
+
+impl BuildInfo {
+    /// Describes the compiler, such as `Adobe Flex 4.6 build 23201`.
+    pub fn compiler(&self) -> Option<String> {
+        let info = self.product_info.as_ref()?;
+        let product = match info.product_id {
+            1 => "Macromedia Flex for J2EE",
+            2 => "Macromedia Flex for .NET",
+            3 => "Adobe Flex",
+            _ => "Unknown product",
+        };
+        Some(format!(
+            "{product} {}.{} build {}",
+            info.major_version, info.minor_version, info.build_number
+        ))
+    }
+
+    /// The debug UUID, in its usual hyphenated form.
+    pub fn debug_id_string(&self) -> Option<String> {
+        let id = self.debug_id?;
+        let hex: Vec<String> = id.iter().map(|b| format!("{b:02x}")).collect();
+        Some(format!(
+            "{}-{}-{}-{}-{}",
+            hex[0..4].concat(),
+            hex[4..6].concat(),
+            hex[6..8].concat(),
+            hex[8..10].concat(),
+            hex[10..16].concat()
+        ))
+    }
+}
+
+impl SwfMovie {
+    /// Reads the informational tags of this movie's root timeline.
+    ///
+    /// Unreadable tags are skipped over, and the first of each kind of tag is used.
+    pub fn build_info(&self) -> BuildInfo {
+        let mut info = BuildInfo::default();
+        let mut reader = swf::read::Reader::new(self.data(), self.version());
+        while !reader.get_ref().is_empty() {
+            let Ok((tag_code, length)) = reader.read_tag_code_and_length() else {
+                break;
+            };
+            let remaining = reader.get_ref();
+            let tag_data = &remaining[..length.min(remaining.len())];
+            *reader.get_mut() = &remaining[tag_data.len()..];
+
+            let mut tag_reader = swf::read::Reader::new(tag_data, self.version());
+            match TagCode::from_u16(tag_code) {
+                Some(TagCode::ProductInfo) if info.product_info.is_none() => {
+                    info.product_info = tag_reader.read_product_info().ok();
+                }
+                Some(TagCode::DebugId) if info.debug_id.is_none() => {
+                    info.debug_id = tag_reader.read_debug_id().ok();
+                }
+                Some(TagCode::EnableTelemetry) if info.telemetry.is_none() => {
+                    // A reserved `u16`, then an optional SHA-256 hash of the password.
+                    info.telemetry = Some(Telemetry {
+                        password_protected: tag_data.len() >= 34,
+                    });
+                }
+                Some(TagCode::End) => break,
+                _ => {}
+            }
+        }
+        info
+    }
+}
@@ -0,0 +1,157 @@
+//! Summary statistics of a movie, for looking over many movies without loading them.
+
+use super::SwfMovie;
+use std::collections::BTreeMap;
+use swf::{Compression, Fixed8, HeaderExt, Rectangle, SwfBuf, TagCode, Twips};
+
+/// What a movie's header says about it, and how many of each tag it contains.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SwfMovieStats {
+    pub version: u8,
+    pub is_action_script_3: bool,
+    pub compression: Compression,
+
+    /// The length of the movie as it was loaded.
+    pub compressed_len: usize,
+
+    /// The length of the movie once decompressed, according to its header.
+    pub uncompressed_len: i32,
+
+    pub num_frames: u16,
+    pub frame_rate: Fixed8,
+    pub stage_size: Rectangle<Twips>,
+
+    /// How many times each tag appears, including within the timelines of sprites.
+    /// Sprites defined inside other sprites are ignored by Flash, so their tags aren't counted.
+    ///
+    /// Tags are named after their [`TagCode`], and unknown tags are listed as `Unknown(code)`.
+    pub tag_counts: BTreeMap<String, usize>,
+}
+
+impl SwfMovieStats {
+    /// Summarizes a decompressed movie, such as one returned by [`swf::decompress_swf`].
+    ///
+    /// `compressed_len` is the length of the movie before it was decompressed.
+    pub fn from_swf_buf(swf_buf: &SwfBuf, compressed_len: usize) -> Self {
+        Self::new(&swf_buf.header, &swf_buf.data, compressed_len)
+    }
+
+    fn new(header: &HeaderExt, data: &[u8], compressed_len: usize) -> Self {
+        let mut tag_counts = BTreeMap::new();
+        count_tags(data, header.version(), true, &mut tag_counts);
+        Self {
+            version: header.version(),
+            is_action_script_3: header.is_action_script_3(),
+            compression: header.compression(),
+            compressed_len,
+            uncompressed_len: header.uncompressed_len(),
+            num_frames: header.num_frames(),
+            frame_rate: header.frame_rate(),
+            stage_size: header.stage_size().clone(),
+            tag_counts,
+        }
+    }
+
+    /// The number of tags in the movie.
+    pub fn total_tags(&self) -> usize {
+        self.tag_counts.values().sum()
+    }
+}
+
+impl SwfMovie {
+    /// Summarizes this movie, counting its tags without parsing them.
+    pub fn stats(&self) -> SwfMovieStats {
+        SwfMovieStats::new(self.header(), self.data(), self.compressed_len())
+    }
+}
+
+/// Counts the tags of a timeline, and those of the sprites it defines if it's the root timeline.
+fn count_tags(data: &[u8], version: u8, is_root: bool, counts: &mut BTreeMap<String, usize>) {
+    let mut reader = swf::read::Reader::new(data, version);
+    while !reader.get_ref().is_empty() {
+        let Ok((tag_code, length)) = reader.read_tag_code_and_length() else {
+            break;
+        };
+        let remaining = reader.get_ref();
+        let tag_data = &remaining[..length.min(remaining.len())];
+        *reader.get_mut() = &remaining[tag_data.len()..];
+
+        let tag = TagCode::from_u16(tag_code);
+        let name = match tag {
+            Some(tag) => format!("{tag:?}"),
+            None => format!("Unknown({tag_code})"),
+        };
+        *counts.entry(name).or_default() += 1;
+
+        if is_root && tag == Some(TagCode::DefineSprite) && tag_data.len() >= 4 {
+            count_tags(&tag_data[4..], version, false, counts);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tag_utils::{SwfMovie, SwfMovieStats};
+    use swf::{Header, Sprite, Tag};
+
+    fn write_movie(tags: &[Tag<'_>]) -> Vec<u8> {
+        let mut swf = vec![];
+        swf::write_swf(&Header::default_with_swf_version(10), tags, &mut swf).unwrap();
+        swf
+    }
+
+    #[test]
+    fn counts_nested_tags() {
+        let tags = [
+            Tag::ShowFrame,
+            Tag::DefineSprite(Sprite {
+                id: 1,
+                num_frames: 2,
+                tags: vec![Tag::ShowFrame, Tag::ShowFrame],
+            }),
+            Tag::ShowFrame,
+        ];
+        let swf = write_movie(&tags);
+        let movie = SwfMovie::from_data(&swf, "file:///test.swf".into(), None).unwrap();
+
+        let stats = movie.stats();
+        assert_eq!(stats.version, 10);
+        assert_eq!(stats.compressed_len, swf.len());
+        assert_eq!(stats.tag_counts["ShowFrame"], 4);
+        assert_eq!(stats.tag_counts["DefineSprite"], 1);
+    }
+
+    #[test]
+    fn ignores_sprites_within_sprites() {
+        let mut sprite = Sprite {
+            id: 1,
+            num_frames: 1,
+            tags: vec![Tag::ShowFrame],
+        };
+        for id in 2..4 {
+            sprite = Sprite {
+                id,
+                num_frames: 1,
+                tags: vec![Tag::DefineSprite(sprite), Tag::ShowFrame],
+            };
+        }
+        let swf = write_movie(&[Tag::DefineSprite(sprite), Tag::ShowFrame]);
+        let movie = SwfMovie::from_data(&swf, "file:///test.swf".into(), None).unwrap();
+
+        let stats = movie.stats();
+        assert_eq!(stats.tag_counts["DefineSprite"], 2);
+        assert_eq!(stats.tag_counts["ShowFrame"], 2);
+    }
+
+    #[test]
+    fn stats_from_decompressed_movie() {
+        let swf = write_movie(&[Tag::ShowFrame, Tag::ShowFrame]);
+        let movie = SwfMovie::from_data(&swf, "file:///test.swf".into(), None).unwrap();
+        let swf_buf = swf::decompress_swf(&swf[..]).unwrap();
+
+        assert_eq!(
+            SwfMovieStats::from_swf_buf(&swf_buf, swf.len()),
+            movie.stats()
+        );
+    }
+}
@@ -0,0 +1,619 @@
+//! Extraction of a single symbol into a standalone SWF.
+
+use super::{Error, SwfMovie};
+use fnv::{FnvHashMap, FnvHashSet};
+use swf::{
+    CharacterId, ExportedAsset, FileAttributes, FillStyle, LineStyle, PlaceObjectAction,
+    ShapeRecord, SwfStr, SymbolClassLink, Tag,
+};
+
+/// The character a tag defines or adds to, and whether it defines it.
+///
+/// Tags like `DefineFontInfo` or `DefineButtonSound` don't define a character of their own,
+/// but have to come along with the character they belong to.
+fn subject(tag: &Tag<'_>) -> Option<(CharacterId, bool)> {
+    let definition = match tag {
+        Tag::DefineBinaryData(data) => data.id,
+        Tag::DefineBits { id, .. } | Tag::DefineBitsJpeg2 { id, .. } => *id,
+        Tag::DefineBitsJpeg3(jpeg) => jpeg.id,
+        Tag::DefineBitsLossless(bitmap) => bitmap.id,
+        Tag::DefineButton(button) | Tag::DefineButton2(button) => button.id,
+        Tag::DefineEditText(edit_text) => edit_text.id(),
+        Tag::DefineFont(font) => font.id,
+        Tag::DefineFont2(font) => font.id,
+        Tag::DefineFont4(font) => font.id,
+        Tag::DefineMorphShape(morph) => morph.id,
+        Tag::DefineShape(shape) => shape.id,
+        Tag::DefineSound(sound) => sound.id,
+        Tag::DefineSprite(sprite) => sprite.id,
+        Tag::DefineText(text) => text.id,
+        Tag::DefineVideoStream(video) => video.id,
+
+        Tag::DefineButtonColorTransform(transform) => return Some((transform.id, false)),
+        Tag::DefineButtonSound(sounds) => return Some((sounds.id, false)),
+        Tag::DefineFontAlignZones { id, .. }
+        | Tag::DefineFontName { id, .. }
+        | Tag::DefineScalingGrid { id, .. }
+        | Tag::DoInitAction { id, .. } => return Some((*id, false)),
+        Tag::DefineFontInfo(info) => return Some((info.id, false)),
+        Tag::CsmTextSettings(settings) => return Some((settings.id, false)),
+        Tag::VideoFrame(frame) => return Some((frame.stream_id, false)),
+        _ => return None,
+    };
+    Some((definition, true))
+}
+
+/// Changes the character returned by `subject`.
+fn set_subject(tag: &mut Tag<'_>, new_id: CharacterId) {
+    match tag {
+        Tag::DefineBinaryData(data) => data.id = new_id,
+        Tag::DefineBits { id, .. } | Tag::DefineBitsJpeg2 { id, .. } => *id = new_id,
+        Tag::DefineBitsJpeg3(jpeg) => jpeg.id = new_id,
+        Tag::DefineBitsLossless(bitmap) => bitmap.id = new_id,
+        Tag::DefineButton(button) | Tag::DefineButton2(button) => button.id = new_id,
+        Tag::DefineEditText(edit_text) => {
+            **edit_text = std::mem::take(&mut **edit_text).with_id(new_id)
+        }
+        Tag::DefineFont(font) => font.id = new_id,
+        Tag::DefineFont2(font) => font.id = new_id,
+        Tag::DefineFont4(font) => font.id = new_id,
+        Tag::DefineMorphShape(morph) => morph.id = new_id,
+        Tag::DefineShape(shape) => shape.id = new_id,
+        Tag::DefineSound(sound) => sound.id = new_id,
+        Tag::DefineSprite(sprite) => sprite.id = new_id,
+        Tag::DefineText(text) => text.id = new_id,
+        Tag::DefineVideoStream(video) => video.id = new_id,
+        Tag::DefineButtonColorTransform(transform) => transform.id = new_id,
+        Tag::DefineButtonSound(sounds) => sounds.id = new_id,
+        Tag::DefineFontAlignZones { id, .. }
+        | Tag::DefineFontName { id, .. }
+        | Tag::DefineScalingGrid { id, .. }
+        | Tag::DoInitAction { id, .. } => *id = new_id,
+        Tag::DefineFontInfo(info) => info.id = new_id,
+        Tag::CsmTextSettings(settings) => settings.id = new_id,
+        Tag::VideoFrame(frame) => frame.stream_id = new_id,
+        _ => {}
+    }
+}
+
+fn visit_fill_style(fill_style: &mut FillStyle, f: &mut dyn FnMut(&mut CharacterId)) {
+    if let FillStyle::Bitmap { id, .. } = fill_style {
+        f(id);
+    }
+}
+
+fn visit_styles(
+    fill_styles: &mut [FillStyle],
+    line_styles: &mut [LineStyle],
+    f: &mut dyn FnMut(&mut CharacterId),
+) {
+    for fill_style in fill_styles {
+        visit_fill_style(fill_style, f);
+    }
+    for line_style in line_styles {
+        if let FillStyle::Bitmap { .. } = line_style.fill_style() {
+            let mut fill_style = line_style.fill_style().clone();
+            visit_fill_style(&mut fill_style, f);
+            *line_style = line_style.clone().with_fill_style(fill_style);
+        }
+    }
+}
+
+fn visit_shape_records(records: &mut [ShapeRecord], f: &mut dyn FnMut(&mut CharacterId)) {
+    for record in records {
+        if let ShapeRecord::StyleChange(style_change) = record {
+            if let Some(styles) = &mut style_change.new_styles {
+                visit_styles(&mut styles.fill_styles, &mut styles.line_styles, f);
+            }
+        }
+    }
+}
+
+/// Calls `f` on every character ID `tag` refers to, other than its `subject`.
+fn visit_references(tag: &mut Tag<'_>, f: &mut dyn FnMut(&mut CharacterId)) {
+    match tag {
+        Tag::DefineShape(shape) => {
+            visit_styles(
+                &mut shape.styles.fill_styles,
+                &mut shape.styles.line_styles,
+                f,
+            );
+            visit_shape_records(&mut shape.shape, f);
+        }
+        Tag::DefineMorphShape(morph) => {
+            for shape in [&mut morph.start, &mut morph.end] {
+                visit_styles(&mut shape.fill_styles, &mut shape.line_styles, f);
+                visit_shape_records(&mut shape.shape, f);
+            }
+        }
+        Tag::DefineSprite(sprite) => {
+            for tag in &mut sprite.tags {
+                visit_references(tag, f);
+            }
+        }
+        Tag::PlaceObject(place_object) => match &mut place_object.action {
+            PlaceObjectAction::Place(id) | PlaceObjectAction::Replace(id) => f(id),
+            PlaceObjectAction::Modify | PlaceObjectAction::PlaceClass => {}
+        },
+        Tag::RemoveObject(remove_object) => {
+            if let Some(id) = &mut remove_object.character_id {
+                f(id);
+            }
+        }
+        Tag::StartSound(start_sound) => f(&mut start_sound.id),
+        Tag::DefineButton(button) | Tag::DefineButton2(button) => {
+            for record in &mut button.records {
+                f(&mut record.id);
+            }
+        }
+        Tag::DefineButtonSound(sounds) => {
+            for (id, _) in [
+                &mut sounds.over_to_up_sound,
+                &mut sounds.up_to_over_sound,
+                &mut sounds.over_to_down_sound,
+                &mut sounds.down_to_over_sound,
+            ]
+            .into_iter()
+            .flatten()
+            {
+                f(id);
+            }
+        }
+        Tag::DefineText(text) => {
+            for record in &mut text.records {
+                if let Some(id) = &mut record.font_id {
+                    f(id);
+                }
+            }
+        }
+        Tag::DefineEditText(edit_text) => {
+            if let (Some(mut font_id), Some(height)) = (edit_text.font_id(), edit_text.height()) {
+                f(&mut font_id);
+                **edit_text = std::mem::take(&mut **edit_text).with_font_id(font_id, height);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Orders `id` and everything it depends on so that dependencies come first.
+fn collect_dependencies(
+    id: CharacterId,
+    tags: &mut [Option<Tag<'_>>],
+    tags_by_subject: &FnvHashMap<CharacterId, Vec<usize>>,
+    visited: &mut FnvHashSet<CharacterId>,
+    order: &mut Vec<CharacterId>,
+) {
+    if !visited.insert(id) {
+        return;
+    }
+
+    let mut dependencies = vec![];
+    for &index in tags_by_subject.get(&id).into_iter().flatten() {
+        if let Some(tag) = &mut tags[index] {
+            visit_references(tag, &mut |id: &mut CharacterId| dependencies.push(*id));
+        }
+    }
+    for dependency in dependencies {
+        if tags_by_subject.contains_key(&dependency) {
+            collect_dependencies(dependency, tags, tags_by_subject, visited, order);
+        }
+    }
+
+    order.push(id);
+}
+
+impl SwfMovie {
+    /// Reads every tag of this movie, indexing them by their `subject`.
+    ///
+    /// Fails if `character_id` isn't defined by any of them.
+    #[allow(clippy::type_complexity)]
+    fn symbol_tags(
+        &self,
+        character_id: CharacterId,
+    ) -> Result<(Vec<Option<Tag<'_>>>, FnvHashMap<CharacterId, Vec<usize>>), Error> {
+        let mut reader = swf::read::Reader::new(self.data(), self.version());
+        let mut tags = vec![];
+        loop {
+            let remaining = reader.get_ref().len();
+            match reader.read_tag() {
+                Ok(Tag::End) => break,
+                Ok(tag) => tags.push(Some(tag)),
+                Err(e) if reader.get_ref().len() < remaining => {
+                    tracing::warn!("Skipping unreadable tag while exporting symbol: {}", e);
+                }
+                Err(_) => break,
+            }
+        }
+
+        let mut tags_by_subject: FnvHashMap<CharacterId, Vec<usize>> = FnvHashMap::default();
+        let mut is_defined = false;
+        for (index, tag) in tags.iter().enumerate() {
+            if let Some((id, defines)) = tag.as_ref().and_then(subject) {
+                tags_by_subject.entry(id).or_default().push(index);
+                is_defined |= defines && id == character_id;
+            }
+        }
+        if !is_defined {
+            return Err(Error::CharacterNotFound(character_id));
+        }
+
+        Ok((tags, tags_by_subject))
+    }
+
+    /// Every character a symbol uses, directly or through other characters, with
+    /// dependencies coming before the characters that use them.
+    pub fn symbol_dependencies(
+        &self,
+        character_id: CharacterId,
+    ) -> Result<Vec<CharacterId>, Error> {
+        let (mut tags, tags_by_subject) = self.symbol_tags(character_id)?;
+        let mut order = vec![];
+        collect_dependencies(
+            character_id,
+            &mut tags,
+            &tags_by_subject,
+            &mut FnvHashSet::default(),
+            &mut order,
+        );
+        // The symbol itself always comes last.
+        order.pop();
+        Ok(order)
+    }
+
+    /// Extract a single symbol, along with every character it uses, into a
+    /// minimal movie of its own.
+    ///
+    /// Character IDs are renumbered from 1, and the symbol is exported under
+    /// the name it's exported or linked with in this movie. AVM1 scripts aren't
+    /// carried over, other than init actions of the included sprites. AS3 movies
+    /// keep all of their ABC blocks, since there's no telling which classes a
+    /// symbol needs, along with the class links of the included characters.
+    ///
+    /// Use `write_to` to save the result as an SWF file.
+    pub fn export_symbol_as_swf(&self, character_id: CharacterId) -> Result<SwfMovie, Error> {
+        let (mut tags, tags_by_subject) = self.symbol_tags(character_id)?;
+
+        let name = tags
+            .iter()
+            .flatten()
+            .find_map(|tag| match tag {
+                Tag::ExportAssets(assets) => assets
+                    .iter()
+                    .find(|asset| asset.id == character_id)
+                    .map(|asset| asset.name.to_string_lossy(self.encoding())),
+                Tag::SymbolClass(links) => links
+                    .iter()
+                    .find(|link| link.id == character_id)
+                    .map(|link| link.class_name.to_string_lossy(self.encoding())),
+                _ => None,
+            })
+            .unwrap_or_else(|| format!("symbol{character_id}"));
+
+        let mut order = vec![];
+        collect_dependencies(
+            character_id,
+            &mut tags,
+            &tags_by_subject,
+            &mut FnvHashSet::default(),
+            &mut order,
+        );
+
+        // References to characters this movie doesn't define, such as imported ones, are
+        // kept as they are, so the new IDs have to steer clear of them.
+        let mut unresolved = FnvHashSet::default();
+        for id in &order {
+            for &index in &tags_by_subject[id] {
+                if let Some(tag) = &mut tags[index] {
+                    visit_references(tag, &mut |reference: &mut CharacterId| {
+                        if !tags_by_subject.contains_key(reference) {
+                            unresolved.insert(*reference);
+                        }
+                    });
+                }
+            }
+        }
+        let free_ids = (1..=CharacterId::MAX).filter(|id| !unresolved.contains(id));
+        let new_ids: FnvHashMap<CharacterId, CharacterId> =
+            order.iter().copied().zip(free_ids).collect();
+
+        let mut output = vec![];
+        if self.version() >= 8 {
+            let mut attributes = FileAttributes::empty();
+            attributes.set(
+                FileAttributes::IS_ACTION_SCRIPT_3,
+                self.is_action_script_3(),
+            );
+            output.push(Tag::FileAttributes(attributes));
+        }
+        if let Some(tables) = tags.iter().flatten().find_map(|tag| match tag {
+            Tag::JpegTables(tables) => Some(*tables),
+            _ => None,
+        }) {
+            // `DefineBits` images share these tables.
+            output.push(Tag::JpegTables(tables));
+        }
+
+        for id in &order {
+            let Some(&new_id) = new_ids.get(id) else {
+                // Only possible if every ID is taken.
+                continue;
+            };
+            for &index in &tags_by_subject[id] {
+                let Some(mut tag) = tags[index].take() else {
+                    continue;
+                };
+                set_subject(&mut tag, new_id);
+                visit_references(&mut tag, &mut |reference: &mut CharacterId| {
+                    if let Some(&new_id) = new_ids.get(reference) {
+                        *reference = new_id;
+                    }
+                });
+                output.push(tag);
+            }
+        }
+
+        if self.is_action_script_3() {
+            output.extend(tags.iter().flatten().filter_map(|tag| match tag {
+                Tag::DoAbc(data) => Some(Tag::DoAbc(*data)),
+                Tag::DoAbc2(abc) => Some(Tag::DoAbc2(abc.clone())),
+                _ => None,
+            }));
+            // The document class (ID 0) is left out, since the symbol becomes the only
+            // thing in the movie.
+            let links: Vec<_> = tags
+                .iter()
+                .flatten()
+                .filter_map(|tag| match tag {
+                    Tag::SymbolClass(links) => Some(links),
+                    _ => None,
+                })
+                .flatten()
+                .filter_map(|link| {
+                    Some(SymbolClassLink {
+                        id: *new_ids.get(&link.id)?,
+                        class_name: link.class_name,
+                    })
+                })
+                .collect();
+            if !links.is_empty() {
+                output.push(Tag::SymbolClass(links));
+            }
+        }
+
+        output.push(Tag::ExportAssets(vec![ExportedAsset {
+            id: new_ids[&character_id],
+            name: SwfStr::from_utf8_str(&name),
+        }]));
+        output.push(Tag::ShowFrame);
+
+        let mut data = vec![];
+        swf::write::write_tag_list(&output, self.version(), &mut data)?;
+        Ok(self.with_data(data, 1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tag_utils::SwfMovie;
+    use swf::{
+        Compression, DoAbc2, DoAbc2Flag, ExportedAsset, FileAttributes, FillStyle, Header, Matrix,
+        PlaceObject, PlaceObjectAction, Rectangle, Shape, ShapeFlag, ShapeStyles, Sprite, SwfStr,
+        SymbolClassLink, Tag, Twips,
+    };
+
+    fn shape(id: u16, bitmap_id: u16) -> Tag<'static> {
+        Tag::DefineShape(Shape {
+            version: 1,
+            id,
+            shape_bounds: Rectangle::default(),
+            edge_bounds: Rectangle::default(),
+            flags: ShapeFlag::empty(),
+            styles: ShapeStyles {
+                fill_styles: vec![FillStyle::Bitmap {
+                    id: bitmap_id,
+                    matrix: Matrix::IDENTITY,
+                    is_smoothed: false,
+                    is_repeating: false,
+                }],
+                line_styles: vec![],
+            },
+            shape: vec![],
+        })
+    }
+
+    fn place(id: u16) -> Tag<'static> {
+        Tag::PlaceObject(Box::new(PlaceObject {
+            version: 2,
+            action: PlaceObjectAction::Place(id),
+            depth: 1,
+            matrix: None,
+            color_transform: None,
+            ratio: None,
+            name: None,
+            clip_depth: None,
+            class_name: None,
+            filters: None,
+            background_color: None,
+            blend_mode: None,
+            clip_actions: None,
+            has_image: false,
+            is_bitmap_cached: None,
+            is_visible: None,
+            amf_data: None,
+        }))
+    }
+
+    #[test]
+    fn exports_dependency_closure() {
+        const JPEG: &[u8] = &[0xFF, 0xD8, 0xFF, 0xD9];
+        let header = Header {
+            compression: Compression::Zlib,
+            num_frames: 1,
+            stage_size: Rectangle {
+                x_min: Twips::ZERO,
+                x_max: Twips::from_pixels(100.0),
+                y_min: Twips::ZERO,
+                y_max: Twips::from_pixels(100.0),
+            },
+            ..Header::default_with_swf_version(10)
+        };
+        let tags = [
+            Tag::DefineBitsJpeg2 {
+                id: 10,
+                jpeg_data: JPEG,
+            },
+            Tag::DefineBitsJpeg2 {
+                id: 11,
+                jpeg_data: JPEG,
+            },
+            shape(20, 11),
+            shape(21, 10),
+            Tag::DefineSprite(Sprite {
+                id: 30,
+                num_frames: 1,
+                tags: vec![place(21), Tag::ShowFrame],
+            }),
+            Tag::ExportAssets(vec![ExportedAsset {
+                id: 30,
+                name: SwfStr::from_utf8_str("item_hat"),
+            }]),
+            Tag::ShowFrame,
+        ];
+        let mut swf = vec![];
+        swf::write_swf(&header, &tags, &mut swf).unwrap();
+        let movie = SwfMovie::from_data(&swf, "file:///items.swf".into(), None).unwrap();
+
+        let exported = movie.export_symbol_as_swf(30).unwrap();
+        let mut written = vec![];
+        exported.write_to(&mut written).unwrap();
+        let swf_buf = swf::decompress_swf(&written[..]).unwrap();
+        assert_eq!(swf_buf.header.compression(), Compression::Zlib);
+        let swf = swf::parse_swf(&swf_buf).unwrap();
+
+        // The unused shape and bitmap are dropped, and IDs are renumbered with
+        // dependencies first.
+        assert!(matches!(swf.tags[1], Tag::DefineBitsJpeg2 { id: 1, .. }));
+        assert_eq!(swf.tags[2], shape(2, 1));
+        assert_eq!(
+            swf.tags[3],
+            Tag::DefineSprite(Sprite {
+                id: 3,
+                num_frames: 1,
+                tags: vec![place(2), Tag::ShowFrame],
+            })
+        );
+        assert_eq!(
+            swf.tags[4],
+            Tag::ExportAssets(vec![ExportedAsset {
+                id: 3,
+                name: SwfStr::from_utf8_str("item_hat"),
+            }])
+        );
+        assert_eq!(swf.tags.len(), 6);
+
+        assert!(movie.export_symbol_as_swf(99).is_err());
+    }
+
+    fn export(tags: &[Tag<'_>], character_id: u16) -> Vec<u8> {
+        let mut swf = vec![];
+        swf::write_swf(&Header::default_with_swf_version(10), tags, &mut swf).unwrap();
+        let movie = SwfMovie::from_data(&swf, "file:///items.swf".into(), None).unwrap();
+        let mut written = vec![];
+        movie
+            .export_symbol_as_swf(character_id)
+            .unwrap()
+            .write_to(&mut written)
+            .unwrap();
+        written
+    }
+
+    #[test]
+    fn renumbering_avoids_undefined_references() {
+        // Character 1 is never defined here, like an imported one would be.
+        let tags = [
+            shape(20, 1),
+            Tag::DefineSprite(Sprite {
+                id: 30,
+                num_frames: 1,
+                tags: vec![place(1), place(20), Tag::ShowFrame],
+            }),
+            Tag::ShowFrame,
+        ];
+        let written = export(&tags, 30);
+        let swf_buf = swf::decompress_swf(&written[..]).unwrap();
+        let swf = swf::parse_swf(&swf_buf).unwrap();
+
+        assert_eq!(swf.tags[1], shape(2, 1));
+        assert_eq!(
+            swf.tags[2],
+            Tag::DefineSprite(Sprite {
+                id: 3,
+                num_frames: 1,
+                tags: vec![place(1), place(2), Tag::ShowFrame],
+            })
+        );
+    }
+
+    #[test]
+    fn keeps_as3_classes() {
+        let abc = DoAbc2 {
+            flags: DoAbc2Flag::LAZY_INITIALIZE,
+            name: SwfStr::from_utf8_str("frame1"),
+            data: &[0x10, 0x00, 0x2e, 0x00],
+        };
+        let tags = [
+            Tag::FileAttributes(FileAttributes::IS_ACTION_SCRIPT_3),
+            Tag::DefineSprite(Sprite {
+                id: 30,
+                num_frames: 1,
+                tags: vec![Tag::ShowFrame],
+            }),
+            Tag::DefineSprite(Sprite {
+                id: 40,
+                num_frames: 1,
+                tags: vec![Tag::ShowFrame],
+            }),
+            Tag::DoAbc2(abc.clone()),
+            Tag::SymbolClass(vec![
+                SymbolClassLink {
+                    id: 0,
+                    class_name: SwfStr::from_utf8_str("Main"),
+                },
+                SymbolClassLink {
+                    id: 30,
+                    class_name: SwfStr::from_utf8_str("items.Hat"),
+                },
+                SymbolClassLink {
+                    id: 40,
+                    class_name: SwfStr::from_utf8_str("items.Shoe"),
+                },
+            ]),
+            Tag::ShowFrame,
+        ];
+        let written = export(&tags, 30);
+        let swf_buf = swf::decompress_swf(&written[..]).unwrap();
+        let swf = swf::parse_swf(&swf_buf).unwrap();
+
+        assert_eq!(
+            swf.tags[0],
+            Tag::FileAttributes(FileAttributes::IS_ACTION_SCRIPT_3)
+        );
+        assert_eq!(swf.tags[2], Tag::DoAbc2(abc));
+        // Only the classes of the exported characters are linked, under their new IDs.
+        assert_eq!(
+            swf.tags[3],
+            Tag::SymbolClass(vec![SymbolClassLink {
+                id: 1,
+                class_name: SwfStr::from_utf8_str("items.Hat"),
+            }])
+        );
+        assert_eq!(
+            swf.tags[4],
+            Tag::ExportAssets(vec![ExportedAsset {
+                id: 1,
+                name: SwfStr::from_utf8_str("items.Hat"),
+            }])
+        );
+    }
+}
@@ -0,0 +1,27 @@
+//! Timing events for profiling tools.
+//!
+//! When telemetry is enabled, the player reports how long each frame and each render took,
+//! along with the metrics that movies send through `flash.profiler.Telemetry`. Everything is
+//! logged as `tracing` events under this module's target, so a frontend can pick them out of
+//! the log (e.g. as JSON) without recording anything else. Times are in milliseconds of the
+//! player's clock, the same ones that `getTimer` returns.
+
+/// A frame of the stage ran, starting at `start` and taking `duration`.
+pub fn frame(start: f64, duration: f64) {
+    tracing::info!(start, duration, "frame");
+}
+
+/// The stage was rendered, starting at `start` and taking `duration`.
+pub fn render(start: f64, duration: f64) {
+    tracing::info!(start, duration, "render");
+}
+
+/// A movie sent a metric with `Telemetry.sendMetric`.
+pub fn metric(name: &str, value: &str) {
+    tracing::info!(name, value, "metric");
+}
+
+/// A movie sent a metric for a span with `Telemetry.sendSpanMetric`.
+pub fn span_metric(name: &str, start: f64, duration: f64, value: Option<&str>) {
+    tracing::info!(name, start, duration, value, "span metric");
+}
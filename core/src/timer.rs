@@ -2,7 +2,8 @@
 //!
 //! We tick the timers during our normal frame loop for deterministic operation.
 //! The timers are stored in a priority queue, where we check if the nearest timer
-//! is ready to tick each frame.
+//! is ready to tick each frame. Timers faster than the frame rate tick several times
+//! per frame, in the order they were scheduled.
 
 use crate::avm1::ExecutionReason;
 use crate::avm1::{
@@ -13,6 +14,7 @@ use crate::avm2::{Activation as Avm2Activation, Object as Avm2Object, Value as A
 use crate::context::UpdateContext;
 use crate::display_object::{DisplayObject, TDisplayObject};
 use crate::string::AvmString;
+use fnv::FnvHashMap;
 use gc_arena::Collect;
 use std::collections::{binary_heap::PeekMut, BinaryHeap};
 
@@ -24,26 +26,31 @@ pub struct Timers<'gc> {
     /// An increasing ID used for created timers.
     timer_counter: i32,
 
-    /// The current global time.
+    /// The time that timers were last updated at, taken from the player's clock.
     cur_time: u64,
+
+    /// Whether a timer callback called `updateAfterEvent`, and the player should render
+    /// before the remaining timers run.
+    render_requested: bool,
+
+    /// How many times each timer has ticked in the current update.
+    /// Kept across the renders requested by `updateAfterEvent`, which continue the same update.
+    tick_counts: FnvHashMap<i32, i32>,
 }
 
 impl<'gc> Timers<'gc> {
-    /// Ticks all timers and runs necessary callbacks.
-    pub fn update_timers(context: &mut UpdateContext<'_, 'gc>, dt: f64) -> Option<f64> {
-        context.timers.cur_time = context
-            .timers
-            .cur_time
-            .wrapping_add((dt * Self::TIMER_SCALE) as u64);
+    /// Ticks all timers up to the time the player's clock has been ticked to, and runs
+    /// necessary callbacks.
+    pub fn update_timers(context: &mut UpdateContext<'_, 'gc>) -> Option<f64> {
+        context.timers.cur_time = (context.clock.tick_time() * Self::TIMER_SCALE) as u64;
         let num_timers = context.timers.num_timers();
 
         if num_timers == 0 {
+            context.timers.tick_counts.clear();
             return None;
         }
 
         let level0 = context.stage.root_clip();
-
-        let mut tick_count = 0;
         let cur_time = context.timers.cur_time;
 
         // We have to be careful because the timer list can be mutated while updating;
@@ -64,18 +71,24 @@ impl<'gc> Timers<'gc> {
                 continue;
             }
 
-            tick_count += 1;
+            let expected_id = timer.id;
+            let tick_count = context.timers.tick_counts.entry(expected_id).or_insert(0);
+            *tick_count += 1;
             // SANITY: Only allow so many ticks per timer per update.
-            if tick_count > Self::MAX_TICKS {
-                // Reset our time to a little bit before the nearest timer.
-                let next_time = context.timers.peek_mut().unwrap().tick_time;
-                context.timers.cur_time = next_time.wrapping_sub(100);
-                break;
+            // A timer that fell further behind skips the ticks it missed, but stays in step
+            // with its original schedule rather than drifting.
+            if *tick_count > Self::MAX_TICKS {
+                let mut timer = context.timers.peek_mut().unwrap();
+                let missed_ticks = cur_time.wrapping_sub(timer.tick_time) / timer.interval + 1;
+                timer.tick_time = timer.tick_time.wrapping_add(missed_ticks * timer.interval);
+                continue;
             }
 
             // TODO: Can we avoid these clones?
-            let callback = timer.callback.clone();
-            let expected_id = timer.id;
+            let callback = context.timers.peek().unwrap().callback.clone();
+
+            // Only an `updateAfterEvent` call from this callback should interrupt the timers.
+            let needed_render = std::mem::replace(context.needs_render, false);
 
             let cancel_timer = match callback {
                 TimerCallback::Avm1Function { func, params } => {
@@ -165,6 +178,9 @@ impl<'gc> Timers<'gc> {
 
             crate::player::Player::run_actions(context);
 
+            let render_requested = *context.needs_render;
+            *context.needs_render |= needed_render;
+
             let mut timer = context.timers.peek_mut().unwrap();
             // Our timer should still be on the top of the heap.
             // The only way that this could fail is the timer callback
@@ -184,6 +200,16 @@ impl<'gc> Timers<'gc> {
                 // Reset setInterval timers. `peek_mut` re-sorts the timer in the priority queue.
                 timer.tick_time = timer.tick_time.wrapping_add(timer.interval);
             }
+
+            if render_requested {
+                // Let the player show this callback's changes before running the next one.
+                context.timers.render_requested = true;
+                break;
+            }
+        }
+
+        if !context.timers.render_requested {
+            context.timers.tick_counts.clear();
         }
 
         // Return estimated time until next timer tick.
@@ -196,7 +222,7 @@ impl<'gc> Timers<'gc> {
     /// The minimum interval we allow for timers.
     const MIN_INTERVAL: i32 = 10;
 
-    /// The maximum timer ticks per call to `update_ticks`, for sanity.
+    /// The maximum ticks of each timer per call to `update_timers`, for sanity.
     const MAX_TICKS: i32 = 10;
 
     /// The scale of the timers (microseconds).
@@ -208,9 +234,17 @@ impl<'gc> Timers<'gc> {
             timers: Default::default(),
             timer_counter: 0,
             cur_time: 0,
+            render_requested: false,
+            tick_counts: FnvHashMap::default(),
         }
     }
 
+    /// Returns whether the last `update_timers` stopped early so the player could render,
+    /// and clears the request.
+    pub fn take_render_request(&mut self) -> bool {
+        std::mem::take(&mut self.render_requested)
+    }
+
     /// The number of timers currently active.
     pub fn num_timers(&self) -> usize {
         self.timers.len()
@@ -303,9 +337,10 @@ pub struct Timer<'gc> {
 }
 
 // Implement `Ord` so that timers can be stored in the BinaryHeap (as a min-heap).
+// Timers that are due at the same time tick in the order they were created.
 impl PartialEq for Timer<'_> {
     fn eq(&self, other: &Self) -> bool {
-        self.tick_time == other.tick_time
+        self.tick_time == other.tick_time && self.id == other.id
     }
 }
 
@@ -319,7 +354,9 @@ impl PartialOrd for Timer<'_> {
 
 impl Ord for Timer<'_> {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.tick_time.cmp(&other.tick_time).reverse()
+        (self.tick_time, self.id)
+            .cmp(&(other.tick_time, other.id))
+            .reverse()
     }
 }
 
@@ -344,3 +381,120 @@ pub enum TimerCallback<'gc> {
         params: Vec<Avm2Value<'gc>>,
     },
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{TimerCallback, Timers};
+    use crate::avm1::{Activation, ActivationIdentifier, ArrayObject, Object, TObject, Value};
+    use crate::context::UpdateContext;
+    use crate::player::PlayerBuilder;
+    use crate::tag_utils::SwfMovie;
+
+    fn with_context(test: impl for<'a, 'gc> FnOnce(&mut UpdateContext<'a, 'gc>)) {
+        let player = PlayerBuilder::new().with_movie(SwfMovie::empty(10)).build();
+        let mut player = player.lock().unwrap();
+        player.mutate_with_update_context(test);
+    }
+
+    /// Adds an interval timer that pushes `value` onto `log` each time it ticks.
+    fn add_logging_timer<'gc>(
+        context: &mut UpdateContext<'_, 'gc>,
+        log: Object<'gc>,
+        value: i32,
+        interval: i32,
+    ) {
+        context.timers.add_timer(
+            TimerCallback::Avm1Method {
+                this: log,
+                method_name: "push".into(),
+                params: vec![value.into()],
+            },
+            interval,
+            false,
+        );
+    }
+
+    /// Ticks the player's clock by `dt` milliseconds and runs the timers that are due.
+    fn advance(context: &mut UpdateContext<'_, '_>, dt: f64) -> Option<f64> {
+        context.clock.tick(dt);
+        Timers::update_timers(context)
+    }
+
+    fn new_log<'gc>(context: &mut UpdateContext<'_, 'gc>) -> Object<'gc> {
+        ArrayObject::new(context.gc_context, context.avm1.prototypes().array, []).into()
+    }
+
+    fn logged<'gc>(context: &mut UpdateContext<'_, 'gc>, log: Object<'gc>) -> Vec<i32> {
+        let root = context.stage.root_clip().unwrap();
+        let mut activation = Activation::from_nothing(
+            context.reborrow(),
+            ActivationIdentifier::root("[Test]"),
+            root,
+        );
+        let length = log.length(&mut activation).unwrap();
+        (0..length)
+            .map(|i| match log.get_element(&mut activation, i) {
+                Value::Number(n) => n as i32,
+                value => panic!("Unexpected log entry {value:?}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn timers_tick_in_schedule_order() {
+        with_context(|context| {
+            let log = new_log(context);
+            add_logging_timer(context, log, 1, 30);
+            add_logging_timer(context, log, 2, 20);
+
+            advance(context, 60.0);
+            assert_eq!(logged(context, log), [2, 1, 2]);
+
+            // Timers due at the same time tick in the order they were created.
+            advance(context, 60.0);
+            assert_eq!(logged(context, log), [2, 1, 2, 1, 2, 2, 1, 2]);
+        });
+    }
+
+    #[test]
+    fn slow_timers_skip_missed_ticks_without_drifting() {
+        with_context(|context| {
+            let log = new_log(context);
+            add_logging_timer(context, log, 1, 10);
+
+            let next_tick = advance(context, 1005.0);
+            assert_eq!(logged(context, log).len(), Timers::MAX_TICKS as usize);
+            assert_eq!(next_tick, Some(5.0));
+
+            advance(context, 5.0);
+            assert_eq!(logged(context, log).len(), Timers::MAX_TICKS as usize);
+            advance(context, 1.0);
+            assert_eq!(logged(context, log).len(), Timers::MAX_TICKS as usize + 1);
+        });
+    }
+
+    #[test]
+    fn update_after_event_shares_the_tick_budget() {
+        with_context(|context| {
+            let global = context.avm1.global_object();
+            context.timers.add_timer(
+                TimerCallback::Avm1Method {
+                    this: global,
+                    method_name: "updateAfterEvent".into(),
+                    params: vec![],
+                },
+                10,
+                false,
+            );
+
+            let mut updates = 1;
+            advance(context, 1000.0);
+            while context.timers.take_render_request() {
+                updates += 1;
+                Timers::update_timers(context);
+            }
+            // Each tick asks for a render, but the timer still only ticks `MAX_TICKS` times.
+            assert_eq!(updates, Timers::MAX_TICKS + 1);
+        });
+    }
+}
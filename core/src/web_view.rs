@@ -0,0 +1,254 @@
+//! Embedder-provided web views behind AIR's `flash.html.HTMLLoader`.
+//!
+//! Ruffle has no HTML engine of its own. An embedder with access to one (such as a system
+//! webview) can provide it through `WebViewProvider`; without one, `HTMLLoader.isSupported` is
+//! `false` and loads are ignored.
+
+use crate::{
+    avm2::{
+        object::WeakObject as Avm2WeakObject, Avm2, EventObject as Avm2EventObject,
+        Object as Avm2Object,
+    },
+    context::UpdateContext,
+    external::Value,
+};
+use gc_arena::{Collect, Mutation};
+use generational_arena::{Arena, Index};
+use std::rc::Rc;
+use std::sync::mpsc::{channel, Receiver, Sender};
+
+pub type WebViewHandle = Index;
+
+/// Creates web views for `HTMLLoader`s.
+pub trait WebViewProvider {
+    /// Creates a view for a new `HTMLLoader`.
+    ///
+    /// `events` reports page loads back to the loader, from any thread, for as long as the view
+    /// is alive. Returns `None` if no view can be created right now.
+    fn create_web_view(&self, events: WebViewEventSender) -> Option<Box<dyn WebView>>;
+}
+
+/// Lets an embedder keep a handle on the provider it gives to the player.
+impl<T: WebViewProvider + ?Sized> WebViewProvider for Rc<T> {
+    fn create_web_view(&self, events: WebViewEventSender) -> Option<Box<dyn WebView>> {
+        (**self).create_web_view(events)
+    }
+}
+
+/// A single web view, owned by an `HTMLLoader`.
+pub trait WebView {
+    /// Navigates to `url`, as `HTMLLoader.load` does.
+    fn load_url(&mut self, url: &str);
+
+    /// Shows the given markup, as `HTMLLoader.loadString` does.
+    fn load_string(&mut self, html: &str);
+
+    /// Runs a script in the current page and returns its result.
+    ///
+    /// Returns `None` if the script threw or its result can't be represented.
+    fn execute_javascript(&mut self, script: &str) -> Option<Value>;
+
+    /// The text content of the current page's body.
+    fn body_text(&mut self) -> String;
+
+    /// The URL of the current page.
+    fn location(&self) -> String;
+
+    /// Called when the view's `HTMLLoader` has been garbage collected, right before the view is
+    /// dropped.
+    fn close(&mut self) {}
+}
+
+/// Something that happened in a web view, to be dispatched to its `HTMLLoader`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WebViewEvent {
+    /// The view started navigating to a new URL.
+    LocationChange,
+    /// The current page finished loading.
+    Complete,
+}
+
+/// Reports what happens in a web view back to its `HTMLLoader`.
+///
+/// Events are dispatched as `Event`s on the loader during the player's next update.
+#[derive(Clone)]
+pub struct WebViewEventSender {
+    handle: WebViewHandle,
+    sender: Sender<(WebViewHandle, WebViewEvent)>,
+}
+
+impl WebViewEventSender {
+    /// Returns `false` if the view has no player to report to anymore.
+    pub fn send(&self, event: WebViewEvent) -> bool {
+        self.sender.send((self.handle, event)).is_ok()
+    }
+}
+
+#[derive(Collect)]
+#[collect(no_drop)]
+struct ActiveWebView<'gc> {
+    /// The `HTMLLoader` this view belongs to.
+    ///
+    /// The view is released once the loader is garbage collected, rather than keeping it alive.
+    target: Avm2WeakObject<'gc>,
+    /// `None` until `WebViewProvider::create_web_view` returns, since the view's event sender
+    /// needs a handle.
+    #[collect(require_static)]
+    view: Option<Box<dyn WebView>>,
+}
+
+/// Manages the web views of every `HTMLLoader`.
+pub struct WebViews<'gc> {
+    provider: Option<Box<dyn WebViewProvider>>,
+    views: Arena<ActiveWebView<'gc>>,
+
+    receiver: Receiver<(WebViewHandle, WebViewEvent)>,
+    sender: Sender<(WebViewHandle, WebViewEvent)>,
+}
+
+unsafe impl<'gc> Collect for WebViews<'gc> {
+    fn trace(&self, cc: &gc_arena::Collection) {
+        for (_, view) in self.views.iter() {
+            view.trace(cc)
+        }
+    }
+}
+
+impl<'gc> WebViews<'gc> {
+    pub fn new(provider: Option<Box<dyn WebViewProvider>>) -> Self {
+        let (sender, receiver) = channel();
+
+        Self {
+            provider,
+            views: Arena::new(),
+            receiver,
+            sender,
+        }
+    }
+
+    /// Whether `HTMLLoader`s can show anything.
+    pub fn is_supported(&self) -> bool {
+        self.provider.is_some()
+    }
+
+    fn find(&self, mc: &Mutation<'gc>, target: Avm2Object<'gc>) -> Option<WebViewHandle> {
+        self.views
+            .iter()
+            .find(|(_, view)| {
+                view.target
+                    .upgrade(mc)
+                    .is_some_and(|view_target| Avm2Object::ptr_eq(view_target, target))
+            })
+            .map(|(handle, _)| handle)
+    }
+
+    /// The view of the `HTMLLoader` object `target`, if it has loaded anything yet.
+    fn existing_view(
+        &mut self,
+        mc: &Mutation<'gc>,
+        target: Avm2Object<'gc>,
+    ) -> Option<&mut dyn WebView> {
+        let handle = self.find(mc, target)?;
+        self.views[handle].view.as_deref_mut()
+    }
+
+    /// Finds the view of the `HTMLLoader` object `target`, creating one if needed.
+    fn view(&mut self, mc: &Mutation<'gc>, target: Avm2Object<'gc>) -> Option<&mut dyn WebView> {
+        let handle = match self.find(mc, target) {
+            Some(handle) => handle,
+            None => {
+                let Some(provider) = &self.provider else {
+                    tracing::warn!("HTMLLoader isn't supported without a web view provider");
+                    return None;
+                };
+
+                // Events are routed by handle, so the slot has to exist before the view does.
+                let handle = self.views.insert(ActiveWebView {
+                    target: target.downgrade(),
+                    view: None,
+                });
+                let events = WebViewEventSender {
+                    handle,
+                    sender: self.sender.clone(),
+                };
+                match provider.create_web_view(events) {
+                    Some(view) => self.views[handle].view = Some(view),
+                    None => {
+                        tracing::warn!("Couldn't create a web view for HTMLLoader");
+                        self.views.remove(handle);
+                        return None;
+                    }
+                }
+                handle
+            }
+        };
+
+        self.views[handle].view.as_deref_mut()
+    }
+
+    pub fn load_url(&mut self, mc: &Mutation<'gc>, target: Avm2Object<'gc>, url: &str) {
+        if let Some(view) = self.view(mc, target) {
+            view.load_url(url);
+        }
+    }
+
+    pub fn load_string(&mut self, mc: &Mutation<'gc>, target: Avm2Object<'gc>, html: &str) {
+        if let Some(view) = self.view(mc, target) {
+            view.load_string(html);
+        }
+    }
+
+    pub fn execute_javascript(
+        &mut self,
+        mc: &Mutation<'gc>,
+        target: Avm2Object<'gc>,
+        script: &str,
+    ) -> Option<Value> {
+        self.existing_view(mc, target)?.execute_javascript(script)
+    }
+
+    pub fn body_text(&mut self, mc: &Mutation<'gc>, target: Avm2Object<'gc>) -> Option<String> {
+        Some(self.existing_view(mc, target)?.body_text())
+    }
+
+    pub fn location(&mut self, mc: &Mutation<'gc>, target: Avm2Object<'gc>) -> Option<String> {
+        Some(self.existing_view(mc, target)?.location())
+    }
+
+    /// Dispatches the events sent by web views since the last update, and releases the views
+    /// of `HTMLLoader`s that have been garbage collected.
+    pub fn update_web_views(context: &mut UpdateContext<'_, 'gc>) {
+        let mc = context.gc_context;
+        context.web_views.views.retain(|_, active| {
+            if active.target.upgrade(mc).is_some() {
+                return true;
+            }
+            if let Some(view) = &mut active.view {
+                view.close();
+            }
+            false
+        });
+
+        let mut events = vec![];
+        while let Ok((handle, event)) = context.web_views.receiver.try_recv() {
+            // Events for released views are dropped.
+            if let Some(target) = context
+                .web_views
+                .views
+                .get(handle)
+                .and_then(|active| active.target.upgrade(mc))
+            {
+                events.push((target, event));
+            }
+        }
+
+        for (target, event) in events {
+            let event_type = match event {
+                WebViewEvent::LocationChange => "locationChange",
+                WebViewEvent::Complete => "complete",
+            };
+            let event = Avm2EventObject::bare_default_event(context, event_type);
+            Avm2::dispatch_event(context, event, target);
+        }
+    }
+}
@@ -32,4 +32,6 @@ debug-menu-open-movie = View Movie
 debug-menu-open-movie-list = Show Known Movies
 debug-menu-search-display-objects = Search Display Objects...
 debug-menu-take-screenshot = Take Screenshot
+debug-menu-log-levels = Log Levels
+debug-menu-log-level-default = Default
 
@@ -0,0 +1,130 @@
+//! Launching AIR applications, either from a `.air` package or from an
+//! unpacked application directory.
+
+use crate::util::create_in_temp_dir;
+use anyhow::{anyhow, Context, Error};
+use ruffle_core::backend::navigator::AirDirectories;
+use ruffle_core::package::{self, AirApplication, AIR_DESCRIPTOR_PATH};
+use std::path::{Component, Path, PathBuf};
+use url::Url;
+
+/// An AIR application ready to be played.
+pub struct AirLaunch {
+    pub application: AirApplication,
+    pub directories: AirDirectories,
+
+    /// The root SWF of the application.
+    pub content_url: Url,
+}
+
+impl AirLaunch {
+    /// Prepares the AIR application at `movie_url`, if it is one.
+    ///
+    /// Packages are extracted into a temporary directory, which then becomes the
+    /// application directory.
+    pub fn open(movie_url: &Url) -> Result<Option<Self>, Error> {
+        let Ok(path) = movie_url.to_file_path() else {
+            return Ok(None);
+        };
+
+        let (application, directory) = if path.is_dir() {
+            let descriptor = path.join(AIR_DESCRIPTOR_PATH);
+            if !descriptor.is_file() {
+                return Ok(None);
+            }
+            let xml = std::fs::read(&descriptor)
+                .with_context(|| format!("Couldn't read {}", descriptor.display()))?;
+            let application = AirApplication::parse(&xml)
+                .ok_or_else(|| anyhow!("Invalid application descriptor"))?;
+            (application, path)
+        } else if path
+            .extension()
+            .map_or(false, |ext| ext.eq_ignore_ascii_case("air"))
+        {
+            let data = std::fs::read(&path)
+                .with_context(|| format!("Couldn't read {}", path.display()))?;
+            let application = package::read_air_descriptor(&data)
+                .ok_or_else(|| anyhow!("AIR package has no valid application descriptor"))?;
+            // A fresh directory only we can access, so nobody can swap out the application's files.
+            let prefix = format!("ruffle-air-{}-", sanitize(&application.id));
+            let (directory, ()) = create_in_temp_dir(&prefix, "", |path| {
+                let mut builder = std::fs::DirBuilder::new();
+                #[cfg(unix)]
+                std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+                builder.create(path)
+            })
+            .context("Couldn't create a directory to extract the package into")?;
+            package::extract_archive(&data, &directory)?;
+            (application, directory)
+        } else {
+            return Ok(None);
+        };
+
+        let storage = storage_directory(&application.id)?;
+        std::fs::create_dir_all(&storage)?;
+
+        let directories = AirDirectories::from_paths(&directory, &storage)
+            .ok_or_else(|| anyhow!("Couldn't build AIR directory URLs"))?;
+        let content_path = contained_path(&directory, &application.initial_window.content)
+            .ok_or_else(|| anyhow!("Content path must stay within the application directory"))?;
+        let content_url =
+            Url::from_file_path(content_path).map_err(|_| anyhow!("Invalid content path"))?;
+
+        Ok(Some(Self {
+            application,
+            directories,
+            content_url,
+        }))
+    }
+
+    /// The window title to show while this application runs.
+    pub fn title(&self) -> &str {
+        self.application
+            .initial_window
+            .title
+            .as_deref()
+            .or(self.application.name.as_deref())
+            .unwrap_or(&self.application.filename)
+    }
+}
+
+/// Where Ruffle keeps the data of the application with the given id.
+pub fn application_data_directory(id: &str) -> Result<PathBuf, Error> {
+    Ok(dirs::data_local_dir()
+        .context("Couldn't find a valid data_local dir")?
+        .join("ruffle")
+        .join("air")
+        .join(sanitize(id)))
+}
+
+/// Where `app-storage:/` points for the application with the given id.
+fn storage_directory(id: &str) -> Result<PathBuf, Error> {
+    Ok(application_data_directory(id)?.join("Local Store"))
+}
+
+/// Joins a relative path from an application descriptor onto `directory`, if it stays inside it.
+fn contained_path(directory: &Path, relative: &str) -> Option<PathBuf> {
+    let relative = Path::new(relative.trim_start_matches('/'));
+    let stays_inside = relative
+        .components()
+        .all(|component| matches!(component, Component::Normal(_) | Component::CurDir));
+    stays_inside.then(|| directory.join(relative))
+}
+
+/// Makes an application id safe to use as a single path component.
+fn sanitize(id: &str) -> String {
+    let name: String = id
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '.' || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    match name.trim_start_matches('.') {
+        "" => "application".to_string(),
+        name => name.to_string(),
+    }
+}
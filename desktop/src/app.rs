@@ -166,12 +166,23 @@ impl App {
                                     height: size.height - height_offset as u32,
                                     scale_factor: viewport_scale_factor,
                                 });
+                                player.update_window_bounds();
                             }
                             self.window.request_redraw();
                             if matches!(loaded, LoadingState::WaitingForResize) {
                                 loaded = LoadingState::Loaded;
                             }
                         }
+                        WindowEvent::Moved(_) => {
+                            if let Some(mut player) = self.player.get() {
+                                player.update_window_bounds();
+                            }
+                        }
+                        WindowEvent::Focused(focused) => {
+                            if let Some(mut player) = self.player.get() {
+                                player.set_window_active(focused);
+                            }
+                        }
                         WindowEvent::CursorMoved { position, .. } => {
                             if self.gui.borrow_mut().is_context_menu_visible() {
                                 return;
@@ -213,16 +224,17 @@ impl App {
                                 ElementState::Pressed => PlayerEvent::MouseDown { x, y, button },
                                 ElementState::Released => PlayerEvent::MouseUp { x, y, button },
                             };
+                            self.player.handle_event(event);
                             if state == ElementState::Pressed && button == RuffleMouseButton::Right
                             {
-                                // Show context menu.
-                                // TODO: Should be squelched if player consumes the right click event.
+                                // Show context menu, unless the content handles right clicks itself.
                                 if let Some(mut player) = self.player.get() {
-                                    let context_menu = player.prepare_context_menu();
-                                    self.gui.borrow_mut().show_context_menu(context_menu);
+                                    if !player.right_click_consumed() {
+                                        let context_menu = player.prepare_context_menu();
+                                        self.gui.borrow_mut().show_context_menu(context_menu);
+                                    }
                                 }
                             }
-                            self.player.handle_event(event);
                             check_redraw = true;
                         }
                         WindowEvent::MouseWheel { delta, .. } => {
@@ -416,6 +428,17 @@ impl App {
                     }
                 }
 
+                winit::event::Event::UserEvent(RuffleEvent::SetNativeMenu(menu)) => {
+                    self.gui.borrow_mut().set_native_menu(menu);
+                    check_redraw = true;
+                }
+
+                winit::event::Event::UserEvent(RuffleEvent::NativeMenuItemClicked(id)) => {
+                    if let Some(mut player) = self.player.get() {
+                        player.run_native_menu_callback(id);
+                    }
+                }
+
                 winit::event::Event::UserEvent(RuffleEvent::BrowseAndOpen(options)) => {
                     if let Some(url) =
                         pick_file(false, None).and_then(|p| Url::from_file_path(p).ok())
@@ -436,6 +459,14 @@ impl App {
                     self.player.destroy();
                 }
 
+                winit::event::Event::UserEvent(RuffleEvent::ReloadFile) => {
+                    if let Some((url, options)) = self.player.movie().cloned() {
+                        self.gui
+                            .borrow_mut()
+                            .create_movie(&mut self.player, options, url);
+                    }
+                }
+
                 winit::event::Event::UserEvent(RuffleEvent::ExitRequested) => {
                     *control_flow = ControlFlow::Exit;
                     return;
@@ -1,11 +1,15 @@
 mod audio;
 mod external_interface;
 mod navigator;
+#[cfg(feature = "encrypted_local_store")]
+mod secure_storage;
 mod storage;
 mod ui;
 
 pub use audio::CpalAudioBackend;
 pub use external_interface::DesktopExternalInterfaceProvider;
 pub use navigator::ExternalNavigatorBackend;
+#[cfg(feature = "encrypted_local_store")]
+pub use secure_storage::DiskSecureStorageBackend;
 pub use storage::DiskStorageBackend;
 pub use ui::DesktopUiBackend;
@@ -9,4 +9,10 @@ impl ExternalInterfaceProvider for DesktopExternalInterfaceProvider {
     }
 
     fn on_callback_available(&self, _name: &str) {}
+
+    fn run_javascript_url(&self, script: &str) -> bool {
+        // There's no page to run it in, and opening it in a browser would do nothing.
+        tracing::info!("Ignoring javascript: URL: {script}");
+        true
+    }
 }
@@ -1,6 +1,7 @@
 //! Navigator backend for web
 
 use crate::custom_event::RuffleEvent;
+use crate::util::create_in_temp_dir;
 use async_channel::{Receiver, TryRecvError};
 use async_io::Timer;
 use async_net::TcpStream;
@@ -9,19 +10,26 @@ use futures::{AsyncReadExt, AsyncWriteExt};
 use futures_lite::FutureExt;
 use isahc::http::{HeaderName, HeaderValue};
 use isahc::{
-    config::RedirectPolicy, prelude::*, AsyncReadResponseExt, HttpClient, Request as IsahcRequest,
+    config::{Dialer, RedirectPolicy as IsahcRedirectPolicy},
+    prelude::*,
+    AsyncReadResponseExt, HttpClient, Request as IsahcRequest,
 };
 use rfd::{AsyncMessageDialog, MessageButtons, MessageDialog, MessageDialogResult, MessageLevel};
 use ruffle_core::backend::navigator::{
-    async_return, create_fetch_error, create_specific_fetch_error, ErrorResponse, NavigationMethod,
-    NavigatorBackend, OpenURLMode, OwnedFuture, Request, SocketMode, SuccessResponse,
+    async_return, create_fetch_error, create_specific_fetch_error, fetch_data_url, AirDirectories,
+    BodyChunk, ErrorResponse, HostOverrides, NavigationMethod, NavigatorBackend, OpenURLMode,
+    OwnedFuture, RedirectPolicy, Request, SocketMode, SuccessResponse,
 };
 use ruffle_core::indexmap::IndexMap;
 use ruffle_core::loader::Error;
+use ruffle_core::net_group::{NetGroupAction, NetGroupHandle};
+use ruffle_core::policy_file::{policy_allows_port, MAX_POLICY_FILE_SIZE};
 use ruffle_core::socket::{ConnectionState, SocketAction, SocketHandle};
 use std::collections::HashSet;
 use std::io;
 use std::io::ErrorKind;
+use std::net::SocketAddr;
+use std::path::PathBuf;
 use std::rc::Rc;
 use std::str::FromStr;
 use std::sync::mpsc::Sender;
@@ -49,6 +57,14 @@ pub struct ExternalNavigatorBackend {
 
     socket_mode: SocketMode,
 
+    host_overrides: HostOverrides,
+
+    /// How long requests wait to connect, unless they ask for a timeout of their own.
+    connection_timeout: Option<Duration>,
+
+    /// Where `app:/` and `app-storage:/` URLs point, when running an AIR application.
+    air_directories: Option<AirDirectories>,
+
     upgrade_to_https: bool,
 
     open_url_mode: OpenURLMode,
@@ -66,12 +82,15 @@ impl ExternalNavigatorBackend {
         open_url_mode: OpenURLMode,
         socket_allowed: HashSet<String>,
         socket_mode: SocketMode,
+        host_overrides: HostOverrides,
+        connection_timeout: Option<Duration>,
+        air_directories: Option<AirDirectories>,
     ) -> Self {
         let proxy = proxy.and_then(|url| url.as_str().parse().ok());
         let builder = HttpClient::builder()
             .proxy(proxy)
             .cookies()
-            .redirect_policy(RedirectPolicy::Follow);
+            .redirect_policy(IsahcRedirectPolicy::Follow);
 
         let client = builder.build().ok().map(Rc::new);
 
@@ -90,88 +109,23 @@ impl ExternalNavigatorBackend {
             open_url_mode,
             socket_allowed,
             socket_mode,
+            host_overrides,
+            connection_timeout,
+            air_directories,
         }
     }
-}
 
-impl NavigatorBackend for ExternalNavigatorBackend {
-    fn navigate_to_url(
+    /// Fetches a request. If `body_sender` is given, the body is sent over it as it
+    /// arrives instead of being collected into the response.
+    fn fetch_with_body_sender(
         &self,
-        url: &str,
-        _target: &str,
-        vars_method: Option<(NavigationMethod, IndexMap<String, String>)>,
-    ) {
-        //TODO: Should we return a result for failed opens? Does Flash care?
-
-        //NOTE: Flash desktop players / projectors ignore the window parameter,
-        //      unless it's a `_layer`, and we shouldn't handle that anyway.
-        let mut parsed_url = match self.resolve_url(url) {
-            Ok(parsed_url) => parsed_url,
-            Err(e) => {
-                tracing::error!(
-                    "Could not parse URL because of {}, the corrupt URL was: {}",
-                    e,
-                    url
-                );
-                return;
-            }
-        };
-
-        let modified_url = match vars_method {
-            Some((_, query_pairs)) => {
-                {
-                    //lifetime limiter because we don't have NLL yet
-                    let mut modifier = parsed_url.query_pairs_mut();
-
-                    for (k, v) in query_pairs.iter() {
-                        modifier.append_pair(k, v);
-                    }
-                }
-
-                parsed_url
-            }
-            None => parsed_url,
-        };
-
-        if modified_url.scheme() == "javascript" {
-            tracing::warn!(
-                "SWF tried to run a script on desktop, but javascript calls are not allowed"
-            );
-            return;
+        mut request: Request,
+        body_sender: Option<async_channel::Sender<BodyChunk>>,
+    ) -> OwnedFuture<SuccessResponse, ErrorResponse> {
+        if request.connection_timeout().is_none() {
+            request.set_connection_timeout(self.connection_timeout);
         }
 
-        if self.open_url_mode == OpenURLMode::Confirm {
-            let message = format!("The SWF file wants to open the website {}", modified_url);
-            // TODO: Add a checkbox with a GUI toolkit
-            let confirm = MessageDialog::new()
-                .set_title("Open website?")
-                .set_level(MessageLevel::Info)
-                .set_description(message)
-                .set_buttons(MessageButtons::OkCancel)
-                .show()
-                == MessageDialogResult::Ok;
-            if !confirm {
-                tracing::info!("SWF tried to open a website, but the user declined the request");
-                return;
-            }
-        } else if self.open_url_mode == OpenURLMode::Deny {
-            tracing::warn!("SWF tried to open a website, but opening a website is not allowed");
-            return;
-        }
-
-        // If the user confirmed or if in Allow mode, open the website
-
-        // TODO: This opens local files in the browser while flash opens them
-        // in the default program for the respective filetype.
-        // This especially includes mailto links. Ruffle opens the browser which opens
-        // the preferred program while flash opens the preferred program directly.
-        match webbrowser::open(modified_url.as_ref()) {
-            Ok(_output) => {}
-            Err(e) => tracing::error!("Could not open URL {}: {}", modified_url.as_str(), e),
-        };
-    }
-
-    fn fetch(&self, request: Request) -> OwnedFuture<SuccessResponse, ErrorResponse> {
         // TODO: honor sandbox type (local-with-filesystem, local-with-network, remote, ...)
         let mut processed_url = match self.resolve_url(request.url()) {
             Ok(url) => url,
@@ -181,6 +135,11 @@ impl NavigatorBackend for ExternalNavigatorBackend {
         };
 
         let client = self.client.clone();
+        let host_override = processed_url
+            .host_str()
+            .zip(processed_url.port_or_known_default())
+            .and_then(|(host, port)| self.host_overrides.get(host, port))
+            .map(|(host, port)| (host.to_string(), port));
 
         match processed_url.scheme() {
             "file" => Box::pin(async move {
@@ -228,13 +187,36 @@ impl NavigatorBackend for ExternalNavigatorBackend {
                     Err(e) => return create_specific_fetch_error("Can't open file", response_url.as_str(), e)
                 };
 
+                let body = match body_sender {
+                    Some(body_sender) => {
+                        let _ = body_sender.send(BodyChunk::Length(body.len())).await;
+                        let _ = body_sender.send(BodyChunk::Data(body)).await;
+                        vec![]
+                    }
+                    None => body,
+                };
+
                 Ok(SuccessResponse {
                     url: response_url.to_string(),
                     body,
                     status: 0,
                     redirected: false,
+                    redirect_url: None,
+                    headers: vec![],
                 })
             }),
+            "data" => {
+                let response = fetch_data_url(&processed_url);
+                Box::pin(async move {
+                    let mut response = response?;
+                    if let Some(body_sender) = body_sender {
+                        let body = std::mem::take(&mut response.body);
+                        let _ = body_sender.send(BodyChunk::Length(body.len())).await;
+                        let _ = body_sender.send(BodyChunk::Data(body)).await;
+                    }
+                    Ok(response)
+                })
+            }
             _ => Box::pin(async move {
                 let client = client.ok_or_else(|| ErrorResponse {
                     url: processed_url.to_string(),
@@ -244,7 +226,33 @@ impl NavigatorBackend for ExternalNavigatorBackend {
                 let mut isahc_request = match request.method() {
                     NavigationMethod::Get => IsahcRequest::get(processed_url.to_string()),
                     NavigationMethod::Post => IsahcRequest::post(processed_url.to_string()),
+                    NavigationMethod::Head => IsahcRequest::head(processed_url.to_string()),
                 };
+                if request.redirect_policy() != RedirectPolicy::Follow {
+                    isahc_request = isahc_request.redirect_policy(IsahcRedirectPolicy::None);
+                }
+                if let Some(timeout) = request.connection_timeout() {
+                    isahc_request = isahc_request.connect_timeout(timeout);
+                }
+                if let Some(timeout) = request.idle_timeout() {
+                    // Any transfer slower than a byte per second counts as idle.
+                    isahc_request = isahc_request.low_speed_timeout(1, timeout);
+                }
+                if let Some((host, port)) = host_override {
+                    // The URL (and with it the `Host` header) stays the same, only the
+                    // address we connect to changes.
+                    let address = async_net::resolve((host.as_str(), port))
+                        .await
+                        .ok()
+                        .and_then(|addresses| addresses.into_iter().next())
+                        .ok_or_else(|| ErrorResponse {
+                            url: processed_url.to_string(),
+                            error: Error::FetchError(format!(
+                                "Unable to resolve host override {host}:{port}"
+                            )),
+                        })?;
+                    isahc_request = isahc_request.dial(Dialer::ip_socket(address));
+                }
                 let (body_data, mime) = request.body().clone().unwrap_or_default();
                 if let Some(headers) = isahc_request.headers_mut() {
                     for (name, val) in request.headers().iter() {
@@ -281,12 +289,39 @@ impl NavigatorBackend for ExternalNavigatorBackend {
                 let url = if let Some(uri) = response.effective_uri() {
                     uri.to_string()
                 } else {
-                    processed_url.into()
+                    processed_url.to_string()
                 };
 
                 let status = response.status().as_u16();
-                let redirected = response.effective_uri().is_some();
-                if !response.status().is_success() {
+                let redirected = url != processed_url.as_str();
+                // Headers that aren't valid strings can't be passed on to the movie.
+                let headers = response
+                    .headers()
+                    .iter()
+                    .filter_map(|(name, value)| {
+                        Some((name.to_string(), value.to_str().ok()?.to_string()))
+                    })
+                    .collect();
+
+                let mut redirect_url = None;
+                if response.status().is_redirection() {
+                    if request.redirect_policy() == RedirectPolicy::Error {
+                        let error = Error::HttpNotOk(
+                            format!("Redirects are not allowed, got {}", response.status()),
+                            status,
+                            redirected,
+                        );
+                        return Err(ErrorResponse { url, error });
+                    }
+                    redirect_url = response
+                        .headers()
+                        .get("Location")
+                        .and_then(|location| location.to_str().ok())
+                        .and_then(|location| processed_url.join(location).ok())
+                        .map(String::from);
+                }
+
+                if !response.status().is_success() && redirect_url.is_none() {
                     let error = Error::HttpNotOk(
                         format!("HTTP status is not ok, got {}", response.status()),
                         status,
@@ -296,31 +331,179 @@ impl NavigatorBackend for ExternalNavigatorBackend {
                 }
 
                 let mut body = vec![];
-                response
-                    .copy_to(&mut body)
-                    .await
-                    .map_err(|e| ErrorResponse {
-                        url: url.clone(),
-                        error: Error::FetchError(e.to_string()),
-                    })?;
+                if let Some(body_sender) = body_sender {
+                    if let Some(length) = response.body().len() {
+                        let _ = body_sender.send(BodyChunk::Length(length as usize)).await;
+                    }
+                    let mut chunk = vec![0; 64 * 1024];
+                    loop {
+                        let read = response.body_mut().read(&mut chunk).await.map_err(|e| {
+                            ErrorResponse {
+                                url: url.clone(),
+                                error: Error::FetchError(e.to_string()),
+                            }
+                        })?;
+                        // Stop at the end of the body, or if nobody wants the data anymore.
+                        let data = BodyChunk::Data(chunk[..read].to_vec());
+                        if read == 0 || body_sender.send(data).await.is_err() {
+                            break;
+                        }
+                    }
+                } else {
+                    response
+                        .copy_to(&mut body)
+                        .await
+                        .map_err(|e| ErrorResponse {
+                            url: url.clone(),
+                            error: Error::FetchError(e.to_string()),
+                        })?;
+                }
 
                 Ok(SuccessResponse {
                     url,
                     body,
                     status,
                     redirected,
+                    redirect_url,
+                    headers,
                 })
             }),
         }
     }
+}
+
+impl NavigatorBackend for ExternalNavigatorBackend {
+    fn navigate_to_url(
+        &self,
+        url: &str,
+        _target: &str,
+        vars_method: Option<(NavigationMethod, IndexMap<String, String>)>,
+    ) {
+        //TODO: Should we return a result for failed opens? Does Flash care?
+
+        //NOTE: Flash desktop players / projectors ignore the window parameter,
+        //      unless it's a `_layer`, and we shouldn't handle that anyway.
+        //      `_blank`, `_self`, `_parent` and `_top` all open the default browser.
+        let mut parsed_url = match self.resolve_url(url) {
+            Ok(parsed_url) => parsed_url,
+            Err(e) => {
+                tracing::error!(
+                    "Could not parse URL because of {}, the corrupt URL was: {}",
+                    e,
+                    url
+                );
+                return;
+            }
+        };
+
+        let (method, vars) = match vars_method {
+            Some((method, vars)) => (method, vars),
+            None => (NavigationMethod::Get, IndexMap::new()),
+        };
+
+        // GET variables are appended to any query the URL already has.
+        // POST variables are submitted through a form instead, see below.
+        if matches!(method, NavigationMethod::Get) && !vars.is_empty() {
+            let mut modifier = parsed_url.query_pairs_mut();
+            for (k, v) in vars.iter() {
+                modifier.append_pair(k, v);
+            }
+        }
+        let modified_url = parsed_url;
+
+        if modified_url.scheme() == "javascript" {
+            tracing::warn!(
+                "SWF tried to run a script on desktop, but javascript calls are not allowed"
+            );
+            return;
+        }
+
+        if self.open_url_mode == OpenURLMode::Confirm {
+            let mut message = format!("The SWF file wants to open the website {}", modified_url);
+            if matches!(method, NavigationMethod::Post) {
+                message.push_str("\n\nThe following data will be sent to it:");
+                for (k, v) in vars.iter() {
+                    message.push_str(&format!("\n{k} = {v}"));
+                }
+            }
+            // TODO: Add a checkbox with a GUI toolkit
+            let confirm = MessageDialog::new()
+                .set_title("Open website?")
+                .set_level(MessageLevel::Info)
+                .set_description(message)
+                .set_buttons(MessageButtons::OkCancel)
+                .show()
+                == MessageDialogResult::Ok;
+            if !confirm {
+                tracing::info!("SWF tried to open a website, but the user declined the request");
+                return;
+            }
+        } else if self.open_url_mode == OpenURLMode::Deny {
+            tracing::warn!("SWF tried to open a website, but opening a website is not allowed");
+            return;
+        }
+
+        // If the user confirmed or if in Allow mode, open the website
+
+        // A browser can only be told to POST by a page, so we write one that
+        // submits a form with the variables as soon as it loads.
+        let form_path = if matches!(method, NavigationMethod::Post) {
+            match write_post_form(&modified_url, &vars) {
+                Ok(form_path) => Some(form_path),
+                Err(e) => {
+                    tracing::error!("Could not create form to POST to {}: {}", modified_url, e);
+                    return;
+                }
+            }
+        } else {
+            None
+        };
+        let open_url = match &form_path {
+            Some(form_path) => form_path.to_string_lossy().into_owned(),
+            None => modified_url.to_string(),
+        };
+
+        // TODO: This opens local files in the browser while flash opens them
+        // in the default program for the respective filetype.
+        // This especially includes mailto links. Ruffle opens the browser which opens
+        // the preferred program while flash opens the preferred program directly.
+        match webbrowser::open(&open_url) {
+            Ok(_output) => {}
+            Err(e) => tracing::error!("Could not open URL {}: {}", modified_url.as_str(), e),
+        };
+
+        if let Some(form_path) = form_path {
+            remove_post_form_later(form_path);
+        }
+    }
+
+    fn fetch(&self, request: Request) -> OwnedFuture<SuccessResponse, ErrorResponse> {
+        self.fetch_with_body_sender(request, None)
+    }
+
+    fn fetch_stream(
+        &self,
+        request: Request,
+        body_sender: async_channel::Sender<BodyChunk>,
+    ) -> OwnedFuture<SuccessResponse, ErrorResponse> {
+        self.fetch_with_body_sender(request, Some(body_sender))
+    }
 
     fn resolve_url(&self, url: &str) -> Result<Url, ParseError> {
+        if let Some(url) = self.air_directories().and_then(|d| d.resolve(url)) {
+            return Ok(url);
+        }
+
         match self.base_url.join(url) {
             Ok(url) => Ok(self.pre_process_url(url)),
             Err(error) => Err(error),
         }
     }
 
+    fn air_directories(&self) -> Option<&AirDirectories> {
+        self.air_directories.as_ref()
+    }
+
     fn spawn_future(&mut self, future: OwnedFuture<(), Error>) {
         self.channel.send(future).expect("working channel send");
 
@@ -342,6 +525,7 @@ impl NavigatorBackend for ExternalNavigatorBackend {
         &mut self,
         host: String,
         port: u16,
+        require_policy_file: bool,
         timeout: Duration,
         handle: SocketHandle,
         receiver: Receiver<Vec<u8>>,
@@ -350,41 +534,38 @@ impl NavigatorBackend for ExternalNavigatorBackend {
         let addr = format!("{}:{}", host, port);
         let is_allowed = self.socket_allowed.contains(&addr);
         let socket_mode = self.socket_mode;
+        let connect_address = self.host_overrides.resolve(&host, port);
+        // Policy files have to allow the domain the movie was loaded from.
+        let domain = self.base_url.host_str().map(str::to_string);
 
         let future = Box::pin(async move {
-            match (is_allowed, socket_mode) {
-                (false, SocketMode::Allow) | (true, _) => {} // the process is allowed to continue. just dont do anything.
-                (false, SocketMode::Deny) => {
-                    // Just fail the connection.
-                    sender
-                        .send(SocketAction::Connect(handle, ConnectionState::Failed))
-                        .expect("working channel send");
-
-                    tracing::warn!(
-                        "SWF tried to open a socket, but opening a socket is not allowed"
-                    );
-
-                    return Ok(());
-                }
-                (false, SocketMode::Ask) => {
-                    let attempt_sandbox_connect = AsyncMessageDialog::new().set_level(MessageLevel::Warning).set_description(format!("The current movie is attempting to connect to {:?} (port {}).\n\nTo allow it to do so, click Yes to grant network access to that host.\n\nOtherwise, click No to deny access.", host, port)).set_buttons(MessageButtons::YesNo)
-                    .show()
-                    .await == MessageDialogResult::Yes;
-
-                    if !attempt_sandbox_connect {
-                        // fail the connection.
-                        sender
-                            .send(SocketAction::Connect(handle, ConnectionState::Failed))
-                            .expect("working channel send");
-
-                        return Ok(());
-                    }
-                }
+            if !socket_access_granted(is_allowed, socket_mode, &host, port).await {
+                sender
+                    .send(SocketAction::Connect(handle, ConnectionState::Failed))
+                    .expect("working channel send");
+                return Ok(());
             }
 
-            let host2 = host.clone();
+            if require_policy_file
+                && !socket_policy_allows(
+                    &connect_address.0,
+                    connect_address.1,
+                    domain.as_deref(),
+                    timeout,
+                )
+                .await
+            {
+                warn!(
+                    "Connection to {}:{} refused, as no socket policy file allows it",
+                    host, port
+                );
+                sender
+                    .send(SocketAction::Connect(handle, ConnectionState::Failed))
+                    .expect("working channel send");
+                return Ok(());
+            }
 
-            let stream = match TcpStream::connect((host, port))
+            let stream = match TcpStream::connect(connect_address)
                 .or(async {
                     Timer::after(timeout).await;
                     Result::<TcpStream, io::Error>::Err(io::Error::new(ErrorKind::TimedOut, ""))
@@ -392,7 +573,7 @@ impl NavigatorBackend for ExternalNavigatorBackend {
                 .await
             {
                 Err(e) if e.kind() == ErrorKind::TimedOut => {
-                    warn!("Connection to {}:{} timed out", host2, port);
+                    warn!("Connection to {}:{} timed out", host, port);
                     sender
                         .send(SocketAction::Connect(handle, ConnectionState::TimedOut))
                         .expect("working channel send");
@@ -406,7 +587,7 @@ impl NavigatorBackend for ExternalNavigatorBackend {
                     stream
                 }
                 Err(err) => {
-                    warn!("Failed to connect to {}:{}, error: {}", host2, port, err);
+                    warn!("Failed to connect to {}:{}, error: {}", host, port, err);
                     sender
                         .send(SocketAction::Connect(handle, ConnectionState::Failed))
                         .expect("working channel send");
@@ -493,4 +674,275 @@ impl NavigatorBackend for ExternalNavigatorBackend {
 
         self.spawn_future(future);
     }
+
+    fn fetch_socket_policy(
+        &self,
+        host: String,
+        port: u16,
+        timeout: Duration,
+    ) -> OwnedFuture<String, Error> {
+        let is_allowed = self.socket_allowed.contains(&format!("{}:{}", host, port));
+        let socket_mode = self.socket_mode;
+        let (connect_host, connect_port) = self.host_overrides.resolve(&host, port);
+        Box::pin(async move {
+            if !socket_access_granted(is_allowed, socket_mode, &host, port).await {
+                return Err(Error::FetchError(
+                    "Opening a socket is not allowed".to_string(),
+                ));
+            }
+
+            read_socket_policy(&connect_host, connect_port)
+                .or(async {
+                    Timer::after(timeout).await;
+                    Err(io::Error::new(ErrorKind::TimedOut, "timed out"))
+                })
+                .await
+                .map_err(|e| Error::FetchError(e.to_string()))
+        })
+    }
+
+    #[cfg(feature = "lan_p2p")]
+    fn join_net_group(
+        &mut self,
+        address: SocketAddr,
+        handle: NetGroupHandle,
+        receiver: Receiver<Vec<u8>>,
+        sender: Sender<NetGroupAction>,
+    ) {
+        if matches!(self.socket_mode, SocketMode::Deny) {
+            tracing::warn!("SWF tried to join a NetGroup, but opening a socket is not allowed");
+            sender
+                .send(NetGroupAction::Connect(handle, false))
+                .expect("working channel send");
+            return;
+        }
+
+        let future = Box::pin(async move {
+            let socket = match bind_multicast(address) {
+                Ok(socket) => {
+                    sender
+                        .send(NetGroupAction::Connect(handle, true))
+                        .expect("working channel send");
+                    socket
+                }
+                Err(err) => {
+                    warn!("Failed to join multicast group {}, error: {}", address, err);
+                    sender
+                        .send(NetGroupAction::Connect(handle, false))
+                        .expect("working channel send");
+                    return Ok(());
+                }
+            };
+
+            //NOTE: We clone the socket here as we cant share it between async tasks.
+            let socket2 = socket.clone();
+
+            let read = std::pin::pin!(async move {
+                let mut buffer = vec![0; 65536];
+                loop {
+                    match socket.recv(&mut buffer).await {
+                        Ok(read) => sender
+                            .send(NetGroupAction::Packet(handle, buffer[..read].to_vec()))
+                            .expect("working channel send"),
+                        Err(err) => {
+                            warn!(
+                                "Failed to receive from multicast group {}, error: {}",
+                                address, err
+                            );
+                            break;
+                        }
+                    }
+                }
+            });
+
+            let write = std::pin::pin!(async move {
+                //NOTE: This ends once the NetGroup is closed and everything it sent is out.
+                while let Ok(packet) = receiver.recv().await {
+                    if let Err(err) = socket2.send_to(&packet, address).await {
+                        warn!(
+                            "Failed to send to multicast group {}, error: {}",
+                            address, err
+                        );
+                    }
+                }
+            });
+
+            //NOTE: If one future exits, this will take the other one down too.
+            select(read, write).await;
+
+            Ok(())
+        });
+
+        self.spawn_future(future);
+    }
+
+    #[cfg(not(feature = "lan_p2p"))]
+    fn join_net_group(
+        &mut self,
+        _address: SocketAddr,
+        handle: NetGroupHandle,
+        _receiver: Receiver<Vec<u8>>,
+        sender: Sender<NetGroupAction>,
+    ) {
+        tracing::warn!("SWF tried to join a NetGroup, but this build has no LAN peer discovery (enable the `lan_p2p` feature)");
+        sender
+            .send(NetGroupAction::Connect(handle, false))
+            .expect("working channel send");
+    }
+}
+
+/// Whether the movie may open a socket to `host:port`, asking the user first in [`SocketMode::Ask`].
+async fn socket_access_granted(
+    is_allowed: bool,
+    socket_mode: SocketMode,
+    host: &str,
+    port: u16,
+) -> bool {
+    match (is_allowed, socket_mode) {
+        (false, SocketMode::Allow) | (true, _) => true,
+        (false, SocketMode::Deny) => {
+            tracing::warn!("SWF tried to open a socket, but opening a socket is not allowed");
+            false
+        }
+        (false, SocketMode::Ask) => {
+            AsyncMessageDialog::new().set_level(MessageLevel::Warning).set_description(format!("The current movie is attempting to connect to {:?} (port {}).\n\nTo allow it to do so, click Yes to grant network access to that host.\n\nOtherwise, click No to deny access.", host, port)).set_buttons(MessageButtons::YesNo)
+                .show()
+                .await == MessageDialogResult::Yes
+        }
+    }
+}
+
+/// Asks `host` for a socket policy file like Flash does: first on the master policy port 843,
+/// and then on `port` itself. Returns whether one of them allows content from `domain` to
+/// connect to `port`.
+async fn socket_policy_allows(
+    host: &str,
+    port: u16,
+    domain: Option<&str>,
+    timeout: Duration,
+) -> bool {
+    for policy_port in [843, port] {
+        let policy = read_socket_policy(host, policy_port)
+            .or(async {
+                Timer::after(timeout).await;
+                Err(io::Error::new(ErrorKind::TimedOut, "timed out"))
+            })
+            .await;
+        match policy {
+            Ok(policy) if policy_allows_port(&policy, domain, port) => {
+                tracing::info!("Socket policy file from {host}:{policy_port} allows port {port}");
+                return true;
+            }
+            Ok(_) => {
+                tracing::info!(
+                    "Socket policy file from {host}:{policy_port} doesn't allow port {port}"
+                )
+            }
+            Err(e) => tracing::info!("No socket policy file from {host}:{policy_port}: {e}"),
+        }
+    }
+    false
+}
+
+async fn read_socket_policy(host: &str, port: u16) -> io::Result<String> {
+    let mut stream = TcpStream::connect((host, port)).await?;
+    stream.write_all(b"<policy-file-request/>\0").await?;
+
+    // The policy file ends with a null byte, or when the server closes the connection.
+    let mut policy = vec![];
+    let mut buffer = [0; 4096];
+    loop {
+        let read = stream.read(&mut buffer).await?;
+        if read == 0 {
+            break;
+        }
+        policy.extend_from_slice(&buffer[..read]);
+        if let Some(end) = policy.iter().position(|&b| b == 0) {
+            policy.truncate(end);
+            break;
+        }
+        if policy.len() > MAX_POLICY_FILE_SIZE {
+            return Err(io::Error::new(
+                ErrorKind::InvalidData,
+                "policy file is too large",
+            ));
+        }
+    }
+    Ok(String::from_utf8_lossy(&policy).into_owned())
+}
+
+/// Binds a UDP socket that receives everything multicast to `address`.
+///
+/// Address reuse is enabled so that several players on one machine can join the same group.
+#[cfg(feature = "lan_p2p")]
+fn bind_multicast(address: SocketAddr) -> io::Result<async_net::UdpSocket> {
+    use socket2::{Domain, Protocol, Socket, Type};
+    use std::net::{Ipv4Addr, Ipv6Addr};
+
+    let socket = Socket::new(
+        Domain::for_address(address),
+        Type::DGRAM,
+        Some(Protocol::UDP),
+    )?;
+    socket.set_reuse_address(true)?;
+    #[cfg(unix)]
+    socket.set_reuse_port(true)?;
+
+    match address {
+        SocketAddr::V4(address) => {
+            socket.bind(&SocketAddr::from((Ipv4Addr::UNSPECIFIED, address.port())).into())?;
+            socket.join_multicast_v4(address.ip(), &Ipv4Addr::UNSPECIFIED)?;
+        }
+        SocketAddr::V6(address) => {
+            socket.bind(&SocketAddr::from((Ipv6Addr::UNSPECIFIED, address.port())).into())?;
+            socket.join_multicast_v6(address.ip(), 0)?;
+        }
+    }
+
+    async_net::UdpSocket::try_from(std::net::UdpSocket::from(socket))
+}
+
+/// Writes an HTML page that immediately POSTs `vars` to `url`, returning its path.
+fn write_post_form(url: &Url, vars: &IndexMap<String, String>) -> io::Result<PathBuf> {
+    fn escape(value: &str) -> String {
+        value
+            .replace('&', "&amp;")
+            .replace('"', "&quot;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+    }
+
+    let mut html = format!(
+        "<!DOCTYPE html>\n<html><body onload=\"document.forms[0].submit()\">\n<form method=\"post\" action=\"{}\">\n",
+        escape(url.as_str())
+    );
+    for (k, v) in vars.iter() {
+        html.push_str(&format!(
+            "<input type=\"hidden\" name=\"{}\" value=\"{}\">\n",
+            escape(k),
+            escape(v)
+        ));
+    }
+    html.push_str("</form></body></html>\n");
+
+    // The form may hold private data, so only we may read it, and nobody can have created it first.
+    let (path, mut file) = create_in_temp_dir("ruffle-post-", ".html", |path| {
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        options.open(path)
+    })?;
+    io::Write::write_all(&mut file, html.as_bytes())?;
+    Ok(path)
+}
+
+/// Deletes a form written by [`write_post_form`], once the browser has had time to load it.
+fn remove_post_form_later(path: PathBuf) {
+    std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_secs(60));
+        if let Err(e) = std::fs::remove_file(&path) {
+            tracing::warn!("Could not remove form {}: {}", path.display(), e);
+        }
+    });
 }
@@ -0,0 +1,211 @@
+use crate::air;
+use anyhow::{anyhow, Context, Error};
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use ruffle_core::backend::storage::SecureStorageBackend;
+use std::collections::HashMap;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+const KEYCHAIN_SERVICE: &str = "Ruffle EncryptedLocalStore";
+const NONCE_LENGTH: usize = 12;
+
+/// Stores the `EncryptedLocalStore` of an AIR application in an encrypted file.
+///
+/// The key is kept in the OS keychain. If there's no keychain to use, it's kept in a file only
+/// readable by the current user instead, which at least keeps items away from other users.
+pub struct DiskSecureStorageBackend {
+    path: PathBuf,
+    cipher: ChaCha20Poly1305,
+    items: HashMap<String, Vec<u8>>,
+}
+
+impl DiskSecureStorageBackend {
+    /// Opens the store of the AIR application with the given id.
+    pub fn new(id: &str) -> Result<Self, Error> {
+        let directory = air::application_data_directory(id)?;
+        fs::create_dir_all(&directory)
+            .with_context(|| format!("Couldn't create {}", directory.display()))?;
+
+        let key = load_key(id, &directory.join("EncryptedLocalStore.key"))?;
+        let cipher = ChaCha20Poly1305::new(&key);
+        let path = directory.join("EncryptedLocalStore.dat");
+
+        // A store that can't be decrypted is left alone rather than replaced, as its key may
+        // only be unavailable for now (such as with a locked keychain).
+        let items = match fs::read(&path) {
+            Ok(data) => decrypt_items(&cipher, &data)
+                .with_context(|| format!("Couldn't decrypt {}", path.display()))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => HashMap::new(),
+            Err(e) => return Err(e).with_context(|| format!("Couldn't read {}", path.display())),
+        };
+
+        Ok(Self {
+            path,
+            cipher,
+            items,
+        })
+    }
+
+    fn save(&self) -> bool {
+        if self.items.is_empty() {
+            return match fs::remove_file(&self.path) {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                    tracing::warn!("Unable to remove {}: {e}", self.path.display());
+                    false
+                }
+                _ => true,
+            };
+        }
+
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let Ok(encrypted) = self
+            .cipher
+            .encrypt(&nonce, encode_items(&self.items).as_slice())
+        else {
+            tracing::warn!("Unable to encrypt EncryptedLocalStore items");
+            return false;
+        };
+        let mut data = nonce.to_vec();
+        data.extend_from_slice(&encrypted);
+
+        // Write to a temporary file first, so a failed write can't lose every item.
+        let temp_path = self.path.with_extension("tmp");
+        if let Err(e) = fs::write(&temp_path, data).and_then(|_| fs::rename(&temp_path, &self.path))
+        {
+            tracing::warn!("Unable to save {}: {e}", self.path.display());
+            return false;
+        }
+        true
+    }
+}
+
+impl SecureStorageBackend for DiskSecureStorageBackend {
+    fn get_item(&self, name: &str) -> Option<Vec<u8>> {
+        self.items.get(name).cloned()
+    }
+
+    fn set_item(&mut self, name: &str, value: &[u8]) -> bool {
+        self.items.insert(name.to_string(), value.to_vec());
+        self.save()
+    }
+
+    fn remove_item(&mut self, name: &str) {
+        if self.items.remove(name).is_some() {
+            self.save();
+        }
+    }
+
+    fn reset(&mut self) {
+        self.items.clear();
+        self.save();
+    }
+}
+
+/// Loads the key of a store, creating one if the store is new.
+///
+/// A key file left behind by an earlier fallback takes priority, so items stay readable.
+fn load_key(id: &str, key_path: &Path) -> Result<Key, Error> {
+    if key_path.exists() {
+        let hex = fs::read_to_string(key_path)
+            .with_context(|| format!("Couldn't read {}", key_path.display()))?;
+        return decode_key(&hex).ok_or_else(|| anyhow!("Invalid key in {}", key_path.display()));
+    }
+
+    match keychain_key(id) {
+        Ok(key) => return Ok(key),
+        Err(e) => tracing::info!("OS keychain unavailable, using a key file instead: {e}"),
+    }
+
+    let key = ChaCha20Poly1305::generate_key(&mut OsRng);
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options
+        .open(key_path)
+        .and_then(|mut file| file.write_all(encode_key(&key).as_bytes()))
+        .with_context(|| format!("Couldn't write {}", key_path.display()))?;
+    Ok(key)
+}
+
+fn keychain_key(id: &str) -> Result<Key, keyring::Error> {
+    let entry = keyring::Entry::new(KEYCHAIN_SERVICE, id)?;
+    match entry.get_password() {
+        Ok(hex) => {
+            if let Some(key) = decode_key(&hex) {
+                return Ok(key);
+            }
+            tracing::warn!("Replacing invalid EncryptedLocalStore key of {id}");
+        }
+        Err(keyring::Error::NoEntry) => {}
+        Err(e) => return Err(e),
+    }
+
+    let key = ChaCha20Poly1305::generate_key(&mut OsRng);
+    entry.set_password(&encode_key(&key))?;
+    Ok(key)
+}
+
+fn encode_key(key: &Key) -> String {
+    key.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn decode_key(hex: &str) -> Option<Key> {
+    let hex = hex.trim();
+    if hex.len() != 64 {
+        return None;
+    }
+    let bytes = (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect::<Option<Vec<u8>>>()?;
+    Some(*Key::from_slice(&bytes))
+}
+
+/// Serializes items as length-prefixed names and values.
+fn encode_items(items: &HashMap<String, Vec<u8>>) -> Vec<u8> {
+    let mut data = vec![];
+    for (name, value) in items {
+        data.extend_from_slice(&(name.len() as u32).to_le_bytes());
+        data.extend_from_slice(name.as_bytes());
+        data.extend_from_slice(&(value.len() as u32).to_le_bytes());
+        data.extend_from_slice(value);
+    }
+    data
+}
+
+fn decrypt_items(
+    cipher: &ChaCha20Poly1305,
+    data: &[u8],
+) -> Result<HashMap<String, Vec<u8>>, Error> {
+    if data.len() < NONCE_LENGTH {
+        return Err(anyhow!("File is too short"));
+    }
+    let (nonce, encrypted) = data.split_at(NONCE_LENGTH);
+    let data = cipher
+        .decrypt(Nonce::from_slice(nonce), encrypted)
+        .map_err(|_| anyhow!("Wrong key, or the file is corrupt"))?;
+
+    let mut items = HashMap::new();
+    let mut data = data.as_slice();
+    while !data.is_empty() {
+        let (Some(name), Some(value)) = (read_chunk(&mut data), read_chunk(&mut data)) else {
+            return Err(anyhow!("Truncated item"));
+        };
+        let name = String::from_utf8(name.to_vec()).context("Item name isn't UTF-8")?;
+        items.insert(name, value.to_vec());
+    }
+    Ok(items)
+}
+
+fn read_chunk<'a>(data: &mut &'a [u8]) -> Option<&'a [u8]> {
+    let length = u32::from_le_bytes(data.get(..4)?.try_into().ok()?) as usize;
+    let chunk = data.get(4..4 + length)?;
+    *data = &data[4 + length..];
+    Some(chunk)
+}
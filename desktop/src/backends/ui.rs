@@ -1,14 +1,20 @@
+use crate::custom_event::RuffleEvent;
 use anyhow::{Context, Error};
 use arboard::Clipboard;
 use rfd::{MessageButtons, MessageDialog, MessageDialogResult, MessageLevel};
 use ruffle_core::backend::navigator::OpenURLMode;
 use ruffle_core::backend::ui::{
-    FontDefinition, FullscreenError, LanguageIdentifier, MouseCursor, UiBackend, US_ENGLISH,
+    DesktopRect, FontDefinition, FullscreenError, LanguageIdentifier, MouseCursor, NativeMenuItem,
+    ScreenInfo, UiBackend, US_ENGLISH,
 };
+use std::path::PathBuf;
 use std::rc::Rc;
 use sys_locale::get_locale;
 use tracing::error;
 use url::Url;
+use winit::dpi::{PhysicalPosition, PhysicalSize};
+use winit::event_loop::EventLoopProxy;
+use winit::monitor::MonitorHandle;
 use winit::window::{Fullscreen, Window};
 
 pub struct DesktopUiBackend {
@@ -18,10 +24,15 @@ pub struct DesktopUiBackend {
     language: LanguageIdentifier,
     preferred_cursor: MouseCursor,
     open_url_mode: OpenURLMode,
+    event_loop: EventLoopProxy<RuffleEvent>,
 }
 
 impl DesktopUiBackend {
-    pub fn new(window: Rc<Window>, open_url_mode: OpenURLMode) -> Result<Self, Error> {
+    pub fn new(
+        window: Rc<Window>,
+        open_url_mode: OpenURLMode,
+        event_loop: EventLoopProxy<RuffleEvent>,
+    ) -> Result<Self, Error> {
         let preferred_language = get_locale();
         let language = preferred_language
             .and_then(|l| l.parse().ok())
@@ -33,6 +44,7 @@ impl DesktopUiBackend {
             language,
             preferred_cursor: MouseCursor::Arrow,
             open_url_mode,
+            event_loop,
         })
     }
 
@@ -75,6 +87,78 @@ impl UiBackend for DesktopUiBackend {
         }
     }
 
+    fn screens(&self) -> Vec<ScreenInfo> {
+        let primary = self.window.primary_monitor();
+        let mut monitors: Vec<MonitorHandle> = self.window.available_monitors().collect();
+        // The primary monitor comes first, as the main screen.
+        monitors.sort_by_key(|monitor| Some(monitor) != primary.as_ref());
+
+        monitors
+            .into_iter()
+            .map(|monitor| {
+                let position = monitor.position();
+                let size = monitor.size();
+                let bounds = DesktopRect {
+                    x: position.x,
+                    y: position.y,
+                    width: size.width,
+                    height: size.height,
+                };
+                // winit can't tell which mode a monitor is in, so go by the deepest one
+                // available at its current size.
+                let color_depth = monitor
+                    .video_modes()
+                    .filter(|mode| mode.size() == size)
+                    .map(|mode| u32::from(mode.bit_depth()))
+                    .max()
+                    .unwrap_or(24);
+                // winit can't tell which parts of a monitor are covered by taskbars.
+                ScreenInfo {
+                    bounds,
+                    visible_bounds: bounds,
+                    color_depth,
+                }
+            })
+            .collect()
+    }
+
+    fn window_bounds(&self) -> Option<DesktopRect> {
+        let position = self.window.outer_position().ok()?;
+        let size = self.window.outer_size();
+        Some(DesktopRect {
+            x: position.x,
+            y: position.y,
+            width: size.width,
+            height: size.height,
+        })
+    }
+
+    fn set_window_bounds(&mut self, bounds: DesktopRect) {
+        self.window
+            .set_outer_position(PhysicalPosition::new(bounds.x, bounds.y));
+
+        // Only the inner size can be set, so account for the decorations.
+        let outer_size = self.window.outer_size();
+        let inner_size = self.window.inner_size();
+        let decoration_width = outer_size.width.saturating_sub(inner_size.width);
+        let decoration_height = outer_size.height.saturating_sub(inner_size.height);
+        self.window.set_inner_size(PhysicalSize::new(
+            bounds.width.saturating_sub(decoration_width).max(1),
+            bounds.height.saturating_sub(decoration_height).max(1),
+        ));
+    }
+
+    fn start_file_drag(&mut self, files: &[PathBuf]) -> bool {
+        // winit can't start a drag out of the window yet, so drags stay inside the player.
+        tracing::warn!("Can't drag {} file(s) out of the player", files.len());
+        false
+    }
+
+    fn set_native_menu(&mut self, menu: Option<Vec<NativeMenuItem>>) {
+        // The menu is drawn by the GUI, in the menu bar.
+        let _ = self.event_loop.send_event(RuffleEvent::SetNativeMenu(menu));
+    }
+
     fn set_fullscreen(&mut self, is_full: bool) -> Result<(), FullscreenError> {
         self.window.set_fullscreen(if is_full {
             Some(Fullscreen::Borderless(None))
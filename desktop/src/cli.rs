@@ -1,8 +1,11 @@
 use crate::RUFFLE_VERSION;
 use anyhow::Error;
 use clap::Parser;
-use ruffle_core::backend::navigator::{OpenURLMode, SocketMode};
+use ruffle_core::backend::navigator::{ArchiveStore, HostOverride, OpenURLMode, SocketMode};
 use ruffle_core::config::Letterbox;
+use ruffle_core::log_filter::{LogFilter, LogFormat};
+use ruffle_core::player_version::PlayerVersion;
+use ruffle_core::tag_utils::TagStrictness;
 use ruffle_core::{LoadBehavior, StageAlign, StageScaleMode};
 use ruffle_render::quality::StageQuality;
 use ruffle_render_wgpu::clap::{GraphicsBackend, PowerPreference};
@@ -89,6 +92,21 @@ pub struct Opt {
     #[clap(long = "socket-allow", number_of_values = 1, action = clap::ArgAction::Append)]
     pub socket_allow: Vec<String>,
 
+    /// Connect to another address instead of a host, e.g. `game.example.com=127.0.0.1:8080`.
+    /// Applies to sockets and HTTP requests. A port may be given on either side.
+    #[clap(long = "host-override", number_of_values = 1, action = clap::ArgAction::Append)]
+    pub host_override: Vec<HostOverride>,
+
+    /// Where to look up `archive://<hash>/path` URLs: either a directory holding one
+    /// sub-directory per archive hash, or the URL of an IPFS gateway.
+    #[clap(long)]
+    pub archive_store: Option<ArchiveStore>,
+
+    /// Maximum number of seconds to wait for a server to accept a connection, before a load
+    /// fails.
+    #[clap(long, default_value = "30")]
+    pub connection_timeout: f64,
+
     /// Define how to deal with TCP Socket connections.
     #[clap(long = "tcp-connections", default_value = "ask")]
     pub tcp_connections: SocketMode,
@@ -107,6 +125,13 @@ pub struct Opt {
     #[clap(long, default_value = "streaming")]
     pub load_behavior: LoadBehavior,
 
+    /// How to deal with malformed tags in movies.
+    ///
+    /// "lenient" recovers what it can from truncated or corrupt movies, while "strict"
+    /// refuses to load them.
+    #[clap(long, default_value = "lenient")]
+    pub tag_strictness: TagStrictness,
+
     /// Specify how Ruffle should handle areas outside the movie stage.
     #[clap(long, default_value = "on")]
     pub letterbox: Letterbox,
@@ -115,9 +140,9 @@ pub struct Opt {
     #[clap(long, value_parser)]
     pub spoof_url: Option<Url>,
 
-    /// The version of the player to emulate
+    /// The version of the player to emulate, such as 32 or 11.2
     #[clap(long)]
-    pub player_version: Option<u8>,
+    pub player_version: Option<PlayerVersion>,
 
     /// Set and lock the player's frame rate, overriding the movie's frame rate.
     #[clap(long)]
@@ -136,6 +161,27 @@ pub struct Opt {
     /// Hides the menu bar (the bar at the top of the window).
     #[clap(long)]
     pub no_gui: bool,
+
+    /// Reload the movie, with the same options, whenever its file changes.
+    #[clap(long, action)]
+    pub watch: bool,
+
+    /// Log how long each frame and render took, along with the metrics the movie sends
+    /// through `flash.profiler.Telemetry`. Use `--log telemetry=info` to see them.
+    #[clap(long)]
+    pub telemetry: bool,
+
+    /// How much to log, as a default level and levels for subsystems, such as
+    /// `warn,avm2=debug,net=trace`. The subsystems are loader, avm1, avm2, render, net
+    /// and telemetry.
+    ///
+    /// Overrides the `RUST_LOG` environment variable.
+    #[clap(long)]
+    pub log: Option<LogFilter>,
+
+    /// How to write log messages. "json" writes one object per line.
+    #[clap(long, default_value = "text")]
+    pub log_format: LogFormat,
 }
 
 fn parse_movie_file_or_url(path: &str) -> Result<Url, Error> {
@@ -1,6 +1,7 @@
 //! Custom event type for desktop ruffle
 
 use crate::player::PlayerOptions;
+use ruffle_core::backend::ui::NativeMenuItem;
 
 /// User-defined events.
 pub enum RuffleEvent {
@@ -19,9 +20,18 @@ pub enum RuffleEvent {
     /// The user requested to close the current SWF.
     CloseFile,
 
+    /// The file of the current SWF changed, and it should be reloaded.
+    ReloadFile,
+
     /// The user requested to exit Ruffle.
     ExitRequested,
 
     /// The user selected an item in the right-click context menu.
     ContextMenuItemClicked(usize),
+
+    /// The content set the menu of its window, or removed it if `None`.
+    SetNativeMenu(Option<Vec<NativeMenuItem>>),
+
+    /// The user selected the item with the given id in the content's window menu.
+    NativeMenuItemClicked(usize),
 }
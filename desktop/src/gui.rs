@@ -9,19 +9,22 @@ use url::Url;
 
 use crate::custom_event::RuffleEvent;
 use crate::gui::open_dialog::OpenDialog;
+use crate::logging;
 use crate::player::PlayerOptions;
 use chrono::DateTime;
 use egui::*;
 use fluent_templates::fluent_bundle::FluentValue;
 use fluent_templates::{static_loader, Loader};
 use rfd::FileDialog;
-use ruffle_core::backend::ui::US_ENGLISH;
+use ruffle_core::backend::ui::{NativeMenuItem, US_ENGLISH};
 use ruffle_core::debug_ui::Message as DebugMessage;
+use ruffle_core::log_filter::LogSubsystem;
 use ruffle_core::Player;
 use std::collections::HashMap;
 use std::fs;
 use std::sync::MutexGuard;
 use sys_locale::get_locale;
+use tracing::level_filters::LevelFilter;
 use unic_langid::LanguageIdentifier;
 use winit::event_loop::EventLoopProxy;
 
@@ -69,6 +72,8 @@ pub struct RuffleGui {
     volume_controls: VolumeControls,
     is_open_dialog_visible: bool,
     context_menu: Vec<ruffle_core::ContextMenuItem>,
+    /// The menus the content set with `NativeWindow.menu`, shown after our own.
+    native_menu: Vec<NativeMenuItem>,
     open_dialog: OpenDialog,
     locale: LanguageIdentifier,
     default_player_options: PlayerOptions,
@@ -100,6 +105,7 @@ impl RuffleGui {
             taking_screenshot: false,
 
             context_menu: vec![],
+            native_menu: vec![],
             open_dialog: OpenDialog::new(
                 default_player_options.clone(),
                 default_path,
@@ -174,6 +180,10 @@ impl RuffleGui {
         !self.context_menu.is_empty()
     }
 
+    pub fn set_native_menu(&mut self, menu: Option<Vec<NativeMenuItem>>) {
+        self.native_menu = menu.unwrap_or_default();
+    }
+
     /// Notifies the GUI that a new player was created.
     fn on_player_created(
         &mut self,
@@ -182,6 +192,7 @@ impl RuffleGui {
         mut player: MutexGuard<Player>,
     ) {
         self.currently_opened = Some((movie_url.clone(), opt.clone()));
+        self.native_menu.clear();
 
         // Update dialog state to reflect the newly-opened movie's options.
         self.is_open_dialog_visible = false;
@@ -315,6 +326,7 @@ impl RuffleGui {
                             self.taking_screenshot = true;
                         }
                     });
+                    ui.menu_button(text(&self.locale, "debug-menu-log-levels"), |ui| self.log_levels_menu(ui));
                 });
                 menu::menu_button(ui, text(&self.locale, "help-menu"), |ui| {
                     if ui.button(text(&self.locale, "help-menu-join-discord")).clicked() {
@@ -334,10 +346,86 @@ impl RuffleGui {
                         self.show_about_screen(ui);
                     }
                 });
+                for item in &self.native_menu {
+                    if let Some(submenu) = &item.submenu {
+                        ui.add_enabled_ui(item.enabled, |ui| {
+                            menu::menu_button(ui, &item.label, |ui| {
+                                self.native_menu_items(ui, submenu);
+                            });
+                        });
+                    } else if !item.is_separator {
+                        self.native_menu_item(ui, item);
+                    }
+                }
             });
         });
     }
 
+    /// Renders a submenu for each subsystem, picking how much it logs.
+    fn log_levels_menu(&self, ui: &mut egui::Ui) {
+        let mut filter = logging::filter();
+        let mut changed = false;
+        for subsystem in LogSubsystem::ALL {
+            let current = filter.subsystem_level(subsystem);
+            ui.menu_button(subsystem.name(), |ui| {
+                let default = text(&self.locale, "debug-menu-log-level-default");
+                if ui.radio(current.is_none(), default).clicked() {
+                    filter.set_subsystem_level(subsystem, None);
+                    changed = true;
+                }
+                for level in [
+                    LevelFilter::OFF,
+                    LevelFilter::ERROR,
+                    LevelFilter::WARN,
+                    LevelFilter::INFO,
+                    LevelFilter::DEBUG,
+                    LevelFilter::TRACE,
+                ] {
+                    let name = level.to_string().to_ascii_lowercase();
+                    if ui.radio(current == Some(level), name).clicked() {
+                        filter.set_subsystem_level(subsystem, Some(level));
+                        changed = true;
+                    }
+                }
+            });
+        }
+        if changed {
+            logging::set_filter(filter);
+            ui.close_menu();
+        }
+    }
+
+    /// Renders the items of a menu the content set with `NativeWindow.menu`.
+    fn native_menu_items(&self, ui: &mut egui::Ui, items: &[NativeMenuItem]) {
+        for item in items {
+            if item.is_separator {
+                ui.separator();
+            } else if let Some(submenu) = &item.submenu {
+                ui.add_enabled_ui(item.enabled, |ui| {
+                    ui.menu_button(&item.label, |ui| self.native_menu_items(ui, submenu));
+                });
+            } else {
+                self.native_menu_item(ui, item);
+            }
+        }
+    }
+
+    fn native_menu_item(&self, ui: &mut egui::Ui, item: &NativeMenuItem) {
+        let clicked = if item.checked {
+            ui.add_enabled(item.enabled, Checkbox::new(&mut true, &item.label))
+                .clicked()
+        } else {
+            ui.add_enabled(item.enabled, Button::new(&item.label).wrap(false))
+                .clicked()
+        };
+        if clicked {
+            let _ = self
+                .event_loop
+                .send_event(RuffleEvent::NativeMenuItemClicked(item.id));
+            ui.close_menu();
+        }
+    }
+
     /// Renders the About Ruffle window.
     fn about_window(&mut self, egui_ctx: &egui::Context) {
         egui::Window::new(text(&self.locale, "about-ruffle"))
@@ -516,6 +604,7 @@ impl RuffleGui {
     fn close_movie(&mut self, ui: &mut egui::Ui) {
         let _ = self.event_loop.send_event(RuffleEvent::CloseFile);
         self.currently_opened = None;
+        self.native_menu.clear();
         ui.close_menu();
     }
 
@@ -7,6 +7,7 @@ use crate::player::{PlayerController, PlayerOptions};
 use anyhow::anyhow;
 use egui::Context;
 use fontdb::{Database, Family, Query, Source};
+use ruffle_core::backend::ui::NativeMenuItem;
 use ruffle_core::Player;
 use ruffle_render_wgpu::backend::{request_adapter_and_device, WgpuRenderBackend};
 use ruffle_render_wgpu::descriptors::Descriptors;
@@ -385,6 +386,10 @@ impl GuiController {
         self.gui.show_context_menu(menu);
     }
 
+    pub fn set_native_menu(&mut self, menu: Option<Vec<NativeMenuItem>>) {
+        self.gui.set_native_menu(menu);
+    }
+
     pub fn is_context_menu_visible(&self) -> bool {
         self.gui.is_context_menu_visible()
     }
@@ -457,9 +457,15 @@ impl OpenDialog {
                 ui.end_row();
 
                 ui.label(text(&self.locale, "player-version"));
-                DragValue::new(&mut self.options.player_version)
-                    .clamp_range(1..=32)
-                    .ui(ui);
+                ui.horizontal(|ui| {
+                    DragValue::new(&mut self.options.player_version.major)
+                        .clamp_range(1..=32)
+                        .ui(ui);
+                    ui.label(".");
+                    DragValue::new(&mut self.options.player_version.minor)
+                        .clamp_range(0..=9)
+                        .ui(ui);
+                });
                 ui.end_row();
 
                 ui.label(text(&self.locale, "custom-framerate"));
@@ -0,0 +1,69 @@
+use ruffle_core::log_filter::{LogFilter, LogFormat};
+use std::sync::{Mutex, OnceLock};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{reload, EnvFilter, Layer, Registry};
+
+/// Lets the filter be changed after logging has started, such as from the debug menu.
+struct ReloadableFilter {
+    handle: reload::Handle<EnvFilter, Registry>,
+    filter: Mutex<LogFilter>,
+}
+
+static FILTER: OnceLock<ReloadableFilter> = OnceLock::new();
+
+/// Starts logging. Without a `filter`, the `RUST_LOG` environment variable is used.
+pub fn init(filter: Option<&LogFilter>, format: LogFormat) {
+    let env_filter = match filter {
+        Some(filter) => EnvFilter::new(filter.directives()),
+        None => EnvFilter::from_default_env(),
+    };
+    let (env_filter, handle) = reload::Layer::new(env_filter);
+    let output = match format {
+        LogFormat::Text => tracing_subscriber::fmt::layer().boxed(),
+        LogFormat::Json => tracing_subscriber::fmt::layer().json().boxed(),
+    };
+    let subscriber = tracing_subscriber::registry().with(env_filter).with(output);
+
+    #[cfg(feature = "tracy")]
+    let subscriber = subscriber.with(tracing_tracy::TracyLayer::new());
+
+    subscriber.init();
+
+    // `RUST_LOG` can hold any directive, but when it only uses subsystems, it can be shown
+    // in the debug menu like `--log` is.
+    let filter = filter
+        .cloned()
+        .or_else(|| std::env::var(EnvFilter::DEFAULT_ENV).ok()?.parse().ok())
+        .unwrap_or_default();
+    let _ = FILTER.set(ReloadableFilter {
+        handle,
+        filter: Mutex::new(filter),
+    });
+}
+
+/// The filter that was last set. Empty if `RUST_LOG` holds directives it can't show.
+pub fn filter() -> LogFilter {
+    FILTER
+        .get()
+        .and_then(|reloadable| reloadable.filter.lock().ok())
+        .map(|filter| filter.clone())
+        .unwrap_or_default()
+}
+
+/// Replaces the filter of the running logger.
+pub fn set_filter(filter: LogFilter) {
+    let Some(reloadable) = FILTER.get() else {
+        return;
+    };
+    if let Err(e) = reloadable
+        .handle
+        .reload(EnvFilter::new(filter.directives()))
+    {
+        tracing::error!("Couldn't change the log filter: {e}");
+        return;
+    }
+    if let Ok(mut current) = reloadable.filter.lock() {
+        *current = filter;
+    }
+}
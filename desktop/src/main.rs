@@ -6,12 +6,14 @@
 // See https://docs.microsoft.com/en-us/cpp/build/reference/subsystem?view=msvc-160 for details.
 #![windows_subsystem = "windows"]
 
+mod air;
 mod app;
 mod backends;
 mod cli;
 mod custom_event;
 mod executor;
 mod gui;
+mod logging;
 mod player;
 mod task;
 mod time_demo;
@@ -25,7 +27,6 @@ use rfd::MessageDialogResult;
 use ruffle_core::StaticCallstack;
 use std::cell::RefCell;
 use std::panic::PanicInfo;
-use tracing_subscriber::util::SubscriberInitExt;
 use url::Url;
 
 thread_local! {
@@ -65,18 +66,6 @@ fn init() {
         prev_hook(info);
         panic_hook(info);
     }));
-
-    let subscriber = tracing_subscriber::fmt::Subscriber::builder()
-        .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
-        .finish();
-
-    #[cfg(feature = "tracy")]
-    let subscriber = {
-        use tracing_subscriber::layer::SubscriberExt;
-        let tracy_subscriber = tracing_tracy::TracyLayer::new();
-        subscriber.with(tracy_subscriber)
-    };
-    subscriber.init();
 }
 
 fn panic_hook(info: &PanicInfo) {
@@ -156,6 +145,7 @@ fn shutdown() {
 fn main() -> Result<(), Error> {
     init();
     let opt = Opt::parse();
+    logging::init(opt.log.as_ref(), opt.log_format);
     let result = if opt.timedemo {
         time_demo::run_timedemo(opt)
     } else {
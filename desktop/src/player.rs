@@ -1,3 +1,6 @@
+use crate::air::AirLaunch;
+#[cfg(feature = "encrypted_local_store")]
+use crate::backends::DiskSecureStorageBackend;
 use crate::backends::{
     CpalAudioBackend, DesktopExternalInterfaceProvider, DesktopUiBackend, DiskStorageBackend,
     ExternalNavigatorBackend,
@@ -8,18 +11,26 @@ use crate::executor::WinitAsyncExecutor;
 use crate::gui::MovieView;
 use crate::{CALLSTACK, RENDER_INFO, SWF_INFO};
 use anyhow::anyhow;
-use ruffle_core::backend::navigator::{OpenURLMode, SocketMode};
+use ruffle_core::backend::navigator::{
+    ArchiveNavigatorBackend, ArchiveStore, HostOverrides, OpenURLMode, SocketMode,
+};
 use ruffle_core::config::Letterbox;
+use ruffle_core::player_version::PlayerVersion;
+use ruffle_core::swf::Twips;
+use ruffle_core::tag_utils::TagStrictness;
 use ruffle_core::{LoadBehavior, Player, PlayerBuilder, PlayerEvent, StageAlign, StageScaleMode};
 use ruffle_render::backend::RenderBackend;
 use ruffle_render::quality::StageQuality;
 use ruffle_render_wgpu::backend::WgpuRenderBackend;
 use ruffle_render_wgpu::descriptors::Descriptors;
 use std::collections::HashSet;
+use std::path::PathBuf;
 use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex, MutexGuard};
-use std::time::Duration;
+use std::time::{Duration, SystemTime};
 use url::Url;
+use winit::dpi::LogicalPosition;
 use winit::event_loop::EventLoopProxy;
 use winit::window::Window;
 
@@ -39,15 +50,21 @@ pub struct PlayerOptions {
     pub proxy: Option<Url>,
     pub socket_allowed: HashSet<String>,
     pub tcp_connections: SocketMode,
+    pub host_overrides: HostOverrides,
+    pub connection_timeout: f64,
+    pub archive_store: Option<ArchiveStore>,
     pub upgrade_to_https: bool,
     pub fullscreen: bool,
     pub load_behavior: LoadBehavior,
+    pub tag_strictness: TagStrictness,
     pub letterbox: Letterbox,
     pub spoof_url: Option<Url>,
-    pub player_version: u8,
+    pub player_version: PlayerVersion,
     pub frame_rate: Option<f64>,
     pub open_url_mode: OpenURLMode,
     pub dummy_external_interface: bool,
+    pub watch: bool,
+    pub telemetry: bool,
 }
 
 impl From<&Opt> for PlayerOptions {
@@ -66,14 +83,20 @@ impl From<&Opt> for PlayerOptions {
             upgrade_to_https: value.upgrade_to_https,
             fullscreen: value.fullscreen,
             load_behavior: value.load_behavior,
+            tag_strictness: value.tag_strictness,
             letterbox: value.letterbox,
             spoof_url: value.spoof_url.clone(),
-            player_version: value.player_version.unwrap_or(32),
+            player_version: value.player_version.unwrap_or(PlayerVersion::new(32, 0)),
             frame_rate: value.frame_rate,
             open_url_mode: value.open_url_mode,
             dummy_external_interface: value.dummy_external_interface,
+            watch: value.watch,
+            telemetry: value.telemetry,
             socket_allowed: HashSet::from_iter(value.socket_allow.iter().cloned()),
             tcp_connections: value.tcp_connections,
+            host_overrides: HostOverrides::new(value.host_override.clone()),
+            connection_timeout: value.connection_timeout,
+            archive_store: value.archive_store.clone(),
         }
     }
 }
@@ -105,6 +128,17 @@ impl ActivePlayer {
             }
         };
 
+        let air = match AirLaunch::open(movie_url) {
+            Ok(air) => air,
+            Err(e) => {
+                tracing::error!("Couldn't open AIR application: {e:#}");
+                None
+            }
+        };
+        let movie_url = air
+            .as_ref()
+            .map_or_else(|| movie_url.clone(), |air| air.content_url.clone());
+
         let (executor, channel) = WinitAsyncExecutor::new(event_loop.clone());
         let navigator = ExternalNavigatorBackend::new(
             opt.base.to_owned().unwrap_or_else(|| movie_url.clone()),
@@ -115,11 +149,18 @@ impl ActivePlayer {
             opt.open_url_mode,
             opt.socket_allowed.clone(),
             opt.tcp_connections,
+            opt.host_overrides.clone(),
+            // Infinite (or nonsensical) timeouts leave it up to the HTTP client.
+            Duration::try_from_secs_f64(opt.connection_timeout).ok(),
+            air.as_ref().map(|air| air.directories.clone()),
         );
 
         if cfg!(feature = "software_video") {
-            builder =
-                builder.with_video(ruffle_video_software::backend::SoftwareVideoBackend::new());
+            builder = builder
+                .with_video(ruffle_video_software::backend::SoftwareVideoBackend::new())
+                .with_media_decoder(
+                    ruffle_video_software::media::SoftwareMediaDecoderBackend::new(),
+                );
         }
 
         let renderer = WgpuRenderBackend::new(descriptors, movie_view)
@@ -138,12 +179,18 @@ impl ActivePlayer {
             Duration::from_secs_f64(opt.max_execution_duration)
         };
 
+        builder = match &opt.archive_store {
+            Some(store) => {
+                builder.with_navigator(ArchiveNavigatorBackend::new(navigator, store.clone()))
+            }
+            None => builder.with_navigator(navigator),
+        };
+
         builder = builder
-            .with_navigator(navigator)
             .with_renderer(renderer)
             .with_storage(DiskStorageBackend::new().expect("Couldn't create storage backend"))
             .with_ui(
-                DesktopUiBackend::new(window.clone(), opt.open_url_mode)
+                DesktopUiBackend::new(window.clone(), opt.open_url_mode, event_loop.clone())
                     .expect("Couldn't create ui backend"),
             )
             .with_autoplay(true)
@@ -154,23 +201,62 @@ impl ActivePlayer {
             .with_scale_mode(opt.scale, opt.force_scale)
             .with_fullscreen(opt.fullscreen)
             .with_load_behavior(opt.load_behavior)
+            .with_tag_strictness(opt.tag_strictness)
             .with_spoofed_url(opt.spoof_url.clone().map(|url| url.to_string()))
             .with_player_version(Some(opt.player_version))
-            .with_frame_rate(opt.frame_rate);
+            .with_frame_rate(opt.frame_rate)
+            .with_telemetry(opt.telemetry);
+
+        #[cfg(feature = "encrypted_local_store")]
+        if let Some(air) = &air {
+            match DiskSecureStorageBackend::new(&air.application.id) {
+                Ok(secure_storage) => {
+                    builder = builder.with_secure_storage(secure_storage);
+                }
+                Err(e) => {
+                    tracing::error!("Unable to open EncryptedLocalStore: {e:#}");
+                }
+            }
+        }
+
         let player = builder.build();
 
-        let name = movie_url
-            .path_segments()
-            .and_then(|segments| segments.last())
-            .unwrap_or_else(|| movie_url.as_str())
-            .to_string();
+        let name = match &air {
+            Some(air) => air.title().to_string(),
+            None => movie_url
+                .path_segments()
+                .and_then(|segments| segments.last())
+                .unwrap_or_else(|| movie_url.as_str())
+                .to_string(),
+        };
 
         window.set_title(&format!("Ruffle - {name}"));
 
         SWF_INFO.with(|i| *i.borrow_mut() = Some(name.clone()));
 
+        let initial_window = air.map(|air| air.application.initial_window);
+        if let Some(initial_window) = &initial_window {
+            if let (Some(x), Some(y)) = (initial_window.x, initial_window.y) {
+                window.set_outer_position(LogicalPosition::new(x, y));
+            }
+            if let Some(resizable) = initial_window.resizable {
+                window.set_resizable(resizable);
+            }
+        }
+
         let on_metadata = move |swf_header: &ruffle_core::swf::HeaderExt| {
-            let _ = event_loop.send_event(RuffleEvent::OnMetadata(swf_header.clone()));
+            let mut swf_header = swf_header.clone();
+            // The descriptor's window size takes precedence over the movie's own.
+            if let Some(initial_window) = initial_window {
+                let stage_size = &mut swf_header.swf_header_mut().stage_size;
+                if let Some(width) = initial_window.width {
+                    stage_size.x_max = stage_size.x_min + Twips::from_pixels(width.into());
+                }
+                if let Some(height) = initial_window.height {
+                    stage_size.y_max = stage_size.y_min + Twips::from_pixels(height.into());
+                }
+            }
+            let _ = event_loop.send_event(RuffleEvent::OnMetadata(swf_header));
         };
 
         {
@@ -189,6 +275,47 @@ impl ActivePlayer {
     }
 }
 
+/// Watches the file of a movie, asking for the movie to be reloaded when it changes.
+///
+/// The file is polled from a thread of its own, which stops when this is dropped.
+struct MovieWatcher {
+    stopped: Arc<AtomicBool>,
+}
+
+impl MovieWatcher {
+    const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+    fn new(path: PathBuf, event_loop: EventLoopProxy<RuffleEvent>) -> Self {
+        let stopped = Arc::new(AtomicBool::new(false));
+        let thread_stopped = stopped.clone();
+        let modified_time = move || -> Option<SystemTime> { path.metadata().ok()?.modified().ok() };
+        std::thread::spawn(move || {
+            let mut last_modified = modified_time();
+            let mut changed = false;
+            while !thread_stopped.load(Ordering::Relaxed) {
+                std::thread::sleep(Self::POLL_INTERVAL);
+                let modified = modified_time();
+                if modified.is_some() && modified != last_modified {
+                    // Wait for the file to stop changing, so that it isn't read half-written.
+                    last_modified = modified;
+                    changed = true;
+                } else if changed && !thread_stopped.load(Ordering::Relaxed) {
+                    tracing::info!("Movie file changed, reloading");
+                    let _ = event_loop.send_event(RuffleEvent::ReloadFile);
+                    break;
+                }
+            }
+        });
+        Self { stopped }
+    }
+}
+
+impl Drop for MovieWatcher {
+    fn drop(&mut self) {
+        self.stopped.store(true, Ordering::Relaxed);
+    }
+}
+
 /// Owner of a Ruffle Player (via ActivePlayer),
 /// responsible for either creating, destroying or communicating with that player.
 pub struct PlayerController {
@@ -196,6 +323,12 @@ pub struct PlayerController {
     event_loop: EventLoopProxy<RuffleEvent>,
     window: Rc<Window>,
     descriptors: Arc<Descriptors>,
+
+    /// The movie and options the current player was created with, to reload it.
+    movie: Option<(Url, PlayerOptions)>,
+
+    /// Set while the movie is reloaded whenever its file changes.
+    watcher: Option<MovieWatcher>,
 }
 
 impl PlayerController {
@@ -209,10 +342,14 @@ impl PlayerController {
             event_loop,
             window,
             descriptors,
+            movie: None,
+            watcher: None,
         }
     }
 
     pub fn create(&mut self, opt: &PlayerOptions, movie_url: &Url, movie_view: MovieView) {
+        // Stop watching the previous movie before the new one is loaded.
+        self.watcher = None;
         self.player = Some(ActivePlayer::new(
             opt,
             self.event_loop.clone(),
@@ -221,10 +358,38 @@ impl PlayerController {
             self.descriptors.clone(),
             movie_view,
         ));
+        self.movie = Some((movie_url.clone(), opt.clone()));
+        if opt.watch {
+            self.watch_and_reload();
+        }
     }
 
     pub fn destroy(&mut self) {
         self.player = None;
+        self.movie = None;
+        self.watcher = None;
+    }
+
+    /// Reloads the current movie whenever its file changes, until the player is destroyed
+    /// or another movie is opened.
+    ///
+    /// The movie is reloaded with the options it was first opened with. Only movies opened from
+    /// local files can be watched.
+    pub fn watch_and_reload(&mut self) {
+        let Some((movie_url, _)) = &self.movie else {
+            return;
+        };
+        match movie_url.to_file_path() {
+            Ok(path) => {
+                self.watcher = Some(MovieWatcher::new(path, self.event_loop.clone()));
+            }
+            Err(()) => tracing::warn!("Can't watch {movie_url} for changes, as it isn't a file"),
+        }
+    }
+
+    /// The movie and options the current player was created with.
+    pub fn movie(&self) -> Option<&(Url, PlayerOptions)> {
+        self.movie.as_ref()
     }
 
     pub fn get(&self) -> Option<MutexGuard<Player>> {
@@ -67,7 +67,9 @@ pub fn run_timedemo(mut opt: Opt) -> Result<(), Error> {
     let mut builder = PlayerBuilder::new();
 
     if cfg!(feature = "software_video") {
-        builder = builder.with_video(ruffle_video_software::backend::SoftwareVideoBackend::new());
+        builder = builder
+            .with_video(ruffle_video_software::backend::SoftwareVideoBackend::new())
+            .with_media_decoder(ruffle_video_software::media::SoftwareMediaDecoderBackend::new());
     }
 
     let player = builder
@@ -2,7 +2,11 @@ use crate::custom_event::RuffleEvent;
 use anyhow::{anyhow, Error};
 use rfd::FileDialog;
 use ruffle_core::events::{KeyCode, TextControlCode};
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::io;
 use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
 use url::Url;
 use winit::dpi::PhysicalSize;
 use winit::event::{ModifiersState, VirtualKeyCode};
@@ -297,6 +301,33 @@ pub fn get_screen_size(event_loop: &EventLoop<RuffleEvent>) -> PhysicalSize<u32>
     (width, height).into()
 }
 
+/// Creates something in the temporary directory under a name that can't be guessed beforehand.
+///
+/// `create` must fail with [`io::ErrorKind::AlreadyExists`] rather than reuse an existing path,
+/// in which case another name is tried.
+pub fn create_in_temp_dir<T>(
+    prefix: &str,
+    suffix: &str,
+    create: impl Fn(&Path) -> io::Result<T>,
+) -> io::Result<(PathBuf, T)> {
+    let mut attempts = 0;
+    loop {
+        let mut random = RandomState::new().build_hasher();
+        random.write_u128(
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_nanos(),
+        );
+        let path = std::env::temp_dir().join(format!("{prefix}{:016x}{suffix}", random.finish()));
+        match create(&path) {
+            Ok(created) => return Ok((path, created)),
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists && attempts < 8 => attempts += 1,
+            Err(e) => return Err(e),
+        }
+    }
+}
+
 pub fn parse_url(path: &Path) -> Result<Url, Error> {
     if path.exists() {
         let absolute_path = path.canonicalize().unwrap_or_else(|_| path.to_owned());
@@ -312,7 +343,7 @@ pub fn parse_url(path: &Path) -> Result<Url, Error> {
 
 fn actually_pick_file(dir: Option<PathBuf>) -> Option<PathBuf> {
     let mut dialog = FileDialog::new()
-        .add_filter("Flash Files", &["swf", "spl"])
+        .add_filter("Flash Files", &["swf", "spl", "air"])
         .add_filter("All Files", &["*"])
         .set_title("Load a Flash File");
 
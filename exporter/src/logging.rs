@@ -0,0 +1,85 @@
+use ruffle_core::log_filter::{LogFilter, LogFormat};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::SystemTime;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{reload, EnvFilter, Layer, Registry};
+
+/// A file to read the log filter from, which is read again whenever it changes.
+struct FilterFile {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+}
+
+/// The logger of a running export, whose filter can be changed by editing a file.
+pub struct Logging {
+    handle: reload::Handle<EnvFilter, Registry>,
+    filter_file: Option<Mutex<FilterFile>>,
+}
+
+impl Logging {
+    /// Starts logging. The filter comes from `filter_file` if there is one, then `filter`, and
+    /// otherwise the `RUST_LOG` environment variable.
+    pub fn init(
+        filter: Option<&LogFilter>,
+        filter_file: Option<PathBuf>,
+        format: LogFormat,
+    ) -> Self {
+        let env_filter = match filter {
+            Some(filter) => EnvFilter::new(filter.directives()),
+            None => EnvFilter::from_default_env(),
+        };
+        let (env_filter, handle) = reload::Layer::new(env_filter);
+        let output = match format {
+            LogFormat::Text => tracing_subscriber::fmt::layer().boxed(),
+            LogFormat::Json => tracing_subscriber::fmt::layer().json().boxed(),
+        };
+        tracing_subscriber::registry()
+            .with(env_filter)
+            .with(output)
+            .init();
+
+        let logging = Self {
+            handle,
+            filter_file: filter_file.map(|path| {
+                Mutex::new(FilterFile {
+                    path,
+                    modified: None,
+                })
+            }),
+        };
+        logging.reload_if_changed();
+        logging
+    }
+
+    /// Switches to the filter in the filter file, if it changed since it was last read.
+    pub fn reload_if_changed(&self) {
+        let Some(Ok(mut file)) = self.filter_file.as_ref().map(|file| file.lock()) else {
+            return;
+        };
+        let modified = std::fs::metadata(&file.path)
+            .and_then(|m| m.modified())
+            .ok();
+        if modified.is_none() || modified == file.modified {
+            return;
+        }
+        file.modified = modified;
+
+        let filter = match std::fs::read_to_string(&file.path) {
+            Ok(filter) => filter.trim().parse::<LogFilter>(),
+            Err(e) => Err(e.to_string()),
+        };
+        match filter {
+            Ok(filter) => {
+                if let Err(e) = self.handle.reload(EnvFilter::new(filter.directives())) {
+                    tracing::error!("Couldn't change the log filter: {e}");
+                }
+            }
+            Err(e) => tracing::error!(
+                "Couldn't read the log filter from {}: {e}",
+                file.path.display()
+            ),
+        }
+    }
+}
@@ -2,22 +2,26 @@ use anyhow::{anyhow, Result};
 use clap::Parser;
 use image::RgbaImage;
 use indicatif::{ProgressBar, ProgressStyle};
+use logging::Logging;
+use player_host::PlayerHost;
 use rayon::prelude::*;
-use ruffle_core::limits::ExecutionLimit;
-use ruffle_core::tag_utils::SwfMovie;
-use ruffle_core::PlayerBuilder;
-use ruffle_render_wgpu::backend::{request_adapter_and_device, WgpuRenderBackend};
+use ruffle_core::embed::{ExecutionLimit, PlayerConfig, SwfMovie};
+use ruffle_core::log_filter::{LogFilter, LogFormat};
+use ruffle_render_wgpu::backend::request_adapter_and_device;
 use ruffle_render_wgpu::clap::{GraphicsBackend, PowerPreference};
 use ruffle_render_wgpu::descriptors::Descriptors;
-use ruffle_render_wgpu::target::TextureTarget;
 use ruffle_render_wgpu::wgpu;
 use std::fs::create_dir_all;
 use std::io::{self, Write};
 use std::panic::catch_unwind;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::Duration;
 use walkdir::{DirEntry, WalkDir};
 
+mod logging;
+mod player_host;
+
 #[derive(Parser, Debug, Copy, Clone)]
 struct SizeOpt {
     /// The amount to scale the page size with
@@ -81,11 +85,38 @@ struct Opt {
     /// Skip unsupported movie types (currently AVM 2)
     #[clap(long, action)]
     skip_unsupported: bool,
+
+    /// A TOML file of player settings (such as quality, load behavior or spoofed URL) to
+    /// export with, so that an export can be reproduced
+    #[clap(long)]
+    config: Option<PathBuf>,
+
+    /// Report every movie loaded by an exported movie that took longer than this many
+    /// milliseconds to fetch and preload
+    #[clap(long)]
+    slow_load_ms: Option<u64>,
+
+    /// How much to log, as a default level and levels for subsystems, such as
+    /// `warn,avm2=debug,net=trace`. The subsystems are loader, avm1, avm2, render and net.
+    ///
+    /// Overrides the `RUST_LOG` environment variable.
+    #[clap(long)]
+    log: Option<LogFilter>,
+
+    /// A file holding a filter like `--log`, which overrides it. The file is read again before
+    /// each movie is exported whenever it changed, so logging can be turned up or down during
+    /// a long export.
+    #[clap(long)]
+    log_filter_file: Option<PathBuf>,
+
+    /// How to write log messages. "json" writes one object per line.
+    #[clap(long, default_value = "text")]
+    log_format: LogFormat,
 }
 
 /// Captures a screenshot. The resulting image uses straight alpha
 fn take_screenshot(
-    descriptors: Arc<Descriptors>,
+    host: &PlayerHost,
     swf_path: &Path,
     frames: u32,
     skipframes: u32,
@@ -111,15 +142,7 @@ fn take_screenshot(
         .unwrap_or_else(|| movie.height().to_pixels());
     let height = (height * size.scale).round() as u32;
 
-    let target = TextureTarget::new(&descriptors.device, (width, height))
-        .map_err(|e| anyhow!(e.to_string()))?;
-    let player = PlayerBuilder::new()
-        .with_renderer(
-            WgpuRenderBackend::new(descriptors, target).map_err(|e| anyhow!(e.to_string()))?,
-        )
-        .with_movie(movie)
-        .with_viewport_dimensions(width, height, size.scale)
-        .build();
+    let player = host.offscreen_player(movie, width, height, size.scale)?;
 
     let mut result = Vec::new();
     let totalframes = frames + skipframes;
@@ -137,15 +160,7 @@ fn take_screenshot(
 
         player.lock().unwrap().run_frame();
         if i >= skipframes {
-            match catch_unwind(|| {
-                player.lock().unwrap().render();
-                let mut player = player.lock().unwrap();
-                let renderer = player
-                    .renderer_mut()
-                    .downcast_mut::<WgpuRenderBackend<TextureTarget>>()
-                    .unwrap();
-                renderer.capture_frame()
-            }) {
+            match catch_unwind(|| PlayerHost::capture_frame(&player)) {
                 Ok(Some(image)) => result.push(image),
                 Ok(None) => return Err(anyhow!("Unable to capture frame {} of {:?}", i, swf_path)),
                 Err(e) => {
@@ -163,6 +178,27 @@ fn take_screenshot(
             progress.inc(1);
         }
     }
+
+    for load in host.slow_loads(&player) {
+        let time = load
+            .time_to_complete
+            .map_or_else(|| "unfinished".to_string(), |time| format!("{time:.2?}"));
+        let message = format!(
+            "Slow load in {:?}: {} ({time}, {} bytes, fetched in {:.2?}, decompressed in {:.2?}, preloaded in {:.2?}, {} characters)",
+            swf_path,
+            load.url.as_deref().unwrap_or("<unknown>"),
+            load.compressed_bytes,
+            load.fetch_duration.unwrap_or_default(),
+            load.decompress_duration.unwrap_or_default(),
+            load.preload_duration,
+            load.decoded_characters,
+        );
+        match progress {
+            Some(progress) => progress.println(message),
+            None => println!("{message}"),
+        }
+    }
+
     Ok(result)
 }
 
@@ -196,7 +232,7 @@ fn find_files(root: &Path, with_progress: bool) -> Vec<DirEntry> {
     results
 }
 
-fn capture_single_swf(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()> {
+fn capture_single_swf(host: &PlayerHost, opt: &Opt) -> Result<()> {
     let output = opt.output_path.clone().unwrap_or_else(|| {
         let mut result = PathBuf::new();
         result.set_file_name(opt.swf.file_stem().unwrap());
@@ -225,7 +261,7 @@ fn capture_single_swf(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()> {
     };
 
     let frames = take_screenshot(
-        descriptors,
+        host,
         &opt.swf,
         opt.frames,
         opt.skipframes,
@@ -293,7 +329,7 @@ fn capture_single_swf(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()> {
 }
 
 #[allow(clippy::branches_sharing_code)]
-fn capture_multiple_swfs(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()> {
+fn capture_multiple_swfs(host: &PlayerHost, opt: &Opt, logging: &Logging) -> Result<()> {
     let output = opt.output_path.clone().unwrap();
     let files = find_files(&opt.swf, !opt.silent);
 
@@ -312,6 +348,7 @@ fn capture_multiple_swfs(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()>
     };
 
     files.par_iter().try_for_each(|file| -> Result<()> {
+        logging.reload_if_changed();
         if let Some(progress) = &progress {
             progress.set_message(
                 file.path()
@@ -322,7 +359,7 @@ fn capture_multiple_swfs(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()>
             );
         }
         if let Ok(frames) = take_screenshot(
-            descriptors.clone(),
+            host,
             file.path(),
             opt.frames,
             opt.skipframes,
@@ -399,8 +436,24 @@ fn trace_path(_opt: &Opt) -> Option<&Path> {
     None
 }
 
+fn load_config(opt: &Opt) -> Result<PlayerConfig> {
+    let Some(path) = &opt.config else {
+        return Ok(PlayerConfig::default());
+    };
+    let toml = std::fs::read_to_string(path)
+        .map_err(|e| anyhow!("Couldn't read config {}: {e}", path.display()))?;
+    PlayerConfig::from_toml(&toml)
+        .map_err(|e| anyhow!("Couldn't parse config {}: {e}", path.display()))
+}
+
 fn main() -> Result<()> {
     let opt: Opt = Opt::parse();
+    let logging = Logging::init(
+        opt.log.as_ref(),
+        opt.log_filter_file.clone(),
+        opt.log_format,
+    );
+    let config = load_config(&opt)?;
     let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
         backends: opt.graphics.into(),
         dx12_shader_compiler: wgpu::Dx12Compiler::default(),
@@ -415,13 +468,15 @@ fn main() -> Result<()> {
     .map_err(|e| anyhow!(e.to_string()))?;
 
     let descriptors = Arc::new(Descriptors::new(instance, adapter, device, queue));
+    let host = PlayerHost::new(descriptors, config)
+        .with_slow_load_threshold(opt.slow_load_ms.map(Duration::from_millis));
 
     if opt.swf.is_file() {
-        capture_single_swf(descriptors, &opt)?;
+        capture_single_swf(&host, &opt)?;
     } else if !opt.swf.is_dir() {
         return Err(anyhow!("Given path is not a file or directory."));
     } else if opt.output_path.is_some() {
-        capture_multiple_swfs(descriptors, &opt)?;
+        capture_multiple_swfs(&host, &opt, &logging)?;
     } else {
         return Err(anyhow!(
             "Output directory is required when exporting multiple files."
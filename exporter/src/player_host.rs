@@ -0,0 +1,84 @@
+//! Runs many players on one graphics device.
+
+use anyhow::{anyhow, Result};
+use image::RgbaImage;
+use ruffle_core::embed::{LoadMetrics, Player, PlayerBuilder, PlayerConfig, SwfMovie};
+use ruffle_render_wgpu::backend::WgpuRenderBackend;
+use ruffle_render_wgpu::descriptors::Descriptors;
+use ruffle_render_wgpu::target::TextureTarget;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Creates players that all render with the same wgpu device and queue.
+///
+/// The pipelines, shaders and samplers of the device are created once and shared by every
+/// player, which only owns its render target and the buffers it draws with. These are freed as
+/// soon as the player is dropped.
+pub struct PlayerHost {
+    descriptors: Arc<Descriptors>,
+    config: PlayerConfig,
+    slow_load_threshold: Option<Duration>,
+}
+
+impl PlayerHost {
+    /// Creates a host whose players are all configured with `config`.
+    pub fn new(descriptors: Arc<Descriptors>, config: PlayerConfig) -> Self {
+        Self {
+            descriptors,
+            config,
+            slow_load_threshold: None,
+        }
+    }
+
+    /// Makes [`PlayerHost::slow_loads`] report loads taking longer than `threshold`.
+    pub fn with_slow_load_threshold(mut self, threshold: Option<Duration>) -> Self {
+        self.slow_load_threshold = threshold;
+        self
+    }
+
+    /// Creates a player of `movie` rendering into a texture of its own, to be read back with
+    /// [`PlayerHost::capture_frame`].
+    pub fn offscreen_player(
+        &self,
+        movie: SwfMovie,
+        width: u32,
+        height: u32,
+        scale_factor: f64,
+    ) -> Result<Arc<Mutex<Player>>> {
+        let target = TextureTarget::new(&self.descriptors.device, (width, height))
+            .map_err(|e| anyhow!(e.to_string()))?;
+        let renderer = WgpuRenderBackend::new(self.descriptors.clone(), target)
+            .map_err(|e| anyhow!(e.to_string()))?;
+        Ok(PlayerBuilder::new()
+            .with_config(self.config.clone())
+            .with_renderer(renderer)
+            .with_movie(movie)
+            .with_viewport_dimensions(width, height, scale_factor)
+            .build())
+    }
+
+    /// The movies loaded by a player that took longer than the slow load threshold to be fetched
+    /// and preloaded, or that haven't finished by then.
+    pub fn slow_loads(&self, player: &Mutex<Player>) -> Vec<LoadMetrics> {
+        let Some(threshold) = self.slow_load_threshold else {
+            return vec![];
+        };
+        let mut player = player.lock().unwrap();
+        player
+            .load_metrics()
+            .into_iter()
+            .filter(|load| load.time_to_complete.map_or(true, |time| time > threshold))
+            .collect()
+    }
+
+    /// Renders the current frame of a player made by [`PlayerHost::offscreen_player`], and reads
+    /// it back. The image uses straight alpha.
+    pub fn capture_frame(player: &Mutex<Player>) -> Option<RgbaImage> {
+        let mut player = player.lock().unwrap();
+        player.render();
+        player
+            .renderer_mut()
+            .downcast_mut::<WgpuRenderBackend<TextureTarget>>()?
+            .capture_frame()
+    }
+}
@@ -9,7 +9,7 @@ mod reader;
 mod error;
 
 pub use error::Error;
-pub use header::Header;
+pub use header::{Header, TypeFlags};
 pub use reader::FlvReader;
 pub use script::{ScriptData, Value, Variable};
 pub use sound::{AudioData, AudioDataType, SoundFormat, SoundRate, SoundSize, SoundType};
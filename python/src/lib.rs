@@ -0,0 +1,506 @@
+//! Python bindings for listing and exporting what's in movies, for scripts that would otherwise
+//! shell out to the exporter.
+//!
+//! ```python
+//! import ruffle
+//!
+//! for item in ruffle.dump_library("penguin.swf"):
+//!     if item["kind"] == "sprite":
+//!         files = ruffle.export_symbol("penguin.swf", item["id"], "spritesheet")
+//! ```
+//!
+//! Every player renders offscreen with one graphics device, created on first use. The module
+//! functions load a fresh player for each call and release the GIL while it runs, while
+//! `Player` keeps one around for scripts that do several things with the same movie.
+
+use image::{ImageOutputFormat, RgbaImage};
+use pyo3::create_exception;
+use pyo3::exceptions::PyException;
+use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyDict};
+use ruffle_core::embed::export::{
+    library_items, BatchEvent, BatchExporter, ExportJob, ExportedFile, ItemKind, LibraryItem,
+};
+use ruffle_core::embed::{
+    ExecutionLimit, Player as CorePlayer, PlayerBuilder, PlayerConfig, SwfMovie,
+};
+use ruffle_core::swf::CharacterId;
+use ruffle_render_wgpu::backend::{request_adapter_and_device, WgpuRenderBackend};
+use ruffle_render_wgpu::descriptors::Descriptors;
+use ruffle_render_wgpu::target::TextureTarget;
+use ruffle_render_wgpu::wgpu;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+
+create_exception!(
+    ruffle,
+    RuffleError,
+    PyException,
+    "A movie couldn't be loaded, rendered or exported from."
+);
+
+fn to_py_err(error: String) -> PyErr {
+    RuffleError::new_err(error)
+}
+
+/// The graphics device every player renders with.
+fn descriptors() -> Result<Arc<Descriptors>, String> {
+    static DESCRIPTORS: OnceLock<Result<Arc<Descriptors>, String>> = OnceLock::new();
+    DESCRIPTORS
+        .get_or_init(|| {
+            let backends = wgpu::Backends::PRIMARY | wgpu::Backends::DX11;
+            let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
+                backends,
+                dx12_shader_compiler: wgpu::Dx12Compiler::default(),
+            });
+            let (adapter, device, queue) = futures::executor::block_on(request_adapter_and_device(
+                backends,
+                &instance,
+                None,
+                wgpu::PowerPreference::HighPerformance,
+                None,
+            ))
+            .map_err(|e| format!("Couldn't create a graphics device: {e}"))?;
+            Ok(Arc::new(Descriptors::new(instance, adapter, device, queue)))
+        })
+        .clone()
+}
+
+fn parse_config(config: Option<&str>) -> PyResult<PlayerConfig> {
+    match config {
+        Some(toml) => PlayerConfig::from_toml(toml)
+            .map_err(|e| to_py_err(format!("Couldn't parse config: {e}"))),
+        None => Ok(PlayerConfig::default()),
+    }
+}
+
+/// How to size a player's frames, as in the exporter's options.
+#[derive(Debug, Clone, Copy)]
+struct Size {
+    scale: f64,
+    width: Option<u32>,
+    height: Option<u32>,
+}
+
+/// Loads `swf` into a player whose frames are the size of the stage, unless overridden.
+fn load_player(
+    swf: &Path,
+    size: Size,
+    config: PlayerConfig,
+) -> Result<Arc<Mutex<CorePlayer>>, String> {
+    let movie = SwfMovie::from_path(swf, None)
+        .map_err(|e| format!("Couldn't load {}: {e}", swf.display()))?;
+    let width = size
+        .width
+        .map(f64::from)
+        .unwrap_or_else(|| movie.width().to_pixels());
+    let height = size
+        .height
+        .map(f64::from)
+        .unwrap_or_else(|| movie.height().to_pixels());
+    let width = ((width * size.scale).round() as u32).max(1);
+    let height = ((height * size.scale).round() as u32).max(1);
+
+    let descriptors = descriptors()?;
+    let target =
+        TextureTarget::new(&descriptors.device, (width, height)).map_err(|e| e.to_string())?;
+    let renderer = WgpuRenderBackend::new(descriptors, target).map_err(|e| e.to_string())?;
+    let player = PlayerBuilder::new()
+        .with_config(config)
+        .with_renderer(renderer)
+        .with_movie(movie)
+        .with_viewport_dimensions(width, height, size.scale)
+        .build();
+    player.lock().unwrap().preload(&mut ExecutionLimit::none());
+    Ok(player)
+}
+
+/// Renders the current frame of `player` to a PNG.
+fn capture_frame(player: &mut CorePlayer) -> Result<Vec<u8>, String> {
+    player.render();
+    let image = player
+        .renderer_mut()
+        .downcast_mut::<WgpuRenderBackend<TextureTarget>>()
+        .and_then(|renderer| renderer.capture_frame())
+        .ok_or("Couldn't capture the frame")?;
+    encode_png(&image)
+}
+
+/// Runs `skip_frames + frames` frames of `player`, capturing the last `frames` of them.
+fn capture_frames_of(
+    player: &Mutex<CorePlayer>,
+    frames: u32,
+    skip_frames: u32,
+) -> Result<Vec<Vec<u8>>, String> {
+    let mut player = player.lock().unwrap();
+    let mut captured = vec![];
+    for i in 0..skip_frames + frames {
+        player.preload(&mut ExecutionLimit::none());
+        player.run_frame();
+        if i >= skip_frames {
+            captured.push(capture_frame(&mut player)?);
+        }
+    }
+    Ok(captured)
+}
+
+fn export_from(
+    player: &Mutex<CorePlayer>,
+    id: CharacterId,
+    format: &str,
+) -> Result<Vec<ExportedFile>, String> {
+    let mut player = player.lock().unwrap();
+    let movie = player.root_movie().clone();
+    player
+        .export_item(&movie, id, format)
+        .map_err(|e| e.to_string())
+}
+
+fn encode_png(image: &RgbaImage) -> Result<Vec<u8>, String> {
+    let mut data = vec![];
+    image
+        .write_to(&mut Cursor::new(&mut data), ImageOutputFormat::Png)
+        .map_err(|e| format!("Couldn't encode the frame: {e}"))?;
+    Ok(data)
+}
+
+/// The kind's name, as it's serialized everywhere else.
+fn kind_name(kind: ItemKind) -> String {
+    serde_json::to_value(kind)
+        .ok()
+        .and_then(|value| value.as_str().map(str::to_owned))
+        .unwrap_or_default()
+}
+
+fn items_to_py(py: Python<'_>, items: Vec<LibraryItem>) -> PyResult<Vec<PyObject>> {
+    items
+        .into_iter()
+        .map(|item| {
+            let dict = PyDict::new(py);
+            dict.set_item("id", item.id)?;
+            dict.set_item("kind", kind_name(item.kind))?;
+            dict.set_item("name", item.name)?;
+            Ok(dict.into())
+        })
+        .collect()
+}
+
+/// The files as a dict of their names to their contents.
+fn files_to_py(py: Python<'_>, files: Vec<ExportedFile>) -> PyResult<PyObject> {
+    let dict = PyDict::new(py);
+    for file in files {
+        dict.set_item(file.name, PyBytes::new(py, &file.data))?;
+    }
+    Ok(dict.into())
+}
+
+fn pngs_to_py(py: Python<'_>, pngs: Vec<Vec<u8>>) -> Vec<PyObject> {
+    pngs.iter()
+        .map(|png| PyBytes::new(py, png).into())
+        .collect()
+}
+
+/// A movie loaded into a player of its own, rendering offscreen.
+#[pyclass(unsendable)]
+struct Player {
+    core: Arc<Mutex<CorePlayer>>,
+}
+
+#[pymethods]
+impl Player {
+    #[new]
+    #[pyo3(signature = (swf, scale = 1.0, width = None, height = None, config = None))]
+    fn new(
+        swf: PathBuf,
+        scale: f64,
+        width: Option<u32>,
+        height: Option<u32>,
+        config: Option<&str>,
+    ) -> PyResult<Self> {
+        let size = Size {
+            scale,
+            width,
+            height,
+        };
+        let core = load_player(&swf, size, parse_config(config)?).map_err(to_py_err)?;
+        Ok(Self { core })
+    }
+
+    /// Runs `frames` frames of the movie.
+    #[pyo3(signature = (frames = 1))]
+    fn run_frames(&self, frames: u32) {
+        let mut core = self.core.lock().unwrap();
+        for _ in 0..frames {
+            core.preload(&mut ExecutionLimit::none());
+            core.run_frame();
+        }
+    }
+
+    /// Renders the current frame to a PNG.
+    fn capture_frame(&self, py: Python<'_>) -> PyResult<PyObject> {
+        let png = capture_frame(&mut self.core.lock().unwrap()).map_err(to_py_err)?;
+        Ok(PyBytes::new(py, &png).into())
+    }
+
+    /// Runs `skip_frames + frames` frames, and renders the last `frames` of them to PNGs.
+    #[pyo3(signature = (frames = 1, skip_frames = 0))]
+    fn capture_frames(
+        &self,
+        py: Python<'_>,
+        frames: u32,
+        skip_frames: u32,
+    ) -> PyResult<Vec<PyObject>> {
+        let pngs = capture_frames_of(&self.core, frames, skip_frames).map_err(to_py_err)?;
+        Ok(pngs_to_py(py, pngs))
+    }
+
+    /// The characters the movie defines, as dicts of their `id`, `kind` and `name`.
+    fn library(&self, py: Python<'_>) -> PyResult<Vec<PyObject>> {
+        let items = library_items(self.core.lock().unwrap().root_movie());
+        items_to_py(py, items)
+    }
+
+    /// Exports character `id` to `format`, as a dict of file names to their contents.
+    #[pyo3(signature = (id, format = "spritesheet"))]
+    fn export_symbol(&self, py: Python<'_>, id: CharacterId, format: &str) -> PyResult<PyObject> {
+        let files = export_from(&self.core, id, format).map_err(to_py_err)?;
+        files_to_py(py, files)
+    }
+
+    /// The formats `export_symbol` can export to.
+    fn export_formats(&self) -> Vec<String> {
+        self.core.lock().unwrap().export_formats()
+    }
+}
+
+/// Lists the characters `swf` defines, as dicts of their `id`, `kind` and `name`.
+#[pyfunction]
+fn dump_library(py: Python<'_>, swf: PathBuf) -> PyResult<Vec<PyObject>> {
+    let movie = SwfMovie::from_path(&swf, None)
+        .map_err(|e| to_py_err(format!("Couldn't load {}: {e}", swf.display())))?;
+    items_to_py(py, library_items(&movie))
+}
+
+/// Exports character `id` of `swf` to `format`, as a dict of file names to their contents.
+#[pyfunction]
+#[pyo3(signature = (swf, id, format = "spritesheet", config = None))]
+fn export_symbol(
+    py: Python<'_>,
+    swf: PathBuf,
+    id: CharacterId,
+    format: &str,
+    config: Option<&str>,
+) -> PyResult<PyObject> {
+    let config = parse_config(config)?;
+    let size = Size {
+        scale: 1.0,
+        width: None,
+        height: None,
+    };
+    let files = py
+        .allow_threads(|| export_from(&load_player(&swf, size, config)?, id, format))
+        .map_err(to_py_err)?;
+    files_to_py(py, files)
+}
+
+/// Plays `swf` for `skip_frames + frames` frames, and renders the last `frames` of them to PNGs.
+#[pyfunction]
+#[pyo3(signature = (
+    swf,
+    frames = 1,
+    skip_frames = 0,
+    scale = 1.0,
+    width = None,
+    height = None,
+    config = None,
+))]
+#[allow(clippy::too_many_arguments)]
+fn capture_frames(
+    py: Python<'_>,
+    swf: PathBuf,
+    frames: u32,
+    skip_frames: u32,
+    scale: f64,
+    width: Option<u32>,
+    height: Option<u32>,
+    config: Option<&str>,
+) -> PyResult<Vec<PyObject>> {
+    let config = parse_config(config)?;
+    let size = Size {
+        scale,
+        width,
+        height,
+    };
+    let pngs = py
+        .allow_threads(|| capture_frames_of(&load_player(&swf, size, config)?, frames, skip_frames))
+        .map_err(to_py_err)?;
+    Ok(pngs_to_py(py, pngs))
+}
+
+/// Exports many characters at once over several threads, like the exporter's batch mode.
+///
+/// Each job is a `(swf, id, format)` tuple. Returns a dict per job, in the order they finished,
+/// with the job's `swf`, `id` and `format` and either its `files` or the `error` it last failed
+/// with. `on_progress` is called with the number of jobs exported, failed and in total each
+/// time one finishes.
+#[pyfunction]
+#[pyo3(signature = (jobs, threads = None, max_attempts = 3, config = None, on_progress = None))]
+fn export_batch(
+    py: Python<'_>,
+    jobs: Vec<(PathBuf, CharacterId, String)>,
+    threads: Option<usize>,
+    max_attempts: u32,
+    config: Option<&str>,
+    on_progress: Option<PyObject>,
+) -> PyResult<Vec<PyObject>> {
+    let config = parse_config(config)?;
+    // Fail early if there's no device, rather than in every job.
+    let descriptors = descriptors().map_err(to_py_err)?;
+    let mut exporter = BatchExporter::new(move || {
+        let target =
+            TextureTarget::new(&descriptors.device, (1, 1)).expect("Texture target for exporting");
+        let renderer =
+            WgpuRenderBackend::new(descriptors.clone(), target).expect("Renderer for exporting");
+        PlayerBuilder::new()
+            .with_config(config.clone())
+            .with_renderer(renderer)
+    })
+    .with_max_attempts(max_attempts);
+    if let Some(threads) = threads {
+        exporter = exporter.with_threads(threads);
+    }
+
+    let jobs = jobs
+        .into_iter()
+        .map(|(swf, character_id, format)| ExportJob {
+            swf,
+            character_id,
+            format,
+            variants: vec![],
+        });
+    let mut results = vec![];
+    let mut callback_error = None;
+    py.allow_threads(|| {
+        exporter.run(jobs, |event, progress| {
+            match event {
+                BatchEvent::Exported { job, files } => {
+                    results.push((job.clone(), Ok(files.clone())))
+                }
+                BatchEvent::Failed { job, error } => {
+                    results.push((job.clone(), Err(error.clone())))
+                }
+                BatchEvent::Retrying { .. } => return,
+            }
+            let Some(on_progress) = on_progress.as_ref().filter(|_| callback_error.is_none())
+            else {
+                return;
+            };
+            Python::with_gil(|py| {
+                let args = (progress.exported, progress.failed, progress.total);
+                if let Err(e) = on_progress.call1(py, args) {
+                    // Raised once the batch is done, as the other threads can't be stopped.
+                    callback_error = Some(e);
+                }
+            });
+        })
+    });
+    if let Some(e) = callback_error {
+        return Err(e);
+    }
+
+    results
+        .into_iter()
+        .map(|(job, result)| {
+            let dict = PyDict::new(py);
+            dict.set_item("swf", job.swf)?;
+            dict.set_item("id", job.character_id)?;
+            dict.set_item("format", job.format)?;
+            match result {
+                Ok(files) => dict.set_item("files", files_to_py(py, files)?)?,
+                Err(error) => dict.set_item("error", error)?,
+            }
+            Ok(dict.into())
+        })
+        .collect()
+}
+
+#[pymodule]
+fn ruffle(py: Python<'_>, m: &PyModule) -> PyResult<()> {
+    m.add("RuffleError", py.get_type::<RuffleError>())?;
+    m.add_class::<Player>()?;
+    m.add_function(wrap_pyfunction!(dump_library, m)?)?;
+    m.add_function(wrap_pyfunction!(export_symbol, m)?)?;
+    m.add_function(wrap_pyfunction!(capture_frames, m)?)?;
+    m.add_function(wrap_pyfunction!(export_batch, m)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_swf(name: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../tests/tests/swfs")
+            .join(name)
+            .join("test.swf")
+    }
+
+    #[test]
+    fn kind_names_match_their_serialization() {
+        assert_eq!(kind_name(ItemKind::Sprite), "sprite");
+        assert_eq!(kind_name(ItemKind::MorphShape), "morphShape");
+        assert_eq!(kind_name(ItemKind::BinaryData), "binaryData");
+    }
+
+    #[test]
+    fn configs_are_parsed() {
+        assert!(parse_config(None).is_ok());
+        assert!(parse_config(Some("")).is_ok());
+        assert!(parse_config(Some("this isn't toml")).is_err());
+    }
+
+    #[test]
+    fn frames_are_encoded_as_png() {
+        let png = encode_png(&RgbaImage::new(2, 2)).unwrap();
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+    }
+
+    #[test]
+    fn missing_movies_are_refused() {
+        let size = Size {
+            scale: 1.0,
+            width: None,
+            height: None,
+        };
+        let error = load_player(Path::new("missing.swf"), size, PlayerConfig::default())
+            .err()
+            .unwrap();
+        assert!(error.starts_with("Couldn't load missing.swf"));
+    }
+
+    #[test]
+    fn module_lists_libraries() {
+        Python::with_gil(|py| {
+            let module = PyModule::new(py, "ruffle").unwrap();
+            ruffle(py, module).unwrap();
+            let dump_library = module.getattr("dump_library").unwrap();
+
+            let items = dump_library
+                .call1((test_swf("avm1/named_shapes"),))
+                .unwrap()
+                .extract::<Vec<&PyDict>>()
+                .unwrap();
+            assert_eq!(items.len(), 1);
+            let item = items[0];
+            let get = |key| item.get_item(key).unwrap().unwrap();
+            assert_eq!(get("id").extract::<CharacterId>().unwrap(), 1);
+            assert_eq!(get("kind").extract::<String>().unwrap(), "shape");
+            assert!(get("name").is_none());
+
+            let error = dump_library.call1(("missing.swf",)).unwrap_err();
+            assert!(error.is_instance_of::<RuffleError>(py));
+        });
+    }
+}
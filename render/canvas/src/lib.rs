@@ -17,7 +17,7 @@ use ruffle_render::transform::Transform;
 use ruffle_web_common::{JsError, JsResult};
 use std::borrow::Cow;
 use std::sync::Arc;
-use swf::{BlendMode, Color, ColorTransform, Point, Twips};
+use swf::{BlendMode, Color, ColorTransform, Point, Rectangle, Twips};
 use wasm_bindgen::{Clamped, JsCast, JsValue};
 use web_sys::{
     CanvasGradient, CanvasPattern, CanvasRenderingContext2d, CanvasWindingRule, DomMatrix, Element,
@@ -35,6 +35,7 @@ pub struct WebCanvasRenderBackend {
     rect: Path2d,
     mask_state: MaskState,
     blend_modes: Vec<RenderBlendMode>,
+    quality: StageQuality,
 
     // This is currnetly unused - we just store it to report
     // in `get_viewport_dimensions`
@@ -129,7 +130,12 @@ struct GradientTransform {
 #[derive(Debug)]
 enum CanvasStrokeStyle {
     Color(CanvasColor),
-    Gradient(swf::Gradient, Option<f64>),
+    Gradient {
+        gradient: swf::Gradient,
+        focal_point: Option<f64>,
+        /// How many times the gradient is repeated, see `gradient_repeats`.
+        repeats: u32,
+    },
     Bitmap(CanvasBitmap),
 }
 
@@ -302,6 +308,7 @@ impl WebCanvasRenderBackend {
             rect,
             mask_state: MaskState::DrawContent,
             blend_modes: vec![RenderBlendMode::Builtin(BlendMode::Normal)],
+            quality: StageQuality::default(),
         };
         Ok(renderer)
     }
@@ -506,7 +513,9 @@ impl RenderBackend for WebCanvasRenderBackend {
         "canvas"
     }
 
-    fn set_quality(&mut self, _quality: StageQuality) {}
+    fn set_quality(&mut self, quality: StageQuality) {
+        self.quality = quality;
+    }
 
     fn compile_pixelbender_shader(
         &mut self,
@@ -536,7 +545,7 @@ impl CommandHandler for WebCanvasRenderBackend {
         bitmap: BitmapHandle,
         transform: Transform,
         smoothing: bool,
-        _pixel_snapping: PixelSnapping,
+        pixel_snapping: PixelSnapping,
     ) {
         if self.mask_state == MaskState::ClearMask {
             return;
@@ -544,7 +553,9 @@ impl CommandHandler for WebCanvasRenderBackend {
 
         self.context.set_image_smoothing_enabled(smoothing);
 
-        self.set_transform(&transform.matrix);
+        let mut matrix = transform.matrix;
+        pixel_snapping.apply(&mut matrix);
+        self.set_transform(&matrix);
         self.set_color_filter(&transform);
         let bitmap = as_bitmap_data(&bitmap);
         let _ = self
@@ -622,7 +633,9 @@ impl CommandHandler for WebCanvasRenderBackend {
                                 }
                                 CanvasFillStyle::Bitmap(bitmap) => {
                                     self.set_color_filter(&transform);
-                                    self.context.set_image_smoothing_enabled(bitmap.smoothed);
+                                    self.context.set_image_smoothing_enabled(
+                                        bitmap.smoothed && self.quality.smooths_bitmaps(),
+                                    );
                                     self.context.set_fill_style(&bitmap.pattern);
                                     self.context.fill_with_path_2d_and_winding(
                                         path,
@@ -664,7 +677,11 @@ impl CommandHandler for WebCanvasRenderBackend {
                                     self.context.set_stroke_style(&color.1.into());
                                     self.context.stroke_with_path(&transformed_path);
                                 }
-                                CanvasStrokeStyle::Gradient(gradient, focal_point) => {
+                                CanvasStrokeStyle::Gradient {
+                                    gradient,
+                                    focal_point,
+                                    repeats,
+                                } => {
                                     // This is the hard case -- the Canvas API provides no good way to transform gradients,
                                     // and the inverse-transform trick used above for gradient fills can't be used here
                                     // because it will distort the stroke geometry.
@@ -681,11 +698,15 @@ impl CommandHandler for WebCanvasRenderBackend {
                                             &self.context,
                                             &gradient,
                                             *focal_point,
+                                            *repeats,
+                                            false,
+                                        ),
+                                        None => create_linear_gradient(
+                                            &self.context,
+                                            &gradient,
+                                            *repeats,
                                             false,
                                         ),
-                                        None => {
-                                            create_linear_gradient(&self.context, &gradient, false)
-                                        }
                                     };
                                     if let Ok(gradient) = gradient {
                                         self.set_color_filter(&transform);
@@ -703,7 +724,9 @@ impl CommandHandler for WebCanvasRenderBackend {
                                         bitmap_matrix.to_dom_matrix().unchecked_ref(),
                                     );
                                     self.set_color_filter(&transform);
-                                    self.context.set_image_smoothing_enabled(bitmap.smoothed);
+                                    self.context.set_image_smoothing_enabled(
+                                        bitmap.smoothed && self.quality.smooths_bitmaps(),
+                                    );
                                     self.context.set_stroke_style(&bitmap.pattern);
                                     self.context.stroke_with_path(&transformed_path);
                                     self.clear_color_filter();
@@ -884,12 +907,23 @@ fn swf_shape_to_canvas_commands(
                 let fill_style = match style {
                     FillStyle::Color(color) => CanvasFillStyle::Color((*color).into()),
                     FillStyle::LinearGradient(gradient) => CanvasFillStyle::Gradient(
-                        create_linear_gradient(&backend.context, gradient, true)
-                            .expect("Couldn't create linear gradient"),
+                        create_linear_gradient(
+                            &backend.context,
+                            gradient,
+                            gradient_repeats(gradient, false, &shape.shape_bounds),
+                            true,
+                        )
+                        .expect("Couldn't create linear gradient"),
                     ),
                     FillStyle::RadialGradient(gradient) => CanvasFillStyle::Gradient(
-                        create_radial_gradient(&backend.context, gradient, 0.0, true)
-                            .expect("Couldn't create radial gradient"),
+                        create_radial_gradient(
+                            &backend.context,
+                            gradient,
+                            0.0,
+                            gradient_repeats(gradient, true, &shape.shape_bounds),
+                            true,
+                        )
+                        .expect("Couldn't create radial gradient"),
                     ),
                     FillStyle::FocalGradient {
                         gradient,
@@ -899,6 +933,7 @@ fn swf_shape_to_canvas_commands(
                             &backend.context,
                             gradient,
                             focal_point.to_f64(),
+                            gradient_repeats(gradient, true, &shape.shape_bounds),
                             true,
                         )
                         .expect("Couldn't create radial gradient"),
@@ -943,16 +978,25 @@ fn swf_shape_to_canvas_commands(
 
                 let stroke_style = match style.fill_style() {
                     FillStyle::Color(color) => CanvasStrokeStyle::Color((*color).into()),
-                    FillStyle::LinearGradient(gradient) => {
-                        CanvasStrokeStyle::Gradient(gradient.clone(), None)
-                    }
-                    FillStyle::RadialGradient(gradient) => {
-                        CanvasStrokeStyle::Gradient(gradient.clone(), Some(0.0))
-                    }
+                    // The number of repeats needed doesn't depend on the final transform.
+                    FillStyle::LinearGradient(gradient) => CanvasStrokeStyle::Gradient {
+                        gradient: gradient.clone(),
+                        focal_point: None,
+                        repeats: gradient_repeats(gradient, false, &shape.shape_bounds),
+                    },
+                    FillStyle::RadialGradient(gradient) => CanvasStrokeStyle::Gradient {
+                        gradient: gradient.clone(),
+                        focal_point: Some(0.0),
+                        repeats: gradient_repeats(gradient, true, &shape.shape_bounds),
+                    },
                     FillStyle::FocalGradient {
                         gradient,
                         focal_point,
-                    } => CanvasStrokeStyle::Gradient(gradient.clone(), Some(focal_point.to_f64())),
+                    } => CanvasStrokeStyle::Gradient {
+                        gradient: gradient.clone(),
+                        focal_point: Some(focal_point.to_f64()),
+                        repeats: gradient_repeats(gradient, true, &shape.shape_bounds),
+                    },
                     FillStyle::Bitmap {
                         id,
                         matrix,
@@ -983,7 +1027,8 @@ fn swf_shape_to_canvas_commands(
                 let (line_join, miter_limit) = match style.join_style() {
                     LineJoinStyle::Round => ("round", 999_999.0),
                     LineJoinStyle::Bevel => ("bevel", 999_999.0),
-                    LineJoinStyle::Miter(ml) => ("miter", ml.to_f32()),
+                    // Flash clamps the miter limit to at least 1.
+                    LineJoinStyle::Miter(ml) => ("miter", ml.to_f32().max(1.0)),
                 };
                 canvas_data.push(CanvasDrawCommand::Stroke {
                     path: canvas_path,
@@ -991,13 +1036,8 @@ fn swf_shape_to_canvas_commands(
                     stroke_style,
                     line_cap: line_cap.to_string(),
                     line_join: line_join.to_string(),
-                    miter_limit: miter_limit as f64 / 20.0,
-                    scale_mode: match (style.allow_scale_x(), style.allow_scale_y()) {
-                        (false, false) => LineScaleMode::None,
-                        (true, false) => LineScaleMode::Horizontal,
-                        (false, true) => LineScaleMode::Vertical,
-                        (true, true) => LineScaleMode::Both,
-                    },
+                    miter_limit: miter_limit as f64,
+                    scale_mode: LineScaleMode::from_style(style),
                 });
             }
         }
@@ -1006,9 +1046,43 @@ fn swf_shape_to_canvas_commands(
     canvas_data
 }
 
+/// Returns how many times a gradient with a reflect or repeat spread mode has to be repeated to
+/// cover `bounds`, as canvas gradients can only pad.
+///
+/// This is always odd, so that the repeats of linear gradients, which are added on both sides of
+/// the gradient, line up with the original gradient.
+fn gradient_repeats(gradient: &swf::Gradient, is_radial: bool, bounds: &Rectangle<Twips>) -> u32 {
+    const MAX_REPEATS: u32 = 999;
+
+    if gradient.spread == swf::GradientSpread::Pad {
+        return 1;
+    }
+    let Some(inverse) = Matrix::from(gradient.matrix).inverse() else {
+        return MAX_REPEATS;
+    };
+    let corners = [
+        Point::new(bounds.x_min, bounds.y_min),
+        Point::new(bounds.x_max, bounds.y_min),
+        Point::new(bounds.x_min, bounds.y_max),
+        Point::new(bounds.x_max, bounds.y_max),
+    ];
+    // Gradients span from -16384 to 16384 twips in gradient space.
+    let extent = corners
+        .into_iter()
+        .map(|corner| {
+            let point = inverse * corner;
+            let (x, y) = (f64::from(point.x.get()), f64::from(point.y.get()));
+            let distance = if is_radial { x.hypot(y) } else { x.abs() };
+            distance / 16384.0
+        })
+        .fold(1.0, f64::max);
+    (extent.ceil() as u32).min(MAX_REPEATS) | 1
+}
+
 fn create_linear_gradient(
     context: &CanvasRenderingContext2d,
     gradient: &swf::Gradient,
+    repeats: u32,
     is_fill: bool,
 ) -> Result<Gradient, JsError> {
     // Canvas linear gradients are configured via the line endpoints, so we only need
@@ -1034,13 +1108,14 @@ fn create_linear_gradient(
             end.y.to_pixels() + dy,
         ))
     };
-    swf_to_canvas_gradient(gradient, transformed, create_fn)
+    swf_to_canvas_gradient(gradient, transformed, repeats, create_fn)
 }
 
 fn create_radial_gradient(
     context: &CanvasRenderingContext2d,
     gradient: &swf::Gradient,
     focal_point: f64,
+    repeats: u32,
     is_fill: bool,
 ) -> Result<Gradient, JsError> {
     // Canvas radial gradients can not be elliptical or skewed, so transform if there
@@ -1079,7 +1154,68 @@ fn create_radial_gradient(
             )
             .into_js_result()
     };
-    swf_to_canvas_gradient(gradient, transformed, create_fn)
+    swf_to_canvas_gradient(gradient, transformed, repeats, create_fn)
+}
+
+/// Returns the color stops of a gradient as ratios in `[0.0, 1.0]` and RGBA colors in `[0.0, 255.0]`.
+///
+/// Canvas gradients always interpolate in sRGB, so gradients interpolated in linear RGB are
+/// approximated by inserting intermediate stops.
+fn gradient_color_stops(gradient: &swf::Gradient) -> Vec<(f32, [f32; 4])> {
+    const LINEAR_RGB_STEPS: usize = 8;
+
+    fn color(record: &swf::GradientRecord) -> [f32; 4] {
+        [
+            record.color.r.into(),
+            record.color.g.into(),
+            record.color.b.into(),
+            record.color.a.into(),
+        ]
+    }
+    fn srgb_to_linear(c: f32) -> f32 {
+        let c = c / 255.0;
+        if c <= 0.04045 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    }
+    fn linear_to_srgb(c: f32) -> f32 {
+        let c = if c <= 0.0031308 {
+            c * 12.92
+        } else {
+            1.055 * c.powf(1.0 / 2.4) - 0.055
+        };
+        c * 255.0
+    }
+
+    let mut stops = Vec::with_capacity(gradient.records.len() * LINEAR_RGB_STEPS);
+    let mut records = gradient.records.iter().peekable();
+    while let Some(record) = records.next() {
+        let ratio = f32::from(record.ratio) / 255.0;
+        stops.push((ratio, color(record)));
+        let Some(next) = records.peek() else {
+            break;
+        };
+        if gradient.interpolation != swf::GradientInterpolation::LinearRgb {
+            continue;
+        }
+        let next_ratio = f32::from(next.ratio) / 255.0;
+        let (from, to) = (color(record), color(next));
+        for step in 1..LINEAR_RGB_STEPS {
+            let t = step as f32 / LINEAR_RGB_STEPS as f32;
+            let mut mixed = [0.0; 4];
+            for channel in 0..3 {
+                let from = srgb_to_linear(from[channel]);
+                let to = srgb_to_linear(to[channel]);
+                mixed[channel] = linear_to_srgb(from + (to - from) * t);
+            }
+            // Alpha is always interpolated linearly.
+            mixed[3] = from[3] + (to[3] - from[3]) * t;
+            stops.push((ratio + (next_ratio - ratio) * t, mixed));
+        }
+    }
+    stops
 }
 
 /// Converts an SWF gradient to a canvas gradient.
@@ -1091,6 +1227,7 @@ fn create_radial_gradient(
 fn swf_to_canvas_gradient(
     swf_gradient: &swf::Gradient,
     transformed: bool,
+    repeats: u32,
     mut create_gradient_fn: impl FnMut(swf::Matrix, f64) -> Result<CanvasGradient, JsError>,
 ) -> Result<Gradient, JsError> {
     let matrix = if transformed {
@@ -1101,31 +1238,26 @@ fn swf_to_canvas_gradient(
         swf_gradient.matrix
     };
 
-    const NUM_REPEATS: f32 = 25.0;
     let gradient_scale = if swf_gradient.spread == swf::GradientSpread::Pad {
         1.0
     } else {
-        f64::from(NUM_REPEATS)
+        f64::from(repeats)
     };
 
     // Canvas does not have support for spread/repeat modes (reflect+repeat), so we have to
-    // simulate these repeat modes by duplicating color stops.
-    // TODO: We'll hit the edge if the gradient is shrunk way down, but don't think we can do
-    // anything better using the current Canvas API. Maybe we could consider the size of the
-    // shape here to make sure we fill the area.
+    // simulate these repeat modes by scaling the gradient up and duplicating color stops.
     let canvas_gradient = create_gradient_fn(matrix, gradient_scale)?;
-    let color_stops: Vec<_> = swf_gradient
-        .records
-        .iter()
-        .map(|record| {
+    let color_stops: Vec<_> = gradient_color_stops(swf_gradient)
+        .into_iter()
+        .map(|(ratio, [r, g, b, a])| {
             (
-                f32::from(record.ratio) / 255.0,
+                ratio,
                 format!(
                     "rgba({},{},{},{})",
-                    record.color.r,
-                    record.color.g,
-                    record.color.b,
-                    f32::from(record.color.a) / 255.0
+                    r.round(),
+                    g.round(),
+                    b.round(),
+                    a / 255.0
                 ),
             )
         })
@@ -1140,43 +1272,43 @@ fn swf_to_canvas_gradient(
             }
         }
         swf::GradientSpread::Reflect => {
-            let mut t = 0.0;
-            let step = 1.0 / NUM_REPEATS;
-            while t < 1.0 {
-                // Add the colors forward.
-                for stop in &color_stops {
-                    canvas_gradient
-                        .add_color_stop(t + stop.0 * step, &stop.1)
-                        .warn_on_error();
-                }
-                t += step;
-                // Add the colors backward.
-                for stop in color_stops.iter().rev() {
-                    canvas_gradient
-                        .add_color_stop(t + (1.0 - stop.0) * step, &stop.1)
-                        .warn_on_error();
+            let step = 1.0 / repeats as f32;
+            for i in 0..repeats {
+                let t = i as f32 * step;
+                if i % 2 == 0 {
+                    // Add the colors forward.
+                    for stop in &color_stops {
+                        canvas_gradient
+                            .add_color_stop((t + stop.0 * step).min(1.0), &stop.1)
+                            .warn_on_error();
+                    }
+                } else {
+                    // Add the colors backward.
+                    for stop in color_stops.iter().rev() {
+                        canvas_gradient
+                            .add_color_stop(t + (1.0 - stop.0) * step, &stop.1)
+                            .warn_on_error();
+                    }
                 }
-                t += step;
             }
         }
         swf::GradientSpread::Repeat => {
             if let (Some(first_stop), Some(last_stop)) = (color_stops.first(), color_stops.last()) {
-                let mut t = 0.0;
-                let step = 1.0 / NUM_REPEATS;
-                while t < 1.0 {
+                let step = 1.0 / repeats as f32;
+                for i in 0..repeats {
+                    let t = i as f32 * step;
                     // Duplicate the start/end stops to ensure we don't blend between the seams.
                     canvas_gradient
                         .add_color_stop(t, &first_stop.1)
                         .warn_on_error();
                     for stop in &color_stops {
                         canvas_gradient
-                            .add_color_stop(t + stop.0 * step, &stop.1)
+                            .add_color_stop((t + stop.0 * step).min(1.0), &stop.1)
                             .warn_on_error();
                     }
                     canvas_gradient
-                        .add_color_stop(t + step, &last_stop.1)
+                        .add_color_stop((t + step).min(1.0), &last_stop.1)
                         .warn_on_error();
-                    t += step;
                 }
             }
         }
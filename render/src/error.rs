@@ -30,6 +30,9 @@ pub enum Error {
     #[error("Empty GIF")]
     EmptyGif,
 
+    #[error("JPEG XR images are not supported")]
+    UnsupportedJpegXr,
+
     #[error("Unsupported DefineBitsLossless{0} format {1:?}")]
     UnsupportedLosslessFormat(u8, swf::BitmapFormat),
 
@@ -0,0 +1,373 @@
+use crate::matrix::Matrix;
+use swf::Twips;
+
+/// A 4x4 transformation matrix, as used by `flash.geom.Matrix3D`.
+///
+/// The elements are stored in column-major order, matching the layout of
+/// `Matrix3D.rawData`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Matrix3D {
+    pub raw_data: [f64; 16],
+}
+
+/// The components of a decomposed `Matrix3D`.
+///
+/// Rotations are in radians and are applied in X, Y, Z order, which matches
+/// the order Flash uses for the `rotationX`, `rotationY` and `rotationZ`
+/// display object properties.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Components3D {
+    pub translation: [f64; 3],
+    pub rotation: [f64; 3],
+    pub scale: [f64; 3],
+}
+
+impl Default for Components3D {
+    fn default() -> Self {
+        Self {
+            translation: [0.0; 3],
+            rotation: [0.0; 3],
+            scale: [1.0; 3],
+        }
+    }
+}
+
+impl Matrix3D {
+    pub const IDENTITY: Self = Self {
+        raw_data: [
+            1.0, 0.0, 0.0, 0.0, //
+            0.0, 1.0, 0.0, 0.0, //
+            0.0, 0.0, 1.0, 0.0, //
+            0.0, 0.0, 0.0, 1.0, //
+        ],
+    };
+
+    /// Returns the element at the given row and column.
+    #[inline]
+    pub fn get(&self, row: usize, column: usize) -> f64 {
+        self.raw_data[column * 4 + row]
+    }
+
+    /// Sets the element at the given row and column.
+    #[inline]
+    pub fn set(&mut self, row: usize, column: usize, value: f64) {
+        self.raw_data[column * 4 + row] = value;
+    }
+
+    /// Builds a matrix that scales, then rotates around X, Y and Z in turn,
+    /// then translates.
+    pub fn recompose(components: &Components3D) -> Self {
+        let [sx, sy, sz] = components.scale;
+        let [rx, ry, rz] = components.rotation;
+        let [tx, ty, tz] = components.translation;
+        let (sin_x, cos_x) = rx.sin_cos();
+        let (sin_y, cos_y) = ry.sin_cos();
+        let (sin_z, cos_z) = rz.sin_cos();
+
+        Self {
+            raw_data: [
+                cos_z * cos_y * sx,
+                sin_z * cos_y * sx,
+                -sin_y * sx,
+                0.0,
+                (cos_z * sin_y * sin_x - sin_z * cos_x) * sy,
+                (sin_z * sin_y * sin_x + cos_z * cos_x) * sy,
+                cos_y * sin_x * sy,
+                0.0,
+                (cos_z * sin_y * cos_x + sin_z * sin_x) * sz,
+                (sin_z * sin_y * cos_x - cos_z * sin_x) * sz,
+                cos_y * cos_x * sz,
+                0.0,
+                tx,
+                ty,
+                tz,
+                1.0,
+            ],
+        }
+    }
+
+    /// Splits this matrix into translation, rotation and scale.
+    ///
+    /// Any skew or projection in the matrix is discarded.
+    pub fn decompose(&self) -> Components3D {
+        let column_length = |c: usize| (0..3).map(|r| self.get(r, c).powi(2)).sum::<f64>().sqrt();
+        let scale = [column_length(0), column_length(1), column_length(2)];
+        let normalized = |r: usize, c: usize| {
+            if scale[c] == 0.0 {
+                0.0
+            } else {
+                self.get(r, c) / scale[c]
+            }
+        };
+
+        let rotation_y = (-normalized(2, 0)).clamp(-1.0, 1.0).asin();
+        let (rotation_x, rotation_z) = if rotation_y.cos().abs() > 1e-9 {
+            (
+                normalized(2, 1).atan2(normalized(2, 2)),
+                normalized(1, 0).atan2(normalized(0, 0)),
+            )
+        } else {
+            // Gimbal lock: X and Z rotate around the same axis.
+            ((-normalized(1, 2)).atan2(normalized(1, 1)), 0.0)
+        };
+
+        Components3D {
+            translation: [self.get(0, 3), self.get(1, 3), self.get(2, 3)],
+            rotation: [rotation_x, rotation_y, rotation_z],
+            scale,
+        }
+    }
+
+    /// Returns `self * other`, i.e. a matrix that applies `other` first.
+    pub fn multiply(&self, other: &Self) -> Self {
+        let mut result = Self {
+            raw_data: [0.0; 16],
+        };
+        for row in 0..4 {
+            for column in 0..4 {
+                let value = (0..4)
+                    .map(|k| self.get(row, k) * other.get(k, column))
+                    .sum();
+                result.set(row, column, value);
+            }
+        }
+        result
+    }
+
+    /// Flattens this matrix into a 2D affine matrix, as seen through the given
+    /// perspective projection.
+    ///
+    /// A point at depth `z` is moved towards the projection centre by a factor of
+    /// `focal_length / (focal_length + z)`. That isn't an affine transformation, so
+    /// the result is the closest affine matrix at the registration point: the
+    /// registration point lands exactly where Flash puts it, and planes tilted
+    /// away from the viewer are foreshortened, but their far edges aren't
+    /// narrowed (there is no keystone distortion).
+    ///
+    /// The projection centre is given in the coordinate space this matrix
+    /// transforms into, i.e. that of the parent display object.
+    pub fn to_projected_matrix(&self, projection: &Projection) -> Matrix {
+        let (center_x, center_y) = projection.center;
+        let focal_length = projection.focal_length;
+        let x = self.get(0, 3) - center_x;
+        let y = self.get(1, 3) - center_y;
+        let depth = focal_length + self.get(2, 3);
+        if focal_length <= 0.0 || depth <= 0.0 {
+            // Behind the viewer, so nothing is visible.
+            return Matrix {
+                a: 0.0,
+                b: 0.0,
+                c: 0.0,
+                d: 0.0,
+                tx: Twips::from_pixels(center_x),
+                ty: Twips::from_pixels(center_y),
+            };
+        }
+
+        // The derivative of `center + (p - center) * focal_length / (focal_length + z)`
+        // along each local axis.
+        let w = focal_length / depth;
+        let project = |column: usize| {
+            let dz = self.get(2, column) / depth;
+            (
+                (w * (self.get(0, column) - x * dz)) as f32,
+                (w * (self.get(1, column) - y * dz)) as f32,
+            )
+        };
+        let (a, b) = project(0);
+        let (c, d) = project(1);
+
+        Matrix {
+            a,
+            b,
+            c,
+            d,
+            tx: Twips::from_pixels(center_x + x * w),
+            ty: Twips::from_pixels(center_y + y * w),
+        }
+    }
+}
+
+/// A perspective projection, as described by `flash.geom.PerspectiveProjection`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Projection {
+    /// The distance from the viewer to the `z = 0` plane, in pixels.
+    pub focal_length: f64,
+
+    /// The vanishing point, in pixels.
+    pub center: (f64, f64),
+}
+
+impl Projection {
+    /// Flash's default field of view, in degrees.
+    pub const DEFAULT_FIELD_OF_VIEW: f64 = 55.0;
+
+    /// The projection Flash uses for a stage of the given size (in pixels): the
+    /// default field of view, centred on the stage.
+    pub fn for_stage(width: f64, height: f64) -> Self {
+        Self {
+            focal_length: focal_length(Self::DEFAULT_FIELD_OF_VIEW, width),
+            center: (width / 2.0, height / 2.0),
+        }
+    }
+
+    /// Returns this projection with its centre moved into another coordinate space.
+    pub fn transformed(&self, matrix: &Matrix) -> Self {
+        let (x, y) = self.center;
+        Self {
+            focal_length: self.focal_length,
+            center: (
+                f64::from(matrix.a) * x + f64::from(matrix.c) * y + matrix.tx.to_pixels(),
+                f64::from(matrix.b) * x + f64::from(matrix.d) * y + matrix.ty.to_pixels(),
+            ),
+        }
+    }
+}
+
+impl Default for Matrix3D {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl From<Matrix> for Matrix3D {
+    fn from(matrix: Matrix) -> Self {
+        Self {
+            raw_data: [
+                matrix.a.into(),
+                matrix.b.into(),
+                0.0,
+                0.0,
+                matrix.c.into(),
+                matrix.d.into(),
+                0.0,
+                0.0,
+                0.0,
+                0.0,
+                1.0,
+                0.0,
+                matrix.tx.to_pixels(),
+                matrix.ty.to_pixels(),
+                0.0,
+                1.0,
+            ],
+        }
+    }
+}
+
+/// Computes the focal length of a perspective projection with the given field
+/// of view (in degrees) over a viewport of the given width (in pixels).
+pub fn focal_length(field_of_view: f64, width: f64) -> f64 {
+    let half_angle = field_of_view.clamp(0.0001, 179.9999).to_radians() / 2.0;
+    (width / 2.0) / half_angle.tan()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+    use std::f64::consts::FRAC_PI_2;
+
+    const PROJECTION: Projection = Projection {
+        focal_length: 480.0,
+        center: (250.0, 250.0),
+    };
+
+    #[test]
+    fn recompose_decompose_roundtrip() {
+        let components = Components3D {
+            translation: [10.0, -20.0, 30.0],
+            rotation: [0.3, -0.6, 1.2],
+            scale: [2.0, 0.5, 1.5],
+        };
+        let decomposed = Matrix3D::recompose(&components).decompose();
+        for i in 0..3 {
+            assert_abs_diff_eq!(
+                decomposed.translation[i],
+                components.translation[i],
+                epsilon = 1e-9
+            );
+            assert_abs_diff_eq!(
+                decomposed.rotation[i],
+                components.rotation[i],
+                epsilon = 1e-9
+            );
+            assert_abs_diff_eq!(decomposed.scale[i], components.scale[i], epsilon = 1e-9);
+        }
+    }
+
+    #[test]
+    fn rotation_z_matches_2d_rotation() {
+        let components = Components3D {
+            rotation: [0.0, 0.0, 0.5],
+            ..Default::default()
+        };
+        let matrix = Matrix3D::recompose(&components).to_projected_matrix(&PROJECTION);
+        assert_abs_diff_eq!(matrix.a, 0.5f32.cos(), epsilon = 1e-6);
+        assert_abs_diff_eq!(matrix.b, 0.5f32.sin(), epsilon = 1e-6);
+        assert_abs_diff_eq!(matrix.c, -0.5f32.sin(), epsilon = 1e-6);
+        assert_abs_diff_eq!(matrix.d, 0.5f32.cos(), epsilon = 1e-6);
+    }
+
+    #[test]
+    fn rotation_y_foreshortens() {
+        let components = Components3D {
+            translation: [250.0, 250.0, 0.0],
+            rotation: [0.0, FRAC_PI_2, 0.0],
+            ..Default::default()
+        };
+        let matrix = Matrix3D::recompose(&components).to_projected_matrix(&PROJECTION);
+        assert_abs_diff_eq!(matrix.a, 0.0, epsilon = 1e-6);
+        assert_abs_diff_eq!(matrix.d, 1.0, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn rotation_y_away_from_center_skews_towards_it() {
+        // A plane right of the centre, turned to face sideways, recedes towards the centre.
+        let components = Components3D {
+            translation: [490.0, 250.0, 0.0],
+            rotation: [0.0, -FRAC_PI_2, 0.0],
+            ..Default::default()
+        };
+        let matrix = Matrix3D::recompose(&components).to_projected_matrix(&PROJECTION);
+        // The local x axis points straight into the screen, at (0, 0, 1).
+        assert_abs_diff_eq!(matrix.a, -0.5, epsilon = 1e-6);
+        assert_abs_diff_eq!(matrix.b, 0.0, epsilon = 1e-6);
+        assert_abs_diff_eq!(matrix.d, 1.0, epsilon = 1e-6);
+        assert_eq!(matrix.tx, Twips::from_pixels(490.0));
+    }
+
+    #[test]
+    fn z_moves_towards_projection_center() {
+        let components = Components3D {
+            translation: [50.0, 450.0, 480.0],
+            ..Default::default()
+        };
+        let matrix = Matrix3D::recompose(&components).to_projected_matrix(&PROJECTION);
+        assert_abs_diff_eq!(matrix.a, 0.5, epsilon = 1e-6);
+        assert_abs_diff_eq!(matrix.d, 0.5, epsilon = 1e-6);
+        assert_eq!(matrix.tx, Twips::from_pixels(150.0));
+        assert_eq!(matrix.ty, Twips::from_pixels(350.0));
+    }
+
+    #[test]
+    fn behind_the_viewer_is_invisible() {
+        let components = Components3D {
+            translation: [0.0, 0.0, -500.0],
+            ..Default::default()
+        };
+        let matrix = Matrix3D::recompose(&components).to_projected_matrix(&PROJECTION);
+        assert_eq!(
+            (matrix.a, matrix.b, matrix.c, matrix.d),
+            (0.0, 0.0, 0.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn default_focal_length() {
+        assert_abs_diff_eq!(focal_length(55.0, 500.0), 480.25, epsilon = 0.01);
+        let projection = Projection::for_stage(550.0, 400.0);
+        assert_abs_diff_eq!(projection.focal_length, 528.27, epsilon = 0.01);
+        assert_eq!(projection.center, (275.0, 200.0));
+    }
+}
@@ -1,4 +1,5 @@
 use ruffle_wstr::{FromWStr, WStr};
+use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::fmt::{Display, Formatter};
 use std::str::FromStr;
@@ -9,38 +10,46 @@ use std::str::FromStr;
 /// These settings currently have no effect in Ruffle, but the active setting is still stored.
 /// [StageQuality in the AS3 Reference](https://help.adobe.com/en_US/FlashPlatform/reference/actionscript/3/flash/display/StageQuality.html)
 #[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
-#[derive(Default, Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Default, Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub enum StageQuality {
     /// No anti-aliasing, and bitmaps are never smoothed.
+    #[serde(rename = "low")]
     Low,
 
     /// 2x anti-aliasing.
+    #[serde(rename = "medium")]
     Medium,
 
     /// 4x anti-aliasing.
     #[default]
+    #[serde(rename = "high")]
     High,
 
     /// 4x anti-aliasing with high quality downsampling.
     /// Bitmaps will use high quality downsampling when scaled down.
     /// Despite the name, this is not the best quality setting as 8x8 and 16x16 modes were added to
     /// Flash Player 11.3.
+    #[serde(rename = "best")]
     Best,
 
     /// 8x anti-aliasing.
     /// Bitmaps will use high quality downsampling when scaled down.
+    #[serde(rename = "8x8")]
     High8x8,
 
     /// 8x anti-aliasing done in linear sRGB space.
     /// Bitmaps will use high quality downsampling when scaled down.
+    #[serde(rename = "8x8linear")]
     High8x8Linear,
 
     /// 16x anti-aliasing.
     /// Bitmaps will use high quality downsampling when scaled down.
+    #[serde(rename = "16x16")]
     High16x16,
 
     /// 16x anti-aliasing done in linear sRGB space.
     /// Bitmaps will use high quality downsampling when scaled down.
+    #[serde(rename = "16x16linear")]
     High16x16Linear,
 }
 
@@ -74,6 +83,13 @@ impl StageQuality {
             StageQuality::High16x16Linear => 16,
         }
     }
+
+    /// Returns whether bitmaps may be smoothed at this quality.
+    ///
+    /// Flash never smooths bitmaps at `LOW` quality, even if smoothing was requested.
+    pub fn smooths_bitmaps(self) -> bool {
+        self != StageQuality::Low
+    }
 }
 
 impl Display for StageQuality {
@@ -103,6 +103,19 @@ pub struct DistilledShape<'a> {
     pub shape_bounds: Rectangle<Twips>,
     pub edge_bounds: Rectangle<Twips>,
     pub id: CharacterId,
+    /// The matrix this shape will be drawn with, used by the tessellator to size strokes that
+    /// don't scale with the shape. Strokes always scale with the shape if this is `None`.
+    pub line_scale_matrix: Option<Matrix>,
+}
+
+impl<'a> DistilledShape<'a> {
+    /// Returns whether any stroke in this shape doesn't scale along with the shape.
+    pub fn has_non_scaling_strokes(&self) -> bool {
+        self.paths.iter().any(|path| {
+            matches!(path, DrawPath::Stroke { style, .. }
+                if LineScaleMode::from_style(style) != LineScaleMode::Both)
+        })
+    }
 }
 
 impl<'a> From<&'a swf::Shape> for DistilledShape<'a> {
@@ -112,6 +125,7 @@ impl<'a> From<&'a swf::Shape> for DistilledShape<'a> {
             shape_bounds: shape.shape_bounds.clone(),
             edge_bounds: shape.edge_bounds.clone(),
             id: shape.id,
+            line_scale_matrix: None,
         }
     }
 }
@@ -210,8 +224,16 @@ impl PathSegment {
         Some((*self.points.last()?).into())
     }
 
+    /// Whether the segment ends where it starts.
+    /// A segment that never leaves its starting point is not closed, so that it still gets
+    /// caps drawn (a dot for round or square caps, like Flash).
     fn is_closed(&self) -> bool {
-        self.start() == self.end()
+        let start = self.start();
+        start == self.end()
+            && self
+                .points
+                .iter()
+                .any(|point| Some(swf::Point::from(*point)) != start)
     }
 
     fn to_draw_commands(&self) -> impl '_ + Iterator<Item = DrawCommand> {
@@ -1212,6 +1234,18 @@ pub enum LineScaleMode {
     Both,
 }
 
+impl LineScaleMode {
+    /// Returns the scale mode of the given line style.
+    pub fn from_style(style: &LineStyle) -> Self {
+        match (style.allow_scale_x(), style.allow_scale_y()) {
+            (false, false) => LineScaleMode::None,
+            (true, false) => LineScaleMode::Horizontal,
+            (false, true) => LineScaleMode::Vertical,
+            (true, true) => LineScaleMode::Both,
+        }
+    }
+}
+
 /// Helper type for calculating line widths for a transformed shape.
 pub struct LineScales<'a> {
     matrix: &'a Matrix,
@@ -1231,18 +1265,33 @@ impl<'a> LineScales<'a> {
     /// Returns the final width of a line after transformation.
     #[inline]
     pub fn transform_width(&mut self, width: f32, scale_mode: LineScaleMode) -> f32 {
+        let scaled_width = width * self.scales()[scale_mode as usize];
+        // Flash draws all strokes with a minimum width of 1 pixel.
+        // This usually occurs in "hairline" strokes (exported with width of 1 twip).
+        scaled_width.max(1.0)
+    }
+
+    /// Returns the width a line has to have before transformation, so that it ends up with the
+    /// final width given by `transform_width` once the whole shape is transformed.
+    #[inline]
+    pub fn untransformed_width(&mut self, width: f32, scale_mode: LineScaleMode) -> f32 {
+        let shape_scale = self.scales()[LineScaleMode::Both as usize];
+        if shape_scale > 0.0 {
+            self.transform_width(width, scale_mode) / shape_scale
+        } else {
+            width
+        }
+    }
+
+    fn scales(&mut self) -> &[f32; 4] {
         // Lazily calculate the scale to avoid doing so for shapes that have no strokes.
-        let scales = self.scales.get_or_insert_with(|| {
+        self.scales.get_or_insert_with(|| {
             let line_scale_x = f32::abs(self.matrix.a + self.matrix.c);
             let line_scale_y = f32::abs(self.matrix.b + self.matrix.d);
             let line_scale =
                 ((line_scale_x * line_scale_x + line_scale_y * line_scale_y) / 2.0).sqrt();
             [1.0, line_scale_x, line_scale_y, line_scale]
-        });
-        let scaled_width = width * scales[scale_mode as usize];
-        // Flash draws all strokes with a minimum width of 1 pixel.
-        // This usually occurs in "hairline" strokes (exported with width of 1 twip).
-        scaled_width.max(1.0)
+        })
     }
 }
 
@@ -1464,4 +1513,121 @@ mod tests {
             1,
         );
     }
+
+    /// A stroke that never leaves its starting point isn't closed, so that it gets caps.
+    #[test]
+    fn zero_length_stroke() {
+        let line_style = LineStyle::new()
+            .with_width(Twips::from_pixels(10.0))
+            .with_start_cap(swf::LineCapStyle::Round)
+            .with_end_cap(swf::LineCapStyle::Round);
+        let records = vec![
+            ShapeRecord::StyleChange(Box::new(swf::StyleChangeData {
+                move_to: Some(swf::Point::from_pixels(100.0, 100.0)),
+                fill_style_0: None,
+                fill_style_1: None,
+                line_style: Some(1),
+                new_styles: None,
+            })),
+            ShapeRecord::StraightEdge {
+                delta: PointDelta::from_pixels(0.0, 0.0),
+            },
+        ];
+        let bounds = calculate_shape_bounds(&records);
+        let shape = swf::Shape {
+            version: 2,
+            id: 1,
+            shape_bounds: bounds.clone(),
+            edge_bounds: bounds,
+            flags: swf::ShapeFlag::HAS_SCALING_STROKES,
+            styles: swf::ShapeStyles {
+                fill_styles: vec![],
+                line_styles: vec![line_style.clone()],
+            },
+            shape: records,
+        };
+        let commands = ShapeConverter::from_shape(&shape).into_commands();
+        let expected = vec![DrawPath::Stroke {
+            style: &line_style,
+            commands: vec![
+                DrawCommand::MoveTo(swf::Point::from_pixels(100.0, 100.0)),
+                DrawCommand::LineTo(swf::Point::from_pixels(100.0, 100.0)),
+            ],
+            is_closed: false,
+        }];
+        assert_eq!(commands, expected);
+    }
+
+    /// Non-scaling strokes are sized so that they keep their width once the shape is transformed.
+    #[test]
+    fn untransformed_line_widths() {
+        let matrix = Matrix::scale(2.0, 2.0);
+        let mut line_scales = LineScales::new(&matrix);
+        assert_eq!(
+            line_scales.untransformed_width(10.0, LineScaleMode::Both),
+            10.0
+        );
+        assert_eq!(
+            line_scales.untransformed_width(10.0, LineScaleMode::None),
+            5.0
+        );
+        assert_eq!(
+            line_scales.untransformed_width(10.0, LineScaleMode::Horizontal),
+            10.0
+        );
+        // Hairlines are still at least 1 pixel wide after the transform.
+        assert_eq!(
+            line_scales.untransformed_width(0.05, LineScaleMode::None),
+            0.5
+        );
+
+        let mut line_scales = LineScales::new(&Matrix::ZERO);
+        assert_eq!(
+            line_scales.untransformed_width(10.0, LineScaleMode::None),
+            10.0
+        );
+    }
+
+    #[test]
+    fn non_scaling_strokes() {
+        let records = vec![
+            ShapeRecord::StyleChange(Box::new(swf::StyleChangeData {
+                move_to: None,
+                fill_style_0: None,
+                fill_style_1: None,
+                line_style: Some(1),
+                new_styles: None,
+            })),
+            ShapeRecord::StraightEdge {
+                delta: PointDelta::from_pixels(10.0, 0.0),
+            },
+        ];
+        let bounds = calculate_shape_bounds(&records);
+        let mut shape = swf::Shape {
+            version: 2,
+            id: 1,
+            shape_bounds: bounds.clone(),
+            edge_bounds: bounds,
+            flags: swf::ShapeFlag::HAS_SCALING_STROKES,
+            styles: swf::ShapeStyles {
+                fill_styles: vec![],
+                line_styles: vec![LineStyle::new().with_width(Twips::from_pixels(2.0))],
+            },
+            shape: records,
+        };
+        assert!(!DistilledShape::from(&shape).has_non_scaling_strokes());
+
+        shape.flags = swf::ShapeFlag::HAS_NON_SCALING_STROKES;
+        shape.styles.line_styles[0] = LineStyle::new()
+            .with_width(Twips::from_pixels(2.0))
+            .with_allow_scale_y(false);
+        let distilled = DistilledShape::from(&shape);
+        assert!(distilled.has_non_scaling_strokes());
+        match &distilled.paths[0] {
+            DrawPath::Stroke { style, .. } => {
+                assert_eq!(LineScaleMode::from_style(style), LineScaleMode::Horizontal)
+            }
+            path => panic!("Expected a stroke, got {path:?}"),
+        }
+    }
 }
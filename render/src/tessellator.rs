@@ -1,5 +1,7 @@
 use crate::bitmap::BitmapSource;
-use crate::shape_utils::{DistilledShape, DrawCommand, DrawPath, GradientType};
+use crate::shape_utils::{
+    DistilledShape, DrawCommand, DrawPath, GradientType, LineScaleMode, LineScales,
+};
 use lyon::path::Path;
 use lyon::tessellation::{
     self,
@@ -39,6 +41,7 @@ impl ShapeTessellator {
     ) -> Mesh {
         self.mesh = Vec::new();
         self.lyon_mesh = VertexBuffers::new();
+        let mut line_scales = shape.line_scale_matrix.as_ref().map(LineScales::new);
         for path in shape.paths {
             let (fill_style, lyon_path, next_is_stroke) = match &path {
                 DrawPath::Fill {
@@ -141,7 +144,15 @@ impl ShapeTessellator {
                 ),
                 DrawPath::Stroke { style, .. } => {
                     // TODO(Herschel): 0 width indicates "hairline".
-                    let width = (style.width().to_pixels() as f32).max(1.0);
+                    let width = style.width().to_pixels() as f32;
+                    let width = match &mut line_scales {
+                        // Size the stroke so that it has the width its scale mode asks for once
+                        // the whole shape is transformed.
+                        Some(line_scales) => {
+                            line_scales.untransformed_width(width, LineScaleMode::from_style(style))
+                        }
+                        None => width.max(1.0),
+                    };
                     let mut stroke_options = StrokeOptions::default()
                         .with_line_width(width)
                         .with_start_cap(match style.start_cap() {
@@ -159,14 +170,11 @@ impl ShapeTessellator {
                         swf::LineJoinStyle::Round => tessellation::LineJoin::Round,
                         swf::LineJoinStyle::Bevel => tessellation::LineJoin::Bevel,
                         swf::LineJoinStyle::Miter(limit) => {
-                            // Avoid lyon assert with small miter limits.
-                            let limit = limit.to_f32();
-                            if limit >= StrokeOptions::MINIMUM_MITER_LIMIT {
-                                stroke_options = stroke_options.with_miter_limit(limit);
-                                tessellation::LineJoin::MiterClip
-                            } else {
-                                tessellation::LineJoin::Bevel
-                            }
+                            // Flash clamps the miter limit to at least 1, which also avoids a lyon
+                            // assert with small miter limits. Miters past the limit are cut off.
+                            let limit = limit.to_f32().max(StrokeOptions::MINIMUM_MITER_LIMIT);
+                            stroke_options = stroke_options.with_miter_limit(limit);
+                            tessellation::LineJoin::MiterClip
                         }
                     };
                     stroke_options = stroke_options.with_line_join(line_join);
@@ -2,6 +2,7 @@ use crate::bitmap::{Bitmap, BitmapFormat};
 use crate::error::Error;
 use std::borrow::Cow;
 use std::io::Read;
+use std::time::Duration;
 use swf::Color;
 
 /// The format of image data in a DefineBitsJpeg2/3 tag.
@@ -12,6 +13,7 @@ pub enum JpegTagFormat {
     Jpeg,
     Png,
     Gif,
+    JpegXr,
     Unknown,
 }
 
@@ -22,22 +24,31 @@ pub fn determine_jpeg_tag_format(data: &[u8]) -> JpegTagFormat {
         [0xff, 0xd9, 0xff, 0xd8, ..] => JpegTagFormat::Jpeg, // erroneous header in SWF
         [0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a, ..] => JpegTagFormat::Png,
         [0x47, 0x49, 0x46, 0x38, 0x39, 0x61, ..] => JpegTagFormat::Gif,
+        [0x49, 0x49, 0xbc, ..] => JpegTagFormat::JpegXr,
         _ => JpegTagFormat::Unknown,
     }
 }
 
-/// Decodes bitmap data from a DefineBitsJPEG2/3 tag.
+/// Decodes bitmap data from a DefineBitsJPEG2/3/4 tag.
 /// The data is returned with pre-multiplied alpha.
-pub fn decode_define_bits_jpeg(data: &[u8], alpha_data: Option<&[u8]>) -> Result<Bitmap, Error> {
+///
+/// `deblocking` is the strength of the deblocking filter from a DefineBitsJPEG4 tag, from 0.0
+/// (none) to 1.0.
+pub fn decode_define_bits_jpeg(
+    data: &[u8],
+    alpha_data: Option<&[u8]>,
+    deblocking: f32,
+) -> Result<Bitmap, Error> {
     let format = determine_jpeg_tag_format(data);
     if format != JpegTagFormat::Jpeg && alpha_data.is_some() {
         // Only DefineBitsJPEG3 with true JPEG data should have separate alpha data.
         tracing::warn!("DefineBitsJPEG contains non-JPEG data with alpha; probably incorrect")
     }
     match format {
-        JpegTagFormat::Jpeg => decode_jpeg(data, alpha_data),
+        JpegTagFormat::Jpeg => decode_jpeg(data, alpha_data, deblocking),
         JpegTagFormat::Png => decode_png(data),
         JpegTagFormat::Gif => decode_gif(data),
+        JpegTagFormat::JpegXr => Err(Error::UnsupportedJpegXr),
         JpegTagFormat::Unknown => Err(Error::UnknownType),
     }
 }
@@ -155,7 +166,11 @@ fn validate_size(width: u16, height: u16) -> Result<(), Error> {
 
 /// Decodes a JPEG with optional alpha data.
 /// The decoded bitmap will have pre-multiplied alpha.
-fn decode_jpeg(jpeg_data: &[u8], alpha_data: Option<&[u8]>) -> Result<Bitmap, Error> {
+fn decode_jpeg(
+    jpeg_data: &[u8],
+    alpha_data: Option<&[u8]>,
+    deblocking: f32,
+) -> Result<Bitmap, Error> {
     let jpeg_data = remove_invalid_jpeg_data(jpeg_data);
 
     let mut decoder = jpeg_decoder::Decoder::new(&jpeg_data[..]);
@@ -166,7 +181,7 @@ fn decode_jpeg(jpeg_data: &[u8], alpha_data: Option<&[u8]>) -> Result<Bitmap, Er
     validate_size(metadata.width, metadata.height)?;
     let decoded_data = decoder.decode()?;
 
-    let decoded_data = match metadata.pixel_format {
+    let mut decoded_data = match metadata.pixel_format {
         jpeg_decoder::PixelFormat::RGB24 => decoded_data,
         jpeg_decoder::PixelFormat::CMYK32 => decoded_data
             .chunks_exact(4)
@@ -189,36 +204,40 @@ fn decode_jpeg(jpeg_data: &[u8], alpha_data: Option<&[u8]>) -> Result<Bitmap, Er
         }
     };
 
-    // Decompress the alpha data (DEFLATE compression).
-    if let Some(alpha_data) = alpha_data {
-        let alpha_data = decompress_zlib(alpha_data)?;
-
-        if alpha_data.len() == decoded_data.len() / 3 {
-            let rgba = decoded_data
-                .chunks_exact(3)
-                .zip(alpha_data)
-                .flat_map(|(rgb, a)| {
-                    // The JPEG data should be premultiplied alpha, but it isn't in some incorrect
-                    // SWFs (see #6893).
-                    // This means 0% alpha pixels may have color and incorrectly show as visible.
-                    // Flash Player clamps color to the alpha value to fix this case.
-                    // Only applies to DefineBitsJPEG3; DefineBitsLossless does not seem to clamp.
-                    let r = rgb[0].min(a);
-                    let g = rgb[1].min(a);
-                    let b = rgb[2].min(a);
-                    [r, g, b, a]
-                })
-                .collect();
-            return Ok(Bitmap::new(
-                metadata.width.into(),
-                metadata.height.into(),
-                BitmapFormat::Rgba,
-                rgba,
-            ));
-        } else {
-            // Size isn't correct; fallback to RGB?
-            tracing::error!("Size mismatch in DefineBitsJPEG3 alpha data");
-        }
+    let num_pixels = metadata.width as usize * metadata.height as usize;
+    if decoded_data.len() == num_pixels * 3 {
+        deblock(
+            &mut decoded_data,
+            metadata.width.into(),
+            metadata.height.into(),
+            deblocking,
+        );
+    }
+
+    // An empty alpha plane means the image is opaque.
+    if let Some(alpha_data) = alpha_data.filter(|alpha_data| !alpha_data.is_empty()) {
+        let alpha_data = decompress_alpha(alpha_data, num_pixels);
+        let rgba = decoded_data
+            .chunks_exact(3)
+            .zip(alpha_data)
+            .flat_map(|(rgb, a)| {
+                // The JPEG data should be premultiplied alpha, but it isn't in some incorrect
+                // SWFs (see #6893).
+                // This means 0% alpha pixels may have color and incorrectly show as visible.
+                // Flash Player clamps color to the alpha value to fix this case.
+                // Only applies to DefineBitsJPEG3; DefineBitsLossless does not seem to clamp.
+                let r = rgb[0].min(a);
+                let g = rgb[1].min(a);
+                let b = rgb[2].min(a);
+                [r, g, b, a]
+            })
+            .collect();
+        return Ok(Bitmap::new(
+            metadata.width.into(),
+            metadata.height.into(),
+            BitmapFormat::Rgba,
+            rgba,
+        ));
     }
 
     // No alpha.
@@ -230,6 +249,96 @@ fn decode_jpeg(jpeg_data: &[u8], alpha_data: Option<&[u8]>) -> Result<Bitmap, Er
     ))
 }
 
+/// Decompresses the alpha plane of a DefineBitsJPEG3/4 tag, which has one byte per pixel.
+///
+/// Some SWFs have truncated or overlong alpha planes. Rather than losing all of the
+/// transparency, whatever could be decompressed is used, and any pixels past that are opaque.
+fn decompress_alpha(data: &[u8], num_pixels: usize) -> Vec<u8> {
+    let mut alpha = Vec::with_capacity(num_pixels);
+    let decoder = flate2::bufread::ZlibDecoder::new(data);
+    // On error, `read_to_end` still keeps what was read before it.
+    if decoder
+        .take(num_pixels as u64)
+        .read_to_end(&mut alpha)
+        .is_err()
+    {
+        tracing::warn!("DefineBitsJPEG3 alpha data is corrupt");
+    }
+    if alpha.len() < num_pixels {
+        tracing::warn!(
+            "DefineBitsJPEG3 alpha data is too short ({} of {} pixels)",
+            alpha.len(),
+            num_pixels
+        );
+        alpha.resize(num_pixels, 255);
+    }
+    alpha
+}
+
+/// Smooths the edges between the 8x8 blocks of a decoded RGB JPEG, for the deblocking
+/// filter of DefineBitsJPEG4 tags.
+///
+/// Flash doesn't document its filter, and this hasn't been compared against its output, so it's
+/// only an approximation: a simple edge filter in the style of H.264's, which won't match Flash
+/// pixel for pixel.
+///
+/// Only small steps across block edges are smoothed, as larger ones are most likely edges in the
+/// image itself, rather than compression artifacts. Both the size of the steps that are smoothed,
+/// and how much they are, grow with `strength`.
+fn deblock(rgb: &mut [u8], width: usize, height: usize, strength: f32) {
+    const BLOCK_SIZE: usize = 8;
+
+    let strength = strength.clamp(0.0, 1.0);
+    let threshold = (strength * 32.0).round() as i32;
+    if threshold == 0 {
+        return;
+    }
+
+    // Filters the edge between `p0` and `q0`, with `p1` and `q1` being the pixels beyond them.
+    let mut filter = |p1: usize, p0: usize, q0: usize, q1: usize| {
+        let [p1_value, p0_value, q0_value, q1_value] = [p1, p0, q0, q1].map(|i| i32::from(rgb[i]));
+        if (p0_value - q0_value).abs() >= threshold
+            || (p1_value - p0_value).abs() >= threshold / 2 + 1
+            || (q1_value - q0_value).abs() >= threshold / 2 + 1
+        {
+            return;
+        }
+        let delta = ((q0_value - p0_value) * 4 + (p1_value - q1_value) + 4) >> 3;
+        let delta = (delta as f32 * strength).round() as i32;
+        rgb[p0] = (p0_value + delta).clamp(0, 255) as u8;
+        rgb[q0] = (q0_value - delta).clamp(0, 255) as u8;
+    };
+    let index = |x: usize, y: usize, channel: usize| (y * width + x) * 3 + channel;
+
+    // Vertical edges.
+    for y in 0..height {
+        for x in (BLOCK_SIZE..width.saturating_sub(1)).step_by(BLOCK_SIZE) {
+            for channel in 0..3 {
+                filter(
+                    index(x - 2, y, channel),
+                    index(x - 1, y, channel),
+                    index(x, y, channel),
+                    index(x + 1, y, channel),
+                );
+            }
+        }
+    }
+
+    // Horizontal edges.
+    for y in (BLOCK_SIZE..height.saturating_sub(1)).step_by(BLOCK_SIZE) {
+        for x in 0..width {
+            for channel in 0..3 {
+                filter(
+                    index(x, y - 2, channel),
+                    index(x, y - 1, channel),
+                    index(x, y, channel),
+                    index(x, y + 1, channel),
+                );
+            }
+        }
+    }
+}
+
 /// Decodes the bitmap data in DefineBitsLossless tag into RGBA.
 /// DefineBitsLossless is Zlib encoded pixel data (similar to PNG), possibly
 /// palletized.
@@ -378,13 +487,9 @@ fn decode_png(data: &[u8]) -> Result<Bitmap, Error> {
     Ok(Bitmap::new(info.width, info.height, format, data))
 }
 
-/// Decodes the bitmap data in DefineBitsLossless tag into RGBA.
-/// DefineBitsLossless is Zlib encoded pixel data (similar to PNG), possibly
-/// palletized.
+/// Decodes the first frame of GIF data into RGBA, at the size of that frame.
 fn decode_gif(data: &[u8]) -> Result<Bitmap, Error> {
-    let mut decode_options = gif::DecodeOptions::new();
-    decode_options.set_color_output(gif::ColorOutput::RGBA);
-    let mut reader = decode_options.read_info(data)?;
+    let mut reader = gif_reader(data)?;
     let frame = reader.read_next_frame()?.ok_or(Error::EmptyGif)?;
     // GIFs embedded in a DefineBitsJPEG tag will not have premultiplied alpha and need to be converted before sending to the renderer.
     let mut data = frame.buffer.to_vec();
@@ -398,6 +503,134 @@ fn decode_gif(data: &[u8]) -> Result<Bitmap, Error> {
     ))
 }
 
+/// A frame of an animated GIF.
+#[derive(Debug)]
+pub(crate) struct GifFrame {
+    /// The whole image as it is shown during this frame, with pre-multiplied alpha.
+    pub bitmap: Bitmap,
+
+    /// How long this frame is shown for.
+    pub delay: Duration,
+}
+
+/// Decodes every frame of GIF data.
+///
+/// Each frame only updates part of the image, so it is drawn over what the previous frame
+/// left behind, as decided by that frame's disposal method.
+///
+/// Nothing plays GIFs yet, as Flash only ever shows their first frame.
+#[allow(dead_code)]
+pub(crate) fn decode_gif_frames(data: &[u8]) -> Result<Vec<GifFrame>, Error> {
+    let mut reader = gif_reader(data)?;
+    let (width, height) = (reader.width(), reader.height());
+    let mut canvas = None;
+    let mut frames = vec![];
+    while let Some(frame) = reader.read_next_frame()? {
+        let canvas = canvas.get_or_insert_with(|| GifCanvas::new(width, height, frame));
+        canvas.draw(frame);
+        frames.push(GifFrame {
+            bitmap: canvas.to_bitmap(),
+            delay: Duration::from_millis(u64::from(frame.delay) * 10),
+        });
+        canvas.dispose(frame);
+    }
+
+    if frames.is_empty() {
+        return Err(Error::EmptyGif);
+    }
+    Ok(frames)
+}
+
+fn gif_reader(data: &[u8]) -> Result<gif::Decoder<&[u8]>, Error> {
+    let mut decode_options = gif::DecodeOptions::new();
+    decode_options.set_color_output(gif::ColorOutput::RGBA);
+    Ok(decode_options.read_info(data)?)
+}
+
+/// The image that the frames of a GIF are drawn onto, in straight RGBA.
+struct GifCanvas {
+    width: usize,
+    height: usize,
+    pixels: Vec<u8>,
+
+    /// What to restore once a frame with `DisposalMethod::Previous` is done.
+    previous: Option<Vec<u8>>,
+}
+
+impl GifCanvas {
+    /// Some GIFs declare a smaller image than their first frame, so the canvas grows to fit it.
+    fn new(width: u16, height: u16, first_frame: &gif::Frame) -> Self {
+        let width = width.max(first_frame.left.saturating_add(first_frame.width));
+        let height = height.max(first_frame.top.saturating_add(first_frame.height));
+        let (width, height) = (usize::from(width), usize::from(height));
+        Self {
+            width,
+            height,
+            pixels: vec![0; width * height * 4],
+            previous: None,
+        }
+    }
+
+    fn draw(&mut self, frame: &gif::Frame) {
+        if frame.dispose == gif::DisposalMethod::Previous {
+            self.previous = Some(self.pixels.clone());
+        }
+        self.blit(frame, false);
+    }
+
+    fn dispose(&mut self, frame: &gif::Frame) {
+        match frame.dispose {
+            // Like browsers, the background is transparent rather than the background color.
+            gif::DisposalMethod::Background => self.blit(frame, true),
+            gif::DisposalMethod::Previous => {
+                if let Some(previous) = self.previous.take() {
+                    self.pixels = previous;
+                }
+            }
+            gif::DisposalMethod::Keep | gif::DisposalMethod::Any => {}
+        }
+    }
+
+    /// Draws `frame` over its area, or clears that area if `clear` is set.
+    fn blit(&mut self, frame: &gif::Frame, clear: bool) {
+        let left = usize::from(frame.left);
+        let top = usize::from(frame.top);
+        let frame_width = usize::from(frame.width);
+        let width = frame_width.min(self.width.saturating_sub(left));
+        let height = usize::from(frame.height).min(self.height.saturating_sub(top));
+
+        for y in 0..height {
+            let start = ((top + y) * self.width + left) * 4;
+            let row = &mut self.pixels[start..start + width * 4];
+            if clear {
+                row.fill(0);
+                continue;
+            }
+
+            let source_start = y * frame_width * 4;
+            let source = &frame.buffer[source_start..source_start + width * 4];
+            for (dest, source) in row.chunks_exact_mut(4).zip(source.chunks_exact(4)) {
+                // Transparent pixels let what's underneath show through.
+                if source[3] != 0 {
+                    dest.copy_from_slice(source);
+                }
+            }
+        }
+    }
+
+    fn to_bitmap(&self) -> Bitmap {
+        // GIFs don't have premultiplied alpha and need to be converted before sending to the renderer.
+        let mut data = self.pixels.clone();
+        premultiply_alpha_rgba(&mut data);
+        Bitmap::new(
+            self.width as u32,
+            self.height as u32,
+            BitmapFormat::Rgba,
+            data,
+        )
+    }
+}
+
 /// Converts standard RBGA to premultiplied alpha.
 fn premultiply_alpha_rgba(rgba: &mut [u8]) {
     rgba.chunks_exact_mut(4).for_each(|rgba| {
@@ -431,3 +664,147 @@ fn decompress_zlib(data: &[u8]) -> Result<Vec<u8>, Error> {
     out_data.shrink_to_fit();
     Ok(out_data)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{deblock, decode_define_bits_jpeg, decode_gif_frames, decompress_alpha};
+    use crate::error::Error;
+    use std::borrow::Cow;
+    use std::io::Write;
+
+    /// A 16x1 RGB image whose left block is `left` and whose right block is `right`.
+    fn two_blocks(left: u8, right: u8) -> Vec<u8> {
+        (0..16)
+            .flat_map(|x| [if x < 8 { left } else { right }; 3])
+            .collect()
+    }
+
+    fn reds(rgb: &[u8]) -> Vec<u8> {
+        rgb.chunks_exact(3).map(|pixel| pixel[0]).collect()
+    }
+
+    fn deblocked_reds(left: u8, right: u8, strength: f32) -> Vec<u8> {
+        let mut rgb = two_blocks(left, right);
+        deblock(&mut rgb, 16, 1, strength);
+        reds(&rgb)
+    }
+
+    fn zlib(data: &[u8]) -> Vec<u8> {
+        let mut encoder = flate2::write::ZlibEncoder::new(vec![], flate2::Compression::default());
+        encoder.write_all(data).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    #[test]
+    fn gif_is_decoded_at_the_size_of_its_first_frame() {
+        let palette = [255, 0, 0, 0, 255, 0];
+        let mut data = vec![];
+        {
+            let mut encoder = gif::Encoder::new(&mut data, 4, 4, &palette).unwrap();
+            encoder
+                .write_frame(&gif::Frame {
+                    left: 1,
+                    top: 2,
+                    width: 2,
+                    height: 1,
+                    buffer: Cow::Borrowed(&[0, 0]),
+                    ..Default::default()
+                })
+                .unwrap();
+        }
+
+        let bitmap = decode_define_bits_jpeg(&data, None, 0.0).unwrap();
+        assert_eq!((bitmap.width(), bitmap.height()), (2, 1));
+        assert_eq!(bitmap.data(), [255, 0, 0, 255, 255, 0, 0, 255]);
+    }
+
+    #[test]
+    fn jpeg_xr_is_rejected() {
+        let data = [0x49, 0x49, 0xbc, 0x01, 0x08, 0x00, 0x00, 0x00];
+        assert!(matches!(
+            decode_define_bits_jpeg(&data, None, 0.0),
+            Err(Error::UnsupportedJpegXr)
+        ));
+    }
+
+    #[test]
+    fn gif_disposal() {
+        let palette = [255, 0, 0, 0, 255, 0];
+        let mut data = vec![];
+        {
+            let mut encoder = gif::Encoder::new(&mut data, 2, 1, &palette).unwrap();
+            // A red frame covering the whole image, cleared once it's done.
+            encoder
+                .write_frame(&gif::Frame {
+                    width: 2,
+                    height: 1,
+                    delay: 5,
+                    dispose: gif::DisposalMethod::Background,
+                    buffer: Cow::Borrowed(&[0, 0]),
+                    ..Default::default()
+                })
+                .unwrap();
+            // A green pixel on the right.
+            encoder
+                .write_frame(&gif::Frame {
+                    left: 1,
+                    width: 1,
+                    height: 1,
+                    buffer: Cow::Borrowed(&[1]),
+                    ..Default::default()
+                })
+                .unwrap();
+        }
+
+        let frames = decode_gif_frames(&data).unwrap();
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0].delay.as_millis(), 50);
+        assert_eq!(frames[0].bitmap.data(), [255, 0, 0, 255, 255, 0, 0, 255]);
+        assert_eq!(frames[1].bitmap.data(), [0, 0, 0, 0, 0, 255, 0, 255]);
+    }
+
+    #[test]
+    fn small_steps_between_blocks_are_smoothed() {
+        let mut expected = vec![100; 7];
+        expected.extend([102, 102]);
+        expected.extend([104; 7]);
+        assert_eq!(deblocked_reds(100, 104, 1.0), expected);
+
+        // A weaker filter moves the pixels at the edge less.
+        expected[7..9].copy_from_slice(&[101, 103]);
+        assert_eq!(deblocked_reds(100, 104, 0.5), expected);
+    }
+
+    #[test]
+    fn large_steps_and_zero_strength_are_left_alone() {
+        assert_eq!(deblocked_reds(100, 200, 1.0), reds(&two_blocks(100, 200)));
+        assert_eq!(deblocked_reds(100, 104, 0.0), reds(&two_blocks(100, 104)));
+    }
+
+    #[test]
+    fn horizontal_block_edges_are_smoothed() {
+        let mut rgb: Vec<u8> = (0..16)
+            .flat_map(|y| [if y < 8 { 100 } else { 104 }; 16 * 3])
+            .collect();
+        deblock(&mut rgb, 16, 16, 1.0);
+        let column: Vec<_> = (0..16).map(|y| rgb[y * 16 * 3]).collect();
+        assert_eq!(column[6..10], [100, 102, 102, 104]);
+    }
+
+    #[test]
+    fn damaged_alpha_planes_are_padded_with_opaque_pixels() {
+        assert_eq!(decompress_alpha(&zlib(&[0, 128]), 4), [0, 128, 255, 255]);
+        assert_eq!(
+            decompress_alpha(&zlib(&[0, 128, 64, 32, 16]), 4),
+            [0, 128, 64, 32]
+        );
+
+        // Everything that was decompressed before the damage is kept.
+        let mut truncated = zlib(&[7; 64]);
+        truncated.truncate(truncated.len() - 4);
+        let alpha = decompress_alpha(&truncated, 64);
+        assert_eq!(alpha.len(), 64);
+        assert!(alpha.iter().all(|&a| a == 7 || a == 255));
+        assert_eq!(decompress_alpha(b"not zlib", 2), [255, 255]);
+    }
+}
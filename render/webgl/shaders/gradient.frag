@@ -22,8 +22,6 @@ uniform int u_interpolation;
 varying vec2 frag_uv;
 
 vec4 interpolate(float t, float ratio1, float ratio2, vec4 color1, vec4 color2) {
-    color1 = clamp(mult_color * color1 + add_color, 0.0, 1.0);
-    color2 = clamp(mult_color * color2 + add_color, 0.0, 1.0);
     float a = (t - ratio1) / (ratio2 - ratio1);
     return mix(color1, color2, a);
 }
@@ -50,8 +48,13 @@ void main() {
         vec2 uv = frag_uv * 2.0 - 1.0;
         vec2 d = vec2(u_focal_point, 0.0) - uv;
         float l = length(d);
-        d /= l;
-        t = l / (sqrt(1.0 -  u_focal_point*u_focal_point*d.y*d.y) + u_focal_point*d.x);
+        if( l == 0.0 ) {
+            // Exactly at the focal point.
+            t = 0.0;
+        } else {
+            d /= l;
+            t = l / (sqrt(1.0 -  u_focal_point*u_focal_point*d.y*d.y) + u_focal_point*d.x);
+        }
     }
     if( u_repeat_mode == 0 )
     {
@@ -82,7 +85,7 @@ void main() {
     // We'd probably be better off passing in the gradient as a texture and sampling from there.
     vec4 color;
     if( t <= u_ratios[0] ) {
-        color = clamp(mult_color * u_colors[0] + add_color, 0.0, 1.0);
+        color = u_colors[0];
     } else if( t <= u_ratios[1] ) {
         color = interpolate(t, u_ratios[0], u_ratios[1], u_colors[0], u_colors[1]);
     } else if( t <= u_ratios[2] ) {
@@ -112,13 +115,16 @@ void main() {
     } else if( t <= u_ratios[14] ) {
         color = interpolate(t, u_ratios[13], u_ratios[14], u_colors[13], u_colors[14]);
     } else {
-        color = clamp(mult_color * u_colors[14] + add_color, 0.0, 1.0);
+        color = u_colors[14];
     }
 
     if( u_interpolation != 0 ) {
         color = vec4(linear_to_srgb(vec3(color)), color.a);
     }
 
+    // The color transform applies to the final sRGB color.
+    color = clamp(mult_color * color + add_color, 0.0, 1.0);
+
     float alpha = clamp(color.a, 0.0, 1.0);
     gl_FragColor = vec4(color.rgb * alpha, alpha);
 }
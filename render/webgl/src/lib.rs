@@ -121,6 +121,7 @@ pub struct WebGlRenderBackend {
     // The frame buffers used for resolving MSAA.
     msaa_buffers: Option<MsaaBuffers>,
     msaa_sample_count: u32,
+    quality: StageQuality,
 
     color_program: ShaderProgram,
     bitmap_program: ShaderProgram,
@@ -292,6 +293,7 @@ impl WebGlRenderBackend {
 
             msaa_buffers: None,
             msaa_sample_count,
+            quality: StageQuality::default(),
 
             color_program,
             gradient_program,
@@ -1120,7 +1122,9 @@ impl RenderBackend for WebGlRenderBackend {
         "webgl"
     }
 
-    fn set_quality(&mut self, _quality: StageQuality) {}
+    fn set_quality(&mut self, quality: StageQuality) {
+        self.quality = quality;
+    }
 
     fn compile_pixelbender_shader(
         &mut self,
@@ -1385,7 +1389,7 @@ impl CommandHandler for WebGlRenderBackend {
                     program.uniform1i(&self.gl, ShaderUniform::BitmapTexture, 0);
 
                     // Set texture parameters.
-                    let filter = if bitmap.is_smoothed {
+                    let filter = if bitmap.is_smoothed && self.quality.smooths_bitmaps() {
                         Gl::LINEAR as i32
                     } else {
                         Gl::NEAREST as i32
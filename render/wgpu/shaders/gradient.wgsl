@@ -60,6 +60,10 @@ fn find_t(uv: vec2<f32>) -> f32 {
         let uv = uv * 2.0 - 1.0;
         var d: vec2<f32> = vec2<f32>(gradient.focal_point, 0.0) - uv;
         let l = length(d);
+        if (l == 0.0) {
+            // Exactly at the focal point.
+            return 0.0;
+        }
         d = d / l;
         return l / (sqrt(1.0 - gradient.focal_point * gradient.focal_point * d.y * d.y) + gradient.focal_point * d.x);
     }
@@ -94,7 +98,8 @@ fn main_fragment(in: VertexOutput) -> @location(0) vec4<f32> {
 
     var color = textureSample(texture, texture_sampler, vec2<f32>(t, 0.0));
     if( gradient.interpolation != 0 ) {
-        color = common::linear_to_srgb(color);
+        // The gradient texture isn't premultiplied, so convert the color channels alone.
+        color = vec4<f32>(common::linear_to_srgb(vec4<f32>(color.rgb, 1.0)).rgb, color.a);
     }
     let out = saturate(color * colorTransforms.mult_color + colorTransforms.add_color);
     let alpha = saturate(out.a);
@@ -1075,8 +1075,11 @@ pub async fn request_adapter_and_device(
     Ok((adapter, device, queue))
 }
 
+/// Requests a device from `adapter` with everything Ruffle can make use of.
+///
+/// Only needed by callers picking an adapter themselves; `request_adapter_and_device` does both.
 // We try to request the highest limits we can get away with
-async fn request_device(
+pub async fn request_device(
     adapter: &wgpu::Adapter,
     trace_path: Option<&Path>,
 ) -> Result<(wgpu::Device, wgpu::Queue), wgpu::RequestDeviceError> {
@@ -310,7 +310,7 @@ impl Texture {
                 samplers.get_sampler(false, smoothed),
                 &quad.texture_transforms,
                 0 as wgpu::BufferAddress,
-                self.texture.create_view(&Default::default()),
+                &self.texture.create_view(&Default::default()),
                 create_debug_label!("Bitmap {:?} bind group (smoothed: {})", handle.0, smoothed),
             )
         })
@@ -330,11 +330,25 @@ impl PendingDrawType {
                         .get_sampler(is_repeating, is_smoothed),
                     uniform_buffer,
                     texture_transforms_index,
-                    texture_view,
-                    bind_group_label,
+                    &texture_view,
+                    bind_group_label.clone(),
                 );
-
-                DrawType::Bitmap { binds }
+                let unsmoothed_binds = is_smoothed.then(|| {
+                    BitmapBinds::new(
+                        &descriptors.device,
+                        &descriptors.bind_layouts.bitmap,
+                        descriptors.bitmap_samplers.get_sampler(is_repeating, false),
+                        uniform_buffer,
+                        texture_transforms_index,
+                        &texture_view,
+                        bind_group_label.map(|label| format!("{label} (unsmoothed)")),
+                    )
+                });
+
+                DrawType::Bitmap {
+                    binds,
+                    unsmoothed_binds,
+                }
             }
         }
     }
@@ -344,8 +358,15 @@ impl PendingDrawType {
 #[derive(Debug)]
 pub enum DrawType {
     Color,
-    Gradient { bind_group: wgpu::BindGroup },
-    Bitmap { binds: BitmapBinds },
+    Gradient {
+        bind_group: wgpu::BindGroup,
+    },
+    Bitmap {
+        binds: BitmapBinds,
+        /// Used instead of `binds` when bitmaps can't be smoothed at the current quality.
+        /// Only present if the fill is smoothed.
+        unsmoothed_binds: Option<BitmapBinds>,
+    },
 }
 
 #[derive(Debug)]
@@ -360,7 +381,7 @@ impl BitmapBinds {
         sampler: &wgpu::Sampler,
         uniform_buffer: &wgpu::Buffer,
         texture_transforms: wgpu::BufferAddress,
-        texture_view: wgpu::TextureView,
+        texture_view: &wgpu::TextureView,
         label: Option<String>,
     ) -> Self {
         let bind_group =
@@ -379,7 +400,7 @@ impl BitmapBinds {
                     },
                     wgpu::BindGroupEntry {
                         binding: 1,
-                        resource: wgpu::BindingResource::TextureView(&texture_view),
+                        resource: wgpu::BindingResource::TextureView(texture_view),
                     },
                     wgpu::BindGroupEntry {
                         binding: 2,
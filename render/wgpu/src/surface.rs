@@ -174,6 +174,7 @@ impl Surface {
                     let mut renderer = CommandRenderer::new(
                         &self.pipelines,
                         descriptors,
+                        self.quality,
                         uniform_buffers,
                         color_buffers,
                         uniform_encoder,
@@ -23,6 +23,7 @@ use super::target::PoolOrArcTexture;
 pub struct CommandRenderer<'pass, 'frame: 'pass, 'global: 'frame> {
     pipelines: &'frame Pipelines,
     descriptors: &'global Descriptors,
+    quality: StageQuality,
     num_masks: u32,
     mask_state: MaskState,
     render_pass: wgpu::RenderPass<'pass>,
@@ -37,6 +38,7 @@ impl<'pass, 'frame: 'pass, 'global: 'frame> CommandRenderer<'pass, 'frame, 'glob
     pub fn new(
         pipelines: &'frame Pipelines,
         descriptors: &'global Descriptors,
+        quality: StageQuality,
         uniform_buffers: &'frame mut UniformBuffer<'global, Transforms>,
         color_buffers: &'frame mut UniformBuffer<'global, ColorAdjustments>,
         uniform_encoder: &'frame mut wgpu::CommandEncoder,
@@ -51,6 +53,7 @@ impl<'pass, 'frame: 'pass, 'global: 'frame> CommandRenderer<'pass, 'frame, 'glob
             mask_state,
             render_pass,
             descriptors,
+            quality,
             uniform_buffers,
             color_buffers,
             uniform_encoder,
@@ -325,7 +328,16 @@ impl<'pass, 'frame: 'pass, 'global: 'frame> CommandRenderer<'pass, 'frame, 'glob
                 DrawType::Gradient { bind_group, .. } => {
                     self.prep_gradient(bind_group);
                 }
-                DrawType::Bitmap { binds, .. } => {
+                DrawType::Bitmap {
+                    binds,
+                    unsmoothed_binds,
+                } => {
+                    let binds = match unsmoothed_binds {
+                        Some(unsmoothed_binds) if !self.quality.smooths_bitmaps() => {
+                            unsmoothed_binds
+                        }
+                        _ => binds,
+                    };
                     self.prep_bitmap(&binds.bind_group, TrivialBlend::Normal, false);
                 }
             }
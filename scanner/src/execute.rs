@@ -5,8 +5,8 @@ use crate::file_results::{AvmType, FileResults, Step};
 use crate::logging::{ScanLogBackend, ThreadLocalScanLogger, LOCAL_LOGGER};
 use ruffle_core::backend::navigator::{NullExecutor, NullNavigatorBackend};
 use ruffle_core::limits::ExecutionLimit;
-use ruffle_core::swf::{decompress_swf, parse_swf};
-use ruffle_core::tag_utils::SwfMovie;
+use ruffle_core::swf::decompress_swf;
+use ruffle_core::tag_utils::{SwfMovie, SwfMovieStats};
 use ruffle_core::PlayerBuilder;
 use sha2::{Digest, Sha256};
 use std::io::{stdout, Write};
@@ -33,6 +33,19 @@ fn execute_swf(file: &Path) {
     //executor.poll_all().unwrap();
 }
 
+/// Lists the tags of a movie as `Name=count` pairs, most common first.
+fn format_tag_counts(stats: &SwfMovieStats) -> String {
+    let mut counts: Vec<_> = stats.tag_counts.iter().collect();
+    counts.sort_by(|(a_name, a_count), (b_name, b_count)| {
+        b_count.cmp(a_count).then_with(|| a_name.cmp(b_name))
+    });
+    counts
+        .iter()
+        .map(|(name, count)| format!("{name}={count}"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 fn checkpoint<W: Write>(
     file_result: &mut FileResults,
     start: &Instant,
@@ -109,31 +122,27 @@ pub fn execute_report_main(execute_report_opt: ExecuteReportOpt) -> Result<(), s
     checkpoint(&mut file_result, &start, &mut writer)?;
     file_result.progress = Step::Parse;
 
-    match catch_unwind(|| parse_swf(&swf_buf)) {
-        Ok(swf) => match swf {
-            Ok(swf) => {
-                let stage_width = swf.header.stage_size().width().to_pixels();
-                let stage_height = swf.header.stage_size().height().to_pixels();
-
-                file_result.uncompressed_len = Some(swf.header.uncompressed_len());
-                file_result.compression = Some(swf.header.compression().into());
-                file_result.version = Some(swf.header.version());
-                file_result.stage_size = Some(format!("{stage_width}x{stage_height}"));
-                file_result.frame_rate = Some(swf.header.frame_rate().into());
-                file_result.num_frames = Some(swf.header.num_frames());
-                file_result.use_direct_blit = Some(swf.header.use_direct_blit());
-                file_result.use_gpu = Some(swf.header.use_gpu());
-                file_result.use_network_sandbox = Some(swf.header.use_network_sandbox());
-                file_result.vm_type = Some(match swf.header.is_action_script_3() {
-                    true => AvmType::Avm2,
-                    false => AvmType::Avm1,
-                });
-            }
-            Err(e) => {
-                file_result.error = Some(format!("Parse error: {e}"));
-                checkpoint(&mut file_result, &start, &mut writer)?;
-            }
-        },
+    match catch_unwind(|| SwfMovieStats::from_swf_buf(&swf_buf, data.len())) {
+        Ok(stats) => {
+            let header = &swf_buf.header;
+            let stage_width = stats.stage_size.width().to_pixels();
+            let stage_height = stats.stage_size.height().to_pixels();
+
+            file_result.uncompressed_len = Some(stats.uncompressed_len);
+            file_result.compression = Some(stats.compression.into());
+            file_result.version = Some(stats.version);
+            file_result.stage_size = Some(format!("{stage_width}x{stage_height}"));
+            file_result.frame_rate = Some(stats.frame_rate.into());
+            file_result.num_frames = Some(stats.num_frames);
+            file_result.use_direct_blit = Some(header.use_direct_blit());
+            file_result.use_gpu = Some(header.use_gpu());
+            file_result.use_network_sandbox = Some(header.use_network_sandbox());
+            file_result.vm_type = Some(match stats.is_action_script_3 {
+                true => AvmType::Avm2,
+                false => AvmType::Avm1,
+            });
+            file_result.tag_counts = Some(format_tag_counts(&stats));
+        }
         Err(e) => match e.downcast::<String>() {
             Ok(e) => {
                 file_result.error = Some(format!("PANIC: {e}"));
@@ -126,6 +126,10 @@ pub struct FileResults {
     /// The AVM type of the movie.
     #[serde(rename = "AVM Version")]
     pub vm_type: Option<AvmType>,
+
+    /// How many of each tag the SWF contains, most common first, as `Name=count` pairs.
+    #[serde(rename = "Tag Counts")]
+    pub tag_counts: Option<String>,
 }
 
 impl Default for FileResults {
@@ -153,6 +157,7 @@ impl FileResults {
             use_gpu: None,
             use_network_sandbox: None,
             vm_type: None,
+            tag_counts: None,
         }
     }
 }
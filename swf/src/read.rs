@@ -1,7 +1,7 @@
 use crate::extensions::ReadSwfExt;
 use crate::{
     error::{Error, Result},
-    string::{Encoding, SwfStr},
+    string::{Encoding, SwfStr, UTF_8},
     tag_code::TagCode,
     types::*,
 };
@@ -104,7 +104,7 @@ pub fn decompress_swf<'a, R: Read + 'a>(mut input: R) -> Result<SwfBuf> {
             }
             // Uncompressed length includes the 4-byte header and 4-byte uncompressed length itself,
             // subtract it here.
-            make_lzma_reader(input, uncompressed_len - 8)?
+            make_lzma_reader(input, uncompressed_len.saturating_sub(8))?
         }
     };
 
@@ -151,13 +151,18 @@ pub fn decompress_swf<'a, R: Read + 'a>(mut input: R) -> Result<SwfBuf> {
 
     // In most SWFs, SetBackgroundColor will be the second or third tag after FileAttributes + Metadata.
     // It's possible for the SetBackgroundColor tag to be missing or appear later in wacky SWFs, so let's
-    // return `None` in this case.
+    // return `None` in this case. The same goes for Metadata, which comes right after FileAttributes.
     let mut background_color = None;
+    let mut metadata = None;
     for _ in 0..2 {
-        if let Ok(Tag::SetBackgroundColor(color)) = tag {
-            background_color = Some(color);
-            break;
-        };
+        match tag {
+            Ok(Tag::SetBackgroundColor(color)) => {
+                background_color = Some(color);
+                break;
+            }
+            Ok(Tag::Metadata(xmp)) => metadata = Some(xmp.to_string_lossy(UTF_8)),
+            _ => (),
+        }
         tag = reader.read_tag();
     }
 
@@ -166,6 +171,7 @@ pub fn decompress_swf<'a, R: Read + 'a>(mut input: R) -> Result<SwfBuf> {
             header,
             file_attributes,
             background_color,
+            metadata,
             uncompressed_len: uncompressed_len as i32,
         },
         data,
@@ -219,9 +225,20 @@ fn make_lzma_reader<'a, R: Read + 'a>(
     // Read compressed length (ignored)
     let _ = input.read_u32::<LittleEndian>()?;
 
+    // Some encoders write a bogus dictionary size, such as 0 or 0xFFFFFFFF.
+    // Matches can never reach further back than the start of the output, so clamp it
+    // between the LZMA minimum and the uncompressed length.
+    let mut properties = [0u8; 5];
+    input.read_exact(&mut properties)?;
+    let dict_size =
+        u32::from_le_bytes([properties[1], properties[2], properties[3], properties[4]]);
+    let dict_size = dict_size.clamp(4096, uncompressed_length.max(4096));
+    properties[1..].copy_from_slice(&dict_size.to_le_bytes());
+    let input = io::Cursor::new(properties).chain(input);
+
     // TODO: Switch to lzma-rs streaming API when stable.
     let mut output = Vec::with_capacity(uncompressed_length as usize);
-    lzma_decompress_with_options(
+    let result = lzma_decompress_with_options(
         &mut io::BufReader::new(input),
         &mut output,
         &Options {
@@ -229,8 +246,14 @@ fn make_lzma_reader<'a, R: Read + 'a>(
             allow_incomplete: true,
             memlimit: None,
         },
-    )
-    .map_err(|_| Error::invalid_data("Unable to decompress LZMA SWF."))?;
+    );
+    if let Err(e) = result {
+        // As with zlib, try to parse whatever was decompressed before the error.
+        if output.is_empty() {
+            return Err(Error::invalid_data("Unable to decompress LZMA SWF."));
+        }
+        log::error!("Error decompressing LZMA SWF: {}", e);
+    }
 
     Ok(Box::new(io::Cursor::new(output)))
 }
@@ -460,8 +483,9 @@ impl<'a> Reader<'a> {
                 Tag::DefineVideoStream(tag_reader.read_define_video_stream()?)
             }
             TagCode::EnableTelemetry => {
+                // Some tools pad the tag without writing a password hash.
                 tag_reader.read_u16()?; // Reserved
-                let password_hash = if length > 2 {
+                let password_hash = if length >= 34 {
                     tag_reader.read_slice(32)?
                 } else {
                     &[]
@@ -625,7 +649,12 @@ impl<'a> Reader<'a> {
             TagCode::NameCharacter => Tag::NameCharacter(tag_reader.read_name_character()?),
         };
 
-        if !tag_reader.input.is_empty() {
+        // Informational tags are padded by some tools, and we never rely on their contents.
+        let is_informational = matches!(
+            tag_code,
+            TagCode::ProductInfo | TagCode::DebugId | TagCode::EnableTelemetry
+        );
+        if !tag_reader.input.is_empty() && !is_informational {
             // There should be no data remaining in the tag if we read it correctly.
             // If there is data remaining, the most likely scenario is we screwed up parsing.
             // But sometimes tools will export SWF tags that are larger than they should be.
@@ -1921,6 +1950,7 @@ impl<'a> Reader<'a> {
                 let id = self.read_u16()?;
                 PlaceObjectAction::Replace(id)
             }
+            (false, false) if class_name.is_some() => PlaceObjectAction::PlaceClass,
             _ => return Err(Error::invalid_data("Invalid PlaceObject type")),
         };
         let matrix = if flags.contains(PlaceFlag::HAS_MATRIX) {
@@ -2646,6 +2676,26 @@ pub mod tests {
         }
     }
 
+    #[test]
+    #[cfg(feature = "lzma")]
+    fn read_lzma_dictionary_sizes() {
+        let header = Header {
+            compression: Compression::Lzma,
+            num_frames: 1,
+            ..Header::default_with_swf_version(13)
+        };
+        let mut data = vec![];
+        crate::write::write_swf(&header, &[Tag::ShowFrame], &mut data).unwrap();
+
+        // The dictionary size follows the 12 byte header and the LZMA properties byte.
+        for dict_size in [0u32, 1, 0xFFFF_FFFF] {
+            data[13..17].copy_from_slice(&dict_size.to_le_bytes());
+            let swf_buf = decompress_swf(&data[..]).unwrap();
+            assert_eq!(swf_buf.header.compression(), Compression::Lzma);
+            assert_eq!(swf_buf.header.num_frames(), 1);
+        }
+    }
+
     #[test]
     fn read_invalid_swf() {
         let junk = [0u8; 128];
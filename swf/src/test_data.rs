@@ -2141,6 +2141,41 @@ pub fn tag_tests() -> Vec<TagTestData> {
             })),
             read_tag_bytes_from_file("tests/swfs/PlaceObject4.swf", TagCode::PlaceObject4),
         ),
+        // PlaceObject3 placing an instance of a class, with no character ID.
+        (
+            9,
+            Tag::PlaceObject(Box::new(PlaceObject {
+                version: 3,
+                action: PlaceObjectAction::PlaceClass,
+                depth: 1,
+                matrix: None,
+                color_transform: None,
+                ratio: None,
+                name: None,
+                clip_depth: None,
+                class_name: Some("Hat".into()),
+                filters: None,
+                background_color: None,
+                blend_mode: None,
+                clip_actions: None,
+                has_image: false,
+                is_bitmap_cached: None,
+                is_visible: None,
+                amf_data: None,
+            })),
+            vec![
+                0b10_001000,
+                0b00010001,
+                0b00000000,
+                0b00001000,
+                1,
+                0,
+                b'H',
+                b'a',
+                b't',
+                0,
+            ],
+        ),
         (
             2,
             Tag::Protect(None),
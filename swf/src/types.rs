@@ -86,7 +86,7 @@ impl Header {
 
 /// The extended metadata of an SWF file.
 ///
-/// This includes the SWF header data as well as metdata from the FileAttributes and
+/// This includes the SWF header data as well as metdata from the FileAttributes, Metadata and
 /// SetBackgroundColor tags.
 ///
 /// This metadata may not reflect the actual data inside a malformed SWF; for example,
@@ -97,6 +97,7 @@ pub struct HeaderExt {
     pub(crate) header: Header,
     pub(crate) file_attributes: FileAttributes,
     pub(crate) background_color: Option<SetBackgroundColor>,
+    pub(crate) metadata: Option<String>,
     pub(crate) uncompressed_len: i32,
 }
 
@@ -108,6 +109,7 @@ impl HeaderExt {
             header: Header::default_with_swf_version(version),
             file_attributes: Default::default(),
             background_color: None,
+            metadata: None,
             uncompressed_len: 0,
         }
     }
@@ -119,6 +121,7 @@ impl HeaderExt {
             header: Header::default_with_swf_version(0),
             file_attributes: Default::default(),
             background_color: None,
+            metadata: None,
             uncompressed_len: -1,
         }
     }
@@ -136,6 +139,7 @@ impl HeaderExt {
             header,
             file_attributes: Default::default(),
             background_color: None,
+            metadata: None,
             uncompressed_len: length,
         }
     }
@@ -162,16 +166,37 @@ impl HeaderExt {
 
     /// Whether this SWF contains XMP metadata in a Metadata tag.
     #[inline]
-    pub fn has_metdata(&self) -> bool {
+    pub fn has_metadata(&self) -> bool {
         self.file_attributes.contains(FileAttributes::HAS_METADATA)
     }
 
+    /// Whether this SWF contains XMP metadata in a Metadata tag.
+    #[deprecated(note = "Renamed to `has_metadata`")]
+    #[inline]
+    pub fn has_metdata(&self) -> bool {
+        self.has_metadata()
+    }
+
+    /// The XMP metadata of the SWF from the Metadata tag.
+    ///
+    /// `None` will be returned if the Metadata tag was not found at the start of the SWF.
+    #[inline]
+    pub fn metadata(&self) -> Option<&str> {
+        self.metadata.as_deref()
+    }
+
     /// Returns the basic SWF header.
     #[inline]
     pub fn swf_header(&self) -> &Header {
         &self.header
     }
 
+    /// Returns the basic SWF header for modification, e.g. before writing the SWF again.
+    #[inline]
+    pub fn swf_header_mut(&mut self) -> &mut Header {
+        &mut self.header
+    }
+
     /// Whether this SWF uses ActionScript 3.0 (AVM2).
     #[inline]
     pub fn is_action_script_3(&self) -> bool {
@@ -203,6 +228,12 @@ impl HeaderExt {
         self.uncompressed_len
     }
 
+    /// Sets the length of the SWF after decompression.
+    #[inline]
+    pub fn set_uncompressed_len(&mut self, uncompressed_len: i32) {
+        self.uncompressed_len = uncompressed_len;
+    }
+
     /// Whether this SWF requests hardware acceleration to blit to the screen.
     #[inline]
     pub fn use_direct_blit(&self) -> bool {
@@ -356,6 +387,10 @@ pub enum PlaceObjectAction {
     Place(CharacterId),
     Modify,
     Replace(CharacterId),
+
+    /// Places an instance of the class named by `class_name`, rather than of a character.
+    /// Only in PlaceObject3 and later.
+    PlaceClass,
 }
 
 #[derive(Clone, Debug, PartialEq)]
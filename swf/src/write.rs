@@ -39,6 +39,13 @@ pub fn write_swf<W: Write>(header: &Header, tags: &[Tag<'_>], output: W) -> Resu
     write_swf_raw_tags(header, &swf_body, output)
 }
 
+/// Writes a tag list, followed by an `End` tag, without any SWF header.
+/// The result can be passed to `write_swf_raw_tags`.
+pub fn write_tag_list<W: Write>(tags: &[Tag<'_>], version: u8, output: W) -> Result<()> {
+    let mut writer = Writer::new(output, version);
+    writer.write_tag_list(tags)
+}
+
 /// Writes a SWF to the output stream, where the tag list has already been serialized to bytes.
 /// This still appends other header information such as stage size.
 pub fn write_swf_raw_tags<W: Write>(header: &Header, tags: &[u8], mut output: W) -> Result<()> {
@@ -1721,7 +1728,7 @@ impl<W: Write> Writer<W> {
             match place_object.action {
                 PlaceObjectAction::Place(character_id)
                 | PlaceObjectAction::Replace(character_id) => writer.write_u16(character_id)?,
-                PlaceObjectAction::Modify => (),
+                PlaceObjectAction::Modify | PlaceObjectAction::PlaceClass => (),
             }
             if let Some(ref matrix) = place_object.matrix {
                 writer.write_matrix(matrix)?;
@@ -19,6 +19,16 @@ pub enum MouseButton {
     Right,
 }
 
+/// How far the mouse wheel is scrolled.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone)]
+pub enum MouseWheelDelta {
+    /// Scroll by a number of lines, as most mouse wheels do.
+    Lines(f64),
+
+    /// Scroll by a number of pixels, as touchpads and high-resolution wheels do.
+    Pixels(f64),
+}
+
 /// Control inputs to a text field
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TextControlCode {
@@ -62,6 +72,9 @@ pub enum AutomatedEvent {
         btn: MouseButton,
     },
 
+    /// Scroll the mouse wheel at the current cursor position.
+    MouseWheel { delta: MouseWheelDelta },
+
     /// Press a key
     KeyDown { key_code: u8 },
 
@@ -70,4 +83,15 @@ pub enum AutomatedEvent {
 
     /// Input a control character code
     TextControl { code: TextControlCode },
+
+    /// Move or resize the player's window, to the given outer bounds in pixels.
+    WindowBounds {
+        x: i32,
+        y: i32,
+        width: u32,
+        height: u32,
+    },
+
+    /// Focus or unfocus the player's window.
+    WindowFocus { focused: bool },
 }
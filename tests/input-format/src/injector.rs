@@ -82,9 +82,12 @@ impl InputInjector {
                 match event {
                     AutomatedEvent::Wait => break,
                     AutomatedEvent::MouseMove { .. }
+                    | AutomatedEvent::MouseWheel { .. }
                     | AutomatedEvent::KeyDown { .. }
                     | AutomatedEvent::TextInput { .. }
-                    | AutomatedEvent::TextControl { .. } => {}
+                    | AutomatedEvent::TextControl { .. }
+                    | AutomatedEvent::WindowBounds { .. }
+                    | AutomatedEvent::WindowFocus { .. } => {}
                     AutomatedEvent::MouseDown { btn, .. } => {
                         self.buttons |= (*btn).into();
                     }
@@ -1,5 +1,5 @@
 mod format;
 mod injector;
 
-pub use format::{AutomatedEvent, MouseButton, TextControlCode};
+pub use format::{AutomatedEvent, MouseButton, MouseWheelDelta, TextControlCode};
 pub use injector::{InputInjector, MouseButtons};
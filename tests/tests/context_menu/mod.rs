@@ -0,0 +1,51 @@
+use crate::set_logger;
+use crate::util::options::TestOptions;
+use crate::util::test::Test;
+use ruffle_core::events::MouseButton;
+use ruffle_core::PlayerEvent;
+use std::path::Path;
+
+pub fn context_menu_suppression_avm2() -> Result<(), libtest_mimic::Failed> {
+    set_logger();
+    // Right click over objects with and without listeners for the right mouse button,
+    // and check whether the frontend would be told to skip its context menu.
+    Test::from_options(
+        TestOptions {
+            num_ticks: Some(1),
+            ..Default::default()
+        },
+        Path::new("tests/swfs/avm2/context_menu_suppression/"),
+        "context_menu_suppression_avm2".to_string(),
+    )?
+    .run(
+        |_| Ok(()),
+        |player| {
+            let mut player = player.lock().unwrap();
+            for (name, x, y) in [
+                ("listener", 50.0, 50.0),
+                ("child", 250.0, 50.0),
+                ("plain", 450.0, 50.0),
+                ("middle", 50.0, 250.0),
+                ("the stage", 450.0, 300.0),
+            ] {
+                player.handle_event(PlayerEvent::MouseMove { x, y });
+                player.handle_event(PlayerEvent::MouseDown {
+                    x,
+                    y,
+                    button: MouseButton::Right,
+                });
+                player.handle_event(PlayerEvent::MouseUp {
+                    x,
+                    y,
+                    button: MouseButton::Right,
+                });
+                let consumed = player.right_click_consumed();
+                player.log_backend().avm_trace(&format!(
+                    "Right click over {name}: context menu suppressed {consumed}"
+                ));
+            }
+            Ok(())
+        },
+    )?;
+    Ok(())
+}
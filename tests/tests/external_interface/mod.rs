@@ -5,16 +5,24 @@
 use ruffle_core::context::UpdateContext;
 use ruffle_core::external::Value as ExternalValue;
 use ruffle_core::external::{ExternalInterfaceMethod, ExternalInterfaceProvider};
+use std::sync::{Arc, Mutex};
 
 pub mod tests;
 
 #[derive(Default)]
-pub struct ExternalInterfaceTestProvider {}
+pub struct ExternalInterfaceTestProvider {
+    javascript_urls: Arc<Mutex<Vec<String>>>,
+}
 
 impl ExternalInterfaceTestProvider {
     pub fn new() -> Self {
         Default::default()
     }
+
+    /// The scripts of the `javascript:` URLs this provider has been given to run.
+    pub fn javascript_urls(&self) -> Arc<Mutex<Vec<String>>> {
+        self.javascript_urls.clone()
+    }
 }
 
 fn do_trace(context: &mut UpdateContext<'_, '_>, args: &[ExternalValue]) -> ExternalValue {
@@ -51,4 +59,12 @@ impl ExternalInterfaceProvider for ExternalInterfaceTestProvider {
     }
 
     fn on_callback_available(&self, _name: &str) {}
+
+    fn run_javascript_url(&self, script: &str) -> bool {
+        self.javascript_urls
+            .lock()
+            .expect("Javascript URLs lock")
+            .push(script.to_string());
+        true
+    }
 }
@@ -108,3 +108,36 @@ pub fn external_interface_avm2() -> Result<(), libtest_mimic::Failed> {
         },
     )
 }
+
+pub fn external_interface_javascript_url_avm1() -> Result<(), libtest_mimic::Failed> {
+    set_logger();
+    let provider = ExternalInterfaceTestProvider::new();
+    let javascript_urls = provider.javascript_urls();
+    Test::from_options(
+        TestOptions {
+            num_frames: Some(1),
+            log_fetch: true,
+            ..Default::default()
+        },
+        Path::new("tests/swfs/avm1/external_interface_javascript_url/"),
+        "external_interface_javascript_url_avm1".to_string(),
+    )?
+    .run(
+        |player| {
+            player
+                .lock()
+                .unwrap()
+                .add_external_interface(Box::new(provider));
+            Ok(())
+        },
+        |player| {
+            let player_locked = player.lock().unwrap();
+            for script in javascript_urls.lock().unwrap().iter() {
+                player_locked
+                    .log_backend()
+                    .avm_trace(&format!("[ExternalInterface] javascript: {script}"));
+            }
+            Ok(())
+        },
+    )
+}
@@ -2,7 +2,10 @@
 //!
 //! Trace output can be compared with correct output from the official Flash Player.
 
-use crate::external_interface::tests::{external_interface_avm1, external_interface_avm2};
+use crate::context_menu::context_menu_suppression_avm2;
+use crate::external_interface::tests::{
+    external_interface_avm1, external_interface_avm2, external_interface_javascript_url_avm1,
+};
 use crate::shared_object::{shared_object_avm1, shared_object_avm2, shared_object_self_ref_avm1};
 use anyhow::Context;
 use anyhow::Result;
@@ -11,6 +14,7 @@ use std::panic::{catch_unwind, resume_unwind};
 use std::path::Path;
 use util::test::Test;
 
+mod context_menu;
 mod external_interface;
 mod shared_object;
 mod util;
@@ -113,6 +117,20 @@ fn main() {
         "external_interface_avm2",
         external_interface_avm2,
     ));
+    tests.push(Trial::test(
+        "external_interface_javascript_url_avm1",
+        external_interface_javascript_url_avm1,
+    ));
+    tests.push(Trial::test(
+        "context_menu_suppression_avm2",
+        context_menu_suppression_avm2,
+    ));
+
+    #[cfg(feature = "imgtests")]
+    tests.push(Trial::test(
+        "perceptual_difference",
+        util::options::perceptual_difference_test,
+    ));
 
     tests.sort_unstable_by(|a, b| a.name().cmp(b.name()));
 
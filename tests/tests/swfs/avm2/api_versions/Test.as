@@ -0,0 +1,50 @@
+package {
+	import flash.display.MovieClip;
+	import flash.system.ApplicationDomain;
+	import flash.utils.describeType;
+	import flash.utils.getDefinitionByName;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			// This movie is SWF 10, which is older than the worker API.
+			var domain = ApplicationDomain.currentDomain;
+			trace("hasDefinition Sprite: " + domain.hasDefinition("flash.display.Sprite"));
+			trace("hasDefinition Worker: " + domain.hasDefinition("flash.system.Worker"));
+			try {
+				getDefinitionByName("flash.system.Worker");
+				trace("getDefinitionByName Worker: found");
+			} catch (e:ReferenceError) {
+				trace("getDefinitionByName Worker: " + e.errorID);
+			}
+
+			// Namespaces only hide the methods of subclasses in the same API version, so
+			// the AS3 methods of Object don't hide ones in the movie.
+			trace("Direct: " + ownMethods(new Direct()));
+			trace("Sub: " + ownMethods(new Sub()));
+		}
+
+		private function ownMethods(obj:Object):String {
+			var names = [];
+			for each (var method in describeType(obj).method) {
+				var name = String(method.@name);
+				if (name == "directMethod" || name == "baseMethod" || name == "subMethod") {
+					names.push(name);
+				}
+			}
+			names.sort();
+			return names.join(",");
+		}
+	}
+}
+
+class Direct {
+	AS3 function directMethod() {}
+}
+
+class Base {
+	AS3 function baseMethod() {}
+}
+
+class Sub extends Base {
+	AS3 function subMethod() {}
+}
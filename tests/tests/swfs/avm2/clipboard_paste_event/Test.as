@@ -0,0 +1,30 @@
+package {
+    import flash.desktop.Clipboard;
+    import flash.desktop.ClipboardFormats;
+    import flash.display.MovieClip;
+    import flash.events.Event;
+
+    public class Test extends MovieClip {
+
+        public function Test() {
+            super();
+            var clipboard:Clipboard = Clipboard.generalClipboard;
+            trace("formats: " + clipboard.formats);
+            try {
+                clipboard.getData(ClipboardFormats.TEXT_FORMAT);
+                trace("read outside of a paste");
+            } catch (e:SecurityError) {
+                trace("SecurityError: " + e.errorID);
+            }
+
+            // Data set within the player can always be read back.
+            clipboard.setData("custom", "player data");
+            trace("custom: " + clipboard.getData("custom"));
+
+            stage.addEventListener(Event.PASTE, function(e:Event):void {
+                trace("paste: " + e.target);
+                trace("text: \"" + clipboard.getData(ClipboardFormats.TEXT_FORMAT) + "\"");
+            });
+        }
+    }
+}
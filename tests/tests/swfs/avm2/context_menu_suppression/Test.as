@@ -0,0 +1,42 @@
+package {
+    import flash.display.Sprite;
+    import flash.events.MouseEvent;
+
+    public class Test extends Sprite {
+        public function Test() {
+            var listener:Sprite = box("listener", 0, 0);
+            listener.addEventListener(MouseEvent.RIGHT_CLICK, onMouse);
+            addChild(listener);
+
+            var parent:Sprite = new Sprite();
+            parent.name = "parent";
+            parent.x = 200;
+            parent.addEventListener(MouseEvent.RIGHT_MOUSE_DOWN, onMouse);
+            parent.addChild(box("child", 0, 0));
+            addChild(parent);
+
+            addChild(box("plain", 400, 0));
+
+            var middle:Sprite = box("middle", 0, 200);
+            middle.addEventListener(MouseEvent.MIDDLE_CLICK, onMouse);
+            addChild(middle);
+
+            trace("Listeners added");
+        }
+
+        private function box(name:String, x:int, y:int):Sprite {
+            var sprite:Sprite = new Sprite();
+            sprite.name = name;
+            sprite.x = x;
+            sprite.y = y;
+            sprite.graphics.beginFill(0xFF0000);
+            sprite.graphics.drawRect(0, 0, 100, 100);
+            sprite.graphics.endFill();
+            return sprite;
+        }
+
+        private function onMouse(e:MouseEvent):void {
+            trace(e.type + ": " + e.target.name + " (listening on " + e.currentTarget.name + ")");
+        }
+    }
+}
@@ -0,0 +1,128 @@
+package {
+    import flash.display.Loader;
+    import flash.display.MovieClip;
+    import flash.events.Event;
+    import flash.events.HTTPStatusEvent;
+    import flash.events.IOErrorEvent;
+    import flash.events.ProgressEvent;
+    import flash.media.Sound;
+    import flash.net.URLLoader;
+    import flash.net.URLLoaderDataFormat;
+    import flash.net.URLRequest;
+    import flash.utils.ByteArray;
+
+    public class Test extends MovieClip {
+        // A 2x3 PNG.
+        private static const PNG:String = "data:image/png;base64,iVBORw0KGgoAAAANSUhEUgAAAAIAAAADCAYAAAC56t6BAAAAFElEQVR42mP4z8AAQkAMJMAcmAgAm38L9RxoVbAAAAAASUVORK5CYII=";
+
+        // Every load is only started once the previous one is done, so that their events
+        // don't interleave.
+        private var steps:Array;
+
+        public function Test() {
+            steps = [
+                function():void { urlLoader("URLLoader percent-encoded", "data:,Hello%2C%20World%21", URLLoaderDataFormat.TEXT); },
+                function():void { urlLoader("URLLoader base64", "data:text/plain;base64,SGVsbG8sIFdvcmxkIQ==", URLLoaderDataFormat.TEXT); },
+                function():void { urlLoader("URLLoader base64 with a space", "data:;base64,SGVs%20bG8=", URLLoaderDataFormat.TEXT); },
+                function():void { urlLoader("URLLoader binary", "data:application/octet-stream;base64,AAEC/w==", URLLoaderDataFormat.BINARY); },
+                function():void { urlLoader("URLLoader without a comma", "data:text/plain;base64", URLLoaderDataFormat.TEXT); },
+                function():void { urlLoader("URLLoader bad base64", "data:;base64,@@@", URLLoaderDataFormat.TEXT); },
+                function():void { loader("Loader base64", PNG); },
+                function():void { loader("Loader bad base64", "data:image/png;base64,iVBO!!!"); },
+                function():void { sound("Sound percent-encoded", "data:audio/mpeg," + mp3Frames(10)); },
+                function():void { sound("Sound without a comma", "data:audio/mpeg"); }
+            ];
+            next();
+        }
+
+        // Silent MPEG-1 layer III frames (128kbps, 44.1kHz, mono), percent-encoded.
+        private function mp3Frames(count:int):String {
+            var frame:String = "%FF%FB%90%C4";
+            for (var i:int = 4; i < 417; i++) {
+                frame += "%00";
+            }
+            var frames:String = "";
+            for (var j:int = 0; j < count; j++) {
+                frames += frame;
+            }
+            return frames;
+        }
+
+        private function next():void {
+            if (steps.length > 0) {
+                steps.shift()();
+            } else {
+                trace("Done");
+            }
+        }
+
+        private function urlLoader(label:String, url:String, dataFormat:String):void {
+            var loader:URLLoader = new URLLoader();
+            loader.dataFormat = dataFormat;
+            loader.addEventListener(Event.OPEN, function(e:Event):void {
+                trace(label + ": open");
+            });
+            loader.addEventListener(ProgressEvent.PROGRESS, function(e:ProgressEvent):void {
+                trace(label + ": progress " + e.bytesLoaded + "/" + e.bytesTotal);
+            });
+            loader.addEventListener(HTTPStatusEvent.HTTP_STATUS, function(e:HTTPStatusEvent):void {
+                trace(label + ": httpStatus " + e.status);
+            });
+            loader.addEventListener(Event.COMPLETE, function(e:Event):void {
+                if (loader.data is ByteArray) {
+                    var bytes:Array = [];
+                    for (var i:int = 0; i < loader.data.length; i++) {
+                        bytes.push(loader.data[i]);
+                    }
+                    trace(label + ": complete, bytes: " + bytes);
+                } else {
+                    trace(label + ": complete, data: \"" + loader.data + "\"");
+                }
+                next();
+            });
+            loader.addEventListener(IOErrorEvent.IO_ERROR, function(e:IOErrorEvent):void {
+                trace(label + ": ioError #" + e.errorID);
+                next();
+            });
+            loader.load(new URLRequest(url));
+        }
+
+        private function loader(label:String, url:String):void {
+            var loader:Loader = new Loader();
+            loader.contentLoaderInfo.addEventListener(Event.OPEN, function(e:Event):void {
+                trace(label + ": open");
+            });
+            loader.contentLoaderInfo.addEventListener(HTTPStatusEvent.HTTP_STATUS, function(e:HTTPStatusEvent):void {
+                trace(label + ": httpStatus " + e.status);
+            });
+            loader.contentLoaderInfo.addEventListener(Event.INIT, function(e:Event):void {
+                trace(label + ": init");
+            });
+            loader.contentLoaderInfo.addEventListener(Event.COMPLETE, function(e:Event):void {
+                trace(label + ": complete, content: " + loader.content + " " + loader.content.width + "x" + loader.content.height);
+                next();
+            });
+            loader.contentLoaderInfo.addEventListener(IOErrorEvent.IO_ERROR, function(e:IOErrorEvent):void {
+                trace(label + ": ioError #" + e.errorID);
+                next();
+            });
+            loader.load(new URLRequest(url));
+        }
+
+        private function sound(label:String, url:String):void {
+            var sound:Sound = new Sound();
+            sound.addEventListener(Event.OPEN, function(e:Event):void {
+                trace(label + ": open");
+            });
+            sound.addEventListener(Event.COMPLETE, function(e:Event):void {
+                trace(label + ": complete");
+                next();
+            });
+            sound.addEventListener(IOErrorEvent.IO_ERROR, function(e:IOErrorEvent):void {
+                trace(label + ": ioError #" + e.errorID);
+                next();
+            });
+            sound.load(new URLRequest(url));
+        }
+    }
+}
@@ -0,0 +1,105 @@
+package {
+    import flash.display.DisplayObject;
+    import flash.display.MovieClip;
+    import flash.display.Sprite;
+    import flash.geom.Matrix3D;
+    import flash.geom.PerspectiveProjection;
+    import flash.geom.Point;
+    import flash.geom.Rectangle;
+
+    public class Test extends MovieClip {
+        public function Test() {
+            trace("// PerspectiveProjection defaults on a 550x400 stage");
+            var projection:PerspectiveProjection = new PerspectiveProjection();
+            trace("fieldOfView: " + projection.fieldOfView);
+            trace("focalLength: " + round(projection.focalLength));
+            trace("projectionCenter: " + projection.projectionCenter);
+            projection.focalLength = 100;
+            trace("fieldOfView after focalLength = 100: " + round(projection.fieldOfView));
+            try {
+                projection.fieldOfView = 180;
+            } catch (e:ArgumentError) {
+                trace("fieldOfView = 180: " + e.errorID);
+            }
+
+            trace("// 2D object");
+            var square:Sprite = makeSquare();
+            square.x = 100;
+            square.y = 100;
+            addChild(square);
+            trace("matrix3D: " + square.transform.matrix3D);
+            trace("perspectiveProjection: " + square.transform.perspectiveProjection);
+            trace("bounds: " + square.getBounds(this));
+
+            trace("// z through the default projection");
+            square.z = new PerspectiveProjection().focalLength;
+            trace("z: " + round(square.z));
+            trace("bounds: " + square.getBounds(this));
+            square.z = 0;
+            trace("bounds at z = 0: " + square.getBounds(this));
+
+            trace("// rotationX, rotationY and scaleZ");
+            square.rotationX = 30;
+            square.rotationY = 90;
+            square.scaleZ = 2;
+            trace("rotationX: " + round(square.rotationX) + ", rotationY: " + round(square.rotationY) + ", scaleZ: " + square.scaleZ);
+            square.rotationX = 0;
+            square.scaleZ = 1;
+            showMatrix("matrix3D with rotationY = 90", square.transform.matrix3D);
+
+            trace("// matrix3D");
+            square.transform.matrix3D = new Matrix3D(Vector.<Number>([2, 0, 0, 0, 0, 2, 0, 0, 0, 0, 1, 0, 10, 20, 30, 1]));
+            trace("x: " + square.x + ", y: " + square.y + ", z: " + square.z + ", scaleX: " + square.scaleX + ", scaleY: " + square.scaleY);
+            square.transform.matrix3D = null;
+            trace("back to 2D, matrix3D: " + square.transform.matrix3D + ", z: " + square.z);
+            trace("bounds: " + square.getBounds(this));
+            removeChild(square);
+
+            trace("// Custom perspectiveProjection");
+            var container:Sprite = new Sprite();
+            addChild(container);
+            projection = new PerspectiveProjection();
+            projection.focalLength = 100;
+            projection.projectionCenter = new Point(0, 0);
+            container.transform.perspectiveProjection = projection;
+            var stored:PerspectiveProjection = container.transform.perspectiveProjection;
+            trace("focalLength: " + round(stored.focalLength) + ", projectionCenter: " + stored.projectionCenter);
+
+            var child:Sprite = makeSquare();
+            child.x = 100;
+            child.y = 50;
+            container.addChild(child);
+            child.z = 100;
+            trace("bounds: " + child.getBounds(this));
+
+            container.x = 200;
+            child.z = 0;
+            child.z = 100;
+            trace("bounds with the container moved: " + child.getBounds(this));
+
+            container.transform.perspectiveProjection = null;
+            trace("perspectiveProjection after removing it: " + container.transform.perspectiveProjection);
+        }
+
+        private function makeSquare():Sprite {
+            var square:Sprite = new Sprite();
+            square.graphics.beginFill(0xFF0000);
+            square.graphics.drawRect(0, 0, 100, 100);
+            square.graphics.endFill();
+            return square;
+        }
+
+        private function round(value:Number):Number {
+            return Math.round(value * 100) / 100;
+        }
+
+        private function showMatrix(label:String, m:Matrix3D):void {
+            var values:Array = [];
+            for each (var value:Number in m.rawData) {
+                value = Math.round(value * 1000) / 1000;
+                values.push(value == 0 ? 0 : value);
+            }
+            trace(label + ": " + values.join(","));
+        }
+    }
+}
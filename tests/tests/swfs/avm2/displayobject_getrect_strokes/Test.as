@@ -0,0 +1,58 @@
+package {
+    import flash.display.Shape;
+    import flash.display.Sprite;
+    import flash.filters.BlurFilter;
+    import flash.filters.GlowFilter;
+    import flash.text.TextField;
+
+    public class Test extends Sprite {
+        public function Test() {
+            var shape:Shape = new Shape();
+            shape.graphics.lineStyle(10, 0);
+            shape.graphics.beginFill(0xFF0000);
+            shape.graphics.drawRect(0, 0, 100, 50);
+            shape.graphics.endFill();
+            trace("// shape, 10px stroke");
+            trace("getBounds: " + shape.getBounds(shape));
+            trace("getRect: " + shape.getRect(shape));
+
+            shape.filters = [new BlurFilter(20, 20)];
+            trace("// shape with a blur filter");
+            trace("getBounds: " + shape.getBounds(shape));
+            trace("getRect: " + shape.getRect(shape));
+
+            var sprite:Sprite = new Sprite();
+            sprite.x = 10;
+            sprite.y = 20;
+            sprite.scaleX = 2;
+            sprite.graphics.lineStyle(4, 0);
+            sprite.graphics.moveTo(-10, 0);
+            sprite.graphics.lineTo(-10, 10);
+            shape.x = 5;
+            sprite.addChild(shape);
+            addChild(sprite);
+            trace("// sprite with a 4px stroke, containing the shape");
+            trace("sprite.getBounds(sprite): " + sprite.getBounds(sprite));
+            trace("sprite.getRect(sprite): " + sprite.getRect(sprite));
+            trace("sprite.getBounds(this): " + sprite.getBounds(this));
+            trace("sprite.getRect(this): " + sprite.getRect(this));
+            trace("shape.getBounds(sprite): " + shape.getBounds(sprite));
+            trace("shape.getRect(sprite): " + shape.getRect(sprite));
+            trace("shape.getBounds(this): " + shape.getBounds(this));
+            trace("shape.getRect(this): " + shape.getRect(this));
+
+            var tf:TextField = new TextField();
+            tf.x = 300;
+            tf.width = 100;
+            tf.height = 20;
+            tf.border = true;
+            tf.filters = [new GlowFilter()];
+            addChild(tf);
+            trace("// text field with a border and a glow filter");
+            trace("tf.getBounds(tf): " + tf.getBounds(tf));
+            trace("tf.getRect(tf): " + tf.getRect(tf));
+            trace("tf.getBounds(this): " + tf.getBounds(this));
+            trace("tf.getRect(this): " + tf.getRect(this));
+        }
+    }
+}
@@ -0,0 +1,58 @@
+package {
+	import flash.display.MovieClip;
+	import flash.display.Shape;
+	import flash.display.Sprite;
+	import flash.geom.Point;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			var a:Sprite = new Sprite();
+			a.name = "a";
+			a.graphics.beginFill(0xFF0000);
+			a.graphics.drawRect(0, 0, 100, 100);
+			addChild(a);
+
+			var s1:Shape = rect("s1", 20, 20, 50, 50);
+			a.addChild(s1);
+
+			addChild(rect("s2", 40, 40, 120, 120));
+
+			var masked:Shape = rect("masked", 0, 0, 200, 200);
+			addChild(masked);
+			var m:Shape = rect("m", 150, 150, 50, 50);
+			addChild(m);
+			masked.mask = m;
+
+			var empty:Sprite = new Sprite();
+			empty.name = "empty";
+			addChild(empty);
+			empty.addChild(rect("s3", 0, 0, 10, 10));
+
+			dump(this, 50, 50);
+			dump(this, 5, 5);
+			dump(this, 155, 155);
+			dump(this, 300, 300);
+			dump(a, 50, 50);
+
+			trace("// areInaccessibleObjectsUnderPoint");
+			trace(areInaccessibleObjectsUnderPoint(new Point(50, 50)));
+		}
+
+		private function rect(name:String, x:Number, y:Number, w:Number, h:Number):Shape {
+			var s:Shape = new Shape();
+			s.name = name;
+			s.graphics.beginFill(0x00FF00);
+			s.graphics.drawRect(x, y, w, h);
+			return s;
+		}
+
+		private function dump(container:Sprite, x:Number, y:Number):void {
+			var names:Array = [];
+			for each (var obj in container.getObjectsUnderPoint(new Point(x, y))) {
+				names.push(obj.name);
+			}
+			trace("// " + container.name + ".getObjectsUnderPoint(" + x + ", " + y + ")");
+			trace(names.join(","));
+		}
+	}
+}
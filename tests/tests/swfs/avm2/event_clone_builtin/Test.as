@@ -0,0 +1,95 @@
+package {
+    import flash.display.BitmapData;
+    import flash.display.MovieClip;
+    import flash.events.AVDictionaryDataEvent;
+    import flash.events.AVPauseAtPeriodEndEvent;
+    import flash.events.AudioOutputChangeEvent;
+    import flash.events.DataEvent;
+    import flash.events.Event;
+    import flash.events.EventDispatcher;
+    import flash.events.GestureEvent;
+    import flash.events.HTTPStatusEvent;
+    import flash.events.IOErrorEvent;
+    import flash.events.MouseEvent;
+    import flash.events.ProgressEvent;
+    import flash.events.ShaderEvent;
+    import flash.events.StageVideoAvailabilityEvent;
+    import flash.events.StageVideoEvent;
+    import flash.events.VideoEvent;
+    import flash.events.VideoTextureEvent;
+    import flash.net.URLRequestHeader;
+    import flash.utils.ByteArray;
+    import flash.utils.Dictionary;
+    import flash.utils.getQualifiedClassName;
+
+    public class Test extends MovieClip {
+        public function Test() {
+            var dictionary:Dictionary = new Dictionary();
+            dictionary["key"] = "value";
+            check(new AVDictionaryDataEvent("avDictionaryData", true, true, dictionary, 12.5), ["dictionary", "time"]);
+            check(new AVPauseAtPeriodEndEvent("avPauseAtPeriodEnd", true, true, 7), ["userData"]);
+            check(new AudioOutputChangeEvent("audioOutputChange", true, true, "deviceChange"), ["reason"]);
+            check(new DataEvent("data", true, true, "some data"), ["data", "text"]);
+            check(new GestureEvent("gestureTwoFingerTap", false, true, "all", 10, 20, true, true, true, true),
+                ["phase", "localX", "localY", "ctrlKey", "altKey", "shiftKey", "controlKey"]);
+
+            var status:HTTPStatusEvent = new HTTPStatusEvent("httpStatus", true, true, 404, true);
+            status.responseURL = "http://example.com/item.swf";
+            status.responseHeaders = [new URLRequestHeader("Content-Type", "text/plain")];
+            check(status, ["status", "redirected", "responseURL", "responseHeaders"]);
+
+            check(new IOErrorEvent("ioError", true, true, "Error #2032: Stream Error", 2032), ["text", "errorID"]);
+            check(new MouseEvent("click", false, true, 3, 4, null, true, true, true, true, 2),
+                ["localX", "localY", "relatedObject", "ctrlKey", "altKey", "shiftKey", "buttonDown", "delta"]);
+            check(new ProgressEvent("progress", true, true, 5, 10), ["bytesLoaded", "bytesTotal"]);
+
+            var vector:Vector.<Number> = new <Number>[1, 2];
+            check(new ShaderEvent("complete", true, true, new BitmapData(1, 1), new ByteArray(), vector),
+                ["bitmapData", "byteArray", "vector"]);
+
+            check(new StageVideoAvailabilityEvent("stageVideoAvailability", true, true, "available"), ["availability"]);
+            check(new StageVideoEvent("renderState", true, true, "accelerated", "BT.709"), ["status", "colorSpace"]);
+            check(new VideoEvent("renderState", true, true, "software"), ["status"]);
+            check(new VideoTextureEvent("renderState", true, true, "accelerated", "BT.601"), ["status", "colorSpace"]);
+        }
+
+        // Dispatches `event`, and from its listener dispatches it again, which dispatches a clone
+        // instead. Every field of the clone that differs from the original is reported.
+        private function check(event:Event, fields:Array):void {
+            var name:String = className(event);
+            var source:EventDispatcher = new EventDispatcher();
+            var relay:EventDispatcher = new EventDispatcher();
+            source.addEventListener(event.type, function(e:Event):void {
+                relay.dispatchEvent(e);
+            });
+            relay.addEventListener(event.type, function(e:Event):void {
+                var line:String = name + ": ";
+                if (e === event) {
+                    line += "not cloned";
+                } else {
+                    line += "cloned as " + className(e);
+                }
+                var differences:Array = [];
+                for each (var field:String in ["type", "bubbles", "cancelable"].concat(fields)) {
+                    if (e[field] !== event[field]) {
+                        differences.push(field + " is " + e[field] + " instead of " + event[field]);
+                    }
+                }
+                if (differences.length == 0) {
+                    line += ", all " + (fields.length + 3) + " fields match";
+                } else {
+                    line += ", " + differences.join(", ");
+                }
+                trace(line);
+                if (e is GestureEvent) {
+                    trace(e.toString());
+                }
+            });
+            source.dispatchEvent(event);
+        }
+
+        private function className(event:Event):String {
+            return getQualifiedClassName(event).replace("flash.events::", "");
+        }
+    }
+}
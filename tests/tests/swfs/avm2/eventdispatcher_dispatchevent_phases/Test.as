@@ -0,0 +1,99 @@
+package {
+	import flash.display.Sprite;
+	import flash.events.Event;
+
+	public class Test extends Sprite {
+		private var parentSprite: Sprite = new Sprite();
+		private var childSprite: Sprite = new Sprite();
+
+		public function Test() {
+			addChild(parentSprite);
+			parentSprite.addChild(childSprite);
+
+			trace("// bubbling event");
+			listenEverywhere("bubbling", null, false);
+			trace(childSprite.dispatchEvent(new Event("bubbling", true)));
+
+			trace("// non-bubbling event");
+			listenEverywhere("nonBubbling", null, false);
+			trace(childSprite.dispatchEvent(new Event("nonBubbling", false)));
+
+			trace("// stopImmediatePropagation in the capture phase");
+			listenEverywhere("stopImmediateCapture", "parent capture high", true);
+			childSprite.dispatchEvent(new Event("stopImmediateCapture", true));
+
+			trace("// stopPropagation in the capture phase");
+			listenEverywhere("stopCapture", "parent capture high", false);
+			childSprite.dispatchEvent(new Event("stopCapture", true));
+
+			trace("// stopImmediatePropagation in the target phase");
+			listenEverywhere("stopImmediateTarget", "child target", true);
+			childSprite.dispatchEvent(new Event("stopImmediateTarget", true));
+
+			trace("// stopPropagation in the target phase");
+			listenEverywhere("stopTarget", "child target", false);
+			childSprite.dispatchEvent(new Event("stopTarget", true));
+
+			trace("// stopImmediatePropagation in the bubble phase");
+			listenEverywhere("stopImmediateBubble", "parent bubble", true);
+			childSprite.dispatchEvent(new Event("stopImmediateBubble", true));
+
+			trace("// stopPropagation in the bubble phase");
+			listenEverywhere("stopBubble", "parent bubble", false);
+			childSprite.dispatchEvent(new Event("stopBubble", true));
+
+			trace("// re-adding a listener with another priority");
+			var twice: Function = function(e: Event): void {
+				trace("registered twice");
+			};
+			addEventListener("twice", twice, false, 0);
+			addEventListener("twice", function(e: Event): void {
+				trace("priority 1");
+			}, false, 1);
+			addEventListener("twice", twice, false, 10);
+			dispatchEvent(new Event("twice"));
+
+			trace("// adding and removing listeners during dispatch");
+			var late: Function = function(e: Event): void {
+				trace("added during dispatch");
+			};
+			var removed: Function = function(e: Event): void {
+				trace("removed during dispatch");
+			};
+			addEventListener("changes", function(e: Event): void {
+				trace("first");
+				addEventListener("changes", late);
+				removeEventListener("changes", removed);
+			});
+			addEventListener("changes", removed);
+			dispatchEvent(new Event("changes"));
+			dispatchEvent(new Event("changes"));
+		}
+
+		private function listen(target: Sprite, type: String, label: String, useCapture: Boolean, priority: int, stopAt: String, immediate: Boolean): void {
+			target.addEventListener(type, function(e: Event): void {
+				trace(label + " (phase " + e.eventPhase + ")");
+				if (label == stopAt) {
+					if (immediate) {
+						e.stopImmediatePropagation();
+					} else {
+						e.stopPropagation();
+					}
+				}
+			}, useCapture, priority);
+		}
+
+		private function listenEverywhere(type: String, stopAt: String, immediate: Boolean): void {
+			listen(this, type, "root capture", true, 0, stopAt, immediate);
+			listen(this, type, "root bubble", false, 0, stopAt, immediate);
+			listen(parentSprite, type, "parent capture", true, 0, stopAt, immediate);
+			listen(parentSprite, type, "parent capture high", true, 5, stopAt, immediate);
+			listen(parentSprite, type, "parent bubble", false, 0, stopAt, immediate);
+			listen(parentSprite, type, "parent bubble second", false, 0, stopAt, immediate);
+			listen(childSprite, type, "child capture", true, 0, stopAt, immediate);
+			listen(childSprite, type, "child target low", false, -1, stopAt, immediate);
+			listen(childSprite, type, "child target", false, 0, stopAt, immediate);
+			listen(childSprite, type, "child target second", false, 0, stopAt, immediate);
+		}
+	}
+}
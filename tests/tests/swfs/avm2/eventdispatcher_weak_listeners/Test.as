@@ -0,0 +1,46 @@
+package {
+	import flash.display.Sprite;
+	import flash.events.Event;
+
+	public class Test extends Sprite {
+		private var frames: int = 0;
+		private var garbage: Array;
+
+		public function Test() {
+			addEventListener("test", function(e: Event): void {
+				trace("anonymous weak listener");
+			}, false, 0, true);
+			addEventListener("test", onTestWeak, false, 0, true);
+			addEventListener("test", function(e: Event): void {
+				trace("anonymous strong listener");
+			});
+			addEventListener("removed", onTestWeak, false, 0, true);
+			removeEventListener("removed", onTestWeak);
+
+			trace("// before collection");
+			dispatchEvent(new Event("test"));
+			trace("hasEventListener('removed'): " + hasEventListener("removed"));
+
+			addEventListener(Event.ENTER_FRAME, onEnterFrame);
+		}
+
+		private function onTestWeak(e: Event): void {
+			trace("method weak listener");
+		}
+
+		private function onEnterFrame(e: Event): void {
+			// Allocate enough for the garbage collector to run several times.
+			for (var i: int = 0; i < 20000; i++) {
+				garbage = [new Object(), new Object(), new Object()];
+			}
+
+			frames++;
+			if (frames == 30) {
+				trace("// after collection");
+				dispatchEvent(new Event("test"));
+				trace("hasEventListener('test'): " + hasEventListener("test"));
+				removeEventListener(Event.ENTER_FRAME, onEnterFrame);
+			}
+		}
+	}
+}
@@ -0,0 +1,22 @@
+package {
+    import flash.display.MovieClip;
+    import flash.filesystem.File;
+
+    public class Test extends MovieClip {
+
+        public function Test() {
+            super();
+            var file:File = File.applicationDirectory.resolvePath("test.swf");
+            trace("url: " + file.url);
+            trace("nativePath ends with test.swf: " + (file.nativePath.substr(-8) == "test.swf"));
+            trace("exists: " + file.exists);
+
+            file = File.applicationDirectory.resolvePath("missing.swf");
+            trace("missing exists: " + file.exists);
+
+            // Only the application's own directories can be probed.
+            file = new File("/");
+            trace("root exists: " + file.exists);
+        }
+    }
+}
@@ -0,0 +1,19 @@
+package {
+    import flash.display.MovieClip;
+    import flash.filesystem.File;
+
+    public class Test extends MovieClip {
+
+        public function Test() {
+            super();
+            // Content outside of AIR can't see into the filesystem.
+            var file:File = File.applicationDirectory.resolvePath("test.swf");
+            trace("url: " + file.url);
+            trace("nativePath: " + file.nativePath);
+            trace("exists: " + file.exists);
+
+            file = new File("/");
+            trace("root exists: " + file.exists);
+        }
+    }
+}
@@ -0,0 +1,102 @@
+package {
+    import flash.display.MovieClip;
+
+    public class Test extends MovieClip {
+        public function Test() {
+            var sum:Function = function(a:* = "-", b:* = "-", c:* = "-"):String {
+                return this + ": " + a + ", " + b + ", " + c;
+            };
+
+            trace("// apply");
+            trace(sum.apply("dense", [1, 2, 3]));
+            trace(sum.apply("short", [1]));
+            trace(sum.apply("empty", []));
+            trace(sum.apply("null", null));
+            trace(sum.apply("undefined", undefined));
+
+            var holes:Array = [1];
+            holes[2] = 3;
+            trace(sum.apply("holes", holes));
+            Array.prototype[1] = "from prototype";
+            trace(sum.apply("holes with prototype", holes));
+            delete Array.prototype[1];
+
+            var sparse:Array = [];
+            sparse[1] = "only";
+            trace(sum.apply("sparse", sparse));
+
+            trace("// call");
+            trace(sum.call("call", 1, 2, 3));
+            trace(sum.call("call short", 1));
+
+            trace("// arguments");
+            trace(countArguments());
+            trace(countArguments(1, "two", null));
+            trace(countArguments.apply(null, [1, 2, 3, 4]));
+            trace(readsCallee(1));
+            trace(ignoresArguments(1, 2, 3));
+            trace(enumeratesArguments("a", "b", "c"));
+            trace(changesArguments(1, 2));
+            trace(sameArguments(1, 2));
+
+            trace("// rest");
+            trace(rest());
+            trace(rest(1, 2, 3));
+            trace(rest.apply(null, ["applied", "rest"]));
+            trace(replacesRest(1, 2, 3));
+            trace(ignoresRest("first", 2, 3));
+            trace(restIsArray(1, 2));
+        }
+
+        private function countArguments():String {
+            return "arguments.length = " + arguments.length;
+        }
+
+        private function readsCallee(a:*):String {
+            return "callee is a Function: " + (arguments.callee is Function);
+        }
+
+        private function ignoresArguments(a:*, b:*, c:*):String {
+            if (a == null) {
+                return "never read: " + arguments.length;
+            }
+            return "ignored: " + a + b + c;
+        }
+
+        private function enumeratesArguments():String {
+            var keys:Array = [];
+            for (var key:String in arguments) {
+                keys.push(key + "=" + arguments[key]);
+            }
+            return "enumerated: " + keys.join(", ");
+        }
+
+        private function changesArguments(a:*, b:*):String {
+            arguments[0] = "changed";
+            a = "parameter";
+            return "changed: " + arguments[0] + ", " + a + ", " + arguments.length;
+        }
+
+        private function sameArguments(a:*, b:*):Boolean {
+            return arguments === arguments;
+        }
+
+        private function rest(... args):String {
+            return "rest: " + args.length + " [" + args + "]";
+        }
+
+        private function replacesRest(... args):String {
+            args = ["replaced"];
+            return "replaced: " + args;
+        }
+
+        private function ignoresRest(first:*, ... args):String {
+            return "ignored rest: " + first;
+        }
+
+        private function restIsArray(... args):String {
+            args.push("pushed");
+            return "rest is Array: " + (args is Array) + ", " + args;
+        }
+    }
+}
@@ -0,0 +1,42 @@
+package {
+	import flash.display.MovieClip;
+	import flash.utils.Dictionary;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			// When emulating a Flash Player release, properties named by small integers
+			// come out in numeric order, whatever order they were added in.
+			var obj = {};
+			var dict = new Dictionary();
+			var arr = [];
+			for each (var i in [3, 0, 2, 1]) {
+				obj[i] = i;
+				dict[i] = i;
+				arr["p" + i] = i;
+			}
+			arr[0] = "a";
+
+			trace("Object: " + keys(obj));
+			trace("Dictionary: " + keys(dict));
+
+			delete obj[2];
+			obj[7] = 7;
+			trace("Object after delete: " + keys(obj));
+
+			var values = [];
+			for each (var value in dict) {
+				values.push(value);
+			}
+			trace("Dictionary values: " + values.join(","));
+			trace("Array keys: " + keys(arr).length);
+		}
+
+		private function keys(obj:Object):String {
+			var names = [];
+			for (var name in obj) {
+				names.push(name);
+			}
+			return names.join(",");
+		}
+	}
+}
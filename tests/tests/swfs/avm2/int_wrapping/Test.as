@@ -0,0 +1,60 @@
+package {
+    import flash.display.Sprite;
+    import flash.utils.getQualifiedClassName;
+
+    public class Test extends Sprite {
+        public function Test() {
+            trace("// int + int past the int range is a Number");
+            var max = 2147483647;
+            var min = -2147483648;
+            var one = 1;
+            trace(max + one, getQualifiedClassName(max + one));
+            trace(min - one, getQualifiedClassName(min - one));
+
+            trace("// typed int and uint locals wrap");
+            var i:int = 2147483647;
+            i++;
+            trace(i);
+            i--;
+            trace(i);
+            var u:uint = 0;
+            u--;
+            trace(u);
+            u++;
+            trace(u);
+            var m:int = 65537;
+            var product:int = m * m;
+            trace(product);
+            var n:int = -2147483648;
+            n = -n;
+            trace(n);
+
+            trace("// conversions wrap modulo 2^32");
+            var big = 3000000000;
+            var bigFraction = -3000000000.5;
+            var huge = 1e20;
+            trace(int(big), uint(big));
+            trace(int(bigFraction), uint(bigFraction));
+            trace(int(huge), uint(huge));
+            trace(uint(min), int(-one), uint(-one));
+
+            trace("// negative zero is only ever a Number");
+            var zero:Number = 0;
+            var negZero:Number = -zero;
+            trace(negZero is int, negZero is uint, negZero is Number);
+            trace(zero is int, zero is uint);
+            trace(1 / negZero);
+            var intZero:int = negZero;
+            trace(1 / intZero);
+
+            trace("// modulo keeps the sign of the dividend");
+            var seven = 7;
+            var three = 3;
+            trace(-seven % three, seven % -three);
+
+            trace("// shifts use the low five bits of the count");
+            var thirtyThree = 33;
+            trace(one << thirtyThree, one << 31, -one >>> 0, min >> 1, -seven >>> 28);
+        }
+    }
+}
@@ -0,0 +1,73 @@
+package {
+    import flash.display.DisplayObject;
+    import flash.display.DisplayObjectContainer;
+    import flash.display.Loader;
+    import flash.display.MovieClip;
+    import flash.display.Sprite;
+    import flash.events.AsyncErrorEvent;
+    import flash.events.Event;
+    import flash.system.LoaderContext;
+    import flash.utils.ByteArray;
+
+    public class Test extends MovieClip {
+        // A 2x3 red PNG.
+        private static const PNG:Array = [
+            137,80,78,71,13,10,26,10,0,0,0,13,73,72,68,82,0,0,0,2,0,0,0,3,8,2,0,0,0,54,136,73,
+            214,0,0,0,16,73,68,65,84,120,156,99,248,207,192,0,68,12,40,20,0,68,208,5,251,164,207,
+            222,128,0,0,0,0,73,69,78,68,174,66,96,130
+        ];
+
+        public function Test() {
+            var noCode:LoaderContext = new LoaderContext();
+            noCode.allowCodeImport = false;
+            try {
+                new Loader().loadBytes(loaderInfo.bytes, noCode);
+                trace("SWF with allowCodeImport = false: loaded");
+            } catch (e:Error) {
+                trace("SWF with allowCodeImport = false: " + e.name + " " + e.errorID);
+            }
+
+            var holder:Sprite = new Sprite();
+            holder.addChild(new Sprite());
+            addChild(holder);
+            loads = [
+                ["Sprite parent", holder, "onDemand"],
+                ["Sprite parent, decoded on load", holder, "onLoad"],
+                ["Loader parent", new Loader(), "onDemand"]
+            ];
+            loadNext();
+        }
+
+        private var loads:Array;
+
+        // One at a time, so that they finish in order.
+        private function loadNext():void {
+            if (loads.length > 0) {
+                load.apply(this, loads.shift());
+            }
+        }
+
+        private function load(label:String, parent:DisplayObjectContainer, policy:String):void {
+            var context:LoaderContext = new LoaderContext();
+            context.requestedContentParent = parent;
+            context.imageDecodingPolicy = policy;
+
+            var loader:Loader = new Loader();
+            loader.contentLoaderInfo.addEventListener(AsyncErrorEvent.ASYNC_ERROR, function(e:AsyncErrorEvent):void {
+                trace(label + ": asyncError " + e.error.name + " " + e.error.errorID);
+            });
+            loader.contentLoaderInfo.addEventListener(Event.COMPLETE, function(e:Event):void {
+                var content:DisplayObject = loader.content;
+                var where:String = content.parent === loader ? "the loader" : content.parent === parent ? "the requested parent" : String(content.parent);
+                trace(label + ": " + content.width + "x" + content.height + " in " + where + " at index " + content.parent.getChildIndex(content));
+                loadNext();
+            });
+
+            var bytes:ByteArray = new ByteArray();
+            for each (var byte:int in PNG) {
+                bytes.writeByte(byte);
+            }
+            loader.loadBytes(bytes, context);
+        }
+    }
+}
@@ -0,0 +1,121 @@
+package {
+    import flash.display.Loader;
+    import flash.display.MovieClip;
+    import flash.events.Event;
+    import flash.events.HTTPStatusEvent;
+    import flash.events.IOErrorEvent;
+    import flash.events.NetStatusEvent;
+    import flash.events.ProgressEvent;
+    import flash.media.Sound;
+    import flash.net.NetConnection;
+    import flash.net.NetStream;
+    import flash.net.URLLoader;
+    import flash.net.URLRequest;
+    import flash.net.URLRequestMethod;
+
+    public class Test extends MovieClip {
+        // Every load is only started once the previous one is done, so that their events
+        // don't interleave.
+        private var steps:Array;
+
+        public function Test() {
+            steps = [
+                function():void { urlLoader("URLLoader 204", "no_content.txt", URLRequestMethod.GET); },
+                function():void { urlLoader("URLLoader empty", "empty.txt", URLRequestMethod.GET); },
+                function():void { urlLoader("URLLoader HEAD", "item.txt", URLRequestMethod.HEAD); },
+                function():void { urlLoader("URLLoader GET", "item.txt", URLRequestMethod.GET); },
+                function():void { loader("Loader 204", "no_content.swf"); },
+                function():void { loader("Loader empty", "empty.swf"); },
+                function():void { sound("Sound 204", "no_content.mp3"); },
+                function():void { sound("Sound empty", "empty.mp3"); },
+                function():void { netStream("NetStream 204", "no_content.flv"); },
+                function():void { netStream("NetStream empty", "empty.flv"); }
+            ];
+            next();
+        }
+
+        private function next():void {
+            if (steps.length > 0) {
+                steps.shift()();
+            } else {
+                trace("Done");
+            }
+        }
+
+        private function urlLoader(label:String, url:String, method:String):void {
+            var request:URLRequest = new URLRequest(url);
+            request.method = method;
+            var loader:URLLoader = new URLLoader();
+            loader.addEventListener(Event.OPEN, function(e:Event):void {
+                trace(label + ": open");
+            });
+            loader.addEventListener(ProgressEvent.PROGRESS, function(e:ProgressEvent):void {
+                trace(label + ": progress " + e.bytesLoaded + "/" + e.bytesTotal);
+            });
+            loader.addEventListener(HTTPStatusEvent.HTTP_STATUS, function(e:HTTPStatusEvent):void {
+                trace(label + ": httpStatus " + e.status);
+            });
+            loader.addEventListener(Event.COMPLETE, function(e:Event):void {
+                trace(label + ": complete, data: \"" + loader.data + "\"");
+                next();
+            });
+            loader.addEventListener(IOErrorEvent.IO_ERROR, function(e:IOErrorEvent):void {
+                trace(label + ": ioError #" + e.errorID);
+                next();
+            });
+            loader.load(request);
+        }
+
+        private function loader(label:String, url:String):void {
+            var loader:Loader = new Loader();
+            loader.contentLoaderInfo.addEventListener(Event.OPEN, function(e:Event):void {
+                trace(label + ": open");
+            });
+            loader.contentLoaderInfo.addEventListener(HTTPStatusEvent.HTTP_STATUS, function(e:HTTPStatusEvent):void {
+                trace(label + ": httpStatus " + e.status);
+            });
+            loader.contentLoaderInfo.addEventListener(Event.INIT, function(e:Event):void {
+                trace(label + ": init");
+            });
+            loader.contentLoaderInfo.addEventListener(Event.COMPLETE, function(e:Event):void {
+                trace(label + ": complete");
+                next();
+            });
+            loader.contentLoaderInfo.addEventListener(IOErrorEvent.IO_ERROR, function(e:IOErrorEvent):void {
+                trace(label + ": ioError #" + e.errorID);
+                next();
+            });
+            loader.load(new URLRequest(url));
+        }
+
+        private function sound(label:String, url:String):void {
+            var sound:Sound = new Sound();
+            sound.addEventListener(Event.OPEN, function(e:Event):void {
+                trace(label + ": open");
+            });
+            sound.addEventListener(Event.COMPLETE, function(e:Event):void {
+                trace(label + ": complete");
+                next();
+            });
+            sound.addEventListener(IOErrorEvent.IO_ERROR, function(e:IOErrorEvent):void {
+                trace(label + ": ioError #" + e.errorID);
+                next();
+            });
+            sound.load(new URLRequest(url));
+        }
+
+        private function netStream(label:String, url:String):void {
+            var connection:NetConnection = new NetConnection();
+            connection.connect(null);
+            var stream:NetStream = new NetStream(connection);
+            stream.client = {};
+            stream.addEventListener(NetStatusEvent.NET_STATUS, function(e:NetStatusEvent):void {
+                trace(label + ": netStatus " + e.info.code + " (" + e.info.level + ")");
+                if (e.info.level == "error") {
+                    next();
+                }
+            });
+            stream.play(url);
+        }
+    }
+}
@@ -0,0 +1,35 @@
+package {
+    import flash.display.Loader;
+    import flash.display.MovieClip;
+    import flash.events.Event;
+    import flash.events.IOErrorEvent;
+    import flash.utils.ByteArray;
+
+    public class Test extends MovieClip {
+        public function Test() {
+            // The start of a JPEG XR file, followed by bytes that aren't any known type.
+            load("JPEG XR", [0x49, 0x49, 0xbc, 0x01, 0x08, 0x00, 0x00, 0x00], function():void {
+                load("Unknown", [0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08], null);
+            });
+        }
+
+        private function load(label:String, data:Array, then:Function):void {
+            var bytes:ByteArray = new ByteArray();
+            for each (var byte:int in data) {
+                bytes.writeByte(byte);
+            }
+
+            var loader:Loader = new Loader();
+            loader.contentLoaderInfo.addEventListener(IOErrorEvent.IO_ERROR, function(e:IOErrorEvent):void {
+                trace(label + ": ioError #" + e.errorID + ": " + e.text);
+                if (then != null) {
+                    then();
+                }
+            });
+            loader.contentLoaderInfo.addEventListener(Event.COMPLETE, function(e:Event):void {
+                trace(label + ": loaded");
+            });
+            loader.loadBytes(bytes);
+        }
+    }
+}
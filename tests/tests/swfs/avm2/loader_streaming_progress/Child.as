@@ -0,0 +1,15 @@
+package {
+    import flash.display.Sprite;
+
+    public class Child extends Sprite {
+        // Padding, so that the movie arrives in a good number of chunks.
+        public static const PADDING:Array = [
+            "Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor",
+            "incididunt ut labore et dolore magna aliqua. Ut enim ad minim veniam, quis nostrud",
+            "exercitation ullamco laboris nisi ut aliquip ex ea commodo consequat. Duis aute",
+            "irure dolor in reprehenderit in voluptate velit esse cillum dolore eu fugiat nulla",
+            "pariatur. Excepteur sint occaecat cupidatat non proident, sunt in culpa qui officia",
+            "deserunt mollit anim id est laborum."
+        ];
+    }
+}
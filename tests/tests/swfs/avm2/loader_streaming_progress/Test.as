@@ -0,0 +1,45 @@
+package {
+    import flash.display.Loader;
+    import flash.display.MovieClip;
+    import flash.events.Event;
+    import flash.events.ProgressEvent;
+    import flash.net.URLRequest;
+
+    public class Test extends MovieClip {
+        public function Test() {
+            var loader:Loader = new Loader();
+            var events:int = 0;
+            var partial:int = 0;
+            var last:Number = -1;
+            var monotonic:Boolean = true;
+            var matching:Boolean = true;
+
+            loader.contentLoaderInfo.addEventListener(ProgressEvent.PROGRESS, function(e:ProgressEvent):void {
+                if (events == 0) {
+                    trace("First progress: " + e.bytesLoaded + ", bytesTotal known: " + (e.bytesTotal > 0));
+                }
+                events++;
+                if (e.bytesLoaded > 0 && e.bytesLoaded < e.bytesTotal) {
+                    partial++;
+                }
+                if (e.bytesLoaded < last) {
+                    monotonic = false;
+                    trace("bytesLoaded went back from " + last + " to " + e.bytesLoaded);
+                }
+                last = e.bytesLoaded;
+                if (loader.contentLoaderInfo.bytesLoaded != e.bytesLoaded) {
+                    matching = false;
+                    trace("LoaderInfo.bytesLoaded is " + loader.contentLoaderInfo.bytesLoaded + ", not " + e.bytesLoaded);
+                }
+            });
+            loader.contentLoaderInfo.addEventListener(Event.COMPLETE, function(e:Event):void {
+                var info = loader.contentLoaderInfo;
+                trace("Complete: " + (info.bytesLoaded == info.bytesTotal));
+                trace("bytesLoaded never went back: " + monotonic);
+                trace("LoaderInfo.bytesLoaded matched every event: " + matching);
+                trace("Rose in several steps: " + (partial >= 3));
+            });
+            loader.load(new URLRequest("child.swf"));
+        }
+    }
+}
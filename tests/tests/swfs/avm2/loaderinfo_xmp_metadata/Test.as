@@ -0,0 +1,12 @@
+package {
+   import flash.display.Sprite;
+
+   public class Test extends Sprite {
+      namespace ruffle = "__ruffle__";
+
+      public function Test() {
+         trace("xmpMetadata" in loaderInfo);
+         trace(loaderInfo.ruffle::xmpMetadata);
+      }
+   }
+}
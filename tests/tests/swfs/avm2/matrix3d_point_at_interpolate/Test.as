@@ -0,0 +1,77 @@
+package {
+    import flash.display.MovieClip;
+    import flash.geom.Matrix3D;
+    import flash.geom.Utils3D;
+    import flash.geom.Vector3D;
+
+    public class Test extends MovieClip {
+        public function Test() {
+            trace("// pointAt");
+            var m:Matrix3D = translated(10, 20, 30);
+            m.pointAt(new Vector3D(10, 20, 130));
+            show("default, straight ahead", m);
+
+            m = new Matrix3D();
+            m.pointAt(new Vector3D(100, 0, 0));
+            show("default, to the side", m);
+
+            m = new Matrix3D(Vector.<Number>([2, 0, 0, 0, 0, 3, 0, 0, 0, 0, 4, 0, 5, 6, 7, 1]));
+            m.pointAt(new Vector3D(5, 106, 7));
+            show("default, scaled", m);
+
+            m = new Matrix3D();
+            m.pointAt(new Vector3D(0, 0, 50), new Vector3D(0, 0, 1));
+            show("custom at", m);
+
+            m = new Matrix3D();
+            m.pointAt(new Vector3D(50, 0, 50), new Vector3D(1, 0, 0), new Vector3D(0, 0, 1));
+            show("custom at and up", m);
+
+            m = translated(1, 2, 3);
+            m.pointAt(new Vector3D(1, 2, 3));
+            show("at own position", m);
+
+            trace("// interpolate");
+            var from:Matrix3D = new Matrix3D();
+            // 90 degrees around z, scaled by 2.
+            var to:Matrix3D = new Matrix3D(Vector.<Number>([0, 2, 0, 0, -2, 0, 0, 0, 0, 0, 2, 0, 100, 50, -20, 1]));
+            show("interpolate 0", Matrix3D.interpolate(from, to, 0));
+            show("interpolate 0.5", Matrix3D.interpolate(from, to, 0.5));
+            show("interpolate 1", Matrix3D.interpolate(from, to, 1));
+            show("interpolate 0.25", Matrix3D.interpolate(from, to, 0.25));
+            show("from is unchanged", from);
+
+            trace("// interpolateTo");
+            m = new Matrix3D();
+            m.interpolateTo(to, 0.25);
+            show("interpolateTo 0.25", m);
+            m = to.clone();
+            m.interpolateTo(new Matrix3D(), 0.5);
+            show("interpolateTo 0.5 from rotated", m);
+
+            trace("// pointTowards");
+            m = new Matrix3D();
+            show("pointTowards 0", Utils3D.pointTowards(0, m, new Vector3D(100, 0, 0)));
+            show("pointTowards 0.5", Utils3D.pointTowards(0.5, m, new Vector3D(100, 0, 0)));
+            show("pointTowards 1", Utils3D.pointTowards(1, m, new Vector3D(100, 0, 0)));
+            show("pointTowards 0.5 custom", Utils3D.pointTowards(0.5, m, new Vector3D(0, 100, 0), new Vector3D(0, 0, 1), new Vector3D(0, 1, 0)));
+            show("mat is unchanged", m);
+        }
+
+        private function translated(x:Number, y:Number, z:Number):Matrix3D {
+            var m:Matrix3D = new Matrix3D();
+            m.position = new Vector3D(x, y, z);
+            return m;
+        }
+
+        private function show(label:String, m:Matrix3D):void {
+            var values:Array = [];
+            for each (var value:Number in m.rawData) {
+                // Round off floating point noise.
+                value = Math.round(value * 1000) / 1000;
+                values.push(value == 0 ? 0 : value);
+            }
+            trace(label + ": " + values.join(","));
+        }
+    }
+}
@@ -0,0 +1,35 @@
+package {
+    import flash.display.Sprite;
+    import flash.events.MouseEvent;
+
+    public class Test extends Sprite {
+        public function Test() {
+            addChild(box("a", 0));
+            addChild(box("b", 200));
+
+            var types:Array = [
+                MouseEvent.RIGHT_MOUSE_DOWN, MouseEvent.RIGHT_MOUSE_UP, MouseEvent.RIGHT_CLICK,
+                MouseEvent.MIDDLE_MOUSE_DOWN, MouseEvent.MIDDLE_MOUSE_UP, MouseEvent.MIDDLE_CLICK,
+                MouseEvent.MOUSE_DOWN, MouseEvent.CLICK
+            ];
+            for each (var type:String in types) {
+                stage.addEventListener(type, onMouse);
+            }
+        }
+
+        private function box(name:String, x:int):Sprite {
+            var sprite:Sprite = new Sprite();
+            sprite.name = name;
+            sprite.x = x;
+            sprite.graphics.beginFill(0xFF0000);
+            sprite.graphics.drawRect(0, 0, 100, 100);
+            sprite.graphics.endFill();
+            return sprite;
+        }
+
+        private function onMouse(e:MouseEvent):void {
+            var target:String = e.target == stage ? "stage" : e.target.name;
+            trace(e.type + ": " + target + ", bubbles " + e.bubbles);
+        }
+    }
+}
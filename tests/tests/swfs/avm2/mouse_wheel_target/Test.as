@@ -0,0 +1,65 @@
+package {
+    import flash.display.Sprite;
+    import flash.events.MouseEvent;
+    import flash.text.TextField;
+
+    public class Test extends Sprite {
+        private var outer:Sprite;
+        private var inner:Sprite;
+        private var tf:TextField;
+        private var wheels:int = 0;
+
+        public function Test() {
+            outer = box("outer", 200);
+            inner = box("inner", 100);
+            outer.addChild(inner);
+            inner.addChild(box("child", 50));
+            addChild(outer);
+
+            tf = new TextField();
+            tf.name = "tf";
+            tf.x = 300;
+            tf.width = 100;
+            tf.height = 40;
+            tf.multiline = true;
+            tf.text = "1\n2\n3\n4\n5\n6\n7\n8\n9\n10";
+            addChild(tf);
+            trace("tf.mouseWheelEnabled: " + tf.mouseWheelEnabled);
+
+            stage.addEventListener(MouseEvent.MOUSE_WHEEL, onWheel);
+        }
+
+        private function box(name:String, size:int):Sprite {
+            var sprite:Sprite = new Sprite();
+            sprite.name = name;
+            sprite.graphics.beginFill(0xFF0000);
+            sprite.graphics.drawRect(0, 0, size, size);
+            sprite.graphics.endFill();
+            return sprite;
+        }
+
+        private function onWheel(e:MouseEvent):void {
+            var target:String = e.target == stage ? "stage" : e.target.name;
+            var message:String = "mouseWheel: target " + target + ", delta " + e.delta;
+            if (e.target == tf) {
+                message += ", scrollV " + tf.scrollV;
+            }
+            trace(message);
+
+            wheels++;
+            if (wheels == 3) {
+                trace("inner.mouseChildren = false");
+                inner.mouseChildren = false;
+            } else if (wheels == 4) {
+                trace("outer.mouseChildren = false");
+                outer.mouseChildren = false;
+            } else if (wheels == 5) {
+                trace("outer.mouseEnabled = false");
+                outer.mouseEnabled = false;
+            } else if (wheels == 9) {
+                trace("tf.mouseWheelEnabled = false");
+                tf.mouseWheelEnabled = false;
+            }
+        }
+    }
+}
@@ -0,0 +1,33 @@
+package {
+    import flash.display.NativeMenu;
+    import flash.display.NativeMenuItem;
+    import flash.display.Sprite;
+
+    public class Test extends Sprite {
+        public function Test() {
+            var menu: NativeMenu = new NativeMenu();
+            var item: NativeMenuItem = new NativeMenuItem("Self");
+            item.submenu = menu;
+            try {
+                menu.addItem(item);
+            } catch (e: ArgumentError) {
+                trace("addItem: " + e.errorID);
+            }
+            trace("items: " + menu.numItems);
+            item.submenu = null;
+
+            var child: NativeMenu = new NativeMenu();
+            menu.addSubmenu(child, "Child");
+            var loop: NativeMenuItem = child.addItem(new NativeMenuItem("Loop"));
+            try {
+                loop.submenu = menu;
+            } catch (e: ArgumentError) {
+                trace("submenu: " + e.errorID);
+            }
+            trace("loop submenu: " + loop.submenu);
+
+            stage.nativeWindow.menu = menu;
+            trace("installed: " + (stage.nativeWindow.menu == menu));
+        }
+    }
+}
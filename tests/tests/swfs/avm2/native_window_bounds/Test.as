@@ -0,0 +1,19 @@
+package {
+    import flash.display.MovieClip;
+    import flash.display.NativeWindow;
+    import flash.geom.Rectangle;
+
+    public class Test extends MovieClip {
+        public function Test() {
+            trace("isSupported: " + NativeWindow.isSupported);
+
+            var window:NativeWindow = stage.nativeWindow;
+            window.bounds = new Rectangle(10, 20, 300, 200);
+            trace("After setting bounds: " + window.x + ", " + window.y + ", " + window.width + ", " + window.height);
+
+            window.x = 40;
+            window.height = 250;
+            trace("After setting x and height: " + window.x + ", " + window.y + ", " + window.width + ", " + window.height);
+        }
+    }
+}
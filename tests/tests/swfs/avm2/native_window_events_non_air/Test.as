@@ -0,0 +1,34 @@
+package {
+    import flash.display.MovieClip;
+    import flash.display.NativeWindow;
+    import flash.events.Event;
+    import flash.events.NativeWindowBoundsEvent;
+
+    public class Test extends MovieClip {
+        private var frames:int = 0;
+
+        public function Test() {
+            var window:NativeWindow = stage.nativeWindow;
+            window.addEventListener(NativeWindowBoundsEvent.MOVE, onBounds);
+            window.addEventListener(NativeWindowBoundsEvent.RESIZE, onBounds);
+            window.addEventListener(Event.ACTIVATE, onActivation);
+            window.addEventListener(Event.DEACTIVATE, onActivation);
+            addEventListener(Event.ENTER_FRAME, onEnterFrame);
+        }
+
+        private function onBounds(e:NativeWindowBoundsEvent):void {
+            trace(e.type + " " + e.beforeBounds + " -> " + e.afterBounds);
+        }
+
+        private function onActivation(e:Event):void {
+            trace(e.type + ", active: " + stage.nativeWindow.active);
+        }
+
+        private function onEnterFrame(e:Event):void {
+            frames++;
+            if (frames == 7) {
+                trace("Done");
+            }
+        }
+    }
+}
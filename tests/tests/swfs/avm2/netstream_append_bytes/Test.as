@@ -0,0 +1,89 @@
+package {
+    import flash.display.MovieClip;
+    import flash.events.Event;
+    import flash.events.NetStatusEvent;
+    import flash.net.NetConnection;
+    import flash.net.NetStream;
+    import flash.net.NetStreamAppendBytesAction;
+    import flash.utils.ByteArray;
+
+    public class Test extends MovieClip {
+        private var stream:NetStream;
+        private var framesToWait:int = -1;
+
+        public function Test() {
+            var connection:NetConnection = new NetConnection();
+            connection.connect(null);
+            stream = new NetStream(connection);
+            stream.client = {
+                onTick: function(n:Number):void {
+                    trace("onTick " + n + " at " + stream.time);
+                    if (n == 3) {
+                        framesToWait = 3;
+                    }
+                }
+            };
+            stream.addEventListener(NetStatusEvent.NET_STATUS, function(e:NetStatusEvent):void {
+                trace(e.info.code + " at " + stream.time);
+            });
+            addEventListener(Event.ENTER_FRAME, onEnterFrame);
+
+            stream.play(null);
+            stream.appendBytesAction(NetStreamAppendBytesAction.RESET_BEGIN);
+
+            var bytes:ByteArray = new ByteArray();
+            // FLV header, without audio or video, followed by the size of the "previous" tag.
+            bytes.writeUTFBytes("FLV");
+            bytes.writeByte(1);
+            bytes.writeByte(0);
+            bytes.writeUnsignedInt(9);
+            bytes.writeUnsignedInt(0);
+            writeTick(bytes, 0, 1);
+            writeTick(bytes, 100, 2);
+            writeTick(bytes, 200, 3);
+            stream.appendBytes(bytes);
+        }
+
+        private function onEnterFrame(e:Event):void {
+            if (framesToWait < 0 || --framesToWait > 0) {
+                return;
+            }
+            removeEventListener(Event.ENTER_FRAME, onEnterFrame);
+
+            // Time stands still while the stream waits for more data.
+            trace("Waited for data at " + stream.time);
+
+            // Tags continue on from the current time, whatever their timestamps say.
+            stream.appendBytesAction(NetStreamAppendBytesAction.RESET_SEEK);
+            var bytes:ByteArray = new ByteArray();
+            writeTick(bytes, 5000, 4);
+            writeTick(bytes, 5100, 5);
+            stream.appendBytes(bytes);
+            stream.appendBytesAction(NetStreamAppendBytesAction.END_SEQUENCE);
+        }
+
+        // Writes a script tag calling `onTick(n)`, and the size of the tag after it.
+        private static function writeTick(bytes:ByteArray, timestamp:uint, n:Number):void {
+            var data:ByteArray = new ByteArray();
+            data.writeByte(2);
+            data.writeShort(6);
+            data.writeUTFBytes("onTick");
+            data.writeByte(0);
+            data.writeDouble(n);
+
+            bytes.writeByte(18);
+            writeUint24(bytes, data.length);
+            writeUint24(bytes, timestamp);
+            bytes.writeByte(0);
+            writeUint24(bytes, 0);
+            bytes.writeBytes(data);
+            bytes.writeUnsignedInt(11 + data.length);
+        }
+
+        private static function writeUint24(bytes:ByteArray, value:uint):void {
+            bytes.writeByte(value >> 16);
+            bytes.writeByte(value >> 8);
+            bytes.writeByte(value);
+        }
+    }
+}
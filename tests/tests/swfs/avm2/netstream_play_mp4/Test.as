@@ -0,0 +1,28 @@
+package {
+    import flash.display.MovieClip;
+    import flash.net.NetConnection;
+    import flash.net.NetStream;
+
+    public class Test extends MovieClip {
+
+        public function Test() {
+            super();
+            var con:NetConnection = new NetConnection();
+            con.connect(null);
+            var stream:NetStream = new NetStream(con);
+            stream.client = {
+                onMetaData: function(info:Object):void {
+                    trace("onMetaData");
+                    trace("duration: " + info.duration);
+                    trace("audiocodecid: " + info.audiocodecid);
+                    trace("videocodecid: " + info.videocodecid);
+                    trace("width: " + info.width);
+                    trace("height: " + info.height);
+                }
+            };
+            // An AAC track of a second, without any samples.
+            stream.play("test_audio.mp4");
+            trace("playing");
+        }
+    }
+}
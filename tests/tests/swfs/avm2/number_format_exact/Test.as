@@ -0,0 +1,44 @@
+package {
+	import flash.display.MovieClip;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			trace("// toFixed");
+			trace((1.005).toFixed(2));
+			trace((1.45).toFixed(1));
+			trace((2.5).toFixed(0));
+			trace((-2.5).toFixed(0));
+			trace((99.99).toFixed(1));
+			trace((0.1).toFixed(20));
+			trace((1e21).toFixed(2));
+
+			trace("// toPrecision");
+			trace((123.456).toPrecision(4));
+			trace((123.456).toPrecision(2));
+			trace((0.000123).toPrecision(2));
+			trace((1.234e-7).toPrecision(2));
+			trace((-99.99).toPrecision(3));
+
+			trace("// toExponential");
+			trace((123456).toExponential(2));
+			trace((-0.00015).toExponential(1));
+			trace((9.96).toExponential(1));
+			trace((5).toExponential(0));
+
+			trace("// toString(radix)");
+			trace((255.9).toString(16));
+			trace((-255).toString(16));
+			trace((35).toString(36));
+			trace((4294967296).toString(2));
+			trace(Math.pow(2, 100).toString(16));
+
+			trace("// String(n)");
+			trace(String(0.1 + 0.2));
+			trace(String(123456789012345680000));
+			trace(String(1e21));
+			trace(String(0.000001));
+			trace(String(1.5e-7));
+			trace(String(-1.5));
+		}
+	}
+}
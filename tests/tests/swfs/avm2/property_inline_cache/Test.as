@@ -0,0 +1,85 @@
+package {
+	import flash.display.MovieClip;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			var objects:Array = [
+				new SlotClass(), new GetterClass(), new SubGetterClass(), new ConstClass(),
+				new DynamicClass(), {value: "plain object"}, new SlotClass()
+			];
+
+			trace("// Reading `value` from the same site on 7 objects of 6 shapes, twice");
+			for (var pass:int = 0; pass < 2; pass++) {
+				for each (var obj:Object in objects) {
+					trace(readValue(obj));
+				}
+			}
+
+			trace("// Writing `value` from the same site");
+			for each (obj in objects) {
+				try {
+					writeValue(obj, "written");
+					trace(readValue(obj));
+				} catch (e:Error) {
+					trace(e.errorID);
+				}
+			}
+
+			trace("// Dynamic properties are not cached as traits");
+			var dyn:DynamicClass = new DynamicClass();
+			trace(readOther(dyn));
+			dyn.other = "added";
+			trace(readOther(dyn));
+			delete dyn.other;
+			trace(readOther(dyn));
+
+			trace("// Prototype properties");
+			trace(readOther(new SlotClass()));
+			SlotClass.prototype.other = "from prototype";
+			trace(readOther(new SlotClass()));
+			delete SlotClass.prototype.other;
+			trace(readOther(new SlotClass()));
+		}
+
+		private function readValue(obj:Object):* {
+			return obj.value;
+		}
+
+		private function writeValue(obj:Object, value:*):void {
+			obj.value = value;
+		}
+
+		private function readOther(obj:Object):* {
+			return obj.other;
+		}
+	}
+}
+
+class SlotClass {
+	public var value:String = "slot";
+}
+
+class GetterClass {
+	protected var stored:String = "getter";
+
+	public function get value():String {
+		return stored;
+	}
+
+	public function set value(v:String):void {
+		stored = v + " via setter";
+	}
+}
+
+class SubGetterClass extends GetterClass {
+	override public function get value():String {
+		return "overridden " + stored;
+	}
+}
+
+class ConstClass {
+	public const value:String = "const";
+}
+
+dynamic class DynamicClass {
+}
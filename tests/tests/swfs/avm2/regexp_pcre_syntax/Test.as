@@ -0,0 +1,37 @@
+package {
+	public class Test {
+	}
+}
+
+trace("// exec() with named groups");
+var date:RegExp = new RegExp("(?P<year>\\d{4})-(?P<month>\\d\\d)");
+var result = date.exec("Due 2023-07 or later");
+trace(result);
+trace("year", result.year, "month", result.month, "index", result.index);
+
+trace("// exec() with a named backreference");
+var quoted:RegExp = new RegExp("(?P<quote>['\"])(?P<text>.*?)(?P=quote)");
+result = quoted.exec("say 'it''s' \"hi\"");
+trace(result[0], result.quote, result.text, result.index);
+
+trace("// x flag");
+var words:RegExp = new RegExp("(?P<word> \\w+ ) \\s+ # a word, then spaces\n (\\d+)", "x");
+trace("extended", words.extended);
+result = words.exec("abc   42");
+trace(result);
+trace("word", result.word);
+trace("without x:", new RegExp("a b").test("ab"), new RegExp("a b", "x").test("ab"));
+trace("spaces in a class are kept:", new RegExp("[ ]", "x").test("a b"));
+
+trace("// s flag");
+trace("dotall", new RegExp("a.b", "s").dotall, new RegExp("a.b").dotall);
+trace("a\\nb with s:", new RegExp("a.b", "s").test("a\nb"));
+trace("a\\nb without s:", new RegExp("a.b").test("a\nb"));
+trace("a\\rb without s:", new RegExp("a.b").test("a\rb"));
+
+trace("// \\s and \\S");
+trace("[\\S]+:", new RegExp("[\\S]+").exec("  ab\tc ")[0]);
+trace("[^\\S]+ length:", new RegExp("[^\\S]+").exec("ab \t c")[0].length);
+trace("[\\S\\d] on a space:", new RegExp("[\\S\\d]").test(" "));
+trace("\\s on a vertical tab:", new RegExp("\\s").test("\x0b"));
+trace("[\\S] on a vertical tab:", new RegExp("[\\S]").test("\x0b"));
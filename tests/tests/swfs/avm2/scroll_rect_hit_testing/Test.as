@@ -0,0 +1,76 @@
+package {
+    import flash.display.Sprite;
+    import flash.events.MouseEvent;
+    import flash.geom.Rectangle;
+
+    public class Test extends Sprite {
+        private var container:Sprite;
+        private var big:Sprite;
+        private var big2:Sprite;
+        private var clicks:int = 0;
+
+        public function Test() {
+            // Shows (0, 0)-(100, 100) of a 300x300 child at the origin.
+            container = new Sprite();
+            container.name = "container";
+            big = box("big");
+            container.addChild(big);
+            container.scrollRect = new Rectangle(0, 0, 100, 100);
+            addChild(container);
+
+            // Shows (50, 50)-(150, 150) of a 300x300 child, at x = 200.
+            var container2:Sprite = new Sprite();
+            container2.name = "container2";
+            container2.x = 200;
+            big2 = box("big2");
+            container2.addChild(big2);
+            container2.scrollRect = new Rectangle(50, 50, 100, 100);
+            addChild(container2);
+
+            // A new scrollRect only takes effect once the stage has been rendered,
+            // so everything is tested from clicks, which come after a render.
+            stage.addEventListener(MouseEvent.CLICK, onClick);
+        }
+
+        private function box(name:String):Sprite {
+            var sprite:Sprite = new Sprite();
+            sprite.name = name;
+            sprite.graphics.beginFill(0xFF0000);
+            sprite.graphics.drawRect(0, 0, 300, 300);
+            sprite.graphics.endFill();
+            return sprite;
+        }
+
+        private function testPoint(target:Sprite, x:Number, y:Number):void {
+            trace(target.name + ".hitTestPoint(" + x + ", " + y + ", true): " + target.hitTestPoint(x, y, true));
+        }
+
+        private function onClick(e:MouseEvent):void {
+            if (clicks == 0) {
+                trace("// hitTestPoint");
+                testPoint(big, 50, 50);
+                testPoint(big, 150, 50);
+                testPoint(big, 50, 150);
+                testPoint(container, 150, 50);
+                testPoint(big2, 250, 50);
+                testPoint(big2, 350, 50);
+                testPoint(big2, 250, 150);
+                trace("// Clicks");
+            } else if (clicks == 5) {
+                trace("// hitTestPoint without a scrollRect");
+                testPoint(big, 150, 50);
+                testPoint(big, 50, 150);
+                trace("// Clicks without a scrollRect");
+            }
+
+            var target:String = e.target == stage ? "stage" : e.target.name;
+            trace("click at (" + e.stageX + ", " + e.stageY + "): " + target);
+
+            clicks++;
+            if (clicks == 5) {
+                trace("// container.scrollRect = null");
+                container.scrollRect = null;
+            }
+        }
+    }
+}
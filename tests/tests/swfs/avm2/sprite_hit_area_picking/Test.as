@@ -0,0 +1,55 @@
+package {
+    import flash.display.Sprite;
+    import flash.events.MouseEvent;
+
+    public class Test extends Sprite {
+        private var owner1:Sprite;
+        private var owner2:Sprite;
+        private var hit:Sprite;
+        private var hit2:Sprite;
+        private var clicks:int = 0;
+
+        public function Test() {
+            owner1 = box("owner1", 0, 0);
+            owner2 = box("owner2", 200, 0);
+            hit = box("hit", 0, 200);
+            hit2 = box("hit2", 200, 200);
+
+            trace("// owner1 and owner2 share hit");
+            owner1.hitArea = hit;
+            owner2.hitArea = hit;
+
+            stage.addEventListener(MouseEvent.CLICK, onClick);
+        }
+
+        private function box(name:String, x:int, y:int):Sprite {
+            var sprite:Sprite = new Sprite();
+            sprite.name = name;
+            sprite.x = x;
+            sprite.y = y;
+            sprite.graphics.beginFill(0xFF0000);
+            sprite.graphics.drawRect(0, 0, 100, 100);
+            sprite.graphics.endFill();
+            addChild(sprite);
+            return sprite;
+        }
+
+        private function onClick(e:MouseEvent):void {
+            var target:String = e.target == stage ? "stage" : e.target.name;
+            trace("click at (" + e.stageX + ", " + e.stageY + "): " + target);
+
+            clicks++;
+            if (clicks == 2) {
+                trace("// owner1.hitArea = null, owner2 still uses hit");
+                owner1.hitArea = null;
+            } else if (clicks == 4) {
+                trace("// owner2.hitArea reassigned to hit2");
+                owner2.hitArea = hit2;
+            } else if (clicks == 6) {
+                trace("// owner1 and owner2 share hit2, then owner2 reassigned to hit");
+                owner1.hitArea = hit2;
+                owner2.hitArea = hit;
+            }
+        }
+    }
+}
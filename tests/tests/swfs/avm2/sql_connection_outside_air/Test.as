@@ -0,0 +1,20 @@
+package {
+    import flash.data.SQLConnection;
+    import flash.display.Sprite;
+    import flash.errors.SQLError;
+
+    public class Test extends Sprite {
+        public function Test() {
+            trace("isSupported: " + SQLConnection.isSupported);
+
+            var connection: SQLConnection = new SQLConnection();
+            try {
+                connection.open(null);
+                trace("opened");
+            } catch (e: SQLError) {
+                trace("SQLError: " + e.errorID + " (" + e.details + ")");
+            }
+            trace("connected: " + connection.connected);
+        }
+    }
+}
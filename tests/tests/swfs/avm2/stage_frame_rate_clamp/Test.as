@@ -0,0 +1,52 @@
+package {
+    import flash.display.MovieClip;
+    import flash.events.Event;
+    import flash.events.TimerEvent;
+    import flash.utils.Timer;
+
+    // Runs at 10fps, and the test ticks 100ms at a time.
+    public class Test extends MovieClip {
+        private var frames:int = 0;
+        private var timer:Timer = new Timer(100);
+
+        public function Test() {
+            trace("Initial frameRate: " + stage.frameRate);
+            setFrameRate(5000);
+            setFrameRate(1000.5);
+            setFrameRate(0);
+            setFrameRate(-1);
+            setFrameRate(0.001);
+            setFrameRate(30);
+            setFrameRate(NaN);
+            setFrameRate(Infinity);
+            setFrameRate(-Infinity);
+
+            // At the lowest rate, no frame is due for 100 seconds.
+            setFrameRate(0);
+
+            addEventListener(Event.ENTER_FRAME, function(e:Event):void {
+                frames++;
+            });
+            timer.addEventListener(TimerEvent.TIMER, onTimer);
+            timer.start();
+        }
+
+        private function setFrameRate(value:Number):void {
+            stage.frameRate = value;
+            trace("frameRate = " + value + ": " + stage.frameRate);
+        }
+
+        // The timer fires once per tick, after that tick's frames.
+        private function onTimer(e:TimerEvent):void {
+            trace("Timer " + timer.currentCount + ": " + frames + " frame(s)");
+            frames = 0;
+
+            if (timer.currentCount == 6) {
+                // The time banked at 0.01fps must not turn into a burst of frames.
+                setFrameRate(10);
+            } else if (timer.currentCount == 10) {
+                timer.stop();
+            }
+        }
+    }
+}
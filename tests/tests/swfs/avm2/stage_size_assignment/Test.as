@@ -0,0 +1,45 @@
+package {
+    import flash.display.MovieClip;
+    import flash.display.StageScaleMode;
+    import flash.events.Event;
+
+    public class Test extends MovieClip {
+        public function Test() {
+            stage.addEventListener(Event.RESIZE, function(e:Event):void {
+                trace("resize: " + size());
+            });
+
+            trace("// showAll");
+            trace(stage.scaleMode + ": " + size());
+            stage.stageWidth = 100;
+            stage.stageHeight = 100;
+            trace("after assigning 100x100: " + size());
+
+            trace("// noScale");
+            stage.scaleMode = StageScaleMode.NO_SCALE;
+            trace(stage.scaleMode + ": " + size());
+            stage.stageWidth = 320;
+            trace("after assigning stageWidth = 320: " + size());
+            stage.stageHeight = 240;
+            trace("after assigning stageHeight = 240: " + size());
+
+            trace("// back to showAll");
+            stage.scaleMode = StageScaleMode.SHOW_ALL;
+            trace(stage.scaleMode + ": " + size());
+
+            trace("// exactFit");
+            stage.scaleMode = StageScaleMode.EXACT_FIT;
+            trace(stage.scaleMode + ": " + size());
+            stage.stageWidth = 100;
+            trace("after assigning stageWidth = 100: " + size());
+
+            trace("// noScale again");
+            stage.scaleMode = StageScaleMode.NO_SCALE;
+            trace(stage.scaleMode + ": " + size());
+        }
+
+        private function size():String {
+            return stage.stageWidth + "x" + stage.stageHeight;
+        }
+    }
+}
@@ -0,0 +1,28 @@
+package {
+	import flash.display.MovieClip;
+	import flash.events.TextEvent;
+	import flash.text.TextField;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			var tf:TextField = new TextField();
+			tf.width = 300;
+			tf.height = 20;
+			tf.htmlText = "<a href=\"event:hello\">click</a> and some plain text after the link";
+			tf.addEventListener(TextEvent.LINK, function(e:TextEvent):void {
+				trace("link: " + e.text + " (target is tf: " + (e.target == tf) + ", bubbles: " + e.bubbles + ")");
+			});
+			addChild(tf);
+
+			var tf2:TextField = new TextField();
+			tf2.y = 50;
+			tf2.width = 300;
+			tf2.height = 20;
+			tf2.htmlText = "<a href=\"event:\">empty</a>";
+			tf2.addEventListener(TextEvent.LINK, function(e:TextEvent):void {
+				trace("link: \"" + e.text + "\"");
+			});
+			addChild(tf2);
+		}
+	}
+}
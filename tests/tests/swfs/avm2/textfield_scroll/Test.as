@@ -0,0 +1,84 @@
+package {
+	import flash.display.MovieClip;
+	import flash.events.Event;
+	import flash.text.TextField;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			var tf:TextField = new TextField();
+			tf.width = 100;
+			tf.height = 40;
+			tf.multiline = true;
+			tf.addEventListener(Event.SCROLL, function(e:Event):void {
+				trace("scroll event");
+			});
+			addChild(tf);
+
+			var text:String = "";
+			for (var i:int = 1; i <= 20; i++) {
+				text += "line " + i + "\n";
+			}
+			tf.text = text;
+
+			trace("// scrollV");
+			trace(tf.scrollV);
+			trace(tf.maxScrollV > 1);
+
+			trace("// scrollV = 3");
+			tf.scrollV = 3;
+			trace(tf.scrollV);
+
+			trace("// scrollV = 3 (unchanged)");
+			tf.scrollV = 3;
+			trace(tf.scrollV);
+
+			trace("// appendText");
+			tf.appendText("line 21\n");
+			trace(tf.scrollV);
+
+			trace("// scrollV = 1000");
+			tf.scrollV = 1000;
+			trace(tf.scrollV == tf.maxScrollV);
+
+			trace("// scrollV = -5");
+			tf.scrollV = -5;
+			trace(tf.scrollV);
+
+			trace("// scrollV = 3, then text is shortened");
+			tf.scrollV = 3;
+			tf.text = "short";
+			trace(tf.scrollV);
+			trace(tf.maxScrollV);
+
+			var tf2:TextField = new TextField();
+			tf2.width = 50;
+			tf2.height = 20;
+			tf2.text = "abcdefghijklmnopqrstuvwxyz0123456789";
+			tf2.addEventListener(Event.SCROLL, function(e:Event):void {
+				trace("scroll event");
+			});
+			addChild(tf2);
+
+			trace("// scrollH");
+			trace(tf2.scrollH);
+			trace(tf2.maxScrollH > 0);
+
+			trace("// scrollH = -5");
+			tf2.scrollH = -5;
+			trace(tf2.scrollH);
+
+			trace("// scrollH = 10");
+			tf2.scrollH = 10;
+			trace(tf2.scrollH);
+
+			trace("// scrollH = 100000");
+			tf2.scrollH = 100000;
+			trace(tf2.scrollH == tf2.maxScrollH);
+
+			trace("// wordWrap = true");
+			tf2.wordWrap = true;
+			trace(tf2.scrollH);
+			trace(tf2.maxScrollH);
+		}
+	}
+}
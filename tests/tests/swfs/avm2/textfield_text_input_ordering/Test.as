@@ -0,0 +1,32 @@
+package {
+    import flash.display.Sprite;
+    import flash.events.Event;
+    import flash.events.TextEvent;
+    import flash.text.TextField;
+    import flash.text.TextFieldType;
+
+    public class Test extends Sprite {
+        public function Test() {
+            var tf:TextField = new TextField();
+            tf.type = TextFieldType.INPUT;
+            addChild(tf);
+
+            var pastes:int = 0;
+            tf.addEventListener(TextEvent.TEXT_INPUT, function(e:TextEvent):void {
+                trace("textInput: " + e.text + " (length " + e.text.length + ", cancelable " + e.cancelable + "), text before: " + tf.text);
+                // Pasted text arrives as a single event; cancel the second paste.
+                if (e.text.length > 1 && e.text.charCodeAt(0) < 0xD800) {
+                    pastes++;
+                    if (pastes == 2) {
+                        trace("Cancelling paste");
+                        e.preventDefault();
+                    }
+                }
+            });
+            tf.addEventListener(Event.CHANGE, function(e:Event):void {
+                trace("change: " + tf.text + " (length " + tf.text.length + "), caret: " + tf.caretIndex);
+            });
+            stage.focus = tf;
+        }
+    }
+}
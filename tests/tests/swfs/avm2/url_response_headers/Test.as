@@ -0,0 +1,46 @@
+package {
+    import flash.display.Sprite;
+    import flash.events.Event;
+    import flash.events.HTTPStatusEvent;
+    import flash.net.URLLoader;
+    import flash.net.URLRequest;
+    import flash.net.URLRequestHeader;
+    import flash.net.URLStream;
+
+    public class Test extends Sprite {
+        private var steps:Array = [
+            function():void { loadWith(new URLLoader(), "URLLoader", "item.txt", true); },
+            function():void { loadWith(new URLStream(), "URLStream", "item.txt", true); },
+            function():void { loadWith(new URLLoader(), "URLLoader redirect followed", "old.txt", true); },
+            function():void { loadWith(new URLLoader(), "URLLoader redirect not followed", "old.txt", false); }
+        ];
+
+        public function Test() {
+            nextStep();
+        }
+
+        private function nextStep():void {
+            if (steps.length == 0) {
+                trace("Done");
+                return;
+            }
+            steps.shift()();
+        }
+
+        private function loadWith(loader:Object, name:String, url:String, followRedirects:Boolean):void {
+            var request:URLRequest = new URLRequest(url);
+            request.followRedirects = followRedirects;
+            loader.addEventListener(HTTPStatusEvent.HTTP_STATUS, function(e:HTTPStatusEvent):void {
+                trace(name + ": status " + e.status);
+                for each (var header:URLRequestHeader in e.responseHeaders) {
+                    trace(name + ":   " + header.name + ": " + header.value);
+                }
+            });
+            loader.addEventListener(Event.COMPLETE, function(e:Event):void {
+                trace(name + ": complete");
+                nextStep();
+            });
+            loader.load(request);
+        }
+    }
+}
@@ -0,0 +1,104 @@
+package {
+    import flash.display.MovieClip;
+    import flash.errors.IOError;
+    import flash.events.Event;
+    import flash.events.HTTPStatusEvent;
+    import flash.events.IOErrorEvent;
+    import flash.events.ProgressEvent;
+    import flash.net.URLRequest;
+    import flash.net.URLStream;
+
+    public class Test extends MovieClip {
+        private var stream:URLStream = new URLStream();
+        private var step:String;
+        private var steps:Array = [loadAndClose, closeImmediately, loadTwice, loadEmpty, loadMissing];
+        private var waitFrames:int = 0;
+
+        public function Test() {
+            stream.addEventListener(Event.OPEN, onEvent);
+            stream.addEventListener(ProgressEvent.PROGRESS, onEvent);
+            stream.addEventListener(HTTPStatusEvent.HTTP_STATUS, onEvent);
+            stream.addEventListener(Event.COMPLETE, onEvent);
+            stream.addEventListener(IOErrorEvent.IO_ERROR, onEvent);
+            addEventListener(Event.ENTER_FRAME, onEnterFrame);
+            nextStep();
+        }
+
+        private function nextStep():void {
+            if (steps.length == 0) {
+                trace("Done");
+                return;
+            }
+            steps.shift()();
+        }
+
+        private function onEnterFrame(e:Event):void {
+            if (waitFrames > 0 && --waitFrames == 0) {
+                trace(step + ": connected=" + stream.connected);
+                nextStep();
+            }
+        }
+
+        private function onEvent(e:Event):void {
+            var line:String = step + ": " + e.type;
+            if (e is ProgressEvent) {
+                line += " " + ProgressEvent(e).bytesLoaded;
+            } else if (e is HTTPStatusEvent) {
+                line += " " + HTTPStatusEvent(e).status;
+            }
+            trace(line + ", connected=" + stream.connected);
+
+            if (e.type == Event.COMPLETE) {
+                trace(step + ": read \"" + stream.readUTFBytes(stream.bytesAvailable) + "\"");
+                if (step == "loadAndClose") {
+                    stream.close();
+                    trace(step + ": closed, connected=" + stream.connected);
+                    tryClose();
+                }
+                waitFrames = 2;
+            } else if (e.type == IOErrorEvent.IO_ERROR) {
+                waitFrames = 2;
+            }
+        }
+
+        private function tryClose():void {
+            try {
+                stream.close();
+                trace(step + ": closed again");
+            } catch (e:IOError) {
+                trace(step + ": " + e.errorID + " " + e.message);
+            }
+        }
+
+        private function loadAndClose():void {
+            step = "loadAndClose";
+            stream.load(new URLRequest("item.txt"));
+            trace(step + ": loading, connected=" + stream.connected);
+        }
+
+        private function closeImmediately():void {
+            step = "closeImmediately";
+            stream.load(new URLRequest("item.txt"));
+            stream.close();
+            trace(step + ": closed, connected=" + stream.connected);
+            tryClose();
+            waitFrames = 3;
+        }
+
+        private function loadTwice():void {
+            step = "loadTwice";
+            stream.load(new URLRequest("first.txt"));
+            stream.load(new URLRequest("item.txt"));
+        }
+
+        private function loadEmpty():void {
+            step = "loadEmpty";
+            stream.load(new URLRequest("no_content.txt"));
+        }
+
+        private function loadMissing():void {
+            step = "loadMissing";
+            stream.load(new URLRequest("missing.txt"));
+        }
+    }
+}
@@ -0,0 +1,91 @@
+package {
+	import flash.display.BitmapData;
+	import flash.display.MovieClip;
+	import flash.geom.Rectangle;
+	import flash.utils.ByteArray;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			trace("// Vector.<int>");
+			var ints:Vector.<int> = new Vector.<int>(3);
+			trace(ints);
+			ints[0] = 7;
+			ints[1] = 4294967295;
+			ints[2] = "12";
+			ints[3] = 2.9;
+			ints.push(1.5, "3", -2, true, null);
+			trace(ints);
+			ints.reverse();
+			trace(ints);
+			ints.sort(Array.NUMERIC);
+			trace(ints);
+			trace(ints.splice(1, 2, 100, 200.5));
+			trace(ints);
+			ints.length = 2;
+			trace(ints.pop(), ints.shift(), ints.pop(), ints.length);
+
+			trace("// Vector.<uint>");
+			var uints:Vector.<uint> = new <uint>[1, 2];
+			uints[0] = -1;
+			uints[1] = 3;
+			uints.push(-2, 5.5, "6");
+			trace(uints);
+			uints.unshift(-3);
+			uints.insertAt(1, 8);
+			trace(uints);
+			trace(uints.removeAt(0));
+			trace(uints.indexOf(8), uints.indexOf(4294967295));
+
+			trace("// Vector.<Number>");
+			var numbers:Vector.<Number> = new Vector.<Number>();
+			numbers.push(1, 2.5, "x", "-4.25", null);
+			numbers[5] = 6;
+			trace(numbers);
+			numbers.length = 8;
+			trace(numbers);
+			trace(numbers.concat(new <Number>[9.5]));
+			trace(numbers.slice(1, 3));
+			trace(numbers.map(function(n:Number, i:int, v:Vector.<Number>):Number {
+				return n * 2;
+			}));
+
+			trace("// fixed Vector.<int>");
+			var fixed:Vector.<int> = new Vector.<int>(2, true);
+			fixed[1] = 5;
+			try {
+				fixed.push(1);
+			} catch (e:RangeError) {
+				trace(e.errorID);
+			}
+			try {
+				fixed[2] = 1;
+			} catch (e:RangeError) {
+				trace(e.errorID);
+			}
+			trace(fixed);
+
+			trace("// AMF");
+			var bytes:ByteArray = new ByteArray();
+			bytes.writeObject(new <int>[1, -2, 3]);
+			bytes.writeObject(new <uint>[4294967295, 0]);
+			bytes.writeObject(new <Number>[0.5, NaN]);
+			bytes.position = 0;
+			var readInts:* = bytes.readObject();
+			trace(readInts is Vector.<int>, readInts);
+			var readUints:* = bytes.readObject();
+			trace(readUints is Vector.<uint>, readUints);
+			var readNumbers:* = bytes.readObject();
+			trace(readNumbers is Vector.<Number>, readNumbers);
+			readInts.push(4);
+			trace(readInts);
+
+			trace("// BitmapData.getVector");
+			var bd:BitmapData = new BitmapData(2, 1, true, 0x80FF0000);
+			var pixels:Vector.<uint> = bd.getVector(new Rectangle(0, 0, 2, 1));
+			trace(pixels is Vector.<uint>, pixels.length, pixels[0].toString(16));
+			pixels[1] = 0xFF00FF00;
+			bd.setVector(new Rectangle(0, 0, 2, 1), pixels);
+			trace(bd.getPixel32(1, 0).toString(16));
+		}
+	}
+}
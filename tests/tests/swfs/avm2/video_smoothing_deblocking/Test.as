@@ -0,0 +1,22 @@
+package {
+    import flash.display.MovieClip;
+    import flash.media.Video;
+
+    public class Test extends MovieClip {
+        public function Test() {
+            var video:Video = new Video();
+            trace("smoothing: " + video.smoothing);
+            trace("deblocking: " + video.deblocking);
+
+            video.smoothing = true;
+            trace("smoothing = true: " + video.smoothing);
+            video.smoothing = false;
+            trace("smoothing = false: " + video.smoothing);
+
+            for each (var value:int in [1, 2, 3, 4, 5, 0, 6, -1]) {
+                video.deblocking = value;
+                trace("deblocking = " + value + ": " + video.deblocking);
+            }
+        }
+    }
+}
@@ -0,0 +1,27 @@
+package {
+  import flash.display.Sprite;
+  public class Test extends Sprite { }
+}
+
+XML.prettyPrinting = false;
+
+var xml: XML = <root><a>1</a><a>2</a><b>3</b></root>;
+var list: XMLList = xml.a;
+trace("list[0] === list[0]:", list[0] === list[0]);
+
+var replacement: XMLList = new XMLList("<c>4</c><c>5</c>");
+list[0] = replacement;
+trace("replacement.length():", replacement.length());
+trace("replacement.toXMLString():", replacement.toXMLString());
+trace("list.length():", list.length());
+trace("list.toXMLString():", list.toXMLString());
+trace("xml.toXMLString():", xml.toXMLString());
+
+trace("///");
+
+delete replacement[1];
+trace("replacement.length():", replacement.length());
+trace("replacement.toXMLString():", replacement.toXMLString());
+trace("list.length():", list.length());
+trace("list.toXMLString():", list.toXMLString());
+trace("xml.toXMLString():", xml.toXMLString());
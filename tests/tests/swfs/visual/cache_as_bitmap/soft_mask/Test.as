@@ -0,0 +1,38 @@
+package {
+    import flash.display.Sprite;
+
+    // Three red squares, each masked by a shape that's half transparent on its left side.
+    // Only the first one has both the square and its mask cached as bitmaps, which makes
+    // the mask a soft mask that lets the square show through at half strength.
+    // The others use a hard vector mask, where every pixel drawn in the mask counts fully.
+    public class Test extends Sprite {
+        public function Test() {
+            addMaskedSquare(0, true, true);
+            addMaskedSquare(100, true, false);
+            addMaskedSquare(200, false, true);
+        }
+
+        private function addMaskedSquare(x:int, cacheSquare:Boolean, cacheMask:Boolean):void {
+            var square:Sprite = new Sprite();
+            square.x = x;
+            square.graphics.beginFill(0xFF0000);
+            square.graphics.drawRect(0, 0, 100, 100);
+            square.graphics.endFill();
+            square.cacheAsBitmap = cacheSquare;
+            addChild(square);
+
+            var mask:Sprite = new Sprite();
+            mask.x = x;
+            mask.graphics.beginFill(0x000000, 0.5);
+            mask.graphics.drawRect(0, 0, 50, 100);
+            mask.graphics.endFill();
+            mask.graphics.beginFill(0x000000, 1);
+            mask.graphics.drawRect(50, 0, 50, 100);
+            mask.graphics.endFill();
+            mask.cacheAsBitmap = cacheMask;
+            addChild(mask);
+
+            square.mask = mask;
+        }
+    }
+}
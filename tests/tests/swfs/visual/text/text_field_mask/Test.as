@@ -0,0 +1,35 @@
+package {
+    import flash.display.Sprite;
+    import flash.text.TextField;
+
+    // Red squares masked by a text field with a blue background covering their top half.
+    // The text field is only drawn into the mask, so no blue shows up. The second square
+    // and its text field are both cached as bitmaps, which makes the text field a soft mask.
+    public class Test extends Sprite {
+        public function Test() {
+            addMaskedSquare(0, false);
+            addMaskedSquare(100, true);
+        }
+
+        private function addMaskedSquare(x:int, cache:Boolean):void {
+            var square:Sprite = new Sprite();
+            square.x = x;
+            square.graphics.beginFill(0xFF0000);
+            square.graphics.drawRect(0, 0, 100, 100);
+            square.graphics.endFill();
+            square.cacheAsBitmap = cache;
+            addChild(square);
+
+            var mask:TextField = new TextField();
+            mask.x = x;
+            mask.width = 100;
+            mask.height = 50;
+            mask.background = true;
+            mask.backgroundColor = 0x0000FF;
+            mask.cacheAsBitmap = cache;
+            addChild(mask);
+
+            square.mask = mask;
+        }
+    }
+}
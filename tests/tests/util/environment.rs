@@ -1,4 +1,4 @@
-use ruffle_render_wgpu::backend::request_adapter_and_device;
+use ruffle_render_wgpu::backend::{request_adapter_and_device, request_device};
 use ruffle_render_wgpu::descriptors::Descriptors;
 use ruffle_render_wgpu::wgpu;
 use std::sync::{Arc, OnceLock};
@@ -15,8 +15,34 @@ use std::sync::{Arc, OnceLock};
    but for `cargo nextest run` it's a big cost per test if it's not going to use it.
 */
 
+/// Whether to render with a software adapter (such as llvmpipe or WARP), as set by the
+/// `RUFFLE_SOFTWARE_RENDERER` environment variable.
+///
+/// Software adapters draw the same on any machine, so images captured with one can be compared
+/// with a much lower tolerance than those from whatever GPU happens to be around.
+pub fn software_renderer() -> bool {
+    std::env::var_os("RUFFLE_SOFTWARE_RENDERER").is_some()
+}
+
+/// Whether image comparisons that fail should replace their expected image instead, as set by
+/// the `RUFFLE_BLESS` environment variable.
+#[cfg(feature = "imgtests")]
+pub fn bless() -> bool {
+    std::env::var_os("RUFFLE_BLESS").is_some()
+}
+
 fn create_wgpu_device() -> Option<(wgpu::Instance, wgpu::Adapter, wgpu::Device, wgpu::Queue)> {
     let instance = wgpu::Instance::new(Default::default());
+    if software_renderer() {
+        let adapter =
+            futures::executor::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+                power_preference: Default::default(),
+                compatible_surface: None,
+                force_fallback_adapter: true,
+            }))?;
+        let (device, queue) = futures::executor::block_on(request_device(&adapter, None)).ok()?;
+        return Some((instance, adapter, device, queue));
+    }
     futures::executor::block_on(request_adapter_and_device(
         wgpu::Backends::all(),
         &instance,
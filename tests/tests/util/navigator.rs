@@ -1,16 +1,26 @@
+use crate::util::options::{ExpectedRequest, FetchFixture};
 use crate::util::runner::TestLogBackend;
+use anyhow::{anyhow, Result};
 use async_channel::Receiver;
 use ruffle_core::backend::log::LogBackend;
 use ruffle_core::backend::navigator::{
-    fetch_path, resolve_url_with_relative_base_path, ErrorResponse, NavigationMethod,
-    NavigatorBackend, NullExecutor, NullSpawner, OwnedFuture, Request, SuccessResponse,
+    async_return, create_specific_fetch_error, fetch_path, resolve_url_with_relative_base_path,
+    AirDirectories, BodyChunk, ErrorResponse, NavigationMethod, NavigatorBackend, NullExecutor,
+    NullSpawner, OwnedFuture, RedirectPolicy, Request, SuccessResponse,
 };
 use ruffle_core::indexmap::IndexMap;
 use ruffle_core::loader::Error;
+use ruffle_core::net_group::{NetGroupAction, NetGroupHandle};
 use ruffle_core::socket::{ConnectionState, SocketAction, SocketHandle};
 use ruffle_socket_format::SocketEvent;
+use std::cell::{Cell, RefCell};
+use std::future::Future;
+use std::net::SocketAddr;
 use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::rc::Rc;
 use std::sync::mpsc::Sender;
+use std::task::{Context, Poll, Waker};
 use std::time::Duration;
 use url::{ParseError, Url};
 
@@ -20,6 +30,9 @@ use url::{ParseError, Url};
 pub struct TestNavigatorBackend {
     spawner: NullSpawner,
     relative_base_path: PathBuf,
+    /// For AIR tests, `app:/` is the test directory, and `app-storage:/` its `app-storage`
+    /// subdirectory.
+    air_directories: Option<AirDirectories>,
     socket_events: Option<Vec<SocketEvent>>,
     log: Option<TestLogBackend>,
 }
@@ -30,39 +43,24 @@ impl TestNavigatorBackend {
         executor: &NullExecutor,
         socket_events: Option<Vec<SocketEvent>>,
         log: Option<TestLogBackend>,
+        air: bool,
     ) -> Result<Self, std::io::Error> {
+        let relative_base_path = path.canonicalize()?;
+        let air_directories = if air {
+            AirDirectories::from_paths(&relative_base_path, &relative_base_path.join("app-storage"))
+        } else {
+            None
+        };
         Ok(Self {
             spawner: executor.spawner(),
-            relative_base_path: path.canonicalize()?,
+            relative_base_path,
+            air_directories,
             socket_events,
             log,
         })
     }
-}
-
-impl NavigatorBackend for TestNavigatorBackend {
-    fn navigate_to_url(
-        &self,
-        url: &str,
-        target: &str,
-        vars_method: Option<(NavigationMethod, IndexMap<String, String>)>,
-    ) {
-        // Log request.
-        if let Some(log) = &self.log {
-            log.avm_trace("Navigator::navigate_to_url:");
-            log.avm_trace(&format!("  URL: {}", url));
-            log.avm_trace(&format!("  Target: {}", target));
-            if let Some((method, vars)) = vars_method {
-                log.avm_trace(&format!("  Method: {}", method));
-                for (key, value) in vars {
-                    log.avm_trace(&format!("  Param: {}={}", key, value));
-                }
-            }
-        }
-    }
 
-    fn fetch(&self, request: Request) -> OwnedFuture<SuccessResponse, ErrorResponse> {
-        // Log request.
+    fn log_fetch(&self, request: &Request) {
         if let Some(log) = &self.log {
             log.avm_trace("Navigator::fetch:");
             log.avm_trace(&format!("  URL: {}", request.url()));
@@ -87,6 +85,32 @@ impl NavigatorBackend for TestNavigatorBackend {
                 }
             }
         }
+    }
+}
+
+impl NavigatorBackend for TestNavigatorBackend {
+    fn navigate_to_url(
+        &self,
+        url: &str,
+        target: &str,
+        vars_method: Option<(NavigationMethod, IndexMap<String, String>)>,
+    ) {
+        // Log request.
+        if let Some(log) = &self.log {
+            log.avm_trace("Navigator::navigate_to_url:");
+            log.avm_trace(&format!("  URL: {}", url));
+            log.avm_trace(&format!("  Target: {}", target));
+            if let Some((method, vars)) = vars_method {
+                log.avm_trace(&format!("  Method: {}", method));
+                for (key, value) in vars {
+                    log.avm_trace(&format!("  Param: {}={}", key, value));
+                }
+            }
+        }
+    }
+
+    fn fetch(&self, request: Request) -> OwnedFuture<SuccessResponse, ErrorResponse> {
+        self.log_fetch(&request);
 
         fetch_path(self, "TestNavigatorBackend", request.url())
     }
@@ -95,6 +119,10 @@ impl NavigatorBackend for TestNavigatorBackend {
         resolve_url_with_relative_base_path(self, self.relative_base_path.clone(), url)
     }
 
+    fn air_directories(&self) -> Option<&AirDirectories> {
+        self.air_directories.as_ref()
+    }
+
     fn spawn_future(&mut self, future: OwnedFuture<(), Error>) {
         self.spawner.spawn_local(future);
     }
@@ -107,6 +135,7 @@ impl NavigatorBackend for TestNavigatorBackend {
         &mut self,
         host: String,
         port: u16,
+        _require_policy_file: bool,
         _timeout: Duration,
         handle: SocketHandle,
         receiver: Receiver<Vec<u8>>,
@@ -156,4 +185,334 @@ impl NavigatorBackend for TestNavigatorBackend {
             }));
         }
     }
+
+    fn join_net_group(
+        &mut self,
+        address: SocketAddr,
+        handle: NetGroupHandle,
+        _receiver: Receiver<Vec<u8>>,
+        sender: Sender<NetGroupAction>,
+    ) {
+        if let Some(log) = &self.log {
+            log.avm_trace("Navigator::join_net_group");
+            log.avm_trace(&format!("    Address: {}", address));
+        }
+
+        sender
+            .send(NetGroupAction::Connect(handle, false))
+            .expect("working channel send");
+    }
+}
+
+/// The requests seen by a `MockNavigatorBackend`, and the frame clock used to delay its responses.
+#[derive(Default)]
+pub struct MockNavigatorState {
+    requests: RefCell<Vec<ExpectedRequest>>,
+    frame: Cell<u32>,
+    wakers: RefCell<Vec<Waker>>,
+}
+
+impl MockNavigatorState {
+    /// Advances the clock by one frame, waking up any responses waiting on it.
+    pub fn advance_frame(&self) {
+        self.frame.set(self.frame.get() + 1);
+        for waker in self.wakers.take() {
+            waker.wake();
+        }
+    }
+
+    /// Checks that exactly the expected requests were made, in order.
+    pub fn check_requests(&self, expected_requests: &[ExpectedRequest]) -> Result<()> {
+        let requests = self.requests.borrow();
+        if requests.len() != expected_requests.len() {
+            return Err(anyhow!(
+                "Expected {} requests, but {} were made: {:#?}",
+                expected_requests.len(),
+                requests.len(),
+                requests
+            ));
+        }
+
+        for (index, (actual, expected)) in requests.iter().zip(expected_requests).enumerate() {
+            let matches = actual.url == expected.url
+                && (expected.method.is_none() || expected.method == actual.method)
+                && (expected.headers.is_none() || expected.headers == actual.headers)
+                && (expected.body.is_none() || expected.body == actual.body);
+            if !matches {
+                return Err(anyhow!(
+                    "Request #{} didn't match.\nExpected: {:#?}\nActual: {:#?}",
+                    index,
+                    expected,
+                    actual
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A future that resolves once the `MockNavigatorState` clock reaches a given frame.
+struct WaitForFrame {
+    state: Rc<MockNavigatorState>,
+    frame: u32,
+}
+
+impl Future for WaitForFrame {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.state.frame.get() >= self.frame {
+            Poll::Ready(())
+        } else {
+            self.state.wakers.borrow_mut().push(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
+/// A `NavigatorBackend` that serves canned responses and records every fetch request.
+///
+/// Requests without a matching fixture, and everything that isn't a fetch, go to the wrapped
+/// `TestNavigatorBackend`.
+pub struct MockNavigatorBackend {
+    inner: TestNavigatorBackend,
+    fixtures: Vec<(String, FetchFixture, Vec<u8>)>,
+    state: Rc<MockNavigatorState>,
+}
+
+impl MockNavigatorBackend {
+    pub fn new(
+        inner: TestNavigatorBackend,
+        fixtures: &[FetchFixture],
+        test_directory: &Path,
+    ) -> Result<Self, std::io::Error> {
+        let fixtures = fixtures
+            .iter()
+            .map(|fixture| {
+                let url = inner
+                    .resolve_url(&fixture.url)
+                    .map(|url| url.to_string())
+                    .unwrap_or_else(|_| fixture.url.clone());
+                let body = match (&fixture.body, &fixture.body_path) {
+                    (_, Some(path)) => std::fs::read(test_directory.join(path))?,
+                    (Some(body), None) => body.as_bytes().to_vec(),
+                    (None, None) => vec![],
+                };
+                Ok((url, fixture.clone(), body))
+            })
+            .collect::<Result<_, std::io::Error>>()?;
+
+        Ok(Self {
+            inner,
+            fixtures,
+            state: Default::default(),
+        })
+    }
+
+    pub fn state(&self) -> Rc<MockNavigatorState> {
+        self.state.clone()
+    }
+
+    fn find_fixture(&self, url: &str) -> Option<&(String, FetchFixture, Vec<u8>)> {
+        self.fixtures
+            .iter()
+            .find(|(fixture_url, _, _)| fixture_url == url)
+    }
+}
+
+impl NavigatorBackend for MockNavigatorBackend {
+    fn navigate_to_url(
+        &self,
+        url: &str,
+        target: &str,
+        vars_method: Option<(NavigationMethod, IndexMap<String, String>)>,
+    ) {
+        self.inner.navigate_to_url(url, target, vars_method)
+    }
+
+    fn fetch(&self, request: Request) -> OwnedFuture<SuccessResponse, ErrorResponse> {
+        let url = self
+            .resolve_url(request.url())
+            .map(|url| url.to_string())
+            .unwrap_or_else(|_| request.url().to_string());
+
+        self.state.requests.borrow_mut().push(ExpectedRequest {
+            url: url.clone(),
+            method: Some(request.method().to_string()),
+            headers: Some(
+                request
+                    .headers()
+                    .iter()
+                    .map(|(key, val)| (key.clone(), val.clone()))
+                    .collect(),
+            ),
+            body: request
+                .body()
+                .as_ref()
+                .map(|(body, _)| String::from_utf8_lossy(body).into_owned()),
+        });
+
+        let Some(mut fixture) = self.find_fixture(&url) else {
+            return self.inner.fetch(request);
+        };
+        self.inner.log_fetch(&request);
+
+        let mut url = url;
+        let mut redirected = false;
+        let mut delay_frames = fixture.1.delay_frames;
+        let mut redirect_url = None;
+        let mut redirects = 0;
+        while let Some(location) = fixture.1.redirect_location(&url) {
+            if request.redirect_policy() != RedirectPolicy::Follow {
+                redirect_url = Some(location);
+                break;
+            }
+            let next = self.find_fixture(&location).filter(|_| redirects < 20);
+            redirects += 1;
+            let Some(next) = next else {
+                return async_return(create_specific_fetch_error(
+                    "MockNavigatorBackend can't follow redirect to",
+                    &location,
+                    "",
+                ));
+            };
+            url = location;
+            redirected = true;
+            delay_frames += next.1.delay_frames;
+            fixture = next;
+        }
+
+        let wait = WaitForFrame {
+            state: self.state.clone(),
+            frame: self.state.frame.get() + delay_frames,
+        };
+        let status = fixture.1.status;
+        // Like a server would, only send the body back if it was asked for.
+        let body = if matches!(request.method(), NavigationMethod::Head) {
+            vec![]
+        } else {
+            fixture.2.clone()
+        };
+        let headers = fixture
+            .1
+            .headers
+            .iter()
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect();
+        let redirect_policy = request.redirect_policy();
+        Box::pin(async move {
+            wait.await;
+            let allowed = match redirect_url {
+                Some(_) => redirect_policy == RedirectPolicy::Manual,
+                None => (200..300).contains(&status),
+            };
+            if allowed {
+                Ok(SuccessResponse {
+                    url,
+                    body,
+                    status,
+                    redirected,
+                    redirect_url,
+                    headers,
+                })
+            } else {
+                Err(ErrorResponse {
+                    url,
+                    error: Error::HttpNotOk(format!("HTTP status {status}"), status, redirected),
+                })
+            }
+        })
+    }
+
+    fn fetch_stream(
+        &self,
+        request: Request,
+        body_sender: async_channel::Sender<BodyChunk>,
+    ) -> OwnedFuture<SuccessResponse, ErrorResponse> {
+        // The response may come from a redirect, so which chunk size applies is only known once
+        // it's here.
+        let chunk_sizes: Vec<(String, usize)> = self
+            .fixtures
+            .iter()
+            .filter_map(|(url, fixture, _)| Some((url.clone(), fixture.chunk_size?)))
+            .collect();
+        let state = self.state.clone();
+        let fetch = self.fetch(request);
+        Box::pin(async move {
+            let mut response = fetch.await?;
+            let body = std::mem::take(&mut response.body);
+            let chunk_size = chunk_sizes
+                .iter()
+                .find(|(url, _)| *url == response.url)
+                .map(|(_, chunk_size)| (*chunk_size).max(1));
+            let _ = body_sender.send(BodyChunk::Length(body.len())).await;
+            match chunk_size {
+                Some(chunk_size) => {
+                    for (index, chunk) in body.chunks(chunk_size).enumerate() {
+                        if index > 0 {
+                            let frame = state.frame.get() + 1;
+                            WaitForFrame {
+                                state: state.clone(),
+                                frame,
+                            }
+                            .await;
+                        }
+                        let _ = body_sender.send(BodyChunk::Data(chunk.to_vec())).await;
+                    }
+                }
+                None => {
+                    let _ = body_sender.send(BodyChunk::Data(body)).await;
+                }
+            }
+            Ok(response)
+        })
+    }
+
+    fn resolve_url(&self, url: &str) -> Result<Url, ParseError> {
+        self.inner.resolve_url(url)
+    }
+
+    fn air_directories(&self) -> Option<&AirDirectories> {
+        self.inner.air_directories()
+    }
+
+    fn spawn_future(&mut self, future: OwnedFuture<(), Error>) {
+        self.inner.spawn_future(future)
+    }
+
+    fn pre_process_url(&self, url: Url) -> Url {
+        self.inner.pre_process_url(url)
+    }
+
+    fn connect_socket(
+        &mut self,
+        host: String,
+        port: u16,
+        require_policy_file: bool,
+        timeout: Duration,
+        handle: SocketHandle,
+        receiver: Receiver<Vec<u8>>,
+        sender: Sender<SocketAction>,
+    ) {
+        self.inner.connect_socket(
+            host,
+            port,
+            require_policy_file,
+            timeout,
+            handle,
+            receiver,
+            sender,
+        )
+    }
+
+    fn join_net_group(
+        &mut self,
+        address: SocketAddr,
+        handle: NetGroupHandle,
+        receiver: Receiver<Vec<u8>>,
+        sender: Sender<NetGroupAction>,
+    ) {
+        self.inner.join_net_group(address, handle, receiver, sender)
+    }
 }
@@ -4,15 +4,17 @@ use crate::util::runner::TestAudioBackend;
 use anyhow::{anyhow, Result};
 use approx::assert_relative_eq;
 use regex::Regex;
+use ruffle_core::player_version::PlayerVersion;
 use ruffle_core::tag_utils::SwfMovie;
 use ruffle_core::{PlayerBuilder, ViewportDimensions};
 use ruffle_render::quality::StageQuality;
 use ruffle_render_wgpu::wgpu;
 use serde::Deserialize;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::time::Duration;
+use url::Url;
 
 #[derive(Deserialize)]
 #[serde(default, deny_unknown_fields)]
@@ -28,7 +30,11 @@ pub struct TestOptions {
     pub approximations: Option<Approximations>,
     pub player_options: PlayerOptions,
     pub log_fetch: bool,
+    /// Runs the movie as an AIR application installed in the test directory.
+    pub air: bool,
     pub required_features: RequiredFeatures,
+    pub fetch_fixtures: Vec<FetchFixture>,
+    pub expected_requests: Option<Vec<ExpectedRequest>>,
 }
 
 impl Default for TestOptions {
@@ -45,7 +51,10 @@ impl Default for TestOptions {
             approximations: None,
             player_options: PlayerOptions::default(),
             log_fetch: false,
+            air: false,
             required_features: RequiredFeatures::default(),
+            fetch_fixtures: Vec::new(),
+            expected_requests: None,
         }
     }
 }
@@ -80,6 +89,61 @@ impl TestOptions {
     }
 }
 
+/// A canned response served by the `MockNavigatorBackend` instead of hitting the filesystem.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct FetchFixture {
+    pub url: String,
+    pub status: u16,
+    /// Response headers. A `Location` header on a 3xx response makes this fixture a redirect.
+    pub headers: BTreeMap<String, String>,
+    pub body: Option<String>,
+    /// Path to a file containing the body, relative to the test directory.
+    pub body_path: Option<PathBuf>,
+    /// How many frames to wait before the response is delivered.
+    pub delay_frames: u32,
+    /// When streamed, the body is sent in chunks of this many bytes, one frame apart.
+    pub chunk_size: Option<usize>,
+}
+
+impl Default for FetchFixture {
+    fn default() -> Self {
+        Self {
+            url: String::new(),
+            status: 200,
+            headers: BTreeMap::new(),
+            body: None,
+            body_path: None,
+            delay_frames: 0,
+            chunk_size: None,
+        }
+    }
+}
+
+impl FetchFixture {
+    /// Where this fixture redirects to, resolved against the URL it was requested from.
+    pub fn redirect_location(&self, url: &str) -> Option<String> {
+        if !(300..400).contains(&self.status) {
+            return None;
+        }
+        let location = self.headers.get("Location")?;
+        match Url::parse(url).and_then(|url| url.join(location)) {
+            Ok(location) => Some(location.to_string()),
+            Err(_) => Some(location.clone()),
+        }
+    }
+}
+
+/// A request that the movie is expected to make. Unset fields aren't checked.
+#[derive(Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub struct ExpectedRequest {
+    pub url: String,
+    pub method: Option<String>,
+    pub headers: Option<BTreeMap<String, String>>,
+    pub body: Option<String>,
+}
+
 #[derive(Deserialize, Default)]
 #[serde(default, deny_unknown_fields)]
 pub struct Approximations {
@@ -118,11 +182,14 @@ impl Approximations {
 pub struct RequiredFeatures {
     lzma: bool,
     jpegxr: bool,
+    aac: bool,
 }
 
 impl RequiredFeatures {
     pub fn can_run(&self) -> bool {
-        (!self.lzma || cfg!(feature = "lzma")) && (!self.jpegxr || cfg!(feature = "jpegxr"))
+        (!self.lzma || cfg!(feature = "lzma"))
+            && (!self.jpegxr || cfg!(feature = "jpegxr"))
+            && (!self.aac || cfg!(feature = "aac"))
     }
 }
 
@@ -134,6 +201,8 @@ pub struct PlayerOptions {
     with_renderer: Option<RenderOptions>,
     with_audio: bool,
     with_video: bool,
+    with_media: bool,
+    player_version: Option<PlayerVersion>,
 }
 
 impl PlayerOptions {
@@ -146,6 +215,10 @@ impl PlayerOptions {
             player_builder = player_builder.with_max_execution_duration(max_execution_duration);
         }
 
+        if self.player_version.is_some() {
+            player_builder = player_builder.with_player_version(self.player_version);
+        }
+
         let (width, height) = if let Some(viewport_dimensions) = self.viewport_dimensions {
             player_builder = player_builder.with_viewport_dimensions(
                 viewport_dimensions.width,
@@ -195,6 +268,12 @@ impl PlayerOptions {
             player_builder = player_builder.with_video(SoftwareVideoBackend::new())
         }
 
+        #[cfg(feature = "aac")]
+        if self.with_media {
+            use ruffle_video_software::media::SoftwareMediaDecoderBackend;
+            player_builder = player_builder.with_media_decoder(SoftwareMediaDecoderBackend::new())
+        }
+
         Ok(player_builder)
     }
 
@@ -221,6 +300,9 @@ impl PlayerOptions {
 pub struct ImageComparison {
     tolerance: u8,
     max_outliers: usize,
+    /// If set, pixels are compared by how different they look (from 0 to 1) instead of channel
+    /// by channel, and `tolerance` is ignored.
+    perceptual_threshold: Option<f32>,
     pub trigger: ImageTrigger,
 }
 
@@ -229,12 +311,65 @@ fn calc_difference(lhs: u8, rhs: u8) -> u8 {
     (lhs as i16 - rhs as i16).unsigned_abs() as u8
 }
 
+/// How different two RGBA pixels look, from 0 (the same) to 1 (as different as red and cyan).
+///
+/// This is the YIQ color difference used by pixelmatch, with both pixels blended onto white
+/// first, so changes in channels the eye is less sensitive to count for less.
+#[cfg(feature = "imgtests")]
+fn perceptual_difference(lhs: &[u8], rhs: &[u8]) -> f32 {
+    fn yiq(pixel: &[u8]) -> [f64; 3] {
+        let alpha = f64::from(pixel[3]) / 255.0;
+        let [r, g, b] =
+            [pixel[0], pixel[1], pixel[2]].map(|c| 255.0 + (f64::from(c) - 255.0) * alpha);
+        [
+            r * 0.29889531 + g * 0.58662247 + b * 0.11448223,
+            r * 0.59597799 - g * 0.27417610 - b * 0.32180189,
+            r * 0.21147017 - g * 0.52261711 + b * 0.31114694,
+        ]
+    }
+
+    // The largest possible difference, between black and white.
+    const MAX_DELTA: f64 = 35215.0;
+    let [y1, i1, q1] = yiq(lhs);
+    let [y2, i2, q2] = yiq(rhs);
+    let (y, i, q) = (y1 - y2, i1 - i2, q1 - q2);
+    let delta = 0.5053 * y * y + 0.299 * i * i + 0.1957 * q * q;
+    (delta / MAX_DELTA).sqrt().min(1.0) as f32
+}
+
+/// Checks `perceptual_difference` against colors with known differences.
+#[cfg(feature = "imgtests")]
+pub fn perceptual_difference_test() -> Result<(), libtest_mimic::Failed> {
+    const BLACK: [u8; 4] = [0, 0, 0, 255];
+    const WHITE: [u8; 4] = [255, 255, 255, 255];
+    const TRANSPARENT: [u8; 4] = [0, 0, 0, 0];
+
+    assert_eq!(perceptual_difference(&BLACK, &BLACK), 0.0);
+    // Transparent pixels are compared as if they were on white.
+    assert_eq!(perceptual_difference(&TRANSPARENT, &WHITE), 0.0);
+    assert!((perceptual_difference(&[255, 0, 0, 255], &[0, 255, 255, 255]) - 1.0).abs() < 1e-3);
+    assert!((perceptual_difference(&BLACK, &WHITE) - 0.966).abs() < 1e-3);
+    assert_eq!(
+        perceptual_difference(&BLACK, &WHITE),
+        perceptual_difference(&WHITE, &BLACK)
+    );
+
+    // The eye is less sensitive to blue than to green.
+    let blue = perceptual_difference(&BLACK, &[0, 0, 255, 255]);
+    let green = perceptual_difference(&BLACK, &[0, 255, 0, 255]);
+    assert!(blue < green);
+
+    // Off by one in every channel is far below any sensible threshold.
+    assert!(perceptual_difference(&[100, 150, 200, 255], &[101, 151, 201, 254]) < 0.01);
+    Ok(())
+}
+
 impl ImageComparison {
     #[cfg(feature = "imgtests")]
     pub fn test(
         &self,
         name: &str,
-        actual_image: image::RgbaImage,
+        actual_image: &image::RgbaImage,
         expected_image: image::RgbaImage,
         test_path: &Path,
         adapter_info: wgpu::AdapterInfo,
@@ -289,21 +424,36 @@ impl ImageComparison {
             })
             .collect();
 
-        let outliers: usize = difference_data
-            .chunks_exact(4)
-            .map(|colors| {
-                (colors[0] > self.tolerance) as usize
-                    + (colors[1] > self.tolerance) as usize
-                    + (colors[2] > self.tolerance) as usize
-                    + (colors[3] > self.tolerance) as usize
-            })
-            .sum();
-
-        let max_difference = difference_data
-            .chunks_exact(4)
-            .map(|colors| colors[0].max(colors[1]).max(colors[2]).max(colors[3]))
-            .max()
-            .unwrap();
+        let (outliers, max_difference) = if let Some(threshold) = self.perceptual_threshold {
+            let differences: Vec<f32> = expected_image
+                .as_raw()
+                .chunks_exact(4)
+                .zip(actual_image.as_raw().chunks_exact(4))
+                .map(|(cmp_chunk, data_chunk)| perceptual_difference(cmp_chunk, data_chunk))
+                .collect();
+            let outliers = differences
+                .iter()
+                .filter(|&&difference| difference > threshold)
+                .count();
+            let max_difference = differences.into_iter().fold(0.0, f32::max);
+            (outliers, max_difference.to_string())
+        } else {
+            let outliers: usize = difference_data
+                .chunks_exact(4)
+                .map(|colors| {
+                    (colors[0] > self.tolerance) as usize
+                        + (colors[1] > self.tolerance) as usize
+                        + (colors[2] > self.tolerance) as usize
+                        + (colors[3] > self.tolerance) as usize
+                })
+                .sum();
+            let max_difference = difference_data
+                .chunks_exact(4)
+                .map(|colors| colors[0].max(colors[1]).max(colors[2]).max(colors[3]))
+                .max()
+                .unwrap();
+            (outliers, max_difference.to_string())
+        };
 
         if outliers > self.max_outliers {
             save_actual_image()?;
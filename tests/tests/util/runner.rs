@@ -1,8 +1,9 @@
 use crate::util::fs_commands::{FsCommand, TestFsCommandProvider};
 use crate::util::image_trigger::ImageTrigger;
-use crate::util::navigator::TestNavigatorBackend;
+use crate::util::navigator::{MockNavigatorBackend, TestNavigatorBackend};
 use crate::util::options::ImageComparison;
 use crate::util::test::Test;
+use crate::util::ui::TestUiBackend;
 use anyhow::{anyhow, Result};
 use ruffle_core::backend::audio::{
     swf, AudioBackend, AudioMixer, DecodeError, RegisterError, SoundHandle, SoundInstanceHandle,
@@ -10,15 +11,18 @@ use ruffle_core::backend::audio::{
 };
 use ruffle_core::backend::log::LogBackend;
 use ruffle_core::backend::navigator::NullExecutor;
+use ruffle_core::backend::ui::DesktopRect;
 use ruffle_core::events::MouseButton as RuffleMouseButton;
-use ruffle_core::events::{KeyCode, TextControlCode as RuffleTextControlCode};
+use ruffle_core::events::{
+    KeyCode, MouseWheelDelta as RuffleMouseWheelDelta, TextControlCode as RuffleTextControlCode,
+};
 use ruffle_core::impl_audio_mixer_backend;
 use ruffle_core::limits::ExecutionLimit;
 use ruffle_core::tag_utils::SwfMovie;
 use ruffle_core::{Player, PlayerBuilder, PlayerEvent};
 use ruffle_input_format::{
     AutomatedEvent, InputInjector, MouseButton as InputMouseButton,
-    TextControlCode as InputTextControlCode,
+    MouseWheelDelta as InputMouseWheelDelta, TextControlCode as InputTextControlCode,
 };
 use ruffle_render_wgpu::descriptors::Descriptors;
 use ruffle_socket_format::SocketEvent;
@@ -106,16 +110,23 @@ pub fn run_swf(
 
     let log = TestLogBackend::new();
     let (fs_command_provider, fs_commands) = TestFsCommandProvider::new();
-    let navigator = TestNavigatorBackend::new(
+    let navigator = MockNavigatorBackend::new(
+        TestNavigatorBackend::new(
+            base_path,
+            &executor,
+            socket_events,
+            test.options.log_fetch.then(|| log.clone()),
+            test.options.air,
+        )?,
+        &test.options.fetch_fixtures,
         base_path,
-        &executor,
-        socket_events,
-        test.options.log_fetch.then(|| log.clone()),
     )?;
+    let navigator_state = navigator.state();
 
     let builder = PlayerBuilder::new()
         .with_log(log.clone())
         .with_navigator(navigator)
+        .with_ui(TestUiBackend::default())
         .with_max_execution_duration(Duration::from_secs(300))
         .with_fs_commands(Box::new(fs_command_provider))
         .with_viewport_dimensions(
@@ -190,6 +201,7 @@ pub fn run_swf(
         }
         remaining_iterations -= 1;
         current_iteration += 1;
+        navigator_state.advance_frame();
         executor.run();
 
         for command in fs_commands.try_iter() {
@@ -218,7 +230,8 @@ pub fn run_swf(
         }
 
         injector.next(|evt, _btns_down| {
-            player.lock().unwrap().handle_event(match evt {
+            let mut player = player.lock().unwrap();
+            let event = match evt {
                 AutomatedEvent::MouseDown { pos, btn } => PlayerEvent::MouseDown {
                     x: pos.0,
                     y: pos.1,
@@ -238,6 +251,14 @@ pub fn run_swf(
                         InputMouseButton::Right => RuffleMouseButton::Right,
                     },
                 },
+                AutomatedEvent::MouseWheel { delta } => PlayerEvent::MouseWheel {
+                    delta: match delta {
+                        InputMouseWheelDelta::Lines(lines) => RuffleMouseWheelDelta::Lines(*lines),
+                        InputMouseWheelDelta::Pixels(pixels) => {
+                            RuffleMouseWheelDelta::Pixels(*pixels)
+                        }
+                    },
+                },
                 AutomatedEvent::KeyDown { key_code } => PlayerEvent::KeyDown {
                     key_code: KeyCode::from_u8(*key_code).expect("Invalid keycode in test"),
                     key_char: None,
@@ -247,8 +268,8 @@ pub fn run_swf(
                 },
                 AutomatedEvent::TextControl { code } => PlayerEvent::TextControl {
                     code: match code {
-                        InputTextControlCode::MoveLeft => RuffleTextControlCode::Backspace,
-                        InputTextControlCode::MoveRight => RuffleTextControlCode::Delete,
+                        InputTextControlCode::MoveLeft => RuffleTextControlCode::MoveLeft,
+                        InputTextControlCode::MoveRight => RuffleTextControlCode::MoveRight,
                         InputTextControlCode::SelectLeft => RuffleTextControlCode::SelectLeft,
                         InputTextControlCode::SelectRight => RuffleTextControlCode::SelectRight,
                         InputTextControlCode::SelectAll => RuffleTextControlCode::SelectAll,
@@ -260,8 +281,28 @@ pub fn run_swf(
                         InputTextControlCode::Delete => RuffleTextControlCode::Delete,
                     },
                 },
+                AutomatedEvent::WindowBounds {
+                    x,
+                    y,
+                    width,
+                    height,
+                } => {
+                    player.ui_mut().set_window_bounds(DesktopRect {
+                        x: *x,
+                        y: *y,
+                        width: *width,
+                        height: *height,
+                    });
+                    player.update_window_bounds();
+                    return;
+                }
+                AutomatedEvent::WindowFocus { focused } => {
+                    player.set_window_active(*focused);
+                    return;
+                }
                 AutomatedEvent::Wait => unreachable!(),
-            });
+            };
+            player.handle_event(event);
         });
         // Rendering has side-effects (such as processing 'DisplayObject.scrollRect' updates)
         player.lock().unwrap().render();
@@ -315,6 +356,10 @@ pub fn run_swf(
 
     executor.run();
 
+    if let Some(expected_requests) = &test.options.expected_requests {
+        navigator_state.check_requests(expected_requests)?;
+    }
+
     let trace = log.trace_output();
     // Null bytes are invisible, and interfere with constructing
     // the expected output.txt file. Any tests dealing with null
@@ -372,14 +417,23 @@ fn capture_and_compare_image(
                 .context("Failed to open expected image")?
                 .into_rgba8();
 
-            image_comparison.test(
+            let bless = crate::util::environment::bless() && !known_failure;
+            let result = image_comparison.test(
                 name,
-                actual_image,
+                &actual_image,
                 expected_image,
                 base_path,
                 wgpu_descriptors.adapter.get_info(),
-                known_failure,
-            )?;
+                // A blessed image replaces the expected one, so there's nothing to look into.
+                known_failure || bless,
+            );
+            match result {
+                Err(error) if bless => {
+                    actual_image.save(expected_image_path)?;
+                    println!("Blessed image '{name}': {error}");
+                }
+                result => result?,
+            }
         } else if !known_failure {
             // If we're expecting this to be wrong, don't save a likely wrong image
             actual_image.save(expected_image_path)?;
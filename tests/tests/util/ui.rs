@@ -0,0 +1,87 @@
+use ruffle_core::backend::ui::{
+    DesktopRect, FontDefinition, FullscreenError, LanguageIdentifier, MouseCursor, NativeMenuItem,
+    NullUiBackend, ScreenInfo, UiBackend,
+};
+use std::path::PathBuf;
+use url::Url;
+
+/// A `UiBackend` used by tests, which has a window that test inputs can move and resize,
+/// and a clipboard that text copied by test inputs can be pasted from.
+///
+/// Everything else is left to `NullUiBackend`.
+#[derive(Default)]
+pub struct TestUiBackend {
+    inner: NullUiBackend,
+    window_bounds: Option<DesktopRect>,
+    clipboard: String,
+}
+
+impl UiBackend for TestUiBackend {
+    fn mouse_visible(&self) -> bool {
+        self.inner.mouse_visible()
+    }
+
+    fn set_mouse_visible(&mut self, visible: bool) {
+        self.inner.set_mouse_visible(visible)
+    }
+
+    fn set_mouse_cursor(&mut self, cursor: MouseCursor) {
+        self.inner.set_mouse_cursor(cursor)
+    }
+
+    fn clipboard_content(&mut self) -> String {
+        self.clipboard.clone()
+    }
+
+    fn set_clipboard_content(&mut self, content: String) {
+        self.clipboard = content;
+    }
+
+    fn screens(&self) -> Vec<ScreenInfo> {
+        self.inner.screens()
+    }
+
+    fn window_bounds(&self) -> Option<DesktopRect> {
+        self.window_bounds
+    }
+
+    fn set_window_bounds(&mut self, bounds: DesktopRect) {
+        self.window_bounds = Some(bounds);
+    }
+
+    fn start_file_drag(&mut self, files: &[PathBuf]) -> bool {
+        self.inner.start_file_drag(files)
+    }
+
+    fn set_native_menu(&mut self, menu: Option<Vec<NativeMenuItem>>) {
+        self.inner.set_native_menu(menu)
+    }
+
+    fn set_fullscreen(&mut self, is_full: bool) -> Result<(), FullscreenError> {
+        self.inner.set_fullscreen(is_full)
+    }
+
+    fn display_root_movie_download_failed_message(&self) {
+        self.inner.display_root_movie_download_failed_message()
+    }
+
+    fn message(&self, message: &str) {
+        self.inner.message(message)
+    }
+
+    fn open_virtual_keyboard(&self) {
+        self.inner.open_virtual_keyboard()
+    }
+
+    fn language(&self) -> &LanguageIdentifier {
+        self.inner.language()
+    }
+
+    fn display_unsupported_video(&self, url: Url) {
+        self.inner.display_unsupported_video(url)
+    }
+
+    fn load_device_font(&self, name: &str, register: &dyn FnMut(FontDefinition)) {
+        self.inner.load_device_font(name, register)
+    }
+}
@@ -41,9 +41,11 @@ impl VideoBackend for SoftwareVideoBackend {
             #[cfg(feature = "h263")]
             VideoCodec::H263 => Box::new(crate::decoder::h263::H263Decoder::new(filter)),
             #[cfg(feature = "vp6")]
-            VideoCodec::Vp6 => Box::new(crate::decoder::vp6::Vp6Decoder::new(false, size)),
+            VideoCodec::Vp6 => Box::new(crate::decoder::vp6::Vp6Decoder::new(false, size, filter)),
             #[cfg(feature = "vp6")]
-            VideoCodec::Vp6WithAlpha => Box::new(crate::decoder::vp6::Vp6Decoder::new(true, size)),
+            VideoCodec::Vp6WithAlpha => {
+                Box::new(crate::decoder::vp6::Vp6Decoder::new(true, size, filter))
+            }
             #[cfg(feature = "screenvideo")]
             VideoCodec::ScreenVideo => Box::new(crate::decoder::screen::ScreenVideoDecoder::new()),
             other => return Err(Error::UnsupportedCodec(other)),
@@ -53,6 +55,20 @@ impl VideoBackend for SoftwareVideoBackend {
         Ok(stream_handle)
     }
 
+    fn set_video_stream_deblocking(
+        &mut self,
+        stream: VideoStreamHandle,
+        filter: VideoDeblocking,
+    ) -> Result<(), Error> {
+        let stream = self
+            .streams
+            .get_mut(stream)
+            .ok_or(Error::VideoStreamIsNotRegistered)?;
+
+        stream.decoder.set_deblocking(filter);
+        Ok(())
+    }
+
     fn preload_video_stream_frame(
         &mut self,
         stream: VideoStreamHandle,
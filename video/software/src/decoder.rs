@@ -1,5 +1,6 @@
 use ruffle_video::error::Error;
 use ruffle_video::frame::{DecodedFrame, EncodedFrame, FrameDependency};
+use swf::VideoDeblocking;
 
 #[cfg(feature = "h263")]
 pub mod h263;
@@ -38,4 +39,10 @@ pub trait VideoDecoder {
     /// The decoded frame should be returned. An `Error` can be returned if
     /// a drawable bitmap can not be produced.
     fn decode_frame(&mut self, encoded_frame: EncodedFrame<'_>) -> Result<DecodedFrame, Error>;
+
+    /// Change the deblocking filter applied to the frames decoded after this.
+    ///
+    /// Only decoders with a deblocking filter of their own need to implement
+    /// this; the rest ignore it.
+    fn set_deblocking(&mut self, _filter: VideoDeblocking) {}
 }
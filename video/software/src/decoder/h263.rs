@@ -114,6 +114,10 @@ impl VideoDecoder for H263Decoder {
             ))
         }
     }
+
+    fn set_deblocking(&mut self, filter: VideoDeblocking) {
+        self.1 = filter;
+    }
 }
 
 impl Default for H263Decoder {
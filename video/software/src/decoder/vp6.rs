@@ -8,6 +8,7 @@ use nihav_core::codecs::NADecoderSupport;
 use nihav_duck::codecs::vp6::{VP56Decoder, VP56Parser, VP6BR};
 use nihav_duck::codecs::vpcommon::{BoolCoder, VP_YUVA420_FORMAT};
 use ruffle_video::frame::{DecodedFrame, EncodedFrame, FrameDependency};
+use swf::VideoDeblocking;
 
 #[derive(thiserror::Error, Debug)]
 pub enum Vp6Error {
@@ -43,10 +44,11 @@ pub struct Vp6Decoder {
     bitreader: VP6BR,
     init_called: bool,
     last_frame: Option<NABufferRef<NAVideoBuffer<u8>>>,
+    deblocking: VideoDeblocking,
 }
 
 impl Vp6Decoder {
-    pub fn new(with_alpha: bool, bounds: (u16, u16)) -> Self {
+    pub fn new(with_alpha: bool, bounds: (u16, u16), deblocking: VideoDeblocking) -> Self {
         // Unfortunately, `init()` cannot be called on the decoder
         // just yet, because `bounds` is only the declared size of
         // the video, to which it will be cropped.
@@ -65,6 +67,113 @@ impl Vp6Decoder {
             bitreader: VP6BR::new(),
             init_called: false,
             last_frame: None,
+            deblocking,
+        }
+    }
+}
+
+/// How strongly block edges are smoothed by the optional deblocking pass.
+const DEBLOCK_STRENGTH: i16 = 8;
+
+/// How close a pixel has to be to all of its neighbours to be smoothed by the
+/// optional deringing pass. Anything further off is taken to be a real edge.
+const DERING_THRESHOLD: i16 = 12;
+
+/// Smooth the edges between the 8x8 blocks of a plane.
+///
+/// This is the H.263 deblocking filter, which VP6 decoders in Flash offer on
+/// top of the loop filter of the codec itself.
+fn deblock_plane(plane: &mut [u8], width: usize) {
+    if width == 0 {
+        return;
+    }
+    let height = plane.len() / width;
+
+    for y in 0..height {
+        for x in (8..width.saturating_sub(1)).step_by(8) {
+            let i = y * width + x;
+            filter_edge(plane, [i - 2, i - 1, i, i + 1]);
+        }
+    }
+    for y in (8..height.saturating_sub(1)).step_by(8) {
+        for x in 0..width {
+            let i = y * width + x;
+            filter_edge(plane, [i - 2 * width, i - width, i, i + width]);
+        }
+    }
+}
+
+/// Filter the two pixels on either side of a block edge, given as the
+/// indices of the pixels in order across the edge.
+fn filter_edge(plane: &mut [u8], [p1, p0, q0, q1]: [usize; 4]) {
+    let [a, b, c, d] = [p1, p0, q0, q1].map(|i| i16::from(plane[i]));
+
+    let delta = (a - 4 * b + 4 * c - d) / 8;
+    // Small steps are smoothed out, while big ones are real edges to be kept.
+    let ramp = (delta.abs() - (2 * delta.abs() - DEBLOCK_STRENGTH).max(0)).max(0);
+    let d1 = delta.signum() * ramp;
+    let limit = (d1 / 2).abs();
+    let d2 = ((a - d) / 4).clamp(-limit, limit);
+
+    let clamp = |value: i16| value.clamp(0, 255) as u8;
+    plane[p1] = clamp(a - d2);
+    plane[p0] = clamp(b + d1);
+    plane[q0] = clamp(c - d1);
+    plane[q1] = clamp(d + d2);
+}
+
+/// Smooth the ripples that compression leaves around edges, by averaging
+/// every pixel that only differs a little from its neighbours.
+fn dering_plane(plane: &mut [u8], width: usize) {
+    if width < 3 || plane.len() / width < 3 {
+        return;
+    }
+    let height = plane.len() / width;
+    let source = plane.to_vec();
+
+    for y in 1..height - 1 {
+        for x in 1..width - 1 {
+            let i = y * width + x;
+            let pixel = i16::from(source[i]);
+            let neighbours = [i - 1, i + 1, i - width, i + width].map(|n| i16::from(source[n]));
+            if neighbours
+                .iter()
+                .all(|&n| (n - pixel).abs() < DERING_THRESHOLD)
+            {
+                let sum: i16 = neighbours.iter().sum();
+                plane[i] = ((4 * pixel + sum + 4) / 8) as u8;
+            }
+        }
+    }
+}
+
+/// Apply the extra filtering `deblocking` asks for to the planes of a frame.
+///
+/// Only levels 3 to 5 are for VP6. Level 5 is the faster version of level 4,
+/// which leaves out deringing the chroma planes.
+fn post_process(
+    deblocking: VideoDeblocking,
+    (y, width): (&mut [u8], usize),
+    (u, v, chroma_width): (&mut [u8], &mut [u8], usize),
+) {
+    let dering_chroma = match deblocking {
+        VideoDeblocking::Level2 => None,
+        VideoDeblocking::Level3 => Some(true),
+        VideoDeblocking::Level4 => Some(false),
+        VideoDeblocking::UseVideoPacketValue | VideoDeblocking::None | VideoDeblocking::Level1 => {
+            return
+        }
+    };
+
+    deblock_plane(y, width);
+    deblock_plane(u, chroma_width);
+    deblock_plane(v, chroma_width);
+
+    if let Some(dering_chroma) = dering_chroma {
+        dering_plane(y, width);
+        if dering_chroma {
+            dering_plane(u, chroma_width);
+            dering_plane(v, chroma_width);
         }
     }
 }
@@ -210,13 +319,19 @@ impl VideoDecoder for Vp6Decoder {
         //(most commonly: unused pieces of macroblocks)
         // Bitmap at the moment does not allow these gaps, so we need to remove them.
 
-        let y = crop(y, width, bounds);
-        let u = crop(u, chroma_width, ((bounds.0 + 1) / 2, (bounds.1 + 1) / 2));
-        let v = crop(v, chroma_width, ((bounds.0 + 1) / 2, (bounds.1 + 1) / 2));
+        let mut y = crop(y, width, bounds);
+        let mut u = crop(u, chroma_width, ((bounds.0 + 1) / 2, (bounds.1 + 1) / 2));
+        let mut v = crop(v, chroma_width, ((bounds.0 + 1) / 2, (bounds.1 + 1) / 2));
 
         width = bounds.0 as usize;
         height = bounds.1 as usize;
 
+        post_process(
+            self.deblocking,
+            (&mut y, width),
+            (&mut u, &mut v, (width + 1) / 2),
+        );
+
         // Adding in the alpha component, if present.
         if self.with_alpha {
             // Apparently it's possible for the alpha channel to be coded in a different size than the Y channel.
@@ -251,10 +366,60 @@ impl VideoDecoder for Vp6Decoder {
             ))
         }
     }
+
+    fn set_deblocking(&mut self, filter: VideoDeblocking) {
+        self.deblocking = filter;
+    }
 }
 
 impl Default for Vp6Decoder {
     fn default() -> Self {
-        Self::new(false, (0, 0))
+        Self::new(false, (0, 0), VideoDeblocking::UseVideoPacketValue)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{deblock_plane, post_process};
+    use swf::VideoDeblocking;
+
+    #[test]
+    fn deblocking_smooths_small_steps_at_block_edges() {
+        let mut plane = [[100; 8], [104; 8]].concat();
+        deblock_plane(&mut plane, 16);
+        assert_eq!(plane[..7], [100; 7]);
+        assert_eq!(plane[7..9], [101, 103]);
+        assert_eq!(plane[9..], [104; 7]);
+    }
+
+    #[test]
+    fn deblocking_keeps_real_edges() {
+        let original = [[0; 8], [200; 8]].concat().repeat(16);
+        let mut plane = original.clone();
+        deblock_plane(&mut plane, 16);
+        assert_eq!(plane, original);
+    }
+
+    #[test]
+    fn levels_choose_which_planes_are_deringed() {
+        let ripple = [100, 100, 100, 100, 110, 100, 100, 100, 100];
+        let smoothed = [100, 100, 100, 100, 105, 100, 100, 100, 100];
+
+        let run = |deblocking| {
+            let [mut y, mut u, mut v] = [ripple; 3];
+            post_process(deblocking, (&mut y, 3), (&mut u, &mut v, 3));
+            [y, u, v]
+        };
+
+        for deblocking in [
+            VideoDeblocking::UseVideoPacketValue,
+            VideoDeblocking::None,
+            VideoDeblocking::Level1,
+            VideoDeblocking::Level2,
+        ] {
+            assert_eq!(run(deblocking), [ripple; 3]);
+        }
+        assert_eq!(run(VideoDeblocking::Level3), [smoothed; 3]);
+        assert_eq!(run(VideoDeblocking::Level4), [smoothed, ripple, ripple]);
     }
 }
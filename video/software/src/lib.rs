@@ -1,2 +1,3 @@
 pub mod backend;
 pub mod decoder;
+pub mod media;
@@ -0,0 +1,113 @@
+#[cfg(feature = "aac")]
+pub mod aac;
+
+#[cfg(feature = "h264")]
+pub mod h264;
+
+use generational_arena::Arena;
+use ruffle_video::error::Error;
+use ruffle_video::frame::DecodedFrame;
+use ruffle_video::media::{DecodedAudio, MediaCodec, MediaDecoderBackend, MediaStreamHandle};
+
+/// Software media decoder backend, decoding H.264 with openh264 and AAC with
+/// Symphonia.
+///
+/// Each codec is only supported if its feature (`h264` or `aac`) is enabled.
+pub struct SoftwareMediaDecoderBackend {
+    streams: Arena<MediaStream>,
+}
+
+impl Default for SoftwareMediaDecoderBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SoftwareMediaDecoderBackend {
+    pub fn new() -> Self {
+        Self {
+            streams: Arena::new(),
+        }
+    }
+}
+
+impl MediaDecoderBackend for SoftwareMediaDecoderBackend {
+    fn supports_codec(&self, codec: MediaCodec) -> bool {
+        match codec {
+            MediaCodec::H264 => cfg!(feature = "h264"),
+            MediaCodec::Aac => cfg!(feature = "aac"),
+        }
+    }
+
+    fn register_media_stream(
+        &mut self,
+        codec: MediaCodec,
+        config: &[u8],
+    ) -> Result<MediaStreamHandle, Error> {
+        let stream = MediaStream::new(codec, config)?;
+        Ok(self.streams.insert(stream))
+    }
+
+    #[cfg_attr(not(feature = "h264"), allow(unused_variables))]
+    fn decode_video_sample(
+        &mut self,
+        stream: MediaStreamHandle,
+        sample: &[u8],
+    ) -> Result<Option<DecodedFrame>, Error> {
+        match self
+            .streams
+            .get_mut(stream)
+            .ok_or(Error::MediaStreamIsNotRegistered)?
+        {
+            #[cfg(feature = "h264")]
+            MediaStream::H264(decoder) => decoder.decode(sample),
+            #[cfg(any(feature = "aac", not(feature = "h264")))]
+            _ => Err(Error::DecoderError("Not a video stream".into())),
+        }
+    }
+
+    #[cfg_attr(not(feature = "aac"), allow(unused_variables))]
+    fn decode_audio_sample(
+        &mut self,
+        stream: MediaStreamHandle,
+        sample: &[u8],
+    ) -> Result<DecodedAudio, Error> {
+        match self
+            .streams
+            .get_mut(stream)
+            .ok_or(Error::MediaStreamIsNotRegistered)?
+        {
+            #[cfg(feature = "aac")]
+            MediaStream::Aac(decoder) => decoder.decode(sample),
+            #[cfg(any(feature = "h264", not(feature = "aac")))]
+            _ => Err(Error::DecoderError("Not an audio stream".into())),
+        }
+    }
+
+    fn unregister_media_stream(&mut self, stream: MediaStreamHandle) {
+        self.streams.remove(stream);
+    }
+}
+
+/// A single media stream, along with the state of its decoder.
+enum MediaStream {
+    #[cfg(feature = "h264")]
+    H264(h264::H264Decoder),
+
+    #[cfg(feature = "aac")]
+    Aac(aac::AacDecoder),
+}
+
+impl MediaStream {
+    #[cfg_attr(not(any(feature = "h264", feature = "aac")), allow(unused_variables))]
+    fn new(codec: MediaCodec, config: &[u8]) -> Result<Self, Error> {
+        match codec {
+            #[cfg(feature = "h264")]
+            MediaCodec::H264 => Ok(Self::H264(h264::H264Decoder::new(config)?)),
+            #[cfg(feature = "aac")]
+            MediaCodec::Aac => Ok(Self::Aac(aac::AacDecoder::new(config)?)),
+            #[cfg(not(all(feature = "h264", feature = "aac")))]
+            other => Err(Error::UnsupportedMediaCodec(other)),
+        }
+    }
+}
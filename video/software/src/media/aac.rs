@@ -0,0 +1,50 @@
+use ruffle_video::error::Error;
+use ruffle_video::media::DecodedAudio;
+use symphonia::core::audio::SampleBuffer;
+use symphonia::core::codecs::{CodecParameters, Decoder, DecoderOptions, CODEC_TYPE_AAC};
+use symphonia::core::formats::Packet;
+use symphonia::default::codecs::AacDecoder as SymphoniaAacDecoder;
+
+#[derive(thiserror::Error, Debug)]
+pub enum AacError {
+    #[error("Decoder error: {0}")]
+    DecoderError(#[from] symphonia::core::errors::Error),
+}
+
+impl From<AacError> for Error {
+    fn from(error: AacError) -> Self {
+        Error::DecoderError(Box::new(error))
+    }
+}
+
+/// AAC audio decoder.
+pub struct AacDecoder(SymphoniaAacDecoder);
+
+impl AacDecoder {
+    /// Create a decoder from an `AudioSpecificConfig`, which holds the
+    /// stream's profile, sample rate and channel layout.
+    pub fn new(config: &[u8]) -> Result<Self, Error> {
+        let mut params = CodecParameters::new();
+        params
+            .for_codec(CODEC_TYPE_AAC)
+            .with_extra_data(config.into());
+        let decoder = SymphoniaAacDecoder::try_new(&params, &DecoderOptions::default())
+            .map_err(AacError::from)?;
+        Ok(Self(decoder))
+    }
+
+    pub fn decode(&mut self, sample: &[u8]) -> Result<DecodedAudio, Error> {
+        let packet = Packet::new_from_slice(0, 0, 0, sample);
+        let decoded = self.0.decode(&packet).map_err(AacError::from)?;
+
+        let spec = *decoded.spec();
+        let mut samples = SampleBuffer::<i16>::new(decoded.capacity() as u64, spec);
+        samples.copy_interleaved_ref(decoded);
+
+        Ok(DecodedAudio {
+            sample_rate: spec.rate,
+            num_channels: spec.channels.count() as u16,
+            samples: samples.samples().to_vec(),
+        })
+    }
+}
@@ -0,0 +1,196 @@
+use openh264::decoder::Decoder;
+use ruffle_render::bitmap::BitmapFormat;
+use ruffle_video::error::Error;
+use ruffle_video::frame::DecodedFrame;
+
+#[derive(thiserror::Error, Debug)]
+pub enum H264Error {
+    #[error("Decoder error: {0}")]
+    // openh264's errors aren't guaranteed to be thread-safe, so only the message is kept.
+    DecoderError(String),
+
+    #[error("Invalid decoder configuration record")]
+    InvalidConfiguration,
+
+    #[error("NAL unit is longer than its sample")]
+    TruncatedNalUnit,
+}
+
+impl From<H264Error> for Error {
+    fn from(error: H264Error) -> Self {
+        Error::DecoderError(Box::new(error))
+    }
+}
+
+impl From<openh264::Error> for H264Error {
+    fn from(error: openh264::Error) -> Self {
+        H264Error::DecoderError(error.to_string())
+    }
+}
+
+const START_CODE: [u8; 4] = [0, 0, 0, 1];
+
+/// H.264 video decoder.
+///
+/// openh264 only reads Annex B bitstreams, where NAL units are separated by
+/// start codes, so the length-prefixed NAL units of FLV and MP4 samples are
+/// converted on the way in.
+pub struct H264Decoder {
+    decoder: Decoder,
+
+    /// The size of the length that prefixes each NAL unit, in bytes.
+    length_size: usize,
+}
+
+impl H264Decoder {
+    /// Create a decoder from an `AVCDecoderConfigurationRecord`, which holds
+    /// the stream's sequence and picture parameter sets.
+    pub fn new(config: &[u8]) -> Result<Self, Error> {
+        let (length_size, parameter_sets) = parse_configuration(config)?;
+        let mut decoder = Decoder::new().map_err(H264Error::from)?;
+        decoder.decode(&parameter_sets).map_err(H264Error::from)?;
+
+        Ok(Self {
+            decoder,
+            length_size,
+        })
+    }
+
+    pub fn decode(&mut self, sample: &[u8]) -> Result<Option<DecodedFrame>, Error> {
+        let bitstream = to_annex_b(sample, self.length_size)?;
+        let Some(picture) = self.decoder.decode(&bitstream).map_err(H264Error::from)? else {
+            return Ok(None);
+        };
+
+        let (width, height) = picture.dimension_rgb();
+        let mut data = vec![0; width * height * 3];
+        picture.write_rgb8(&mut data);
+
+        Ok(Some(DecodedFrame::new(
+            width as u32,
+            height as u32,
+            BitmapFormat::Rgb,
+            data,
+        )))
+    }
+}
+
+/// Read the NAL unit length size and parameter sets out of an
+/// `AVCDecoderConfigurationRecord` (ISO/IEC 14496-15), the latter converted
+/// to Annex B.
+fn parse_configuration(config: &[u8]) -> Result<(usize, Vec<u8>), H264Error> {
+    let length_size = (config.get(4).ok_or(H264Error::InvalidConfiguration)? & 0b11) as usize + 1;
+    let mut parameter_sets = vec![];
+    let mut rest = config.get(5..).ok_or(H264Error::InvalidConfiguration)?;
+
+    // Sequence parameter sets come first, then picture parameter sets.
+    for count_mask in [0b1_1111, 0b1111_1111] {
+        let (&count, sets) = rest.split_first().ok_or(H264Error::InvalidConfiguration)?;
+        rest = sets;
+        for _ in 0..(count & count_mask) {
+            let len = rest
+                .get(..2)
+                .map(|len| u16::from_be_bytes([len[0], len[1]]) as usize)
+                .ok_or(H264Error::InvalidConfiguration)?;
+            let set = rest
+                .get(2..2 + len)
+                .ok_or(H264Error::InvalidConfiguration)?;
+            parameter_sets.extend_from_slice(&START_CODE);
+            parameter_sets.extend_from_slice(set);
+            rest = &rest[2 + len..];
+        }
+    }
+
+    Ok((length_size, parameter_sets))
+}
+
+/// Replace the length prefixes of a sample's NAL units with start codes.
+fn to_annex_b(mut sample: &[u8], length_size: usize) -> Result<Vec<u8>, H264Error> {
+    let mut bitstream = Vec::with_capacity(sample.len() + 4);
+    while !sample.is_empty() {
+        let len = sample
+            .get(..length_size)
+            .ok_or(H264Error::TruncatedNalUnit)?
+            .iter()
+            .fold(0, |len, byte| len << 8 | *byte as usize);
+        let end = length_size
+            .checked_add(len)
+            .ok_or(H264Error::TruncatedNalUnit)?;
+        let nal_unit = sample
+            .get(length_size..end)
+            .ok_or(H264Error::TruncatedNalUnit)?;
+        bitstream.extend_from_slice(&START_CODE);
+        bitstream.extend_from_slice(nal_unit);
+        sample = &sample[end..];
+    }
+    Ok(bitstream)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_configuration, to_annex_b, H264Error};
+
+    /// A configuration with 4-byte NAL unit lengths, one sequence parameter set and one picture parameter set.
+    const CONFIG: [u8; 16] = [
+        1, 0x42, 0, 0x1e, 0xff, 0xe1, 0, 3, 0x67, 1, 2, 1, 0, 2, 0x68, 3,
+    ];
+
+    #[test]
+    fn parses_configuration() {
+        let (length_size, parameter_sets) = parse_configuration(&CONFIG).unwrap();
+        assert_eq!(length_size, 4);
+        assert_eq!(
+            parameter_sets,
+            [0, 0, 0, 1, 0x67, 1, 2, 0, 0, 0, 1, 0x68, 3]
+        );
+    }
+
+    #[test]
+    fn rejects_truncated_configuration() {
+        for len in 0..16 {
+            assert!(
+                matches!(
+                    parse_configuration(&CONFIG[..len]),
+                    Err(H264Error::InvalidConfiguration)
+                ),
+                "configuration cut off at {len} bytes"
+            );
+        }
+
+        // The picture parameter set claims to be longer than what follows it.
+        let mut config = CONFIG;
+        config[13] = 3;
+        assert!(matches!(
+            parse_configuration(&config),
+            Err(H264Error::InvalidConfiguration)
+        ));
+    }
+
+    #[test]
+    fn converts_to_annex_b() {
+        assert_eq!(
+            to_annex_b(&[0, 0, 0, 2, 0x65, 1, 0, 0, 0, 1, 0x41], 4).unwrap(),
+            [0, 0, 0, 1, 0x65, 1, 0, 0, 0, 1, 0x41]
+        );
+        assert_eq!(
+            to_annex_b(&[0, 1, 0x65, 0, 0], 2).unwrap(),
+            [0, 0, 0, 1, 0x65, 0, 0, 0, 1]
+        );
+        assert!(to_annex_b(&[], 4).unwrap().is_empty());
+    }
+
+    #[test]
+    fn rejects_truncated_nal_units() {
+        for sample in [
+            &[0, 0, 0][..],
+            &[0, 0, 0, 3, 0x65, 1],
+            &[0, 0, 0, 1, 0x65, 0, 0],
+            &[0xff, 0xff, 0xff, 0xff, 0x65],
+        ] {
+            assert!(
+                matches!(to_annex_b(sample, 4), Err(H264Error::TruncatedNalUnit)),
+                "sample {sample:?}"
+            );
+        }
+    }
+}
@@ -26,6 +26,16 @@ pub trait VideoBackend {
         filter: VideoDeblocking,
     ) -> Result<VideoStreamHandle, Error>;
 
+    /// Change the deblocking filter applied to a video stream's frames.
+    ///
+    /// This takes effect from the next decoded frame. Codecs that have no
+    /// such filter are free to ignore it.
+    fn set_video_stream_deblocking(
+        &mut self,
+        stream: VideoStreamHandle,
+        filter: VideoDeblocking,
+    ) -> Result<(), Error>;
+
     /// Preload a frame of a given video stream.
     ///
     /// No decoding is intended to happen at this point in time. Instead, the
@@ -1,3 +1,4 @@
+use crate::media::MediaCodec;
 use swf::VideoCodec;
 use thiserror::Error;
 
@@ -9,9 +10,15 @@ pub enum Error {
     #[error("Unsupported video codec type: {0:?}")]
     UnsupportedCodec(VideoCodec),
 
+    #[error("Unsupported media codec type: {0:?}")]
+    UnsupportedMediaCodec(MediaCodec),
+
     #[error("Video stream is not registered")]
     VideoStreamIsNotRegistered,
 
+    #[error("Media stream is not registered")]
+    MediaStreamIsNotRegistered,
+
     #[error("Couldn't create bitmap for video frame")]
     BitmapError(#[from] ruffle_render::error::Error),
 
@@ -5,6 +5,7 @@ use generational_arena::Index;
 pub mod backend;
 pub mod error;
 pub mod frame;
+pub mod media;
 pub mod null;
 
 pub type VideoStreamHandle = Index;
@@ -0,0 +1,72 @@
+use crate::error::Error;
+use crate::frame::DecodedFrame;
+use generational_arena::Index;
+
+pub type MediaStreamHandle = Index;
+
+/// A codec that can only be played from FLV and MP4 files, rather than
+/// embedded in a SWF.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MediaCodec {
+    /// H.264/AVC video.
+    ///
+    /// Samples are NAL units, each prefixed with its big-endian length, as
+    /// they are stored in both FLV and MP4 files.
+    H264,
+
+    /// AAC audio, in raw access units.
+    Aac,
+}
+
+/// The audio decoded from a single sample.
+#[derive(Clone, Debug)]
+pub struct DecodedAudio {
+    pub sample_rate: u32,
+    pub num_channels: u16,
+
+    /// Signed 16-bit samples, interleaved by channel.
+    pub samples: Vec<i16>,
+}
+
+/// A backend that decodes the audio and video codecs of FLV and MP4 files
+/// that Flash Player supports in addition to the SWF ones.
+///
+/// Unlike `VideoBackend`, which decodes whole video streams of a known number
+/// of frames, a media stream is decoded one sample at a time as it arrives.
+/// Implementations don't need to support any codec; `NetStream` reports the
+/// media as unplayable if they don't.
+pub trait MediaDecoderBackend {
+    /// Whether streams of the given codec can be decoded at all.
+    fn supports_codec(&self, codec: MediaCodec) -> bool;
+
+    /// Register a new media stream.
+    ///
+    /// `config` is the codec's decoder configuration: an
+    /// `AVCDecoderConfigurationRecord` for H.264, and an `AudioSpecificConfig`
+    /// for AAC. Both FLV and MP4 files carry these as they are.
+    fn register_media_stream(
+        &mut self,
+        codec: MediaCodec,
+        config: &[u8],
+    ) -> Result<MediaStreamHandle, Error>;
+
+    /// Decode the next sample of a video stream.
+    ///
+    /// Samples must be given in decoding order. Decoders may hold on to
+    /// frames before returning them, in which case this returns `None`.
+    fn decode_video_sample(
+        &mut self,
+        stream: MediaStreamHandle,
+        sample: &[u8],
+    ) -> Result<Option<DecodedFrame>, Error>;
+
+    /// Decode the next sample of an audio stream.
+    fn decode_audio_sample(
+        &mut self,
+        stream: MediaStreamHandle,
+        sample: &[u8],
+    ) -> Result<DecodedAudio, Error>;
+
+    /// Drop a media stream that won't be decoded from again.
+    fn unregister_media_stream(&mut self, stream: MediaStreamHandle);
+}
@@ -1,6 +1,7 @@
 use crate::backend::VideoBackend;
 use crate::error::Error;
-use crate::frame::{EncodedFrame, FrameDependency};
+use crate::frame::{DecodedFrame, EncodedFrame, FrameDependency};
+use crate::media::{DecodedAudio, MediaCodec, MediaDecoderBackend, MediaStreamHandle};
 use crate::VideoStreamHandle;
 use generational_arena::Arena;
 use ruffle_render::backend::RenderBackend;
@@ -44,6 +45,14 @@ impl VideoBackend for NullVideoBackend {
         Ok(self.streams.insert(()))
     }
 
+    fn set_video_stream_deblocking(
+        &mut self,
+        _stream: VideoStreamHandle,
+        _filter: VideoDeblocking,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
     fn preload_video_stream_frame(
         &mut self,
         _stream: VideoStreamHandle,
@@ -61,3 +70,45 @@ impl VideoBackend for NullVideoBackend {
         Err(Error::DecodingNotSupported)
     }
 }
+
+/// Implementation of media decoding that does not support any codec.
+#[derive(Default)]
+pub struct NullMediaDecoderBackend;
+
+impl NullMediaDecoderBackend {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl MediaDecoderBackend for NullMediaDecoderBackend {
+    fn supports_codec(&self, _codec: MediaCodec) -> bool {
+        false
+    }
+
+    fn register_media_stream(
+        &mut self,
+        codec: MediaCodec,
+        _config: &[u8],
+    ) -> Result<MediaStreamHandle, Error> {
+        Err(Error::UnsupportedMediaCodec(codec))
+    }
+
+    fn decode_video_sample(
+        &mut self,
+        _stream: MediaStreamHandle,
+        _sample: &[u8],
+    ) -> Result<Option<DecodedFrame>, Error> {
+        Err(Error::MediaStreamIsNotRegistered)
+    }
+
+    fn decode_audio_sample(
+        &mut self,
+        _stream: MediaStreamHandle,
+        _sample: &[u8],
+    ) -> Result<DecodedAudio, Error> {
+        Err(Error::MediaStreamIsNotRegistered)
+    }
+
+    fn unregister_media_stream(&mut self, _stream: MediaStreamHandle) {}
+}
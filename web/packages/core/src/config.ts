@@ -45,4 +45,6 @@ export const DEFAULT_CONFIG: Required<BaseLoadOptions> = {
     allowNetworking: NetworkingAccessMode.All,
     openInNewTab: null,
     socketProxy: [],
+    socketProxyTemplate: null,
+    airDirectories: null,
 };
@@ -267,6 +267,21 @@ export interface SocketProxy {
     proxyUrl: string;
 }
 
+/**
+ * The URLs of the directories of an AIR application unpacked on a server.
+ */
+export interface AirDirectories {
+    /**
+     * The directory the application was unpacked to, which `app:/` URLs refer to.
+     */
+    application: string;
+
+    /**
+     * The application's storage directory, which `app-storage:/` URLs refer to.
+     */
+    storage: string;
+}
+
 /**
  * Any options used for loading a movie.
  */
@@ -555,12 +570,36 @@ export interface BaseLoadOptions {
      * a matching SocketProxy object in this array and use it to establish a WebSocket connection,
      * through which all communication is tunneled through.
      *
-     * When none are found, Ruffle will fail the connection gracefully.
+     * When none are found, Ruffle will use `socketProxyTemplate` if set,
+     * or otherwise fail the connection gracefully.
      * When multiple matching SocketProxy objects exist, the first one is used.
      *
      * @default []
      */
     socketProxy?: Array<SocketProxy>;
+
+    /**
+     * A WebSocket URL to tunnel Socket connections through when no entry of `socketProxy` matches.
+     *
+     * The placeholders `{host}` and `{port}` are replaced by the host and port the SWF
+     * tries to connect to, e.g. `"wss://example.com/proxy/{host}/{port}"`. The host is
+     * percent-encoded, as if by `encodeURIComponent`.
+     *
+     * When null, connections without a matching SocketProxy fail.
+     *
+     * @default null
+     */
+    socketProxyTemplate?: string | null;
+
+    /**
+     * Runs the movie as an AIR application, whose directories are at these URLs.
+     *
+     * Relative URLs are resolved against `base`. Only AIR applications can use
+     * `app:/` and `app-storage:/` URLs and the `flash.filesystem` APIs.
+     *
+     * @default null
+     */
+    airDirectories?: AirDirectories | null;
 }
 
 /**
@@ -0,0 +1,146 @@
+//! Extracting items from a movie's library in the browser, like the exporter does natively.
+
+use crate::create_renderer;
+use js_sys::{Array, Object, Promise, Reflect, Uint8Array};
+use ruffle_core::embed::export::{
+    library_items, ColorVariant, ExportedFile, ItemExporterPlugin, PngExporter, SoundExporter,
+};
+use ruffle_core::embed::{ExecutionLimit, Player, PlayerBuilder, SwfMovie};
+use ruffle_core::swf::CharacterId;
+use std::sync::{Arc, Mutex};
+use wasm_bindgen::prelude::*;
+
+/// A player that only loads a movie in order to export its items.
+///
+/// The movie is loaded but never played, and nothing is attached to the page.
+#[wasm_bindgen]
+pub struct ItemExtractor {
+    core: Arc<Mutex<Player>>,
+}
+
+#[wasm_bindgen]
+impl ItemExtractor {
+    /// Loads the movie in `swf_data`, which sees `url` as its own URL.
+    ///
+    /// Resolves to an `ItemExtractor` once the movie's library is ready.
+    #[allow(clippy::new_ret_no_self)]
+    #[wasm_bindgen(constructor)]
+    pub fn new(swf_data: Uint8Array, url: String, preferred_renderer: Option<String>) -> Promise {
+        wasm_bindgen_futures::future_to_promise(async move {
+            let extractor = Self::new_internal(swf_data, url, preferred_renderer.as_deref())
+                .await
+                .map_err(|err| JsValue::from(format!("Error creating extractor: {err}")))?;
+            Ok(JsValue::from(extractor))
+        })
+    }
+
+    /// The characters the movie defines, as `{ id, kind, name }` objects.
+    pub fn items(&self) -> Result<JsValue, JsValue> {
+        let core = self.core.lock().map_err(|_| "Player is poisoned")?;
+        let items = library_items(core.root_movie());
+        Ok(serde_wasm_bindgen::to_value(&items)?)
+    }
+
+    /// The formats `export_item` can export to.
+    pub fn export_formats(&self) -> Result<Array, JsValue> {
+        let core = self.core.lock().map_err(|_| "Player is poisoned")?;
+        Ok(core
+            .export_formats()
+            .into_iter()
+            .map(JsValue::from)
+            .collect())
+    }
+
+    /// Exports character `id` to `format`, as an array of `{ name, data }` objects where `data`
+    /// is a `Uint8Array`. The last file is the item's manifest.
+    pub fn export_item(&self, id: CharacterId, format: &str) -> Result<Array, JsValue> {
+        self.export(id, format)?
+            .into_iter()
+            .map(file_to_js)
+            .collect()
+    }
+
+    /// Renders `frame` of character `id` (counting from 1) to a PNG, `scale` times its size.
+    pub fn render_png(
+        &self,
+        id: CharacterId,
+        frame: u16,
+        scale: f64,
+    ) -> Result<Uint8Array, JsValue> {
+        let plugin = PngExporter {
+            scale,
+            frame: Some(frame),
+        };
+        self.export_data(id, &plugin)
+    }
+
+    /// Extracts sound `id`, as an MP3 file if it's stored that way and a WAV file otherwise.
+    pub fn extract_sound(&self, id: CharacterId) -> Result<Uint8Array, JsValue> {
+        self.export_data(id, &SoundExporter)
+    }
+}
+
+impl ItemExtractor {
+    async fn new_internal(
+        swf_data: Uint8Array,
+        url: String,
+        preferred_renderer: Option<&str>,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let window = web_sys::window().ok_or("Expected window")?;
+        let document = window.document().ok_or("Expected document")?;
+        let (builder, _canvas) =
+            create_renderer(PlayerBuilder::new(), &document, preferred_renderer, true).await?;
+
+        let movie = SwfMovie::from_data(&swf_data.to_vec(), url, None)?;
+        let core = builder.with_movie(movie).with_autoplay(false).build();
+        core.lock()
+            .map_err(|_| "Player is poisoned")?
+            .preload(&mut ExecutionLimit::none());
+        Ok(Self { core })
+    }
+
+    fn with_core_mut<T>(&self, f: impl FnOnce(&mut Player) -> T) -> Result<T, JsValue> {
+        let mut core = self.core.lock().map_err(|_| "Player is poisoned")?;
+        Ok(f(&mut core))
+    }
+
+    fn export(&self, id: CharacterId, format: &str) -> Result<Vec<ExportedFile>, JsValue> {
+        self.with_core_mut(|core| {
+            let movie = core.root_movie().clone();
+            core.export_item(&movie, id, format)
+        })?
+        .map_err(|e| JsValue::from(format!("Error exporting character {id}: {e}")))
+    }
+
+    /// The single file `plugin` exports character `id` to, leaving out the manifest.
+    ///
+    /// The plugin isn't registered, so its settings don't carry over to `export_item`.
+    fn export_data(
+        &self,
+        id: CharacterId,
+        plugin: &dyn ItemExporterPlugin,
+    ) -> Result<Uint8Array, JsValue> {
+        let mut files = self
+            .with_core_mut(|core| {
+                let movie = core.root_movie().clone();
+                core.export_item_with(&movie, id, plugin, &[ColorVariant::default()])
+            })?
+            .map_err(|e| JsValue::from(format!("Error exporting character {id}: {e}")))?;
+        files.retain(|file| !file.name.ends_with(".manifest.json"));
+        match files.as_slice() {
+            [file] => Ok(Uint8Array::from(file.data.as_slice())),
+            _ => Err(format!("Character {id} exported to {} files", files.len()).into()),
+        }
+    }
+}
+
+fn file_to_js(file: ExportedFile) -> Result<JsValue, JsValue> {
+    let object = Object::new();
+    Reflect::set(&object, &"name".into(), &file.name.into())?;
+    Reflect::set(
+        &object,
+        &"data".into(),
+        &Uint8Array::from(file.data.as_slice()),
+    )?;
+    Ok(object.into())
+}
@@ -2,6 +2,7 @@
 
 //! Ruffle web frontend.
 mod audio;
+mod export;
 mod log_adapter;
 mod navigator;
 mod storage;
@@ -18,6 +19,7 @@ use ruffle_core::external::{
     ExternalInterfaceMethod, ExternalInterfaceProvider, FsCommandProvider, Value as ExternalValue,
     Value,
 };
+use ruffle_core::player_version::PlayerVersion;
 use ruffle_core::tag_utils::SwfMovie;
 use ruffle_core::{
     Color, Player, PlayerBuilder, PlayerEvent, SandboxType, StageAlign, StageScaleMode,
@@ -242,6 +244,14 @@ where
     deserializer.deserialize_any(DurationVisitor)
 }
 
+/// Where the `app:/` and `app-storage:/` URLs of an AIR application point to.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AirDirectoryUrls {
+    application: String,
+    storage: String,
+}
+
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SocketProxy {
@@ -300,6 +310,10 @@ struct Config {
     allow_networking: NetworkingAccessMode,
 
     socket_proxy: Vec<SocketProxy>,
+
+    socket_proxy_template: Option<String>,
+
+    air_directories: Option<AirDirectoryUrls>,
 }
 
 /// Metadata about the playing SWF file to be passed back to JavaScript.
@@ -534,8 +548,13 @@ impl Ruffle {
         let window = web_sys::window().ok_or("Expected window")?;
         let document = window.document().ok_or("Expected document")?;
 
-        let (mut builder, canvas) =
-            create_renderer(PlayerBuilder::new(), &document, &config).await?;
+        let (mut builder, canvas) = create_renderer(
+            PlayerBuilder::new(),
+            &document,
+            config.preferred_renderer.as_deref(),
+            config.wmode.as_deref() == Some("transparent"),
+        )
+        .await?;
 
         parent
             .append_child(&canvas.clone().into())
@@ -554,6 +573,8 @@ impl Ruffle {
             log_subscriber.clone(),
             config.open_url_mode,
             config.socket_proxy,
+            config.socket_proxy_template,
+            config.air_directories,
         ));
 
         match window.local_storage() {
@@ -587,7 +608,7 @@ impl Ruffle {
             .with_video(SoftwareVideoBackend::new())
             .with_letterbox(config.letterbox)
             .with_max_execution_duration(config.max_execution_duration)
-            .with_player_version(config.player_version)
+            .with_player_version(config.player_version.map(PlayerVersion::from))
             .with_compatibility_rules(if config.compatibility_rules {
                 CompatibilityRules::default()
             } else {
@@ -1455,6 +1476,14 @@ impl ExternalInterfaceProvider for JavascriptInterface {
     fn on_callback_available(&self, name: &str) {
         self.js_player.on_callback_available(name);
     }
+
+    fn run_javascript_url(&self, script: &str) -> bool {
+        // Indirect eval runs it in the global scope of the page, like the URL would.
+        if let Err(e) = js_sys::eval(script) {
+            tracing::warn!("javascript: URL threw an error: {e:?}");
+        }
+        true
+    }
 }
 
 impl FsCommandProvider for JavascriptInterface {
@@ -1531,7 +1560,8 @@ fn external_to_js_value(external: ExternalValue) -> JsValue {
 async fn create_renderer(
     builder: PlayerBuilder,
     document: &web_sys::Document,
-    config: &Config,
+    preferred_renderer: Option<&str>,
+    _is_transparent: bool,
 ) -> Result<(PlayerBuilder, HtmlCanvasElement), Box<dyn Error>> {
     #[cfg(not(any(
         feature = "canvas",
@@ -1541,13 +1571,11 @@ async fn create_renderer(
     )))]
     std::compile_error!("You must enable one of the render backend features (e.g., webgl).");
 
-    let _is_transparent = config.wmode.as_deref() == Some("transparent");
-
     let mut renderer_list = vec!["webgpu", "wgpu-webgl", "webgl", "canvas"];
-    if let Some(preferred_renderer) = &config.preferred_renderer {
+    if let Some(preferred_renderer) = preferred_renderer {
         if let Some(pos) = renderer_list.iter().position(|&r| r == preferred_renderer) {
             renderer_list.remove(pos);
-            renderer_list.insert(0, preferred_renderer.as_str());
+            renderer_list.insert(0, preferred_renderer);
         } else {
             tracing::error!("Unrecognized renderer name: {}", preferred_renderer);
         }
@@ -1,17 +1,20 @@
 //! Navigator backend for web
-use crate::SocketProxy;
+use crate::{AirDirectoryUrls, SocketProxy};
 use async_channel::Receiver;
 use futures_util::{SinkExt, StreamExt};
 use gloo_net::websocket::{futures::WebSocket, Message};
 use js_sys::{Array, ArrayBuffer, Uint8Array};
 use ruffle_core::backend::navigator::{
-    async_return, create_fetch_error, create_specific_fetch_error, ErrorResponse, NavigationMethod,
-    NavigatorBackend, OpenURLMode, OwnedFuture, Request, SuccessResponse,
+    async_return, create_fetch_error, create_specific_fetch_error, AirDirectories, ErrorResponse,
+    NavigationMethod, NavigatorBackend, OpenURLMode, OwnedFuture, RedirectPolicy, Request,
+    SuccessResponse,
 };
 use ruffle_core::config::NetworkingAccessMode;
 use ruffle_core::indexmap::IndexMap;
 use ruffle_core::loader::Error;
+use ruffle_core::net_group::{NetGroupAction, NetGroupHandle};
 use ruffle_core::socket::{ConnectionState, SocketAction, SocketHandle};
+use std::net::SocketAddr;
 use std::sync::mpsc::Sender;
 use std::sync::Arc;
 use std::time::Duration;
@@ -19,11 +22,12 @@ use tracing_subscriber::layer::Layered;
 use tracing_subscriber::Registry;
 use tracing_wasm::WASMLayer;
 use url::{ParseError, Url};
+use wasm_bindgen::closure::Closure;
 use wasm_bindgen::JsCast;
 use wasm_bindgen_futures::{spawn_local, JsFuture};
 use web_sys::{
-    window, Blob, BlobPropertyBag, HtmlFormElement, HtmlInputElement, Request as WebRequest,
-    RequestInit, Response as WebResponse,
+    window, AbortController, Blob, BlobPropertyBag, HtmlFormElement, HtmlInputElement,
+    Request as WebRequest, RequestInit, RequestRedirect, Response as WebResponse, ResponseType,
 };
 
 pub struct WebNavigatorBackend {
@@ -34,9 +38,12 @@ pub struct WebNavigatorBackend {
     base_url: Option<Url>,
     open_url_mode: OpenURLMode,
     socket_proxies: Vec<SocketProxy>,
+    socket_proxy_template: Option<String>,
+    air_directories: Option<AirDirectories>,
 }
 
 impl WebNavigatorBackend {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         allow_script_access: bool,
         allow_networking: NetworkingAccessMode,
@@ -45,6 +52,8 @@ impl WebNavigatorBackend {
         log_subscriber: Arc<Layered<WASMLayer, Registry>>,
         open_url_mode: OpenURLMode,
         socket_proxies: Vec<SocketProxy>,
+        socket_proxy_template: Option<String>,
+        air_directories: Option<AirDirectoryUrls>,
     ) -> Self {
         let window = web_sys::window().expect("window()");
 
@@ -79,6 +88,31 @@ impl WebNavigatorBackend {
             tracing::error!("Could not get base URL for base directory inference.");
         }
 
+        // The directories of an AIR application may be given relative to the base URL.
+        let air_directories = air_directories.and_then(|directories| {
+            let resolve = |mut url: String| {
+                if !url.ends_with('/') {
+                    url.push('/');
+                }
+                match &base_url {
+                    Some(base_url) => base_url.join(&url).ok(),
+                    None => Url::parse(&url).ok(),
+                }
+            };
+            match (
+                resolve(directories.application),
+                resolve(directories.storage),
+            ) {
+                (Some(application), Some(storage)) => {
+                    Some(AirDirectories::new(application, storage))
+                }
+                _ => {
+                    tracing::error!("Could not parse the URLs of the AIR application directories.");
+                    None
+                }
+            }
+        });
+
         Self {
             allow_script_access,
             allow_networking,
@@ -87,6 +121,8 @@ impl WebNavigatorBackend {
             log_subscriber,
             open_url_mode,
             socket_proxies,
+            socket_proxy_template,
+            air_directories,
         }
     }
 }
@@ -241,6 +277,23 @@ impl NavigatorBackend for WebNavigatorBackend {
             let mut init = RequestInit::new();
 
             init.method(&request.method().to_string());
+            init.redirect(match request.redirect_policy() {
+                RedirectPolicy::Follow => RequestRedirect::Follow,
+                RedirectPolicy::Manual => RequestRedirect::Manual,
+                RedirectPolicy::Error => RequestRedirect::Error,
+            });
+
+            // Browsers have no notion of connection or idle timeouts, so we approximate them
+            // by aborting the fetch if the headers or the body take too long, respectively.
+            let abort_controller =
+                if request.connection_timeout().is_some() || request.idle_timeout().is_some() {
+                    AbortController::new().ok()
+                } else {
+                    None
+                };
+            if let Some(abort_controller) = &abort_controller {
+                init.signal(Some(&abort_controller.signal()));
+            }
 
             if let Some((data, mime)) = request.body() {
                 let blob = Blob::new_with_buffer_source_sequence_and_options(
@@ -283,12 +336,19 @@ impl NavigatorBackend for WebNavigatorBackend {
             }
 
             let window = web_sys::window().expect("window()");
-            let fetchval = JsFuture::from(window.fetch_with_request(&web_request))
-                .await
-                .map_err(|_| ErrorResponse {
-                    url: url.to_string(),
-                    error: Error::FetchError("Got JS error".to_string()),
-                })?;
+            let timeout = abort_after(&window, &abort_controller, request.connection_timeout());
+            let fetchval = JsFuture::from(window.fetch_with_request(&web_request)).await;
+            if let Some(timeout) = timeout {
+                window.clear_timeout_with_handle(timeout);
+            }
+            let fetchval = fetchval.map_err(|_| ErrorResponse {
+                url: url.to_string(),
+                error: if timed_out(&abort_controller) {
+                    Error::FetchError("Request timed out".to_string())
+                } else {
+                    Error::FetchError("Got JS error".to_string())
+                },
+            })?;
 
             let response: WebResponse = fetchval.dyn_into().map_err(|_| ErrorResponse {
                 url: url.to_string(),
@@ -297,6 +357,20 @@ impl NavigatorBackend for WebNavigatorBackend {
             let url = response.url();
             let status = response.status();
             let redirected = response.redirected();
+
+            // Browsers hide everything about a redirect that wasn't followed,
+            // including where it points to.
+            if response.type_() == ResponseType::Opaqueredirect {
+                return Ok(SuccessResponse {
+                    url,
+                    body: vec![],
+                    status,
+                    redirected,
+                    redirect_url: None,
+                    headers: vec![],
+                });
+            }
+
             if !response.ok() {
                 let error = Error::HttpNotOk(
                     format!("HTTP status is not ok, got {}", response.status_text()),
@@ -306,24 +380,34 @@ impl NavigatorBackend for WebNavigatorBackend {
                 return Err(ErrorResponse { url, error });
             }
 
-            let body: ArrayBuffer = JsFuture::from(response.array_buffer().map_err(|_| {
-                ErrorResponse {
-                    url: url.clone(),
-                    error: Error::FetchError("Got JS error".to_string()),
-                }
-            })?)
-            .await
-            .map_err(|_| ErrorResponse {
-                url: url.clone(),
-                error: Error::FetchError(
-                    "Could not allocate array buffer for response".to_string(),
-                ),
-            })?
-            .dyn_into()
-            .map_err(|_| ErrorResponse {
+            let headers = response_headers(&response);
+            let timeout = abort_after(&window, &abort_controller, request.idle_timeout());
+            let body = JsFuture::from(response.array_buffer().map_err(|_| ErrorResponse {
                 url: url.clone(),
-                error: Error::FetchError("array_buffer result wasn't an ArrayBuffer".to_string()),
-            })?;
+                error: Error::FetchError("Got JS error".to_string()),
+            })?)
+            .await;
+            if let Some(timeout) = timeout {
+                window.clear_timeout_with_handle(timeout);
+            }
+            let body: ArrayBuffer = body
+                .map_err(|_| ErrorResponse {
+                    url: url.clone(),
+                    error: if timed_out(&abort_controller) {
+                        Error::FetchError("Request timed out".to_string())
+                    } else {
+                        Error::FetchError(
+                            "Could not allocate array buffer for response".to_string(),
+                        )
+                    },
+                })?
+                .dyn_into()
+                .map_err(|_| ErrorResponse {
+                    url: url.clone(),
+                    error: Error::FetchError(
+                        "array_buffer result wasn't an ArrayBuffer".to_string(),
+                    ),
+                })?;
             let body = Uint8Array::new(&body).to_vec();
 
             Ok(SuccessResponse {
@@ -331,11 +415,17 @@ impl NavigatorBackend for WebNavigatorBackend {
                 body,
                 status,
                 redirected,
+                redirect_url: None,
+                headers,
             })
         })
     }
 
     fn resolve_url(&self, url: &str) -> Result<Url, ParseError> {
+        if let Some(url) = self.air_directories().and_then(|d| d.resolve(url)) {
+            return Ok(self.pre_process_url(url));
+        }
+
         if let Some(base_url) = &self.base_url {
             match base_url.join(url) {
                 Ok(full_url) => Ok(self.pre_process_url(full_url)),
@@ -349,6 +439,10 @@ impl NavigatorBackend for WebNavigatorBackend {
         }
     }
 
+    fn air_directories(&self) -> Option<&AirDirectories> {
+        self.air_directories.as_ref()
+    }
+
     fn spawn_future(&mut self, future: OwnedFuture<(), Error>) {
         let subscriber = self.log_subscriber.clone();
         spawn_local(async move {
@@ -370,17 +464,30 @@ impl NavigatorBackend for WebNavigatorBackend {
         &mut self,
         host: String,
         port: u16,
+        // NOTE: Sockets go through WebSocket proxies, which stand in for policy files.
+        _require_policy_file: bool,
         // NOTE: WebSocket does not allow specifying a timeout, so this goes unused.
         _timeout: Duration,
         handle: SocketHandle,
         receiver: Receiver<Vec<u8>>,
         sender: Sender<SocketAction>,
     ) {
-        let Some(proxy) = self
+        let proxy_url = self
             .socket_proxies
             .iter()
             .find(|x| x.host == host && x.port == port)
-        else {
+            .map(|proxy| proxy.proxy_url.clone())
+            .or_else(|| {
+                self.socket_proxy_template.as_ref().map(|template| {
+                    // The host comes from the movie, so it mustn't be able to change the
+                    // structure of the URL, e.g. with a `/` or `?`.
+                    let host = String::from(js_sys::encode_uri_component(&host));
+                    template
+                        .replace("{host}", &host)
+                        .replace("{port}", &port.to_string())
+                })
+            });
+        let Some(proxy_url) = proxy_url else {
             tracing::warn!("Missing WebSocket proxy for host {}, port {}", host, port);
             sender
                 .send(SocketAction::Connect(handle, ConnectionState::Failed))
@@ -388,9 +495,9 @@ impl NavigatorBackend for WebNavigatorBackend {
             return;
         };
 
-        tracing::info!("Connecting to {}", proxy.proxy_url);
+        tracing::info!("Connecting to {}", proxy_url);
 
-        let ws = match WebSocket::open(&proxy.proxy_url) {
+        let ws = match WebSocket::open(&proxy_url) {
             Ok(x) => x,
             Err(e) => {
                 tracing::error!("Failed to create WebSocket, reason {:?}", e);
@@ -414,7 +521,10 @@ impl NavigatorBackend for WebNavigatorBackend {
                     Ok(Message::Bytes(buf)) => stream_sender
                         .send(SocketAction::Data(handle, buf))
                         .expect("working channel send"),
-                    Ok(_) => tracing::warn!("Server sent unexpected text message"),
+                    // Servers that only speak text frames get their text passed on as UTF-8.
+                    Ok(Message::Text(text)) => stream_sender
+                        .send(SocketAction::Data(handle, text.into_bytes()))
+                        .expect("working channel send"),
                     Err(_) => {
                         stream_sender
                             .send(SocketAction::Close(handle))
@@ -441,4 +551,55 @@ impl NavigatorBackend for WebNavigatorBackend {
             Ok(())
         }));
     }
+
+    fn join_net_group(
+        &mut self,
+        // NOTE: Browsers can't send UDP, so there's no way to reach other peers.
+        _address: SocketAddr,
+        handle: NetGroupHandle,
+        _receiver: Receiver<Vec<u8>>,
+        sender: Sender<NetGroupAction>,
+    ) {
+        sender
+            .send(NetGroupAction::Connect(handle, false))
+            .expect("working channel send");
+    }
+}
+
+/// Aborts a fetch once `timeout` elapses, returning the handle of the scheduled abort.
+fn abort_after(
+    window: &web_sys::Window,
+    abort_controller: &Option<AbortController>,
+    timeout: Option<Duration>,
+) -> Option<i32> {
+    let abort_controller = abort_controller.clone()?;
+    let timeout = timeout?;
+    let abort = Closure::once_into_js(move || abort_controller.abort());
+    window
+        .set_timeout_with_callback_and_timeout_and_arguments_0(
+            abort.unchecked_ref(),
+            timeout.as_millis().try_into().unwrap_or(i32::MAX),
+        )
+        .ok()
+}
+
+/// The headers of a response that the page is allowed to see.
+fn response_headers(response: &WebResponse) -> Vec<(String, String)> {
+    let Ok(Some(entries)) = js_sys::try_iter(&response.headers()) else {
+        return vec![];
+    };
+    entries
+        .flatten()
+        .filter_map(|entry| {
+            let entry = entry.dyn_into::<Array>().ok()?;
+            Some((entry.get(0).as_string()?, entry.get(1).as_string()?))
+        })
+        .collect()
+}
+
+/// Whether a fetch failed because one of its timeouts aborted it.
+fn timed_out(abort_controller: &Option<AbortController>) -> bool {
+    abort_controller
+        .as_ref()
+        .is_some_and(|abort_controller| abort_controller.signal().aborted())
 }
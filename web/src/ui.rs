@@ -1,9 +1,11 @@
 use super::JavascriptPlayer;
 use ruffle_core::backend::ui::{
-    FontDefinition, FullscreenError, LanguageIdentifier, MouseCursor, UiBackend, US_ENGLISH,
+    DesktopRect, FontDefinition, FullscreenError, LanguageIdentifier, MouseCursor, NativeMenuItem,
+    ScreenInfo, UiBackend, US_ENGLISH,
 };
 use ruffle_web_common::JsResult;
 use std::borrow::Cow;
+use std::path::PathBuf;
 use url::Url;
 use wasm_bindgen::JsCast;
 use web_sys::{HtmlCanvasElement, HtmlDocument, HtmlTextAreaElement};
@@ -115,6 +117,45 @@ impl UiBackend for WebUiBackend {
         }
     }
 
+    fn screens(&self) -> Vec<ScreenInfo> {
+        let Some(screen) = web_sys::window().and_then(|window| window.screen().ok()) else {
+            return vec![];
+        };
+        let dimension = |value: Result<i32, _>| value.unwrap_or(0).max(0) as u32;
+
+        vec![ScreenInfo {
+            bounds: DesktopRect {
+                x: 0,
+                y: 0,
+                width: dimension(screen.width()),
+                height: dimension(screen.height()),
+            },
+            visible_bounds: DesktopRect {
+                x: screen.avail_left().unwrap_or(0),
+                y: screen.avail_top().unwrap_or(0),
+                width: dimension(screen.avail_width()),
+                height: dimension(screen.avail_height()),
+            },
+            color_depth: dimension(screen.color_depth()),
+        }]
+    }
+
+    fn window_bounds(&self) -> Option<DesktopRect> {
+        // The player lives in a page, not in a window of its own.
+        None
+    }
+
+    fn set_window_bounds(&mut self, _bounds: DesktopRect) {}
+
+    fn start_file_drag(&mut self, _files: &[PathBuf]) -> bool {
+        // Pages can't drag local files out of the browser.
+        false
+    }
+
+    fn set_native_menu(&mut self, _menu: Option<Vec<NativeMenuItem>>) {
+        // AIR window menus have nowhere to go in a page.
+    }
+
     fn set_fullscreen(&mut self, is_full: bool) -> Result<(), FullscreenError> {
         match self.js_player.set_fullscreen(is_full) {
             Ok(_) => Ok(()),